@@ -0,0 +1,23 @@
+#!/bin/bash
+
+#
+# Copyright 2025 OPPO.
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+#     http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+#
+
+# Offline viewer of the master metadata, e.g.
+# curvine-image.sh --checkpoint <dir> dump --format csv
+# curvine-image.sh --checkpoint <dir> report --depth 2
+source "$(cd "`dirname "$0"`"; pwd)"/../conf/curvine-env.sh
+${CURVINE_HOME}/lib/curvine-image "$@"
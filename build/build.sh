@@ -271,6 +271,7 @@ declare -a COPY_TARGETS=()
 if should_build_package "server"; then
   RUST_BUILD_ARGS+=("-p" "curvine-server")
   COPY_TARGETS+=("curvine-server")
+  COPY_TARGETS+=("curvine-image")
 fi
 
 if should_build_package "client"; then
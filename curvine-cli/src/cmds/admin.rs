@@ -0,0 +1,225 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::util::*;
+use clap::{Parser, Subcommand};
+use curvine_client::file::FsClient;
+use curvine_common::conf::FederationConf;
+use curvine_common::state::{MetaBackupInfo, MetaBackupStatus, WorkerAddress};
+use orpc::common::ByteUnit;
+use orpc::{err_box, CommonResult};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Parser, Debug)]
+pub struct AdminCommand {
+    #[clap(subcommand)]
+    action: AdminSubCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AdminSubCommand {
+    /// Metadata backup command
+    Backup {
+        /// The namespace whose metadata is backed up, each namespace has its own masters
+        #[arg(long, default_value = FederationConf::DEFAULT_NAMESPACE)]
+        namespace: String,
+
+        #[clap(subcommand)]
+        action: BackupSubCommand,
+    },
+
+    /// Show or change the io bandwidth limits of workers
+    Throttle {
+        /// Only the worker with this hostname, ip or ip:port, default all live workers
+        #[arg(long)]
+        worker: Option<String>,
+
+        /// Limits to set, e.g. "load=100MB,replication=50MB,total=1GB", 0 means unlimited.
+        /// Classes: total, client_read, client_write, load, replication, eviction
+        #[arg(long)]
+        set: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BackupSubCommand {
+    /// Take a metadata backup now and upload it to master.backup_dir
+    Now {
+        /// Return after the backup is started instead of waiting for it
+        #[arg(long)]
+        no_wait: bool,
+    },
+
+    /// Show whether a metadata backup is running and the result of the last one
+    Status,
+
+    /// List the metadata backups in master.backup_dir
+    List,
+}
+
+impl AdminCommand {
+    fn print_backups(backups: &[MetaBackupInfo]) {
+        println!(
+            "{:<50} {:<15} {:<10} {:<10} {:<12}",
+            "Name", "Journal Index", "Term", "Files", "Size"
+        );
+        println!("{}", "-".repeat(100));
+        for info in backups {
+            println!(
+                "{:<50} {:<15} {:<10} {:<10} {:<12}",
+                info.name,
+                info.journal_index,
+                info.journal_term,
+                info.files,
+                ByteUnit::byte_to_string(info.len)
+            );
+        }
+    }
+
+    fn print_backup_status(status: &MetaBackupStatus) {
+        let state = if status.running { "running" } else { "idle" };
+        println!("State: {}", state);
+        if let Some(info) = &status.last_backup {
+            println!("Last backup:");
+            Self::print_backups(&[info.clone()]);
+        }
+        if let Some(e) = &status.last_error {
+            println!("Last error: {}", e);
+        }
+    }
+
+    // Start a backup and wait until it is uploaded.
+    async fn backup_now(client: Arc<FsClient>, no_wait: bool) -> CommonResult<()> {
+        let mut status = handle_rpc_result(client.backup_meta()).await;
+        if no_wait {
+            Self::print_backup_status(&status);
+            return Ok(());
+        }
+
+        while status.running {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            status = handle_rpc_result(client.meta_backup_status()).await;
+        }
+
+        let info = match (&status.last_error, &status.last_backup) {
+            (None, Some(info)) => info.clone(),
+            (Some(e), _) => return err_box!("Backup failed: {}", e),
+            (None, None) => return err_box!("Backup finished without a result"),
+        };
+        Self::print_backups(&[info.clone()]);
+        println!();
+        println!("Backup {} created", info.name);
+        println!(
+            "Restore it with: curvine-server --service master --restore-from {}",
+            info.name
+        );
+        Ok(())
+    }
+
+    // Parse "load=100MB,total=1GB" into (class, bytes per second).
+    fn parse_limits(str: &str) -> CommonResult<Vec<(String, u64)>> {
+        let mut limits = vec![];
+        for item in str.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+            let (class, rate) = match item.split_once('=') {
+                Some(v) => v,
+                None => return err_box!("Invalid throttle limit {}, expected class=rate", item),
+            };
+            let rate = ByteUnit::from_str(rate.trim())?.as_byte();
+            limits.push((class.trim().to_string(), rate));
+        }
+        Ok(limits)
+    }
+
+    fn worker_matches(addr: &WorkerAddress, worker: &str) -> bool {
+        addr.hostname == worker || addr.ip_addr == worker || addr.connect_addr() == worker
+    }
+
+    async fn throttle(
+        client: Arc<FsClient>,
+        worker: Option<&str>,
+        set: Option<&str>,
+    ) -> CommonResult<()> {
+        let limits = match set {
+            Some(v) => Self::parse_limits(v)?,
+            None => vec![],
+        };
+
+        let info = handle_rpc_result(client.get_master_info()).await;
+        let workers: Vec<WorkerAddress> = info
+            .live_workers
+            .into_iter()
+            .map(|x| x.address)
+            .filter(|x| worker.map(|w| Self::worker_matches(x, w)).unwrap_or(true))
+            .collect();
+        if workers.is_empty() {
+            return err_box!("No live worker matches {}", worker.unwrap_or("*"));
+        }
+
+        println!("{:<30} {:<15} {:<12}", "Worker", "Class", "Limit");
+        println!("{}", "-".repeat(60));
+        for addr in workers {
+            let block_client = client.context().block_client(&addr).await?;
+            let res = block_client.worker_throttle(limits.clone()).await;
+            let rates = match res {
+                Ok(v) => v,
+                Err(e) => {
+                    eprintln!("❌ Worker {}: {}", addr.connect_addr(), e);
+                    continue;
+                }
+            };
+
+            for (class, rate) in rates {
+                let limit = if rate == 0 {
+                    "unlimited".to_string()
+                } else {
+                    format!("{}/s", ByteUnit::byte_to_string(rate))
+                };
+                println!("{:<30} {:<15} {:<12}", addr.connect_addr(), class, limit);
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn execute(&self, client: Arc<FsClient>) -> CommonResult<()> {
+        match &self.action {
+            AdminSubCommand::Backup { namespace, action } => {
+                let client = Arc::new(client.namespace(namespace)?);
+                Self::backup(client, action).await
+            }
+
+            AdminSubCommand::Throttle { worker, set } => {
+                Self::throttle(client, worker.as_deref(), set.as_deref()).await
+            }
+        }
+    }
+
+    async fn backup(client: Arc<FsClient>, action: &BackupSubCommand) -> CommonResult<()> {
+        match action {
+            BackupSubCommand::Now { no_wait } => Self::backup_now(client, *no_wait).await,
+
+            BackupSubCommand::Status => {
+                let status = handle_rpc_result(client.meta_backup_status()).await;
+                Self::print_backup_status(&status);
+                Ok(())
+            }
+
+            BackupSubCommand::List => {
+                let backups = handle_rpc_result(client.list_meta_backups()).await;
+                Self::print_backups(&backups);
+                Ok(())
+            }
+        }
+    }
+}
@@ -5,9 +5,9 @@ use std::path::PathBuf;
 
 use crate::cmds::fs::{
     blocks::BlocksCommand, cat::CatCommand, chmod::ChmodCommand, chown::ChownCommand,
-    count::CountCommand, df::DfCommand, du::DuCommand, get::GetCommand, ls::LsCommand,
-    mkdir::MkdirCommand, mv::MvCommand, put::PutCommand, rm::RmCommand, stat::StatCommand,
-    touch::TouchCommand,
+    count::CountCommand, df::DfCommand, du::DuCommand, find::FindCommand, get::GetCommand,
+    ls::LsCommand, mkdir::MkdirCommand, mv::MvCommand, policy::PolicyCommand, put::PutCommand,
+    rm::RmCommand, stat::StatCommand, touch::TouchCommand,
 };
 
 #[derive(Parser, Debug)]
@@ -166,6 +166,39 @@ pub enum FsSubCommand {
     Count {
         #[clap(help = "Path of the directory to count")]
         path: String,
+
+        #[clap(short = 't', long, help = "Show the bytes by storage type")]
+        storage_types: bool,
+    },
+
+    /// Find files and directories on the master
+    Find {
+        #[clap(help = "Path to search")]
+        path: String,
+
+        #[clap(long, help = "Name or glob of the name, e.g. '*.log'")]
+        name: Option<String>,
+
+        #[clap(
+            long = "type",
+            help = "Entry type: f (file), d (directory), l (symlink)"
+        )]
+        file_type: Option<String>,
+
+        #[clap(long, help = "Minimum file size, e.g. 1MB")]
+        min_size: Option<String>,
+
+        #[clap(long, help = "Maximum file size, e.g. 1GB")]
+        max_size: Option<String>,
+
+        #[clap(long, help = "Modified within the duration, e.g. 7d")]
+        newer: Option<String>,
+
+        #[clap(long, help = "Modified before the duration ago, e.g. 30d")]
+        older: Option<String>,
+
+        #[clap(long, help = "Depth below the path to search, 0 means the path only")]
+        max_depth: Option<i32>,
     },
 
     /// Move file or directory
@@ -205,6 +238,60 @@ pub enum FsSubCommand {
         #[clap(long, default_value = "table")]
         format: String,
     },
+
+    /// Set the default storage policy of a directory
+    Setpolicy {
+        #[clap(help = "Path of the directory")]
+        path: String,
+
+        #[clap(long, help = "Storage type of new files: mem, ssd, hdd, disk")]
+        storage_type: Option<String>,
+
+        #[clap(long, help = "Replica number of new files")]
+        replicas: Option<u16>,
+
+        #[clap(long, help = "Block size of new files, e.g. 128MB")]
+        block_size: Option<String>,
+
+        #[clap(long, help = "TTL of new files and directories, e.g. 7d")]
+        ttl: Option<String>,
+
+        #[clap(long, help = "TTL action: none, delete, persist, evict, flush")]
+        ttl_action: Option<String>,
+
+        #[clap(long, help = "Erasure coding policy of new files, e.g. RS-6-3")]
+        ec_policy: Option<String>,
+
+        #[clap(
+            long,
+            help = "Remove an attribute from the policy: storage-type, replicas, block-size, ttl, ttl-action, ec-policy"
+        )]
+        unset: Vec<String>,
+
+        #[clap(
+            long,
+            help = "Remove the whole policy before applying the other options"
+        )]
+        clear: bool,
+    },
+
+    /// Show the storage policy of a directory and the effective policy of its new children
+    Getpolicy {
+        #[clap(help = "Path of the directory")]
+        path: String,
+    },
+
+    /// Change the storage type of existing files and move their blocks in the background
+    Setstorage {
+        #[clap(help = "Path of the file or directory")]
+        path: String,
+
+        #[clap(help = "Storage type: mem, ssd, hdd, disk")]
+        storage_type: String,
+
+        #[clap(short, long, help = "Apply to all files under the directory")]
+        recursive: bool,
+    },
 }
 
 impl FsCommand {
@@ -315,11 +402,40 @@ impl FsCommand {
                 stat_cmd.execute(client).await
             }
 
-            FsSubCommand::Count { path } => {
-                let count_cmd = CountCommand::Count { path: path.clone() };
+            FsSubCommand::Count {
+                path,
+                storage_types,
+            } => {
+                let count_cmd = CountCommand::Count {
+                    path: path.clone(),
+                    storage_types: *storage_types,
+                };
                 count_cmd.execute(client).await
             }
 
+            FsSubCommand::Find {
+                path,
+                name,
+                file_type,
+                min_size,
+                max_size,
+                newer,
+                older,
+                max_depth,
+            } => {
+                let find_cmd = FindCommand::Find {
+                    path: path.clone(),
+                    name: name.clone(),
+                    file_type: file_type.clone(),
+                    min_size: min_size.clone(),
+                    max_size: max_size.clone(),
+                    newer: newer.clone(),
+                    older: older.clone(),
+                    max_depth: *max_depth,
+                };
+                find_cmd.execute(client).await
+            }
+
             FsSubCommand::Mv { src_path, dst_path } => {
                 let mv_cmd = MvCommand::Mv {
                     source: src_path.clone(),
@@ -361,6 +477,49 @@ impl FsCommand {
                 };
                 blocks_cmd.execute(client).await
             }
+
+            FsSubCommand::Setpolicy {
+                path,
+                storage_type,
+                replicas,
+                block_size,
+                ttl,
+                ttl_action,
+                ec_policy,
+                unset,
+                clear,
+            } => {
+                let policy_cmd = PolicyCommand::SetPolicy {
+                    path: path.clone(),
+                    storage_type: storage_type.clone(),
+                    replicas: *replicas,
+                    block_size: block_size.clone(),
+                    ttl: ttl.clone(),
+                    ttl_action: ttl_action.clone(),
+                    ec_policy: ec_policy.clone(),
+                    unset: unset.clone(),
+                    clear: *clear,
+                };
+                policy_cmd.execute(client).await
+            }
+
+            FsSubCommand::Getpolicy { path } => {
+                let policy_cmd = PolicyCommand::GetPolicy { path: path.clone() };
+                policy_cmd.execute(client).await
+            }
+
+            FsSubCommand::Setstorage {
+                path,
+                storage_type,
+                recursive,
+            } => {
+                let policy_cmd = PolicyCommand::SetStorage {
+                    path: path.clone(),
+                    storage_type: storage_type.clone(),
+                    recursive: *recursive,
+                };
+                policy_cmd.execute(client).await
+            }
         }
     }
 }
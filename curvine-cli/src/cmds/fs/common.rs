@@ -1,51 +1,3 @@
-use super::content_summary::ContentSummary;
-use curvine_client::unified::UnifiedFileSystem;
-use curvine_common::fs::{CurvineURI, FileSystem};
-use orpc::CommonResult;
-
-/// Calculates content summary (directory size, file count, directory count) on the client side
-pub async fn calculate_content_summary(
-    client: &UnifiedFileSystem,
-    path: &CurvineURI,
-) -> CommonResult<ContentSummary> {
-    calculate_content_summary_impl(client, path).await
-}
-
-/// Implementation of calculate_content_summary that handles recursion properly
-async fn calculate_content_summary_impl(
-    client: &UnifiedFileSystem,
-    path: &CurvineURI,
-) -> CommonResult<ContentSummary> {
-    // First check if the path exists and get its status
-    let status = client.get_status(path).await?;
-
-    if !status.is_dir {
-        // For a file, return a simple summary with just the file's length
-        return Ok(ContentSummary::for_file(status.len));
-    }
-
-    // For a directory, we need to recursively calculate the summary
-    let mut summary = ContentSummary::for_empty_dir();
-    let children = client.list_status(path).await?;
-
-    for child in children {
-        if child.is_dir {
-            // Recursively get summary for subdirectories
-            let child_path = CurvineURI::new(&child.path)?;
-            // Use Box::pin to handle recursive async call
-            let child_summary =
-                Box::pin(calculate_content_summary_impl(client, &child_path)).await?;
-            summary.merge(&child_summary);
-        } else {
-            // For files, just add their length and count
-            summary.length += child.len;
-            summary.file_count += 1;
-        }
-    }
-
-    Ok(summary)
-}
-
 /// Formats a size in bytes to a human-readable string (KB, MB, GB, etc.)
 pub fn format_size(size: u64) -> String {
     const KB: u64 = 1024;
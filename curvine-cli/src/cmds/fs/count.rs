@@ -9,32 +9,74 @@ pub enum CountCommand {
     Count {
         #[clap(help = "Path of the directory to count")]
         path: String,
+
+        #[clap(short = 't', long, help = "Show the bytes by storage type")]
+        storage_types: bool,
+
+        #[clap(short = 'q', long, help = "Show the quota and the remaining quota")]
+        quota: bool,
     },
 }
 
 impl CountCommand {
     pub async fn execute(&self, mut client: UnifiedFileSystem) -> CommonResult<()> {
         match self {
-            CountCommand::Count { path } => {
+            CountCommand::Count {
+                path,
+                storage_types,
+                quota,
+            } => {
                 client.disable_unified();
                 let path = CurvineURI::new(path)?;
 
-                match crate::cmds::fs::common::calculate_content_summary(&client, &path).await {
+                match client.content_summary(&path).await {
                     Ok(summary) => {
-                        // Format similar to HDFS count output
-                        println!("   DIR_COUNT    FILE_COUNT       CONTENT_SIZE PATHNAME");
-                        println!(
+                        // Format similar to HDFS count output, there is no namespace quota.
+                        let counts = format!(
                             "{:>12} {:>12} {:>18} {}",
                             summary.directory_count,
                             summary.file_count,
                             summary.length,
                             path.full_path()
                         );
+                        if *quota {
+                            // -1 if the quota is not known, e.g. of a ufs path.
+                            let space = |v: i64, unset: &str| {
+                                if v < 0 {
+                                    unset.to_string()
+                                } else {
+                                    v.to_string()
+                                }
+                            };
+                            println!(
+                                "       QUOTA       REM_QUOTA     SPACE_QUOTA REM_SPACE_QUOTA    DIR_COUNT   FILE_COUNT       CONTENT_SIZE PATHNAME"
+                            );
+                            println!(
+                                "{:>12} {:>15} {:>15} {:>15} {}",
+                                "none",
+                                "inf",
+                                space(summary.space_quota, "none"),
+                                space(summary.space_quota_remaining, "inf"),
+                                counts
+                            );
+                        } else {
+                            println!("   DIR_COUNT    FILE_COUNT       CONTENT_SIZE PATHNAME");
+                            println!("{}", counts);
+                        }
+
+                        if *storage_types {
+                            let mut type_lengths: Vec<_> = summary.type_lengths.iter().collect();
+                            type_lengths
+                                .sort_by_key(|(storage_type, _)| storage_type.as_str_name());
+                            for (storage_type, length) in type_lengths {
+                                println!("{:>12} {:>31}", storage_type.as_str_name(), length);
+                            }
+                        }
                         Ok(())
                     }
                     Err(e) => {
                         eprintln!("count: Cannot count '{}': {}", path.full_path(), e);
-                        Err(e)
+                        Err(e.into())
                     }
                 }
             }
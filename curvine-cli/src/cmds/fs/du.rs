@@ -1,6 +1,6 @@
 use clap::Subcommand;
 use curvine_client::unified::UnifiedFileSystem;
-use curvine_common::fs::{CurvineURI, FileSystem};
+use curvine_common::fs::CurvineURI;
 use orpc::CommonResult;
 
 #[derive(Subcommand, Debug)]
@@ -40,14 +40,10 @@ impl DuCommand {
                     );
                 }
 
-                match crate::cmds::fs::common::calculate_content_summary(&client, &path).await {
+                match client.content_summary(&path).await {
                     Ok(summary) => {
-                        // Get file status to determine replication factor
-                        let status = client.get_status(&path).await;
-                        // Default replication factor is 3 if we can't get the status
-                        let replicas = status.map(|s| s.replicas).unwrap_or(1) as i64;
-                        // Calculate disk space consumed with all replicas
-                        let disk_space = summary.length * replicas;
+                        // The bytes of all the replicas, computed from the replication of each file.
+                        let disk_space = summary.space_consumed;
 
                         // Format similar to HDFS du output with single replica data added
                         if *human_readable {
@@ -71,7 +67,7 @@ impl DuCommand {
                     }
                     Err(e) => {
                         eprintln!("du: Cannot access '{}': {}", path.full_path(), e);
-                        Err(e)
+                        Err(e.into())
                     }
                 }
             }
@@ -0,0 +1,104 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_client::unified::UnifiedFileSystem;
+use curvine_common::fs::Path;
+use curvine_common::state::{FileType, FindOpts};
+use orpc::common::{ByteUnit, DurationUnit, LocalTime};
+use orpc::{err_box, CommonResult};
+
+// The number of entries fetched from the master in one call.
+const FIND_PAGE_SIZE: i32 = 1000;
+
+#[derive(Debug)]
+pub enum FindCommand {
+    Find {
+        path: String,
+        name: Option<String>,
+        file_type: Option<String>,
+        min_size: Option<String>,
+        max_size: Option<String>,
+        newer: Option<String>,
+        older: Option<String>,
+        max_depth: Option<i32>,
+    },
+}
+
+impl FindCommand {
+    pub async fn execute(&self, client: UnifiedFileSystem) -> CommonResult<()> {
+        match self {
+            FindCommand::Find {
+                path,
+                name,
+                file_type,
+                min_size,
+                max_size,
+                newer,
+                older,
+                max_depth,
+            } => {
+                let path = Path::from_str(path)?;
+                let now = LocalTime::mills() as i64;
+
+                let mut opts = FindOpts {
+                    name: name.clone(),
+                    max_depth: *max_depth,
+                    ..Default::default()
+                };
+                if let Some(v) = file_type {
+                    opts.file_type = Some(Self::parse_type(v)?);
+                }
+                if let Some(v) = min_size {
+                    opts.min_size = Some(ByteUnit::from_str(v)?.as_byte() as i64);
+                }
+                if let Some(v) = max_size {
+                    opts.max_size = Some(ByteUnit::from_str(v)?.as_byte() as i64);
+                }
+                if let Some(v) = newer {
+                    opts.mtime_after = Some(now - DurationUnit::from_str(v)?.as_millis() as i64);
+                }
+                if let Some(v) = older {
+                    opts.mtime_before = Some(now - DurationUnit::from_str(v)?.as_millis() as i64);
+                }
+
+                // Print each page as it arrives, a large tree is never held in memory.
+                let mut start_after: Option<String> = None;
+                loop {
+                    let (list, has_more) = client
+                        .find_page(&path, &opts, start_after.as_deref(), FIND_PAGE_SIZE)
+                        .await?;
+                    for status in &list {
+                        println!("{}", status.path);
+                    }
+
+                    match list.last() {
+                        Some(last) if has_more => start_after = Some(last.path.clone()),
+                        _ => break,
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    fn parse_type(s: &str) -> CommonResult<FileType> {
+        match s {
+            "f" | "file" => Ok(FileType::File),
+            "d" | "dir" => Ok(FileType::Dir),
+            "l" | "link" => Ok(FileType::Link),
+            _ => err_box!("Invalid type {}, expected f, d or l", s),
+        }
+    }
+}
@@ -4,14 +4,15 @@ mod chmod;
 mod chown;
 mod commands;
 mod common;
-mod content_summary;
 mod count;
 mod df;
 mod du;
+mod find;
 mod get;
 mod ls;
 mod mkdir;
 mod mv;
+mod policy;
 mod put;
 mod rm;
 mod stat;
@@ -0,0 +1,178 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::util::*;
+use curvine_client::unified::UnifiedFileSystem;
+use curvine_common::fs::Path;
+use curvine_common::state::{DirPolicy, EcPolicy, StorageType, TtlAction};
+use orpc::common::{ByteUnit, DurationUnit};
+use orpc::CommonResult;
+
+#[derive(Debug)]
+pub enum PolicyCommand {
+    SetPolicy {
+        path: String,
+        storage_type: Option<String>,
+        replicas: Option<u16>,
+        block_size: Option<String>,
+        ttl: Option<String>,
+        ttl_action: Option<String>,
+        ec_policy: Option<String>,
+        unset: Vec<String>,
+        clear: bool,
+    },
+
+    GetPolicy {
+        path: String,
+    },
+
+    SetStorage {
+        path: String,
+        storage_type: String,
+        recursive: bool,
+    },
+}
+
+impl PolicyCommand {
+    pub async fn execute(&self, client: UnifiedFileSystem) -> CommonResult<()> {
+        match self {
+            PolicyCommand::SetPolicy {
+                path,
+                storage_type,
+                replicas,
+                block_size,
+                ttl,
+                ttl_action,
+                ec_policy,
+                unset,
+                clear,
+            } => {
+                let path = Path::from_str(path)?;
+                let fs = client.fs_client();
+
+                let mut policy = if *clear {
+                    DirPolicy::default()
+                } else {
+                    handle_rpc_result(fs.get_dir_policy(&path)).await.0
+                };
+
+                for name in unset {
+                    match name.as_str() {
+                        "storage-type" => policy.storage_type = None,
+                        "replicas" => policy.replicas = None,
+                        "block-size" => policy.block_size = None,
+                        "ttl" => policy.ttl_ms = None,
+                        "ttl-action" => policy.ttl_action = None,
+                        "ec-policy" => policy.ec_policy = None,
+                        _ => return Err(format!("Unknown policy attribute: {}", name).into()),
+                    }
+                }
+
+                if let Some(v) = storage_type {
+                    policy.storage_type = Some(StorageType::try_from(v.as_str())?);
+                }
+                if let Some(v) = replicas {
+                    policy.replicas = Some(*v);
+                }
+                if let Some(v) = block_size {
+                    policy.block_size = Some(ByteUnit::from_str(v)?.as_byte() as i64);
+                }
+                if let Some(v) = ttl {
+                    policy.ttl_ms = Some(DurationUnit::from_str(v)?.as_millis() as i64);
+                }
+                if let Some(v) = ttl_action {
+                    policy.ttl_action = Some(TtlAction::try_from(v.as_str())?);
+                }
+                if let Some(v) = ec_policy {
+                    policy.ec_policy = Some(EcPolicy::try_from(v.as_str())?);
+                }
+
+                handle_rpc_result(fs.set_dir_policy(&path, policy.clone())).await;
+                println!("Set storage policy of '{}'", path);
+                Self::print_policy("Policy", &policy);
+
+                Ok(())
+            }
+
+            PolicyCommand::GetPolicy { path } => {
+                let path = Path::from_str(path)?;
+                let (policy, effective) =
+                    handle_rpc_result(client.fs_client().get_dir_policy(&path)).await;
+
+                println!("Path: {}", path);
+                Self::print_policy("Policy", &policy);
+                Self::print_policy("Effective", &effective);
+
+                Ok(())
+            }
+
+            PolicyCommand::SetStorage {
+                path,
+                storage_type,
+                recursive,
+            } => {
+                let path = Path::from_str(path)?;
+                let storage_type = StorageType::try_from(storage_type.as_str())?;
+                let (job_id, blocks, size) = handle_rpc_result(
+                    client
+                        .fs_client()
+                        .set_storage_policy(&path, storage_type, *recursive),
+                )
+                .await;
+
+                println!(
+                    "Set storage type of '{}' to {}, moving {} blocks ({})",
+                    path,
+                    storage_type.as_str_name(),
+                    blocks,
+                    ByteUnit::byte_to_string(size as u64)
+                );
+                println!("Job id: {}", job_id);
+                println!("Use 'cv load-status {}' to check the progress", job_id);
+
+                Ok(())
+            }
+        }
+    }
+
+    fn print_policy(title: &str, policy: &DirPolicy) {
+        fn show<T: ToString>(v: Option<T>) -> String {
+            v.map(|x| x.to_string()).unwrap_or_else(|| "-".to_string())
+        }
+
+        println!("{}:", title);
+        println!(
+            "  storage-type: {}",
+            show(policy.storage_type.map(|x| x.as_str_name()))
+        );
+        println!("  replicas:     {}", show(policy.replicas));
+        println!(
+            "  block-size:   {}",
+            show(
+                policy
+                    .block_size
+                    .map(|x| ByteUnit::byte_to_string(x as u64))
+            )
+        );
+        println!(
+            "  ttl:          {}",
+            show(policy.ttl_ms.map(|x| format!("{}ms", x)))
+        );
+        println!(
+            "  ttl-action:   {}",
+            show(policy.ttl_action.map(|x| format!("{:?}", x).to_lowercase()))
+        );
+        println!("  ec-policy:    {}", show(policy.ec_policy));
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::util::*;
+use clap::Parser;
+use curvine_client::file::FsClient;
+use curvine_common::fs::Path;
+use curvine_common::state::{FsckAction, FsckBlockState, FsckOpts, FsckReport};
+use orpc::common::ByteUnit;
+use orpc::CommonResult;
+use std::sync::Arc;
+
+const FSCK_PAGE_SIZE: usize = 1000;
+
+#[derive(Parser, Debug)]
+pub struct FsckCommand {
+    #[clap(help = "Path to check", default_value = "/")]
+    path: String,
+
+    #[clap(
+        long,
+        conflicts_with = "move_corrupted",
+        help = "Delete the corrupted files"
+    )]
+    delete: bool,
+
+    #[clap(long = "move", help = "Move the corrupted files to /lost+found")]
+    move_corrupted: bool,
+
+    #[clap(long, help = "Report the blocks on workers that no file refers to")]
+    orphans: bool,
+}
+
+impl FsckCommand {
+    pub async fn execute(&self, client: Arc<FsClient>) -> CommonResult<()> {
+        let path = Path::from_str(&self.path)?;
+        let action = if self.delete {
+            FsckAction::Delete
+        } else if self.move_corrupted {
+            FsckAction::Move
+        } else {
+            FsckAction::None
+        };
+        let mut opts = FsckOpts {
+            action,
+            check_orphans: self.orphans,
+            start_after: None,
+            limit: FSCK_PAGE_SIZE,
+        };
+
+        // Print the problems of each page as it arrives, the totals are added up.
+        let mut report = FsckReport::default();
+        loop {
+            let page = handle_rpc_result(client.fsck(&path, opts.clone())).await;
+            Self::print_problems(&page);
+            report.merge(&page);
+
+            match page.next_start_after {
+                Some(v) => opts.start_after = Some(v),
+                None => break,
+            }
+        }
+        Self::print_totals(&report);
+
+        match action {
+            FsckAction::Delete => println!("Deleted {} corrupted files", report.handled_files),
+            FsckAction::Move => println!(
+                "Moved {} corrupted files to /lost+found",
+                report.handled_files
+            ),
+            FsckAction::None => (),
+        }
+
+        if report.is_healthy() {
+            println!("The filesystem under path '{}' is HEALTHY", self.path);
+            Ok(())
+        } else {
+            println!("The filesystem under path '{}' is CORRUPT", self.path);
+            std::process::exit(1);
+        }
+    }
+
+    fn print_problems(report: &FsckReport) {
+        for file in &report.files {
+            if file.open_for_write {
+                println!("{}: open for write, {} bytes", file.path, file.len);
+                continue;
+            }
+            for block in &file.blocks {
+                let state = match block.state {
+                    FsckBlockState::Missing => "MISSING",
+                    FsckBlockState::Corrupt => "CORRUPT",
+                    FsckBlockState::UnderReplicated => "UNDER REPLICATED",
+                    FsckBlockState::OverReplicated => "OVER REPLICATED",
+                };
+                println!(
+                    "{}: block {} len {} {}, live {}/{}",
+                    file.path, block.block_id, block.len, state, block.live, block.expected
+                );
+            }
+        }
+        for orphan in &report.orphan_blocks {
+            println!(
+                "Orphan block {} on worker {}",
+                orphan.block_id, orphan.worker_id
+            );
+        }
+        if report.orphan_count > report.orphan_blocks.len() as i64 {
+            println!(
+                "... {} more orphan blocks",
+                report.orphan_count - report.orphan_blocks.len() as i64
+            );
+        }
+    }
+
+    fn print_totals(report: &FsckReport) {
+        println!();
+        println!("Total dirs:               {}", report.total_dirs);
+        println!("Total files:              {}", report.total_files);
+        println!(
+            "Total size:               {}",
+            ByteUnit::byte_to_string(report.total_size as u64)
+        );
+        println!("Total blocks:             {}", report.total_blocks);
+        println!("Missing blocks:           {}", report.missing_blocks);
+        println!("Corrupt blocks:           {}", report.corrupt_blocks);
+        println!(
+            "Under-replicated blocks:  {}",
+            report.under_replicated_blocks
+        );
+        println!(
+            "Over-replicated blocks:   {}",
+            report.over_replicated_blocks
+        );
+        println!("Open files:               {}", report.open_files);
+        println!("Corrupt files:            {}", report.corrupt_files);
+        if report.orphan_count > 0 {
+            println!("Orphan blocks:            {}", report.orphan_count);
+        }
+        println!();
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::util::*;
+use clap::{Parser, Subcommand};
+use curvine_client::file::FsClient;
+use orpc::io::net::InetAddr;
+use orpc::{err_box, CommonResult};
+use std::sync::Arc;
+
+#[derive(Parser, Debug)]
+pub struct MasterCommand {
+    #[clap(subcommand)]
+    action: MasterSubCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MasterSubCommand {
+    /// List the masters of the raft group
+    List,
+
+    /// Add a master, it joins as a learner and becomes a voter once it catches up
+    Add {
+        #[clap(long, help = "Raft node id of the new master")]
+        id: u64,
+
+        #[clap(help = "Journal address of the new master (format: hostname:port)")]
+        addr: String,
+
+        #[clap(
+            long,
+            help = "Rpc port of the new master, the port of the current masters by default"
+        )]
+        rpc_port: Option<u16>,
+
+        #[clap(long, help = "Add the master as an observer, it never votes")]
+        observer: bool,
+    },
+
+    /// Remove a master, the leader must transfer its leadership first
+    Remove {
+        #[clap(long, help = "Raft node id of the master")]
+        id: u64,
+    },
+
+    /// Transfer the leadership to another master
+    TransferLeader {
+        #[clap(long, help = "Raft node id of the new leader")]
+        id: u64,
+    },
+}
+
+impl MasterCommand {
+    fn parse_addr(addr: &str) -> CommonResult<InetAddr> {
+        match addr.split_once(':') {
+            Some((hostname, port)) => match port.parse::<u16>() {
+                Ok(port) => Ok(InetAddr::new(hostname, port)),
+                Err(_) => err_box!("Invalid port number in address: '{}'", addr),
+            },
+            None => err_box!("Invalid address '{}', expected format: hostname:port", addr),
+        }
+    }
+
+    async fn handle_list(&self, client: Arc<FsClient>) -> CommonResult<()> {
+        let group = handle_rpc_result(client.list_masters()).await;
+
+        println!("Master Nodes:");
+        println!(
+            "{:<10} {:<30} {:<30} {:<10}",
+            "Id", "Journal Address", "Rpc Address", "Role"
+        );
+        println!("{}", "-".repeat(80));
+        for master in &group.masters {
+            let role = if master.id == group.leader_id {
+                "leader"
+            } else if master.observer {
+                "observer"
+            } else if master.learner {
+                "learner"
+            } else {
+                "follower"
+            };
+            let addr = format!("{}:{}", master.hostname, master.port);
+            let rpc_addr = format!("{}:{}", master.hostname, master.rpc_port);
+            println!(
+                "{:<10} {:<30} {:<30} {:<10}",
+                master.id, addr, rpc_addr, role
+            );
+        }
+
+        Ok(())
+    }
+
+    pub async fn execute(&self, client: Arc<FsClient>) -> CommonResult<()> {
+        match &self.action {
+            MasterSubCommand::List => self.handle_list(client).await,
+
+            MasterSubCommand::Add {
+                id,
+                addr,
+                rpc_port,
+                observer,
+            } => {
+                let addr = Self::parse_addr(addr)?;
+                handle_rpc_result(client.add_master(*id, &addr, *rpc_port, *observer)).await;
+                if *observer {
+                    println!("Master {}({}) added as an observer", id, addr);
+                    println!(
+                        "Start it with journal.join_cluster = true and observer = true \
+                        in its journal_addrs entry"
+                    );
+                } else {
+                    println!("Master {}({}) added as a learner", id, addr);
+                    println!(
+                        "Start it with journal.join_cluster = true, \
+                        the other masters and the clients discover it from the raft group"
+                    );
+                }
+                Ok(())
+            }
+
+            MasterSubCommand::Remove { id } => {
+                handle_rpc_result(client.remove_master(*id)).await;
+                println!("Master {} removed", id);
+                Ok(())
+            }
+
+            MasterSubCommand::TransferLeader { id } => {
+                handle_rpc_result(client.transfer_master_leader(*id)).await;
+                println!("Leadership transfer to master {} started", id);
+                Ok(())
+            }
+        }
+    }
+}
@@ -12,19 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod admin;
 mod fs;
+mod fsck;
 mod load;
 mod load_cancel;
 mod load_status;
+mod master;
 mod mount;
 mod node;
 mod report;
 mod umount;
 
+pub use admin::AdminCommand;
 pub use fs::FsCommand;
+pub use fsck::FsckCommand;
 pub use load::LoadCommand;
 pub use load_cancel::CancelLoadCommand;
 pub use load_status::LoadStatusCommand;
+pub use master::MasterCommand;
 pub use mount::MountCommand;
 pub use node::NodeCommand;
 pub use report::ReportCommand;
@@ -42,8 +42,16 @@ pub enum ReportSubCommand {
 
 impl ReportCommand {
     pub async fn execute(&self, fs: UnifiedFileSystem) -> CommonResult<()> {
-        let rep = handle_rpc_result(fs.get_master_info()).await;
-        let report = CurvineReport { info: rep };
+        let report = if fs.conf().federation.is_enabled() {
+            let namespaces = handle_rpc_result(fs.fs_client().get_namespaces_info()).await;
+            CurvineReport::federated(namespaces)
+        } else {
+            let rep = handle_rpc_result(fs.get_master_info()).await;
+            CurvineReport {
+                info: rep,
+                namespaces: vec![],
+            }
+        };
         match &self.action {
             Some(action) => match action {
                 ReportSubCommand::Json => {
@@ -75,18 +83,57 @@ impl ReportCommand {
 }
 
 struct CurvineReport {
+    // With federation, the sum of all namespaces.
     info: MasterInfo,
+    namespaces: Vec<(String, MasterInfo)>,
 }
 
 impl CurvineReport {
+    // Aggregate the capacity and inode counts of all namespaces, the masters are those of the default namespace.
+    fn federated(namespaces: Vec<(String, MasterInfo)>) -> Self {
+        let mut info = namespaces.first().map(|x| x.1.clone()).unwrap_or_default();
+        for (_, ns) in namespaces.iter().skip(1) {
+            info.merge(ns);
+        }
+
+        Self { info, namespaces }
+    }
+
     // Serialize the MasterInfo to JSON
     pub fn to_json(&self) -> String {
-        match serde_json::to_string_pretty(&self.info) {
+        let res = if self.namespaces.is_empty() {
+            serde_json::to_string_pretty(&self.info)
+        } else {
+            let namespaces: serde_json::Map<String, serde_json::Value> = self
+                .namespaces
+                .iter()
+                .map(|(name, info)| (name.clone(), serde_json::json!(info)))
+                .collect();
+            serde_json::to_string_pretty(&serde_json::json!({
+                "total": self.info,
+                "namespaces": namespaces,
+            }))
+        };
+
+        match res {
             Ok(json) => json,
             Err(e) => format!("Error serializing to JSON: {}", e),
         }
     }
 
+    fn namespace_summary(name: &str, info: &MasterInfo) -> String {
+        format!(
+            "{} ({}), {}/{} ({:.2}%), dirs {}, files {}",
+            name,
+            info.active_master,
+            bytes_to_string(&info.available.to_bigint().unwrap()),
+            bytes_to_string(&info.capacity.to_bigint().unwrap()),
+            Self::get_percent(info.available, info.capacity),
+            info.inode_dir_num,
+            info.inode_file_num
+        )
+    }
+
     pub fn simple(&self, show_workers: bool) -> String {
         let mut builder = String::new();
         builder.push_str(&format!(
@@ -157,6 +204,15 @@ impl CurvineReport {
         ));
         builder.push_str(&format!("{:>20}: {}\n", "block_num", self.info.block_num));
 
+        for (i, (name, info)) in self.namespaces.iter().enumerate() {
+            let str = Self::namespace_summary(name, info);
+            if i == 0 {
+                builder.push_str(&format!("{:>20}: {}\n", "namespaces", str));
+            } else {
+                builder.push_str(&format!("{}{}\n", " ".repeat(22), str));
+            }
+        }
+
         if !show_workers {
             return builder;
         }
@@ -226,6 +282,25 @@ impl CurvineReport {
             bytes_to_string(&self.info.non_fs_used.to_bigint().unwrap())
         ));
 
+        for (name, info) in &self.namespaces {
+            builder.push_str(&format!("\n=== Namespace {} ===\n", name));
+            builder.push_str(&format!(
+                "Capacity: {}\n",
+                bytes_to_string(&info.capacity.to_bigint().unwrap())
+            ));
+            builder.push_str(&format!(
+                "Available: {} ({:.2}%)\n",
+                bytes_to_string(&info.available.to_bigint().unwrap()),
+                Self::get_percent(info.available, info.capacity)
+            ));
+            builder.push_str(&format!(
+                "Fs-used: {} ({:.2}%)\n",
+                bytes_to_string(&info.fs_used.to_bigint().unwrap()),
+                Self::get_percent(info.fs_used, info.capacity)
+            ));
+            builder.push_str(&format!("Files: {}\n", info.inode_file_num));
+        }
+
         builder
     }
 
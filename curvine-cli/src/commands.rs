@@ -47,6 +47,18 @@ pub enum Commands {
     #[command(name = "node")]
     Node(NodeCommand),
 
+    /// Master membership command
+    #[command(name = "master")]
+    Master(MasterCommand),
+
+    /// Check the files for missing, corrupt and mis-replicated blocks
+    #[command(name = "fsck")]
+    Fsck(FsckCommand),
+
+    /// Administration command, e.g. metadata backup
+    #[command(name = "admin")]
+    Admin(AdminCommand),
+
     /// show cli version
     #[command(name = "version")]
     Version,
@@ -125,6 +125,9 @@ fn main() -> CommonResult<()> {
             Commands::Mount(cmd) => cmd.execute(curvine_fs).await,
             Commands::UnMount(cmd) => cmd.execute(fs_client).await,
             Commands::Node(cmd) => cmd.execute(fs_client, conf.clone()).await,
+            Commands::Master(cmd) => cmd.execute(fs_client).await,
+            Commands::Fsck(cmd) => cmd.execute(fs_client).await,
+            Commands::Admin(cmd) => cmd.execute(fs_client).await,
             Commands::Version => {
                 println!("curvine-cli {}", version::VERSION);
                 Ok(())
@@ -25,7 +25,8 @@ use curvine_common::fs::RpcCode;
 use curvine_common::proto::{
     BlockReadRequest, BlockReadResponse, BlockWriteRequest, BlockWriteResponse,
     BlocksBatchCommitRequest, BlocksBatchWriteRequest, BlocksBatchWriteResponse, DataHeaderProto,
-    FileWriteData, FilesBatchWriteRequest,
+    FileWriteData, FilesBatchWriteRequest, IoThrottleProto, WorkerThrottleRequest,
+    WorkerThrottleResponse,
 };
 use curvine_common::state::{ExtendedBlock, StorageType, WorkerAddress};
 use curvine_common::utils::ProtoUtils;
@@ -423,6 +424,38 @@ impl BlockClient {
         let _ = self.rpc(msg).await?;
         Ok(())
     }
+
+    // Change the io throttle limits of the worker, returns all limits after the change.
+    // An empty list only queries the limits.
+    pub async fn worker_throttle(
+        &self,
+        limits: Vec<(String, u64)>,
+    ) -> FsResult<Vec<(String, u64)>> {
+        let request = WorkerThrottleRequest {
+            limits: limits
+                .into_iter()
+                .map(|(traffic_class, rate)| IoThrottleProto {
+                    traffic_class,
+                    rate,
+                })
+                .collect(),
+        };
+
+        let msg = Builder::new_rpc(RpcCode::WorkerThrottle)
+            .request(RequestStatus::Rpc)
+            .proto_header(request)
+            .build();
+
+        let rep = self.rpc(msg).await?;
+        let rep_header: WorkerThrottleResponse = rep.parse_header()?;
+        let limits = rep_header
+            .limits
+            .into_iter()
+            .map(|x| (x.traffic_class, x.rate))
+            .collect();
+
+        Ok(limits)
+    }
 }
 
 impl Drop for BlockClient {
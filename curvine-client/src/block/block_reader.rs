@@ -12,8 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::block::block_reader::ReaderAdapter::{Hole, Local, Remote};
-use crate::block::{BlockReaderHole, BlockReaderLocal, BlockReaderRemote};
+use crate::block::block_reader::ReaderAdapter::{Hole, Local, Remote, Striped};
+use crate::block::{BlockReaderHole, BlockReaderLocal, BlockReaderRemote, StripedBlockReader};
 use crate::file::FsContext;
 use curvine_common::state::{ClientAddress, ExtendedBlock, LocatedBlock, WorkerAddress};
 use curvine_common::FsResult;
@@ -29,6 +29,7 @@ enum ReaderAdapter {
     Local(BlockReaderLocal),
     Remote(BlockReaderRemote),
     Hole(BlockReaderHole),
+    Striped(StripedBlockReader),
 }
 
 impl ReaderAdapter {
@@ -37,6 +38,7 @@ impl ReaderAdapter {
             Local(r) => r.read().await,
             Remote(r) => r.read().await,
             Hole(r) => r.read(),
+            Striped(r) => r.read().await,
         }
     }
 
@@ -46,6 +48,7 @@ impl ReaderAdapter {
             Local(r) => r.blocking_read(),
             Remote(r) => rt.block_on(r.read()),
             Hole(r) => r.read(),
+            Striped(r) => rt.block_on(r.read()),
         }
     }
 
@@ -54,6 +57,7 @@ impl ReaderAdapter {
             Local(r) => r.complete().await,
             Remote(r) => r.complete().await,
             Hole(r) => r.complete(),
+            Striped(r) => r.complete().await,
         }
     }
 
@@ -62,6 +66,7 @@ impl ReaderAdapter {
             Local(r) => r.remaining(),
             Remote(r) => r.remaining(),
             Hole(r) => r.remaining(),
+            Striped(r) => r.remaining(),
         }
     }
 
@@ -70,6 +75,7 @@ impl ReaderAdapter {
             Local(r) => r.seek(pos),
             Remote(r) => r.seek(pos),
             Hole(r) => r.seek(pos),
+            Striped(r) => r.seek(pos),
         }
     }
 
@@ -78,6 +84,7 @@ impl ReaderAdapter {
             Local(r) => r.pos(),
             Remote(r) => r.pos(),
             Hole(r) => r.pos(),
+            Striped(r) => r.pos(),
         }
     }
 
@@ -86,6 +93,7 @@ impl ReaderAdapter {
             Local(r) => r.len(),
             Remote(r) => r.len(),
             Hole(r) => r.len(),
+            Striped(r) => r.len(),
         }
     }
 
@@ -94,6 +102,7 @@ impl ReaderAdapter {
             Local(r) => r.block_id(),
             Remote(r) => r.block_id(),
             Hole(r) => r.block_id(),
+            Striped(r) => r.block_id(),
         }
     }
 
@@ -102,6 +111,7 @@ impl ReaderAdapter {
             Local(r) => r.worker_address(),
             Remote(r) => r.worker_address(),
             Hole(r) => r.worker_address(),
+            Striped(r) => r.worker_address(),
         }
     }
 }
@@ -121,6 +131,17 @@ impl BlockReader {
     ) -> CommonResult<Self> {
         let len = located.block.len;
 
+        // Erasure-coded blocks recover from unit failures internally, there is no replica to retry.
+        if located.is_striped() {
+            let reader = StripedBlockReader::new(fs_context.clone(), located.clone(), off)?;
+            return Ok(Self {
+                inner: Striped(reader),
+                locs: vec![],
+                block: located.block,
+                fs_context,
+            });
+        }
+
         let locs = Self::sort_locs(
             located.locs,
             fs_context.conf.client.short_circuit,
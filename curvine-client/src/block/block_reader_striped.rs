@@ -0,0 +1,268 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::block::BlockReaderRemote;
+use crate::file::FsContext;
+use bytes::BytesMut;
+use curvine_common::ec::ReedSolomon;
+use curvine_common::state::{EcPolicy, ExtendedBlock, LocatedBlock, WorkerAddress};
+use curvine_common::FsResult;
+use log::warn;
+use orpc::sys::DataSlice;
+use orpc::{err_box, try_option_mut};
+use std::sync::Arc;
+
+struct UnitReader {
+    addr: Option<WorkerAddress>,
+    reader: Option<BlockReaderRemote>,
+    failed: bool,
+}
+
+/// Reader of an erasure-coded block.
+///
+/// Cells are read directly from the data units. When a unit can not be read,
+/// the cell is decoded from any `data_units` other units of the same stripe.
+pub struct StripedBlockReader {
+    fs_context: Arc<FsContext>,
+    block: ExtendedBlock,
+    policy: EcPolicy,
+    codec: ReedSolomon,
+    units: Vec<UnitReader>,
+    pos: i64,
+    len: i64,
+    worker_address: WorkerAddress,
+}
+
+impl StripedBlockReader {
+    pub fn new(fs_context: Arc<FsContext>, located: LocatedBlock, off: i64) -> FsResult<Self> {
+        let striped = match &located.striped {
+            Some(v) => v.clone(),
+            None => return err_box!("Block {} is not erasure-coded", located.block.id),
+        };
+        let policy = striped.policy;
+
+        let mut units: Vec<UnitReader> = (0..policy.total_units())
+            .map(|_| UnitReader {
+                addr: None,
+                reader: None,
+                failed: false,
+            })
+            .collect();
+        for (index, addr) in striped.indices.iter().zip(located.locs) {
+            match units.get_mut(*index as usize) {
+                Some(v) => v.addr = Some(addr),
+                None => return err_box!("Invalid unit index {} of {}", index, policy),
+            }
+        }
+
+        let live = units.iter().filter(|x| x.addr.is_some()).count();
+        if live < policy.data_units as usize {
+            return err_box!(
+                "Block {} is unreadable, {} of {} units available",
+                located.block.id,
+                live,
+                policy.total_units()
+            );
+        }
+
+        let reader = Self {
+            fs_context,
+            len: located.block.len,
+            block: located.block,
+            codec: ReedSolomon::with_policy(&policy)?,
+            policy,
+            units,
+            pos: off,
+            worker_address: WorkerAddress::default(),
+        };
+        Ok(reader)
+    }
+
+    // Returns the remaining bytes of the current cell.
+    pub async fn read(&mut self) -> FsResult<DataSlice> {
+        if self.remaining() <= 0 {
+            return err_box!("No readable data");
+        }
+
+        let stripe_size = self.policy.stripe_size();
+        let stripe = self.pos / stripe_size;
+        let index = ((self.pos % stripe_size) / self.policy.cell_size) as usize;
+        let cell_off = (self.pos % self.policy.cell_size) as usize;
+
+        let mut cell = self.read_unit_cell(stripe, index).await?;
+        let mut chunk = cell.split_off(cell_off);
+        chunk.truncate(self.remaining() as usize);
+
+        self.pos += chunk.len() as i64;
+        Ok(DataSlice::buffer(chunk))
+    }
+
+    /// Read the cell of unit `index` in the given stripe, decoding it if the unit is unavailable.
+    pub async fn read_unit_cell(&mut self, stripe: i64, index: usize) -> FsResult<BytesMut> {
+        let len = self.cell_len(stripe, index);
+        if len == 0 {
+            return Ok(BytesMut::new());
+        }
+
+        if !self.units[index].failed && self.units[index].addr.is_some() {
+            match self.read_cell(stripe, index, len).await {
+                Ok(v) => return Ok(v),
+                Err(e) => {
+                    warn!(
+                        "fail to read unit {} of block {}, decoding it: {}",
+                        index, self.block.id, e
+                    );
+                    self.units[index].failed = true;
+                    self.units[index].reader = None;
+                }
+            }
+        }
+
+        self.decode_cell(stripe, index).await
+    }
+
+    async fn decode_cell(&mut self, stripe: i64, index: usize) -> FsResult<BytesMut> {
+        let total = self.policy.total_units() as usize;
+        let shard_len = self.cell_len(stripe, self.policy.data_units as usize) as usize;
+
+        let mut shards: Vec<Option<Vec<u8>>> = vec![None; total];
+        let mut present = 0;
+        let candidates: Vec<usize> = (0..total)
+            .filter(|i| *i != index && !self.units[*i].failed && self.units[*i].addr.is_some())
+            .collect();
+        for i in candidates {
+            if present == self.policy.data_units as usize {
+                break;
+            }
+
+            let len = self.cell_len(stripe, i);
+            let cell = if len == 0 {
+                Ok(BytesMut::new())
+            } else {
+                self.read_cell(stripe, i, len).await
+            };
+            match cell {
+                Ok(v) => {
+                    // Short data cells of the last stripe were encoded with zero padding.
+                    let mut shard = v.to_vec();
+                    shard.resize(shard_len, 0);
+                    shards[i] = Some(shard);
+                    present += 1;
+                }
+                Err(e) => {
+                    warn!("fail to read unit {} of block {}: {}", i, self.block.id, e);
+                    self.units[i].failed = true;
+                    self.units[i].reader = None;
+                }
+            }
+        }
+
+        if present < self.policy.data_units as usize {
+            return err_box!(
+                "Block {} is unreadable, only {} units available to decode unit {}",
+                self.block.id,
+                present,
+                index
+            );
+        }
+
+        self.codec.reconstruct(&mut shards)?;
+        let mut cell = match shards[index].take() {
+            Some(v) => v,
+            None => return err_box!("Failed to decode unit {} of block {}", index, self.block.id),
+        };
+        cell.truncate(self.cell_len(stripe, index) as usize);
+        Ok(BytesMut::from(&cell[..]))
+    }
+
+    async fn read_cell(&mut self, stripe: i64, index: usize, len: i64) -> FsResult<BytesMut> {
+        let unit_len = self.policy.unit_len(self.len, index);
+        let off = stripe * self.policy.cell_size;
+
+        let unit = &mut self.units[index];
+        if unit.reader.is_none() {
+            let addr = match &unit.addr {
+                Some(v) => v.clone(),
+                None => return err_box!("Unit {} has no location", index),
+            };
+            let mut block = self.block.clone();
+            block.len = unit_len;
+            let reader =
+                BlockReaderRemote::new(&self.fs_context, block, addr, off, unit_len).await?;
+            unit.reader.replace(reader);
+        }
+        let reader = try_option_mut!(unit.reader);
+
+        reader.seek(off)?;
+        let mut buf = BytesMut::with_capacity(len as usize);
+        while (buf.len() as i64) < len {
+            let chunk = reader.read().await?;
+            if chunk.is_empty() {
+                return err_box!("Unexpected end of unit {}, offset {}", index, reader.pos());
+            }
+            buf.extend_from_slice(chunk.as_slice());
+        }
+        buf.truncate(len as usize);
+        Ok(buf)
+    }
+
+    // The number of bytes of unit `index` in the given stripe.
+    fn cell_len(&self, stripe: i64, index: usize) -> i64 {
+        let unit_len = self.policy.unit_len(self.len, index);
+        (unit_len - stripe * self.policy.cell_size).clamp(0, self.policy.cell_size)
+    }
+
+    pub async fn complete(&mut self) -> FsResult<()> {
+        for unit in &mut self.units {
+            if let Some(mut reader) = unit.reader.take() {
+                if let Err(e) = reader.complete().await {
+                    warn!("fail to complete unit reader: {}", e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remaining(&self) -> i64 {
+        self.len - self.pos
+    }
+
+    pub fn seek(&mut self, pos: i64) -> FsResult<i64> {
+        if pos < 0 || pos > self.len {
+            return err_box!("Seek position {} out of block length {}", pos, self.len);
+        }
+        self.pos = pos;
+        Ok(self.pos)
+    }
+
+    pub fn pos(&self) -> i64 {
+        self.pos
+    }
+
+    pub fn len(&self) -> i64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn block_id(&self) -> i64 {
+        self.block.id
+    }
+
+    pub fn worker_address(&self) -> &WorkerAddress {
+        &self.worker_address
+    }
+}
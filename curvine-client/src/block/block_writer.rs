@@ -14,21 +14,50 @@
 
 #![allow(unused)]
 
-use crate::block::block_writer::WriterAdapter::{Local, Remote};
-use crate::block::{BlockWriterLocal, BlockWriterRemote};
-use crate::file::FsContext;
+use crate::block::block_writer::WriterAdapter::{Local, Remote, Striped};
+use crate::block::{BlockReaderRemote, BlockWriterLocal, BlockWriterRemote, StripedBlockWriter};
+use crate::file::{FsClient, FsContext};
+use curvine_common::error::FsError;
+use curvine_common::fs::Path;
 use curvine_common::state::{BlockLocation, CommitBlock, LocatedBlock, WorkerAddress};
 use curvine_common::FsResult;
-use futures::future::try_join_all;
+use futures::future::{join_all, try_join_all};
+use log::{info, warn};
 use orpc::err_box;
 use orpc::error::ErrorExt;
 use orpc::runtime::{RpcRuntime, Runtime};
 use orpc::sys::DataSlice;
+use std::fmt::{Display, Formatter};
 use std::sync::Arc;
+use std::{fmt, mem};
+
+// The pipeline operation during which a replica failed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum PipelineOp {
+    Create,
+    Write,
+    Flush,
+    Complete,
+    Seek,
+}
+
+impl Display for PipelineOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            PipelineOp::Create => "create",
+            PipelineOp::Write => "write",
+            PipelineOp::Flush => "flush",
+            PipelineOp::Complete => "complete",
+            PipelineOp::Seek => "seek",
+        };
+        write!(f, "{}", op)
+    }
+}
 
 enum WriterAdapter {
     Local(BlockWriterLocal),
     Remote(BlockWriterRemote),
+    Striped(StripedBlockWriter),
 }
 
 impl WriterAdapter {
@@ -36,6 +65,7 @@ impl WriterAdapter {
         match self {
             Local(f) => f.worker_address(),
             Remote(f) => f.worker_address(),
+            Striped(f) => f.worker_address(),
         }
     }
 
@@ -43,6 +73,7 @@ impl WriterAdapter {
         match self {
             Local(f) => f.write(buf).await,
             Remote(f) => f.write(buf).await,
+            Striped(f) => f.write(buf).await,
         }
     }
 
@@ -50,6 +81,7 @@ impl WriterAdapter {
         match self {
             Local(f) => f.blocking_write(buf.clone()),
             Remote(f) => rt.block_on(f.write(buf.clone())),
+            Striped(f) => rt.block_on(f.write(buf.clone())),
         }
     }
 
@@ -57,6 +89,7 @@ impl WriterAdapter {
         match self {
             Local(f) => f.flush().await,
             Remote(f) => f.flush().await,
+            Striped(f) => f.flush().await,
         }
     }
 
@@ -64,6 +97,7 @@ impl WriterAdapter {
         match self {
             Local(f) => f.complete().await,
             Remote(f) => f.complete().await,
+            Striped(f) => f.complete().await,
         }
     }
 
@@ -71,6 +105,7 @@ impl WriterAdapter {
         match self {
             Local(f) => f.cancel().await,
             Remote(f) => f.cancel().await,
+            Striped(f) => f.cancel().await,
         }
     }
 
@@ -78,6 +113,7 @@ impl WriterAdapter {
         match self {
             Local(f) => f.remaining(),
             Remote(f) => f.remaining(),
+            Striped(f) => f.remaining(),
         }
     }
 
@@ -85,6 +121,7 @@ impl WriterAdapter {
         match self {
             Local(f) => f.pos(),
             Remote(f) => f.pos(),
+            Striped(f) => f.pos(),
         }
     }
 
@@ -93,6 +130,7 @@ impl WriterAdapter {
         match self {
             Local(f) => f.seek(pos).await,
             Remote(f) => f.seek(pos).await,
+            Striped(f) => f.seek(pos).await,
         }
     }
 
@@ -100,6 +138,7 @@ impl WriterAdapter {
         match self {
             Local(f) => f.len(),
             Remote(f) => f.len(),
+            Striped(f) => f.len(),
         }
     }
 
@@ -137,48 +176,93 @@ impl WriterAdapter {
     }
 }
 
+/// Writes a block to all its replicas.
+///
+/// When `enable_pipeline_recovery` is set, a replica that fails is dropped from the pipeline
+/// and the write continues on the others. A replacement worker is requested from the master,
+/// the data written so far is copied to it from a healthy replica. If no replacement is found,
+/// the block is committed with fewer replicas and reported to the master as under-replicated.
 pub struct BlockWriter {
     inners: Vec<WriterAdapter>,
     locate: LocatedBlock,
     fs_context: Arc<FsContext>,
+    // The file of the block, replacement workers are assigned by the master group that owns it.
+    fs_client: FsClient,
+    path: Path,
+    // The number of replicas the block was allocated with.
+    replicas: usize,
+    // Workers dropped from the pipeline, they are never chosen again for this block.
+    failed_workers: Vec<u32>,
 }
 
 impl BlockWriter {
-    pub async fn new(fs_context: Arc<FsContext>, locate: LocatedBlock, pos: i64) -> FsResult<Self> {
+    const MAX_REPLACE_ATTEMPTS: usize = 3;
+
+    /// `block_size` is the block size of the file, erasure-coded blocks size their units by it.
+    pub async fn new(
+        fs_client: &FsClient,
+        path: &Path,
+        locate: LocatedBlock,
+        pos: i64,
+        block_size: i64,
+    ) -> FsResult<Self> {
+        let fs_context = fs_client.context().clone();
         if locate.locs.is_empty() {
             return err_box!("There is no available worker");
         }
 
+        if locate.is_striped() {
+            let writer =
+                StripedBlockWriter::new(&fs_context, locate.clone(), pos, block_size).await?;
+            return Ok(Self {
+                inners: vec![Striped(writer)],
+                locate,
+                fs_context,
+                fs_client: fs_client.clone(),
+                path: path.clone(),
+                replicas: 1,
+                failed_workers: vec![],
+            });
+        }
+
+        let futures = locate
+            .locs
+            .iter()
+            .map(|addr| WriterAdapter::new(fs_context.clone(), &locate, addr, pos));
+        let res = join_all(futures).await;
+
         let mut inners = Vec::with_capacity(locate.locs.len());
-        for addr in &locate.locs {
-            let adapter = WriterAdapter::new(fs_context.clone(), &locate, addr, pos).await?;
-            inners.push(adapter);
+        let mut failed = vec![];
+        for (addr, adapter) in locate.locs.iter().zip(res) {
+            match adapter {
+                Ok(v) => inners.push(v),
+                Err(e) => failed.push((addr.clone(), e)),
+            }
         }
 
-        let writer = Self {
+        let mut writer = Self {
             inners,
+            replicas: locate.locs.len(),
             locate,
             fs_context,
+            fs_client: fs_client.clone(),
+            path: path.clone(),
+            failed_workers: vec![],
         };
+        writer.recover(PipelineOp::Create, failed).await?;
         Ok(writer)
     }
 
     pub async fn write(&mut self, chunk: DataSlice) -> FsResult<()> {
         let chunk = chunk.freeze();
-        let futures = self.inners.iter_mut().map(|writer| {
-            let chunk_clone = chunk.clone();
-            async move {
-                writer
-                    .write(chunk_clone)
-                    .await
-                    .map_err(|e| (writer.worker_address().clone(), e))
-            }
-        });
-
-        if let Err((worker_addr, e)) = try_join_all(futures).await {
-            return Err(e.ctx(format!("failed to write block on {}", worker_addr)));
-        }
-        Ok(())
+        let futures = self
+            .inners
+            .iter_mut()
+            .map(|writer| writer.write(chunk.clone()));
+        let res = join_all(futures).await;
+
+        let failed = self.remove_failed(res);
+        self.recover(PipelineOp::Write, failed).await
     }
 
     pub fn blocking_write(&mut self, rt: &Runtime, chunk: DataSlice) -> FsResult<()> {
@@ -186,30 +270,19 @@ impl BlockWriter {
     }
 
     pub async fn flush(&mut self) -> FsResult<()> {
-        let futures = self.inners.iter_mut().map(|writer| async move {
-            writer
-                .flush()
-                .await
-                .map_err(|e| (writer.worker_address().clone(), e))
-        });
+        let futures = self.inners.iter_mut().map(|writer| writer.flush());
+        let res = join_all(futures).await;
 
-        if let Err((worker_addr, e)) = try_join_all(futures).await {
-            return Err(e.ctx(format!("failed to flush block on {}", worker_addr)));
-        }
-        Ok(())
+        let failed = self.remove_failed(res);
+        self.recover(PipelineOp::Flush, failed).await
     }
 
     pub async fn complete(&mut self) -> FsResult<CommitBlock> {
-        let futures = self.inners.iter_mut().map(|writer| async move {
-            writer
-                .complete()
-                .await
-                .map_err(|e| (writer.worker_address().clone(), e))
-        });
+        let futures = self.inners.iter_mut().map(|writer| writer.complete());
+        let res = join_all(futures).await;
 
-        if let Err((worker_addr, e)) = try_join_all(futures).await {
-            return Err(e.ctx(format!("failed to complete block on {}", worker_addr)));
-        }
+        let failed = self.remove_failed(res);
+        self.recover(PipelineOp::Complete, failed).await?;
         Ok(self.to_commit_block())
     }
 
@@ -252,26 +325,177 @@ impl BlockWriter {
         self.locate.block.id
     }
 
+    // Whether the block has fewer replicas than it was allocated with.
+    pub fn is_degraded(&self) -> bool {
+        self.inners.len() < self.replicas
+    }
+
     // Implement seek support for random writes
     pub async fn seek(&mut self, pos: i64) -> FsResult<()> {
         if pos < 0 {
             return err_box!("Cannot seek to negative position: {}", pos);
         }
 
-        let futures = self.inners.iter_mut().map(|writer| async move {
-            writer
-                .seek(pos)
-                .await
-                .map_err(|e| (writer.worker_address().clone(), e))
-        });
+        let futures = self.inners.iter_mut().map(|writer| writer.seek(pos));
+        let res = join_all(futures).await;
 
-        if let Err((worker_addr, e)) = try_join_all(futures).await {
-            return Err(e.ctx(format!("failed to seek to worker {}", worker_addr)));
+        let failed = self.remove_failed(res);
+        self.recover(PipelineOp::Seek, failed).await
+    }
+
+    // Removes the replicas whose operation failed from the pipeline.
+    fn remove_failed(&mut self, res: Vec<FsResult<()>>) -> Vec<(WorkerAddress, FsError)> {
+        let mut failed = vec![];
+        let inners = mem::take(&mut self.inners);
+        for (writer, res) in inners.into_iter().zip(res) {
+            match res {
+                Ok(_) => self.inners.push(writer),
+                Err(e) => failed.push((writer.worker_address().clone(), e)),
+            }
+        }
+        failed
+    }
+
+    async fn recover(
+        &mut self,
+        op: PipelineOp,
+        mut failed: Vec<(WorkerAddress, FsError)>,
+    ) -> FsResult<()> {
+        if failed.is_empty() {
+            return Ok(());
+        }
+
+        let recoverable = self.fs_context.conf.client.enable_pipeline_recovery
+            && !self.locate.is_striped()
+            && !self.inners.is_empty();
+        if !recoverable {
+            let (addr, e) = failed.swap_remove(0);
+            return Err(e.ctx(format!("failed to {} block on {}", op, addr)));
+        }
+
+        for (addr, e) in &failed {
+            warn!(
+                "Failed to {} block {} on {}, remove it from the pipeline: {}",
+                op,
+                self.block_id(),
+                addr,
+                e
+            );
+            self.fs_context.add_failed_worker(addr);
+            self.failed_workers.push(addr.worker_id);
+            self.locate.locs.retain(|x| x.worker_id != addr.worker_id);
+        }
+
+        let mut attempts = 0;
+        while self.inners.len() < self.replicas && attempts < Self::MAX_REPLACE_ATTEMPTS {
+            attempts += 1;
+            if let Err(e) = self.add_replacement(op).await {
+                warn!(
+                    "Failed to replace a replica of block {}: {}",
+                    self.block_id(),
+                    e
+                );
+            }
+        }
+
+        if self.is_degraded() {
+            warn!(
+                "Block {} continues with {} of {} replicas",
+                self.block_id(),
+                self.inners.len(),
+                self.replicas
+            );
         }
         Ok(())
     }
 
+    // Adds a new worker to the pipeline, it catches up with the healthy replicas before
+    // taking part in the following writes.
+    async fn add_replacement(&mut self, op: PipelineOp) -> FsResult<()> {
+        let mut exclude_workers: Vec<u32> = self.locate.locs.iter().map(|x| x.worker_id).collect();
+        exclude_workers.extend(&self.failed_workers);
+
+        let worker = self
+            .fs_client
+            .request_replacement_worker(&self.path, self.block_id(), exclude_workers)
+            .await?;
+
+        match self.sync_replica(op, &worker).await {
+            Ok(writer) => {
+                info!(
+                    "Replica of block {} is replaced by {}, synced {} bytes",
+                    self.block_id(),
+                    worker,
+                    writer.len()
+                );
+                self.inners.push(writer);
+                self.locate.locs.push(worker);
+                Ok(())
+            }
+
+            Err(e) => {
+                self.fs_context.add_failed_worker(&worker);
+                self.failed_workers.push(worker.worker_id);
+                Err(e.ctx(format!("failed to sync block to {}", worker)))
+            }
+        }
+    }
+
+    // Copies the data written so far from a healthy replica to the new worker.
+    async fn sync_replica(
+        &mut self,
+        op: PipelineOp,
+        worker: &WorkerAddress,
+    ) -> FsResult<WriterAdapter> {
+        let source = &mut self.inners[0];
+        if op != PipelineOp::Complete && op != PipelineOp::Flush {
+            source.flush().await?;
+        }
+        let (pos, len) = (source.pos(), source.len());
+        let source_addr = source.worker_address().clone();
+
+        let mut writer =
+            WriterAdapter::new(self.fs_context.clone(), &self.locate, worker, 0).await?;
+        let mut reader = BlockReaderRemote::new(
+            &self.fs_context,
+            self.locate.block.clone(),
+            source_addr,
+            0,
+            len,
+        )
+        .await?;
+
+        while reader.remaining() > 0 {
+            let chunk = reader.read().await?;
+            if chunk.is_empty() {
+                return err_box!(
+                    "Block {} ends at {}, expected length {}",
+                    self.block_id(),
+                    reader.pos(),
+                    len
+                );
+            }
+            writer.write(chunk).await?;
+        }
+        reader.complete().await?;
+
+        if pos != len {
+            writer.seek(pos).await?;
+        }
+        match op {
+            PipelineOp::Flush => writer.flush().await?,
+            PipelineOp::Complete => writer.complete().await?,
+            _ => (),
+        }
+
+        Ok(writer)
+    }
+
     pub fn to_commit_block(&self) -> CommitBlock {
+        if let [Striped(f)] = &self.inners[..] {
+            return f.to_commit_block();
+        }
+
         let locs = self
             .locate
             .locs
@@ -38,10 +38,21 @@ impl BlockWriterRemote {
         block: ExtendedBlock,
         worker_address: WorkerAddress,
         pos: i64,
+    ) -> FsResult<Self> {
+        let block_size = fs_context.block_size();
+        Self::with_block_size(fs_context, block, worker_address, pos, block_size).await
+    }
+
+    // Write a block whose capacity is not the client block size, e.g. a unit of a striped block.
+    pub async fn with_block_size(
+        fs_context: &FsContext,
+        block: ExtendedBlock,
+        worker_address: WorkerAddress,
+        pos: i64,
+        block_size: i64,
     ) -> FsResult<Self> {
         let req_id = Utils::req_id();
         let seq_id = 0;
-        let block_size = fs_context.block_size();
 
         let client = fs_context.acquire_write(&worker_address).await?;
         let write_context = client
@@ -0,0 +1,258 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::block::BlockWriterRemote;
+use crate::file::FsContext;
+use bytes::BytesMut;
+use curvine_common::ec::ReedSolomon;
+use curvine_common::state::{BlockLocation, CommitBlock, EcPolicy, LocatedBlock, WorkerAddress};
+use curvine_common::FsResult;
+use futures::future::try_join_all;
+use orpc::err_box;
+use orpc::error::ErrorExt;
+use orpc::sys::DataSlice;
+
+/// Writer of an erasure-coded block.
+///
+/// Data is buffered until a full stripe is collected, then the stripe is split into
+/// `data_units` cells, `parity_units` parity cells are encoded and every cell is sent
+/// to the worker holding its unit. Only sequential writes are supported.
+pub struct StripedBlockWriter {
+    locate: LocatedBlock,
+    policy: EcPolicy,
+    codec: ReedSolomon,
+    units: Vec<BlockWriterRemote>,
+    stripe: BytesMut,
+    // Logical position at the start of the buffered stripe.
+    stripe_pos: i64,
+    // The logical block size of the file.
+    block_size: i64,
+}
+
+impl StripedBlockWriter {
+    pub async fn new(
+        fs_context: &FsContext,
+        locate: LocatedBlock,
+        pos: i64,
+        block_size: i64,
+    ) -> FsResult<Self> {
+        let striped = match &locate.striped {
+            Some(v) => v.clone(),
+            None => return err_box!("Block {} is not erasure-coded", locate.block.id),
+        };
+        if pos != 0 {
+            return err_box!(
+                "Erasure-coded block {} only supports sequential writes",
+                locate.block.id
+            );
+        }
+
+        let policy = striped.policy;
+        let total = policy.total_units() as usize;
+        if locate.locs.len() != total || striped.indices.len() != total {
+            return err_box!(
+                "Erasure-coded block {} needs {} workers, got {}",
+                locate.block.id,
+                total,
+                locate.locs.len()
+            );
+        }
+
+        // Order the unit writers by unit index.
+        let mut addrs: Vec<Option<&WorkerAddress>> = vec![None; total];
+        for (index, addr) in striped.indices.iter().zip(&locate.locs) {
+            addrs[*index as usize] = Some(addr);
+        }
+
+        // Each unit holds 1/data_units of the logical block.
+        let unit_size = policy.unit_capacity(block_size);
+        let mut units = Vec::with_capacity(total);
+        for addr in addrs {
+            let addr = match addr {
+                Some(v) => v.clone(),
+                None => {
+                    return err_box!(
+                        "Erasure-coded block {} has duplicate units",
+                        locate.block.id
+                    )
+                }
+            };
+            let writer = BlockWriterRemote::with_block_size(
+                fs_context,
+                locate.block.clone(),
+                addr,
+                0,
+                unit_size,
+            )
+            .await?;
+            units.push(writer);
+        }
+
+        let writer = Self {
+            codec: ReedSolomon::with_policy(&policy)?,
+            policy,
+            units,
+            stripe: BytesMut::with_capacity(policy.stripe_size() as usize),
+            stripe_pos: 0,
+            block_size,
+            locate,
+        };
+        Ok(writer)
+    }
+
+    pub async fn write(&mut self, mut chunk: DataSlice) -> FsResult<()> {
+        let stripe_size = self.policy.stripe_size() as usize;
+        while !chunk.is_empty() {
+            let len = chunk.len().min(stripe_size - self.stripe.len());
+            self.stripe
+                .extend_from_slice(chunk.split_to(len).as_slice());
+
+            if self.stripe.len() == stripe_size {
+                self.write_stripe().await?;
+                self.stripe_pos += stripe_size as i64;
+                self.stripe.clear();
+            }
+        }
+        Ok(())
+    }
+
+    // Encode the buffered stripe and send every cell to its unit.
+    // A partial stripe is zero padded for encoding, but only the real bytes are stored.
+    async fn write_stripe(&mut self) -> FsResult<()> {
+        let cell = self.policy.cell_size as usize;
+        let len = self.stripe.len();
+        let parity_len = len.min(cell);
+
+        let mut cells = Vec::with_capacity(self.policy.total_units() as usize);
+        for i in 0..self.policy.data_units as usize {
+            let start = (i * cell).min(len);
+            let end = ((i + 1) * cell).min(len);
+            let mut buf = vec![0u8; parity_len];
+            buf[..end - start].copy_from_slice(&self.stripe[start..end]);
+            cells.push((buf, end - start));
+        }
+
+        let parity = self
+            .codec
+            .encode(&cells.iter().map(|x| &x.0).collect::<Vec<_>>())?;
+        cells.extend(parity.into_iter().map(|x| (x, parity_len)));
+
+        let futures = self
+            .units
+            .iter_mut()
+            .zip(cells)
+            .filter(|(_, (_, len))| *len > 0)
+            .map(|(writer, (mut buf, len))| async move {
+                buf.truncate(len);
+                writer
+                    .write(DataSlice::buffer(BytesMut::from(&buf[..])))
+                    .await
+                    .map_err(|e| (writer.worker_address().clone(), e))
+            });
+
+        if let Err((worker_addr, e)) = try_join_all(futures).await {
+            return Err(e.ctx(format!("failed to write block unit on {}", worker_addr)));
+        }
+        Ok(())
+    }
+
+    pub async fn flush(&mut self) -> FsResult<()> {
+        // Persist the partial stripe, then rewind the units so that it is rewritten
+        // together with the following data.
+        if !self.stripe.is_empty() {
+            self.write_stripe().await?;
+        }
+
+        let unit_pos = self.stripe_pos / self.policy.data_units as i64;
+        let futures = self.units.iter_mut().map(|writer| async move {
+            writer.flush().await?;
+            writer.seek(unit_pos).await
+        });
+        try_join_all(futures).await?;
+        Ok(())
+    }
+
+    pub async fn complete(&mut self) -> FsResult<()> {
+        if !self.stripe.is_empty() {
+            self.write_stripe().await?;
+        }
+
+        let futures = self.units.iter_mut().map(|writer| async move {
+            writer
+                .complete()
+                .await
+                .map_err(|e| (writer.worker_address().clone(), e))
+        });
+        if let Err((worker_addr, e)) = try_join_all(futures).await {
+            return Err(e.ctx(format!("failed to complete block unit on {}", worker_addr)));
+        }
+        Ok(())
+    }
+
+    pub async fn cancel(&mut self) -> FsResult<()> {
+        let futures = self.units.iter_mut().map(|writer| writer.cancel());
+        try_join_all(futures).await?;
+        Ok(())
+    }
+
+    pub fn worker_address(&self) -> &WorkerAddress {
+        self.units[0].worker_address()
+    }
+
+    pub fn remaining(&self) -> i64 {
+        self.block_size - self.pos()
+    }
+
+    pub fn pos(&self) -> i64 {
+        self.stripe_pos + self.stripe.len() as i64
+    }
+
+    pub fn len(&self) -> i64 {
+        self.pos()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub async fn seek(&mut self, pos: i64) -> FsResult<()> {
+        if pos != self.pos() {
+            return err_box!(
+                "Erasure-coded block {} does not support seek to {}, current position {}",
+                self.locate.block.id,
+                pos,
+                self.pos()
+            );
+        }
+        Ok(())
+    }
+
+    // Locations are ordered by unit index.
+    pub fn to_commit_block(&self) -> CommitBlock {
+        let locs = self
+            .units
+            .iter()
+            .map(|x| BlockLocation {
+                worker_id: x.worker_address().worker_id,
+                storage_type: self.locate.block.storage_type,
+            })
+            .collect();
+
+        CommitBlock {
+            block_id: self.locate.block.id,
+            block_len: self.len(),
+            locations: locs,
+        }
+    }
+}
@@ -30,6 +30,9 @@ pub use self::batch_block_writer::BatchBlockWriter;
 mod block_writer;
 pub use self::block_writer::BlockWriter;
 
+mod block_writer_striped;
+pub use self::block_writer_striped::StripedBlockWriter;
+
 mod context;
 pub use self::context::*;
 
@@ -45,6 +48,9 @@ pub use self::block_reader_remote::BlockReaderRemote;
 mod block_reader_hole;
 pub use self::block_reader_hole::BlockReaderHole;
 
+mod block_reader_striped;
+pub use self::block_reader_striped::StripedBlockReader;
+
 mod batch_block_writer_local;
 pub use self::batch_block_writer_local::BatchBlockWriterLocal;
 
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::block::BatchBlockWriter;
-use crate::file::{FsClient, FsContext, FsReader, FsWriter, FsWriterBase};
+use crate::file::{FsClient, FsContext, FsReader, FsWriter, FsWriterBase, ListStatusIter};
 use crate::ClientMetrics;
 use bytes::BytesMut;
 use curvine_common::conf::ClusterConf;
@@ -21,9 +21,9 @@ use curvine_common::error::FsError;
 use curvine_common::fs::{Path, Reader, Writer};
 use curvine_common::state::CommitBlock;
 use curvine_common::state::{
-    CreateFileOpts, CreateFileOptsBuilder, FileAllocOpts, FileBlocks, FileLock, FileStatus,
-    MasterInfo, MkdirOpts, MkdirOptsBuilder, MountInfo, MountOptions, MountType, OpenFlags,
-    SetAttrOpts,
+    ContentSummary, CreateFileOpts, CreateFileOptsBuilder, DirPolicy, FileAllocOpts, FileBlocks,
+    FileLock, FileStatus, FindOpts, MasterInfo, MkdirOpts, MkdirOptsBuilder, MountInfo,
+    MountOptions, MountType, OpenFlags, SetAttrOpts, StorageType,
 };
 use curvine_common::utils::ProtoUtils;
 use curvine_common::version::GIT_VERSION;
@@ -205,10 +205,54 @@ impl CurvineFileSystem {
         self.fs_client.list_status_bytes(path).await
     }
 
+    // Iterate the children of the directory, they are fetched page by page.
+    pub fn list_status_iter(&self, path: &Path, start_after: Option<String>) -> ListStatusIter {
+        ListStatusIter::new(
+            self.fs_client.clone(),
+            path.clone(),
+            self.conf().client.list_status_page_size,
+            start_after,
+        )
+    }
+
     pub async fn list_files(&self, path: &Path) -> FsResult<Vec<FileStatus>> {
         self.fs_client.list_files(path).await
     }
 
+    pub async fn content_summary(&self, path: &Path) -> FsResult<ContentSummary> {
+        self.fs_client.content_summary(path).await
+    }
+
+    pub async fn find_page(
+        &self,
+        path: &Path,
+        opts: &FindOpts,
+        start_after: Option<&str>,
+        limit: i32,
+    ) -> FsResult<(Vec<FileStatus>, bool)> {
+        self.fs_client
+            .find_page(path, opts, start_after, limit)
+            .await
+    }
+
+    // Find all the entries under the path that match the opts, they are fetched page by page.
+    pub async fn find(&self, path: &Path, opts: &FindOpts) -> FsResult<Vec<FileStatus>> {
+        let page_size = self.conf().client.list_status_page_size;
+        let mut res: Vec<FileStatus> = vec![];
+        loop {
+            let start_after = res.last().map(|x| x.path.clone());
+            let (list, has_more) = self
+                .find_page(path, opts, start_after.as_deref(), page_size)
+                .await?;
+            let done = !has_more || list.is_empty();
+            res.extend(list);
+            if done {
+                break;
+            }
+        }
+        Ok(res)
+    }
+
     pub async fn get_block_locations(&self, path: &Path) -> FsResult<FileBlocks> {
         self.fs_client.get_block_locations(path).await
     }
@@ -265,6 +309,25 @@ impl CurvineFileSystem {
         self.fs_client.set_attr(path, opts).await
     }
 
+    pub async fn set_dir_policy(&self, path: &Path, policy: DirPolicy) -> FsResult<FileStatus> {
+        self.fs_client.set_dir_policy(path, policy).await
+    }
+
+    pub async fn get_dir_policy(&self, path: &Path) -> FsResult<(DirPolicy, DirPolicy)> {
+        self.fs_client.get_dir_policy(path).await
+    }
+
+    pub async fn set_storage_policy(
+        &self,
+        path: &Path,
+        storage_type: StorageType,
+        recursive: bool,
+    ) -> FsResult<(String, i64, i64)> {
+        self.fs_client
+            .set_storage_policy(path, storage_type, recursive)
+            .await
+    }
+
     pub async fn symlink(&self, target: &str, link: &Path, force: bool) -> FsResult<()> {
         self.fs_client.symlink(target, link, force).await
     }
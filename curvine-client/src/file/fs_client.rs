@@ -23,6 +23,7 @@ use curvine_common::utils::ProtoUtils;
 use curvine_common::FsResult;
 use orpc::client::ClusterConnector;
 use orpc::err_box;
+use orpc::io::net::InetAddr;
 use orpc::message::MessageBuilder;
 use orpc::runtime::RpcRuntime;
 use prost::Message as PMessage;
@@ -49,13 +50,104 @@ impl FsClient {
         &self.context.conf
     }
 
+    // A client bound to the master group of the namespace that owns the path.
+    pub fn route(&self, path: &Path) -> Self {
+        Self {
+            context: self.context.clone(),
+            connector: self.context.namespace_connector(path).clone(),
+        }
+    }
+
+    // Clients of all namespaces, the default namespace comes first.
+    pub fn namespace_clients(&self) -> Vec<Self> {
+        self.context
+            .all_namespaces()
+            .into_iter()
+            .map(|(_, connector)| Self {
+                context: self.context.clone(),
+                connector: connector.clone(),
+            })
+            .collect()
+    }
+
+    // A client bound to the master group of the named namespace.
+    pub fn namespace(&self, name: &str) -> FsResult<Self> {
+        for (ns, connector) in self.context.all_namespaces() {
+            if ns == name {
+                return Ok(Self {
+                    context: self.context.clone(),
+                    connector: connector.clone(),
+                });
+            }
+        }
+        err_box!("Namespace {} not found", name)
+    }
+
+    // A client bound to the namespace that mounts the ufs path, paths of curvine are routed by the
+    // mount table of the federation.
+    pub async fn route_ufs(&self, path: &Path) -> FsResult<Self> {
+        if path.is_cv() || !self.conf().federation.is_enabled() {
+            return Ok(self.route(path));
+        }
+
+        for client in self.namespace_clients() {
+            let rep: GetMountTableResponse = client
+                .rpc(RpcCode::GetMountTable, GetMountTableRequest {})
+                .await?;
+            let mounted = rep.mount_table.iter().any(|mnt| {
+                let ufs_path = mnt.ufs_path.trim_end_matches('/');
+                match path.full_path().strip_prefix(ufs_path) {
+                    Some(rest) => rest.is_empty() || rest.starts_with('/'),
+                    None => false,
+                }
+            });
+            if mounted {
+                return Ok(client);
+            }
+        }
+        Ok(self.clone())
+    }
+
+    // All paths of a batch request must belong to the same namespace.
+    fn route_batch<'a>(&self, mut paths: impl Iterator<Item = &'a String>) -> FsResult<Self> {
+        if !self.conf().federation.is_enabled() {
+            return Ok(self.clone());
+        }
+
+        let first = match paths.next() {
+            Some(v) => Path::from_str(v)?,
+            None => return Ok(self.clone()),
+        };
+
+        for path in paths {
+            let path = Path::from_str(path)?;
+            self.check_same_namespace(&first, &path)?;
+        }
+        Ok(self.route(&first))
+    }
+
+    fn check_same_namespace(&self, src: &Path, dst: &Path) -> FsResult<()> {
+        let src_ns = self.context.namespace_name(src);
+        let dst_ns = self.context.namespace_name(dst);
+        if src_ns != dst_ns {
+            return Err(FsError::invalid_path(
+                src.path(),
+                format!(
+                    "{} belongs to namespace {}, {} belongs to namespace {}",
+                    src, src_ns, dst, dst_ns
+                ),
+            ));
+        }
+        Ok(())
+    }
+
     pub async fn mkdir(&self, path: &Path, opts: MkdirOpts) -> FsResult<FileStatus> {
         let header = MkdirRequest {
             path: path.encode(),
             opts: ProtoUtils::mkdir_opts_to_pb(opts),
         };
 
-        let rep_header: MkdirResponse = self.rpc(RpcCode::Mkdir, header).await?;
+        let rep_header: MkdirResponse = self.route(path).rpc(RpcCode::Mkdir, header).await?;
         Ok(ProtoUtils::file_status_from_pb(rep_header.status))
     }
 
@@ -85,11 +177,12 @@ impl FsClient {
             })
             .collect();
 
+        let client = self.route_batch(pb_requests.iter().map(|x| &x.path))?;
         let header = CreateFilesBatchRequest {
             requests: pb_requests,
         };
 
-        let rep: CreateFilesBatchResponse = self.rpc(RpcCode::CreateFilesBatch, header).await?;
+        let rep: CreateFilesBatchResponse = client.rpc(RpcCode::CreateFilesBatch, header).await?;
         Ok(rep
             .file_statuses
             .into_iter()
@@ -112,7 +205,8 @@ impl FsClient {
             flags: flags.value(),
         };
 
-        let rep_header: CreateFileResponse = self.rpc(RpcCode::CreateFile, header).await?;
+        let rep_header: CreateFileResponse =
+            self.route(path).rpc(RpcCode::CreateFile, header).await?;
         let status = ProtoUtils::file_status_from_pb(rep_header.file_status);
         Ok(status)
     }
@@ -128,7 +222,7 @@ impl FsClient {
             opts: ProtoUtils::create_opts_to_pb(opts, self.context.clone_client_name()),
             flags: flags.value(),
         };
-        let rep_header: OpenFileResponse = self.rpc(RpcCode::OpenFile, header).await?;
+        let rep_header: OpenFileResponse = self.route(path).rpc(RpcCode::OpenFile, header).await?;
         let status = ProtoUtils::file_blocks_from_pb(rep_header.file_blocks);
         Ok(status)
     }
@@ -136,9 +230,13 @@ impl FsClient {
     pub async fn file_status(&self, path: &Path) -> FsResult<FileStatus> {
         let header = GetFileStatusRequest {
             path: path.encode(),
+            follower_read: self.follower_read(),
         };
 
-        let rep_header: GetFileStatusResponse = self.rpc(RpcCode::FileStatus, header).await?;
+        let rep_header: GetFileStatusResponse = self
+            .route(path)
+            .read_rpc(RpcCode::FileStatus, header)
+            .await?;
         let status = ProtoUtils::file_status_from_pb(rep_header.status);
         Ok(status)
     }
@@ -146,16 +244,20 @@ impl FsClient {
     pub async fn file_status_bytes(&self, path: &Path) -> FsResult<BytesMut> {
         let header = GetFileStatusRequest {
             path: path.encode(),
+            follower_read: self.follower_read(),
         };
-        self.rpc_bytes(RpcCode::FileStatus, header).await
+        self.route(path)
+            .read_rpc_bytes(RpcCode::FileStatus, header)
+            .await
     }
 
     pub async fn exists(&self, path: &Path) -> FsResult<bool> {
         let header = ExistsRequest {
             path: path.encode(),
+            follower_read: self.follower_read(),
         };
 
-        let rep_header: ExistsResponse = self.rpc(RpcCode::Exists, header).await?;
+        let rep_header: ExistsResponse = self.route(path).read_rpc(RpcCode::Exists, header).await?;
         Ok(rep_header.exists)
     }
 
@@ -165,45 +267,172 @@ impl FsClient {
             recursive,
         };
 
-        let _: DeleteResponse = self.rpc(RpcCode::Delete, header).await?;
+        let _: DeleteResponse = self.route(path).rpc(RpcCode::Delete, header).await?;
         Ok(())
     }
 
+    // Rename across namespaces is rejected, the master groups do not share inodes.
     pub async fn rename(&self, src: &Path, dst: &Path) -> FsResult<bool> {
+        self.check_same_namespace(src, dst)?;
         let header = RenameRequest {
             src: src.encode(),
             dst: dst.encode(),
             flags: RenameFlags::empty().value(),
         };
 
-        let rep_header: RenameResponse = self.rpc(RpcCode::Rename, header).await?;
+        let rep_header: RenameResponse = self.route(src).rpc(RpcCode::Rename, header).await?;
         Ok(rep_header.result)
     }
 
+    // List all the children, a large directory is fetched page by page.
     pub async fn list_status(&self, path: &Path) -> FsResult<Vec<FileStatus>> {
+        let page_size = self.conf().client.list_status_page_size;
+        let (mut res, mut has_more) = self.list_status_page(path, None, page_size).await?;
+
+        while has_more {
+            let start_after = match res.last() {
+                Some(v) => v.name.clone(),
+                None => break,
+            };
+            let (list, more) = self
+                .list_status_page(path, Some(&start_after), page_size)
+                .await?;
+            has_more = more && !list.is_empty();
+            res.extend(list);
+        }
+
+        Ok(res)
+    }
+
+    // List at most limit children with names after start_after, a limit of 0 means no limit.
+    // Returns the statuses and whether there are more children.
+    pub async fn list_status_page(
+        &self,
+        path: &Path,
+        start_after: Option<&str>,
+        limit: i32,
+    ) -> FsResult<(Vec<FileStatus>, bool)> {
         let header = ListStatusRequest {
             path: path.encode(),
             need_location: false,
+            follower_read: self.follower_read(),
+            start_after: start_after.map(|x| x.to_string()),
+            limit: Some(limit),
+        };
+
+        let rep_header: ListStatusResponse = self
+            .route(path)
+            .read_rpc(RpcCode::ListStatus, header)
+            .await?;
+
+        let mut res: Vec<FileStatus> = rep_header
+            .statuses
+            .into_iter()
+            .map(ProtoUtils::file_status_from_pb)
+            .collect();
+        let has_more = rep_header.has_more.unwrap_or(false);
+
+        if self.conf().federation.is_enabled() && path.is_cv() {
+            self.add_mount_children(path, start_after, has_more, &mut res)
+                .await?;
+        }
+
+        Ok((res, has_more))
+    }
+
+    // Add the children of the dir that lead to mount points of other namespaces to a page.
+    // A child is added to the page that covers its name, so that paging still works.
+    async fn add_mount_children(
+        &self,
+        path: &Path,
+        start_after: Option<&str>,
+        has_more: bool,
+        res: &mut Vec<FileStatus>,
+    ) -> FsResult<()> {
+        let last = res.last().map(|x| x.name.clone());
+        let mut added = false;
+        for name in self.conf().federation.mount_children(path.path()) {
+            if start_after.is_some_and(|x| name.as_str() <= x)
+                || (has_more && last.as_ref().is_some_and(|x| &name > x))
+                || res.iter().any(|x| x.name == name)
+            {
+                continue;
+            }
+
+            let child = Path::from_str(format!("{}/{}", path.path().trim_end_matches('/'), name))?;
+            let status = match self.file_status(&child).await {
+                Ok(v) => v,
+                // The parent dirs of a mount point may not exist in any namespace.
+                Err(FsError::FileNotFound(_)) => {
+                    let mut status = FileStatus::with_name(0, name, true);
+                    status.path = child.path().to_string();
+                    status
+                }
+                Err(e) => return Err(e),
+            };
+            res.push(status);
+            added = true;
+        }
+
+        if added {
+            res.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        Ok(())
+    }
+
+    // The recursive summary of the path, computed on the master.
+    pub async fn content_summary(&self, path: &Path) -> FsResult<ContentSummary> {
+        let header = GetContentSummaryRequest {
+            path: path.encode(),
+            follower_read: self.follower_read(),
         };
 
-        let rep_header: ListStatusResponse = self.rpc(RpcCode::ListStatus, header).await?;
+        let rep_header: GetContentSummaryResponse = self
+            .route(path)
+            .read_rpc(RpcCode::GetContentSummary, header)
+            .await?;
+        Ok(ProtoUtils::content_summary_from_pb(rep_header.summary))
+    }
+
+    // Find at most limit entries under the path after start_after, in depth-first name order.
+    // Returns the statuses and whether there are more entries.
+    pub async fn find_page(
+        &self,
+        path: &Path,
+        opts: &FindOpts,
+        start_after: Option<&str>,
+        limit: i32,
+    ) -> FsResult<(Vec<FileStatus>, bool)> {
+        let header = FindRequest {
+            path: path.encode(),
+            opts: ProtoUtils::find_opts_to_pb(opts.clone()),
+            start_after: start_after.map(|x| x.to_string()),
+            limit,
+            follower_read: self.follower_read(),
+        };
 
+        let rep_header: FindResponse = self.route(path).read_rpc(RpcCode::Find, header).await?;
         let res = rep_header
             .statuses
             .into_iter()
             .map(ProtoUtils::file_status_from_pb)
             .collect();
 
-        Ok(res)
+        Ok((res, rep_header.has_more))
     }
 
     pub async fn list_status_bytes(&self, path: &Path) -> FsResult<BytesMut> {
         let header = ListStatusRequest {
             path: path.encode(),
             need_location: false,
+            follower_read: self.follower_read(),
+            start_after: None,
+            limit: None,
         };
 
-        self.rpc_bytes(RpcCode::ListStatus, header).await
+        self.route(path)
+            .read_rpc_bytes(RpcCode::ListStatus, header)
+            .await
     }
 
     pub async fn list_files(&self, path: &Path) -> FsResult<Vec<FileStatus>> {
@@ -247,7 +476,7 @@ impl FsClient {
             last_block: last_block.map(ProtoUtils::extend_block_to_pb),
         };
 
-        let rep_header = self.rpc(RpcCode::AddBlock, header).await?;
+        let rep_header = self.route(path).rpc(RpcCode::AddBlock, header).await?;
         let locate_block = ProtoUtils::located_block_from_pb(rep_header);
         Ok(locate_block)
     }
@@ -269,10 +498,11 @@ impl FsClient {
             })
             .collect();
 
+        let client = self.route_batch(pb_requests.iter().map(|x| &x.path))?;
         let header = AddBlocksBatchRequest {
             requests: pb_requests,
         };
-        let rep: AddBlocksBatchResponse = self.rpc(RpcCode::AddBlocksBatch, header).await?;
+        let rep: AddBlocksBatchResponse = client.rpc(RpcCode::AddBlocksBatch, header).await?;
         Ok(rep
             .blocks
             .into_iter()
@@ -300,7 +530,7 @@ impl FsClient {
             only_flush,
         };
 
-        let rep: CompleteFileResponse = self.rpc(RpcCode::CompleteFile, header).await?;
+        let rep: CompleteFileResponse = self.route(path).rpc(RpcCode::CompleteFile, header).await?;
 
         Ok(rep.file_blocks.map(ProtoUtils::file_blocks_from_pb))
     }
@@ -326,37 +556,151 @@ impl FsClient {
             })
             .collect();
 
+        let client = self.route_batch(pb_requests.iter().map(|x| &x.path))?;
         let header = CompleteFilesBatchRequest {
             requests: pb_requests,
         };
 
-        let rep: CompleteFilesBatchResponse = self.rpc(RpcCode::CompleteFilesBatch, header).await?;
+        let rep: CompleteFilesBatchResponse =
+            client.rpc(RpcCode::CompleteFilesBatch, header).await?;
         Ok(rep.results)
     }
 
     pub async fn get_block_locations(&self, path: &Path) -> FsResult<FileBlocks> {
         let header = GetBlockLocationsRequest {
             path: path.encode(),
+            follower_read: self.follower_read(),
         };
 
-        let rep: GetBlockLocationsResponse = self.rpc(RpcCode::GetBlockLocations, header).await?;
+        let rep: GetBlockLocationsResponse = self
+            .route(path)
+            .read_rpc(RpcCode::GetBlockLocations, header)
+            .await?;
         let res = ProtoUtils::file_blocks_from_pb(rep.blocks);
 
         Ok(res)
     }
 
+    // With federation, the capacity, inodes and workers of all namespaces are summed up.
     pub async fn get_master_info(&self) -> FsResult<MasterInfo> {
+        if self.conf().federation.is_enabled() {
+            let mut namespaces = self.get_namespaces_info().await?.into_iter();
+            let mut res = namespaces.next().map(|x| x.1).unwrap_or_default();
+            for (_, info) in namespaces {
+                res.merge(&info);
+            }
+            return Ok(res);
+        }
+
         let header = GetMasterInfoRequest::default();
         let rep: GetMasterInfoResponse = self.rpc(RpcCode::GetMasterInfo, header).await?;
         let res = ProtoUtils::master_info_from_pb(rep);
         Ok(res)
     }
 
+    // Get the read index from the leader, see LeaderReadIndexRequest.
+    pub async fn leader_read_index(&self) -> FsResult<u64> {
+        let header = LeaderReadIndexRequest::default();
+        let rep: LeaderReadIndexResponse = self.rpc(RpcCode::LeaderReadIndex, header).await?;
+        Ok(rep.index)
+    }
+
     pub async fn get_master_info_bytes(&self) -> FsResult<BytesMut> {
         let header = GetMasterInfoRequest::default();
         self.rpc_bytes(RpcCode::GetMasterInfo, header).await
     }
 
+    // Add a master to the raft group, addr is its journal address.
+    // An observer replicates the journal but is never promoted to a voter.
+    pub async fn add_master(
+        &self,
+        id: u64,
+        addr: &InetAddr,
+        rpc_port: Option<u16>,
+        observer: bool,
+    ) -> FsResult<()> {
+        let header = AddMasterRequest {
+            node_id: id,
+            hostname: addr.hostname.clone(),
+            port: addr.port as u32,
+            observer: Some(observer),
+            rpc_port: rpc_port.map(|x| x as u32),
+        };
+        let _: AddMasterResponse = self.rpc(RpcCode::AddMaster, header).await?;
+        Ok(())
+    }
+
+    pub async fn remove_master(&self, id: u64) -> FsResult<()> {
+        let header = RemoveMasterRequest { node_id: id };
+        let _: RemoveMasterResponse = self.rpc(RpcCode::RemoveMaster, header).await?;
+        Ok(())
+    }
+
+    pub async fn transfer_master_leader(&self, id: u64) -> FsResult<()> {
+        let header = TransferMasterLeaderRequest { node_id: id };
+        let _: TransferMasterLeaderResponse =
+            self.rpc(RpcCode::TransferMasterLeader, header).await?;
+        Ok(())
+    }
+
+    pub async fn list_masters(&self) -> FsResult<MasterGroup> {
+        let header = ListMastersRequest::default();
+        let rep: ListMastersResponse = self.rpc(RpcCode::ListMasters, header).await?;
+        Ok(ProtoUtils::master_group_from_pb(rep))
+    }
+
+    // Connect to the masters added to the group after the client started,
+    // returns the number of masters discovered.
+    pub async fn refresh_masters(&self) -> FsResult<usize> {
+        let group = self.list_masters().await?;
+        let mut added = 0;
+        for master in group.masters {
+            let addr = InetAddr::new(master.hostname, master.rpc_port);
+            if self.connector.add_addr(addr)? {
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    // Check a page of the files under the path on the master, it always runs on the leader.
+    pub async fn fsck(&self, path: &Path, opts: FsckOpts) -> FsResult<FsckReport> {
+        let header = FsckRequest {
+            path: path.encode(),
+            action: opts.action.into(),
+            check_orphans: opts.check_orphans,
+            start_after: opts.start_after,
+            limit: Some(opts.limit as u32),
+        };
+        let rep: FsckResponse = self.route(path).rpc(RpcCode::Fsck, header).await?;
+        Ok(ProtoUtils::fsck_report_from_pb(rep))
+    }
+
+    // Start a metadata backup on the leader in the background, unless one is running.
+    pub async fn backup_meta(&self) -> FsResult<MetaBackupStatus> {
+        let header = BackupMetaRequest {};
+        let rep: BackupMetaResponse = self.rpc(RpcCode::BackupMeta, header).await?;
+        Ok(ProtoUtils::meta_backup_status_from_pb(rep.status))
+    }
+
+    pub async fn meta_backup_status(&self) -> FsResult<MetaBackupStatus> {
+        let header = GetMetaBackupStatusRequest {};
+        let rep: GetMetaBackupStatusResponse =
+            self.rpc(RpcCode::GetMetaBackupStatus, header).await?;
+        Ok(ProtoUtils::meta_backup_status_from_pb(rep.status))
+    }
+
+    // List the metadata backups in the backup directory, sorted from oldest to newest.
+    pub async fn list_meta_backups(&self) -> FsResult<Vec<MetaBackupInfo>> {
+        let header = ListMetaBackupsRequest {};
+        let rep: ListMetaBackupsResponse = self.rpc(RpcCode::ListMetaBackups, header).await?;
+        Ok(rep
+            .backups
+            .into_iter()
+            .map(ProtoUtils::meta_backup_info_from_pb)
+            .collect())
+    }
+
     pub async fn mount(
         &self,
         ufs_path: &Path,
@@ -369,7 +713,7 @@ impl FsClient {
             mount_options: ProtoUtils::mount_options_to_pb(opts),
         };
 
-        let rep: MountResponse = self.rpc(RpcCode::Mount, req).await?;
+        let rep: MountResponse = self.route(cv_path).rpc(RpcCode::Mount, req).await?;
         Ok(rep)
     }
 
@@ -378,17 +722,31 @@ impl FsClient {
             cv_path: cv_path.encode(),
         };
 
-        let rep: UnMountResponse = self.rpc(RpcCode::UnMount, req).await?;
+        let rep: UnMountResponse = self.route(cv_path).rpc(RpcCode::UnMount, req).await?;
         Ok(rep)
     }
 
+    // A ufs path may be mounted in any namespace, they are searched in order.
     pub async fn get_mount_info(&self, path: &Path) -> FsResult<Option<MountInfo>> {
         let req = GetMountInfoRequest {
             path: path.encode_uri(),
         };
 
-        let rep: GetMountInfoResponse = self.rpc(RpcCode::GetMountInfo, req).await?;
-        Ok(rep.mount_info.map(ProtoUtils::mount_info_from_pb))
+        if path.is_cv() {
+            let rep: GetMountInfoResponse =
+                self.route(path).rpc(RpcCode::GetMountInfo, req).await?;
+            return Ok(rep.mount_info.map(ProtoUtils::mount_info_from_pb));
+        }
+
+        for (_, connector) in self.context.all_namespaces() {
+            let rep: GetMountInfoResponse = connector
+                .proto_rpc::<_, _, FsError>(RpcCode::GetMountInfo, req.clone())
+                .await?;
+            if let Some(info) = rep.mount_info {
+                return Ok(Some(ProtoUtils::mount_info_from_pb(info)));
+            }
+        }
+        Ok(None)
     }
 
     pub async fn get_mount_info_bytes(&self, path: &Path) -> FsResult<BytesMut> {
@@ -396,7 +754,10 @@ impl FsClient {
             path: path.encode_uri(),
         };
 
-        let bytes = self.rpc_bytes(RpcCode::GetMountInfo, req).await?;
+        let bytes = self
+            .route(path)
+            .rpc_bytes(RpcCode::GetMountInfo, req)
+            .await?;
         Ok(bytes)
     }
 
@@ -415,10 +776,28 @@ impl FsClient {
         Ok(conf)
     }
 
+    // The ufs mount tables of all namespaces.
     pub async fn get_mount_table(&self) -> FsResult<GetMountTableResponse> {
-        let req = GetMountTableRequest {};
-        let rep: GetMountTableResponse = self.rpc(RpcCode::GetMountTable, req).await?;
-        Ok(rep)
+        let mut res = GetMountTableResponse::default();
+        for (_, connector) in self.context.all_namespaces() {
+            let rep: GetMountTableResponse = connector
+                .proto_rpc::<_, _, FsError>(RpcCode::GetMountTable, GetMountTableRequest {})
+                .await?;
+            res.mount_table.extend(rep.mount_table);
+        }
+        Ok(res)
+    }
+
+    // The master info of every namespace, the default namespace comes first.
+    pub async fn get_namespaces_info(&self) -> FsResult<Vec<(String, MasterInfo)>> {
+        let mut res = vec![];
+        for (name, connector) in self.context.all_namespaces() {
+            let rep: GetMasterInfoResponse = connector
+                .proto_rpc::<_, _, FsError>(RpcCode::GetMasterInfo, GetMasterInfoRequest::default())
+                .await?;
+            res.push((name.to_string(), ProtoUtils::master_info_from_pb(rep)));
+        }
+        Ok(res)
     }
 
     pub async fn set_attr(&self, path: &Path, opts: SetAttrOpts) -> FsResult<FileStatus> {
@@ -426,10 +805,45 @@ impl FsClient {
             path: path.encode(),
             opts: ProtoUtils::set_attr_opts_to_pb(opts),
         };
-        let rep: SetAttrResponse = self.rpc(RpcCode::SetAttr, req).await?;
+        let rep: SetAttrResponse = self.route(path).rpc(RpcCode::SetAttr, req).await?;
         Ok(ProtoUtils::file_status_from_pb(rep.status))
     }
 
+    pub async fn set_dir_policy(&self, path: &Path, policy: DirPolicy) -> FsResult<FileStatus> {
+        let opts = SetAttrOptsBuilder::new().dir_policy(policy).build();
+        self.set_attr(path, opts).await
+    }
+
+    // Returns the policy set on the directory and the effective policy of its new children.
+    pub async fn get_dir_policy(&self, path: &Path) -> FsResult<(DirPolicy, DirPolicy)> {
+        let req = GetDirPolicyRequest {
+            path: path.encode(),
+        };
+        let rep: GetDirPolicyResponse = self.route(path).rpc(RpcCode::GetDirPolicy, req).await?;
+        Ok((
+            ProtoUtils::dir_policy_from_pb(rep.policy),
+            ProtoUtils::dir_policy_from_pb(rep.effective),
+        ))
+    }
+
+    // Change the storage type of existing files, returns the id of the job moving the blocks,
+    // the number of blocks and their total size.
+    pub async fn set_storage_policy(
+        &self,
+        path: &Path,
+        storage_type: StorageType,
+        recursive: bool,
+    ) -> FsResult<(String, i64, i64)> {
+        let req = SetStoragePolicyRequest {
+            path: path.encode(),
+            storage_type: storage_type.into(),
+            recursive,
+        };
+        let rep: SetStoragePolicyResponse =
+            self.route(path).rpc(RpcCode::SetStoragePolicy, req).await?;
+        Ok((rep.job_id, rep.total_blocks, rep.total_size))
+    }
+
     pub async fn symlink(&self, target: &str, link: &Path, force: bool) -> FsResult<()> {
         let req = SymlinkRequest {
             target: target.to_string(),
@@ -437,7 +851,7 @@ impl FsClient {
             force,
             mode: ClientConf::DEFAULT_FILE_SYSTEM_MODE,
         };
-        let _: SymlinkResponse = self.rpc(RpcCode::Symlink, req).await?;
+        let _: SymlinkResponse = self.route(link).rpc(RpcCode::Symlink, req).await?;
         Ok(())
     }
 
@@ -451,16 +865,21 @@ impl FsClient {
             source: "".to_string(),
             metrics: ProtoUtils::metrics_report_to_pb(metrics),
         };
-        let _: MetricsReportResponse = self.rpc(RpcCode::MetricsReport, req).await?;
+
+        // The client uses the masters of all namespaces, each of them gets the metrics.
+        for client in self.namespace_clients() {
+            let _: MetricsReportResponse = client.rpc(RpcCode::MetricsReport, req.clone()).await?;
+        }
         Ok(())
     }
 
     pub async fn link(&self, src_path: &Path, dst_path: &Path) -> FsResult<()> {
+        self.check_same_namespace(src_path, dst_path)?;
         let req = LinkRequest {
             src_path: src_path.encode(),
             dst_path: dst_path.encode(),
         };
-        let _: LinkResponse = self.rpc(RpcCode::Link, req).await?;
+        let _: LinkResponse = self.route(src_path).rpc(RpcCode::Link, req).await?;
         Ok(())
     }
 
@@ -470,7 +889,7 @@ impl FsClient {
             opts: ProtoUtils::file_alloc_opts_to_pb(alloc_opts),
         };
 
-        let rep: FileResizeResponse = self.rpc(RpcCode::ResizeFile, req).await?;
+        let rep: FileResizeResponse = self.route(path).rpc(RpcCode::ResizeFile, req).await?;
         Ok(ProtoUtils::file_blocks_from_pb(rep.file_blocks))
     }
 
@@ -482,16 +901,54 @@ impl FsClient {
             client_address: self.context.client_addr_pb(),
         };
 
-        let rep: AssignWorkerResponse = self.rpc(RpcCode::AssignWorker, req).await?;
+        let rep: AssignWorkerResponse = self.route(path).rpc(RpcCode::AssignWorker, req).await?;
         Ok(ProtoUtils::located_block_from_pb(rep.block))
     }
 
+    pub async fn request_replacement_worker(
+        &self,
+        path: &Path,
+        block_id: i64,
+        exclude_workers: Vec<u32>,
+    ) -> FsResult<WorkerAddress> {
+        let req = RequestReplacementWorkerRequest {
+            block_id,
+            exclude_workers,
+        };
+
+        let rep: RequestReplacementWorkerResponse = self
+            .route(path)
+            .rpc(RpcCode::RequestReplacementWorker, req)
+            .await?;
+        Ok(ProtoUtils::worker_address_from_pb(&rep.worker))
+    }
+
+    // The blocks belong to the file of the path.
+    pub async fn report_under_replicated_blocks(
+        &self,
+        path: &Path,
+        block_ids: Vec<i64>,
+    ) -> FsResult<()> {
+        let req = ReportUnderReplicatedBlocksRequest { block_ids };
+        let rep: ReportUnderReplicatedBlocksResponse = self
+            .route(path)
+            .rpc(RpcCode::ReportUnderReplicatedBlocks, req)
+            .await?;
+        if !rep.success {
+            return err_box!(
+                "Failed to report under-replicated blocks: {}",
+                rep.message.unwrap_or_default()
+            );
+        }
+        Ok(())
+    }
+
     pub async fn get_lock(&self, path: &Path, lock: FileLock) -> FsResult<Option<FileLock>> {
         let req = GetLockRequest {
             path: path.encode(),
             lock: ProtoUtils::file_lock_to_pb(lock),
         };
-        let rep: GetLockResponse = self.rpc(RpcCode::GetLock, req).await?;
+        let rep: GetLockResponse = self.route(path).rpc(RpcCode::GetLock, req).await?;
         Ok(rep.conflict.map(ProtoUtils::file_lock_from_pb))
     }
 
@@ -500,7 +957,7 @@ impl FsClient {
             path: path.encode(),
             lock: ProtoUtils::file_lock_to_pb(lock),
         };
-        let rep: SetLockResponse = self.rpc(RpcCode::SetLock, req).await?;
+        let rep: SetLockResponse = self.route(path).rpc(RpcCode::SetLock, req).await?;
         Ok(rep.conflict.map(ProtoUtils::file_lock_from_pb))
     }
 
@@ -524,6 +981,47 @@ impl FsClient {
         }
     }
 
+    // Allow standby masters to serve the read if configured.
+    fn follower_read(&self) -> Option<FollowerReadProto> {
+        let conf = &self.context.conf.client;
+        if conf.read_consistency.is_follower_read() {
+            Some(FollowerReadProto {
+                consistency: conf.read_consistency.into(),
+                max_staleness_ms: conf.max_read_staleness.as_millis() as u64,
+            })
+        } else {
+            None
+        }
+    }
+
+    // Reads that standby masters can serve, they go to the leader unless follower reads are enabled.
+    pub async fn read_rpc<T, R>(&self, code: RpcCode, header: T) -> FsResult<R>
+    where
+        T: PMessage + Default,
+        R: PMessage + Default,
+    {
+        if self.context.conf.client.read_consistency.is_follower_read() {
+            self.connector
+                .proto_read_rpc::<T, R, FsError>(code, header)
+                .await
+        } else {
+            self.rpc(code, header).await
+        }
+    }
+
+    pub async fn read_rpc_bytes(&self, code: RpcCode, header: impl PMessage) -> FsResult<BytesMut> {
+        if !self.context.conf.client.read_consistency.is_follower_read() {
+            return self.rpc_bytes(code, header).await;
+        }
+
+        let msg = MessageBuilder::new_rpc(code).proto_header(header).build();
+        let msg = self.connector.read_rpc::<FsError>(msg).await?;
+        match msg.header {
+            None => Ok(BytesMut::new()),
+            Some(v) => Ok(v),
+        }
+    }
+
     pub fn rpc_blocking<T, R>(&self, code: RpcCode, header: T) -> FsResult<R>
     where
         T: PMessage + Default,
@@ -16,6 +16,7 @@ use crate::block::{BlockClient, BlockClientPool};
 use crate::file::CurvineFileSystem;
 use crate::ClientMetrics;
 use curvine_common::conf::ClusterConf;
+use curvine_common::fs::Path;
 use curvine_common::proto::ClientAddressProto;
 use curvine_common::state::{ClientAddress, WorkerAddress};
 use curvine_common::utils::ProtoUtils;
@@ -33,6 +34,7 @@ use orpc::runtime::{RpcRuntime, Runtime};
 use orpc::sys::CacheManager;
 use std::hash::BuildHasherDefault;
 use std::sync::Arc;
+use std::time::Instant;
 
 static CLIENT_METRICS: OnceCell<ClientMetrics> = OnceCell::new();
 
@@ -43,6 +45,8 @@ static CLIENT_METRICS: OnceCell<ClientMetrics> = OnceCell::new();
 pub struct FsContext {
     pub(crate) conf: ClusterConf,
     pub(crate) connector: Arc<ClusterConnector>,
+    // Connectors of the federated namespaces, in the order of conf.federation.namespaces.
+    pub(crate) namespaces: Vec<Arc<ClusterConnector>>,
     pub(crate) client_addr: ClientAddress,
     pub(crate) os_cache: CacheManager,
     pub(crate) failed_workers: Cache<u32, WorkerAddress, BuildHasherDefault<FxHasher>>,
@@ -73,6 +77,15 @@ impl FsContext {
             connector.add_node(node)?;
         }
 
+        let mut namespaces = Vec::with_capacity(conf.federation.namespaces.len());
+        for ns in &conf.federation.namespaces {
+            let ns_connector = ClusterConnector::with_rt(conf.client_rpc_conf(), rt.clone());
+            for node in ns.master_nodes() {
+                ns_connector.add_node(node)?;
+            }
+            namespaces.push(Arc::new(ns_connector));
+        }
+
         let os_cache = CacheManager::new(
             conf.client.enable_read_ahead,
             conf.client.read_ahead_len,
@@ -94,6 +107,7 @@ impl FsContext {
         let context = Self {
             conf,
             connector: Arc::new(connector),
+            namespaces,
             client_addr,
             os_cache,
             failed_workers: exclude_workers,
@@ -102,6 +116,35 @@ impl FsContext {
         Ok(context)
     }
 
+    // The connector of the master group that owns the path.
+    pub fn namespace_connector(&self, path: &Path) -> &Arc<ClusterConnector> {
+        if !path.is_cv() {
+            return &self.connector;
+        }
+        match self.conf.federation.resolve(path.path()) {
+            Some(index) => &self.namespaces[index],
+            None => &self.connector,
+        }
+    }
+
+    pub fn namespace_name(&self, path: &Path) -> &str {
+        let index = if path.is_cv() {
+            self.conf.federation.resolve(path.path())
+        } else {
+            None
+        };
+        self.conf.federation.namespace_name(index)
+    }
+
+    // All namespaces with their connectors, the default namespace comes first.
+    pub fn all_namespaces(&self) -> Vec<(&str, &Arc<ClusterConnector>)> {
+        let mut res = vec![(self.conf.federation.namespace_name(None), &self.connector)];
+        for (ns, connector) in self.conf.federation.namespaces.iter().zip(&self.namespaces) {
+            res.push((ns.name.as_str(), connector));
+        }
+        res
+    }
+
     pub fn clone_client_name(&self) -> String {
         self.client_addr.client_name.clone()
     }
@@ -207,14 +250,25 @@ impl FsContext {
     pub fn start_clean_task(fs: CurvineFileSystem, pool: Arc<BlockClientPool>) {
         let metric_report_enable = fs.conf().client.metric_report_enable;
         let interval = fs.conf().client.clean_task_interval;
+        let refresh_interval = fs.conf().client.master_refresh_interval;
 
         fs.clone_runtime().spawn(async move {
             let mut interval = tokio::time::interval(interval);
+            let mut last_refresh = Instant::now();
             loop {
                 interval.tick().await;
 
                 pool.clear_idle_conn();
 
+                if last_refresh.elapsed() >= refresh_interval {
+                    last_refresh = Instant::now();
+                    for client in fs.fs_client().namespace_clients() {
+                        if let Err(e) = client.refresh_masters().await {
+                            warn!("refresh masters: {}", e)
+                        }
+                    }
+                }
+
                 if metric_report_enable {
                     if let Err(e) = fs.metrics_report().await {
                         warn!("metrics report: {}", e)
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::file::{FsContext, FsReaderBuffer, ReadDetector};
+use crate::file::{FsContext, FsReaderBuffer, FsReaderPositional, ReadDetector};
 use curvine_common::fs::{Path, Reader};
 use curvine_common::state::{FileBlocks, FileStatus};
 use curvine_common::FsResult;
@@ -31,6 +31,7 @@ pub struct FsReader {
     pos: i64,
     len: i64,
     status: FileStatus,
+    positional: FsReaderPositional,
 }
 
 impl FsReader {
@@ -55,6 +56,7 @@ impl FsReader {
             conf.read_ahead_len
         );
 
+        let positional = FsReaderPositional::new(fs_context.clone(), file_blocks.clone());
         let inner = FsReaderBuffer::new(path, fs_context, file_blocks, read_detector)?;
         let reader = Self {
             inner,
@@ -63,9 +65,19 @@ impl FsReader {
             pos: 0,
             len,
             status,
+            positional,
         };
         Ok(reader)
     }
+
+    // Positional reads share the file blocks with this reader, but not its position.
+    pub fn positional(&self) -> FsReaderPositional {
+        self.positional.clone()
+    }
+
+    pub async fn pread(&self, pos: i64, buf: &mut [u8]) -> FsResult<usize> {
+        self.positional.pread(pos, buf).await
+    }
 }
 
 impl Reader for FsReader {
@@ -123,6 +135,7 @@ impl Reader for FsReader {
     }
 
     async fn complete(&mut self) -> FsResult<()> {
+        self.positional.complete().await?;
         self.inner.complete().await
     }
 }
@@ -19,6 +19,7 @@ use curvine_common::fs::Path;
 use curvine_common::state::FileBlocks;
 use curvine_common::FsResult;
 use log::error;
+use orpc::common::Span;
 use orpc::err_box;
 use orpc::runtime::RpcRuntime;
 use orpc::sync::channel::{AsyncChannel, AsyncReceiver, AsyncSender, CallChannel, CallSender};
@@ -187,13 +188,19 @@ impl FsReaderBuffer {
                 let (task_sender, task_receiver) = AsyncChannel::new(2).split();
                 let monitor = err_monitor.clone();
                 let parallel_id = reader.parallel_id();
+                let mut span = Span::current_child("client.read");
+                span.set_attr("path", &path);
+                span.set_attr("parallel_id", parallel_id);
 
                 rt.spawn(async move {
-                    let res = Self::read_future(chunk_sender, task_receiver, reader).await;
+                    let res = span
+                        .instrument(Self::read_future(chunk_sender, task_receiver, reader))
+                        .await;
                     match res {
                         Ok(_) => {}
                         Err(e) => {
                             error!("buffer read(parallel id {})error: {:?}", parallel_id, e);
+                            span.set_error(&e);
                             monitor.set_error(e);
                         }
                     }
@@ -0,0 +1,203 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::block::BlockReader;
+use crate::file::FsContext;
+use curvine_common::state::{FileBlocks, LocatedBlock, SearchFileBlocks};
+use curvine_common::FsResult;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use orpc::err_box;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+// A piece of a requested range located in a single block: (range index, block offset, buffer).
+type Piece<'a> = (usize, i64, &'a mut [u8]);
+
+// Positional reads that do not depend on the position of the stream reader.
+// Each requested range is split at block boundaries, the pieces of one block are read in order by
+// one BlockReader and different blocks are read concurrently.
+// Block readers are kept after a read and reused by later reads of the same block, so the small
+// reads of Parquet/ORC footers and column chunks do not open a block for every call.
+#[derive(Clone)]
+pub struct FsReaderPositional {
+    fs_context: Arc<FsContext>,
+    file_blocks: Arc<SearchFileBlocks>,
+    len: i64,
+    parallel: usize,
+
+    // Idle block readers, the least recently used one is closed when there are more than cache_limit.
+    cache_limit: usize,
+    cache_readers: Arc<Mutex<VecDeque<BlockReader>>>,
+}
+
+impl FsReaderPositional {
+    pub fn new(fs_context: Arc<FsContext>, file_blocks: FileBlocks) -> Self {
+        let len = file_blocks.status.len;
+        let parallel = fs_context.conf.client.max_read_parallel.max(1) as usize;
+        let cache_limit = fs_context.conf.client.max_cache_block_handles;
+        Self {
+            fs_context,
+            file_blocks: Arc::new(SearchFileBlocks::new(file_blocks)),
+            len,
+            parallel,
+            cache_limit,
+            cache_readers: Arc::new(Mutex::new(VecDeque::with_capacity(cache_limit + 1))),
+        }
+    }
+
+    pub fn len(&self) -> i64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The number of idle block readers kept for later reads.
+    pub fn cached_readers(&self) -> usize {
+        self.cache_readers.lock().unwrap().len()
+    }
+
+    // Read data starting at pos into the buffer.
+    // The read is shorter than the buffer only at the end of the file.
+    pub async fn pread(&self, pos: i64, buf: &mut [u8]) -> FsResult<usize> {
+        let mut res = self.read_ranges(vec![(pos, buf)]).await?;
+        Ok(res.pop().unwrap_or(0))
+    }
+
+    // Read a batch of ranges, returns the number of bytes read for each range in order.
+    pub async fn read_ranges(&self, ranges: Vec<(i64, &mut [u8])>) -> FsResult<Vec<usize>> {
+        let mut res = vec![0; ranges.len()];
+        let mut blocks: Vec<(LocatedBlock, Vec<Piece>)> = vec![];
+        let mut block_index = HashMap::new();
+        for (index, (pos, buf)) in ranges.into_iter().enumerate() {
+            for (block_off, loc, piece) in self.split(pos, buf)? {
+                let i = *block_index.entry(loc.block.id).or_insert_with(|| {
+                    blocks.push((loc, vec![]));
+                    blocks.len() - 1
+                });
+                blocks[i].1.push((index, block_off, piece));
+            }
+        }
+
+        let reads: Vec<_> = blocks
+            .into_iter()
+            .map(|(loc, pieces)| self.read_block(loc, pieces))
+            .collect();
+        let read_len: Vec<Vec<(usize, usize)>> = stream::iter(reads)
+            .buffer_unordered(self.parallel)
+            .try_collect()
+            .await?;
+
+        for (index, n) in read_len.into_iter().flatten() {
+            res[index] += n;
+        }
+        Ok(res)
+    }
+
+    // Close the cached block readers.
+    pub async fn complete(&self) -> FsResult<()> {
+        let readers: Vec<_> = self.cache_readers.lock().unwrap().drain(..).collect();
+        for mut reader in readers {
+            reader.complete().await?;
+        }
+        Ok(())
+    }
+
+    // Split a range into the pieces that are located in a single block.
+    fn split<'a>(
+        &self,
+        pos: i64,
+        buf: &'a mut [u8],
+    ) -> FsResult<Vec<(i64, LocatedBlock, &'a mut [u8])>> {
+        let mut pieces = vec![];
+        if pos < 0 {
+            return err_box!("Cannot read from negative offset {}", pos);
+        } else if pos >= self.len {
+            return Ok(pieces);
+        }
+
+        let read_len = (self.len - pos).min(buf.len() as i64) as usize;
+        let mut file_pos = pos;
+        let mut rest = &mut buf[..read_len];
+        while !rest.is_empty() {
+            let (block_off, loc) = self.file_blocks.get_read_block(file_pos)?;
+            let piece_len = (loc.block.len - block_off).min(rest.len() as i64) as usize;
+            let (piece, tail) = rest.split_at_mut(piece_len);
+
+            pieces.push((block_off, loc, piece));
+            file_pos += piece_len as i64;
+            rest = tail;
+        }
+
+        Ok(pieces)
+    }
+
+    // Read the pieces of a block in offset order with a cached or a new block reader.
+    async fn read_block(
+        &self,
+        loc: LocatedBlock,
+        mut pieces: Vec<Piece<'_>>,
+    ) -> FsResult<Vec<(usize, usize)>> {
+        pieces.sort_by_key(|x| x.1);
+        let mut reader = match self.take_reader(loc.block.id) {
+            Some(v) => v,
+            None => BlockReader::new(self.fs_context.clone(), loc, pieces[0].1).await?,
+        };
+
+        let mut res = Vec::with_capacity(pieces.len());
+        for (index, block_off, buf) in pieces {
+            if reader.pos() != block_off {
+                reader.seek(block_off)?;
+            }
+            let mut off = 0;
+            while off < buf.len() {
+                let chunk = reader.read().await?;
+                if chunk.is_empty() {
+                    break;
+                }
+                let n = chunk.len().min(buf.len() - off);
+                buf[off..off + n].copy_from_slice(&chunk.as_slice()[..n]);
+                off += n;
+            }
+            res.push((index, off));
+        }
+
+        self.release_reader(reader).await?;
+        Ok(res)
+    }
+
+    fn take_reader(&self, block_id: i64) -> Option<BlockReader> {
+        let mut readers = self.cache_readers.lock().unwrap();
+        let index = readers.iter().rposition(|x| x.block_id() == block_id)?;
+        readers.remove(index)
+    }
+
+    async fn release_reader(&self, reader: BlockReader) -> FsResult<()> {
+        let removed = {
+            let mut readers = self.cache_readers.lock().unwrap();
+            readers.push_back(reader);
+            if readers.len() > self.cache_limit {
+                readers.pop_front()
+            } else {
+                None
+            }
+        };
+
+        if let Some(mut removed) = removed {
+            removed.complete().await?;
+        }
+        Ok(())
+    }
+}
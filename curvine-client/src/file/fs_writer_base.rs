@@ -19,6 +19,7 @@ use curvine_common::state::{FileAllocOpts, FileBlocks, FileStatus, WriteFileBloc
 use curvine_common::FsResult;
 use fxhash::FxHasher;
 use linked_hash_map::LinkedHashMap;
+use log::warn;
 use orpc::runtime::{RpcRuntime, Runtime};
 use orpc::sys::DataSlice;
 use orpc::{err_box, try_option_mut};
@@ -37,6 +38,10 @@ pub struct FsWriterBase {
 
     cache_limit: usize,
     cache_writers: LinkedHashMap<i64, BlockWriter, BuildHasherDefault<FxHasher>>,
+
+    // Blocks completed with fewer replicas than allocated after a pipeline recovery,
+    // they are reported to the master once committed.
+    under_replicated: Vec<i64>,
 }
 
 impl FsWriterBase {
@@ -60,6 +65,7 @@ impl FsWriterBase {
             cur_writer: None,
             cache_limit,
             cache_writers,
+            under_replicated: vec![],
         }
     }
 
@@ -71,6 +77,10 @@ impl FsWriterBase {
         &self.file_blocks.status
     }
 
+    fn block_size(&self) -> i64 {
+        self.file_blocks.status.block_size
+    }
+
     pub fn path_str(&self) -> &str {
         self.path.path()
     }
@@ -168,7 +178,11 @@ impl FsWriterBase {
                 writer.flush().await?;
                 writer.to_commit_block()
             } else {
-                writer.complete().await?
+                let commit_block = writer.complete().await?;
+                if writer.is_degraded() {
+                    self.under_replicated.push(writer.block_id());
+                }
+                commit_block
             };
 
             self.file_blocks.add_commit(commit_block)?;
@@ -179,9 +193,38 @@ impl FsWriterBase {
         }
 
         let commits_blocks = self.file_blocks.take_commit_blocks();
-        self.fs_client
+        let res = self
+            .fs_client
             .complete_file(&self.path, self.len, commits_blocks, only_flush)
+            .await?;
+        self.report_under_replicated().await;
+        Ok(res)
+    }
+
+    async fn report_under_replicated(&mut self) {
+        if self.under_replicated.is_empty() {
+            return;
+        }
+
+        let blocks = mem::take(&mut self.under_replicated);
+        if let Err(e) = self
+            .fs_client
+            .report_under_replicated_blocks(&self.path, blocks.clone())
             .await
+        {
+            warn!(
+                "Failed to report under-replicated blocks {:?} of {}: {}",
+                blocks, self.path, e
+            );
+        }
+    }
+
+    async fn complete_writer(&mut self, mut writer: BlockWriter) -> FsResult<()> {
+        let commit_block = writer.complete().await?;
+        if writer.is_degraded() {
+            self.under_replicated.push(writer.block_id());
+        }
+        self.file_blocks.add_commit(commit_block)
     }
 
     async fn get_writer(&mut self) -> FsResult<&mut BlockWriter> {
@@ -213,7 +256,14 @@ impl FsWriterBase {
                                 } else {
                                     lb
                                 };
-                                BlockWriter::new(self.fs_context.clone(), lb, off).await?
+                                BlockWriter::new(
+                                    &self.fs_client,
+                                    &self.path,
+                                    lb,
+                                    off,
+                                    self.block_size(),
+                                )
+                                .await?
                             }
                         };
 
@@ -230,8 +280,15 @@ impl FsWriterBase {
                             .add_block(&self.path, commit_blocks, self.len, last_block)
                             .await?;
                         self.file_blocks.add_block(lb.clone())?;
-                        let writer =
-                            BlockWriter::new(self.fs_context.clone(), lb.clone(), 0).await?;
+                        self.report_under_replicated().await;
+                        let writer = BlockWriter::new(
+                            &self.fs_client,
+                            &self.path,
+                            lb.clone(),
+                            0,
+                            self.block_size(),
+                        )
+                        .await?;
 
                         self.cur_writer.replace(writer);
                     }
@@ -269,22 +326,20 @@ impl FsWriterBase {
     }
 
     async fn update_writer(&mut self, cur: Option<BlockWriter>, cache: bool) -> FsResult<()> {
-        let mut old = match mem::replace(&mut self.cur_writer, cur) {
+        let old = match mem::replace(&mut self.cur_writer, cur) {
             Some(v) => v,
             None => return Ok(()),
         };
 
         if cache && self.cache_limit > 0 {
             if self.cache_writers.len() >= self.cache_limit {
-                if let Some((_, mut removed)) = self.cache_writers.pop_front() {
-                    let commit_blocks = removed.complete().await?;
-                    self.file_blocks.add_commit(commit_blocks)?;
+                if let Some((_, removed)) = self.cache_writers.pop_front() {
+                    self.complete_writer(removed).await?;
                 }
             }
             self.cache_writers.insert(old.block_id(), old);
         } else {
-            let commit_blocks = old.complete().await?;
-            self.file_blocks.add_commit(commit_blocks)?;
+            self.complete_writer(old).await?;
         }
 
         Ok(())
@@ -332,9 +387,15 @@ impl FsWriterBase {
         // At most one such block exists.
         for lb in &mut file_blocks.block_locs {
             if lb.should_resize() {
-                let mut writer = BlockWriter::new(self.fs_context.clone(), lb.clone(), 0).await?;
-                let commit_block = writer.complete().await?;
-                self.file_blocks.add_commit(commit_block)?;
+                let writer = BlockWriter::new(
+                    &self.fs_client,
+                    &self.path,
+                    lb.clone(),
+                    0,
+                    self.block_size(),
+                )
+                .await?;
+                self.complete_writer(writer).await?;
             }
         }
 
@@ -18,6 +18,7 @@ use curvine_common::fs::Path;
 use curvine_common::state::{FileAllocOpts, FileBlocks, FileStatus};
 use curvine_common::FsResult;
 use log::error;
+use orpc::common::Span;
 use orpc::io::IOError;
 use orpc::runtime::RpcRuntime;
 use orpc::sync::channel::{AsyncChannel, AsyncReceiver, AsyncSender, CallChannel, CallSender};
@@ -120,13 +121,20 @@ impl FsWriterBuffer {
         let (task_sender, task_receiver) = AsyncChannel::new(2).split();
         let monitor = err_monitor.clone();
 
+        // The writer task continues the trace of the caller, so the worker spans join it.
+        let mut span = Span::current_child("client.write");
+        span.set_attr("path", &path);
+
         let rt = writer.fs_context().clone_runtime();
         rt.spawn(async move {
-            let res = Self::write_future(chunk_receiver, task_receiver, writer).await;
+            let res = span
+                .instrument(Self::write_future(chunk_receiver, task_receiver, writer))
+                .await;
             match res {
                 Ok(_) => {}
                 Err(e) => {
                     error!("buffer writer error: {:?}", e);
+                    span.set_error(&e);
                     monitor.set_error(e);
                 }
             }
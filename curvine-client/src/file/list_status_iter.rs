@@ -0,0 +1,134 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::file::FsClient;
+use curvine_common::fs::Path;
+use curvine_common::state::FileStatus;
+use curvine_common::FsResult;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Iterate the children of a directory in name order.
+/// The entries are fetched from the master page by page, so listing a directory with
+/// millions of children never loads them in a single response.
+pub struct ListStatusIter {
+    client: Option<Arc<FsClient>>,
+    path: Path,
+    page_size: i32,
+    start_after: Option<String>,
+    buf: VecDeque<FileStatus>,
+    has_more: bool,
+}
+
+impl ListStatusIter {
+    pub fn new(
+        client: Arc<FsClient>,
+        path: Path,
+        page_size: i32,
+        start_after: Option<String>,
+    ) -> Self {
+        Self {
+            client: Some(client),
+            path,
+            page_size: page_size.max(1),
+            start_after,
+            buf: VecDeque::new(),
+            has_more: true,
+        }
+    }
+
+    // Iterate a listing that was already loaded, e.g. from a ufs.
+    pub fn from_list(path: Path, mut list: Vec<FileStatus>, start_after: Option<String>) -> Self {
+        list.sort_by(|a, b| a.name.cmp(&b.name));
+        if let Some(name) = &start_after {
+            list.retain(|x| &x.name > name);
+        }
+
+        Self {
+            client: None,
+            path,
+            page_size: 0,
+            start_after,
+            buf: list.into(),
+            has_more: false,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // The name of the last entry returned, a new iterator starting after it continues the listing.
+    pub fn start_after(&self) -> Option<&str> {
+        self.start_after.as_deref()
+    }
+
+    pub async fn next(&mut self) -> FsResult<Option<FileStatus>> {
+        if self.buf.is_empty() && self.has_more {
+            self.fetch().await?;
+        }
+
+        let next = self.buf.pop_front();
+        if let Some(status) = &next {
+            self.start_after = Some(status.name.clone());
+        }
+        Ok(next)
+    }
+
+    // Returns at most max entries, an empty list means the listing is finished.
+    pub async fn next_batch(&mut self, max: usize) -> FsResult<Vec<FileStatus>> {
+        let mut res = Vec::with_capacity(max.min(self.page_size.max(1) as usize));
+        while res.len() < max {
+            match self.next().await? {
+                Some(v) => res.push(v),
+                None => break,
+            }
+        }
+        Ok(res)
+    }
+
+    // Whether there may be more entries.
+    pub fn has_next(&self) -> bool {
+        !self.buf.is_empty() || self.has_more
+    }
+
+    async fn fetch(&mut self) -> FsResult<()> {
+        let client = match &self.client {
+            Some(v) => v,
+            None => {
+                self.has_more = false;
+                return Ok(());
+            }
+        };
+
+        let (list, has_more) = client
+            .list_status_page(&self.path, self.start_after.as_deref(), self.page_size)
+            .await?;
+        self.has_more = has_more && !list.is_empty();
+        self.buf.extend(list);
+        Ok(())
+    }
+}
+
+impl Debug for ListStatusIter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ListStatusIter")
+            .field("path", &self.path)
+            .field("start_after", &self.start_after)
+            .field("buffered", &self.buf.len())
+            .field("has_more", &self.has_more)
+            .finish()
+    }
+}
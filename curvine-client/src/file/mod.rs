@@ -39,8 +39,14 @@ pub use self::fs_reader_buffer::FsReaderBuffer;
 mod fs_reader_parallel;
 pub use self::fs_reader_parallel::FsReaderParallel;
 
+mod fs_reader_positional;
+pub use self::fs_reader_positional::FsReaderPositional;
+
 mod fs_writer_buffer;
 pub use self::fs_writer_buffer::FsWriterBuffer;
 
 mod read_detector;
 pub use self::read_detector::*;
+
+mod list_status_iter;
+pub use self::list_status_iter::ListStatusIter;
@@ -16,7 +16,7 @@ use log::info;
 use std::sync::Arc;
 use tokio::time;
 
-use curvine_common::fs::RpcCode;
+use curvine_common::fs::{Path, RpcCode};
 use curvine_common::proto::{
     CancelJobRequest, CancelJobResponse, GetJobStatusRequest, GetJobStatusResponse,
     SubmitJobRequest, SubmitJobResponse, TaskReportRequest, TaskReportResponse,
@@ -28,6 +28,7 @@ use curvine_common::utils::{ProtoUtils, SerdeUtils};
 use curvine_common::FsResult;
 use orpc::common::TimeSpent;
 use orpc::err_box;
+use prost::Message as PMessage;
 
 use crate::file::{FsClient, FsContext};
 
@@ -54,12 +55,17 @@ impl JobMasterClient {
 
     // Submit loading task
     pub async fn submit_load_job(&self, command: LoadJobCommand) -> FsResult<LoadJobResult> {
+        // The job runs on the namespace that mounts the source path.
+        let client = self
+            .client
+            .route_ufs(&Path::from_str(&command.source_path)?)
+            .await?;
         let req = SubmitJobRequest {
             job_type: JobTaskType::Load.into(),
             job_command: SerdeUtils::serialize(&command)?,
         };
 
-        let rep: SubmitJobResponse = self.client.rpc(RpcCode::SubmitJob, req).await?;
+        let rep: SubmitJobResponse = client.rpc(RpcCode::SubmitJob, req).await?;
         Ok(LoadJobResult {
             job_id: rep.job_id,
             target_path: rep.target_path,
@@ -73,7 +79,7 @@ impl JobMasterClient {
             verbose: false,
         };
 
-        let status: GetJobStatusResponse = self.client.rpc(RpcCode::GetJobStatus, req).await?;
+        let status: GetJobStatusResponse = self.job_rpc(RpcCode::GetJobStatus, req).await?;
 
         Ok(JobStatus {
             job_id: status.job_id,
@@ -89,10 +95,29 @@ impl JobMasterClient {
         let req = CancelJobRequest {
             job_id: job_id.as_ref().to_string(),
         };
-        let _: CancelJobResponse = self.client.rpc(RpcCode::CancelJob, req).await?;
+        let _: CancelJobResponse = self.job_rpc(RpcCode::CancelJob, req).await?;
         Ok(())
     }
 
+    // The job id does not tell the namespace of the job, the namespaces are asked in turn
+    // until one of them knows the job.
+    async fn job_rpc<T, R>(&self, code: RpcCode, req: T) -> FsResult<R>
+    where
+        T: PMessage + Default + Clone,
+        R: PMessage + Default,
+    {
+        let mut clients = self.client.namespace_clients();
+        let last = clients
+            .pop()
+            .unwrap_or_else(|| self.client.as_ref().clone());
+        for client in clients {
+            if let Ok(rep) = client.rpc(code, req.clone()).await {
+                return Ok(rep);
+            }
+        }
+        last.rpc(code, req).await
+    }
+
     pub async fn report_task(
         &self,
         job_id: impl AsRef<str>,
@@ -12,18 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::file::{CurvineFileSystem, FsClient, FsContext, FsReader};
+use crate::file::{CurvineFileSystem, FsClient, FsContext, FsReader, ListStatusIter};
 use crate::rpc::JobMasterClient;
 use crate::unified::{CacheSyncWriter, MountCache, MountValue, UnifiedReader, UnifiedWriter};
 use crate::ClientMetrics;
 use bytes::BytesMut;
 use curvine_common::conf::ClusterConf;
 use curvine_common::error::FsError;
-use curvine_common::fs::{FileSystem, Path, Reader};
+use curvine_common::fs::{FileSystem, FindFilter, Path, Reader};
 use curvine_common::state::{
-    ConsistencyStrategy, CreateFileOpts, FileAllocOpts, FileLock, FileStatus, LoadJobCommand,
-    MasterInfo, MkdirOpts, MkdirOptsBuilder, MountInfo, MountOptions, OpenFlags, SetAttrOpts,
-    WriteType,
+    ConsistencyStrategy, ContentSummary, CreateFileOpts, FileAllocOpts, FileLock, FileStatus,
+    FindOpts, LoadJobCommand, MasterInfo, MkdirOpts, MkdirOptsBuilder, MountInfo, MountOptions,
+    OpenFlags, SetAttrOpts, StorageType, WriteType,
 };
 use curvine_common::utils::CommonUtils;
 use curvine_common::FsResult;
@@ -376,6 +376,128 @@ impl UnifiedFileSystem {
         }
     }
 
+    // Iterate the children of the directory in name order, starting after the given name.
+    // A ufs directory is listed in one call, the ufs has no pagination, so callers reading
+    // it page by page should keep the iterator instead of creating one for every page.
+    pub async fn list_status_iter(
+        &self,
+        path: &Path,
+        start_after: Option<String>,
+    ) -> FsResult<ListStatusIter> {
+        match self.get_mount(path).await? {
+            None => Ok(self.cv.list_status_iter(path, start_after)),
+            Some((ufs_path, mount)) => {
+                let list = mount.ufs.list_status(&ufs_path).await?;
+                Ok(ListStatusIter::from_list(path.clone(), list, start_after))
+            }
+        }
+    }
+
+    // The recursive summary of the path. It is computed by the master, a ufs directory is
+    // walked from the client.
+    pub async fn content_summary(&self, path: &Path) -> FsResult<ContentSummary> {
+        let (ufs_path, mount) = match self.get_mount(path).await? {
+            None => return self.cv.content_summary(path).await,
+            Some(v) => v,
+        };
+
+        let mut summary = ContentSummary::default();
+        let status = mount.ufs.get_status(&ufs_path).await?;
+        let mut stack = vec![status];
+        while let Some(status) = stack.pop() {
+            if !status.is_dir {
+                summary.add_file(status.len, status.len, StorageType::Ufs);
+                continue;
+            }
+
+            summary.add_dir();
+            let child_path = Path::from_str(&status.path)?;
+            stack.extend(mount.ufs.list_status(&child_path).await?);
+        }
+
+        Ok(summary)
+    }
+
+    // Find a page of the entries under the path, in depth-first name order. It is done by the
+    // master, a ufs directory is walked from the client and its entries have ufs paths,
+    // start_after is then the ufs path of the last entry of the previous page.
+    pub async fn find_page(
+        &self,
+        path: &Path,
+        opts: &FindOpts,
+        start_after: Option<&str>,
+        limit: i32,
+    ) -> FsResult<(Vec<FileStatus>, bool)> {
+        let (ufs_path, mount) = match self.get_mount(path).await? {
+            None => return self.cv.find_page(path, opts, start_after, limit).await,
+            Some(v) => v,
+        };
+
+        let filter = FindFilter::new(opts.clone());
+        let limit = if limit <= 0 {
+            usize::MAX
+        } else {
+            limit as usize
+        };
+        let root = mount.ufs.get_status(&ufs_path).await?;
+
+        // The names of start_after below the path, an entry is returned if its names are
+        // greater, which is the depth-first name order.
+        let after: Option<Vec<String>> = match start_after {
+            None => None,
+            Some(v) => {
+                let prefix = root.path.trim_end_matches('/');
+                let rest = if v.trim_end_matches('/') == prefix {
+                    Some("")
+                } else {
+                    v.strip_prefix(prefix).and_then(|x| x.strip_prefix('/'))
+                };
+                match rest {
+                    Some(rest) => Some(
+                        rest.split('/')
+                            .filter(|x| !x.is_empty())
+                            .map(String::from)
+                            .collect(),
+                    ),
+                    None => return err_box!("{} is not under {}", v, root.path),
+                }
+            }
+        };
+
+        let mut res = vec![];
+        let mut stack = vec![(root, vec![])];
+        while let Some((status, names)) = stack.pop() {
+            let is_new = after.as_ref().is_none_or(|x| &names > x);
+            if is_new && filter.matches_status(&status) {
+                if res.len() >= limit {
+                    return Ok((res, true));
+                }
+                res.push(status.clone());
+            }
+
+            if !status.is_dir || names.len() >= filter.max_depth() {
+                continue;
+            }
+            // Skip the directories returned before, unless they hold start_after.
+            if !is_new && !after.as_ref().is_some_and(|x| x.starts_with(&names)) {
+                continue;
+            }
+
+            let mut children = mount
+                .ufs
+                .list_status(&Path::from_str(&status.path)?)
+                .await?;
+            children.sort_by(|a, b| b.name.cmp(&a.name));
+            for child in children {
+                let mut child_names = names.clone();
+                child_names.push(child.name.clone());
+                stack.push((child, child_names));
+            }
+        }
+
+        Ok((res, false))
+    }
+
     pub async fn get_lock(&self, path: &Path, lock: FileLock) -> FsResult<Option<FileLock>> {
         match self.get_mount(path).await? {
             None => self.cv.get_lock(path, lock).await,
@@ -13,10 +13,11 @@
 // limitations under the License.
 
 use crate::conf::ClusterConf;
-use crate::state::{StorageType, TtlAction};
+use crate::state::{EcPolicy, ReadConsistency, StorageType, TtlAction};
 use orpc::client::ClientConf as RpcConf;
 use orpc::common::{ByteUnit, DurationUnit, Utils};
 use orpc::io::net::InetAddr;
+use orpc::message::CompressionType;
 use orpc::CommonResult;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -86,6 +87,27 @@ pub struct ClientConf {
     #[serde(alias = "ttl_action")]
     pub ttl_action_str: String,
 
+    // Erasure coding policy of new files, e.g. "RS-6-3-1024k". Empty means full replicas.
+    #[serde(skip)]
+    pub ec_policy: Option<EcPolicy>,
+    #[serde(alias = "ec_policy")]
+    pub ec_policy_str: String,
+
+    // Consistency of FileStatus, ListStatus, Exists and GetBlockLocations:
+    // "leader", "linearizable" or "bounded_staleness".
+    // The last two spread these reads across standby masters. Linearizable reads see every
+    // journaled write, the leader journals writes in batches of journal.writer_flush_batch_ms.
+    #[serde(skip)]
+    pub read_consistency: ReadConsistency,
+    #[serde(alias = "read_consistency")]
+    pub read_consistency_str: String,
+
+    // For bounded staleness reads, the max time since a standby master last heard from the leader.
+    #[serde(skip)]
+    pub max_read_staleness: Duration,
+    #[serde(alias = "max_read_staleness")]
+    pub max_read_staleness_str: String,
+
     /// Whether to enable automatic caching function
     /// When enabled, when the client reads files from external file systems (such as S3, OSS, etc.),
     /// will automatically submit a load request to the master and cache the file into curvine
@@ -113,6 +135,24 @@ pub struct ClientConf {
     // Whether to close the idle rpc connection.
     pub rpc_close_idle: bool,
 
+    // Rpc payload compression proposed to master and workers when connecting: "none", "lz4" or "zstd".
+    // Servers must be upgraded before clients enable it. Short-circuit reads and data sent with
+    // sendfile are never compressed.
+    #[serde(skip)]
+    pub rpc_compress_type: CompressionType,
+    #[serde(alias = "rpc_compress_type")]
+    pub rpc_compress_type_str: String,
+    // Messages smaller than this are sent uncompressed.
+    #[serde(skip)]
+    pub rpc_compress_min_size: usize,
+    #[serde(alias = "rpc_compress_min_size")]
+    pub rpc_compress_min_size_str: String,
+
+    // The user sent to the master and workers when connecting, the master limits the requests
+    // of each user when rate limiting is enabled, otherwise those of each host.
+    // Empty sends no user. Like compression, servers must be upgraded before clients set it.
+    pub user: String,
+
     //Configuration of timeout for a request.
     pub conn_timeout_ms: u64,
     pub rpc_timeout_ms: u64,
@@ -123,6 +163,10 @@ pub struct ClientConf {
     // After testing 3 connections, the best performance can be achieved, so the default value is 3.
     pub master_conn_pool_size: usize,
 
+    // The number of entries fetched by each list status request, large directories are listed
+    // page by page.
+    pub list_status_page_size: i32,
+
     // Whether to enable pre-reading
     pub enable_read_ahead: bool,
     // Default is 0, the value is read_chunk_size * read_chunk_num
@@ -162,6 +206,12 @@ pub struct ClientConf {
     #[serde(alias = "clean_task_interval")]
     pub clean_task_interval_str: String,
 
+    // Interval for discovering the masters added to the raft group, checked by the clean task.
+    #[serde(skip)]
+    pub master_refresh_interval: Duration,
+    #[serde(alias = "master_refresh_interval")]
+    pub master_refresh_interval_str: String,
+
     pub close_timeout_secs: u64,
 
     pub metadata_operation_buckets: Vec<f64>,
@@ -213,6 +263,10 @@ pub struct ClientConf {
 
     // Sequential read check threshold
     pub sequential_read_threshold: u64,
+
+    // When a replica worker fails while writing a block, drop it and continue writing
+    // to the remaining replicas, a replacement worker is requested from the master if possible.
+    pub enable_pipeline_recovery: bool,
 }
 
 impl ClientConf {
@@ -224,6 +278,8 @@ impl ClientConf {
 
     pub const DEFAULT_CLEAN_TASK_INTERVAL_STR: &'static str = "10s";
 
+    pub const DEFAULT_MASTER_REFRESH_INTERVAL_STR: &'static str = "60s";
+
     pub const DEFAULT_CLOSE_TIMEOUT_SECS: u64 = 5;
 
     pub fn init(&mut self) -> CommonResult<()> {
@@ -263,10 +319,25 @@ impl ClientConf {
         self.ttl_ms = DurationUnit::from_str(&self.ttl_ms_str)?.as_millis() as i64;
         self.ttl_action = TtlAction::try_from(self.ttl_action_str.as_str())?;
         self.storage_type = StorageType::try_from(self.storage_type_str.as_str())?;
+        self.ec_policy = if self.ec_policy_str.is_empty() {
+            None
+        } else {
+            Some(EcPolicy::try_from(self.ec_policy_str.as_str())?)
+        };
+        self.read_consistency = ReadConsistency::try_from(self.read_consistency_str.as_str())?;
+
+        self.rpc_compress_type = CompressionType::try_from(self.rpc_compress_type_str.as_str())?;
+        self.rpc_compress_min_size =
+            ByteUnit::from_str(&self.rpc_compress_min_size_str)?.as_byte() as usize;
+        self.max_read_staleness =
+            DurationUnit::from_str(&self.max_read_staleness_str)?.as_duration();
 
         self.clean_task_interval =
             DurationUnit::from_str(&self.clean_task_interval_str)?.as_duration();
 
+        self.master_refresh_interval =
+            DurationUnit::from_str(&self.master_refresh_interval_str)?.as_duration();
+
         self.sync_check_interval_min =
             DurationUnit::from_str(&self.sync_check_interval_min_str)?.as_duration();
         self.sync_check_interval_max =
@@ -297,6 +368,10 @@ impl ClientConf {
 
             close_idle: conf.rpc_close_idle,
 
+            compress_type: conf.rpc_compress_type,
+            compress_min_size: conf.rpc_compress_min_size,
+            user: (!conf.user.is_empty()).then(|| conf.user.clone()),
+
             conn_timeout_ms: conf.conn_timeout_ms,
             rpc_timeout_ms: conf.rpc_timeout_ms,
             data_timeout_ms: conf.data_timeout_ms,
@@ -345,6 +420,12 @@ impl Default for ClientConf {
             ttl_ms_str: "0".to_string(),
             ttl_action: TtlAction::None,
             ttl_action_str: "none".to_string(),
+            ec_policy: None,
+            ec_policy_str: "".to_string(),
+            read_consistency: ReadConsistency::Leader,
+            read_consistency_str: "leader".to_string(),
+            max_read_staleness: Duration::default(),
+            max_read_staleness_str: "5s".to_string(),
 
             auto_cache_enabled: false,
             auto_cache_ttl: "7d".to_string(),
@@ -359,11 +440,17 @@ impl Default for ClientConf {
             rpc_retry_max_sleep_ms: 10 * 1000,
 
             rpc_close_idle: true,
+            rpc_compress_type: CompressionType::None,
+            rpc_compress_type_str: "none".to_string(),
+            rpc_compress_min_size: 0,
+            rpc_compress_min_size_str: "4KB".to_string(),
+            user: "".to_string(),
             conn_timeout_ms: 30 * 1000,
             rpc_timeout_ms: 120 * 1000,
             data_timeout_ms: 120 * 1000,
             pipeline_timeout_ms: 120 * 1000,
             master_conn_pool_size: 1,
+            list_status_page_size: 1000,
 
             enable_read_ahead: true,
             read_ahead_len: 0,
@@ -387,6 +474,9 @@ impl Default for ClientConf {
             clean_task_interval: Default::default(),
             clean_task_interval_str: Self::DEFAULT_CLEAN_TASK_INTERVAL_STR.to_string(),
 
+            master_refresh_interval: Default::default(),
+            master_refresh_interval_str: Self::DEFAULT_MASTER_REFRESH_INTERVAL_STR.to_string(),
+
             close_timeout_secs: Self::DEFAULT_CLOSE_TIMEOUT_SECS,
 
             metadata_operation_buckets: vec![
@@ -418,6 +508,8 @@ impl Default for ClientConf {
             large_file_size_str: "10GB".to_string(),
             max_read_parallel: 8,
             sequential_read_threshold: 7,
+
+            enable_pipeline_recovery: true,
         };
 
         conf.init().unwrap();
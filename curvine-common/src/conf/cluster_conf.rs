@@ -13,12 +13,14 @@
 // limitations under the License.
 
 use crate::conf::CliConf;
-use crate::conf::{ClientConf, FuseConf, JobConf, JournalConf, MasterConf, WorkerConf};
+use crate::conf::{
+    ClientConf, FederationConf, FuseConf, JobConf, JournalConf, MasterConf, WorkerConf,
+};
 use crate::rocksdb::DBConf;
 use crate::version;
 use log::info;
 use orpc::client::{ClientConf as RpcConf, ClientFactory, SyncClient};
-use orpc::common::{LogConf, Utils};
+use orpc::common::{LogConf, TraceConf, Utils};
 use orpc::io::net::{InetAddr, NodeAddr};
 use orpc::io::retry::TimeBondedRetryBuilder;
 use orpc::server::ServerConf;
@@ -51,6 +53,9 @@ pub struct ClusterConf {
 
     pub log: LogConf,
 
+    // Distributed tracing shared by master, worker and fuse.
+    pub trace: TraceConf,
+
     pub client: ClientConf,
 
     pub fuse: FuseConf,
@@ -60,6 +65,9 @@ pub struct ClusterConf {
     pub job: JobConf,
 
     pub cli: CliConf,
+
+    // Master groups that own parts of the namespace, routed by the client.
+    pub federation: FederationConf,
 }
 
 impl ClusterConf {
@@ -75,6 +83,9 @@ impl ClusterConf {
     pub const ENV_CLIENT_HOSTNAME: &'static str = "CURVINE_CLIENT_HOSTNAME";
     pub const ENV_CONF_FILE: &'static str = "CURVINE_CONF_FILE";
 
+    // The first node id of the client connector of a master group, see client_master_nodes.
+    pub const CLIENT_MASTER_NODE_START_ID: u64 = 100;
+
     pub fn from<T: AsRef<str>>(path: T) -> CommonResult<Self> {
         let str = try_err!(read_to_string(path.as_ref()));
         let mut conf = try_err!(toml::from_str::<Self>(&str));
@@ -95,13 +106,23 @@ impl ClusterConf {
         }
 
         conf.master.init()?;
+        conf.worker.init()?;
+        conf.journal.init()?;
         conf.client.init()?;
         conf.fuse.init()?;
         conf.job.init()?;
+        conf.federation.init()?;
+
+        // The masters listen on the same rpc port unless journal_addrs says otherwise.
+        for peer in &mut conf.journal.journal_addrs {
+            if peer.service_port == 0 {
+                peer.service_port = conf.master.rpc_port;
+            }
+        }
 
         if conf.client.master_addrs.is_empty() {
             for peer in &mut conf.journal.journal_addrs {
-                let node = InetAddr::new(&peer.hostname, conf.master.rpc_port);
+                let node = InetAddr::new(&peer.hostname, peer.service_port);
                 conf.client.master_addrs.push(node);
             }
         }
@@ -142,6 +163,7 @@ impl ClusterConf {
         // master will automatically close the idle connection, and the customer service will automatically maintain a heartbeat.
         conf.close_idle = self.master.io_close_idle;
         conf.timeout_ms = self.master.io_timeout_ms();
+        conf.compress_codecs = self.master.rpc_compress_codecs();
         conf
     }
 
@@ -163,18 +185,25 @@ impl ClusterConf {
 
     // Get all master nodes
     pub fn master_nodes(&self) -> Vec<NodeAddr> {
-        let mut map = vec![];
-
-        let start = 100;
         if self.client.master_addrs.is_empty() {
-            map.push(NodeAddr::from_addr(start, self.master_addr()));
+            Self::client_master_nodes(&[self.master_addr()])
         } else {
-            for (index, addr) in self.client.master_addrs.iter().enumerate() {
-                let id = start + index as u64;
-                map.push(NodeAddr::from_addr(id, addr.clone()));
-            }
+            Self::client_master_nodes(&self.client.master_addrs)
         }
-        map
+    }
+
+    // The nodes of the client connector of a master group, the ids only identify the connections.
+    pub fn client_master_nodes(addrs: &[InetAddr]) -> Vec<NodeAddr> {
+        addrs
+            .iter()
+            .enumerate()
+            .map(|(index, addr)| {
+                NodeAddr::from_addr(
+                    Self::CLIENT_MASTER_NODE_START_ID + index as u64,
+                    addr.clone(),
+                )
+            })
+            .collect()
     }
 
     pub fn masters_string(&self) -> String {
@@ -203,6 +232,7 @@ impl ClusterConf {
         conf.pipe_pool_idle_time = self.worker.pipe_pool_idle_time;
 
         conf.enable_send_file = self.worker.enable_send_file;
+        conf.compress_codecs = self.worker.rpc_compress_codecs();
         conf
     }
 
@@ -277,11 +307,13 @@ impl Default for ClusterConf {
             journal: Default::default(),
             worker: Default::default(),
             log: Default::default(),
+            trace: Default::default(),
             client: Default::default(),
             fuse: FuseConf::default(),
             s3_gateway: Default::default(),
             job: Default::default(),
             cli: Default::default(),
+            federation: Default::default(),
         }
     }
 }
@@ -0,0 +1,131 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::conf::ClusterConf;
+use orpc::io::net::{InetAddr, NodeAddr};
+use orpc::{err_box, CommonResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+// Namespace federation: several independent master groups behind one client view.
+// Each namespace owns some path prefixes (mount points), the paths that are not owned by any namespace
+// belong to the default namespace, which is served by client.master_addrs.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct FederationConf {
+    pub namespaces: Vec<NamespaceConf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct NamespaceConf {
+    pub name: String,
+
+    // Rpc addresses of the masters of this namespace.
+    pub master_addrs: Vec<InetAddr>,
+
+    // Absolute paths owned by this namespace, e.g. "/warehouse".
+    // Mount points can not be nested, neither within one namespace nor across namespaces.
+    pub mount_points: Vec<String>,
+}
+
+impl NamespaceConf {
+    pub fn master_nodes(&self) -> Vec<NodeAddr> {
+        ClusterConf::client_master_nodes(&self.master_addrs)
+    }
+}
+
+impl FederationConf {
+    pub const DEFAULT_NAMESPACE: &'static str = "default";
+
+    pub fn init(&mut self) -> CommonResult<()> {
+        let mut names = HashSet::new();
+        let mut mount_points: Vec<String> = vec![];
+
+        for ns in &mut self.namespaces {
+            if ns.name.is_empty() || ns.name == Self::DEFAULT_NAMESPACE {
+                return err_box!("Invalid namespace name '{}'", ns.name);
+            } else if !names.insert(ns.name.clone()) {
+                return err_box!("Duplicate namespace {}", ns.name);
+            } else if ns.master_addrs.is_empty() {
+                return err_box!("Namespace {} has no master_addrs", ns.name);
+            }
+
+            for mnt in &mut ns.mount_points {
+                let trimmed = mnt.trim_end_matches('/');
+                if !trimmed.starts_with('/') {
+                    return err_box!(
+                        "Mount point '{}' of namespace {} must be an absolute path other than /",
+                        mnt,
+                        ns.name
+                    );
+                }
+                *mnt = trimmed.to_string();
+
+                for other in &mount_points {
+                    if Self::is_prefix(other, mnt) || Self::is_prefix(mnt, other) {
+                        return err_box!("Mount point {} is nested with {}", mnt, other);
+                    }
+                }
+                mount_points.push(mnt.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.namespaces.is_empty()
+    }
+
+    // Returns the index of the namespace that owns the path, None means the default namespace.
+    pub fn resolve(&self, path: &str) -> Option<usize> {
+        self.namespaces
+            .iter()
+            .position(|ns| ns.mount_points.iter().any(|mnt| Self::is_prefix(mnt, path)))
+    }
+
+    // The names of the children of the dir that lead to the mount points under it.
+    // A listing of the dir shows them, even though they belong to other namespaces.
+    pub fn mount_children(&self, dir: &str) -> Vec<String> {
+        let dir = dir.trim_end_matches('/');
+        let mut res: Vec<String> = self
+            .namespaces
+            .iter()
+            .flat_map(|ns| ns.mount_points.iter())
+            .filter_map(|mnt| {
+                let rest = mnt.strip_prefix(dir)?.strip_prefix('/')?;
+                rest.split('/').next().map(|x| x.to_string())
+            })
+            .collect();
+        res.sort();
+        res.dedup();
+        res
+    }
+
+    pub fn namespace_name(&self, index: Option<usize>) -> &str {
+        match index {
+            Some(i) => &self.namespaces[i].name,
+            None => Self::DEFAULT_NAMESPACE,
+        }
+    }
+
+    // Whether the path is the mount point itself or a descendant of it.
+    fn is_prefix(mnt: &str, path: &str) -> bool {
+        match path.strip_prefix(mnt) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+}
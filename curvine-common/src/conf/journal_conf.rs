@@ -17,7 +17,9 @@ use crate::raft::RaftPeer;
 use orpc::client::ClientConf;
 use orpc::common::Utils;
 use orpc::io::net::{InetAddr, NetUtils};
+use orpc::message::CompressionType;
 use orpc::runtime::Runtime;
+use orpc::CommonResult;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::vec;
@@ -38,11 +40,30 @@ pub struct JournalConf {
     pub message_size: usize,
 
     // Master candidate node
+    // A peer with observer = true replicates the journal and snapshots without voting,
+    // it is never promoted to a voter and can not become the leader.
+    // service_port is the master rpc port of the peer, master.rpc_port if not set.
     pub journal_addrs: Vec<RaftPeer>,
 
+    // Start as a new member of a running cluster instead of bootstrapping the group from journal_addrs.
+    // journal_addrs only needs to contain some existing masters and this one, the node joins as a learner
+    // and the leader promotes it to a voter once it has caught up, see `cv master add`.
+    pub join_cluster: bool,
+
     // raft log storage configuration
     pub journal_dir: String,
 
+    // The raft log storage, rocksdb or file.
+    // file saves the logs in preallocated segment files, avoiding the write amplification of rocksdb.
+    // The two formats are not compatible, changing it requires formatting the master.
+    pub log_storage: String,
+
+    // The size of a log segment file when log_storage is file, the file is preallocated to this size.
+    pub log_segment_size: String,
+
+    // Whether to fsync the segment file after each append when log_storage is file.
+    pub log_sync: bool,
+
     // Write enables debug and will print every log.
     pub writer_debug: bool,
 
@@ -82,6 +103,14 @@ pub struct JournalConf {
     // How many connections can be used when connecting to share.
     pub conn_size: usize,
 
+    // Compression of the raft messages and snapshots sent to the other masters: "none", "lz4" or "zstd".
+    // All masters must be upgraded before it is enabled. Messages smaller than rpc_compress_min_size
+    // bytes are sent uncompressed.
+    pub rpc_compress_type: String,
+    #[serde(skip)]
+    pub rpc_compress_type_unit: CompressionType,
+    pub rpc_compress_min_size: usize,
+
     // raft related configuration
     pub raft_poll_interval_ms: u64,
     pub raft_tick_interval_ms: u64,
@@ -89,7 +118,11 @@ pub struct JournalConf {
     pub raft_heartbeat_tick: usize,
     pub raft_min_election_ticks: usize,
     pub raft_max_election_ticks: usize,
+    // The leader steps down if it can not reach a quorum within an election timeout.
     pub raft_check_quorum: bool,
+    // A node starts an election only after a pre-vote round shows it can win,
+    // so a partitioned node rejoining the group does not disrupt the leader with a higher term.
+    pub raft_pre_vote: bool,
     pub raft_max_size_per_msg: u64,
     pub raft_max_inflight_msgs: usize,
     pub raft_max_committed_size_per_ready: u64,
@@ -108,6 +141,11 @@ pub struct JournalConf {
 impl JournalConf {
     pub const DEFAULT_NODE_ID: u64 = 0;
 
+    pub fn init(&mut self) -> CommonResult<()> {
+        self.rpc_compress_type_unit = CompressionType::try_from(self.rpc_compress_type.as_str())?;
+        Ok(())
+    }
+
     // Create a test configuration, which will also randomly select a server port.
     pub fn with_test() -> Self {
         let mut conf = Self::default();
@@ -149,6 +187,9 @@ impl JournalConf {
             conn_size: self.conn_size,
 
             use_libc: false,
+
+            compress_type: self.rpc_compress_type_unit,
+            compress_min_size: self.rpc_compress_min_size,
             ..Default::default()
         }
     }
@@ -167,7 +208,7 @@ impl JournalConf {
 
             check_quorum: self.raft_check_quorum,
             skip_bcast_commit: true,
-            pre_vote: true,
+            pre_vote: self.raft_pre_vote,
             ..Default::default()
         }
     }
@@ -190,7 +231,11 @@ impl Default for JournalConf {
             worker_threads: 8,
             message_size: 200,
             journal_addrs,
+            join_cluster: false,
             journal_dir,
+            log_storage: "rocksdb".to_string(),
+            log_segment_size: "64MB".to_string(),
+            log_sync: false,
             writer_debug: false,
             writer_channel_size: 0,
             writer_flush_batch_size: 1000,
@@ -213,6 +258,10 @@ impl Default for JournalConf {
 
             conn_size: 1,
 
+            rpc_compress_type: "none".to_string(),
+            rpc_compress_type_unit: CompressionType::None,
+            rpc_compress_min_size: 4 * 1024,
+
             raft_poll_interval_ms: 100,
             raft_tick_interval_ms: 1000,
             raft_election_tick: 10,
@@ -220,6 +269,7 @@ impl Default for JournalConf {
             raft_min_election_ticks: 10,
             raft_max_election_ticks: 30,
             raft_check_quorum: true,
+            raft_pre_vote: true,
             raft_max_size_per_msg: 1024 * 1024,
             raft_max_inflight_msgs: 256,
             raft_max_committed_size_per_ready: 16 * 1024 * 1024,
@@ -14,9 +14,11 @@
 
 use crate::conf::ClusterConf;
 use orpc::common::{DurationUnit, LogConf, Utils};
+use orpc::message::CompressionType;
 use orpc::runtime::GroupExecutor;
 use orpc::{err_box, CommonResult};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 // master Configuration file.
@@ -33,6 +35,11 @@ pub struct MasterConf {
     pub io_timeout: String,
     pub io_close_idle: bool,
 
+    // Rpc payload compression codecs accepted from clients, e.g. "lz4,zstd"; empty disables compression.
+    pub rpc_compress_codecs: String,
+    #[serde(skip)]
+    pub rpc_compress_codecs_list: Vec<CompressionType>,
+
     // Metadata configuration, currently only supports rocksdb.
     // rocksdb configuration.
     pub meta_dir: String,
@@ -91,6 +98,40 @@ pub struct MasterConf {
     #[serde(skip)]
     pub block_replication_retry_interval_unit: DurationUnit,
 
+    // Replication reconciler, compares the live replicas of each block with the
+    // replica number of its file, and adds or removes copies to match.
+    pub block_reconcile_enabled: bool,
+    pub block_reconcile_interval: String,
+    #[serde(skip)]
+    pub block_reconcile_interval_unit: DurationUnit,
+    // The number of inodes scanned in each round.
+    pub block_reconcile_scan_batch: usize,
+    // The maximum number of replications and deletions scheduled in each round.
+    pub block_reconcile_max_actions: usize,
+
+    // The number of inodes a tree walk (content summary, find, fsck) visits under one
+    // hold of the locks, the locks are released between the batches.
+    pub tree_walk_batch: usize,
+
+    // Storage migration, moves the blocks of files whose storage type was changed.
+    pub storage_migration_interval: String,
+    #[serde(skip)]
+    pub storage_migration_interval_unit: DurationUnit,
+    // The maximum number of block moves scheduled in each round.
+    pub storage_migration_max_moves: usize,
+
+    // Metadata backup, the leader periodically uploads a checkpoint of the metadata to backup_dir.
+    // backup_dir is a ufs path, e.g. s3://bucket/curvine/backup, a path without scheme is a local directory.
+    pub backup_enabled: bool,
+    pub backup_dir: String,
+    pub backup_interval: String,
+    #[serde(skip)]
+    pub backup_interval_unit: DurationUnit,
+    // The number of backups to keep, the oldest ones are deleted after a new backup is uploaded.
+    pub backup_retain_num: usize,
+    // The ufs configuration used to access backup_dir, e.g. s3.credentials.access.
+    pub backup_properties: HashMap<String, String>,
+
     pub log: LogConf,
 
     pub ttl_checker_retry_attempts: u32,
@@ -130,6 +171,26 @@ pub struct MasterConf {
     pub lock_expire_time: String,
     #[serde(skip)]
     pub lock_expire_time_unit: DurationUnit,
+
+    // Metadata rate limiting, requests are divided into read, write and list classes.
+    // The rates are requests per second, 0 means unlimited.
+    // A client is the user it sends when connecting, or its host if it sends none.
+    // Clients listed in rate_limit_clients get their own limits, e.g. "10.0.0.1" = "5000,1000,100" (read,write,list);
+    // the other clients share rate_limit_read/write/list, which is divided fairly among the active clients.
+    pub rate_limit_enabled: bool,
+    pub rate_limit_read: u64,
+    pub rate_limit_write: u64,
+    pub rate_limit_list: u64,
+    // How long a client can burst above its rate.
+    pub rate_limit_burst: String,
+    #[serde(skip)]
+    pub rate_limit_burst_unit: DurationUnit,
+    // How long a request over the rate may wait for its turn, a request that would wait longer
+    // is rejected and the client backs off.
+    pub rate_limit_queue_wait: String,
+    #[serde(skip)]
+    pub rate_limit_queue_wait_unit: DurationUnit,
+    pub rate_limit_clients: HashMap<String, String>,
 }
 
 impl MasterConf {
@@ -150,10 +211,21 @@ impl MasterConf {
         self.ttl_retry_interval_unit = DurationUnit::from_str(&self.ttl_retry_interval)?;
         self.block_replication_retry_interval_unit =
             DurationUnit::from_str(&self.block_replication_retry_interval)?;
+        self.block_reconcile_interval_unit =
+            DurationUnit::from_str(&self.block_reconcile_interval)?;
+        self.storage_migration_interval_unit =
+            DurationUnit::from_str(&self.storage_migration_interval)?;
+        self.backup_interval_unit = DurationUnit::from_str(&self.backup_interval)?;
 
         // Initialize lock expiration time
         self.lock_expire_time_unit = DurationUnit::from_str(&self.lock_expire_time)?;
 
+        self.rate_limit_burst_unit = DurationUnit::from_str(&self.rate_limit_burst)?;
+        self.rate_limit_queue_wait_unit = DurationUnit::from_str(&self.rate_limit_queue_wait)?;
+        self.rate_limit_client_limits()?;
+
+        self.rpc_compress_codecs_list = CompressionType::parse_list(&self.rpc_compress_codecs)?;
+
         if self.heartbeat_interval_unit > self.worker_blacklist_interval_unit {
             return err_box!("Worker_blacklist_interval must be greater than heartbeat_interval");
         };
@@ -201,10 +273,56 @@ impl MasterConf {
         self.block_replication_retry_interval_unit.as_millis()
     }
 
+    pub fn block_reconcile_interval_ms(&self) -> u64 {
+        self.block_reconcile_interval_unit.as_millis()
+    }
+
+    pub fn storage_migration_interval_ms(&self) -> u64 {
+        self.storage_migration_interval_unit.as_millis()
+    }
+
+    pub fn backup_interval_ms(&self) -> u64 {
+        self.backup_interval_unit.as_millis()
+    }
+
     pub fn lock_expire_time_ms(&self) -> u64 {
         self.lock_expire_time_unit.as_millis()
     }
 
+    pub fn rate_limit_burst_ms(&self) -> u64 {
+        self.rate_limit_burst_unit.as_millis()
+    }
+
+    pub fn rate_limit_queue_wait_ms(&self) -> u64 {
+        self.rate_limit_queue_wait_unit.as_millis()
+    }
+
+    // Parse rate_limit_clients, the value of each client is "read,write,list".
+    pub fn rate_limit_client_limits(&self) -> CommonResult<HashMap<String, [u64; 3]>> {
+        let mut res = HashMap::new();
+        for (client, value) in &self.rate_limit_clients {
+            let rates: Vec<&str> = value.split(',').map(|x| x.trim()).collect();
+            if rates.len() != 3 {
+                return err_box!(
+                    "Invalid rate limit {} of client {}, expected read,write,list",
+                    value,
+                    client
+                );
+            }
+
+            let mut limits = [0; 3];
+            for (i, rate) in rates.iter().enumerate() {
+                limits[i] = rate.parse()?;
+            }
+            res.insert(client.to_string(), limits);
+        }
+        Ok(res)
+    }
+
+    pub fn rpc_compress_codecs(&self) -> Vec<CompressionType> {
+        self.rpc_compress_codecs_list.clone()
+    }
+
     pub fn io_timeout_ms(&self) -> u64 {
         let dur = DurationUnit::from_str(&self.io_timeout).unwrap();
         dur.as_millis()
@@ -231,6 +349,8 @@ impl Default for MasterConf {
             worker_threads: Utils::worker_threads(32),
             io_timeout: "10m".to_string(),
             io_close_idle: true,
+            rpc_compress_codecs: "lz4,zstd".to_string(),
+            rpc_compress_codecs_list: vec![CompressionType::Lz4, CompressionType::Zstd],
 
             meta_dir: dir,
             meta_disable_wal: true,
@@ -275,6 +395,26 @@ impl Default for MasterConf {
             block_replication_concurrency_limit: 1000,
             block_replication_retry_interval: "5s".to_string(),
             block_replication_retry_interval_unit: Default::default(),
+
+            block_reconcile_enabled: false,
+            block_reconcile_interval: "60s".to_string(),
+            block_reconcile_interval_unit: Default::default(),
+            block_reconcile_scan_batch: 1000,
+            block_reconcile_max_actions: 100,
+
+            tree_walk_batch: 10000,
+
+            storage_migration_interval: "10s".to_string(),
+            storage_migration_interval_unit: Default::default(),
+            storage_migration_max_moves: 1000,
+
+            backup_enabled: false,
+            backup_dir: Utils::cur_dir_sub("fs-backup"),
+            backup_interval: "6h".to_string(),
+            backup_interval_unit: Default::default(),
+            backup_retain_num: 7,
+            backup_properties: HashMap::new(),
+
             log: Default::default(),
 
             ttl_checker_retry_attempts: 3,
@@ -303,6 +443,16 @@ impl Default for MasterConf {
 
             lock_expire_time: "5m".to_string(),
             lock_expire_time_unit: Default::default(),
+
+            rate_limit_enabled: false,
+            rate_limit_read: 50000,
+            rate_limit_write: 10000,
+            rate_limit_list: 2000,
+            rate_limit_burst: "1s".to_string(),
+            rate_limit_burst_unit: Default::default(),
+            rate_limit_queue_wait: "100ms".to_string(),
+            rate_limit_queue_wait_unit: Default::default(),
+            rate_limit_clients: HashMap::new(),
         };
 
         conf.init().unwrap();
@@ -46,11 +46,15 @@ pub use self::job_conf::JobConf;
 mod cli_conf;
 pub use self::cli_conf::CliConf;
 
+mod federation_conf;
+pub use self::federation_conf::*;
+
 #[cfg(test)]
 mod tests {
-    use crate::conf::{ClusterConf, WorkerDataDir};
+    use crate::conf::{ClusterConf, FederationConf, NamespaceConf, WorkerDataDir};
     use crate::state::StorageType;
     use orpc::common::ByteUnit;
+    use orpc::io::net::InetAddr;
 
     #[test]
     fn cluster() {
@@ -83,4 +87,43 @@ mod tests {
             assert_eq!(res, obj);
         }
     }
+
+    #[test]
+    fn federation() {
+        let ns = |name: &str, mount_points: &[&str]| NamespaceConf {
+            name: name.to_string(),
+            master_addrs: vec![InetAddr::new("localhost", 8995)],
+            mount_points: mount_points.iter().map(|x| x.to_string()).collect(),
+        };
+
+        let mut conf = FederationConf {
+            namespaces: vec![ns("ns1", &["/warehouse/", "/logs"]), ns("ns2", &["/tmp"])],
+        };
+        conf.init().unwrap();
+
+        assert_eq!(conf.resolve("/warehouse"), Some(0));
+        assert_eq!(conf.resolve("/warehouse/db/t1"), Some(0));
+        assert_eq!(conf.resolve("/logs/a.log"), Some(0));
+        assert_eq!(conf.resolve("/tmp/x"), Some(1));
+        assert_eq!(conf.resolve("/warehouse2"), None);
+        assert_eq!(conf.resolve("/"), None);
+        assert_eq!(conf.namespace_name(conf.resolve("/tmp")), "ns2");
+        assert_eq!(conf.namespace_name(None), FederationConf::DEFAULT_NAMESPACE);
+        assert_eq!(conf.mount_children("/"), vec!["logs", "tmp", "warehouse"]);
+        assert!(conf.mount_children("/warehouse").is_empty());
+        assert_eq!(
+            conf.namespaces[0].master_nodes()[0].id,
+            ClusterConf::CLIENT_MASTER_NODE_START_ID
+        );
+
+        let mut nested = FederationConf {
+            namespaces: vec![ns("ns1", &["/data"]), ns("ns2", &["/data/hot"])],
+        };
+        assert!(nested.init().is_err());
+
+        let mut root = FederationConf {
+            namespaces: vec![ns("ns1", &["/"])],
+        };
+        assert!(root.init().is_err());
+    }
 }
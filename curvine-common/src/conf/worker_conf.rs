@@ -16,11 +16,16 @@
 
 use crate::conf::ClusterConf;
 use crate::state::StorageType;
+use once_cell::sync::Lazy;
 use orpc::common::{ByteUnit, DurationUnit, FileUtils, LogConf, Utils};
+use orpc::message::CompressionType;
 use orpc::{err_box, CommonResult};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+static TIER_WATERMARK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\[(\w+)\]([\d.]+):([\d.]+)$").unwrap());
+
 #[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq)]
 #[serde(default)]
 pub struct WorkerDataDir {
@@ -108,6 +113,42 @@ impl WorkerDataDir {
     }
 }
 
+// Watermarks of a storage tier, format: [MEM]0.9:0.7
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TierWatermark {
+    pub storage_type: StorageType,
+    pub high: f64,
+    pub low: f64,
+}
+
+impl TierWatermark {
+    pub fn from_str(str: &str) -> CommonResult<Self> {
+        let caps = match TIER_WATERMARK.captures(str.trim()) {
+            None => return err_box!("Incorrect tier watermark format {}", str),
+            Some(v) => v,
+        };
+
+        let mark = Self {
+            storage_type: StorageType::try_from(&caps[1])?,
+            high: caps[2].parse()?,
+            low: caps[3].parse()?,
+        };
+        Self::check(mark.high, mark.low)?;
+        Ok(mark)
+    }
+
+    pub fn check(high: f64, low: f64) -> CommonResult<()> {
+        if !(0.0..=1.0).contains(&high) || !(0.0..=high).contains(&low) {
+            return err_box!(
+                "Tier watermark must satisfy 0 <= low <= high <= 1, high {}, low {}",
+                high,
+                low
+            );
+        }
+        Ok(())
+    }
+}
+
 // Worker configuration file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -132,6 +173,11 @@ pub struct WorkerConf {
     pub io_timeout: String,
     pub io_close_idle: bool,
 
+    // Rpc payload compression codecs accepted from clients, e.g. "lz4,zstd"; empty disables compression.
+    pub rpc_compress_codecs: String,
+    #[serde(skip)]
+    pub rpc_compress_codecs_list: Vec<CompressionType>,
+
     pub scheduler_threads: usize,
 
     pub log: LogConf,
@@ -159,18 +205,85 @@ pub struct WorkerConf {
 
     // Enable S3 gateway alongside worker
     pub enable_s3_gateway: bool,
+
+    // Tier migration: frequently read blocks are promoted to faster tiers (MEM > SSD > HDD),
+    // cold blocks are demoted when a tier exceeds its high watermark, until it drops below the low watermark.
+    pub enable_tier_migration: bool,
+    pub tier_check_interval: String,
+    #[serde(skip)]
+    pub tier_check_interval_unit: DurationUnit,
+    // Number of reads, halved every check interval, for a block to be promoted.
+    pub tier_promote_reads: u32,
+    // Maximum number of blocks moved per check interval.
+    pub tier_max_moves: usize,
+    pub tier_high_watermark: f64,
+    pub tier_low_watermark: f64,
+    // Per tier watermarks overriding the defaults above, e.g. ["[MEM]0.9:0.7"]
+    pub tier_watermarks: Vec<String>,
+    #[serde(skip)]
+    pub tier_watermarks_list: Vec<TierWatermark>,
+
+    // Io bandwidth limits in bytes per second, e.g. "200MB"; "0" means unlimited.
+    // io_throttle_total is shared by all traffic classes. Client reads and writes are counted against it
+    // but never wait for it, so load, replication and eviction only get the bandwidth the clients leave.
+    // Short-circuit reads and writes bypass the worker and are not throttled.
+    // The limits can be changed at runtime with `cv admin throttle`.
+    pub io_throttle_total: String,
+    pub io_throttle_client_read: String,
+    pub io_throttle_client_write: String,
+    pub io_throttle_load: String,
+    pub io_throttle_replication: String,
+    // Blocks moved between storage tiers.
+    pub io_throttle_eviction: String,
 }
 
 impl WorkerConf {
+    pub fn init(&mut self) -> CommonResult<()> {
+        self.rpc_compress_codecs_list = CompressionType::parse_list(&self.rpc_compress_codecs)?;
+
+        self.tier_check_interval_unit = DurationUnit::from_str(&self.tier_check_interval)?;
+        TierWatermark::check(self.tier_high_watermark, self.tier_low_watermark)?;
+        self.tier_watermarks_list = self
+            .tier_watermarks
+            .iter()
+            .map(|x| TierWatermark::from_str(x))
+            .collect::<CommonResult<_>>()?;
+
+        Ok(())
+    }
+
     pub fn io_slow_us(&self) -> u64 {
         let dur = DurationUnit::from_str(&self.io_slow_threshold).unwrap();
         dur.as_millis() * 1000
     }
 
+    pub fn rpc_compress_codecs(&self) -> Vec<CompressionType> {
+        self.rpc_compress_codecs_list.clone()
+    }
+
     pub fn io_timeout_ms(&self) -> u64 {
         let dur = DurationUnit::from_str(&self.io_timeout).unwrap();
         dur.as_millis()
     }
+
+    pub fn tier_check_interval_ms(&self) -> u64 {
+        self.tier_check_interval_unit.as_millis()
+    }
+
+    pub fn tier_watermark(&self, storage_type: StorageType) -> TierWatermark {
+        match self
+            .tier_watermarks_list
+            .iter()
+            .find(|x| x.storage_type == storage_type)
+        {
+            Some(mark) => *mark,
+            None => TierWatermark {
+                storage_type,
+                high: self.tier_high_watermark,
+                low: self.tier_low_watermark,
+            },
+        }
+    }
 }
 
 impl Default for WorkerConf {
@@ -186,6 +299,8 @@ impl Default for WorkerConf {
             worker_threads: Utils::worker_threads(32),
             io_timeout: "10m".to_string(),
             io_close_idle: false,
+            rpc_compress_codecs: "lz4,zstd".to_string(),
+            rpc_compress_codecs_list: vec![CompressionType::Lz4, CompressionType::Zstd],
 
             scheduler_threads: 2,
             log: Default::default(),
@@ -203,6 +318,23 @@ impl Default for WorkerConf {
             block_replication_concurrency_limit: 100,
             block_replication_chunk_size: 1024 * 1024,
             enable_s3_gateway: false,
+
+            enable_tier_migration: false,
+            tier_check_interval: "30s".to_string(),
+            tier_check_interval_unit: Default::default(),
+            tier_promote_reads: 4,
+            tier_max_moves: 64,
+            tier_high_watermark: 0.9,
+            tier_low_watermark: 0.75,
+            tier_watermarks: vec![],
+            tier_watermarks_list: vec![],
+
+            io_throttle_total: "0".to_string(),
+            io_throttle_client_read: "0".to_string(),
+            io_throttle_client_write: "0".to_string(),
+            io_throttle_load: "0".to_string(),
+            io_throttle_replication: "0".to_string(),
+            io_throttle_eviction: "0".to_string(),
         }
     }
 }
@@ -0,0 +1,256 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use orpc::{err_box, CommonResult};
+
+// Arithmetic over GF(2^8) with the primitive polynomial x^8 + x^4 + x^3 + x^2 + 1.
+const POLYNOMIAL: u32 = 0x11d;
+
+const fn build_tables() -> ([u8; 512], [u8; 256]) {
+    let mut exp = [0u8; 512];
+    let mut log = [0u8; 256];
+    let mut x: u32 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = x as u8;
+        log[x as usize] = i as u8;
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= POLYNOMIAL;
+        }
+        i += 1;
+    }
+    // Duplicate the table so that exp[log(a) + log(b)] never needs a modulo.
+    while i < 512 {
+        exp[i] = exp[i - 255];
+        i += 1;
+    }
+    (exp, log)
+}
+
+const TABLES: ([u8; 512], [u8; 256]) = build_tables();
+static EXP: [u8; 512] = TABLES.0;
+static LOG: [u8; 256] = TABLES.1;
+
+pub fn add(a: u8, b: u8) -> u8 {
+    a ^ b
+}
+
+pub fn mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        EXP[LOG[a as usize] as usize + LOG[b as usize] as usize]
+    }
+}
+
+pub fn div(a: u8, b: u8) -> u8 {
+    assert_ne!(b, 0, "divide by zero in GF(2^8)");
+    if a == 0 {
+        0
+    } else {
+        EXP[LOG[a as usize] as usize + 255 - LOG[b as usize] as usize]
+    }
+}
+
+pub fn pow(a: u8, n: usize) -> u8 {
+    if n == 0 {
+        1
+    } else if a == 0 {
+        0
+    } else {
+        EXP[(LOG[a as usize] as usize * n) % 255]
+    }
+}
+
+/// Multiply every byte of `input` by `c` and xor the result into `output`.
+pub fn mul_add_slice(c: u8, input: &[u8], output: &mut [u8]) {
+    match c {
+        0 => (),
+        1 => {
+            for (o, i) in output.iter_mut().zip(input) {
+                *o ^= *i;
+            }
+        }
+        _ => {
+            let mut table = [0u8; 256];
+            for (x, v) in table.iter_mut().enumerate() {
+                *v = mul(c, x as u8);
+            }
+            for (o, i) in output.iter_mut().zip(input) {
+                *o ^= table[*i as usize];
+            }
+        }
+    }
+}
+
+/// A dense row-major matrix over GF(2^8).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<u8>,
+}
+
+impl Matrix {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![0; rows * cols],
+        }
+    }
+
+    pub fn identity(size: usize) -> Self {
+        let mut m = Self::new(size, size);
+        for i in 0..size {
+            m.set(i, i, 1);
+        }
+        m
+    }
+
+    // Vandermonde matrix, any `cols` rows of it are linearly independent.
+    pub fn vandermonde(rows: usize, cols: usize) -> Self {
+        let mut m = Self::new(rows, cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                m.set(r, c, pow(r as u8, c));
+            }
+        }
+        m
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> u8 {
+        self.data[r * self.cols + c]
+    }
+
+    pub fn set(&mut self, r: usize, c: usize, v: u8) {
+        self.data[r * self.cols + c] = v;
+    }
+
+    pub fn row(&self, r: usize) -> &[u8] {
+        &self.data[r * self.cols..(r + 1) * self.cols]
+    }
+
+    pub fn multiply(&self, other: &Matrix) -> CommonResult<Matrix> {
+        if self.cols != other.rows {
+            return err_box!(
+                "Matrix size mismatch: {}x{} * {}x{}",
+                self.rows,
+                self.cols,
+                other.rows,
+                other.cols
+            );
+        }
+
+        let mut res = Matrix::new(self.rows, other.cols);
+        for r in 0..self.rows {
+            for c in 0..other.cols {
+                let mut v = 0;
+                for i in 0..self.cols {
+                    v = add(v, mul(self.get(r, i), other.get(i, c)));
+                }
+                res.set(r, c, v);
+            }
+        }
+        Ok(res)
+    }
+
+    pub fn sub_rows(&self, rows: &[usize]) -> Matrix {
+        let mut res = Matrix::new(rows.len(), self.cols);
+        for (i, r) in rows.iter().enumerate() {
+            res.data[i * self.cols..(i + 1) * self.cols].copy_from_slice(self.row(*r));
+        }
+        res
+    }
+
+    // Gauss-Jordan elimination.
+    pub fn invert(&self) -> CommonResult<Matrix> {
+        if self.rows != self.cols {
+            return err_box!("Only square matrix can be inverted");
+        }
+
+        let size = self.rows;
+        let mut work = self.clone();
+        let mut res = Matrix::identity(size);
+
+        for c in 0..size {
+            // Find a pivot row and move it into place.
+            let pivot = match (c..size).find(|r| work.get(*r, c) != 0) {
+                Some(v) => v,
+                None => return err_box!("Matrix is singular"),
+            };
+            if pivot != c {
+                work.swap_rows(pivot, c);
+                res.swap_rows(pivot, c);
+            }
+
+            let scale = div(1, work.get(c, c));
+            for i in 0..size {
+                work.set(c, i, mul(work.get(c, i), scale));
+                res.set(c, i, mul(res.get(c, i), scale));
+            }
+
+            for r in 0..size {
+                let factor = work.get(r, c);
+                if r == c || factor == 0 {
+                    continue;
+                }
+                for i in 0..size {
+                    let w = add(work.get(r, i), mul(factor, work.get(c, i)));
+                    work.set(r, i, w);
+                    let v = add(res.get(r, i), mul(factor, res.get(c, i)));
+                    res.set(r, i, v);
+                }
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for c in 0..self.cols {
+            self.data.swap(a * self.cols + c, b * self.cols + c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ec::galois::{div, mul, Matrix};
+
+    #[test]
+    fn field() {
+        for a in 1..=255u8 {
+            assert_eq!(div(mul(a, 7), 7), a);
+            assert_eq!(mul(a, div(1, a)), 1);
+        }
+        assert_eq!(mul(0, 9), 0);
+    }
+
+    #[test]
+    fn invert() {
+        let m = Matrix::vandermonde(5, 5);
+        let inv = m.invert().unwrap();
+        assert_eq!(m.multiply(&inv).unwrap(), Matrix::identity(5));
+    }
+}
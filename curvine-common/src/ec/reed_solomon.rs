@@ -0,0 +1,209 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::ec::galois::{mul_add_slice, Matrix};
+use crate::state::EcPolicy;
+use orpc::{err_box, CommonResult};
+
+/// Systematic Reed-Solomon codec.
+///
+/// The encoding matrix is derived from a Vandermonde matrix whose top square is turned
+/// into the identity, so data shards are stored as-is and only parity shards are computed.
+#[derive(Debug, Clone)]
+pub struct ReedSolomon {
+    data_shards: usize,
+    parity_shards: usize,
+    matrix: Matrix,
+}
+
+impl ReedSolomon {
+    pub fn new(data_shards: usize, parity_shards: usize) -> CommonResult<Self> {
+        let total = data_shards + parity_shards;
+        if data_shards == 0 || parity_shards == 0 || total > EcPolicy::MAX_UNITS as usize {
+            return err_box!(
+                "Unsupported reed-solomon config: {} data shards, {} parity shards",
+                data_shards,
+                parity_shards
+            );
+        }
+
+        let vm = Matrix::vandermonde(total, data_shards);
+        let top: Vec<usize> = (0..data_shards).collect();
+        let top_inv = vm.sub_rows(&top).invert()?;
+        let matrix = vm.multiply(&top_inv)?;
+
+        Ok(Self {
+            data_shards,
+            parity_shards,
+            matrix,
+        })
+    }
+
+    pub fn with_policy(policy: &EcPolicy) -> CommonResult<Self> {
+        Self::new(policy.data_units as usize, policy.parity_units as usize)
+    }
+
+    pub fn data_shards(&self) -> usize {
+        self.data_shards
+    }
+
+    pub fn parity_shards(&self) -> usize {
+        self.parity_shards
+    }
+
+    pub fn total_shards(&self) -> usize {
+        self.data_shards + self.parity_shards
+    }
+
+    /// Compute parity shards from data shards, all shards must have the same length.
+    pub fn encode<T: AsRef<[u8]>>(&self, data: &[T]) -> CommonResult<Vec<Vec<u8>>> {
+        if data.len() != self.data_shards {
+            return err_box!(
+                "Expected {} data shards, got {}",
+                self.data_shards,
+                data.len()
+            );
+        }
+
+        let len = data[0].as_ref().len();
+        if data.iter().any(|x| x.as_ref().len() != len) {
+            return err_box!("All data shards must have the same length");
+        }
+
+        let rows: Vec<usize> = (self.data_shards..self.total_shards()).collect();
+        Ok(self.code_shards(&self.matrix.sub_rows(&rows), data, len))
+    }
+
+    /// Rebuild the missing shards in place.
+    /// At least `data_shards` shards must be present and all present shards must have the same length.
+    pub fn reconstruct(&self, shards: &mut [Option<Vec<u8>>]) -> CommonResult<()> {
+        if shards.len() != self.total_shards() {
+            return err_box!(
+                "Expected {} shards, got {}",
+                self.total_shards(),
+                shards.len()
+            );
+        }
+
+        let present: Vec<usize> = (0..shards.len()).filter(|i| shards[*i].is_some()).collect();
+        if present.len() == shards.len() {
+            return Ok(());
+        } else if present.len() < self.data_shards {
+            return err_box!(
+                "Too few shards to reconstruct, need {}, present {}",
+                self.data_shards,
+                present.len()
+            );
+        }
+
+        let len = match &shards[present[0]] {
+            Some(v) => v.len(),
+            None => 0,
+        };
+        if present
+            .iter()
+            .any(|i| shards[*i].as_ref().map(|x| x.len()) != Some(len))
+        {
+            return err_box!("All present shards must have the same length");
+        }
+
+        // Solve the data shards from any `data_shards` present shards.
+        let rows = &present[..self.data_shards];
+        let decode = self.matrix.sub_rows(rows).invert()?;
+        let inputs: Vec<&[u8]> = rows
+            .iter()
+            .map(|i| shards[*i].as_deref().unwrap_or_default())
+            .collect();
+
+        let missing_data: Vec<usize> = (0..self.data_shards)
+            .filter(|i| shards[*i].is_none())
+            .collect();
+        let decoded = self.code_shards(&decode.sub_rows(&missing_data), &inputs, len);
+        for (i, v) in missing_data.into_iter().zip(decoded) {
+            shards[i] = Some(v);
+        }
+
+        // Parity shards are re-encoded from the now complete data shards.
+        let missing_parity: Vec<usize> = (self.data_shards..self.total_shards())
+            .filter(|i| shards[*i].is_none())
+            .collect();
+        if !missing_parity.is_empty() {
+            let data: Vec<&[u8]> = shards[..self.data_shards]
+                .iter()
+                .map(|x| x.as_deref().unwrap_or_default())
+                .collect();
+            let encoded = self.code_shards(&self.matrix.sub_rows(&missing_parity), &data, len);
+            for (i, v) in missing_parity.into_iter().zip(encoded) {
+                shards[i] = Some(v);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn code_shards<T: AsRef<[u8]>>(
+        &self,
+        matrix: &Matrix,
+        inputs: &[T],
+        len: usize,
+    ) -> Vec<Vec<u8>> {
+        let mut outputs = Vec::with_capacity(matrix.rows());
+        for r in 0..matrix.rows() {
+            let mut out = vec![0u8; len];
+            for (c, input) in inputs.iter().enumerate() {
+                mul_add_slice(matrix.get(r, c), input.as_ref(), &mut out);
+            }
+            outputs.push(out);
+        }
+        outputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ec::ReedSolomon;
+
+    fn shards(k: usize, len: usize) -> Vec<Vec<u8>> {
+        (0..k)
+            .map(|i| (0..len).map(|j| (i * 31 + j * 7) as u8).collect())
+            .collect()
+    }
+
+    #[test]
+    fn encode_reconstruct() {
+        let rs = ReedSolomon::new(6, 3).unwrap();
+        let data = shards(6, 1000);
+        let parity = rs.encode(&data).unwrap();
+        assert_eq!(parity.len(), 3);
+
+        let all: Vec<Vec<u8>> = data.iter().chain(parity.iter()).cloned().collect();
+
+        // Lose any 3 shards.
+        for lost in [[0, 1, 2], [3, 7, 8], [0, 4, 6], [6, 7, 8]] {
+            let mut input: Vec<Option<Vec<u8>>> = all.iter().cloned().map(Some).collect();
+            for i in lost {
+                input[i] = None;
+            }
+            rs.reconstruct(&mut input).unwrap();
+            let output: Vec<Vec<u8>> = input.into_iter().map(|x| x.unwrap()).collect();
+            assert_eq!(output, all);
+        }
+
+        let mut input: Vec<Option<Vec<u8>>> = all.iter().cloned().map(Some).collect();
+        for v in input.iter_mut().take(4) {
+            *v = None;
+        }
+        assert!(rs.reconstruct(&mut input).is_err());
+    }
+}
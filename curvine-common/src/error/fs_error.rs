@@ -27,6 +27,7 @@ use serde_json::json;
 use std::io;
 use std::num::ParseIntError;
 use std::sync::mpsc::{RecvError, SendError};
+use std::time::Duration;
 use thiserror::Error;
 use tokio::task::JoinError;
 use tokio::time::error::Elapsed;
@@ -60,6 +61,7 @@ pub enum ErrorKind {
     JobNotFound = 23,
     Pipeline = 24,
     MinReplicasNotMet = 25,
+    Throttled = 26,
 
     #[num_enum(default)]
     Common = 10000,
@@ -164,6 +166,10 @@ pub enum FsError {
     #[error("{0}")]
     JobNotFound(ErrorImpl<StringError>),
 
+    // The master is throttling the client, the data is the backoff in ms to retry after.
+    #[error("{0}")]
+    Throttled(ErrorImpl<StringError, u64>),
+
     // Other errors that are not defined.
     #[error("{0}")]
     Common(ErrorImpl<StringError>),
@@ -254,6 +260,14 @@ impl FsError {
         Self::MinReplicasNotMet(ErrorImpl::with_source(msg.into()))
     }
 
+    pub fn throttled(code: RpcCode, client: &str, backoff_ms: u64) -> Self {
+        let msg = format!(
+            "Too many requests, code={:?}, client={}, backoff={}ms",
+            code, client, backoff_ms
+        );
+        Self::Throttled(ErrorImpl::with_data(msg.into(), backoff_ms))
+    }
+
     pub fn is_pipeline_error(&self) -> bool {
         matches!(self, FsError::Pipeline(_))
     }
@@ -273,6 +287,13 @@ impl FsError {
         }
     }
 
+    pub fn backoff_ms(&self) -> Option<u64> {
+        match self {
+            FsError::Throttled(e) => e.data,
+            _ => None,
+        }
+    }
+
     // Determine whether the current error allows retry.
     // NotLeaderMaster error indicates that a master switch has occurred and you need to retry access to the next master
     pub fn retry_master(&self) -> bool {
@@ -313,6 +334,7 @@ impl FsError {
             FsError::Pipeline(_) => ErrorKind::Pipeline,
             FsError::MinReplicasNotMet(_) => ErrorKind::MinReplicasNotMet,
             FsError::JobNotFound(_) => ErrorKind::JobNotFound,
+            FsError::Throttled(_) => ErrorKind::Throttled,
             FsError::Common(_) => ErrorKind::Common,
         }
     }
@@ -429,6 +451,7 @@ impl ErrorExt for FsError {
             FsError::Pipeline(e) => FsError::Pipeline(e.ctx(ctx)),
             FsError::MinReplicasNotMet(e) => FsError::MinReplicasNotMet(e.ctx(ctx)),
             FsError::JobNotFound(e) => FsError::JobNotFound(e.ctx(ctx)),
+            FsError::Throttled(e) => FsError::Throttled(e.ctx(ctx)),
             FsError::Common(e) => FsError::Common(e.ctx(ctx)),
         }
     }
@@ -460,6 +483,7 @@ impl ErrorExt for FsError {
             FsError::Pipeline(e) => e.encode(ErrorKind::Pipeline),
             FsError::MinReplicasNotMet(e) => e.encode(ErrorKind::MinReplicasNotMet),
             FsError::JobNotFound(e) => e.encode(ErrorKind::JobNotFound),
+            FsError::Throttled(e) => e.encode(ErrorKind::Throttled),
             FsError::Common(e) => e.encode(ErrorKind::Common),
         }
     }
@@ -494,6 +518,7 @@ impl ErrorExt for FsError {
             ErrorKind::Pipeline => FsError::Pipeline(de.into_string()),
             ErrorKind::MinReplicasNotMet => FsError::MinReplicasNotMet(de.into_string()),
             ErrorKind::JobNotFound => FsError::JobNotFound(de.into_string()),
+            ErrorKind::Throttled => FsError::Throttled(de.into_string()),
             ErrorKind::Common => FsError::Common(de.into_string()),
         }
     }
@@ -501,13 +526,19 @@ impl ErrorExt for FsError {
     fn should_retry(&self) -> bool {
         self.retry_master()
     }
+
+    fn backoff(&self) -> Option<Duration> {
+        self.backoff_ms().map(Duration::from_millis)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::ErrorKind;
     use crate::error::fs_error::FsError;
+    use crate::fs::RpcCode;
     use orpc::error::{ErrorExt, ErrorImpl};
+    use std::time::Duration;
 
     #[test]
     pub fn error_test() {
@@ -566,4 +597,17 @@ mod tests {
         let decoded = FsError::decode(bytes);
         assert!(matches!(decoded.kind(), ErrorKind::MinReplicasNotMet));
     }
+
+    #[test]
+    pub fn throttled_error_round_trip_test() {
+        let error = FsError::throttled(RpcCode::FileStatus, "10.0.0.1", 120);
+
+        let bytes = error.encode();
+        let decoded = FsError::decode(bytes);
+        assert!(matches!(decoded.kind(), ErrorKind::Throttled));
+        assert_eq!(decoded.backoff_ms(), Some(120));
+        assert_eq!(decoded.backoff(), Some(Duration::from_millis(120)));
+        assert!(!decoded.should_retry());
+        assert_eq!(FsError::common("Some error").backoff(), None);
+    }
 }
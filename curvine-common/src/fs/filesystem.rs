@@ -57,7 +57,10 @@ pub trait FileSystem<Writer, Reader> {
                 .map(ProtoUtils::file_status_to_pb)
                 .collect::<Vec<_>>();
 
-            let rep = ListStatusResponse { statuses };
+            let rep = ListStatusResponse {
+                statuses,
+                has_more: None,
+            };
             Ok(ProtoUtils::encode(rep)?)
         }
     }
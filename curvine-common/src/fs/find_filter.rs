@@ -0,0 +1,91 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::state::{FileStatus, FileType, FindOpts};
+use crate::utils::parse_glob_pattern;
+use glob::Pattern;
+
+/// Matches the entries visited by a find against its predicates, the inodes on the master
+/// and the ufs statuses on the client.
+pub struct FindFilter {
+    opts: FindOpts,
+    // None if the name is not a glob, it is compared as is.
+    pattern: Option<Pattern>,
+}
+
+impl FindFilter {
+    pub fn new(opts: FindOpts) -> Self {
+        let pattern = match &opts.name {
+            Some(name) => parse_glob_pattern(name).1,
+            None => None,
+        };
+        Self { opts, pattern }
+    }
+
+    pub fn max_depth(&self) -> usize {
+        match self.opts.max_depth {
+            Some(v) if v >= 0 => v as usize,
+            _ => usize::MAX,
+        }
+    }
+
+    // len is None for a directory.
+    pub fn matches(&self, name: &str, file_type: FileType, len: Option<i64>, mtime: i64) -> bool {
+        let opts = &self.opts;
+        if let Some(expected) = &opts.name {
+            let matched = match &self.pattern {
+                Some(pattern) => pattern.matches(name),
+                None => name == expected,
+            };
+            if !matched {
+                return false;
+            }
+        }
+
+        if opts.file_type.is_some_and(|x| x != file_type) {
+            return false;
+        }
+
+        if opts.min_size.is_some() || opts.max_size.is_some() {
+            let len = match len {
+                Some(v) => v,
+                None => return false,
+            };
+            if opts.min_size.is_some_and(|x| len < x) || opts.max_size.is_some_and(|x| len > x) {
+                return false;
+            }
+        }
+
+        if opts.mtime_after.is_some_and(|x| mtime < x)
+            || opts.mtime_before.is_some_and(|x| mtime > x)
+        {
+            return false;
+        }
+
+        true
+    }
+
+    pub fn matches_status(&self, status: &FileStatus) -> bool {
+        if status.is_dir {
+            self.matches(&status.name, FileType::Dir, None, status.mtime)
+        } else {
+            self.matches(
+                &status.name,
+                status.file_type,
+                Some(status.len),
+                status.mtime,
+            )
+        }
+    }
+}
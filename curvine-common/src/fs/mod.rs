@@ -33,5 +33,8 @@ pub use self::meta_cache::MetaCache;
 mod state_file;
 pub use self::state_file::*;
 
+mod find_filter;
+pub use self::find_filter::FindFilter;
+
 // CurvineURI is used in the Curvine system to describe paths, including external storage
 pub type CurvineURI = Path;
@@ -47,6 +47,10 @@ pub enum RpcCode {
     CreateFilesBatch = 23,
     AddBlocksBatch = 24,
     CompleteFilesBatch = 25,
+    GetDirPolicy = 26,
+    SetStoragePolicy = 27,
+    GetContentSummary = 28,
+    Find = 29,
 
     // manager interface.
     Mount = 30,
@@ -68,6 +72,23 @@ pub enum RpcCode {
     RequestReplacementWorker = 44,
     ReportUnderReplicatedBlocks = 45,
 
+    // master membership interface.
+    AddMaster = 46,
+    RemoveMaster = 47,
+    TransferMasterLeader = 48,
+    ListMasters = 49,
+
+    // admin interface.
+    Fsck = 50,
+    BackupMeta = 51,
+    ListMetaBackups = 52,
+    WorkerThrottle = 53,
+
+    // A standby master asks the leader for the index of a linearizable read.
+    LeaderReadIndex = 54,
+
+    GetMetaBackupStatus = 55,
+
     MetricsReport = 60,
 
     // block interface.
@@ -15,6 +15,7 @@
 use crate::error::FsError;
 
 pub mod conf;
+pub mod ec;
 pub mod error;
 pub mod executor;
 pub mod fs;
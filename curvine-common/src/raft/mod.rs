@@ -35,7 +35,7 @@ mod raft_group;
 pub use self::raft_group::RaftGroup;
 
 mod raft_peer;
-pub use self::raft_peer::RaftPeer;
+pub use self::raft_peer::{PeerContext, RaftPeer};
 
 mod raft_error;
 pub use self::raft_error::RaftError;
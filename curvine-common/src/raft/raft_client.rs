@@ -15,8 +15,7 @@
 use crate::conf::JournalConf;
 use crate::proto::raft::*;
 use crate::raft::raft_error::RaftError;
-use crate::raft::{LibRaftMessage, NodeId, RaftCode, RaftGroup, RaftResult};
-use crate::utils::SerdeUtils;
+use crate::raft::{LibRaftMessage, NodeId, PeerContext, RaftCode, RaftGroup, RaftPeer, RaftResult};
 use orpc::client::{ClientConf, ClusterConnector, SyncClient};
 use orpc::io::net::{InetAddr, NodeAddr};
 use orpc::message::{Builder, Message, RefMessage};
@@ -86,12 +85,26 @@ impl RaftClient {
         self.rt.block_on(self.send_propose(data))
     }
 
-    // Join the cluster.
-    pub async fn join_cluster(&self, id: NodeId, addr: &InetAddr) -> RaftResult<()> {
+    // Add a node as a learner, the leader promotes it to a voter once it has caught up with the log.
+    // An observer is never promoted.
+    pub async fn add_learner(&self, peer: &RaftPeer) -> RaftResult<()> {
         let change = ConfChange {
-            change_type: ConfChangeType::AddNode.into(),
+            change_type: ConfChangeType::AddLearnerNode.into(),
+            node_id: peer.id,
+            context: PeerContext::from_peer(peer).encode()?,
+            id: peer.id,
+        };
+        let header = ConfChangeRequest { change };
+        let _: ConfChangeResponse = self.leader_rpc(RaftCode::ConfChange, header).await?;
+        Ok(())
+    }
+
+    // Remove a node from the cluster, the leader can not remove itself.
+    pub async fn remove_node(&self, id: NodeId) -> RaftResult<()> {
+        let change = ConfChange {
+            change_type: ConfChangeType::RemoveNode.into(),
             node_id: id,
-            context: SerdeUtils::serialize(addr)?,
+            context: vec![],
             id,
         };
         let header = ConfChangeRequest { change };
@@ -99,6 +112,39 @@ impl RaftClient {
         Ok(())
     }
 
+    // Ask the leader to transfer the leadership to the specified voter.
+    pub async fn transfer_leader(&self, id: NodeId) -> RaftResult<()> {
+        let header = TransferLeaderRequest { node_id: id };
+        let _: TransferLeaderResponse = self.leader_rpc(RaftCode::TransferLeader, header).await?;
+        Ok(())
+    }
+
+    // Get the leader and the members of the cluster from the leader.
+    pub async fn group_info(&self) -> RaftResult<PingResponse> {
+        self.leader_rpc(RaftCode::Ping, PingRequest::default())
+            .await
+    }
+
+    // Get a read index confirmed by the leader, the node `id` responds once it has applied the index.
+    pub async fn read_index(&self, id: NodeId) -> RaftResult<u64> {
+        let req = Builder::new_rpc(RaftCode::ReadIndex)
+            .proto_header(ReadIndexRequest::default())
+            .build();
+
+        let rep: ReadIndexResponse = self.timeout_rpc(id, req).await.map_err(|x| x.1)?;
+        Ok(rep.index)
+    }
+
+    // Wait until the node `id` has applied the index.
+    pub async fn wait_applied(&self, id: NodeId, index: u64) -> RaftResult<u64> {
+        let req = Builder::new_rpc(RaftCode::WaitApplied)
+            .proto_header(WaitAppliedRequest { index })
+            .build();
+
+        let rep: ReadIndexResponse = self.timeout_rpc(id, req).await.map_err(|x| x.1)?;
+        Ok(rep.index)
+    }
+
     pub async fn ping(&self, id: NodeId) -> RaftResult<PingResponse> {
         let header = PingRequest::default();
         let req = Builder::new_rpc(RaftCode::Ping)
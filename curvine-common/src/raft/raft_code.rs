@@ -24,4 +24,7 @@ pub enum RaftCode {
     Unreachable = 4,
     Raft = 5,
     SnapshotDownload = 6,
+    ReadIndex = 7,
+    TransferLeader = 8,
+    WaitApplied = 9,
 }
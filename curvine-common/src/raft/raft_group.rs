@@ -13,10 +13,13 @@
 // limitations under the License.
 
 use crate::conf::JournalConf;
-use crate::proto::raft::RaftPeerProto;
-use crate::raft::{NodeId, RaftPeer};
+use crate::proto::raft::{RaftPeerProto, SnapshotData};
+use crate::raft::storage::LogStorage;
+use crate::raft::{NodeId, PeerContext, RaftPeer, RaftResult};
 use orpc::io::net::InetAddr;
 use orpc::{err_box, CommonResult};
+use prost::Message;
+use raft::eraftpb::{ConfChange, ConfChangeType, EntryType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -48,10 +51,7 @@ impl RaftGroup {
     pub fn from_proto<T: AsRef<str>>(name: T, list: Vec<RaftPeerProto>) -> Self {
         let mut peers = HashMap::new();
         for item in list {
-            peers.insert(
-                item.node_id,
-                RaftPeer::new(item.node_id, item.hostname, item.port as u16),
-            );
+            peers.insert(item.node_id, RaftPeer::from_proto(item));
         }
 
         Self::new(name, peers)
@@ -60,11 +60,7 @@ impl RaftGroup {
     pub fn to_proto(&self) -> Vec<RaftPeerProto> {
         let mut vec = vec![];
         for peer in self.peers.values() {
-            vec.push(RaftPeerProto {
-                node_id: peer.id,
-                hostname: peer.hostname.to_string(),
-                port: peer.port as u32,
-            })
+            vec.push(peer.to_proto())
         }
         vec
     }
@@ -101,12 +97,74 @@ impl RaftGroup {
         self.peers.get(id).map(|x| x.to_addr())
     }
 
-    pub fn insert(&mut self, id: NodeId, addr: &InetAddr) {
-        let peer = RaftPeer::new(id, addr.hostname.clone(), addr.port);
+    pub fn insert(&mut self, peer: RaftPeer) {
         self.peers.insert(peer.id, peer);
     }
 
+    pub fn get_peer(&self, id: &NodeId) -> Option<&RaftPeer> {
+        self.peers.get(id)
+    }
+
+    pub fn get_peer_check(&self, id: &NodeId) -> CommonResult<RaftPeer> {
+        match self.peers.get(id) {
+            None => err_box!("Node {} not exists", id),
+            Some(v) => Ok(v.clone()),
+        }
+    }
+
+    pub fn is_observer(&self, id: &NodeId) -> bool {
+        self.peers.get(id).map(|x| x.observer).unwrap_or(false)
+    }
+
+    // The initial voters and learners of a new group, observers start as learners.
+    pub fn initial_members(&self) -> (Vec<NodeId>, Vec<NodeId>) {
+        let (learners, voters): (Vec<_>, Vec<_>) = self.peers.values().partition(|x| x.observer);
+        (
+            voters.into_iter().map(|x| x.id).collect(),
+            learners.into_iter().map(|x| x.id).collect(),
+        )
+    }
+
     pub fn remove(&mut self, id: &NodeId) {
         self.peers.remove(id);
     }
+
+    // Restore the members changed at runtime, e.g. the observers added by the admin command,
+    // journal_addrs only has the initial members. The snapshot records the members when it was
+    // created, and the conf changes after it are replayed from the log.
+    pub fn restore<S: LogStorage>(&mut self, log_store: &S) -> RaftResult<()> {
+        let mut index = 0;
+        if let Some(snapshot) = log_store.latest_snapshot()? {
+            let data = SnapshotData::decode(snapshot.get_data())?;
+            for peer in data.peers {
+                self.insert(RaftPeer::from_proto(peer));
+            }
+            index = snapshot.get_metadata().index;
+        }
+
+        loop {
+            let entries = log_store.scan_entries(index + 1, index + 1000)?;
+            let last = match entries.last() {
+                Some(v) => v.index,
+                None => break,
+            };
+
+            for entry in entries {
+                if entry.get_entry_type() != EntryType::EntryConfChange {
+                    continue;
+                }
+                let change = ConfChange::decode(entry.get_data())?;
+                match change.get_change_type() {
+                    ConfChangeType::AddNode | ConfChangeType::AddLearnerNode => {
+                        let ctx = PeerContext::decode(change.get_context())?;
+                        self.insert(ctx.to_peer(change.get_node_id()));
+                    }
+                    ConfChangeType::RemoveNode => self.remove(&change.get_node_id()),
+                }
+            }
+            index = last;
+        }
+
+        Ok(())
+    }
 }
@@ -64,7 +64,11 @@ where
     }
 
     pub async fn run(self) -> RaftResult<RoleStateListener> {
-        self.run_candidate().await
+        if self.conf.join_cluster {
+            self.run_follower().await
+        } else {
+            self.run_candidate().await
+        }
     }
 
     // Start the current raft node as a leader candidate node.
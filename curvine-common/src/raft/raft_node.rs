@@ -23,12 +23,11 @@ use crate::utils::SerdeUtils;
 use log::{error, info, warn};
 use orpc::client::dispatch::{Callback, Envelope};
 use orpc::common::{DurationUnit, LocalTime, TimeSpent};
-use orpc::io::net::InetAddr;
 use orpc::message::{Builder, RefMessage, ResponseStatus};
 use orpc::runtime::{RpcRuntime, Runtime};
 use orpc::try_err;
 use prost::Message as PMessage;
-use raft::eraftpb::{ConfChange, Entry, EntryType, MessageType, Snapshot};
+use raft::eraftpb::{ConfChange, ConfState, Entry, EntryType, MessageType, Snapshot};
 use raft::prelude::ConfChangeType;
 use raft::RawNode;
 use std::collections::HashMap;
@@ -75,6 +74,14 @@ where
 
     // Index of each node commit
     commit_info: HashMap<NodeId, u64>,
+
+    leader_contact: LeaderContact,
+
+    // Read index requests waiting for the leader to confirm the read index.
+    read_promise: HashMap<i64, Envelope>,
+
+    // Read index requests waiting for the confirmed index to be applied.
+    pending_reads: Vec<(u64, Envelope)>,
 }
 
 impl<A, B> RaftNode<A, B>
@@ -82,6 +89,9 @@ where
     A: LogStorage,
     B: AppStorage,
 {
+    // Request id of the proposals made by the node itself, nobody waits for their response.
+    const INTERNAL_REQ_ID: i64 = 0;
+
     // Create a leader node.
     pub async fn new_candidate(
         rt: Arc<Runtime>,
@@ -93,9 +103,10 @@ where
         sender: mpsc::Sender<Envelope>,
         logger: &slog::Logger,
     ) -> RaftResult<Self> {
-        let group = RaftGroup::from_conf(conf);
+        let mut group = RaftGroup::from_conf(conf);
         let id = group.get_node_id(&conf.local_addr())?;
-        let voters = group.peers.iter().map(|x| *x.0).collect();
+        group.restore(&log_store)?;
+        let (voters, learners) = group.initial_members();
 
         let client = RaftClient::new(rt.clone(), &group, conf.new_client_conf());
         let snapshot_interval_ms = DurationUnit::from_str(&conf.snapshot_interval)
@@ -105,13 +116,14 @@ where
         let tick_interval = Duration::from_millis(conf.raft_tick_interval_ms);
         let poll_interval = Duration::from_millis(conf.raft_poll_interval_ms);
 
-        let last_applied = Self::install_snapshot(&log_store, &app_store, voters)?;
+        let last_applied = Self::install_snapshot(&log_store, &app_store, voters, learners)?;
         let config = conf.new_raft_conf(id, last_applied);
         config.validate()?;
 
         let storage = PeerStorage::new(log_store, app_store, client.clone(), conf);
         let raw = RawNode::new(&config, storage.clone(), logger)?;
         // raw.raft.become_candidate();
+        let leader_contact = role_monitor.leader_contact();
 
         let node = Self {
             rt,
@@ -129,6 +141,9 @@ where
             last_snapshot_ms: LocalTime::mills(),
             last_snapshot_index: 0,
             commit_info: Default::default(),
+            leader_contact,
+            read_promise: Default::default(),
+            pending_reads: vec![],
         };
 
         Ok(node)
@@ -147,6 +162,7 @@ where
     ) -> RaftResult<Self> {
         let group = RaftGroup::from_conf(conf);
         let id = group.get_node_id(&conf.local_addr())?;
+        let mut local = group.get_peer_check(&id)?;
         let client = RaftClient::new(rt.clone(), &group, conf.new_client_conf());
         let snapshot_interval_ms = DurationUnit::from_str(&conf.snapshot_interval)
             .unwrap()
@@ -159,9 +175,36 @@ where
         let config = conf.new_raft_conf(id, 0);
         config.validate()?;
 
-        client.join_cluster(id, &conf.local_addr()).await?;
+        // The members of the running cluster are obtained from the leader,
+        // journal_addrs only needs to contain some of them.
+        let info = client.group_info().await?;
+        let mut group = RaftGroup::from_proto(&conf.group_name, info.group);
+        local.observer = local.observer || group.is_observer(&id);
+        group.insert(local.clone());
+        for peer in group.peers.values() {
+            client.add_node(peer.id, &peer.to_addr())?;
+        }
+
+        // A new node starts as a learner of the current members,
+        // the log replicated from the leader may not contain the initial members.
+        if log_store.initial_state()?.conf_state == ConfState::default() {
+            let mut conf_state = ConfState::default();
+            for peer in group.peers.keys() {
+                if *peer == id || info.learners.contains(peer) {
+                    conf_state.learners.push(*peer);
+                } else {
+                    conf_state.voters.push(*peer);
+                }
+            }
+            log_store.set_conf_state(&conf_state)?;
+        }
+
+        // Unlike the initial members, a node joining a running cluster is added as a learner,
+        // so it can not affect the quorum before it has caught up with the leader.
+        client.add_learner(&local).await?;
         let storage = PeerStorage::new(log_store, app_store, client.clone(), conf);
         let raw = RawNode::new(&config, storage.clone(), logger)?;
+        let leader_contact = role_monitor.leader_contact();
         let node = Self {
             rt,
             raw,
@@ -178,19 +221,28 @@ where
             last_snapshot_ms: LocalTime::mills(),
             last_snapshot_index: 0,
             commit_info: Default::default(),
+            leader_contact,
+            read_promise: Default::default(),
+            pending_reads: vec![],
         };
 
         Ok(node)
     }
 
     // Check whether recovery from snapshot is required.
-    pub fn install_snapshot(log_store: &A, app_store: &B, voters: Vec<u64>) -> RaftResult<u64> {
+    pub fn install_snapshot(
+        log_store: &A,
+        app_store: &B,
+        voters: Vec<u64>,
+        learners: Vec<u64>,
+    ) -> RaftResult<u64> {
         let spend = TimeSpent::new();
 
         let snapshot = match log_store.latest_snapshot()? {
             None => {
                 let mut snapshot = Snapshot::default();
                 snapshot.mut_metadata().mut_conf_state().voters = voters;
+                snapshot.mut_metadata().mut_conf_state().learners = learners;
 
                 log_store.apply_snapshot(snapshot.clone())?;
                 snapshot
@@ -198,6 +250,7 @@ where
 
             Some(mut snapshot) => {
                 snapshot.mut_metadata().mut_conf_state().voters = voters;
+                snapshot.mut_metadata().mut_conf_state().learners = learners;
                 // log store application snapshot.
                 log_store.apply_snapshot(snapshot.clone())?;
                 // app store app snapshot.
@@ -294,6 +347,7 @@ where
             if now.elapsed() >= self.tick_interval {
                 now = Instant::now();
                 self.raw.tick();
+                self.promote_learners()?;
             }
 
             // The raft state processing failed and the node directly reported an error.
@@ -323,15 +377,113 @@ where
         let mut change: ConfChange = header.change;
 
         if !self.is_leader() {
-            Self::send_not_leader(self.leader(), env, &self.group)?;
+            return Self::send_not_leader(self.leader(), env, &self.group);
+        }
+
+        if change.get_node_id() == 0 {
+            change.set_node_id(self.id())
+        }
+
+        let id = change.get_node_id();
+        let conf = self.raw.raft.prs().conf();
+        let is_member = conf.voters().contains(id) || conf.learners().contains(&id);
+        let error = match change.get_change_type() {
+            // The node is already in the cluster, e.g. a learner restarts and joins again.
+            ConfChangeType::AddNode | ConfChangeType::AddLearnerNode if is_member => {
+                let msg = Builder::success(&env.msg)
+                    .proto_header(ConfChangeResponse::default())
+                    .build();
+                env.send_with_log(Ok(msg));
+                return Ok(());
+            }
+
+            ConfChangeType::RemoveNode if !is_member => {
+                Some(format!("Node {} is not a member of the cluster", id))
+            }
+
+            ConfChangeType::RemoveNode if id == self.id() => Some(format!(
+                "Node {} is the leader, transfer the leadership before removing it",
+                id
+            )),
+
+            // raft-rs silently drops a conf change while another one is pending.
+            _ if self.raw.raft.has_pending_conf() => {
+                Some("Another configuration change is in progress".to_string())
+            }
+
+            _ => None,
+        };
+
+        if let Some(error) = error {
+            let msg = env.msg.error_ext(&RaftError::other(error.into()));
+            env.send_with_log(Ok(msg));
+            return Ok(());
+        }
+
+        let context = SerdeUtils::serialize(&env.msg.req_id())?;
+        self.raw.propose_conf_change(context, change)?;
+        promise.insert(env.msg.req_id(), env.cb);
+
+        Ok(())
+    }
+
+    fn handle_transfer_leader(&mut self, env: Envelope) -> RaftResult<()> {
+        if !self.is_leader() {
+            return Self::send_not_leader(self.leader(), env, &self.group);
+        }
+
+        let header: TransferLeaderRequest = env.msg.parse_header()?;
+        let id = header.node_id;
+        let msg = if !self.raw.raft.prs().conf().voters().contains(id) {
+            let error = RaftError::other(format!("Node {} is not a voter", id).into());
+            env.msg.error_ext(&error)
         } else {
-            if change.get_node_id() == 0 {
-                change.set_node_id(self.id())
+            // The transfer completes asynchronously, the leader stops accepting proposals meanwhile.
+            if id != self.id() {
+                info!("Raft transfer leadership from {} to {}", self.id(), id);
+                self.raw.transfer_leader(id);
             }
+            Builder::success(&env.msg)
+                .proto_header(TransferLeaderResponse::default())
+                .build()
+        };
+
+        env.send_with_log(Ok(msg));
+        Ok(())
+    }
 
-            let context = SerdeUtils::serialize(&env.msg.req_id())?;
+    // Promote the learners that have caught up with the leader's log to voters, one at a time.
+    // Observers stay learners.
+    fn promote_learners(&mut self) -> RaftResult<()> {
+        if !self.is_leader() || self.raw.raft.has_pending_conf() {
+            return Ok(());
+        }
+
+        let committed = self.raw.raft.raft_log.committed;
+        let prs = self.raw.raft.prs();
+        let learner = prs
+            .conf()
+            .learners()
+            .iter()
+            .filter(|x| !self.group.is_observer(*x))
+            .find(|x| {
+                prs.get(**x)
+                    .map(|p| p.matched >= committed)
+                    .unwrap_or(false)
+            })
+            .copied();
+
+        if let Some(id) = learner {
+            let peer = self.group.get_peer_check(&id)?.with_observer(false);
+            info!("Raft promoting learner {}({}) to voter", id, peer.to_addr());
+            let change = ConfChange {
+                change_type: ConfChangeType::AddNode.into(),
+                node_id: id,
+                context: PeerContext::from_peer(&peer).encode()?,
+                id,
+            };
+            let context = SerdeUtils::serialize(&Self::INTERNAL_REQ_ID)?;
             self.raw.propose_conf_change(context, change)?;
-            promise.insert(env.msg.req_id(), env.cb);
         }
 
         Ok(())
@@ -340,6 +492,9 @@ where
     // Handle raft internal messages, such as elections, heartbeats, voting, etc.
     fn handle_raft(&mut self, env: Envelope) -> RaftResult<()> {
         let raft: RaftRequest = try_err!(env.msg.parse_header());
+        if raft.message.from == self.leader() {
+            self.leader_contact.touch();
+        }
         self.commit_info
             .insert(raft.message.from, raft.message.commit);
         self.raw.step(raft.message)?;
@@ -379,10 +534,55 @@ where
         Ok(())
     }
 
+    // Ask the leader for the read index, followers forward the request to the leader.
+    // The response is sent after the read index has been applied locally.
+    fn handle_read_index(&mut self, env: Envelope) -> RaftResult<()> {
+        if self.leader() == DEFAULT_LEADER_ID {
+            return Self::send_not_leader(self.leader(), env, &self.group);
+        }
+
+        let req_id = env.msg.req_id();
+        self.raw.read_index(SerdeUtils::serialize(&req_id)?);
+        self.read_promise.insert(req_id, env);
+        Ok(())
+    }
+
+    // Respond once the index, confirmed by the leader beforehand, has been applied locally.
+    fn handle_wait_applied(&mut self, env: Envelope) -> RaftResult<()> {
+        let req: WaitAppliedRequest = try_err!(env.msg.parse_header());
+        self.pending_reads.push((req.index, env));
+        self.reply_reads();
+        Ok(())
+    }
+
+    fn reply_reads(&mut self) {
+        let applied = self.raw.raft.raft_log.applied;
+        let (ready, pending) = std::mem::take(&mut self.pending_reads)
+            .into_iter()
+            .partition(|x| x.0 <= applied);
+        self.pending_reads = pending;
+
+        for (index, env) in ready {
+            let msg = Builder::success(&env.msg)
+                .proto_header(ReadIndexResponse { index })
+                .build();
+            env.send_with_log(Ok(msg));
+        }
+    }
+
     fn handle_ping(&self, env: Envelope) -> RaftResult<()> {
         let header = PingResponse {
             leader_id: self.leader(),
             group: self.group.to_proto(),
+            learners: self
+                .raw
+                .raft
+                .prs()
+                .conf()
+                .learners()
+                .iter()
+                .copied()
+                .collect(),
         };
         let msg = Builder::success(&env.msg).proto_header(header).build();
 
@@ -402,6 +602,12 @@ where
 
             RaftCode::Ping => self.handle_ping(env),
 
+            RaftCode::ReadIndex => self.handle_read_index(env),
+
+            RaftCode::WaitApplied => self.handle_wait_applied(env),
+
+            RaftCode::TransferLeader => self.handle_transfer_leader(env),
+
             _ => {
                 let ext = env
                     .msg
@@ -438,6 +644,14 @@ where
                 .gen_apply_snapshot_job(ready.snapshot().clone())?;
         }
 
+        // Read index confirmed by the leader, wait for it to be applied.
+        for rs in ready.take_read_states() {
+            let req_id: i64 = SerdeUtils::deserialize(&rs.request_ctx)?;
+            if let Some(env) = self.read_promise.remove(&req_id) {
+                self.pending_reads.push((rs.index, env));
+            }
+        }
+
         // Get the committed log entries, that is, the messages confirmed by most nodes.
         // Only the leader will run.
         self.apply_committed_entries(ready.take_committed_entries(), promise)?;
@@ -460,6 +674,11 @@ where
                 self.leader()
             );
             self.role_monitor.advance_role(ss);
+
+            // Read requests may be dropped by the old leader, let the clients retry.
+            for (_, env) in self.read_promise.drain() {
+                Self::send_not_leader(self.raw.raft.leader_id, env, &self.group)?;
+            }
         }
 
         // Get the message that the leader has fallen into the disk and send these messages to other nodes.
@@ -482,6 +701,7 @@ where
         self.apply_committed_entries(light_rd.take_committed_entries(), promise)?;
 
         self.raw.advance_apply();
+        self.reply_reads();
 
         // Determine whether a snapshot is needed.
         self.apply_create_snapshot()?;
@@ -528,17 +748,19 @@ where
         let id = change.get_node_id();
 
         match change.get_change_type() {
-            ConfChangeType::AddNode => {
-                let addr: InetAddr = SerdeUtils::deserialize(change.get_context())?;
+            ConfChangeType::AddNode | ConfChangeType::AddLearnerNode => {
+                let peer = PeerContext::decode(change.get_context())?.to_peer(id);
                 info!(
-                    "Raft adding node: {}({}), current leader: {}({:?})",
+                    "Raft adding node: {}({}), {:?}, observer: {}, current leader: {}({:?})",
                     id,
-                    addr,
+                    peer.to_addr(),
+                    change.get_change_type(),
+                    peer.observer,
                     self.leader(),
                     self.group.get_addr(&self.leader())
                 );
-                self.group.insert(id, &addr);
-                self.client.add_node(id, &addr)?;
+                self.client.add_node(id, &peer.to_addr())?;
+                self.group.insert(peer);
             }
 
             ConfChangeType::RemoveNode => {
@@ -548,8 +770,6 @@ where
                     self.group.remove(&id);
                 }
             }
-
-            _ => unimplemented!(),
         }
 
         // When a new node joins, create a snapshot.
@@ -585,8 +805,12 @@ where
             }
 
             let compact_id = *(self.commit_info.values().min().unwrap_or(&0));
-            self.storage
-                .gen_create_snapshot_job(self.id(), last_applied, compact_id)?;
+            self.storage.gen_create_snapshot_job(
+                self.id(),
+                last_applied,
+                compact_id,
+                self.group.to_proto(),
+            )?;
 
             self.last_snapshot_ms = LocalTime::mills();
             self.last_snapshot_index = last_applied;
@@ -631,6 +855,8 @@ where
                         }
                     }
 
+                    None if req_id == Self::INTERNAL_REQ_ID => (),
+
                     None => {
                         warn!("Not found client for request {}", req_id)
                     }
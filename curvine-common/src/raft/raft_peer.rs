@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::proto::raft::RaftPeerProto;
 use crate::raft::NodeId;
+use crate::utils::SerdeUtils;
 use orpc::common::Utils;
 use orpc::io::net::InetAddr;
+use orpc::CommonResult;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
@@ -25,6 +28,12 @@ pub struct RaftPeer {
     pub id: NodeId,
     pub hostname: String,
     pub port: u16,
+    // An observer is a learner that is never promoted, it receives the log and snapshots
+    // without voting, so it can not affect the quorum or become the leader.
+    pub observer: bool,
+    // The port of the service replicated by the group on the same host, e.g. the master rpc port,
+    // clients use it to discover the nodes added at runtime. 0 if unknown.
+    pub service_port: u16,
 }
 
 impl RaftPeer {
@@ -33,9 +42,21 @@ impl RaftPeer {
             id,
             hostname: hostname.as_ref().to_string(),
             port,
+            observer: false,
+            service_port: 0,
         }
     }
 
+    pub fn with_observer(mut self, observer: bool) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    pub fn with_service_port(mut self, service_port: u16) -> Self {
+        self.service_port = service_port;
+        self
+    }
+
     pub fn from_addr<T: AsRef<str>>(hostname: T, port: u16) -> Self {
         let id = Self::create_id(format!("{}{}", hostname.as_ref(), port));
         Self::new(id, hostname, port)
@@ -48,6 +69,22 @@ impl RaftPeer {
     pub fn to_addr(&self) -> InetAddr {
         InetAddr::new(self.hostname.clone(), self.port)
     }
+
+    pub fn from_proto(proto: RaftPeerProto) -> Self {
+        Self::new(proto.node_id, proto.hostname, proto.port as u16)
+            .with_observer(proto.observer.unwrap_or(false))
+            .with_service_port(proto.service_port.unwrap_or(0) as u16)
+    }
+
+    pub fn to_proto(&self) -> RaftPeerProto {
+        RaftPeerProto {
+            node_id: self.id,
+            hostname: self.hostname.to_string(),
+            port: self.port as u32,
+            observer: Some(self.observer),
+            service_port: Some(self.service_port as u32),
+        }
+    }
 }
 
 impl Display for RaftPeer {
@@ -62,6 +99,53 @@ impl Default for RaftPeer {
             id: 0,
             hostname: "".to_string(),
             port: 0,
+            observer: false,
+            service_port: 0,
+        }
+    }
+}
+
+/// The context of a conf change that adds a node, it is replicated with the conf change
+/// so that every node knows the address of the new node and whether it is an observer.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PeerContext {
+    pub addr: InetAddr,
+    pub observer: bool,
+    pub service_port: u16,
+}
+
+impl PeerContext {
+    pub fn new(addr: &InetAddr, observer: bool) -> Self {
+        Self {
+            addr: addr.clone(),
+            observer,
+            service_port: 0,
+        }
+    }
+
+    pub fn from_peer(peer: &RaftPeer) -> Self {
+        Self {
+            addr: peer.to_addr(),
+            observer: peer.observer,
+            service_port: peer.service_port,
+        }
+    }
+
+    pub fn to_peer(&self, id: NodeId) -> RaftPeer {
+        RaftPeer::new(id, &self.addr.hostname, self.addr.port)
+            .with_observer(self.observer)
+            .with_service_port(self.service_port)
+    }
+
+    pub fn encode(&self) -> CommonResult<Vec<u8>> {
+        SerdeUtils::serialize(self)
+    }
+
+    // The context written by old versions only contains the address.
+    pub fn decode(bytes: &[u8]) -> CommonResult<Self> {
+        match SerdeUtils::deserialize::<Self>(bytes) {
+            Ok(v) => Ok(v),
+            Err(_) => Ok(Self::new(&SerdeUtils::deserialize(bytes)?, false)),
         }
     }
 }
@@ -54,6 +54,7 @@ impl RaftUtils {
             create_time: LocalTime::mills(),
             bytes_data: None,
             files_data: Some(list),
+            peers: vec![],
         };
 
         Ok(data)
@@ -13,9 +13,12 @@
 // limitations under the License.
 
 use num_enum::{FromPrimitive, IntoPrimitive};
+use orpc::common::LocalTime;
 use orpc::sync::{StateCtl, StateListener, StateMonitor};
 use orpc::CommonResult;
 use raft::{SoftState, StateRole};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 // raft node status.
 #[repr(i8)]
@@ -29,12 +32,34 @@ pub enum RoleState {
     Exit = 3,
 }
 
+// The last time a follower received a message from the leader.
+// It is used to estimate how stale the state of a follower is.
+#[derive(Clone, Default)]
+pub struct LeaderContact(Arc<AtomicU64>);
+
+impl LeaderContact {
+    pub fn touch(&self) {
+        self.0.store(LocalTime::mills(), Ordering::Relaxed)
+    }
+
+    // Milliseconds since the leader was last heard from, u64::MAX if never.
+    pub fn elapsed_ms(&self) -> u64 {
+        match self.0.load(Ordering::Relaxed) {
+            0 => u64::MAX,
+            v => LocalTime::mills().saturating_sub(v),
+        }
+    }
+}
+
 // Asynchronous Task Status Monitor.
-pub struct RoleMonitor(StateMonitor);
+pub struct RoleMonitor(StateMonitor, LeaderContact);
 
 impl RoleMonitor {
     pub fn new() -> Self {
-        Self(StateMonitor::new(RoleState::Init.into()))
+        Self(
+            StateMonitor::new(RoleState::Init.into()),
+            LeaderContact::default(),
+        )
     }
 
     // Node role conversion.
@@ -67,6 +92,10 @@ impl RoleMonitor {
         self.0.read_ctl()
     }
 
+    pub fn leader_contact(&self) -> LeaderContact {
+        self.1.clone()
+    }
+
     pub fn state(&self) -> RoleState {
         self.0.state()
     }
@@ -0,0 +1,460 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::raft::storage::file::LogSegment;
+use crate::raft::{RaftError, RaftResult, LOG_START_INDEX};
+use log::{info, warn};
+use orpc::common::FileUtils;
+use orpc::{err_box, err_ext};
+use prost::Message;
+use raft::eraftpb::{ConfState, Entry, HardState, Snapshot, SnapshotMetadata};
+use raft::{GetEntriesContext, RaftState};
+use std::cmp;
+use std::fs;
+
+/// Raft log storage based on segment files, the semantics are the same as RocksStorageCore.
+/// The directory contains:
+/// 1. log_{start_index}, the log segments, see LogSegment.
+/// 2. snapshot, the current snapshot.
+/// 3. first_index, the first index after the last compaction.
+pub struct FileStorageCore {
+    pub(crate) raft_state: RaftState,
+    pub(crate) snapshot_metadata: SnapshotMetadata,
+    dir: String,
+    segment_size: u64,
+    sync: bool,
+    segments: Vec<LogSegment>,
+    first_index: Option<u64>,
+
+    pub(crate) trigger_snap_unavailable: bool,
+    pub(crate) trigger_log_unavailable: bool,
+    pub(crate) get_entries_context: Option<GetEntriesContext>,
+}
+
+impl FileStorageCore {
+    pub const SNAP_FILE: &'static str = "snapshot";
+    pub const INDEX_FILE: &'static str = "first_index";
+
+    pub fn new<T: AsRef<str>>(
+        dir: T,
+        segment_size: u64,
+        sync: bool,
+        format: bool,
+    ) -> RaftResult<Self> {
+        let dir = dir.as_ref().to_string();
+        if format {
+            FileUtils::delete_path(&dir, true)?;
+        }
+        FileUtils::create_dir(&dir, true)?;
+
+        let mut core = Self {
+            raft_state: Default::default(),
+            snapshot_metadata: Default::default(),
+            dir,
+            segment_size,
+            sync,
+            segments: vec![],
+            first_index: None,
+            trigger_snap_unavailable: false,
+            trigger_log_unavailable: false,
+            get_entries_context: None,
+        };
+
+        core.first_index = core.read_first_index()?;
+        core.load_segments()?;
+
+        info!(
+            "Create file log storage success, dir: {}, format: {}, segments: {}, index range: [{}, {}]",
+            core.dir,
+            format,
+            core.segments.len(),
+            core.first_index(),
+            core.last_index()
+        );
+        Ok(core)
+    }
+
+    pub fn init_state(&mut self) -> RaftResult<RaftState> {
+        Ok(self.raft_state.clone())
+    }
+
+    // Get the term of the specified index, the entry must exist.
+    pub fn term(&self, index: u64) -> RaftResult<u64> {
+        match self.find_segment(index).and_then(|x| x.term(index)) {
+            None => err_box!("entry {} not exists", index),
+            Some(v) => Ok(v),
+        }
+    }
+
+    pub fn get_check(&self, index: u64) -> RaftResult<Entry> {
+        let mut entries = self.scan_entries(index, index + 1)?;
+        match entries.pop() {
+            Some(v) if v.index == index => Ok(v),
+            _ => err_box!("entry {} not exists", index),
+        }
+    }
+
+    pub fn has_entry_at(&self, index: u64) -> bool {
+        index >= self.first_index() && index <= self.last_index()
+    }
+
+    pub fn set_hard_state(&mut self, hs: HardState) -> RaftResult<()> {
+        self.raft_state.hard_state = hs;
+        Ok(())
+    }
+
+    pub fn set_hard_state_commit(&mut self, commit: u64) -> RaftResult<()> {
+        self.mut_hard_state().set_commit(commit);
+        Ok(())
+    }
+
+    pub fn hard_state(&self) -> &HardState {
+        &self.raft_state.hard_state
+    }
+
+    pub fn mut_hard_state(&mut self) -> &mut HardState {
+        &mut self.raft_state.hard_state
+    }
+
+    pub fn set_conf_state(&mut self, cs: ConfState) -> RaftResult<()> {
+        self.raft_state.conf_state = cs;
+        Ok(())
+    }
+
+    pub fn first_index(&self) -> u64 {
+        match self
+            .first_index
+            .or(self.segments.first().map(|x| x.start_index))
+        {
+            Some(index) => index,
+            None => self.snapshot_metadata.index + 1,
+        }
+    }
+
+    pub fn last_index(&self) -> u64 {
+        match self.segments.last() {
+            Some(segment) => segment.end_index(),
+            None => self.first_index() - 1,
+        }
+    }
+
+    pub fn apply_snapshot(&mut self, snapshot: Snapshot) -> RaftResult<()> {
+        let meta = snapshot.get_metadata();
+        let index = meta.index;
+
+        // Index is 0, indicating that there is no snapshot, but there may be log entry
+        if index > LOG_START_INDEX && self.first_index() > index {
+            return err_ext!(RaftError::raft(raft::Error::Store(
+                raft::StorageError::SnapshotOutOfDate
+            )));
+        }
+
+        // Non-initialized snapshots need to be saved.
+        if index > LOG_START_INDEX {
+            self.write_file(Self::SNAP_FILE, &snapshot.encode_to_vec())?;
+
+            // All logs are behind the snapshot, the next log will start after the snapshot.
+            if index > self.last_index() {
+                self.set_first_index(index + 1)?;
+                for segment in self.segments.drain(..) {
+                    segment.delete()?;
+                }
+            }
+        }
+
+        self.snapshot_metadata = meta.clone();
+        self.raft_state.hard_state.term = cmp::max(self.raft_state.hard_state.term, meta.term);
+        self.raft_state.hard_state.commit = index;
+        self.raft_state.conf_state = meta.get_conf_state().clone();
+
+        Ok(())
+    }
+
+    pub fn create_snapshot(&self, data: Vec<u8>, request_index: u64) -> RaftResult<Snapshot> {
+        let mut snapshot = Snapshot::default();
+        snapshot.set_data(data);
+        let meta = snapshot.mut_metadata();
+        meta.index = self.raft_state.hard_state.commit.min(request_index);
+        meta.term = match meta.index.cmp(&self.snapshot_metadata.index) {
+            cmp::Ordering::Equal => self.snapshot_metadata.term,
+            cmp::Ordering::Greater => self.term(meta.index).unwrap(),
+            cmp::Ordering::Less => {
+                panic!(
+                    "commit {} < snapshot_metadata.index {}",
+                    meta.index, self.snapshot_metadata.index
+                );
+            }
+        };
+
+        meta.set_conf_state(self.raft_state.conf_state.clone());
+        self.write_file(Self::SNAP_FILE, &snapshot.encode_to_vec())?;
+
+        Ok(snapshot)
+    }
+
+    // Get the latest snapshot.
+    pub fn last_snapshot(&self) -> RaftResult<Snapshot> {
+        let path = FileUtils::join_path(self.dir.as_str(), Self::SNAP_FILE);
+        if !FileUtils::exists(&path) {
+            let err = raft::Error::Store(raft::StorageError::SnapshotTemporarilyUnavailable);
+            return Err(RaftError::raft(err));
+        }
+
+        let mut snapshot = Snapshot::decode(&fs::read(&path)?[..])?;
+
+        // Solve the problem that the newly added node is not in conf_state, resulting in the snapshot not being referenced correctly.
+        if let Some(old_meta) = &snapshot.metadata {
+            let meta = SnapshotMetadata {
+                conf_state: Some(self.raft_state.conf_state.clone()),
+                index: old_meta.index,
+                term: old_meta.term,
+            };
+            snapshot.metadata = Some(meta);
+        }
+
+        Ok(snapshot)
+    }
+
+    // Only whole segments are deleted, the entries before compact_index in the remaining segment are hidden by first_index.
+    pub fn compact(&mut self, compact_index: u64) -> RaftResult<()> {
+        if compact_index <= self.first_index() {
+            // Don't need to treat this case as an error.
+            return Ok(());
+        }
+
+        if compact_index > self.last_index() + 1 {
+            panic!(
+                "compact not received raft logs: {}, last index: {}",
+                compact_index,
+                self.last_index()
+            );
+        }
+
+        // Save first_index before deleting files, a crash in between only leaves some segments that are no longer read.
+        self.set_first_index(compact_index)?;
+        self.remove_compacted()
+    }
+
+    pub fn set_entries(&mut self, entries: &[Entry]) -> RaftResult<()> {
+        // Delete historical data.
+        for segment in self.segments.drain(..) {
+            segment.delete()?;
+        }
+        FileUtils::delete_path(
+            FileUtils::join_path(self.dir.as_str(), Self::INDEX_FILE),
+            false,
+        )?;
+        self.first_index = None;
+
+        // Append new data.
+        self.write_entries(entries)
+    }
+
+    pub fn append(&mut self, entries: &[Entry]) -> RaftResult<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        if self.first_index() > entries[0].index {
+            panic!(
+                "overwrite compacted raft logs, compacted: {}, append: {}",
+                self.first_index() - 1,
+                entries[0].index,
+            );
+        }
+        if self.last_index() + 1 < entries[0].index {
+            panic!(
+                "raft logs should be continuous, last index: {}, new appended: {}",
+                self.last_index(),
+                entries[0].index,
+            );
+        }
+
+        // The conflicting entries are replaced by the new ones.
+        self.truncate(entries[0].index)?;
+        self.write_entries(entries)
+    }
+
+    pub fn trigger_snap_unavailable(&mut self) {
+        self.trigger_snap_unavailable = true;
+    }
+
+    pub fn trigger_log_unavailable(&mut self, v: bool) {
+        self.trigger_log_unavailable = v;
+    }
+
+    pub fn take_get_entries_context(&mut self) -> Option<GetEntriesContext> {
+        self.get_entries_context.take()
+    }
+
+    pub fn get_entries(&self, low: u64, high: u64) -> RaftResult<Vec<Entry>> {
+        if low < self.first_index() {
+            return err_ext!(RaftError::raft(raft::Error::Store(
+                raft::StorageError::Compacted
+            )));
+        }
+
+        if high > self.last_index() + 1 {
+            panic!(
+                "index out of bound (last: {}, high: {})",
+                self.last_index() + 1,
+                high
+            );
+        }
+        self.scan_entries(low, high)
+    }
+
+    // Read the existing entries in [low, high).
+    pub fn scan_entries(&self, low: u64, high: u64) -> RaftResult<Vec<Entry>> {
+        let low = cmp::max(low, self.first_index());
+        let high = cmp::min(high, self.last_index() + 1);
+        if low >= high {
+            return Ok(vec![]);
+        }
+
+        let mut vec = Vec::with_capacity((high - low) as usize);
+        for segment in &self.segments {
+            let start = cmp::max(low, segment.start_index);
+            let end = cmp::min(high, segment.next_index());
+            if start < end {
+                segment.read(start, end, &mut vec)?;
+            }
+        }
+
+        Ok(vec)
+    }
+
+    pub fn segments(&self) -> &[LogSegment] {
+        &self.segments
+    }
+
+    fn find_segment(&self, index: u64) -> Option<&LogSegment> {
+        let pos = self.segments.partition_point(|x| x.start_index <= index);
+        if pos == 0 {
+            None
+        } else {
+            Some(&self.segments[pos - 1])
+        }
+    }
+
+    fn write_entries(&mut self, mut entries: &[Entry]) -> RaftResult<()> {
+        while !entries.is_empty() {
+            let written = match self.segments.last_mut() {
+                Some(segment) => segment.append(entries, self.segment_size)?,
+                None => 0,
+            };
+
+            if written == 0 {
+                // The current segment is full, it is synced before rolling, so only the last segment may have a torn write.
+                if let Some(segment) = self.segments.last() {
+                    segment.sync()?;
+                }
+                let segment = LogSegment::create(&self.dir, entries[0].index, self.segment_size)?;
+                self.segments.push(segment);
+            }
+
+            entries = &entries[written..];
+        }
+
+        if self.sync {
+            if let Some(segment) = self.segments.last() {
+                segment.sync()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Delete the entries whose index >= index.
+    fn truncate(&mut self, index: u64) -> RaftResult<()> {
+        while let Some(segment) = self.segments.last_mut() {
+            if segment.start_index >= index {
+                self.segments.pop().unwrap().delete()?;
+            } else {
+                segment.truncate(index)?;
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    // Delete the segments that only contain compacted entries, the last segment is kept to continue writing.
+    fn remove_compacted(&mut self) -> RaftResult<()> {
+        let first_index = self.first_index();
+        while self.segments.len() > 1 && self.segments[0].end_index() < first_index {
+            self.segments.remove(0).delete()?;
+        }
+        Ok(())
+    }
+
+    fn set_first_index(&mut self, index: u64) -> RaftResult<()> {
+        self.write_file(Self::INDEX_FILE, &index.to_be_bytes())?;
+        let _ = self.first_index.replace(index);
+        Ok(())
+    }
+
+    fn read_first_index(&self) -> RaftResult<Option<u64>> {
+        let path = FileUtils::join_path(self.dir.as_str(), Self::INDEX_FILE);
+        if !FileUtils::exists(&path) {
+            return Ok(None);
+        }
+
+        let bytes = fs::read(&path)?;
+        match bytes.try_into() {
+            Ok(v) => Ok(Some(u64::from_be_bytes(v))),
+            Err(_) => err_box!("invalid first index file {}", path),
+        }
+    }
+
+    // Write the file through a temporary file and rename, the file is either the old or the new content.
+    fn write_file(&self, name: &str, bytes: &[u8]) -> RaftResult<()> {
+        let path = FileUtils::join_path(self.dir.as_str(), name);
+        let tmp = format!("{}.tmp", path);
+        fs::write(&tmp, bytes)?;
+        fs::File::open(&tmp)?.sync_all()?;
+        FileUtils::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    // Open all segments in index order.
+    // A segment that does not follow the previous one can only come from a write interrupted by a crash,
+    // it and the segments after it are deleted.
+    fn load_segments(&mut self) -> RaftResult<()> {
+        let mut starts = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(start) = LogSegment::parse_name(&name) {
+                starts.push((start, entry.path().to_string_lossy().to_string()));
+            }
+        }
+        starts.sort_by_key(|x| x.0);
+
+        for (start, path) in starts {
+            let expect = self.segments.last().map(|x| x.next_index());
+            if expect.is_some_and(|x| x != start) {
+                warn!(
+                    "Segment {} is discontinuous with the previous segment, expect start index {:?}, it will be deleted",
+                    path, expect
+                );
+                FileUtils::delete_path(&path, false)?;
+                continue;
+            }
+
+            let segment = LogSegment::open(path, start, self.segment_size)?;
+            self.segments.push(segment);
+        }
+
+        self.remove_compacted()
+    }
+}
@@ -12,76 +12,295 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::hash::Hasher;
-use std::io::{BufWriter, Write};
-use prost::bytes::BytesMut;
+use crate::raft::RaftResult;
+use log::warn;
+use orpc::common::FileUtils;
+use orpc::{err_box, sys};
 use prost::Message;
 use raft::eraftpb::Entry;
-use tokio_util::bytes::BufMut;
-use orpc::err_box;
-use orpc::os::LocalFile;
-use crate::raft::RaftResult;
+use std::fs::{File, OpenOptions};
+use std::os::unix::fs::FileExt;
 
+/// A raft log segment file, holding the continuous entries starting from start_index.
+/// Each entry is saved as a record: [len: u32][crc32 of payload: u32][payload: Entry]
+/// The file is preallocated, a zero length marks the end of the written records.
 pub struct LogSegment {
     pub(crate) path: String,
     pub(crate) start_index: u64,
-    pub(crate) end_index: u64,
-    pub(crate) file: Option<BufWriter<LocalFile>>,
-    pub(crate) cache: Vec<Entry>,
-    pub(crate) cur_index: u64,
-    pub(crate) checksum: crc32fast::Hasher,
+    file: File,
+    // The file offset and term of each entry, used to locate entries without reading the file.
+    offsets: Vec<u64>,
+    terms: Vec<u64>,
     pub(crate) write_len: u64,
+    prealloc_len: u64,
 }
 
 impl LogSegment {
-    pub fn get_last_entry(&self) -> Option<&Entry> {
-        self.cache.last()
+    pub const FILE_PREFIX: &'static str = "log_";
+    pub const HEADER_LEN: usize = 8;
+
+    pub fn file_name(start_index: u64) -> String {
+        format!("{}{:020}", Self::FILE_PREFIX, start_index)
     }
 
-    pub fn get_first_entry(&self) -> Option<&Entry> {
-        self.cache.first()
+    // Parse the start index from the segment file name, other files return None.
+    pub fn parse_name(name: &str) -> Option<u64> {
+        name.strip_prefix(Self::FILE_PREFIX)?.parse().ok()
     }
 
-    pub fn append(&mut self, mut entry: Entry) -> RaftResult<()> {
-       /* if let Some(v) = self.get_last_entry() {
-            if entry.index != v.index + 1 {
-                return err_box!("gap between entries {} and {}", entry.index, v.index)
+    pub fn create(dir: &str, start_index: u64, prealloc_len: u64) -> RaftResult<Self> {
+        let name = Self::file_name(start_index);
+        let path = FileUtils::join_path(dir, name.as_str());
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+
+        let segment = Self {
+            path,
+            start_index,
+            file,
+            offsets: vec![],
+            terms: vec![],
+            write_len: 0,
+            prealloc_len,
+        };
+        segment.preallocate()?;
+        Ok(segment)
+    }
+
+    // Open an existing segment and rebuild the entry index.
+    // Reading stops at the first record that is incomplete, fails the checksum or breaks the index order,
+    // it and everything after it is the residue of a write interrupted by a crash and will be cleared.
+    pub fn open(path: String, start_index: u64, prealloc_len: u64) -> RaftResult<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(&path)?;
+        let file_len = file.metadata()?.len();
+        let mut buf = vec![0u8; file_len as usize];
+        file.read_exact_at(&mut buf, 0)?;
+
+        let mut segment = Self {
+            path,
+            start_index,
+            file,
+            offsets: vec![],
+            terms: vec![],
+            write_len: 0,
+            prealloc_len,
+        };
+
+        let mut pos = 0;
+        while let Some((entry, len)) = Self::decode_record(&buf[pos..]) {
+            if entry.index != segment.next_index() {
+                break;
             }
+            segment.offsets.push(pos as u64);
+            segment.terms.push(entry.term);
+            pos += len;
+        }
+        segment.write_len = pos as u64;
+
+        if buf[pos..].iter().any(|x| *x != 0) {
+            warn!(
+                "Segment {} has a torn write at offset {}, the remaining {} bytes will be discarded",
+                segment.path,
+                pos,
+                file_len - pos as u64
+            );
+            segment.reset_tail()?;
         }
 
-        let len = entry.encoded_len();
-        let mut bytes = BytesMut::with_capacity(len + 8);
+        Ok(segment)
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn start_index(&self) -> u64 {
+        self.start_index
+    }
+
+    pub fn write_len(&self) -> u64 {
+        self.write_len
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    pub fn next_index(&self) -> u64 {
+        self.start_index + self.offsets.len() as u64
+    }
+
+    // The last index of the segment, an empty segment returns start_index - 1
+    pub fn end_index(&self) -> u64 {
+        self.next_index() - 1
+    }
 
-        bytes.put_u32(len as u32);
-        entry.encode(&mut bytes[4..4 + len])?;
+    pub fn contains(&self, index: u64) -> bool {
+        index >= self.start_index && index < self.next_index()
+    }
 
-        self.checksum.reset();
-        self.checksum.write(&mut bytes[4..4 + len]);
-        bytes.put_u32(self.checksum.finalize());
+    pub fn term(&self, index: u64) -> Option<u64> {
+        if self.contains(index) {
+            Some(self.terms[(index - self.start_index) as usize])
+        } else {
+            None
+        }
+    }
 
-        let file = match self.file.as_mut() {
-            None => return err_box!("Segment file {} already close", self.path),
-            Some(f) => f
+    // Write the entries to the end of the segment until the length exceeds limit, return the number of entries written.
+    // An empty segment always accepts the first entry, so an entry larger than limit still has a place.
+    pub fn append(&mut self, entries: &[Entry], limit: u64) -> RaftResult<usize> {
+        let mut buf = Vec::new();
+        let mut offsets = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let len = (Self::HEADER_LEN + entry.encoded_len()) as u64;
+            let offset = self.write_len + buf.len() as u64;
+            if offset + len > limit && offset > 0 {
+                break;
+            }
+            if entry.index != self.next_index() + offsets.len() as u64 {
+                return err_box!(
+                    "raft logs should be continuous, segment {} next index: {}, append: {}",
+                    self.path,
+                    self.next_index() + offsets.len() as u64,
+                    entry.index
+                );
+            }
+
+            Self::encode_record(entry, &mut buf);
+            offsets.push(offset);
+        }
+
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        self.file.write_all_at(&buf, self.write_len)?;
+        self.write_len += buf.len() as u64;
+        for (i, offset) in offsets.iter().enumerate() {
+            self.offsets.push(*offset);
+            self.terms.push(entries[i].term);
+        }
+
+        Ok(offsets.len())
+    }
+
+    // Read the entries of [low, high), the range must be within the segment.
+    pub fn read(&self, low: u64, high: u64, entries: &mut Vec<Entry>) -> RaftResult<()> {
+        if low >= high {
+            return Ok(());
+        }
+        if !self.contains(low) || !self.contains(high - 1) {
+            return err_box!(
+                "segment {} range [{}, {}) not contains [{}, {})",
+                self.path,
+                self.start_index,
+                self.next_index(),
+                low,
+                high
+            );
+        }
+
+        let start = self.offset(low);
+        let end = if high == self.next_index() {
+            self.write_len
+        } else {
+            self.offset(high)
         };
+        let mut buf = vec![0u8; (end - start) as usize];
+        self.file.read_exact_at(&mut buf, start)?;
+
+        let mut pos = 0;
+        for index in low..high {
+            match Self::decode_record(&buf[pos..]) {
+                Some((entry, len)) if entry.index == index => {
+                    entries.push(entry);
+                    pos += len;
+                }
 
-        file.write(&bytes)?;
-        self.write_len += len;
-        self.end_index = entry.index;
-        self.cache.push(entry);
+                _ => return err_box!("segment {} entry {} is corrupted", self.path, index),
+            }
+        }
+
+        Ok(())
+    }
+
+    // Delete the entries whose index >= index.
+    pub fn truncate(&mut self, index: u64) -> RaftResult<()> {
+        if index >= self.next_index() {
+            return Ok(());
+        }
+
+        let keep = index.saturating_sub(self.start_index) as usize;
+        self.write_len = if keep == 0 { 0 } else { self.offsets[keep] };
+        self.offsets.truncate(keep);
+        self.terms.truncate(keep);
+        self.reset_tail()
+    }
 
-        Ok(())*/
+    pub fn sync(&self) -> RaftResult<()> {
+        self.file.sync_data()?;
         Ok(())
     }
 
-    pub fn flush(&mut self) -> RaftResult<()> {
-        /*if let Some(w) = &mut self.file {
-            w.flush()?;
-        }*/
+    pub fn delete(self) -> RaftResult<()> {
+        drop(self.file);
+        FileUtils::delete_path(&self.path, false)?;
         Ok(())
     }
 
-    pub fn clear(&mut self) {
-        self.end_index = self.start_index - 1;
-        self.cache.clear();
+    fn offset(&self, index: u64) -> u64 {
+        self.offsets[(index - self.start_index) as usize]
+    }
+
+    fn preallocate(&self) -> RaftResult<()> {
+        if self.prealloc_len > self.write_len {
+            sys::fallocate(&self.file, 0, self.prealloc_len as i64, 0)?;
+        }
+        Ok(())
+    }
+
+    // Zero everything after write_len, so that the old records cannot be read back as valid ones.
+    fn reset_tail(&self) -> RaftResult<()> {
+        self.file.set_len(self.write_len)?;
+        self.preallocate()?;
+        self.file.sync_data()?;
+        Ok(())
+    }
+
+    fn encode_record(entry: &Entry, buf: &mut Vec<u8>) {
+        let payload = entry.encode_to_vec();
+        buf.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        buf.extend_from_slice(&crc32fast::hash(&payload).to_be_bytes());
+        buf.extend_from_slice(&payload);
+    }
+
+    // Decode a record at the beginning of buf, return the entry and the record length.
+    fn decode_record(buf: &[u8]) -> Option<(Entry, usize)> {
+        if buf.len() < Self::HEADER_LEN {
+            return None;
+        }
+
+        let len = u32::from_be_bytes(buf[0..4].try_into().unwrap()) as usize;
+        let crc = u32::from_be_bytes(buf[4..8].try_into().unwrap());
+        let end = Self::HEADER_LEN + len;
+        if len == 0 || buf.len() < end {
+            return None;
+        }
+
+        let payload = &buf[Self::HEADER_LEN..end];
+        if crc32fast::hash(payload) != crc {
+            return None;
+        }
+
+        Entry::decode(payload).ok().map(|entry| (entry, end))
     }
 }
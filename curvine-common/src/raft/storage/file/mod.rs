@@ -1,5 +1,3 @@
-
-
 // Copyright 2025 OPPO.
 //
 // Licensed under the Apache License, Version 2.0 (the "License");
@@ -14,5 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// @todo file pattern, the design may be relatively complicated and there is a lot of code. Consider using rocksdb to implement it first
-mod log_segment;
\ No newline at end of file
+// Raft log storage on preallocated and checksummed segment files.
+// It avoids the write amplification of rocksdb on the journal write path.
+mod log_segment;
+pub use self::log_segment::LogSegment;
+
+mod file_storage_core;
+pub use self::file_storage_core::FileStorageCore;
+
+mod file_log_storage;
+pub use self::file_log_storage::FileLogStorage;
@@ -98,6 +98,7 @@ where
             create_time: LocalTime::mills(),
             bytes_data: Some(bytes),
             files_data: None,
+            peers: vec![],
         };
         Ok(data)
     }
@@ -0,0 +1,149 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::conf::JournalConf;
+use crate::proto::raft::SnapshotData;
+use crate::raft::storage::{FileLogStorage, LogStorage, RocksLogStorage};
+use crate::raft::{LibRaftResult, RaftResult};
+use orpc::{err_box, CommonError};
+use raft::eraftpb::{ConfState, Entry, HardState, Snapshot};
+use raft::{GetEntriesContext, RaftState, Storage};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LogStorageType {
+    Rocks,
+    File,
+}
+
+impl TryFrom<&str> for LogStorageType {
+    type Error = CommonError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let typ = match value.to_lowercase().as_str() {
+            "rocksdb" | "rocks" => Self::Rocks,
+            "file" => Self::File,
+            _ => return err_box!("invalid log storage: {}", value),
+        };
+
+        Ok(typ)
+    }
+}
+
+/// The raft log storage of the journal, the implementation is selected by JournalConf.log_storage.
+#[derive(Clone)]
+pub enum JournalLogStorage {
+    Rocks(RocksLogStorage),
+    File(FileLogStorage),
+}
+
+macro_rules! dispatch {
+    ($self:expr, $s:ident => $e:expr) => {
+        match $self {
+            JournalLogStorage::Rocks($s) => $e,
+            JournalLogStorage::File($s) => $e,
+        }
+    };
+}
+
+impl JournalLogStorage {
+    pub fn from_conf(conf: &JournalConf, format: bool) -> RaftResult<Self> {
+        let storage = match LogStorageType::try_from(conf.log_storage.as_str())? {
+            LogStorageType::Rocks => Self::Rocks(RocksLogStorage::from_conf(conf, format)),
+            LogStorageType::File => Self::File(FileLogStorage::from_conf(conf, format)?),
+        };
+        Ok(storage)
+    }
+
+    pub fn storage_type(&self) -> LogStorageType {
+        match self {
+            Self::Rocks(_) => LogStorageType::Rocks,
+            Self::File(_) => LogStorageType::File,
+        }
+    }
+}
+
+impl LogStorage for JournalLogStorage {
+    fn append(&self, entries: &[Entry]) -> RaftResult<()> {
+        dispatch!(self, s => s.append(entries))
+    }
+
+    fn set_entries(&self, entries: &[Entry]) -> RaftResult<()> {
+        dispatch!(self, s => s.set_entries(entries))
+    }
+
+    fn scan_entries(&self, low: u64, high: u64) -> RaftResult<Vec<Entry>> {
+        dispatch!(self, s => s.scan_entries(low, high))
+    }
+
+    fn set_hard_state(&self, hard_state: &HardState) -> RaftResult<()> {
+        dispatch!(self, s => s.set_hard_state(hard_state))
+    }
+
+    fn set_hard_state_commit(&self, commit: u64) -> RaftResult<()> {
+        dispatch!(self, s => s.set_hard_state_commit(commit))
+    }
+
+    fn set_conf_state(&self, conf_state: &ConfState) -> RaftResult<()> {
+        dispatch!(self, s => s.set_conf_state(conf_state))
+    }
+
+    fn create_snapshot(&self, data: SnapshotData, request_index: u64) -> RaftResult<()> {
+        dispatch!(self, s => s.create_snapshot(data, request_index))
+    }
+
+    fn apply_snapshot(&self, snapshot: Snapshot) -> RaftResult<()> {
+        dispatch!(self, s => s.apply_snapshot(snapshot))
+    }
+
+    fn compact(&self, index: u64) -> RaftResult<()> {
+        dispatch!(self, s => s.compact(index))
+    }
+
+    fn trigger_snap_unavailable(&mut self) {
+        dispatch!(self, s => s.trigger_snap_unavailable())
+    }
+}
+
+impl Storage for JournalLogStorage {
+    fn initial_state(&self) -> LibRaftResult<RaftState> {
+        dispatch!(self, s => s.initial_state())
+    }
+
+    fn entries(
+        &self,
+        low: u64,
+        high: u64,
+        max_size: impl Into<Option<u64>>,
+        context: GetEntriesContext,
+    ) -> LibRaftResult<Vec<Entry>> {
+        let max_size = max_size.into();
+        dispatch!(self, s => s.entries(low, high, max_size, context))
+    }
+
+    fn term(&self, idx: u64) -> LibRaftResult<u64> {
+        dispatch!(self, s => s.term(idx))
+    }
+
+    fn first_index(&self) -> LibRaftResult<u64> {
+        dispatch!(self, s => s.first_index())
+    }
+
+    fn last_index(&self) -> LibRaftResult<u64> {
+        dispatch!(self, s => s.last_index())
+    }
+
+    fn snapshot(&self, request_index: u64, to: u64) -> LibRaftResult<Snapshot> {
+        dispatch!(self, s => s.snapshot(request_index, to))
+    }
+}
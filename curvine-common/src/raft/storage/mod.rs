@@ -30,5 +30,11 @@ pub use self::rocks_storage_core::RocksStorageCore;
 mod rocks_log_storage;
 pub use self::rocks_log_storage::RocksLogStorage;
 
+pub mod file;
+pub use self::file::FileLogStorage;
+
+mod journal_log_storage;
+pub use self::journal_log_storage::{JournalLogStorage, LogStorageType};
+
 mod peer_storage;
 pub use self::peer_storage::PeerStorage;
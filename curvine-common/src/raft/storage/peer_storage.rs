@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::conf::JournalConf;
-use crate::proto::raft::SnapshotData;
+use crate::proto::raft::{RaftPeerProto, SnapshotData};
 use crate::raft::snapshot::{DownloadJob, SnapshotState};
 use crate::raft::storage::{AppStorage, LogStorage};
 use crate::raft::{LibRaftResult, RaftClient, RaftError, RaftResult};
@@ -196,6 +196,7 @@ where
         node_id: u64,
         last_applied: u64,
         compact_id: u64,
+        peers: Vec<RaftPeerProto>,
     ) -> RaftResult<()> {
         if !self.can_generate_snapshot() {
             return err_box!("Currently creating snapshot");
@@ -211,7 +212,8 @@ where
         let job = move || {
             let cost = TimeSpent::new();
 
-            let snapshot = app_store.create_snapshot(node_id, last_applied)?;
+            let mut snapshot = app_store.create_snapshot(node_id, last_applied)?;
+            snapshot.peers = peers;
             let snapshot_id = snapshot.snapshot_id;
             log_store.create_snapshot(snapshot, last_applied)?;
             log_store.compact(compact_id)?;
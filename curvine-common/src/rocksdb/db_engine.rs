@@ -49,6 +49,28 @@ impl DBEngine {
         })
     }
 
+    // Open an existing db read-only, e.g. a checkpoint, the files are not changed.
+    // conf.data_dir is the db directory itself.
+    pub fn open_read_only(conf: DBConf) -> CommonResult<Self> {
+        let mut db_opt = conf.create_db_opt();
+        db_opt.create_if_missing(false);
+        let write_opt = conf.create_write_opt();
+        let cfs = conf.create_cf_opt();
+
+        let db = try_err!(DB::open_cf_with_opts_for_read_only(
+            &db_opt,
+            &conf.data_dir,
+            cfs,
+            false
+        ));
+        info!("Open rocksdb read-only success, conf: {:?}", conf);
+        Ok(Self {
+            db,
+            write_opt,
+            conf,
+        })
+    }
+
     pub fn from_dir<T: AsRef<str>>(dir: T, format: bool) -> CommonResult<Self> {
         let conf = DBConf::new(dir);
         Self::new(conf, format)
@@ -215,9 +237,7 @@ impl DBEngine {
             FileUtils::delete_path(&checkpoint_path, true)?;
         }
 
-        FileUtils::create_parent_dir(&checkpoint_path, true)?;
-        let checkpoint = try_err!(Checkpoint::new(&self.db));
-        checkpoint.create_checkpoint(&checkpoint_path)?;
+        self.create_checkpoint_to(&checkpoint_path)?;
 
         info!(
             "created checkpoint successfully, id: {}, path: {}, existed_before: {}",
@@ -227,6 +247,14 @@ impl DBEngine {
         Ok(checkpoint_path)
     }
 
+    // Create a checkpoint in the specified directory, the directory must not exist.
+    pub fn create_checkpoint_to(&self, path: &str) -> CommonResult<()> {
+        FileUtils::create_parent_dir(path, true)?;
+        let checkpoint = try_err!(Checkpoint::new(&self.db));
+        checkpoint.create_checkpoint(path)?;
+        Ok(())
+    }
+
     // Whether to recreate a database. Delete the previous directory and create a new directory.
     fn format(format: bool, conf: &DBConf) -> CommonResult<()> {
         let base_dir = &conf.base_dir;
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::state::{FileAllocOpts, FileStatus, FileType, StorageType, WorkerAddress};
+use crate::state::{EcPolicy, FileAllocOpts, FileStatus, FileType, StorageType, WorkerAddress};
 use crate::FsResult;
 use orpc::common::{ByteUnit, FastHashMap};
 use orpc::{err_box, CommonResult};
@@ -124,15 +124,56 @@ impl ExtendedBlock {
     }
 }
 
+// Layout of an erasure-coded block: locs[i] of the located block stores unit indices[i].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StripedBlock {
+    pub policy: EcPolicy,
+    pub indices: Vec<u32>,
+}
+
+impl StripedBlock {
+    pub fn new(policy: EcPolicy, indices: Vec<u32>) -> Self {
+        Self { policy, indices }
+    }
+
+    // The unit indices that have no live location.
+    pub fn missing_units(&self) -> Vec<u32> {
+        (0..self.policy.total_units())
+            .filter(|x| !self.indices.contains(x))
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct LocatedBlock {
     pub block: ExtendedBlock,
     pub locs: Vec<WorkerAddress>,
+    pub striped: Option<StripedBlock>,
 }
 
 impl LocatedBlock {
     pub fn new(block: ExtendedBlock, locs: Vec<WorkerAddress>) -> Self {
-        Self { block, locs }
+        Self {
+            block,
+            locs,
+            striped: None,
+        }
+    }
+
+    pub fn with_striped(
+        block: ExtendedBlock,
+        locs: Vec<WorkerAddress>,
+        striped: StripedBlock,
+    ) -> Self {
+        Self {
+            block,
+            locs,
+            striped: Some(striped),
+        }
+    }
+
+    pub fn is_striped(&self) -> bool {
+        self.striped.is_some()
     }
 
     pub fn should_resize(&self) -> bool {
@@ -0,0 +1,78 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::state::StorageType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The recursive summary of a path: the number of files and directories, and the bytes
+/// they use.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContentSummary {
+    pub length: i64,
+    pub file_count: i64,
+    // Including the directory itself.
+    pub directory_count: i64,
+    // The bytes of all the replicas, or of all the units of an erasure-coded file.
+    pub space_consumed: i64,
+    // The file bytes by the storage type of the files.
+    pub type_lengths: HashMap<StorageType, i64>,
+    // The space the path may use, the capacity of the cluster, -1 if it is not known.
+    pub space_quota: i64,
+    // The space left to the path, the available space of the cluster, -1 if it is not known.
+    pub space_quota_remaining: i64,
+}
+
+impl Default for ContentSummary {
+    fn default() -> Self {
+        Self {
+            length: 0,
+            file_count: 0,
+            directory_count: 0,
+            space_consumed: 0,
+            type_lengths: HashMap::new(),
+            space_quota: -1,
+            space_quota_remaining: -1,
+        }
+    }
+}
+
+impl ContentSummary {
+    pub fn add_file(&mut self, length: i64, space_consumed: i64, storage_type: StorageType) {
+        self.length += length;
+        self.file_count += 1;
+        self.space_consumed += space_consumed;
+        *self.type_lengths.entry(storage_type).or_insert(0) += length;
+    }
+
+    pub fn add_dir(&mut self) {
+        self.directory_count += 1;
+    }
+
+    // Only the usage is merged, the quota stays the one of self.
+    pub fn merge(&mut self, other: &ContentSummary) {
+        self.length += other.length;
+        self.file_count += other.file_count;
+        self.directory_count += other.directory_count;
+        self.space_consumed += other.space_consumed;
+        for (storage_type, length) in &other.type_lengths {
+            *self.type_lengths.entry(*storage_type).or_insert(0) += length;
+        }
+    }
+
+    // The number of inodes, the namespace usage of the path.
+    pub fn inode_count(&self) -> i64 {
+        self.file_count + self.directory_count
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::state::{CreateFileOpts, EcPolicy, MkdirOpts, StoragePolicy, StorageType, TtlAction};
+use serde::{Deserialize, Serialize};
+
+/// Default storage attributes of a directory.
+///
+/// Files and directories created under the directory take each attribute from the nearest
+/// ancestor that sets it, unless the client sets the attribute explicitly.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DirPolicy {
+    pub storage_type: Option<StorageType>,
+    pub replicas: Option<u16>,
+    pub block_size: Option<i64>,
+    pub ttl_ms: Option<i64>,
+    pub ttl_action: Option<TtlAction>,
+    pub ec_policy: Option<EcPolicy>,
+}
+
+impl DirPolicy {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    // Fill the attributes that are not set from the policy of an ancestor directory.
+    pub fn inherit(&mut self, parent: &DirPolicy) {
+        self.storage_type = self.storage_type.or(parent.storage_type);
+        self.replicas = self.replicas.or(parent.replicas);
+        self.block_size = self.block_size.or(parent.block_size);
+        self.ttl_ms = self.ttl_ms.or(parent.ttl_ms);
+        self.ttl_action = self.ttl_action.or(parent.ttl_action);
+        self.ec_policy = self.ec_policy.or(parent.ec_policy);
+    }
+
+    /// Apply the policy to a new file, the attributes set by the client are kept.
+    pub fn apply_file(&self, opts: &mut CreateFileOpts) {
+        let overrides = opts.overrides;
+        if let Some(v) = self
+            .replicas
+            .filter(|_| !overrides.contains(PolicyOverrides::REPLICAS))
+        {
+            opts.replicas = v;
+        }
+        if let Some(v) = self
+            .block_size
+            .filter(|_| !overrides.contains(PolicyOverrides::BLOCK_SIZE))
+        {
+            opts.block_size = v;
+        }
+        self.apply_storage(overrides, &mut opts.storage_policy);
+    }
+
+    /// Apply the policy to a new directory, the attributes set by the client are kept.
+    pub fn apply_dir(&self, opts: &mut MkdirOpts) {
+        self.apply_storage(opts.overrides, &mut opts.storage_policy);
+    }
+
+    fn apply_storage(&self, overrides: PolicyOverrides, policy: &mut StoragePolicy) {
+        if let Some(v) = self
+            .storage_type
+            .filter(|_| !overrides.contains(PolicyOverrides::STORAGE_TYPE))
+        {
+            policy.storage_type = v;
+        }
+        if let Some(v) = self
+            .ttl_ms
+            .filter(|_| !overrides.contains(PolicyOverrides::TTL_MS))
+        {
+            policy.ttl_ms = v;
+        }
+        if let Some(v) = self
+            .ttl_action
+            .filter(|_| !overrides.contains(PolicyOverrides::TTL_ACTION))
+        {
+            policy.ttl_action = v;
+        }
+        if self.ec_policy.is_some() && !overrides.contains(PolicyOverrides::EC_POLICY) {
+            policy.ec_policy = self.ec_policy;
+        }
+    }
+}
+
+/// The storage attributes of a create or mkdir request that were set explicitly by the client,
+/// they are not replaced by the directory policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PolicyOverrides(u32);
+
+impl PolicyOverrides {
+    pub const STORAGE_TYPE: Self = Self(1);
+    pub const REPLICAS: Self = Self(1 << 1);
+    pub const BLOCK_SIZE: Self = Self(1 << 2);
+    pub const TTL_MS: Self = Self(1 << 3);
+    pub const TTL_ACTION: Self = Self(1 << 4);
+    pub const EC_POLICY: Self = Self(1 << 5);
+
+    pub fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0
+    }
+
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
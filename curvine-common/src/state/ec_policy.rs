@@ -0,0 +1,180 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use orpc::common::ByteUnit;
+use orpc::{err_box, CommonError, CommonResult};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Reed-Solomon erasure coding policy.
+///
+/// A logical block is cut into cells of `cell_size` bytes which are striped round-robin
+/// over `data_units` workers; every stripe also produces `parity_units` parity cells.
+/// Each unit is stored as a regular block on a distinct worker, and any `data_units`
+/// of the `data_units + parity_units` units are enough to rebuild the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EcPolicy {
+    pub data_units: u32,
+    pub parity_units: u32,
+    pub cell_size: i64,
+}
+
+impl EcPolicy {
+    pub const DEFAULT_CELL_SIZE: i64 = ByteUnit::MB as i64;
+
+    // The GF(2^8) codec supports at most 255 units in total.
+    pub const MAX_UNITS: u32 = 255;
+
+    pub fn new(data_units: u32, parity_units: u32, cell_size: i64) -> CommonResult<Self> {
+        let policy = Self {
+            data_units,
+            parity_units,
+            cell_size,
+        };
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    pub fn rs(data_units: u32, parity_units: u32) -> CommonResult<Self> {
+        Self::new(data_units, parity_units, Self::DEFAULT_CELL_SIZE)
+    }
+
+    pub fn validate(&self) -> CommonResult<()> {
+        if self.data_units == 0 || self.parity_units == 0 {
+            return err_box!(
+                "Invalid ec policy {}: data and parity units must be > 0",
+                self
+            );
+        }
+        if self.total_units() > Self::MAX_UNITS {
+            return err_box!(
+                "Invalid ec policy {}: total units must be <= {}",
+                self,
+                Self::MAX_UNITS
+            );
+        }
+        if self.cell_size <= 0 {
+            return err_box!("Invalid ec policy {}: cell size must be > 0", self);
+        }
+        Ok(())
+    }
+
+    pub fn total_units(&self) -> u32 {
+        self.data_units + self.parity_units
+    }
+
+    /// The number of data bytes in one full stripe.
+    pub fn stripe_size(&self) -> i64 {
+        self.cell_size * self.data_units as i64
+    }
+
+    pub fn is_parity(&self, index: usize) -> bool {
+        index >= self.data_units as usize
+    }
+
+    /// The number of bytes stored by the unit `index` for a block of logical length `block_len`.
+    pub fn unit_len(&self, block_len: i64, index: usize) -> i64 {
+        let stripe_size = self.stripe_size();
+        let full = (block_len / stripe_size) * self.cell_size;
+        let rem = block_len % stripe_size;
+        let tail = if self.is_parity(index) {
+            rem.min(self.cell_size)
+        } else {
+            (rem - index as i64 * self.cell_size).clamp(0, self.cell_size)
+        };
+        full + tail
+    }
+
+    /// The capacity of a single unit for blocks of `block_size` bytes.
+    pub fn unit_capacity(&self, block_size: i64) -> i64 {
+        self.unit_len(block_size, 0)
+    }
+}
+
+impl Display for EcPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "RS-{}-{}-{}k",
+            self.data_units,
+            self.parity_units,
+            self.cell_size / ByteUnit::KB as i64
+        )
+    }
+}
+
+// Parse policy names such as "RS-6-3" or "RS-6-3-1024k".
+impl TryFrom<&str> for EcPolicy {
+    type Error = CommonError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let parts: Vec<&str> = value.trim().split('-').collect();
+        if parts.len() < 3 || parts.len() > 4 || !parts[0].eq_ignore_ascii_case("RS") {
+            return err_box!(
+                "invalid ec policy: {}, expected format RS-<k>-<m>[-<cell>]",
+                value
+            );
+        }
+
+        let data_units = match parts[1].parse::<u32>() {
+            Ok(v) => v,
+            Err(_) => return err_box!("invalid ec policy data units: {}", value),
+        };
+        let parity_units = match parts[2].parse::<u32>() {
+            Ok(v) => v,
+            Err(_) => return err_box!("invalid ec policy parity units: {}", value),
+        };
+        let cell_size = match parts.get(3) {
+            Some(v) => ByteUnit::from_str(v)?.as_byte() as i64,
+            None => Self::DEFAULT_CELL_SIZE,
+        };
+
+        Self::new(data_units, parity_units, cell_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::EcPolicy;
+
+    #[test]
+    fn parse() {
+        let policy = EcPolicy::try_from("RS-6-3-1024k").unwrap();
+        assert_eq!(policy, EcPolicy::new(6, 3, 1024 * 1024).unwrap());
+        assert_eq!(policy.to_string(), "RS-6-3-1024k");
+
+        let policy = EcPolicy::try_from("rs-3-2").unwrap();
+        assert_eq!(policy.cell_size, EcPolicy::DEFAULT_CELL_SIZE);
+
+        assert!(EcPolicy::try_from("RS-0-2").is_err());
+        assert!(EcPolicy::try_from("XOR-2-1").is_err());
+        assert!(EcPolicy::try_from("RS-200-100").is_err());
+    }
+
+    #[test]
+    fn unit_len() {
+        let policy = EcPolicy::new(3, 2, 10).unwrap();
+        // 2 full stripes + 15 bytes: units get 10, 5, 0 bytes of the last stripe.
+        let len = 2 * 30 + 15;
+        assert_eq!(policy.unit_len(len, 0), 30);
+        assert_eq!(policy.unit_len(len, 1), 25);
+        assert_eq!(policy.unit_len(len, 2), 20);
+        assert_eq!(policy.unit_len(len, 3), 30);
+        assert_eq!(policy.unit_len(len, 4), 30);
+
+        let total: i64 = (0..3).map(|i| policy.unit_len(len, i)).sum();
+        assert_eq!(total, len);
+        assert_eq!(policy.unit_capacity(90), 30);
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+use serde::{Deserialize, Serialize};
+
+/// The problem of a block found by fsck.
+#[repr(i32)]
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, IntoPrimitive, FromPrimitive,
+)]
+pub enum FsckBlockState {
+    // No replica or unit is on a live worker.
+    #[num_enum(default)]
+    Missing = 0,
+    // An erasure-coded block with fewer live units than data units, it can not be decoded,
+    // or a replicated block whose live replicas all have another length than the committed one.
+    Corrupt = 1,
+    UnderReplicated = 2,
+    OverReplicated = 3,
+}
+
+/// What fsck does with the corrupted files, the files that have missing or corrupt blocks.
+#[repr(i32)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    IntoPrimitive,
+    FromPrimitive,
+    Default,
+)]
+pub enum FsckAction {
+    #[default]
+    None = 0,
+    Delete = 1,
+    // Move to the same path under /lost+found.
+    Move = 2,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FsckOpts {
+    pub action: FsckAction,
+    // Check the blocks on the workers that no file refers to, it reads the blocks of all workers.
+    // They are checked on the last page.
+    pub check_orphans: bool,
+    // The next_start_after of the previous page.
+    pub start_after: Option<String>,
+    // The maximum number of files with problems and of orphan blocks in a page, 0 means no limit.
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FsckBlock {
+    pub block_id: i64,
+    pub len: i64,
+    pub state: FsckBlockState,
+    // The replicas or units on live workers.
+    pub live: i32,
+    pub expected: i32,
+}
+
+/// A file with problems, only the unhealthy blocks are kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FsckFile {
+    pub path: String,
+    pub len: i64,
+    // The blocks of a file being written are not checked.
+    pub open_for_write: bool,
+    pub blocks: Vec<FsckBlock>,
+}
+
+impl FsckFile {
+    pub fn is_corrupted(&self) -> bool {
+        self.blocks
+            .iter()
+            .any(|x| matches!(x.state, FsckBlockState::Missing | FsckBlockState::Corrupt))
+    }
+}
+
+/// A block on a worker that no file refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FsckOrphanBlock {
+    pub worker_id: u32,
+    pub block_id: i64,
+}
+
+/// The result of a fsck, the totals of the checked path and the files with problems.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FsckReport {
+    pub total_dirs: i64,
+    pub total_files: i64,
+    pub total_size: i64,
+    pub total_blocks: i64,
+
+    pub missing_blocks: i64,
+    pub corrupt_blocks: i64,
+    pub under_replicated_blocks: i64,
+    pub over_replicated_blocks: i64,
+    pub open_files: i64,
+    pub corrupt_files: i64,
+    // The corrupted files that were deleted or moved to /lost+found.
+    pub handled_files: i64,
+
+    pub files: Vec<FsckFile>,
+    // All the orphan blocks found, only the first limit of them are in orphan_blocks.
+    pub orphan_count: i64,
+    pub orphan_blocks: Vec<FsckOrphanBlock>,
+
+    // The path the next page starts after, None on the last page.
+    pub next_start_after: Option<String>,
+}
+
+impl FsckReport {
+    pub fn add_file(&mut self, file: FsckFile) {
+        for block in &file.blocks {
+            match block.state {
+                FsckBlockState::Missing => self.missing_blocks += 1,
+                FsckBlockState::Corrupt => self.corrupt_blocks += 1,
+                FsckBlockState::UnderReplicated => self.under_replicated_blocks += 1,
+                FsckBlockState::OverReplicated => self.over_replicated_blocks += 1,
+            }
+        }
+        if file.open_for_write {
+            self.open_files += 1;
+        }
+        if file.is_corrupted() {
+            self.corrupt_files += 1;
+        }
+        self.files.push(file);
+    }
+
+    // Add up the totals of a page, the files and orphans are not kept.
+    pub fn merge(&mut self, other: &FsckReport) {
+        self.total_dirs += other.total_dirs;
+        self.total_files += other.total_files;
+        self.total_size += other.total_size;
+        self.total_blocks += other.total_blocks;
+        self.missing_blocks += other.missing_blocks;
+        self.corrupt_blocks += other.corrupt_blocks;
+        self.under_replicated_blocks += other.under_replicated_blocks;
+        self.over_replicated_blocks += other.over_replicated_blocks;
+        self.open_files += other.open_files;
+        self.corrupt_files += other.corrupt_files;
+        self.handled_files += other.handled_files;
+        self.orphan_count += other.orphan_count;
+    }
+
+    // Replication problems and open files do not lose data, the path is still healthy.
+    pub fn is_healthy(&self) -> bool {
+        self.corrupt_files == 0
+    }
+}
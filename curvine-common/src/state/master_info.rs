@@ -37,6 +37,24 @@ pub struct MasterInfo {
 }
 
 impl MasterInfo {
+    // Add the capacity, inode counts and workers of another namespace, the masters are kept.
+    pub fn merge(&mut self, other: &MasterInfo) {
+        self.inode_dir_num += other.inode_dir_num;
+        self.inode_file_num += other.inode_file_num;
+        self.block_num += other.block_num;
+        self.capacity += other.capacity;
+        self.available += other.available;
+        self.fs_used += other.fs_used;
+        self.non_fs_used += other.non_fs_used;
+        self.reserved_bytes += other.reserved_bytes;
+        self.live_workers.extend(other.live_workers.iter().cloned());
+        self.blacklist_workers
+            .extend(other.blacklist_workers.iter().cloned());
+        self.decommission_workers
+            .extend(other.decommission_workers.iter().cloned());
+        self.lost_workers.extend(other.lost_workers.iter().cloned());
+    }
+
     pub fn journal_nodes_count(&self) -> usize {
         self.journal_nodes.len()
     }
@@ -53,3 +71,21 @@ impl MasterInfo {
         self.lost_workers.get(index)
     }
 }
+
+// A master of the raft group, the address is its journal address.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct MasterNode {
+    pub id: u64,
+    pub hostname: String,
+    pub port: u16,
+    pub learner: bool,
+    pub observer: bool,
+    // The rpc port of the master on the same host, clients connect to it.
+    pub rpc_port: u16,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct MasterGroup {
+    pub leader_id: u64,
+    pub masters: Vec<MasterNode>,
+}
@@ -0,0 +1,51 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+
+/// A metadata backup uploaded to the backup directory.
+/// The backup is a rocksdb checkpoint of the metadata, it contains all the journal entries up to journal_index.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetaBackupInfo {
+    pub name: String,
+    pub journal_index: u64,
+    pub journal_term: u64,
+    pub create_time: i64,
+    pub files: u64,
+    pub len: u64,
+}
+
+impl MetaBackupInfo {
+    pub const NAME_PREFIX: &'static str = "backup-";
+
+    // The backup name is sortable by time, e.g. backup-20250101120000000-00000000000000012345
+    pub fn backup_name(create_time: i64, journal_index: u64) -> String {
+        let time = chrono::DateTime::from_timestamp_millis(create_time)
+            .unwrap_or_default()
+            .format("%Y%m%d%H%M%S%3f");
+        format!("{}{}-{:020}", Self::NAME_PREFIX, time, journal_index)
+    }
+}
+
+/// The state of the metadata backups on the leader, a backup runs in the background.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetaBackupStatus {
+    pub running: bool,
+    // The start time of the running or the last backup.
+    pub start_time: i64,
+    // The last backup that succeeded.
+    pub last_backup: Option<MetaBackupInfo>,
+    // The error of the last backup, none if it succeeded.
+    pub last_error: Option<String>,
+}
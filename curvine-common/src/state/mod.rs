@@ -39,14 +39,32 @@ pub use self::client_address::ClientAddress;
 mod storage_policy;
 pub use self::storage_policy::*;
 
+mod ec_policy;
+pub use self::ec_policy::EcPolicy;
+
+mod dir_policy;
+pub use self::dir_policy::*;
+
+mod read_consistency;
+pub use self::read_consistency::ReadConsistency;
+
 mod block_info;
 pub use self::block_info::*;
 
 mod file_status;
 pub use self::file_status::FileStatus;
 
+mod content_summary;
+pub use self::content_summary::ContentSummary;
+
+mod fsck;
+pub use self::fsck::*;
+
+mod meta_backup;
+pub use self::meta_backup::{MetaBackupInfo, MetaBackupStatus};
+
 mod master_info;
-pub use self::master_info::MasterInfo;
+pub use self::master_info::{MasterGroup, MasterInfo, MasterNode};
 
 mod worker_command;
 pub use self::worker_command::*;
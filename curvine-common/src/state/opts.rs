@@ -31,6 +31,7 @@ pub struct CreateFileOpts {
     pub client_name: String,
     pub owner: String,
     pub group: String,
+    pub overrides: PolicyOverrides,
 }
 
 impl CreateFileOpts {
@@ -46,6 +47,7 @@ impl CreateFileOpts {
             mode: ClientConf::DEFAULT_FILE_SYSTEM_MODE,
             owner: "".to_string(),
             group: "".to_string(),
+            overrides: PolicyOverrides::default(),
         }
     }
 
@@ -57,6 +59,7 @@ impl CreateFileOpts {
             mode: self.mode,
             owner: self.owner.clone(),
             group: self.group.clone(),
+            overrides: PolicyOverrides::default(),
         }
     }
 }
@@ -73,6 +76,7 @@ pub struct CreateFileOptsBuilder {
     client_name: Option<String>,
     pub owner: String,
     pub group: String,
+    overrides: PolicyOverrides,
 }
 
 impl Default for CreateFileOptsBuilder {
@@ -94,6 +98,7 @@ impl CreateFileOptsBuilder {
             client_name: None,
             owner: "".to_string(),
             group: "".to_string(),
+            overrides: PolicyOverrides::default(),
         }
     }
 
@@ -108,12 +113,14 @@ impl CreateFileOptsBuilder {
                 storage_type: conf.storage_type,
                 ttl_ms: conf.ttl_ms,
                 ttl_action: conf.ttl_action,
+                ec_policy: conf.ec_policy,
                 ..Default::default()
             },
             mode: conf.get_mode(),
             client_name: None,
             owner: "".to_string(),
             group: "".to_string(),
+            overrides: PolicyOverrides::default(),
         }
     }
 
@@ -124,11 +131,13 @@ impl CreateFileOptsBuilder {
 
     pub fn replicas(mut self, replicas: i32) -> Self {
         self.replicas = replicas;
+        self.overrides.insert(PolicyOverrides::REPLICAS);
         self
     }
 
     pub fn block_size(mut self, block_size: i64) -> Self {
         self.block_size = block_size;
+        self.overrides.insert(PolicyOverrides::BLOCK_SIZE);
         self
     }
 
@@ -144,16 +153,25 @@ impl CreateFileOptsBuilder {
 
     pub fn storage_type(mut self, t: StorageType) -> Self {
         self.storage_policy.storage_type = t;
+        self.overrides.insert(PolicyOverrides::STORAGE_TYPE);
         self
     }
 
     pub fn ttl_ms(mut self, ms: i64) -> Self {
         self.storage_policy.ttl_ms = ms;
+        self.overrides.insert(PolicyOverrides::TTL_MS);
         self
     }
 
     pub fn ttl_action(mut self, action: TtlAction) -> Self {
         self.storage_policy.ttl_action = action;
+        self.overrides.insert(PolicyOverrides::TTL_ACTION);
+        self
+    }
+
+    pub fn ec_policy(mut self, policy: Option<EcPolicy>) -> Self {
+        self.storage_policy.ec_policy = policy;
+        self.overrides.insert(PolicyOverrides::EC_POLICY);
         self
     }
 
@@ -201,6 +219,7 @@ impl CreateFileOptsBuilder {
             client_name: self.client_name.unwrap_or_default(),
             owner: self.owner,
             group: self.group,
+            overrides: self.overrides,
         }
     }
 }
@@ -213,6 +232,7 @@ pub struct MkdirOpts {
     pub mode: u32,
     pub owner: String,
     pub group: String,
+    pub overrides: PolicyOverrides,
 }
 
 impl MkdirOpts {
@@ -224,6 +244,7 @@ impl MkdirOpts {
             mode: ClientConf::DEFAULT_FILE_SYSTEM_MODE,
             owner: "".to_string(),
             group: "".to_string(),
+            overrides: PolicyOverrides::default(),
         }
     }
 
@@ -235,6 +256,7 @@ impl MkdirOpts {
             mode: self.mode,
             owner: self.owner.clone(),
             group: self.group.clone(),
+            overrides: PolicyOverrides::default(),
         }
     }
 }
@@ -246,6 +268,7 @@ pub struct MkdirOptsBuilder {
     mode: u32,
     pub owner: String,
     pub group: String,
+    overrides: PolicyOverrides,
 }
 
 impl Default for MkdirOptsBuilder {
@@ -263,6 +286,7 @@ impl MkdirOptsBuilder {
             mode: ClientConf::DEFAULT_FILE_SYSTEM_MODE,
             owner: "".to_string(),
             group: "".to_string(),
+            overrides: PolicyOverrides::default(),
         }
     }
 
@@ -274,11 +298,13 @@ impl MkdirOptsBuilder {
                 storage_type: conf.storage_type,
                 ttl_ms: conf.ttl_ms,
                 ttl_action: conf.ttl_action,
+                ec_policy: conf.ec_policy,
                 ..Default::default()
             },
             mode: conf.get_mode(),
             owner: "".to_string(),
             group: "".to_string(),
+            overrides: PolicyOverrides::default(),
         }
     }
 
@@ -294,16 +320,25 @@ impl MkdirOptsBuilder {
 
     pub fn storage_type(mut self, t: StorageType) -> Self {
         self.storage_policy.storage_type = t;
+        self.overrides.insert(PolicyOverrides::STORAGE_TYPE);
         self
     }
 
     pub fn ttl_ms(mut self, ms: i64) -> Self {
         self.storage_policy.ttl_ms = ms;
+        self.overrides.insert(PolicyOverrides::TTL_MS);
         self
     }
 
     pub fn ttl_action(mut self, action: TtlAction) -> Self {
         self.storage_policy.ttl_action = action;
+        self.overrides.insert(PolicyOverrides::TTL_ACTION);
+        self
+    }
+
+    pub fn ec_policy(mut self, policy: Option<EcPolicy>) -> Self {
+        self.storage_policy.ec_policy = policy;
+        self.overrides.insert(PolicyOverrides::EC_POLICY);
         self
     }
 
@@ -337,10 +372,27 @@ impl MkdirOptsBuilder {
             mode: self.mode,
             owner: self.owner,
             group: self.group,
+            overrides: self.overrides,
         }
     }
 }
 
+/// The predicates of a find, an entry is returned if it matches all the set ones.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FindOpts {
+    // A glob or the exact name, e.g. *.log.
+    pub name: Option<String>,
+    pub file_type: Option<FileType>,
+    // Size range of files, directories never match a size predicate.
+    pub min_size: Option<i64>,
+    pub max_size: Option<i64>,
+    // Modification time range in milliseconds.
+    pub mtime_after: Option<i64>,
+    pub mtime_before: Option<i64>,
+    // The depth below the path to search, 0 means the path only.
+    pub max_depth: Option<i32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SetAttrOpts {
     pub recursive: bool,
@@ -357,6 +409,12 @@ pub struct SetAttrOpts {
     pub add_x_attr: HashMap<String, Vec<u8>>,
     pub remove_x_attr: Vec<String>,
     pub ufs_mtime: Option<i64>,
+    // Replace the default storage policy of a directory, an empty policy removes it.
+    #[serde(default)]
+    pub dir_policy: Option<DirPolicy>,
+    // Change the storage type of existing files, the blocks are moved by a migration job.
+    #[serde(default)]
+    pub storage_type: Option<StorageType>,
 }
 
 impl SetAttrOpts {
@@ -375,6 +433,8 @@ impl SetAttrOpts {
             add_x_attr: HashMap::default(),
             remove_x_attr: vec![],
             ufs_mtime: None,
+            dir_policy: None,
+            storage_type: self.storage_type,
         }
     }
 }
@@ -392,6 +452,8 @@ pub struct SetAttrOptsBuilder {
     add_x_attr: HashMap<String, Vec<u8>>,
     remove_x_attr: Vec<String>,
     ufs_mtime: Option<i64>,
+    dir_policy: Option<DirPolicy>,
+    storage_type: Option<StorageType>,
 }
 
 impl Default for SetAttrOptsBuilder {
@@ -415,6 +477,8 @@ impl SetAttrOptsBuilder {
             add_x_attr: HashMap::new(),
             remove_x_attr: vec![],
             ufs_mtime: None,
+            dir_policy: None,
+            storage_type: None,
         }
     }
 
@@ -478,6 +542,16 @@ impl SetAttrOptsBuilder {
         self
     }
 
+    pub fn dir_policy(mut self, policy: DirPolicy) -> Self {
+        let _ = self.dir_policy.insert(policy);
+        self
+    }
+
+    pub fn storage_type(mut self, storage_type: StorageType) -> Self {
+        let _ = self.storage_type.insert(storage_type);
+        self
+    }
+
     pub fn build(self) -> SetAttrOpts {
         SetAttrOpts {
             recursive: self.recursive,
@@ -492,6 +566,8 @@ impl SetAttrOptsBuilder {
             add_x_attr: self.add_x_attr,
             remove_x_attr: self.remove_x_attr,
             ufs_mtime: self.ufs_mtime,
+            dir_policy: self.dir_policy,
+            storage_type: self.storage_type,
         }
     }
 }
@@ -0,0 +1,63 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+use orpc::{err_box, CommonError};
+use serde::{Deserialize, Serialize};
+
+// Consistency of metadata reads.
+// Leader: all reads are served by the leader master.
+// Linearizable: a standby master serves the read after it has applied the leader's read index.
+// BoundedStaleness: a standby master serves the read if it heard from the leader recently.
+#[repr(i32)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    IntoPrimitive,
+    FromPrimitive,
+    Eq,
+    Default,
+    Hash,
+)]
+pub enum ReadConsistency {
+    #[default]
+    Leader = 0,
+    Linearizable = 1,
+    BoundedStaleness = 2,
+}
+
+impl ReadConsistency {
+    pub fn is_follower_read(&self) -> bool {
+        *self != ReadConsistency::Leader
+    }
+}
+
+impl TryFrom<&str> for ReadConsistency {
+    type Error = CommonError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let consistency = match value.to_uppercase().as_str() {
+            "LEADER" => ReadConsistency::Leader,
+            "LINEARIZABLE" => ReadConsistency::Linearizable,
+            "BOUNDED_STALENESS" => ReadConsistency::BoundedStaleness,
+            _ => return err_box!("invalid read consistency: {}", value),
+        };
+
+        Ok(consistency)
+    }
+}
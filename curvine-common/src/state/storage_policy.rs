@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::state::{StorageType, TtlAction};
+use crate::state::{EcPolicy, StorageType, TtlAction};
 
 // File storage policy.
 use serde::{Deserialize, Serialize};
@@ -23,6 +23,8 @@ pub struct StoragePolicy {
     pub ttl_ms: i64,
     pub ttl_action: TtlAction,
     pub ufs_mtime: i64,
+    // Erasure coding policy, None means the blocks are fully replicated.
+    pub ec_policy: Option<EcPolicy>,
 }
 
 impl Default for StoragePolicy {
@@ -32,6 +34,13 @@ impl Default for StoragePolicy {
             ttl_ms: 0,
             ttl_action: TtlAction::None,
             ufs_mtime: 0,
+            ec_policy: None,
         }
     }
 }
+
+impl StoragePolicy {
+    pub fn is_striped(&self) -> bool {
+        self.ec_policy.is_some()
+    }
+}
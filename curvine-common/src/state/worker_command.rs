@@ -20,8 +20,14 @@ pub struct DeleteBlockCmd {
     pub blocks: Vec<i64>,
 }
 
+pub struct MoveBlockCmd {
+    pub storage_type: StorageType,
+    pub blocks: Vec<i64>,
+}
+
 pub enum WorkerCommand {
     DeleteBlock(DeleteBlockCmd),
+    MoveBlock(MoveBlockCmd),
 }
 
 #[repr(i32)]
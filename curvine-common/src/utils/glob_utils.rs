@@ -0,0 +1,29 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use glob::Pattern;
+
+/// Returns `true` + compiled `Pattern` if valid glob, `false` + `None` otherwise
+pub fn parse_glob_pattern(s: &str) -> (bool, Option<Pattern>) {
+    // Fast heuristic: check common metachars first
+    if s.contains(['*', '?', '[', '{', '\\']) {
+        // Double-check with actual Pattern compilation
+        match Pattern::new(s) {
+            Ok(pattern) => (true, Some(pattern)),
+            Err(_) => (false, None),
+        }
+    } else {
+        (false, None)
+    }
+}
@@ -24,3 +24,6 @@ pub use self::rpc_utils::RpcUtils;
 
 mod common_utils;
 pub use self::common_utils::CommonUtils;
+
+mod glob_utils;
+pub use self::glob_utils::*;
@@ -172,6 +172,7 @@ impl ProtoUtils {
             block: b,
             offset: 0,
             locs,
+            striped: block.striped.map(Self::striped_block_to_pb),
         }
     }
 
@@ -185,6 +186,37 @@ impl ProtoUtils {
         LocatedBlock {
             block: Self::extend_block_from_pb(block.block),
             locs,
+            striped: block.striped.map(Self::striped_block_from_pb),
+        }
+    }
+
+    pub fn ec_policy_to_pb(policy: EcPolicy) -> EcPolicyProto {
+        EcPolicyProto {
+            data_units: policy.data_units,
+            parity_units: policy.parity_units,
+            cell_size: policy.cell_size,
+        }
+    }
+
+    pub fn ec_policy_from_pb(policy: EcPolicyProto) -> EcPolicy {
+        EcPolicy {
+            data_units: policy.data_units,
+            parity_units: policy.parity_units,
+            cell_size: policy.cell_size,
+        }
+    }
+
+    pub fn striped_block_to_pb(striped: StripedBlock) -> StripedBlockProto {
+        StripedBlockProto {
+            policy: Self::ec_policy_to_pb(striped.policy),
+            indices: striped.indices,
+        }
+    }
+
+    pub fn striped_block_from_pb(striped: StripedBlockProto) -> StripedBlock {
+        StripedBlock {
+            policy: Self::ec_policy_from_pb(striped.policy),
+            indices: striped.indices,
         }
     }
 
@@ -200,6 +232,7 @@ impl ProtoUtils {
             ttl_ms: policy.ttl_ms,
             ttl_action: policy.ttl_action.into(),
             ufs_mtime: policy.ufs_mtime,
+            ec_policy: policy.ec_policy.map(Self::ec_policy_to_pb),
         }
     }
 
@@ -209,6 +242,29 @@ impl ProtoUtils {
             ttl_ms: policy.ttl_ms,
             ttl_action: TtlAction::from(policy.ttl_action),
             ufs_mtime: policy.ufs_mtime,
+            ec_policy: policy.ec_policy.map(Self::ec_policy_from_pb),
+        }
+    }
+
+    pub fn dir_policy_to_pb(policy: DirPolicy) -> DirPolicyProto {
+        DirPolicyProto {
+            storage_type: policy.storage_type.map(|v| v.into()),
+            replicas: policy.replicas.map(|v| v as i32),
+            block_size: policy.block_size,
+            ttl_ms: policy.ttl_ms,
+            ttl_action: policy.ttl_action.map(|v| v.into()),
+            ec_policy: policy.ec_policy.map(Self::ec_policy_to_pb),
+        }
+    }
+
+    pub fn dir_policy_from_pb(policy: DirPolicyProto) -> DirPolicy {
+        DirPolicy {
+            storage_type: policy.storage_type.map(StorageType::from),
+            replicas: policy.replicas.map(|v| v as u16),
+            block_size: policy.block_size,
+            ttl_ms: policy.ttl_ms,
+            ttl_action: policy.ttl_action.map(TtlAction::from),
+            ec_policy: policy.ec_policy.map(Self::ec_policy_from_pb),
         }
     }
 
@@ -261,6 +317,205 @@ impl ProtoUtils {
         }
     }
 
+    pub fn content_summary_to_pb(summary: ContentSummary) -> ContentSummaryProto {
+        let type_lengths = summary
+            .type_lengths
+            .into_iter()
+            .map(|(storage_type, length)| StorageTypeLengthProto {
+                storage_type: storage_type.into(),
+                length,
+            })
+            .collect();
+        ContentSummaryProto {
+            length: summary.length,
+            file_count: summary.file_count,
+            directory_count: summary.directory_count,
+            space_consumed: summary.space_consumed,
+            type_lengths,
+            space_quota: Some(summary.space_quota).filter(|x| *x >= 0),
+            space_quota_remaining: Some(summary.space_quota_remaining).filter(|x| *x >= 0),
+        }
+    }
+
+    pub fn content_summary_from_pb(summary: ContentSummaryProto) -> ContentSummary {
+        let type_lengths = summary
+            .type_lengths
+            .into_iter()
+            .map(|x| (StorageType::from(x.storage_type), x.length))
+            .collect();
+        ContentSummary {
+            length: summary.length,
+            file_count: summary.file_count,
+            directory_count: summary.directory_count,
+            space_consumed: summary.space_consumed,
+            type_lengths,
+            space_quota: summary.space_quota.unwrap_or(-1),
+            space_quota_remaining: summary.space_quota_remaining.unwrap_or(-1),
+        }
+    }
+
+    pub fn find_opts_to_pb(opts: FindOpts) -> FindOptsProto {
+        FindOptsProto {
+            name: opts.name,
+            file_type: opts.file_type.map(|v| v.into()),
+            min_size: opts.min_size,
+            max_size: opts.max_size,
+            mtime_after: opts.mtime_after,
+            mtime_before: opts.mtime_before,
+            max_depth: opts.max_depth,
+        }
+    }
+
+    pub fn find_opts_from_pb(opts: FindOptsProto) -> FindOpts {
+        FindOpts {
+            name: opts.name,
+            file_type: opts.file_type.map(FileType::from),
+            min_size: opts.min_size,
+            max_size: opts.max_size,
+            mtime_after: opts.mtime_after,
+            mtime_before: opts.mtime_before,
+            max_depth: opts.max_depth,
+        }
+    }
+
+    pub fn fsck_report_to_pb(report: FsckReport) -> FsckResponse {
+        let files = report
+            .files
+            .into_iter()
+            .map(|file| FsckFileProto {
+                path: file.path,
+                len: file.len,
+                open_for_write: file.open_for_write,
+                blocks: file
+                    .blocks
+                    .into_iter()
+                    .map(|x| FsckBlockProto {
+                        block_id: x.block_id,
+                        len: x.len,
+                        state: x.state.into(),
+                        live: x.live,
+                        expected: x.expected,
+                    })
+                    .collect(),
+            })
+            .collect();
+        let orphan_blocks = report
+            .orphan_blocks
+            .into_iter()
+            .map(|x| FsckOrphanBlockProto {
+                worker_id: x.worker_id,
+                block_id: x.block_id,
+            })
+            .collect();
+
+        FsckResponse {
+            total_dirs: report.total_dirs,
+            total_files: report.total_files,
+            total_size: report.total_size,
+            total_blocks: report.total_blocks,
+            missing_blocks: report.missing_blocks,
+            corrupt_blocks: report.corrupt_blocks,
+            under_replicated_blocks: report.under_replicated_blocks,
+            over_replicated_blocks: report.over_replicated_blocks,
+            open_files: report.open_files,
+            corrupt_files: report.corrupt_files,
+            handled_files: report.handled_files,
+            files,
+            orphan_blocks,
+            orphan_count: Some(report.orphan_count),
+            next_start_after: report.next_start_after,
+        }
+    }
+
+    pub fn fsck_report_from_pb(rep: FsckResponse) -> FsckReport {
+        let files = rep
+            .files
+            .into_iter()
+            .map(|file| FsckFile {
+                path: file.path,
+                len: file.len,
+                open_for_write: file.open_for_write,
+                blocks: file
+                    .blocks
+                    .into_iter()
+                    .map(|x| FsckBlock {
+                        block_id: x.block_id,
+                        len: x.len,
+                        state: FsckBlockState::from(x.state),
+                        live: x.live,
+                        expected: x.expected,
+                    })
+                    .collect(),
+            })
+            .collect();
+        let orphan_blocks = rep
+            .orphan_blocks
+            .into_iter()
+            .map(|x| FsckOrphanBlock {
+                worker_id: x.worker_id,
+                block_id: x.block_id,
+            })
+            .collect();
+
+        FsckReport {
+            total_dirs: rep.total_dirs,
+            total_files: rep.total_files,
+            total_size: rep.total_size,
+            total_blocks: rep.total_blocks,
+            missing_blocks: rep.missing_blocks,
+            corrupt_blocks: rep.corrupt_blocks,
+            under_replicated_blocks: rep.under_replicated_blocks,
+            over_replicated_blocks: rep.over_replicated_blocks,
+            open_files: rep.open_files,
+            corrupt_files: rep.corrupt_files,
+            handled_files: rep.handled_files,
+            files,
+            orphan_count: rep.orphan_count.unwrap_or(0),
+            orphan_blocks,
+            next_start_after: rep.next_start_after,
+        }
+    }
+
+    pub fn meta_backup_info_to_pb(info: MetaBackupInfo) -> MetaBackupInfoProto {
+        MetaBackupInfoProto {
+            name: info.name,
+            journal_index: info.journal_index,
+            journal_term: info.journal_term,
+            create_time: info.create_time,
+            files: info.files,
+            len: info.len,
+        }
+    }
+
+    pub fn meta_backup_info_from_pb(info: MetaBackupInfoProto) -> MetaBackupInfo {
+        MetaBackupInfo {
+            name: info.name,
+            journal_index: info.journal_index,
+            journal_term: info.journal_term,
+            create_time: info.create_time,
+            files: info.files,
+            len: info.len,
+        }
+    }
+
+    pub fn meta_backup_status_to_pb(status: MetaBackupStatus) -> MetaBackupStatusProto {
+        MetaBackupStatusProto {
+            running: status.running,
+            start_time: status.start_time,
+            last_backup: status.last_backup.map(Self::meta_backup_info_to_pb),
+            last_error: status.last_error,
+        }
+    }
+
+    pub fn meta_backup_status_from_pb(status: MetaBackupStatusProto) -> MetaBackupStatus {
+        MetaBackupStatus {
+            running: status.running,
+            start_time: status.start_time,
+            last_backup: status.last_backup.map(Self::meta_backup_info_from_pb),
+            last_error: status.last_error,
+        }
+    }
+
     pub fn file_blocks_to_pb(src: FileBlocks) -> FileBlocksProto {
         let block_locs: Vec<LocatedBlockProto> = src
             .block_locs
@@ -360,6 +615,46 @@ impl ProtoUtils {
         }
     }
 
+    pub fn master_group_to_pb(src: MasterGroup) -> ListMastersResponse {
+        let masters = src
+            .masters
+            .into_iter()
+            .map(|x| MasterNodeProto {
+                node_id: x.id,
+                hostname: x.hostname,
+                port: x.port as u32,
+                learner: x.learner,
+                observer: Some(x.observer),
+                rpc_port: Some(x.rpc_port as u32),
+            })
+            .collect();
+
+        ListMastersResponse {
+            leader_id: src.leader_id,
+            masters,
+        }
+    }
+
+    pub fn master_group_from_pb(src: ListMastersResponse) -> MasterGroup {
+        let masters = src
+            .masters
+            .into_iter()
+            .map(|x| MasterNode {
+                id: x.node_id,
+                hostname: x.hostname,
+                port: x.port as u16,
+                learner: x.learner,
+                observer: x.observer.unwrap_or(false),
+                rpc_port: x.rpc_port.unwrap_or(0) as u16,
+            })
+            .collect();
+
+        MasterGroup {
+            leader_id: src.leader_id,
+            masters,
+        }
+    }
+
     pub fn worker_info_from_pb(workers: Vec<WorkerInfoProto>) -> Vec<WorkerInfo> {
         let mut vec = vec![];
         for info in workers {
@@ -390,6 +685,18 @@ impl ProtoUtils {
                 WorkerCommand::DeleteBlock(cmd) => {
                     let pb_cmd = WorkerCommandProto {
                         delete_block: Some(DeleteBlockCmdProto { blocks: cmd.blocks }),
+                        move_block: None,
+                    };
+                    vec.push(pb_cmd)
+                }
+
+                WorkerCommand::MoveBlock(cmd) => {
+                    let pb_cmd = WorkerCommandProto {
+                        delete_block: None,
+                        move_block: Some(MoveBlockCmdProto {
+                            storage_type: cmd.storage_type.into(),
+                            blocks: cmd.blocks,
+                        }),
                     };
                     vec.push(pb_cmd)
                 }
@@ -406,6 +713,13 @@ impl ProtoUtils {
                 let my_cmd = WorkerCommand::DeleteBlock(DeleteBlockCmd { blocks: c.blocks });
                 vec.push(my_cmd);
             }
+            if let Some(c) = cmd.move_block {
+                let my_cmd = WorkerCommand::MoveBlock(MoveBlockCmd {
+                    storage_type: StorageType::from(c.storage_type),
+                    blocks: c.blocks,
+                });
+                vec.push(my_cmd);
+            }
         }
         vec
     }
@@ -444,6 +758,8 @@ impl ProtoUtils {
             add_x_attr: opts.add_x_attr,
             remove_x_attr: opts.remove_x_attr,
             ufs_mtime: opts.ufs_mtime,
+            dir_policy: opts.dir_policy.map(Self::dir_policy_to_pb),
+            storage_type: opts.storage_type.map(|v| v.into()),
         }
     }
 
@@ -461,6 +777,8 @@ impl ProtoUtils {
             add_x_attr: opts.add_x_attr,
             remove_x_attr: opts.remove_x_attr,
             ufs_mtime: opts.ufs_mtime,
+            dir_policy: opts.dir_policy.map(Self::dir_policy_from_pb),
+            storage_type: opts.storage_type.map(StorageType::from),
         }
     }
 
@@ -480,6 +798,7 @@ impl ProtoUtils {
             mode: opts.mode,
             owner: opts.owner,
             group: opts.group,
+            overrides: Some(opts.overrides.bits()),
         }
     }
 
@@ -495,6 +814,7 @@ impl ProtoUtils {
             client_name: opts.client_name,
             owner: opts.owner,
             group: opts.group,
+            overrides: PolicyOverrides::from_bits(opts.overrides.unwrap_or(0)),
         }
     }
 
@@ -506,6 +826,7 @@ impl ProtoUtils {
             mode: opts.mode,
             owner: opts.owner,
             group: opts.group,
+            overrides: Some(opts.overrides.bits()),
         }
     }
 
@@ -517,6 +838,7 @@ impl ProtoUtils {
             mode: opts.mode,
             owner: opts.owner,
             group: opts.group,
+            overrides: PolicyOverrides::from_bits(opts.overrides.unwrap_or(0)),
         }
     }
 
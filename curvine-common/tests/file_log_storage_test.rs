@@ -0,0 +1,311 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_common::conf::JournalConf;
+use curvine_common::proto::raft::{RaftPeerProto, SnapshotData};
+use curvine_common::raft::storage::{
+    FileLogStorage, JournalLogStorage, LogStorage, LogStorageType,
+};
+use curvine_common::raft::{PeerContext, RaftGroup, RaftResult};
+use orpc::common::Utils;
+use orpc::io::net::InetAddr;
+use prost::Message;
+use raft::eraftpb::{ConfChange, ConfChangeType, Entry, EntryType, HardState};
+use raft::{GetEntriesContext, Storage, NO_LIMIT};
+use std::fs::OpenOptions;
+use std::os::unix::fs::FileExt;
+
+fn new_conf(name: &str) -> JournalConf {
+    JournalConf {
+        journal_dir: Utils::test_sub_dir(format!("file-log-test/{}", name)),
+        log_storage: "file".to_string(),
+        log_segment_size: "1KB".to_string(),
+        ..Default::default()
+    }
+}
+
+fn new_entries(low: u64, high: u64, term: u64) -> Vec<Entry> {
+    (low..high)
+        .map(|index| Entry {
+            index,
+            term,
+            data: format!("entry-{}-{}", index, term).into_bytes(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn conf_change_entry(index: u64, change_type: ConfChangeType, id: u64, observer: bool) -> Entry {
+    let addr = InetAddr::new("localhost", 8000 + id as u16);
+    let change = ConfChange {
+        change_type: change_type.into(),
+        node_id: id,
+        context: PeerContext::new(&addr, observer).encode().unwrap(),
+        id,
+    };
+    let mut entry = Entry {
+        index,
+        term: 1,
+        data: change.encode_to_vec(),
+        ..Default::default()
+    };
+    entry.set_entry_type(EntryType::EntryConfChange);
+    entry
+}
+
+fn initial_group() -> RaftGroup {
+    let peers = (1..4)
+        .map(|id| RaftPeerProto {
+            node_id: id,
+            hostname: "localhost".to_string(),
+            port: 8000 + id as u32,
+            observer: None,
+            service_port: None,
+        })
+        .collect();
+    RaftGroup::from_proto("restore", peers)
+}
+
+fn check_entries(store: &FileLogStorage, low: u64, high: u64, term: u64) {
+    let entries = store
+        .entries(low, high, NO_LIMIT, GetEntriesContext::empty(false))
+        .unwrap();
+    assert_eq!(entries, new_entries(low, high, term));
+}
+
+fn last_segment(store: &FileLogStorage) -> (String, u64) {
+    let core = store.read();
+    let segment = core.segments().last().unwrap();
+    (segment.path().to_string(), segment.write_len())
+}
+
+#[test]
+fn test_file_log_append_and_reopen() -> RaftResult<()> {
+    let conf = new_conf("append");
+    let store = FileLogStorage::from_conf(&conf, true)?;
+    store.append(&new_entries(1, 101, 1))?;
+
+    // 1KB segments, the entries are split into multiple files.
+    assert!(store.read().segments().len() > 1);
+    assert_eq!(store.first_index()?, 1);
+    assert_eq!(store.last_index()?, 100);
+    assert_eq!(store.term(50)?, 1);
+    check_entries(&store, 1, 101, 1);
+    check_entries(&store, 37, 64, 1);
+    drop(store);
+
+    let store = FileLogStorage::from_conf(&conf, false)?;
+    assert_eq!(store.first_index()?, 1);
+    assert_eq!(store.last_index()?, 100);
+    check_entries(&store, 1, 101, 1);
+
+    // scan_entries only returns the existing entries.
+    assert_eq!(store.scan_entries(91, 200)?.len(), 10);
+    Ok(())
+}
+
+#[test]
+fn test_file_log_truncate_conflict() -> RaftResult<()> {
+    let conf = new_conf("truncate");
+    let store = FileLogStorage::from_conf(&conf, true)?;
+    store.append(&new_entries(1, 101, 1))?;
+
+    // A new leader overwrites the uncommitted entries.
+    store.append(&new_entries(40, 46, 2))?;
+    assert_eq!(store.last_index()?, 45);
+    check_entries(&store, 1, 40, 1);
+    check_entries(&store, 40, 46, 2);
+    drop(store);
+
+    let store = FileLogStorage::from_conf(&conf, false)?;
+    assert_eq!(store.last_index()?, 45);
+    assert_eq!(store.term(45)?, 2);
+    check_entries(&store, 40, 46, 2);
+
+    store.append(&new_entries(46, 51, 2))?;
+    check_entries(&store, 40, 51, 2);
+    Ok(())
+}
+
+#[test]
+fn test_file_log_snapshot_compact() -> RaftResult<()> {
+    let conf = new_conf("compact");
+    let store = FileLogStorage::from_conf(&conf, true)?;
+    store.append(&new_entries(1, 101, 1))?;
+    store.set_hard_state(&HardState {
+        term: 1,
+        commit: 100,
+        ..Default::default()
+    })?;
+
+    let segments = store.read().segments().len();
+    store.create_snapshot(SnapshotData::default(), 80)?;
+    store.compact(80)?;
+
+    assert!(store.read().segments().len() < segments);
+    assert_eq!(store.first_index()?, 80);
+    assert_eq!(store.last_index()?, 100);
+    assert!(store.term(79).is_err());
+    assert!(store
+        .entries(70, 90, NO_LIMIT, GetEntriesContext::empty(false))
+        .is_err());
+    check_entries(&store, 80, 101, 1);
+    drop(store);
+
+    let store = FileLogStorage::from_conf(&conf, false)?;
+    let snapshot = store.latest_snapshot()?.unwrap();
+    assert_eq!(snapshot.get_metadata().index, 80);
+    assert_eq!(snapshot.get_metadata().term, 1);
+    assert_eq!(store.first_index()?, 80);
+    assert_eq!(store.scan_entries(1, 1000)?, new_entries(80, 101, 1));
+
+    // A snapshot ahead of all logs drops them.
+    store.apply_snapshot(snapshot.clone())?;
+    let mut snapshot = snapshot;
+    snapshot.mut_metadata().index = 200;
+    snapshot.mut_metadata().term = 3;
+    store.apply_snapshot(snapshot)?;
+    assert_eq!(store.first_index()?, 201);
+    assert_eq!(store.last_index()?, 200);
+    assert_eq!(store.term(200)?, 3);
+
+    store.append(&new_entries(201, 211, 3))?;
+    drop(store);
+
+    let store = FileLogStorage::from_conf(&conf, false)?;
+    assert_eq!(store.first_index()?, 201);
+    check_entries(&store, 201, 211, 3);
+    Ok(())
+}
+
+#[test]
+fn test_file_log_recover_torn_write() -> RaftResult<()> {
+    let conf = new_conf("torn");
+    let store = FileLogStorage::from_conf(&conf, true)?;
+    store.append(&new_entries(1, 31, 1))?;
+    let (path, write_len) = last_segment(&store);
+    let last_index = store.last_index()?;
+    drop(store);
+
+    // The last record is only partially written.
+    let file = OpenOptions::new().write(true).open(&path)?;
+    file.set_len(write_len - 5)?;
+    drop(file);
+
+    let store = FileLogStorage::from_conf(&conf, false)?;
+    assert_eq!(store.last_index()?, last_index - 1);
+    check_entries(&store, 1, last_index, 1);
+
+    // The log continues from the torn position.
+    store.append(&new_entries(last_index, last_index + 10, 2))?;
+    check_entries(&store, last_index, last_index + 10, 2);
+    drop(store);
+
+    let store = FileLogStorage::from_conf(&conf, false)?;
+    assert_eq!(store.last_index()?, last_index + 9);
+    check_entries(&store, 1, last_index, 1);
+    check_entries(&store, last_index, last_index + 10, 2);
+    Ok(())
+}
+
+#[test]
+fn test_file_log_recover_corrupted_record() -> RaftResult<()> {
+    let conf = new_conf("corrupted");
+    let store = FileLogStorage::from_conf(&conf, true)?;
+    store.append(&new_entries(1, 31, 1))?;
+    let (path, write_len) = last_segment(&store);
+    let last_index = store.last_index()?;
+    drop(store);
+
+    // The last record has its payload written but the data is broken, the checksum will not match.
+    let file = OpenOptions::new().write(true).open(&path)?;
+    file.write_all_at(b"xx", write_len - 2)?;
+    drop(file);
+
+    let store = FileLogStorage::from_conf(&conf, false)?;
+    assert_eq!(store.last_index()?, last_index - 1);
+    check_entries(&store, 1, last_index, 1);
+
+    // The garbage is cleared, a shorter record cannot be followed by the old bytes.
+    let (_, new_len) = last_segment(&store);
+    assert!(new_len < write_len);
+    drop(store);
+
+    let store = FileLogStorage::from_conf(&conf, false)?;
+    assert_eq!(store.last_index()?, last_index - 1);
+    Ok(())
+}
+
+#[test]
+fn test_journal_log_storage_switch() -> RaftResult<()> {
+    let mut conf = new_conf("switch");
+    let store = JournalLogStorage::from_conf(&conf, true)?;
+    assert_eq!(store.storage_type(), LogStorageType::File);
+    store.append(&new_entries(1, 11, 1))?;
+    assert_eq!(store.scan_entries(1, 11)?, new_entries(1, 11, 1));
+
+    conf.log_storage = "rocksdb".to_string();
+    conf.journal_dir = Utils::test_sub_dir("file-log-test/switch-rocks");
+    let store = JournalLogStorage::from_conf(&conf, true)?;
+    assert_eq!(store.storage_type(), LogStorageType::Rocks);
+
+    conf.log_storage = "unknown".to_string();
+    assert!(JournalLogStorage::from_conf(&conf, true).is_err());
+    Ok(())
+}
+
+// The members added at runtime are restored from the log and the snapshot after a restart.
+#[test]
+fn test_file_log_restore_group() -> RaftResult<()> {
+    let conf = new_conf("restore_group");
+    let store = FileLogStorage::from_conf(&conf, true)?;
+    store.append(&new_entries(1, 11, 1))?;
+    store.append(&[
+        conf_change_entry(11, ConfChangeType::AddLearnerNode, 4, true),
+        conf_change_entry(12, ConfChangeType::AddLearnerNode, 5, false),
+    ])?;
+    store.set_hard_state(&HardState {
+        term: 1,
+        commit: 12,
+        ..Default::default()
+    })?;
+
+    let mut group = initial_group();
+    group.restore(&store)?;
+    assert!(group.is_observer(&4));
+    assert!(!group.is_observer(&5));
+    assert_eq!(group.get_addr(&5), Some(InetAddr::new("localhost", 8005)));
+
+    // The conf changes are compacted, the snapshot keeps the members.
+    let data = SnapshotData {
+        peers: group.to_proto(),
+        ..Default::default()
+    };
+    store.create_snapshot(data, 12)?;
+    store.compact(12)?;
+    store.append(&[conf_change_entry(13, ConfChangeType::RemoveNode, 5, false)])?;
+    drop(store);
+
+    let store = FileLogStorage::from_conf(&conf, false)?;
+    let mut group = initial_group();
+    group.restore(&store)?;
+    assert!(group.is_observer(&4));
+    assert_eq!(group.get_addr(&5), None);
+
+    let (mut voters, learners) = group.initial_members();
+    voters.sort();
+    assert_eq!(voters, vec![1, 2, 3]);
+    assert_eq!(learners, vec![4]);
+    Ok(())
+}
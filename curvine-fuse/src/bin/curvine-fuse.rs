@@ -18,7 +18,7 @@ use curvine_common::version;
 use curvine_fuse::fs::CurvineFileSystem;
 use curvine_fuse::session::FuseSession;
 use curvine_fuse::web_server::WebServer;
-use orpc::common::Logger;
+use orpc::common::{Logger, Tracer};
 use orpc::io::net::InetAddr;
 use orpc::runtime::{AsyncRuntime, RpcRuntime};
 use orpc::{err_box, CommonResult};
@@ -38,6 +38,10 @@ fn main() -> CommonResult<()> {
 
     let cluster_conf = args.get_conf()?;
     Logger::init(cluster_conf.fuse.log.clone());
+    Tracer::init(
+        &cluster_conf.trace,
+        format!("{}-fuse", cluster_conf.cluster_id),
+    )?;
     cluster_conf.print();
 
     let rt = Arc::new(AsyncRuntime::new(
@@ -19,6 +19,7 @@ use crate::raw::FuseDirentList;
 use crate::session::{FuseBuf, FuseResponse};
 use crate::*;
 use crate::{err_fuse, FuseError, FuseResult, FuseUtils};
+use curvine_client::file::ListStatusIter;
 use curvine_client::unified::UnifiedFileSystem;
 use curvine_common::conf::{ClusterConf, FuseConf};
 use curvine_common::error::FsError;
@@ -254,6 +255,21 @@ impl CurvineFileSystem {
     ) -> FuseResult<FuseDirentList> {
         let handle = self.state.find_dir_handle(header.nodeid, arg.fh)?;
 
+        // Fetch the next pages of a large directory until there are enough entries to fill the reply.
+        let page_size = self.list_page_size();
+        while let Some(start_after) = handle.next_fetch(arg.offset as usize, page_size) {
+            let mut iter = match handle.take_iter(&start_after) {
+                Some(v) => v,
+                None => {
+                    let path = Path::from_str(&handle.path)?;
+                    self.fs.list_status_iter(&path, start_after.clone()).await?
+                }
+            };
+            let page = iter.next_batch(page_size).await?;
+            handle.add_page(start_after, page, iter.has_next());
+            handle.set_iter(iter);
+        }
+
         let mut map = self.state.node_write();
         let mut res = FuseDirentList::new(arg);
         for (index, status) in handle.get_list(arg.offset as usize) {
@@ -262,17 +278,17 @@ impl CurvineFileSystem {
                     let path = Path::from_str(&status.path)?;
                     self.state.meta_cache().put_status(&path, status.clone());
                 }
-                map.do_lookup(header.nodeid, Some(&status.name), status)?
+                map.do_lookup(header.nodeid, Some(&status.name), &status)?
             } else {
-                Self::status_to_attr(&self.conf, status)?
+                Self::status_to_attr(&self.conf, &status)?
             };
             let entry = Self::create_entry_out(&self.conf, attr);
 
             if plus {
-                if !res.add_plus((index + 1) as u64, status, entry) {
+                if !res.add_plus((index + 1) as u64, &status, entry) {
                     break;
                 }
-            } else if !res.add((index + 1) as u64, status, entry) {
+            } else if !res.add((index + 1) as u64, &status, entry) {
                 break;
             }
         }
@@ -545,14 +561,27 @@ impl CurvineFileSystem {
         Ok(status)
     }
 
-    async fn get_cached_list(&self, path: &Path) -> FuseResult<Vec<FileStatus>> {
+    fn list_page_size(&self) -> usize {
+        self.state.client_conf().list_status_page_size.max(1) as usize
+    }
+
+    // Returns the first page of the directory, the name of its last child and the iterator
+    // that continues the listing if there are more.
+    // Only a directory listed in a single page is put into the meta cache.
+    async fn get_cached_list(
+        &self,
+        path: &Path,
+    ) -> FuseResult<(Vec<FileStatus>, Option<String>, Option<ListStatusIter>)> {
         if self.conf.enable_meta_cache {
             if let Some(list) = self.state.meta_cache().get_list(path) {
-                return Ok(list);
+                return Ok((list, None, None));
             }
         }
 
-        let list = self.fs.list_status(path).await?;
+        let mut iter = self.fs.list_status_iter(path, None).await?;
+        let list = iter.next_batch(self.list_page_size()).await?;
+        let has_more = iter.has_next();
+        let start_after = list.last().map(|x| x.name.clone());
         let mut res = Vec::with_capacity(list.len() + 2);
         res.push(CurvineFileSystem::new_dot_status(FUSE_CURRENT_DIR));
         res.push(CurvineFileSystem::new_dot_status(FUSE_PARENT_DIR));
@@ -560,11 +589,12 @@ impl CurvineFileSystem {
             res.push(status);
         }
 
-        if self.conf.enable_meta_cache {
+        if self.conf.enable_meta_cache && !has_more {
             self.state.meta_cache().put_list(path, res.clone());
         }
 
-        Ok(res)
+        let iter = if has_more { Some(iter) } else { None };
+        Ok((res, start_after, iter))
     }
     fn invalidate_cache(&self, path: &Path) -> FuseResult<()> {
         if !self.conf.enable_meta_cache {
@@ -994,8 +1024,20 @@ impl fs::FileSystem for CurvineFileSystem {
         self.check_permissions(&dir_path, op.header, action.acl_mask())
             .await?;
 
-        let list = self.get_cached_list(&dir_path).await?;
-        let handle = self.state.new_dir_handle(op.header.nodeid, list).await?;
+        let (list, start_after, iter) = self.get_cached_list(&dir_path).await?;
+        let handle = self
+            .state
+            .new_dir_handle(
+                op.header.nodeid,
+                &dir_path,
+                list,
+                start_after,
+                iter.is_some(),
+            )
+            .await?;
+        if let Some(iter) = iter {
+            handle.set_iter(iter);
+        }
         let open_flags = Self::fill_open_flags(&self.conf, op.arg.flags);
         let attr = fuse_open_out {
             fh: handle.fh,
@@ -21,13 +21,14 @@ use curvine_common::fs::{Path, Reader};
 use curvine_common::state::FileStatus;
 use curvine_common::FsResult;
 use log::error;
+use orpc::common::{Span, SpanKind, TraceContext};
 use orpc::runtime::{RpcRuntime, Runtime};
 use orpc::sync::channel::{AsyncChannel, AsyncReceiver, AsyncSender, CallChannel, CallSender};
 use orpc::sync::ErrorMonitor;
 use std::sync::Arc;
 
 enum ReadTask {
-    Read(i64, usize, FuseResponse),
+    Read(i64, usize, FuseResponse, Option<TraceContext>),
     Complete(CallSender<i8>, Option<FuseResponse>),
 }
 
@@ -96,6 +97,7 @@ impl FuseReader {
                 op.arg.offset as i64,
                 op.arg.size as usize,
                 reply,
+                TraceContext::current(),
             ))
             .await
             .map_err(|e| self.check_error(e.into()));
@@ -118,8 +120,14 @@ impl FuseReader {
     ) -> FsResult<()> {
         while let Some(task) = req_receiver.recv().await {
             match task {
-                ReadTask::Read(off, len, reply) => {
-                    let data = reader.fuse_read(off, len).await;
+                ReadTask::Read(off, len, reply, trace) => {
+                    let mut span = Span::child_of("fuse.read.data", SpanKind::Internal, trace);
+                    span.set_attr("path", reader.path());
+                    span.set_attr("offset", off);
+                    let data = span.instrument(reader.fuse_read(off, len)).await;
+                    if let Err(e) = &data {
+                        span.set_error(e);
+                    }
                     reply.send_data(data.map_err(|x| x.into())).await?;
                 }
 
@@ -12,35 +12,148 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::fs::CurvineFileSystem;
+use crate::{FUSE_CURRENT_DIR, FUSE_PARENT_DIR};
+use curvine_client::file::ListStatusIter;
 use curvine_common::state::FileStatus;
 use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
 
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct DirCursor {
+    // The offset of the first entry in the list.
+    base: usize,
+    // The name of the last child fetched, the next page starts after it.
+    start_after: Option<String>,
+    has_more: bool,
+}
+
+/// An opened directory.
+/// A large directory is fetched page by page while it is read, and the entries already
+/// read are dropped, so the handle only keeps about a page of entries.
+/// The listing iterator is kept between pages, a ufs directory is listed only once.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct DirHandle {
     pub ino: u64,
     pub fh: u64,
+    // The path of the directory, the next pages are listed from it.
+    #[serde(default)]
+    pub path: String,
 
-    list: Vec<FileStatus>,
+    // Lock order: cursor, then list.
+    list: Mutex<Vec<FileStatus>>,
+    #[serde(default)]
+    cursor: Mutex<DirCursor>,
+    #[serde(skip)]
+    iter: Mutex<Option<ListStatusIter>>,
 }
 
 impl DirHandle {
-    pub fn new(ino: u64, fh: u64, list: Vec<FileStatus>) -> Self {
-        DirHandle { ino, fh, list }
+    // The list is the first page of the directory, including "." and "..".
+    pub fn new(
+        ino: u64,
+        fh: u64,
+        path: String,
+        list: Vec<FileStatus>,
+        start_after: Option<String>,
+        has_more: bool,
+    ) -> Self {
+        let cursor = DirCursor {
+            base: 0,
+            start_after,
+            has_more,
+        };
+        DirHandle {
+            ino,
+            fh,
+            path,
+            list: Mutex::new(list),
+            cursor: Mutex::new(cursor),
+            iter: Mutex::new(None),
+        }
     }
 
-    pub fn get_list(&self, skip: usize) -> impl Iterator<Item = (usize, &FileStatus)> {
-        self.list.iter().enumerate().skip(skip)
+    // Take the iterator that continues the listing after start_after, if it is kept.
+    pub fn take_iter(&self, start_after: &Option<String>) -> Option<ListStatusIter> {
+        let mut iter = self.iter.lock().unwrap();
+        match iter.take() {
+            Some(v) if v.start_after() == start_after.as_deref() => Some(v),
+            _ => None,
+        }
     }
 
-    pub fn len(&self) -> usize {
-        self.list.len()
+    // Keep the iterator for the next page, a finished listing is dropped.
+    pub fn set_iter(&self, iter: ListStatusIter) {
+        if iter.has_next() {
+            *self.iter.lock().unwrap() = Some(iter);
+        }
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.list.is_empty()
+    // Returns the name to list after, if more entries are needed to read count entries
+    // from the offset.
+    pub fn next_fetch(&self, offset: usize, count: usize) -> Option<Option<String>> {
+        let mut cursor = self.cursor.lock().unwrap();
+        let mut list = self.list.lock().unwrap();
+
+        // Only a directory that can be listed again drops the entries already read.
+        if !self.path.is_empty() {
+            if offset < cursor.base {
+                // rewinddir, list the directory from the beginning.
+                list.clear();
+                list.push(CurvineFileSystem::new_dot_status(FUSE_CURRENT_DIR));
+                list.push(CurvineFileSystem::new_dot_status(FUSE_PARENT_DIR));
+                *cursor = DirCursor {
+                    base: 0,
+                    start_after: None,
+                    has_more: true,
+                };
+            }
+
+            let read = (offset - cursor.base).min(list.len());
+            list.drain(..read);
+            cursor.base += read;
+        }
+
+        if cursor.has_more && cursor.base + list.len() < offset + count {
+            Some(cursor.start_after.clone())
+        } else {
+            None
+        }
+    }
+
+    // Add a page listed after start_after.
+    pub fn add_page(&self, start_after: Option<String>, page: Vec<FileStatus>, has_more: bool) {
+        let mut cursor = self.cursor.lock().unwrap();
+        if cursor.start_after != start_after || !cursor.has_more {
+            // The page was added by a concurrent read.
+            return;
+        }
+
+        let mut list = self.list.lock().unwrap();
+        if let Some(last) = page.last() {
+            cursor.start_after = Some(last.name.clone());
+        }
+        cursor.has_more = has_more && !page.is_empty();
+        list.extend(page);
+    }
+
+    // The entries from the offset, with their indexes.
+    pub fn get_list(&self, offset: usize) -> Vec<(usize, FileStatus)> {
+        let cursor = self.cursor.lock().unwrap();
+        let list = self.list.lock().unwrap();
+        let skip = offset.saturating_sub(cursor.base);
+        list.iter()
+            .enumerate()
+            .skip(skip)
+            .map(|(i, status)| (cursor.base + i, status.clone()))
+            .collect()
     }
 
-    pub fn get_all(&self) -> &[FileStatus] {
-        &self.list
+    pub fn len(&self) -> usize {
+        self.list.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
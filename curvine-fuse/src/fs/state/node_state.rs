@@ -472,9 +472,19 @@ impl NodeState {
     pub async fn new_dir_handle(
         &self,
         ino: u64,
+        path: &Path,
         list: Vec<FileStatus>,
+        start_after: Option<String>,
+        has_more: bool,
     ) -> FuseResult<Arc<DirHandle>> {
-        let handle = Arc::new(DirHandle::new(ino, self.next_fh(), list));
+        let handle = Arc::new(DirHandle::new(
+            ino,
+            self.next_fh(),
+            path.full_path().to_string(),
+            list,
+            start_after,
+            has_more,
+        ));
         let mut lock = self.dir_handles.write();
         lock.entry(ino)
             .or_default()
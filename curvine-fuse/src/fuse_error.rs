@@ -77,6 +77,7 @@ impl From<FsError> for FuseError {
             FsError::Unsupported(_) => Some(libc::ENOSYS),
             FsError::InProgress(_) => Some(libc::EBUSY),
             FsError::UnsupportedUfsRead(_) => Some(libc::EOPNOTSUPP),
+            FsError::Throttled(_) => Some(libc::EAGAIN),
             _ => None,
         };
 
@@ -19,6 +19,7 @@ use crate::session::{FuseRequest, FuseResponse, FuseTask};
 use crate::{err_fuse, FuseResult, FUSE_IN_HEADER_LEN};
 use libc::{EAGAIN, EINTR, ENODEV, ENOENT};
 use log::{debug, error, info};
+use orpc::common::Span;
 use orpc::io::IOResult;
 use orpc::runtime::{RpcRuntime, Runtime};
 use orpc::sync::channel::AsyncSender;
@@ -123,20 +124,28 @@ impl<T: FileSystem> FuseReceiver<T> {
     pub async fn send_stream(&self, req: FuseRequest) -> FuseResult<()> {
         let operator = req.parse_operator()?;
         let rep = self.new_replay(req.unique());
-        let res = match operator {
-            FuseOperator::Read(op) => self.fs.read(op, rep).await,
+        let mut span = Span::root(format!("fuse.{:?}", req.opcode()));
+        let res = span
+            .instrument(async {
+                match operator {
+                    FuseOperator::Read(op) => self.fs.read(op, rep).await,
 
-            FuseOperator::Write(op) => self.fs.write(op, rep).await,
+                    FuseOperator::Write(op) => self.fs.write(op, rep).await,
 
-            FuseOperator::Flush(op) => self.fs.flush(op, rep).await,
+                    FuseOperator::Flush(op) => self.fs.flush(op, rep).await,
 
-            FuseOperator::Release(op) => self.fs.release(op, rep).await,
+                    FuseOperator::Release(op) => self.fs.release(op, rep).await,
 
-            FuseOperator::FSync(op) => self.fs.fsync(op, rep).await,
+                    FuseOperator::FSync(op) => self.fs.fsync(op, rep).await,
 
-            _ => err_fuse!(libc::ENOSYS, "unsupported operation {:?}", req.opcode()),
-        };
+                    _ => err_fuse!(libc::ENOSYS, "unsupported operation {:?}", req.opcode()),
+                }
+            })
+            .await;
 
+        if let Err(e) = &res {
+            span.set_error(e);
+        }
         if res.is_err() {
             self.new_replay(req.unique()).send_rep(res).await?;
         }
@@ -170,9 +179,13 @@ impl<T: FileSystem> FuseReceiver<T> {
                                 let reply = self.new_replay(req.unique());
                                 let fs = self.fs.clone();
                                 let pending_requests = self.pending_requests.clone();
+                                let mut span = Span::root(format!("fuse.{:?}", req.opcode()));
 
                                 self.rt.spawn(async move {
-                                    if let Err(e) = Self::dispatch_meta_interrupt(fs, pending_requests, req, reply).await {
+                                    let fut = Self::dispatch_meta_interrupt(fs, pending_requests, req, reply);
+                                    let res = span.instrument(fut).await;
+                                    if let Err(e) = res {
+                                        span.set_error(&e);
                                         error!("failed to dispatch meta request: {}", e);
                                     }
                                 });
@@ -0,0 +1,75 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_client::file::ListStatusIter;
+use curvine_common::fs::Path;
+use curvine_common::state::FileStatus;
+use curvine_fuse::fs::state::DirHandle;
+use curvine_fuse::fs::CurvineFileSystem;
+use curvine_fuse::{FUSE_CURRENT_DIR, FUSE_PARENT_DIR};
+
+fn status(name: &str) -> FileStatus {
+    FileStatus {
+        name: name.to_string(),
+        path: format!("/ufs/{}", name),
+        ..Default::default()
+    }
+}
+
+// A ufs directory is listed once, the following pages are read from the kept iterator.
+#[tokio::test]
+async fn dir_handle_keeps_ufs_listing() {
+    let names: Vec<String> = (0..10).map(|x| format!("f{:02}", x)).collect();
+    let list = names.iter().rev().map(|x| status(x)).collect();
+    let mut iter = ListStatusIter::from_list(Path::from_str("/ufs").unwrap(), list, None);
+
+    let first = iter.next_batch(4).await.unwrap();
+    let start_after = first.last().map(|x| x.name.clone());
+    let mut page = vec![
+        CurvineFileSystem::new_dot_status(FUSE_CURRENT_DIR),
+        CurvineFileSystem::new_dot_status(FUSE_PARENT_DIR),
+    ];
+    page.extend(first);
+    let handle = DirHandle::new(1, 1, "/ufs".to_string(), page, start_after, true);
+    handle.set_iter(iter);
+
+    let mut read = vec![];
+    let mut offset = 0;
+    loop {
+        while let Some(start_after) = handle.next_fetch(offset, 4) {
+            let mut iter = handle.take_iter(&start_after).expect("iterator is kept");
+            let page = iter.next_batch(4).await.unwrap();
+            handle.add_page(start_after, page, iter.has_next());
+            handle.set_iter(iter);
+        }
+
+        let entries = handle.get_list(offset);
+        if entries.is_empty() {
+            break;
+        }
+        for (_, status) in entries.into_iter().take(4) {
+            read.push(status.name);
+            offset += 1;
+        }
+    }
+
+    let mut expected = vec![FUSE_CURRENT_DIR.to_string(), FUSE_PARENT_DIR.to_string()];
+    expected.extend(names);
+    assert_eq!(read, expected);
+
+    // The listing is finished, and a rewind lists the directory again.
+    assert!(handle.take_iter(&Some("f09".to_string())).is_none());
+    assert_eq!(handle.next_fetch(0, 4), Some(None));
+    assert!(handle.take_iter(&None).is_none());
+}
@@ -12,7 +12,88 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const ERROR_KIND_SRC: &str = "../curvine-common/src/error/fs_error.rs";
+
+// The variants of curvine_common::error::ErrorKind and their values.
+fn error_kinds() -> Vec<(String, i32)> {
+    let text = fs::read_to_string(ERROR_KIND_SRC).unwrap();
+    let start = text
+        .find("pub enum ErrorKind {")
+        .expect("ErrorKind not found");
+    let body = &text[start..];
+    let body = &body[body.find('{').unwrap() + 1..body.find('}').unwrap()];
+
+    body.lines()
+        .filter_map(|line| {
+            let (name, value) = line.trim().trim_end_matches(',').split_once('=')?;
+            Some((name.trim().to_string(), value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+// NotLeaderMaster -> NOT_LEADER_MASTER, PBDecode -> PB_DECODE.
+fn screaming_snake(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut res = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        let prev_lower = i > 0 && chars[i - 1].is_lowercase();
+        let acronym_end = i > 0
+            && chars[i - 1].is_uppercase()
+            && chars.get(i + 1).is_some_and(|x| x.is_lowercase());
+        if c.is_uppercase() && (prev_lower || acronym_end) {
+            res.push('_');
+        }
+        res.push(c.to_ascii_uppercase());
+    }
+    res
+}
+
+// The error codes of the c abi are CURVINE_OK and the negative values of ErrorKind, they are
+// generated into the rust constants of src/c and the defines of include/libcurvine.h.
+fn error_codes(out_dir: &Path) -> String {
+    let mut codes = vec![("CURVINE_OK".to_string(), 0)];
+    for (name, value) in error_kinds() {
+        codes.push((format!("CURVINE_ERR_{}", screaming_snake(&name)), -value));
+    }
+
+    let mut consts = String::new();
+    let mut defines = vec![];
+    for (name, code) in codes {
+        consts.push_str(&format!("pub const {}: i32 = {};\n", name, code));
+        defines.push(format!("#define {} {}", name, code));
+    }
+    fs::write(out_dir.join("error_codes.rs"), consts).unwrap();
+    defines.join("\n\n")
+}
+
+// Generate the header of the c abi, so that it always matches src/c.
+fn c_header(crate_dir: &Path, error_defines: String) {
+    let mut config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+    let after_includes = config.after_includes.take().unwrap_or_default();
+    config.after_includes = Some(format!("{}\n\n{}", after_includes, error_defines));
+
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(crate_dir.join("src/c/mod.rs"))
+        .generate()
+        .expect("Unable to generate include/libcurvine.h")
+        .write_to_file(crate_dir.join("include/libcurvine.h"));
+}
+
 fn main() {
     println!("cargo:rustc-env=CFLAGS=\"-O3 -Werror -Wno-attributes -fPIC -fno-omit-frame-pointer -Wunused-variable -fvisibility=hidden\"");
     println!("cargo:rustc-env=LDFLAGS=\"-Wl,--no-as-needed -lrt -Wl\"");
+
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    println!("cargo:rerun-if-changed={}", ERROR_KIND_SRC);
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src/c");
+
+    let error_defines = error_codes(&out_dir);
+    c_header(&crate_dir, error_defines);
 }
@@ -0,0 +1,149 @@
+#ifndef LIBCURVINE_H
+#define LIBCURVINE_H
+
+/* Generated by build.rs from src/c, do not edit it manually. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+/*
+ * libcurvine, the c abi of the curvine client, link with -lcurvine_libsdk.
+ *
+ * Functions return CURVINE_OK or a negative CURVINE_ERR_* code, read functions return the
+ * bytes read. The message of the last error is returned by curvine_last_error in the same thread.
+ *
+ * Thread safety: a curvine_fs_t can be shared by multiple threads, a curvine_reader_t or
+ * curvine_writer_t must be used by one thread at a time. Open a reader per thread for
+ * concurrent reads.
+ */
+
+typedef struct curvine_fs_t curvine_fs_t;
+typedef struct curvine_reader_t curvine_reader_t;
+typedef struct curvine_writer_t curvine_writer_t;
+
+#define CURVINE_OK 0
+
+#define CURVINE_ERR_IO -1
+
+#define CURVINE_ERR_NOT_LEADER_MASTER -2
+
+#define CURVINE_ERR_RAFT -3
+
+#define CURVINE_ERR_TIMEOUT -4
+
+#define CURVINE_ERR_PB_DECODE -5
+
+#define CURVINE_ERR_PB_ENCODE -6
+
+#define CURVINE_ERR_FILE_ALREADY_EXISTS -7
+
+#define CURVINE_ERR_FILE_NOT_FOUND -8
+
+#define CURVINE_ERR_INVALID_FILE_SIZE -9
+
+#define CURVINE_ERR_PARENT_NOT_DIR -10
+
+#define CURVINE_ERR_DIR_NOT_EMPTY -11
+
+#define CURVINE_ERR_ABNORMAL_DATA -12
+
+#define CURVINE_ERR_BLOCK_IS_WRITING -13
+
+#define CURVINE_ERR_BLOCK_INFO -14
+
+#define CURVINE_ERR_LEASE -15
+
+#define CURVINE_ERR_INVALID_PATH -16
+
+#define CURVINE_ERR_DISK_OUT_OF_SPACE -17
+
+#define CURVINE_ERR_IN_PROGRESS -18
+
+#define CURVINE_ERR_UNSUPPORTED -19
+
+#define CURVINE_ERR_UFS -20
+
+#define CURVINE_ERR_EXPIRED -21
+
+#define CURVINE_ERR_UNSUPPORTED_UFS_READ -22
+
+#define CURVINE_ERR_JOB_NOT_FOUND -23
+
+#define CURVINE_ERR_PIPELINE -24
+
+#define CURVINE_ERR_MIN_REPLICAS_NOT_MET -25
+
+#define CURVINE_ERR_THROTTLED -26
+
+#define CURVINE_ERR_COMMON -10000
+
+typedef struct curvine_file_status_t {
+  int64_t id;
+  char *path;
+  bool is_dir;
+  int64_t len;
+  int64_t mtime;
+  int64_t atime;
+  int32_t replicas;
+  int64_t block_size;
+  int32_t file_type;
+  uint32_t mode;
+  bool is_complete;
+} curvine_file_status_t;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+const char *curvine_last_error(void);
+
+int32_t curvine_connect(const char *conf_path, curvine_fs_t **fs_out);
+
+int32_t curvine_disconnect(curvine_fs_t *fs_ptr);
+
+int32_t curvine_open(const curvine_fs_t *fs_ptr,
+                     const char *path,
+                     curvine_reader_t **reader_out,
+                     int64_t *len_out);
+
+int64_t curvine_read(curvine_reader_t *reader_ptr, uint8_t *buf, size_t len);
+
+int64_t curvine_pread(curvine_reader_t *reader_ptr, int64_t offset, uint8_t *buf, size_t len);
+
+int32_t curvine_close_reader(curvine_reader_t *reader_ptr);
+
+int32_t curvine_create(const curvine_fs_t *fs_ptr,
+                       const char *path,
+                       bool overwrite,
+                       curvine_writer_t **writer_out);
+
+int32_t curvine_write(curvine_writer_t *writer_ptr, const uint8_t *buf, size_t len);
+
+int32_t curvine_flush(curvine_writer_t *writer_ptr);
+
+int32_t curvine_close_writer(curvine_writer_t *writer_ptr);
+
+int32_t curvine_stat(const curvine_fs_t *fs_ptr,
+                     const char *path,
+                     struct curvine_file_status_t *status_out);
+
+void curvine_free_file_status(struct curvine_file_status_t *status);
+
+int32_t curvine_list(const curvine_fs_t *fs_ptr,
+                     const char *path,
+                     struct curvine_file_status_t **list_out,
+                     size_t *count_out);
+
+void curvine_free_list(struct curvine_file_status_t *list, size_t count);
+
+int32_t curvine_mkdir(const curvine_fs_t *fs_ptr, const char *path, bool create_parent);
+
+int32_t curvine_rename(const curvine_fs_t *fs_ptr, const char *src, const char *dst);
+
+int32_t curvine_delete(const curvine_fs_t *fs_ptr, const char *path, bool recursive);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* LIBCURVINE_H */
@@ -197,7 +197,7 @@ public class CurvineFileSystem extends FileSystem {
 
         long[] tmp = new long[] {0, 0};
         long nativeHandle = libFs.open(formatPath(path), tmp);
-        FSInputStream inputStream = new CurvineInputStream(libFs, nativeHandle, tmp[0], statistics);
+        FSInputStream inputStream = new CurvineInputStream(libFs, nativeHandle, tmp[0], tmp[1] == 1, statistics);
         return new FSDataInputStream(inputStream);
     }
 
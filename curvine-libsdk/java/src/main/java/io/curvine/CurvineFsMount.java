@@ -95,6 +95,18 @@ public class CurvineFsMount {
         checkError(res);
     }
 
+    // Read into the direct buffer from pos without moving the stream position, returns the bytes read.
+    public int pread(long readerHandle, long pos, ByteBuffer buffer, int len) throws IOException {
+        long res = CurvineNative.pread(readerHandle, pos, ((DirectBuffer) buffer).address(), len);
+        checkError(res);
+        return (int) res;
+    }
+
+    // ranges is a flat array of (pos, buffer address, len), len is replaced by the bytes read.
+    public void preadVectored(long readerHandle, long[] ranges) throws IOException {
+        checkError(CurvineNative.preadVectored(readerHandle, ranges));
+    }
+
     public void closeReader(long readerHandle) throws IOException {
         checkError(CurvineNative.closeReader(readerHandle));
     }
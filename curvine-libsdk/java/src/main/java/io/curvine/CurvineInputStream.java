@@ -14,32 +14,58 @@
 
 package io.curvine;
 
+import java.io.EOFException;
 import java.io.IOException;
 import java.nio.ByteBuffer;
+import java.util.ArrayList;
+import java.util.Comparator;
+import java.util.List;
+import java.util.concurrent.CompletableFuture;
+import java.util.function.IntFunction;
 
 import javax.annotation.Nonnull;
 
 import org.apache.hadoop.fs.FSExceptionMessages;
 import org.apache.hadoop.fs.FSInputStream;
 import org.apache.hadoop.fs.FileSystem;
+import sun.nio.ch.DirectBuffer;
 
 public class CurvineInputStream extends FSInputStream {
+    // Positional reads copy through a per-thread direct buffer of at most this size.
+    private static final int PREAD_BUFFER_SIZE = 1024 * 1024;
+    private static final ThreadLocal<ByteBuffer> PREAD_BUFFER = new ThreadLocal<>();
+
+    // The defaults of PositionedReadable in hadoop 3.3.5+.
+    private static final int MIN_SEEK_FOR_VECTOR_READS = 4 * 1024;
+    private static final int MAX_READ_SIZE_FOR_VECTOR_READS = 1024 * 1024;
+    private static final Class<?> FILE_RANGE = loadClass("org.apache.hadoop.fs.FileRange");
+
     private long nativeHandle;
     private byte[] oneByte = new byte[1];
     private final CurvineFsMount libFs;
-    private boolean closed;
+    private volatile boolean closed;
     private long pos;
     private final long fileSize;
 
+    // Positional and vectored reads are served by the native positional reader,
+    // they don't move or lock the stream position, so they can be issued concurrently.
+    // Files that are only in the ufs fall back to seek + read.
+    private final boolean positional;
+
     private final long[] tmp = new long[] {0, 0};
     private ByteBuffer buffer;
 
     private final FileSystem.Statistics statistics;
 
-    public CurvineInputStream(CurvineFsMount libFs, long nativeHandle, long fileSize, FileSystem.Statistics statistics) {
+    public CurvineInputStream(CurvineFsMount libFs,
+                              long nativeHandle,
+                              long fileSize,
+                              boolean positional,
+                              FileSystem.Statistics statistics) {
         this.libFs = libFs;
         this.nativeHandle = nativeHandle;
         this.fileSize = fileSize;
+        this.positional = positional;
         this.statistics = statistics;
     }
 
@@ -105,6 +131,235 @@ public class CurvineInputStream extends FSInputStream {
         return size;
     }
 
+    @Override
+    public int read(long position, byte[] buf, int offset, int length) throws IOException {
+        if (!positional) {
+            return super.read(position, buf, offset, length);
+        }
+
+        checkClosed();
+        validatePositionedReadArgs(position, buf, offset, length);
+        if (length == 0) {
+            return 0;
+        } else if (position >= fileSize) {
+            return -1;
+        }
+
+        ByteBuffer buffer = getPreadBuffer(Math.min(length, PREAD_BUFFER_SIZE));
+        int total = 0;
+        while (total < length) {
+            int size = Math.min(length - total, buffer.capacity());
+            int n = libFs.pread(nativeHandle, position + total, buffer, size);
+            if (n <= 0) {
+                break;
+            }
+
+            buffer.clear();
+            buffer.get(buf, offset + total, n);
+            total += n;
+            if (n < size) {
+                break; // read end
+            }
+        }
+
+        if (statistics != null) {
+            statistics.incrementBytesRead(total);
+        }
+        return total == 0 ? -1 : total;
+    }
+
+    // Hadoop 3.3.5+ issues vectored reads through PositionedReadable.readVectored(List<? extends FileRange>, IntFunction).
+    // The sdk is built against hadoop 3.3.4 which has no FileRange, so this method overrides it by the erased
+    // signature and accesses the ranges by reflection.
+    public void readVectored(List<?> ranges, IntFunction<ByteBuffer> allocate) throws IOException {
+        List<ReadRange> list = new ArrayList<>(ranges.size());
+        for (Object range : ranges) {
+            list.add(ReadRange.of(range));
+        }
+        readRanges(list, allocate);
+    }
+
+    // Ranges are sorted and coalesced when the gap between them is small, every combined range is read
+    // into one direct buffer and all combined ranges are read concurrently by a single native call.
+    // The futures of the ranges are completed before returning.
+    void readRanges(List<ReadRange> ranges, IntFunction<ByteBuffer> allocate) throws IOException {
+        checkClosed();
+        List<ReadRange> readable = new ArrayList<>(ranges.size());
+        for (ReadRange range : sortRanges(ranges)) {
+            if (range.offset + range.length > fileSize) {
+                range.data.completeExceptionally(new EOFException(
+                        "Range " + range + " exceeds file length " + fileSize));
+            } else {
+                readable.add(range);
+            }
+        }
+
+        if (!positional) {
+            for (ReadRange range : readable) {
+                try {
+                    byte[] bytes = new byte[range.length];
+                    readFully(range.offset, bytes);
+                    ByteBuffer dst = allocate.apply(range.length);
+                    dst.put(bytes);
+                    dst.flip();
+                    range.data.complete(dst);
+                } catch (IOException e) {
+                    range.data.completeExceptionally(e);
+                }
+            }
+            return;
+        }
+
+        List<CombinedRange> combined = mergeRanges(readable, MIN_SEEK_FOR_VECTOR_READS, MAX_READ_SIZE_FOR_VECTOR_READS);
+        ByteBuffer[] buffers = new ByteBuffer[combined.size()];
+        long[] args = new long[combined.size() * 3];
+        for (int i = 0; i < combined.size(); i++) {
+            CombinedRange range = combined.get(i);
+            buffers[i] = CurvineNative.createBuffer(range.length());
+            args[i * 3] = range.offset;
+            args[i * 3 + 1] = ((DirectBuffer) buffers[i]).address();
+            args[i * 3 + 2] = range.length();
+        }
+
+        try {
+            libFs.preadVectored(nativeHandle, args);
+        } catch (IOException e) {
+            for (ReadRange range : readable) {
+                range.data.completeExceptionally(e);
+            }
+            return;
+        }
+
+        long total = 0;
+        for (int i = 0; i < combined.size(); i++) {
+            CombinedRange range = combined.get(i);
+            if (args[i * 3 + 2] < range.length()) {
+                EOFException e = new EOFException("Short read at " + range.offset
+                        + ", expected " + range.length() + " bytes, actual " + args[i * 3 + 2]);
+                for (ReadRange child : range.ranges) {
+                    child.data.completeExceptionally(e);
+                }
+                continue;
+            }
+
+            for (ReadRange child : range.ranges) {
+                ByteBuffer src = buffers[i].duplicate();
+                int start = (int) (child.offset - range.offset);
+                src.position(start);
+                src.limit(start + child.length);
+
+                ByteBuffer dst = allocate.apply(child.length);
+                dst.put(src);
+                dst.flip();
+                child.data.complete(dst);
+            }
+            total += range.length();
+        }
+
+        if (statistics != null) {
+            statistics.incrementBytesRead(total);
+        }
+    }
+
+    private static ByteBuffer getPreadBuffer(int len) {
+        ByteBuffer buffer = PREAD_BUFFER.get();
+        if (buffer == null || buffer.capacity() < len) {
+            buffer = CurvineNative.createBuffer(len);
+            PREAD_BUFFER.set(buffer);
+        }
+        buffer.clear();
+        return buffer;
+    }
+
+    private static Class<?> loadClass(String name) {
+        try {
+            return Class.forName(name);
+        } catch (ClassNotFoundException e) {
+            return null;
+        }
+    }
+
+    static List<ReadRange> sortRanges(List<ReadRange> ranges) throws EOFException {
+        List<ReadRange> sorted = new ArrayList<>(ranges);
+        sorted.sort(Comparator.comparingLong(x -> x.offset));
+
+        ReadRange prev = null;
+        for (ReadRange range : sorted) {
+            if (range.offset < 0) {
+                throw new EOFException("Range " + range + " has a negative offset");
+            } else if (range.length < 0) {
+                throw new IllegalArgumentException("Range " + range + " has a negative length");
+            } else if (prev != null && prev.offset + prev.length > range.offset) {
+                throw new IllegalArgumentException("Range " + prev + " overlaps with " + range);
+            }
+            prev = range;
+        }
+        return sorted;
+    }
+
+    // Coalesce the sorted ranges whose gap is at most minSeek, a combined range never exceeds maxSize
+    // unless a single range is larger than it.
+    static List<CombinedRange> mergeRanges(List<ReadRange> sorted, int minSeek, int maxSize) {
+        List<CombinedRange> res = new ArrayList<>();
+        CombinedRange cur = null;
+        for (ReadRange range : sorted) {
+            long end = range.offset + range.length;
+            if (cur != null && range.offset - cur.end <= minSeek && end - cur.offset <= maxSize) {
+                cur.end = end;
+            } else {
+                cur = new CombinedRange(range.offset, end);
+                res.add(cur);
+            }
+            cur.ranges.add(range);
+        }
+        return res;
+    }
+
+    // A range of a vectored read, its data future is the future of the FileRange it is created from.
+    static class ReadRange {
+        final long offset;
+        final int length;
+        final CompletableFuture<ByteBuffer> data = new CompletableFuture<>();
+
+        ReadRange(long offset, int length) {
+            this.offset = offset;
+            this.length = length;
+        }
+
+        static ReadRange of(Object range) throws IOException {
+            Class<?> type = FILE_RANGE != null && FILE_RANGE.isInstance(range) ? FILE_RANGE : range.getClass();
+            try {
+                long offset = (long) type.getMethod("getOffset").invoke(range);
+                int length = (int) type.getMethod("getLength").invoke(range);
+                ReadRange res = new ReadRange(offset, length);
+                type.getMethod("setData", CompletableFuture.class).invoke(range, res.data);
+                return res;
+            } catch (ReflectiveOperationException e) {
+                throw new IOException("Invalid file range " + range, e);
+            }
+        }
+
+        @Override
+        public String toString() {
+            return "range[" + offset + "," + (offset + length) + ")";
+        }
+    }
+
+    static class CombinedRange {
+        final long offset;
+        long end;
+        final List<ReadRange> ranges = new ArrayList<>();
+
+        CombinedRange(long offset, long end) {
+            this.offset = offset;
+            this.end = end;
+        }
+
+        int length() {
+            return (int) (end - offset);
+        }
+    }
+
     @Override
     public void close() throws IOException {
         if (closed) {
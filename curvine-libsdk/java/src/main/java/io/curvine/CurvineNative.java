@@ -250,6 +250,10 @@ public class CurvineNative {
 
     public static native long seek(long nativeHandle, long pos) throws IOException;
 
+    public static native long pread(long nativeHandle, long pos, long address, int len) throws IOException;
+
+    public static native long preadVectored(long nativeHandle, long[] ranges) throws IOException;
+
     public static native long closeReader(long nativeHandle) throws IOException;
 
     public static native long closeFilesystem(long nativeHandle) throws IOException;
@@ -22,10 +22,13 @@ import org.apache.hadoop.conf.Configuration;
 import org.junit.*;
 
 import java.io.IOException;
+import java.nio.ByteBuffer;
 import java.nio.charset.StandardCharsets;
 import java.util.ArrayList;
 import java.util.Arrays;
+import java.util.List;
 import java.util.Optional;
+import java.util.concurrent.CompletableFuture;
 
 public class CurvineFileSystemTest {
     static FileSystem fs;
@@ -60,6 +63,7 @@ public class CurvineFileSystemTest {
         mkdirs();
         writeAndRead();
         seek();
+        positionRead();
         rename();
         delete();
         getFileStatus();
@@ -116,6 +120,68 @@ public class CurvineFileSystemTest {
         }
     }
 
+    public void positionRead() throws Exception {
+        Path path = new Path("/a/b/aaa.txt");
+        long len = fs.getFileStatus(path).getLen();
+        byte[] expect = StringUtils.repeat("A", 1024).getBytes(StandardCharsets.UTF_8);
+
+        try (FSDataInputStream in = fs.open(path)) {
+            // Positional reads across a block boundary don't move the stream position.
+            byte[] buf = new byte[1024];
+            in.seek(100);
+            in.readFully(64L * 1024 * 1024 - 512, buf);
+            assert Arrays.equals(expect, buf);
+            assert in.getPos() == 100;
+            assert in.read(len - 10, buf, 0, buf.length) == 10;
+            assert in.read(len, buf, 0, buf.length) == -1;
+
+            // FSDataInputStream.readVectored is not in hadoop 3.3.4, call the stream with ranges
+            // that have the methods of FileRange.
+            List<TestRange> ranges = Arrays.asList(
+                    new TestRange(len - 1024, 1024),
+                    new TestRange(0, 1024),
+                    new TestRange(2048, 1024),
+                    new TestRange(32L * 1024 * 1024, 1024)
+            );
+            ((CurvineInputStream) in.getWrappedStream()).readVectored(ranges, ByteBuffer::allocate);
+            for (TestRange range : ranges) {
+                ByteBuffer data = range.getData().get();
+                assert data.remaining() == 1024;
+                byte[] bytes = new byte[1024];
+                data.get(bytes);
+                assert Arrays.equals(expect, bytes);
+            }
+            assert in.getPos() == 100;
+        }
+    }
+
+    public static class TestRange {
+        private final long offset;
+        private final int length;
+        private CompletableFuture<ByteBuffer> data;
+
+        public TestRange(long offset, int length) {
+            this.offset = offset;
+            this.length = length;
+        }
+
+        public long getOffset() {
+            return offset;
+        }
+
+        public int getLength() {
+            return length;
+        }
+
+        public CompletableFuture<ByteBuffer> getData() {
+            return data;
+        }
+
+        public void setData(CompletableFuture<ByteBuffer> data) {
+            this.data = data;
+        }
+    }
+
     public void rename() throws IOException {
         Path src = new Path("/a/b/aaa.txt");
         Path dst = new Path("/a/b/bbb.txt");
@@ -19,6 +19,8 @@ import org.apache.hadoop.conf.Configuration;
 import org.junit.Test;
 
 import java.io.File;
+import java.util.Arrays;
+import java.util.List;
 
 public class LibFsTest {
     @Test
@@ -54,6 +56,43 @@ public class LibFsTest {
         System.out.println(open);
     }
 
+    @Test
+    public void mergeRanges() throws Exception {
+        List<CurvineInputStream.ReadRange> sorted = CurvineInputStream.sortRanges(Arrays.asList(
+                new CurvineInputStream.ReadRange(10000, 100),
+                new CurvineInputStream.ReadRange(0, 100),
+                new CurvineInputStream.ReadRange(200, 100),
+                new CurvineInputStream.ReadRange(1000, 2000)
+        ));
+        assert sorted.get(0).offset == 0;
+        assert sorted.get(3).offset == 10000;
+
+        // The gap of 100 bytes is merged, 700 bytes is not.
+        List<CurvineInputStream.CombinedRange> combined = CurvineInputStream.mergeRanges(sorted, 500, 4096);
+        assert combined.size() == 3;
+        assert combined.get(0).offset == 0 && combined.get(0).length() == 300;
+        assert combined.get(0).ranges.size() == 2;
+        assert combined.get(1).offset == 1000 && combined.get(1).length() == 2000;
+        assert combined.get(2).offset == 10000 && combined.get(2).length() == 100;
+
+        // A combined range does not exceed the max size.
+        combined = CurvineInputStream.mergeRanges(sorted, 100000, 3000);
+        assert combined.size() == 2;
+        assert combined.get(0).length() == 3000;
+        assert combined.get(0).ranges.size() == 3;
+
+        boolean overlap = false;
+        try {
+            CurvineInputStream.sortRanges(Arrays.asList(
+                    new CurvineInputStream.ReadRange(0, 100),
+                    new CurvineInputStream.ReadRange(50, 100)
+            ));
+        } catch (IllegalArgumentException e) {
+            overlap = true;
+        }
+        assert overlap;
+    }
+
     @Test
     public void osVersion() throws Exception {
         String ver = CurvineNative.getOsVersion("src/test/resources/os-version");
@@ -1,6 +1,7 @@
 import curvine_libsdk
 from common_pb2 import FileStatusProto
 from master_pb2 import GetFileStatusResponse, GetMasterInfoResponse,ListStatusResponse
+from job_pb2 import GetJobStatusResponse
 from curvinefs.curvineReader import CurvineReader
 from curvinefs.curvineWriter import CurvineWriter
 
@@ -195,6 +196,84 @@ class CurvineClient:
         start = max(0, file_length - size)  
         return self.read_range(path, start, min(size, file_length - start))
     
+    # Read into a writable buffer at the offset of the file with a reader opened for this call.
+    # Open the file once and use CurvineReader.pread_into for repeated reads.
+    def pread_into(self, path, offset, buf):
+        reader = self.open(path)
+        try:
+            return reader.pread_into(offset, buf)
+        finally:
+            reader.close()
+
+    # Read length bytes at the offset, returns a memoryview that may be shorter at the end of the file.
+    def pread(self, path, offset, length):
+        reader = self.open(path)
+        try:
+            return reader.pread(offset, length)
+        finally:
+            reader.close()
+
+    def set_attr(self, path, recursive=False, replicas=None, owner=None, group=None, mode=None, ttl_ms=None, ttl_action=None):
+        try:
+            curvine_libsdk.python_io_curvine_curvine_native_set_attr(
+                self.file_system_ptr, path, recursive, replicas, owner, group, mode, ttl_ms, ttl_action)
+        except Exception as e:
+            raise IOError(f"Native set attr failed: {e}")
+
+    def symlink(self, target, link, force=False):
+        try:
+            curvine_libsdk.python_io_curvine_curvine_native_symlink(self.file_system_ptr, target, link, force)
+        except Exception as e:
+            raise IOError(f"Native symlink failed: {e}")
+
+    def mount(self, ufs_path, cv_path, properties=None):
+        try:
+            curvine_libsdk.python_io_curvine_curvine_native_mount(self.file_system_ptr, ufs_path, cv_path, properties or {})
+        except Exception as e:
+            raise IOError(f"Native mount failed: {e}")
+
+    def umount(self, cv_path):
+        try:
+            curvine_libsdk.python_io_curvine_curvine_native_umount(self.file_system_ptr, cv_path)
+        except Exception as e:
+            raise IOError(f"Native umount failed: {e}")
+
+    def free(self, path, recursive=False):
+        try:
+            curvine_libsdk.python_io_curvine_curvine_native_free(self.file_system_ptr, path, recursive)
+        except Exception as e:
+            raise IOError(f"Native free failed: {e}")
+
+    def load(self, path):
+        try:
+            job_id, target_path = curvine_libsdk.python_io_curvine_curvine_native_submit_load(self.file_system_ptr, path)
+        except Exception as e:
+            raise IOError(f"Native submit load failed: {e}")
+        return {"job_id": job_id, "target_path": target_path}
+
+    def load_status(self, job_id):
+        try:
+            status_bytes = curvine_libsdk.python_io_curvine_curvine_native_get_load_status(self.file_system_ptr, job_id)
+        except Exception as e:
+            raise IOError(f"Native get load status failed: {e}")
+        status = GetJobStatusResponse()
+        status.ParseFromString(status_bytes)
+        return {
+            "job_id": status.job_id,
+            "state": status.state,
+            "source_path": status.source_path,
+            "target_path": status.target_path,
+            "total_size": status.progress.total_size,
+            "loaded_size": status.progress.loaded_size,
+            "message": status.progress.message,
+        }
+
+    def cancel_load(self, job_id):
+        try:
+            curvine_libsdk.python_io_curvine_curvine_native_cancel_load(self.file_system_ptr, job_id)
+        except Exception as e:
+            raise IOError(f"Native cancel load failed: {e}")
+
     def create(self, path, overwrite):
         try:
             writerHandle = curvine_libsdk.python_io_curvine_curvine_native_create(self.file_system_ptr, path, overwrite)
@@ -1,11 +1,14 @@
 from fsspec import AbstractFileSystem
+from fsspec.spec import AbstractBufferedFile
 import curvinefs.curvineClient as curvineClient
 import os;
 from urllib.parse import urlsplit
 
 class CurvineFileSystem(AbstractFileSystem):
+    # fsspec.filesystem("curvine", config_path=...) or "curvine:///path" urls.
+    protocol = "curvine"
 
-    def __init__(self, config_path, write_chunk_size, write_chunk_num, *args, **storage_options):
+    def __init__(self, config_path, write_chunk_size=1024 * 1024, write_chunk_num=8, *args, **storage_options):
         super().__init__(*args, **storage_options)
         self.client = curvineClient.CurvineClient(config_path, write_chunk_num, write_chunk_size)
         
@@ -16,7 +19,7 @@ class CurvineFileSystem(AbstractFileSystem):
         elif "w" in mode or "a" in mode:
             return self.create(path)
         elif "r" in mode:
-            return self.open(path)
+            return CurvineFile(self, path, mode, block_size or "default", cache_options=cache_options, **kwargs)
         else:
             raise ValueError("Mode must be r, w, a, or rw")
    
@@ -50,21 +53,40 @@ class CurvineFileSystem(AbstractFileSystem):
         else:
             raise ValueError("Path can be only string or list")
           
-    def cat_file(self, path, start=0, end=None, **kwargs):
+    def cat_file(self, path, start=None, end=None, **kwargs):
         path = self.formatPath(path)
+        size = self.size(path)
 
-        if start is None:
-            start = 0
+        start = 0 if start is None else start
+        end = size if end is None else end
+        if start < 0:
+            start = max(0, size + start)
+        if end < 0:
+            end = size + end
+        end = min(end, size)
+        if start >= end:
+            return b""
 
-        if end is None:  # Read until the end    
-            length = -1
-        else: # Read from start to end
-            length = end - start
-
-        return self.client.read_range(path, start, length)
+        return bytes(self.client.pread(path, start, end - start))
    
     def cat_ranges(self, paths, starts, ends, max_gap=None, on_error="return", **kwargs):
-        raise NotImplementedError
+        if not isinstance(starts, list):
+            starts = [starts] * len(paths)
+        if not isinstance(ends, list):
+            ends = [ends] * len(paths)
+        if len(starts) != len(paths) or len(ends) != len(paths):
+            raise ValueError("paths, starts and ends must have the same length")
+
+        out = []
+        for path, start, end in zip(paths, starts, ends):
+            try:
+                out.append(self.cat_file(path, start, end))
+            except Exception as e:
+                if on_error == "return":
+                    out.append(e)
+                else:
+                    raise
+        return out
     
     def checksum(self, path):
         raise NotImplementedError
@@ -144,6 +166,12 @@ class CurvineFileSystem(AbstractFileSystem):
     def info(self, path, *args, **kwargs):
         path = self.formatPath(path)
         file_status = self.client.get_file_status(path)
+        if file_status is None:
+            raise FileNotFoundError(path)
+        # fsspec requires name, size and type.
+        file_status["name"] = file_status["path"]
+        file_status["size"] = file_status["len"]
+        file_status["type"] = "directory" if file_status["is_dir"] else "file"
         return file_status 
    
     def invalidate_cache(self, path=None):
@@ -200,7 +228,10 @@ class CurvineFileSystem(AbstractFileSystem):
         path2 = self.formatPath(path2)
         self.client.mv(path1, path2)
     
-    def open(self, path):
+    # Without a mode the native sequential reader is returned, otherwise it behaves as fsspec open.
+    def open(self, path, mode=None, **kwargs):
+        if mode is not None:
+            return super().open(path, mode, **kwargs)
         path = self.formatPath(path)
         return self.client.open(path) 
     
@@ -245,7 +276,7 @@ class CurvineFileSystem(AbstractFileSystem):
         raise NotImplementedError
     
     def size(self, path):
-        raise NotImplementedError
+        return self.info(path)["size"]
     
     def sizes(self, paths):
         raise NotImplementedError
@@ -307,6 +338,79 @@ class CurvineFileSystem(AbstractFileSystem):
     
     def get_master_info(self):
         return self.client.get_master_info()
+
+    def pread(self, path, offset, length):
+        path = self.formatPath(path)
+        return self.client.pread(path, offset, length)
+
+    def pread_into(self, path, offset, buf):
+        path = self.formatPath(path)
+        return self.client.pread_into(path, offset, buf)
+
+    def set_attr(self, path, recursive=False, **kwargs):
+        path = self.formatPath(path)
+        self.client.set_attr(path, recursive, **kwargs)
+
+    def chmod(self, path, mode, recursive=False):
+        self.set_attr(path, recursive, mode=mode)
+
+    def chown(self, path, owner=None, group=None, recursive=False):
+        self.set_attr(path, recursive, owner=owner, group=group)
+
+    def symlink(self, target, link, force=False):
+        link = self.formatPath(link)
+        self.client.symlink(target, link, force)
+
+    def mount(self, ufs_path, cv_path, properties=None):
+        cv_path = self.formatPath(cv_path)
+        self.client.mount(ufs_path, cv_path, properties)
+
+    def umount(self, cv_path):
+        cv_path = self.formatPath(cv_path)
+        self.client.umount(cv_path)
+
+    def free(self, path, recursive=False):
+        path = self.formatPath(path)
+        self.client.free(path, recursive)
+
+    def load(self, path):
+        return self.client.load(path)
+
+    def load_status(self, job_id):
+        return self.client.load_status(job_id)
+
+    def cancel_load(self, job_id):
+        self.client.cancel_load(job_id)
     
     def close(self):
-        self.client.close()
\ No newline at end of file
+        self.client.close()
+
+
+class CurvineFile(AbstractBufferedFile):
+    # Ranges are fetched with positional reads of one native reader,
+    # which is opened on the first fetch and closed with the file.
+    reader = None
+
+    def _reader(self):
+        if self.reader is None:
+            self.reader = self.fs.client.open(self.path)
+        return self.reader
+
+    def _fetch_range(self, start, end):
+        return bytes(self._reader().pread(start, end - start))
+
+    # Read into the buffer directly when it is not served by the cache.
+    def readinto(self, b):
+        if self.closed:
+            raise ValueError("I/O operation on closed file.")
+        if self.loc >= self.size:
+            return 0
+        read_len = self._reader().pread_into(self.loc, b)
+        self.loc += read_len
+        return read_len
+
+    def close(self):
+        super().close()
+        if self.reader is not None:
+            self.reader.close()
+            self.reader = None
@@ -1,4 +1,5 @@
 import ctypes
+import threading
 import curvine_libsdk
 from io import BufferedReader
 
@@ -8,10 +9,14 @@ class CurvineReader:
     read_pos = 0  # read position
     read_buffer_pos = 0 # buffer position
     file_size = 0
+    positional = False
+    pread_lock = None
 
     def __init__(self, nativeHandle, file_size):
         self.readerHandle = nativeHandle
         self.file_size = file_size
+        self.positional = curvine_libsdk.python_io_curvine_curvine_native_support_positional(nativeHandle)
+        self.pread_lock = threading.Lock()
     
     # read
     def read(self, offset, length): 
@@ -48,6 +53,33 @@ class CurvineReader:
 
         return data.decode("utf-8", errors='ignore')
     
+    # Read into a writable buffer(bytearray, numpy array, memoryview) at the offset of the file,
+    # the data is written to the buffer directly. Returns the number of bytes read.
+    # Files cached in curvine are read positionally, so threads can share the reader;
+    # the reads of other files seek the reader and are serialized.
+    def pread_into(self, offset, buf):
+        view = memoryview(buf).cast("B")
+        if view.readonly:
+            raise ValueError("Buffer must be writable")
+        length = view.nbytes
+        if length == 0:
+            return 0
+
+        address = ctypes.addressof((ctypes.c_char * length).from_buffer(view))
+        try:
+            if self.positional:
+                return curvine_libsdk.python_io_curvine_curvine_native_pread(self.readerHandle, offset, address, length)
+            with self.pread_lock:
+                return curvine_libsdk.python_io_curvine_curvine_native_pread(self.readerHandle, offset, address, length)
+        except Exception as e:
+            raise IOError(f"Native pread failed: {e}")
+
+    # Read length bytes at the offset, returns a memoryview that may be shorter at the end of the file.
+    def pread(self, offset, length):
+        buf = bytearray(length)
+        read_len = self.pread_into(offset, buf)
+        return memoryview(buf)[:read_len]
+
     # seek
     def seek(self, pos):        
         file_len = self.file_size
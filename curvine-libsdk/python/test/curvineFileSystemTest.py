@@ -113,6 +113,34 @@ class Test(unittest.TestCase):
         print("------------------------------------------------")   
         fs.rename(test_path+"/aa.txt",test_path+"/a.txt")
 
+        writer = fs.create(test_path+"/p.txt", True)
+        writer.write(bytes("ABCDEFGHIJKLMNOPQRSTUVWXYZ",'utf-8'))
+        writer.close()
+
+        data = fs.pread(test_path+"/p.txt", 3, 4)
+        self.assertEqual(bytes(data), b"DEFG")
+        buf = bytearray(8)
+        read_len = fs.pread_into(test_path+"/p.txt", 20, buf)
+        self.assertEqual(read_len, 6)
+        self.assertEqual(bytes(buf[:read_len]), b"UVWXYZ")
+        self.assertEqual(fs.cat_ranges([test_path+"/p.txt"] * 2, [0, 24], [2, None]), [b"AB", b"YZ"])
+
+        # One reader serves all the positional reads.
+        reader = fs.client.open(test_path+"/p.txt")
+        self.assertEqual(bytes(reader.pread(0, 3)), b"ABC")
+        self.assertEqual(bytes(reader.pread(23, 8)), b"XYZ")
+        self.assertEqual(bytes(reader.pread(26, 8)), b"")
+        reader.close()
+
+        with fs.open(test_path+"/p.txt", "rb") as f:
+            f.seek(10)
+            self.assertEqual(f.read(3), b"KLM")
+
+        fs.chmod(test_path+"/p.txt", 0o600)
+        fs.symlink(test_path+"/p.txt", test_path+"/p.link", True)
+        print("ls result after symlink:", fs.ls(test_path, True))
+        print("------------------------------------------------")
+
         fs.close()
 
 
@@ -0,0 +1,281 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![allow(clippy::missing_safety_doc)]
+
+// The stable c abi of curvine, see include/libcurvine.h.
+// Thread safety: a filesystem handle can be shared by multiple threads, a reader or writer
+// handle must be used by one thread at a time. Errors are returned as negative codes and the
+// message is available by curvine_last_error in the same thread.
+
+use crate::c::{CUtils, CURVINE_OK};
+use crate::{c_err, LibFilesystem, LibFsReader, LibFsWriter};
+use bytes::BytesMut;
+use curvine_common::conf::ClusterConf;
+use curvine_common::state::FileStatus;
+use orpc::sys::{DataSlice, FFIUtils};
+use std::ffi::c_char;
+
+#[repr(C)]
+pub struct CurvineFileStatus {
+    pub id: i64,
+    // The full path, freed by curvine_free_file_status or curvine_free_list.
+    pub path: *mut c_char,
+    pub is_dir: bool,
+    pub len: i64,
+    pub mtime: i64,
+    pub atime: i64,
+    pub replicas: i32,
+    pub block_size: i64,
+    // 0: dir, 1: file, 2: link, 3: stream, 4: agg, 5: object.
+    pub file_type: i32,
+    pub mode: u32,
+    pub is_complete: bool,
+}
+
+impl From<FileStatus> for CurvineFileStatus {
+    fn from(status: FileStatus) -> Self {
+        Self {
+            id: status.id,
+            path: CUtils::new_c_string(&status.path),
+            is_dir: status.is_dir,
+            len: status.len,
+            mtime: status.mtime,
+            atime: status.atime,
+            replicas: status.replicas,
+            block_size: status.block_size,
+            file_type: status.file_type as i32,
+            mode: status.mode,
+            is_complete: status.is_complete,
+        }
+    }
+}
+
+// The message of the last failed call in the current thread, or null.
+#[no_mangle]
+pub extern "C" fn curvine_last_error() -> *const c_char {
+    CUtils::last_error()
+}
+
+// Connect to the cluster with the configuration file(curvine-cluster.toml).
+#[no_mangle]
+pub unsafe extern "C" fn curvine_connect(
+    conf_path: *const c_char,
+    fs_out: *mut *mut LibFilesystem,
+) -> i32 {
+    c_err!(CUtils::check_out(fs_out));
+    let conf_path = c_err!(CUtils::to_str(conf_path));
+    let conf = c_err!(ClusterConf::from(conf_path));
+    let fs = c_err!(LibFilesystem::new(conf));
+    fs_out.write(Box::into_raw(Box::new(fs)));
+    CURVINE_OK
+}
+
+// Release the filesystem, all its readers and writers must be closed before.
+#[no_mangle]
+pub unsafe extern "C" fn curvine_disconnect(fs_ptr: *mut LibFilesystem) -> i32 {
+    let fs = c_err!(CUtils::as_ref(fs_ptr));
+    fs.cleanup();
+    FFIUtils::free_raw_ptr(fs_ptr);
+    CURVINE_OK
+}
+
+// Open a file for reading, len_out receives the file length if it is not null.
+#[no_mangle]
+pub unsafe extern "C" fn curvine_open(
+    fs_ptr: *const LibFilesystem,
+    path: *const c_char,
+    reader_out: *mut *mut LibFsReader,
+    len_out: *mut i64,
+) -> i32 {
+    let fs = c_err!(CUtils::as_ref(fs_ptr));
+    let path = c_err!(CUtils::to_str(path));
+    c_err!(CUtils::check_out(reader_out));
+    let reader = c_err!(fs.open(path));
+    if !len_out.is_null() {
+        len_out.write(reader.len());
+    }
+    reader_out.write(Box::into_raw(Box::new(reader)));
+    CURVINE_OK
+}
+
+// Read from the current position, returns the bytes read, 0 at the end of the file.
+#[no_mangle]
+pub unsafe extern "C" fn curvine_read(
+    reader_ptr: *mut LibFsReader,
+    buf: *mut u8,
+    len: usize,
+) -> i64 {
+    let reader = c_err!(CUtils::as_mut(reader_ptr));
+    let buf = c_err!(CUtils::as_mut_slice(buf, len));
+    c_err!(reader.read_into(buf)) as i64
+}
+
+// Read at the offset and move the position of the reader after the data read.
+// Returns the bytes read, which is less than len only at the end of the file.
+#[no_mangle]
+pub unsafe extern "C" fn curvine_pread(
+    reader_ptr: *mut LibFsReader,
+    offset: i64,
+    buf: *mut u8,
+    len: usize,
+) -> i64 {
+    let reader = c_err!(CUtils::as_mut(reader_ptr));
+    let buf = c_err!(CUtils::as_mut_slice(buf, len));
+    c_err!(reader.pread(offset, buf)) as i64
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn curvine_close_reader(reader_ptr: *mut LibFsReader) -> i32 {
+    let reader = c_err!(CUtils::as_mut(reader_ptr));
+    c_err!(reader.complete(), FFIUtils::free_raw_ptr(reader_ptr));
+    CURVINE_OK
+}
+
+// Create a file for writing.
+#[no_mangle]
+pub unsafe extern "C" fn curvine_create(
+    fs_ptr: *const LibFilesystem,
+    path: *const c_char,
+    overwrite: bool,
+    writer_out: *mut *mut LibFsWriter,
+) -> i32 {
+    let fs = c_err!(CUtils::as_ref(fs_ptr));
+    let path = c_err!(CUtils::to_str(path));
+    c_err!(CUtils::check_out(writer_out));
+    let writer = c_err!(fs.create(path, overwrite));
+    writer_out.write(Box::into_raw(Box::new(writer)));
+    CURVINE_OK
+}
+
+// The data is copied, the buffer can be reused once the call returns.
+#[no_mangle]
+pub unsafe extern "C" fn curvine_write(
+    writer_ptr: *mut LibFsWriter,
+    buf: *const u8,
+    len: usize,
+) -> i32 {
+    let writer = c_err!(CUtils::as_mut(writer_ptr));
+    let buf = c_err!(CUtils::as_slice(buf, len));
+    if !buf.is_empty() {
+        c_err!(writer.write(DataSlice::Buffer(BytesMut::from(buf))));
+    }
+    CURVINE_OK
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn curvine_flush(writer_ptr: *mut LibFsWriter) -> i32 {
+    let writer = c_err!(CUtils::as_mut(writer_ptr));
+    c_err!(writer.flush());
+    CURVINE_OK
+}
+
+// Complete the file and release the writer.
+#[no_mangle]
+pub unsafe extern "C" fn curvine_close_writer(writer_ptr: *mut LibFsWriter) -> i32 {
+    let writer = c_err!(CUtils::as_mut(writer_ptr));
+    c_err!(writer.complete(), FFIUtils::free_raw_ptr(writer_ptr));
+    CURVINE_OK
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn curvine_stat(
+    fs_ptr: *const LibFilesystem,
+    path: *const c_char,
+    status_out: *mut CurvineFileStatus,
+) -> i32 {
+    let fs = c_err!(CUtils::as_ref(fs_ptr));
+    let path = c_err!(CUtils::to_str(path));
+    c_err!(CUtils::check_out(status_out));
+    let status = c_err!(fs.get_file_status(path));
+    status_out.write(status.into());
+    CURVINE_OK
+}
+
+// Free the strings of a status filled by curvine_stat.
+#[no_mangle]
+pub unsafe extern "C" fn curvine_free_file_status(status: *mut CurvineFileStatus) {
+    if let Some(status) = status.as_mut() {
+        CUtils::free_c_string(status.path);
+        status.path = std::ptr::null_mut();
+    }
+}
+
+// List a directory, the array must be freed by curvine_free_list.
+#[no_mangle]
+pub unsafe extern "C" fn curvine_list(
+    fs_ptr: *const LibFilesystem,
+    path: *const c_char,
+    list_out: *mut *mut CurvineFileStatus,
+    count_out: *mut usize,
+) -> i32 {
+    let fs = c_err!(CUtils::as_ref(fs_ptr));
+    let path = c_err!(CUtils::to_str(path));
+    c_err!(CUtils::check_out(list_out));
+    c_err!(CUtils::check_out(count_out));
+    let list = c_err!(fs.list_file_status(path));
+
+    let list: Box<[CurvineFileStatus]> = list.into_iter().map(|x| x.into()).collect();
+    count_out.write(list.len());
+    list_out.write(Box::into_raw(list) as *mut CurvineFileStatus);
+    CURVINE_OK
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn curvine_free_list(list: *mut CurvineFileStatus, count: usize) {
+    if list.is_null() {
+        return;
+    }
+    let list = Box::from_raw(std::ptr::slice_from_raw_parts_mut(list, count));
+    for status in list.iter() {
+        CUtils::free_c_string(status.path);
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn curvine_mkdir(
+    fs_ptr: *const LibFilesystem,
+    path: *const c_char,
+    create_parent: bool,
+) -> i32 {
+    let fs = c_err!(CUtils::as_ref(fs_ptr));
+    let path = c_err!(CUtils::to_str(path));
+    c_err!(fs.mkdir(path, create_parent));
+    CURVINE_OK
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn curvine_rename(
+    fs_ptr: *const LibFilesystem,
+    src: *const c_char,
+    dst: *const c_char,
+) -> i32 {
+    let fs = c_err!(CUtils::as_ref(fs_ptr));
+    let src = c_err!(CUtils::to_str(src));
+    let dst = c_err!(CUtils::to_str(dst));
+    c_err!(fs.rename(src, dst));
+    CURVINE_OK
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn curvine_delete(
+    fs_ptr: *const LibFilesystem,
+    path: *const c_char,
+    recursive: bool,
+) -> i32 {
+    let fs = c_err!(CUtils::as_ref(fs_ptr));
+    let path = c_err!(CUtils::to_str(path));
+    c_err!(fs.delete(path, recursive));
+    CURVINE_OK
+}
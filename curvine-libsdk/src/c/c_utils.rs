@@ -0,0 +1,104 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![allow(clippy::missing_safety_doc)]
+
+use curvine_common::error::FsError;
+use curvine_common::FsResult;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+pub struct CUtils;
+
+impl CUtils {
+    // Save the message of the error for curvine_last_error and return the error code.
+    pub fn set_error(error: impl Into<FsError>) -> i32 {
+        let error = error.into();
+        let msg = CString::new(error.to_string().replace('\0', " ")).unwrap_or_default();
+        LAST_ERROR.with(|x| x.replace(Some(msg)));
+        error.libc_kind() as i32
+    }
+
+    // The message is owned by the current thread and valid until its next failed call.
+    pub fn last_error() -> *const c_char {
+        LAST_ERROR.with(|x| match x.borrow().as_ref() {
+            Some(msg) => msg.as_ptr(),
+            None => std::ptr::null(),
+        })
+    }
+
+    pub unsafe fn to_str<'a>(s: *const c_char) -> FsResult<&'a str> {
+        if s.is_null() {
+            return Err(FsError::invalid_path("", "path is null"));
+        }
+        CStr::from_ptr(s)
+            .to_str()
+            .map_err(|e| FsError::invalid_path("", e.to_string()))
+    }
+
+    pub unsafe fn as_ref<'a, T>(ptr: *const T) -> FsResult<&'a T> {
+        ptr.as_ref()
+            .ok_or_else(|| FsError::from("handle is null".to_string()))
+    }
+
+    pub unsafe fn as_mut<'a, T>(ptr: *mut T) -> FsResult<&'a mut T> {
+        ptr.as_mut()
+            .ok_or_else(|| FsError::from("handle is null".to_string()))
+    }
+
+    // Output pointers are checked before the call does anything, so a null one changes nothing.
+    pub fn check_out<T>(ptr: *mut T) -> FsResult<()> {
+        if ptr.is_null() {
+            Err(FsError::from("output pointer is null".to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub unsafe fn as_slice<'a>(buf: *const u8, len: usize) -> FsResult<&'a [u8]> {
+        if len == 0 {
+            Ok(&[])
+        } else if buf.is_null() {
+            Err(FsError::from("buffer is null".to_string()))
+        } else {
+            Ok(std::slice::from_raw_parts(buf, len))
+        }
+    }
+
+    pub unsafe fn as_mut_slice<'a>(buf: *mut u8, len: usize) -> FsResult<&'a mut [u8]> {
+        if len == 0 {
+            Ok(&mut [])
+        } else if buf.is_null() {
+            Err(FsError::from("buffer is null".to_string()))
+        } else {
+            Ok(std::slice::from_raw_parts_mut(buf, len))
+        }
+    }
+
+    pub fn new_c_string(s: impl AsRef<str>) -> *mut c_char {
+        CString::new(s.as_ref().replace('\0', " "))
+            .unwrap_or_default()
+            .into_raw()
+    }
+
+    pub unsafe fn free_c_string(s: *mut c_char) {
+        if !s.is_null() {
+            drop(CString::from_raw(s));
+        }
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod c_abi;
+
+mod c_utils;
+pub use self::c_utils::CUtils;
+
+// Every function of the c abi returns CURVINE_OK or a negative error code, which is the
+// negative value of curvine_common::error::ErrorKind, the same as the java abi.
+// The codes are generated from ErrorKind by build.rs, CURVINE_ERR_COMMON is returned for
+// null handles, invalid arguments and all other errors.
+include!(concat!(env!("OUT_DIR"), "/error_codes.rs"));
+
+// If it fails, save the error message of the current thread and return the error code.
+#[macro_export]
+macro_rules! c_err {
+    ($expr:expr) => {{
+        match $expr {
+            Err(e) => return $crate::c::CUtils::set_error(e).into(),
+            Ok(res) => res,
+        }
+    }};
+
+    ($expr:expr, $final:expr) => {{
+        match $expr {
+            Err(e) => {
+                $final;
+                return $crate::c::CUtils::set_error(e).into();
+            }
+
+            Ok(res) => {
+                $final;
+                res
+            }
+        }
+    }};
+}
@@ -14,13 +14,16 @@
 
 #![allow(clippy::missing_safety_doc)]
 
-use crate::java::{JavaFilesystem, SUCCESS};
+use crate::java::{JavaFilesystem, JavaUtils, SUCCESS};
 use crate::{java_err, java_err2, LibFsReader, LibFsWriter};
+use curvine_common::FsResult;
 use jni::objects::{JLongArray, JObject, JString};
 use jni::sys::{jarray, jboolean, jint, jlong};
 use jni::JNIEnv;
+use orpc::err_box;
 use orpc::sys::DataSlice;
 use orpc::sys::{FFIUtils, RawVec};
+use std::slice;
 
 // It's too troublesome to parse object members by jni. Here the configuration will be passed through the json string.
 #[no_mangle]
@@ -117,8 +120,8 @@ pub unsafe extern "C" fn Java_io_curvine_CurvineNative_open(
 ) -> jlong {
     let fs = &*fs_ptr;
     let reader = java_err!(env, fs.open(&mut env, path));
-    // Returns the file length.
-    let arr = [reader.len()];
+    // Returns the file length and whether positional reads are supported.
+    let arr = [reader.len(), reader.support_positional() as jlong];
     env.set_long_array_region(tmp, 0, &arr).unwrap();
 
     FFIUtils::into_raw_ptr(reader)
@@ -154,6 +157,65 @@ pub unsafe extern "C" fn Java_io_curvine_CurvineNative_seek(
     SUCCESS
 }
 
+// Positional read into the direct buffer, the stream position is not changed.
+// Returns the number of bytes read, 0 at the end of the file.
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_curvine_CurvineNative_pread(
+    mut env: JNIEnv,
+    _this: JObject,
+    reader_ptr: *const LibFsReader,
+    pos: jlong,
+    buf: jlong,
+    len: jint,
+) -> jlong {
+    let reader = &*reader_ptr;
+    let buf = slice::from_raw_parts_mut(buf as *mut u8, len as usize);
+    let n = java_err!(env, reader.read_at(pos, buf));
+    n as jlong
+}
+
+// Read a batch of ranges concurrently, ranges is a flat array of (pos, buffer address, len).
+// The len of each range is replaced by the number of bytes read.
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_curvine_CurvineNative_preadVectored(
+    mut env: JNIEnv,
+    _this: JObject,
+    reader_ptr: *const LibFsReader,
+    ranges: JLongArray,
+) -> jlong {
+    let reader = &*reader_ptr;
+    java_err!(env, read_vectored(&mut env, reader, &ranges));
+    SUCCESS
+}
+
+unsafe fn read_vectored(
+    env: &mut JNIEnv,
+    reader: &LibFsReader,
+    ranges: &JLongArray,
+) -> FsResult<()> {
+    let mut arr = JavaUtils::get_long_array(env, ranges)?;
+    if arr.len() % 3 != 0 {
+        return err_box!("Invalid vectored read ranges, len {}", arr.len());
+    }
+
+    let bufs = arr
+        .chunks(3)
+        .map(|x| {
+            (
+                x[0],
+                slice::from_raw_parts_mut(x[1] as *mut u8, x[2] as usize),
+            )
+        })
+        .collect();
+    let read_len = reader.read_ranges(bufs)?;
+
+    for (index, n) in read_len.into_iter().enumerate() {
+        arr[index * 3 + 2] = n as jlong;
+    }
+    env.set_long_array_region(ranges, 0, &arr)?;
+    Ok(())
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn Java_io_curvine_CurvineNative_closeReader(
     mut env: JNIEnv,
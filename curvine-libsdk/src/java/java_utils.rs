@@ -13,8 +13,8 @@
 // limitations under the License.
 
 use curvine_common::error::FsError;
-use jni::objects::{JObject, JString, JThrowable, JValue};
-use jni::sys::{jarray, jboolean, jstring, JNI_TRUE};
+use jni::objects::{JLongArray, JObject, JString, JThrowable, JValue};
+use jni::sys::{jarray, jboolean, jlong, jstring, JNI_TRUE};
 use jni::JNIEnv;
 use orpc::CommonResult;
 
@@ -110,6 +110,13 @@ impl JavaUtils {
         Ok(array.into_raw())
     }
 
+    pub fn get_long_array(env: &mut JNIEnv, array: &JLongArray) -> CommonResult<Vec<jlong>> {
+        let len = env.get_array_length(array)?;
+        let mut buf = vec![0; len as usize];
+        env.get_long_array_region(array, 0, &mut buf)?;
+        Ok(buf)
+    }
+
     pub fn new_jstring(env: &mut JNIEnv, r_string: Option<String>) -> CommonResult<jstring> {
         if let Some(v) = r_string {
             let string = env.new_string(v)?;
@@ -25,3 +25,5 @@ mod lib_fs_reader;
 pub use self::lib_fs_reader::LibFsReader;
 
 pub mod java;
+
+pub mod c;
@@ -14,9 +14,13 @@
 
 use crate::{LibFsReader, LibFsWriter};
 use bytes::BytesMut;
+use curvine_client::rpc::JobMasterClient;
 use curvine_client::unified::UnifiedFileSystem;
 use curvine_common::conf::ClusterConf;
 use curvine_common::fs::{FileSystem, Path};
+use curvine_common::proto::GetJobStatusResponse;
+use curvine_common::state::{FileStatus, LoadJobResult, MountOptions, SetAttrOpts};
+use curvine_common::utils::ProtoUtils;
 use curvine_common::FsResult;
 use orpc::common::Logger;
 use orpc::runtime::{RpcRuntime, Runtime};
@@ -71,6 +75,18 @@ impl LibFilesystem {
             .block_on(async { self.inner.list_status_bytes(&path).await })
     }
 
+    pub fn get_file_status(&self, path: impl AsRef<str>) -> FsResult<FileStatus> {
+        let path = Path::from_str(path)?;
+        self.rt
+            .block_on(async { self.inner.get_status(&path).await })
+    }
+
+    pub fn list_file_status(&self, path: impl AsRef<str>) -> FsResult<Vec<FileStatus>> {
+        let path = Path::from_str(path)?;
+        self.rt
+            .block_on(async { self.inner.list_status(&path).await })
+    }
+
     pub fn open(&self, path: impl AsRef<str>) -> FsResult<LibFsReader> {
         let path = Path::from_str(path)?;
         let reader = self.rt.block_on(async { self.inner.open(&path).await })?;
@@ -91,6 +107,70 @@ impl LibFilesystem {
         Ok(LibFsWriter::new(self.rt.clone(), writer))
     }
 
+    pub fn set_attr(&self, path: impl AsRef<str>, opts: SetAttrOpts) -> FsResult<()> {
+        let path = Path::from_str(path)?;
+        self.rt
+            .block_on(async { self.inner.set_attr(&path, opts).await })
+    }
+
+    pub fn symlink(
+        &self,
+        target: impl AsRef<str>,
+        link: impl AsRef<str>,
+        force: bool,
+    ) -> FsResult<()> {
+        let link = Path::from_str(link)?;
+        self.rt
+            .block_on(async { self.inner.symlink(target.as_ref(), &link, force).await })
+    }
+
+    pub fn mount(
+        &self,
+        ufs_path: impl AsRef<str>,
+        cv_path: impl AsRef<str>,
+        opts: MountOptions,
+    ) -> FsResult<()> {
+        let ufs_path = Path::from_str(ufs_path)?;
+        let cv_path = Path::from_str(cv_path)?;
+        self.rt
+            .block_on(async { self.inner.mount(&ufs_path, &cv_path, opts).await })
+    }
+
+    pub fn umount(&self, cv_path: impl AsRef<str>) -> FsResult<()> {
+        let cv_path = Path::from_str(cv_path)?;
+        self.rt
+            .block_on(async { self.inner.umount(&cv_path).await })
+    }
+
+    pub fn free(&self, path: impl AsRef<str>, recursive: bool) -> FsResult<()> {
+        let path = Path::from_str(path)?;
+        self.rt
+            .block_on(async { self.inner.free(&path, recursive).await })
+    }
+
+    pub fn submit_load(&self, path: impl AsRef<str>) -> FsResult<LoadJobResult> {
+        let client = JobMasterClient::new(self.inner.fs_client());
+        self.rt.block_on(client.submit_load(path))
+    }
+
+    pub fn get_load_status(&self, job_id: impl AsRef<str>) -> FsResult<BytesMut> {
+        let client = JobMasterClient::new(self.inner.fs_client());
+        let status = self.rt.block_on(client.get_job_status(job_id))?;
+        let rep = GetJobStatusResponse {
+            job_id: status.job_id,
+            state: i8::from(status.state) as i32,
+            source_path: status.source_path,
+            target_path: status.target_path,
+            progress: ProtoUtils::work_progress_to_pb(status.progress),
+        };
+        Ok(ProtoUtils::encode(rep)?)
+    }
+
+    pub fn cancel_load(&self, job_id: impl AsRef<str>) -> FsResult<()> {
+        let client = JobMasterClient::new(self.inner.fs_client());
+        self.rt.block_on(client.cancel_job(job_id))
+    }
+
     pub fn get_master_info(&self) -> FsResult<BytesMut> {
         self.rt
             .block_on(async { self.inner.get_master_info_bytes().await })
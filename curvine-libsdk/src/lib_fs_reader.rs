@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use curvine_client::file::FsReaderPositional;
 use curvine_client::unified::UnifiedReader;
+use curvine_common::error::FsError;
 use curvine_common::fs::{Path, Reader};
 use curvine_common::FsResult;
 use orpc::runtime::{RpcRuntime, Runtime};
@@ -23,14 +25,22 @@ pub struct LibFsReader {
     rt: Arc<Runtime>,
     inner: UnifiedReader,
     cur_chunk: Option<DataSlice>,
+    // Only files cached in curvine support positional reads without moving the stream position.
+    positional: Option<FsReaderPositional>,
 }
 
 impl LibFsReader {
     pub fn new(rt: Arc<Runtime>, reader: UnifiedReader) -> Self {
+        let positional = match &reader {
+            UnifiedReader::Cv(r) => Some(r.positional()),
+            _ => None,
+        };
+
         Self {
             rt,
             inner: reader,
             cur_chunk: None,
+            positional,
         }
     }
 
@@ -52,6 +62,51 @@ impl LibFsReader {
         Ok(r.as_slice())
     }
 
+    // Read into the buffer, returns 0 at the end of the file.
+    pub fn read_into(&mut self, buf: &mut [u8]) -> FsResult<usize> {
+        self.rt.block_on(self.inner.read(buf))
+    }
+
+    // Fill the buffer from pos, it is shorter than the buffer only at the end of the file.
+    // Ufs readers have no positional reader, they seek to the pos and move the stream position.
+    pub fn pread(&mut self, pos: i64, buf: &mut [u8]) -> FsResult<usize> {
+        if self.positional.is_some() {
+            return self.read_at(pos, buf);
+        } else if pos >= self.inner.len() {
+            return Ok(0);
+        }
+        self.rt.block_on(async {
+            self.inner.seek(pos).await?;
+            self.inner.read_full(buf).await
+        })
+    }
+
+    pub fn support_positional(&self) -> bool {
+        self.positional.is_some()
+    }
+
+    // Positional read that leaves the stream position untouched, it can be called concurrently.
+    pub fn read_at(&self, pos: i64, buf: &mut [u8]) -> FsResult<usize> {
+        let positional = self.get_positional()?;
+        self.rt.block_on(positional.pread(pos, buf))
+    }
+
+    // Read a batch of ranges concurrently, returns the number of bytes read for each range.
+    pub fn read_ranges(&self, ranges: Vec<(i64, &mut [u8])>) -> FsResult<Vec<usize>> {
+        let positional = self.get_positional()?;
+        self.rt.block_on(positional.read_ranges(ranges))
+    }
+
+    fn get_positional(&self) -> FsResult<&FsReaderPositional> {
+        match &self.positional {
+            Some(v) => Ok(v),
+            None => Err(FsError::unsupported(format!(
+                "positional read is not supported for {}",
+                self.path()
+            ))),
+        }
+    }
+
     pub fn complete(&mut self) -> FsResult<()> {
         let _ = self.cur_chunk.take();
         self.rt.block_on(self.inner.complete())
@@ -3,15 +3,29 @@
 use crate::python::PythonFilesystem;
 use crate::{LibFsReader, LibFsWriter};
 use curvine_common::error::{ErrorKind, FsError};
+use curvine_common::state::{SetAttrOpts, TtlAction};
+use curvine_common::FsResult;
 use orpc::sys::DataSlice;
 use orpc::sys::{FFIUtils, RawVec};
 use pyo3::exceptions::*;
 use pyo3::prelude::*;
 use pyo3::types::{PyBytes, PyList};
+use std::collections::HashMap;
+
+// All blocking calls release the GIL, so python threads(e.g. dataloader workers) do io in parallel.
+// The native handles are passed into the closure as addresses and dereferenced inside.
+fn fs_ref<'a>(ptr: usize) -> &'a PythonFilesystem {
+    unsafe { &*(ptr as *mut PythonFilesystem) }
+}
 
 #[pyfunction]
-pub fn python_io_curvine_curvine_native_new_filesystem(conf_path: String) -> PyResult<i64> {
-    let fs = PythonFilesystem::new(conf_path).map_err(|e| e.into_py_err())?;
+pub fn python_io_curvine_curvine_native_new_filesystem(
+    conf_path: String,
+    py: Python<'_>,
+) -> PyResult<i64> {
+    let fs = py
+        .allow_threads(|| PythonFilesystem::new(conf_path))
+        .map_err(|e| e.into_py_err())?;
     Ok(FFIUtils::into_raw_ptr(fs))
 }
 
@@ -20,10 +34,11 @@ pub fn python_io_curvine_curvine_native_create(
     ptr: usize,
     path: String,
     overwrite: bool,
+    py: Python<'_>,
 ) -> PyResult<i64> {
-    let fs_ptr = ptr as *mut PythonFilesystem;
-    let fs = unsafe { &*fs_ptr };
-    let writer = fs.create(path, overwrite).map_err(|e| e.into_py_err())?;
+    let writer = py
+        .allow_threads(|| fs_ref(ptr).create(path, overwrite))
+        .map_err(|e| e.into_py_err())?;
     Ok(FFIUtils::into_raw_ptr(writer))
 }
 
@@ -32,10 +47,11 @@ pub fn python_io_curvine_curvine_native_append(
     ptr: usize,
     path: String,
     tmp: &Bound<'_, PyList>,
+    py: Python<'_>,
 ) -> PyResult<i64> {
-    let fs_ptr = ptr as *mut PythonFilesystem;
-    let fs = unsafe { &*fs_ptr };
-    let writer = fs.append(path).map_err(|e| e.into_py_err())?;
+    let writer = py
+        .allow_threads(|| fs_ref(ptr).append(path))
+        .map_err(|e| e.into_py_err())?;
     let arr = writer.pos();
 
     tmp.set_item(0, arr)?;
@@ -43,30 +59,43 @@ pub fn python_io_curvine_curvine_native_append(
 }
 
 #[pyfunction]
-pub fn python_io_curvine_curvine_native_write(ptr: usize, buf: i64, len: i32) -> PyResult<()> {
-    let writer_ptr = ptr as *mut LibFsWriter;
-    let writer = unsafe { &mut *writer_ptr };
-    let raw_vec = RawVec::from_raw(buf as *mut u8, len as usize);
-    let buf = DataSlice::MemSlice(raw_vec);
-    writer.write(buf).map_err(|e| e.into_py_err())?;
+pub fn python_io_curvine_curvine_native_write(
+    ptr: usize,
+    buf: i64,
+    len: i32,
+    py: Python<'_>,
+) -> PyResult<()> {
+    py.allow_threads(|| {
+        let writer = unsafe { &mut *(ptr as *mut LibFsWriter) };
+        let raw_vec = RawVec::from_raw(buf as *mut u8, len as usize);
+        writer.write(DataSlice::MemSlice(raw_vec))
+    })
+    .map_err(|e| e.into_py_err())?;
 
     Ok(())
 }
 
 #[pyfunction]
-pub fn python_io_curvine_curvine_native_flush(ptr: usize) -> PyResult<()> {
-    let writer_ptr = ptr as *mut LibFsWriter;
-    let writer = unsafe { &mut *writer_ptr };
-    writer.flush().map_err(|e| e.into_py_err())?;
+pub fn python_io_curvine_curvine_native_flush(ptr: usize, py: Python<'_>) -> PyResult<()> {
+    py.allow_threads(|| {
+        let writer = unsafe { &mut *(ptr as *mut LibFsWriter) };
+        writer.flush()
+    })
+    .map_err(|e| e.into_py_err())?;
     Ok(())
 }
 
 #[pyfunction]
-pub unsafe fn python_io_curvine_curvine_native_close_writer(ptr: usize) -> PyResult<()> {
-    let writer_ptr = ptr as *mut LibFsWriter;
-    let writer = unsafe { &mut *writer_ptr };
-    writer.complete().map_err(|e| e.into_py_err())?;
-    FFIUtils::free_raw_ptr(writer_ptr);
+pub unsafe fn python_io_curvine_curvine_native_close_writer(
+    ptr: usize,
+    py: Python<'_>,
+) -> PyResult<()> {
+    py.allow_threads(|| {
+        let writer = unsafe { &mut *(ptr as *mut LibFsWriter) };
+        writer.complete()
+    })
+    .map_err(|e| e.into_py_err())?;
+    FFIUtils::free_raw_ptr(ptr as *mut LibFsWriter);
     Ok(())
 }
 
@@ -75,42 +104,94 @@ pub fn python_io_curvine_curvine_native_open(
     ptr: usize,
     path: String,
     tmp: &Bound<'_, PyList>,
+    py: Python<'_>,
 ) -> PyResult<i64> {
-    let fs_ptr = ptr as *mut PythonFilesystem;
-    let fs = unsafe { &*fs_ptr };
-    let reader = fs.open(path).map_err(|e| e.into_py_err())?;
+    let reader = py
+        .allow_threads(|| fs_ref(ptr).open(path))
+        .map_err(|e| e.into_py_err())?;
     let arr = reader.len();
     tmp.set_item(0, arr)?;
     Ok(FFIUtils::into_raw_ptr(reader))
 }
 
 #[pyfunction]
-pub fn python_io_curvine_curvine_native_read(ptr: usize, tmp: &Bound<'_, PyList>) -> PyResult<()> {
-    let reader_ptr = ptr as *mut LibFsReader;
-    let reader = unsafe { &mut *reader_ptr };
-    let bytes = reader.read().map_err(|e| e.into_py_err())?;
-    let bytes_ptr = bytes.as_ptr() as i64;
-    let bytes_len = bytes.len() as i64;
+pub fn python_io_curvine_curvine_native_read(
+    ptr: usize,
+    tmp: &Bound<'_, PyList>,
+    py: Python<'_>,
+) -> PyResult<()> {
+    let (bytes_ptr, bytes_len) = py
+        .allow_threads(|| {
+            let reader = unsafe { &mut *(ptr as *mut LibFsReader) };
+            let bytes = reader.read()?;
+            FsResult::Ok((bytes.as_ptr() as i64, bytes.len() as i64))
+        })
+        .map_err(|e| e.into_py_err())?;
     tmp.set_item(0, bytes_ptr)?;
     tmp.set_item(1, bytes_len)?;
 
     Ok(())
 }
 
+// Read at the offset of the file with the reader into the buffer at address buf, the buffer is
+// owned by python(bytearray, numpy array, etc.), so the data is copied only once.
+// Returns the bytes read, which is less than len only at the end of the file.
+// Readers of files cached in curvine read positionally and can be shared by threads, the others
+// seek the reader and must be used by one thread at a time.
+#[pyfunction]
+pub fn python_io_curvine_curvine_native_pread(
+    ptr: usize,
+    offset: i64,
+    buf: i64,
+    len: i64,
+    py: Python<'_>,
+) -> PyResult<i64> {
+    if offset < 0 || len < 0 {
+        return Err(PyValueError::new_err("offset and len must be non-negative"));
+    }
+
+    let read_len = py
+        .allow_threads(|| {
+            let buf = unsafe { std::slice::from_raw_parts_mut(buf as *mut u8, len as usize) };
+            let reader = unsafe { &*(ptr as *const LibFsReader) };
+            if reader.support_positional() {
+                reader.read_at(offset, buf)
+            } else {
+                let reader = unsafe { &mut *(ptr as *mut LibFsReader) };
+                reader.pread(offset, buf)
+            }
+        })
+        .map_err(|e| e.into_py_err())?;
+    Ok(read_len as i64)
+}
+
+#[pyfunction]
+pub fn python_io_curvine_curvine_native_support_positional(ptr: usize) -> PyResult<bool> {
+    let reader = unsafe { &*(ptr as *const LibFsReader) };
+    Ok(reader.support_positional())
+}
+
 #[pyfunction]
-pub fn python_io_curvine_curvine_native_seek(ptr: usize, pos: i64) -> PyResult<()> {
-    let reader_ptr = ptr as *mut LibFsReader;
-    let reader = unsafe { &mut *reader_ptr };
-    reader.seek(pos).map_err(|e| e.into_py_err())?;
+pub fn python_io_curvine_curvine_native_seek(ptr: usize, pos: i64, py: Python<'_>) -> PyResult<()> {
+    py.allow_threads(|| {
+        let reader = unsafe { &mut *(ptr as *mut LibFsReader) };
+        reader.seek(pos)
+    })
+    .map_err(|e| e.into_py_err())?;
     Ok(())
 }
 
 #[pyfunction]
-pub unsafe fn python_io_curvine_curvine_native_close_reader(ptr: usize) -> PyResult<()> {
-    let reader_ptr = ptr as *mut LibFsReader;
-    let reader = &mut *reader_ptr;
-    reader.complete().map_err(|e| e.into_py_err())?;
-    FFIUtils::free_raw_ptr(reader_ptr);
+pub unsafe fn python_io_curvine_curvine_native_close_reader(
+    ptr: usize,
+    py: Python<'_>,
+) -> PyResult<()> {
+    py.allow_threads(|| {
+        let reader = unsafe { &mut *(ptr as *mut LibFsReader) };
+        reader.complete()
+    })
+    .map_err(|e| e.into_py_err())?;
+    FFIUtils::free_raw_ptr(ptr as *mut LibFsReader);
     Ok(())
 }
 
@@ -126,10 +207,11 @@ pub fn python_io_curvine_curvine_native_mkdir(
     ptr: usize,
     path: String,
     create_parent: bool,
+    py: Python<'_>,
 ) -> PyResult<bool> {
-    let fs_ptr = ptr as *mut PythonFilesystem;
-    let fs = unsafe { &*fs_ptr };
-    let is_success = fs.mkdir(path, create_parent).map_err(|e| e.into_py_err())?;
+    let is_success = py
+        .allow_threads(|| fs_ref(ptr).mkdir(path, create_parent))
+        .map_err(|e| e.into_py_err())?;
     Ok(is_success)
 }
 
@@ -139,11 +221,10 @@ pub fn python_io_curvine_curvine_native_get_file_status<'py>(
     path: String,
     py: Python<'py>,
 ) -> PyResult<Bound<'py, PyBytes>> {
-    let fs_ptr = ptr as *mut PythonFilesystem;
-    let fs = unsafe { &*fs_ptr };
-    let status = fs.get_file_status(path).map_err(|e| e.into_py_err())?;
-    let bytes_data = status.freeze().to_vec();
-    Ok(PyBytes::new(py, &bytes_data))
+    let status = py
+        .allow_threads(|| fs_ref(ptr).get_file_status(path))
+        .map_err(|e| e.into_py_err())?;
+    Ok(PyBytes::new(py, &status))
 }
 
 #[pyfunction]
@@ -152,11 +233,10 @@ pub fn python_io_curvine_curvine_native_list_status<'py>(
     path: String,
     py: Python<'py>,
 ) -> PyResult<Bound<'py, PyBytes>> {
-    let fs_ptr = ptr as *mut PythonFilesystem;
-    let fs = unsafe { &*fs_ptr };
-    let status = fs.list_status(path).map_err(|e| e.into_py_err())?;
-    let bytes_data = status.freeze().to_vec();
-    Ok(PyBytes::new(py, &bytes_data))
+    let status = py
+        .allow_threads(|| fs_ref(ptr).list_status(path))
+        .map_err(|e| e.into_py_err())?;
+    Ok(PyBytes::new(py, &status))
 }
 
 #[pyfunction]
@@ -164,10 +244,11 @@ pub fn python_io_curvine_curvine_native_rename(
     ptr: usize,
     src: String,
     dst: String,
+    py: Python<'_>,
 ) -> PyResult<bool> {
-    let fs_ptr = ptr as *mut PythonFilesystem;
-    let fs = unsafe { &*fs_ptr };
-    let is_rename = fs.rename(src, dst).map_err(|e| e.into_py_err())?;
+    let is_rename = py
+        .allow_threads(|| fs_ref(ptr).rename(src, dst))
+        .map_err(|e| e.into_py_err())?;
     Ok(is_rename)
 }
 
@@ -176,10 +257,10 @@ pub fn python_io_curvine_curvine_native_delete(
     ptr: usize,
     path: String,
     recursive: bool,
+    py: Python<'_>,
 ) -> PyResult<()> {
-    let fs_ptr = ptr as *mut PythonFilesystem;
-    let fs = unsafe { &*fs_ptr };
-    fs.delete(path, recursive).map_err(|e| e.into_py_err())?;
+    py.allow_threads(|| fs_ref(ptr).delete(path, recursive))
+        .map_err(|e| e.into_py_err())?;
     Ok(())
 }
 
@@ -188,11 +269,139 @@ pub fn python_io_curvine_curvine_native_get_master_info<'py>(
     ptr: usize,
     py: Python<'py>,
 ) -> PyResult<Bound<'py, PyBytes>> {
-    let fs_ptr = ptr as *mut PythonFilesystem;
-    let fs = unsafe { &*fs_ptr };
-    let status = fs.get_master_info().map_err(|e| e.into_py_err())?;
-    let bytes_data = status.freeze().to_vec();
-    Ok(PyBytes::new(py, &bytes_data))
+    let status = py
+        .allow_threads(|| fs_ref(ptr).get_master_info())
+        .map_err(|e| e.into_py_err())?;
+    Ok(PyBytes::new(py, &status))
+}
+
+// Unset attributes are passed as None and left unchanged.
+#[pyfunction]
+#[pyo3(signature = (ptr, path, recursive, replicas=None, owner=None, group=None, mode=None, ttl_ms=None, ttl_action=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn python_io_curvine_curvine_native_set_attr(
+    ptr: usize,
+    path: String,
+    recursive: bool,
+    replicas: Option<i32>,
+    owner: Option<String>,
+    group: Option<String>,
+    mode: Option<u32>,
+    ttl_ms: Option<i64>,
+    ttl_action: Option<String>,
+    py: Python<'_>,
+) -> PyResult<()> {
+    let mut opts = SetAttrOpts::builder().recursive(recursive);
+    if let Some(replicas) = replicas {
+        opts = opts.replicas(replicas);
+    }
+    if let Some(owner) = owner {
+        opts = opts.owner(owner);
+    }
+    if let Some(group) = group {
+        opts = opts.group(group);
+    }
+    if let Some(mode) = mode {
+        opts = opts.mode(mode);
+    }
+    if let Some(ttl_ms) = ttl_ms {
+        opts = opts.ttl_ms(ttl_ms);
+    }
+    if let Some(ttl_action) = ttl_action {
+        let action = TtlAction::try_from(ttl_action.as_str())
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        opts = opts.ttl_action(action);
+    }
+    let opts = opts.build();
+
+    py.allow_threads(|| fs_ref(ptr).set_attr(path, opts))
+        .map_err(|e| e.into_py_err())?;
+    Ok(())
+}
+
+#[pyfunction]
+pub fn python_io_curvine_curvine_native_symlink(
+    ptr: usize,
+    target: String,
+    link: String,
+    force: bool,
+    py: Python<'_>,
+) -> PyResult<()> {
+    py.allow_threads(|| fs_ref(ptr).symlink(target, link, force))
+        .map_err(|e| e.into_py_err())?;
+    Ok(())
+}
+
+#[pyfunction]
+pub fn python_io_curvine_curvine_native_mount(
+    ptr: usize,
+    ufs_path: String,
+    cv_path: String,
+    properties: HashMap<String, String>,
+    py: Python<'_>,
+) -> PyResult<()> {
+    py.allow_threads(|| fs_ref(ptr).mount(ufs_path, cv_path, properties))
+        .map_err(|e| e.into_py_err())?;
+    Ok(())
+}
+
+#[pyfunction]
+pub fn python_io_curvine_curvine_native_umount(
+    ptr: usize,
+    cv_path: String,
+    py: Python<'_>,
+) -> PyResult<()> {
+    py.allow_threads(|| fs_ref(ptr).umount(cv_path))
+        .map_err(|e| e.into_py_err())?;
+    Ok(())
+}
+
+#[pyfunction]
+pub fn python_io_curvine_curvine_native_free(
+    ptr: usize,
+    path: String,
+    recursive: bool,
+    py: Python<'_>,
+) -> PyResult<()> {
+    py.allow_threads(|| fs_ref(ptr).free(path, recursive))
+        .map_err(|e| e.into_py_err())?;
+    Ok(())
+}
+
+// Returns (job_id, target_path).
+#[pyfunction]
+pub fn python_io_curvine_curvine_native_submit_load(
+    ptr: usize,
+    path: String,
+    py: Python<'_>,
+) -> PyResult<(String, String)> {
+    let res = py
+        .allow_threads(|| fs_ref(ptr).submit_load(path))
+        .map_err(|e| e.into_py_err())?;
+    Ok((res.job_id, res.target_path))
+}
+
+#[pyfunction]
+pub fn python_io_curvine_curvine_native_get_load_status<'py>(
+    ptr: usize,
+    job_id: String,
+    py: Python<'py>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let status = py
+        .allow_threads(|| fs_ref(ptr).get_load_status(job_id))
+        .map_err(|e| e.into_py_err())?;
+    Ok(PyBytes::new(py, &status))
+}
+
+#[pyfunction]
+pub fn python_io_curvine_curvine_native_cancel_load(
+    ptr: usize,
+    job_id: String,
+    py: Python<'_>,
+) -> PyResult<()> {
+    py.allow_threads(|| fs_ref(ptr).cancel_load(job_id))
+        .map_err(|e| e.into_py_err())?;
+    Ok(())
 }
 
 pub trait ToPyErr {
@@ -251,6 +460,11 @@ fn curvine_libsdk(m: &Bound<'_, PyModule>) -> PyResult<()> {
     )?)?;
     m.add_function(wrap_pyfunction!(python_io_curvine_curvine_native_open, m)?)?;
     m.add_function(wrap_pyfunction!(python_io_curvine_curvine_native_read, m)?)?;
+    m.add_function(wrap_pyfunction!(python_io_curvine_curvine_native_pread, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        python_io_curvine_curvine_native_support_positional,
+        m
+    )?)?;
     m.add_function(wrap_pyfunction!(python_io_curvine_curvine_native_seek, m)?)?;
     m.add_function(wrap_pyfunction!(
         python_io_curvine_curvine_native_close_reader,
@@ -281,6 +495,32 @@ fn curvine_libsdk(m: &Bound<'_, PyModule>) -> PyResult<()> {
         python_io_curvine_curvine_native_get_master_info,
         m
     )?)?;
+    m.add_function(wrap_pyfunction!(
+        python_io_curvine_curvine_native_set_attr,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        python_io_curvine_curvine_native_symlink,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(python_io_curvine_curvine_native_mount, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        python_io_curvine_curvine_native_umount,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(python_io_curvine_curvine_native_free, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        python_io_curvine_curvine_native_submit_load,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        python_io_curvine_curvine_native_get_load_status,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        python_io_curvine_curvine_native_cancel_load,
+        m
+    )?)?;
 
     Ok(())
 }
@@ -1,7 +1,9 @@
 use crate::{LibFilesystem, LibFsReader, LibFsWriter};
 use bytes::BytesMut;
+use curvine_common::state::{LoadJobResult, MountOptions, SetAttrOpts};
 use curvine_common::{conf::ClusterConf, FsResult};
 use pyo3::prelude::*;
+use std::collections::HashMap;
 
 //Define the PythonFilesystem struct
 #[pyclass]
@@ -65,4 +67,50 @@ impl PythonFilesystem {
         let status = self.inner.get_master_info()?;
         Ok(status)
     }
+
+    //Set file attributes
+    pub fn set_attr(&self, path: String, opts: SetAttrOpts) -> FsResult<()> {
+        self.inner.set_attr(path, opts)
+    }
+
+    //Create a symbolic link
+    pub fn symlink(&self, target: String, link: String, force: bool) -> FsResult<()> {
+        self.inner.symlink(target, link, force)
+    }
+
+    //Mount the ufs path
+    pub fn mount(
+        &self,
+        ufs_path: String,
+        cv_path: String,
+        properties: HashMap<String, String>,
+    ) -> FsResult<()> {
+        let opts = MountOptions::builder().set_properties(properties).build();
+        self.inner.mount(ufs_path, cv_path, opts)
+    }
+
+    //Umount the curvine path
+    pub fn umount(&self, cv_path: String) -> FsResult<()> {
+        self.inner.umount(cv_path)
+    }
+
+    //Free the cached data
+    pub fn free(&self, path: String, recursive: bool) -> FsResult<()> {
+        self.inner.free(path, recursive)
+    }
+
+    //Submit a load job
+    pub fn submit_load(&self, path: String) -> FsResult<LoadJobResult> {
+        self.inner.submit_load(path)
+    }
+
+    //Get the load job status
+    pub fn get_load_status(&self, job_id: String) -> FsResult<BytesMut> {
+        self.inner.get_load_status(job_id)
+    }
+
+    //Cancel a load job
+    pub fn cancel_load(&self, job_id: String) -> FsResult<()> {
+        self.inner.cancel_load(job_id)
+    }
 }
@@ -0,0 +1,164 @@
+/*
+ * Copyright 2025 OPPO.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/*
+ * Exercise libcurvine against a running cluster, it is run by curvine-tests/tests/c_abi_test.rs.
+ * usage: curvine_test <curvine-cluster.toml>
+ */
+
+#include <pthread.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "libcurvine.h"
+
+#define CHECK(expr)                                                                  \
+    do {                                                                             \
+        if (!(expr)) {                                                               \
+            const char *err = curvine_last_error();                                  \
+            fprintf(stderr, "%s:%d check failed: %s, %s\n", __FILE__, __LINE__, #expr, \
+                    err ? err : "");                                                 \
+            exit(1);                                                                 \
+        }                                                                            \
+    } while (0)
+
+#define DIR "/c_abi"
+#define FILE_PATH DIR "/a.log"
+#define CHUNK_SIZE 4096
+#define CHUNK_NUM 16
+#define THREAD_NUM 4
+
+static curvine_fs_t *fs = NULL;
+
+static uint8_t expect_byte(int64_t pos) { return (uint8_t)(pos % 251); }
+
+static void write_file(void) {
+    curvine_writer_t *writer = NULL;
+    uint8_t buf[CHUNK_SIZE];
+    int64_t pos = 0;
+
+    CHECK(curvine_create(fs, FILE_PATH, true, &writer) == CURVINE_OK);
+    for (int i = 0; i < CHUNK_NUM; i++) {
+        for (int j = 0; j < CHUNK_SIZE; j++) {
+            buf[j] = expect_byte(pos++);
+        }
+        CHECK(curvine_write(writer, buf, CHUNK_SIZE) == CURVINE_OK);
+    }
+    CHECK(curvine_flush(writer) == CURVINE_OK);
+    CHECK(curvine_close_writer(writer) == CURVINE_OK);
+}
+
+static void read_file(void) {
+    curvine_reader_t *reader = NULL;
+    int64_t len = 0, pos = 0, n;
+    uint8_t buf[1000];
+
+    CHECK(curvine_open(fs, FILE_PATH, &reader, &len) == CURVINE_OK);
+    CHECK(len == CHUNK_SIZE * CHUNK_NUM);
+    while ((n = curvine_read(reader, buf, sizeof(buf))) > 0) {
+        for (int64_t i = 0; i < n; i++) {
+            CHECK(buf[i] == expect_byte(pos + i));
+        }
+        pos += n;
+    }
+    CHECK(n == 0);
+    CHECK(pos == len);
+    CHECK(curvine_close_reader(reader) == CURVINE_OK);
+}
+
+// Every thread reads with a reader of its own, the filesystem is shared.
+static void *pread_file(void *arg) {
+    int64_t id = (int64_t)(intptr_t)arg;
+    curvine_reader_t *reader = NULL;
+    int64_t len = 0;
+    uint8_t buf[CHUNK_SIZE];
+
+    CHECK(curvine_open(fs, FILE_PATH, &reader, &len) == CURVINE_OK);
+    for (int64_t off = id * 1000; off < len; off += THREAD_NUM * 1000) {
+        int64_t n = curvine_pread(reader, off, buf, sizeof(buf));
+        int64_t expect = len - off < CHUNK_SIZE ? len - off : CHUNK_SIZE;
+        CHECK(n == expect);
+        for (int64_t i = 0; i < n; i++) {
+            CHECK(buf[i] == expect_byte(off + i));
+        }
+    }
+    CHECK(curvine_pread(reader, len, buf, sizeof(buf)) == 0);
+    CHECK(curvine_close_reader(reader) == CURVINE_OK);
+    return NULL;
+}
+
+static void stat_and_list(void) {
+    curvine_file_status_t status;
+    curvine_file_status_t *list = NULL;
+    size_t count = 0;
+
+    CHECK(curvine_stat(fs, FILE_PATH, &status) == CURVINE_OK);
+    CHECK(!status.is_dir && status.is_complete);
+    CHECK(status.len == CHUNK_SIZE * CHUNK_NUM);
+    CHECK(strcmp(status.path, FILE_PATH) == 0);
+    curvine_free_file_status(&status);
+
+    CHECK(curvine_list(fs, DIR, &list, &count) == CURVINE_OK);
+    CHECK(count == 1);
+    CHECK(strcmp(list[0].path, FILE_PATH) == 0);
+    curvine_free_list(list, count);
+}
+
+int main(int argc, char **argv) {
+    pthread_t threads[THREAD_NUM];
+    curvine_file_status_t status;
+    curvine_writer_t *writer = NULL;
+    size_t count = 0;
+
+    CHECK(argc == 2);
+    CHECK(curvine_connect(argv[1], NULL) == CURVINE_ERR_COMMON);
+    CHECK(curvine_connect(argv[1], &fs) == CURVINE_OK);
+
+    CHECK(curvine_mkdir(fs, DIR, true) == CURVINE_OK);
+    write_file();
+    read_file();
+    for (int i = 0; i < THREAD_NUM; i++) {
+        CHECK(pthread_create(&threads[i], NULL, pread_file, (void *)(intptr_t)i) == 0);
+    }
+    for (int i = 0; i < THREAD_NUM; i++) {
+        CHECK(pthread_join(threads[i], NULL) == 0);
+    }
+    stat_and_list();
+
+    // Errors are returned as codes with the message of the current thread.
+    CHECK(curvine_stat(fs, DIR "/not_exists", &status) == CURVINE_ERR_FILE_NOT_FOUND);
+    CHECK(curvine_last_error() != NULL);
+    CHECK(curvine_create(fs, FILE_PATH, false, &writer) == CURVINE_ERR_FILE_ALREADY_EXISTS);
+    CHECK(writer == NULL);
+    CHECK(curvine_open(NULL, FILE_PATH, NULL, NULL) == CURVINE_ERR_COMMON);
+
+    // A null output pointer is rejected before the call does anything.
+    CHECK(curvine_create(fs, DIR "/null.log", true, NULL) == CURVINE_ERR_COMMON);
+    CHECK(curvine_stat(fs, DIR "/null.log", &status) == CURVINE_ERR_FILE_NOT_FOUND);
+    CHECK(curvine_open(fs, FILE_PATH, NULL, NULL) == CURVINE_ERR_COMMON);
+    CHECK(curvine_stat(fs, FILE_PATH, NULL) == CURVINE_ERR_COMMON);
+    CHECK(curvine_list(fs, DIR, NULL, &count) == CURVINE_ERR_COMMON);
+    CHECK(curvine_list(fs, DIR, NULL, NULL) == CURVINE_ERR_COMMON);
+
+    CHECK(curvine_rename(fs, FILE_PATH, DIR "/b.log") == CURVINE_OK);
+    CHECK(curvine_stat(fs, FILE_PATH, &status) == CURVINE_ERR_FILE_NOT_FOUND);
+    CHECK(curvine_delete(fs, DIR, true) == CURVINE_OK);
+    CHECK(curvine_disconnect(fs) == CURVINE_OK);
+
+    printf("c abi test passed\n");
+    return 0;
+}
@@ -166,6 +166,12 @@ pub struct ListObjectResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delimiter: Option<String>,
     pub is_truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_continuation_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_after: Option<String>,
     #[serde(default)]
     pub contents: Vec<ListObjectContent>,
     #[serde(default)]
@@ -182,6 +188,9 @@ impl Default for ListObjectResult {
             max_keys: None,
             delimiter: None,
             is_truncated: false,
+            continuation_token: None,
+            next_continuation_token: None,
+            start_after: None,
             contents: Vec::new(),
             common_prefixes: Vec::new(),
         }
@@ -13,9 +13,9 @@
 // limitations under the License.
 
 use super::types::{PutContext, PutOperation};
-use super::ListObjectContent;
 use super::ListObjectHandler;
 use super::ListObjectOption;
+use super::ListObjectPage;
 use super::ListObjectVersionsHandler;
 use super::ListObjectVersionsOption;
 use super::ListObjectVersionsResult;
@@ -658,7 +658,7 @@ impl ListObjectHandler for S3Handlers {
         &self,
         opt: &ListObjectOption,
         bucket: &str,
-    ) -> impl std::future::Future<Output = Result<Vec<ListObjectContent>, String>> + Send {
+    ) -> impl std::future::Future<Output = Result<ListObjectPage, String>> + Send {
         let this = self.clone();
         let opt = opt.clone();
         let bucket = bucket.to_string();
@@ -702,33 +702,59 @@ impl ListObjectHandler for S3Handlers {
                 (bkt_path.clone(), String::new())
             };
 
-            let list = this
+            // The continuation token is the last key returned, the listing continues after it.
+            let dir_prefix = if prefix_dir.is_empty() {
+                String::new()
+            } else {
+                format!("{}/", prefix_dir)
+            };
+            let start_after = match opt.continuation_token.as_ref().or(opt.start_after.as_ref()) {
+                None => None,
+                Some(marker) => match marker.strip_prefix(&dir_prefix) {
+                    Some(name) => Some(name.to_string()),
+                    None if marker.as_str() < dir_prefix.as_str() => None,
+                    // All keys in the directory are before the marker.
+                    None => return Ok((vec![], None)),
+                },
+            };
+            let max_keys = opt.max_keys.unwrap_or(1000).max(0) as usize;
+
+            let mut iter = this
                 .fs
-                .list_status(&list_path)
+                .list_status_iter(&list_path, start_after)
                 .await
                 .map_err(|e| e.to_string())?;
 
             let mut contents = Vec::new();
+            let mut next_token = None;
 
-            for st in list {
+            while let Some(st) = iter.next().await.map_err(|e| e.to_string())? {
                 // Build the S3 key from file status
-                let key = if prefix_dir.is_empty() {
-                    st.name.clone()
-                } else {
-                    format!("{}/{}", prefix_dir, st.name)
-                };
+                let key = format!("{}{}", dir_prefix, st.name);
 
-                // Apply prefix filter
+                // Apply prefix filter, the keys are in order so no later key matches
+                // once a key sorts after the prefix.
                 if let Some(pref) = &opt.prefix {
                     if !key.starts_with(pref) {
+                        if key.as_str() > pref.as_str() {
+                            break;
+                        }
                         continue;
                     }
                 }
 
+                if contents.len() >= max_keys {
+                    next_token = contents
+                        .last()
+                        .map(|x| x.key.clone())
+                        .or(opt.continuation_token.clone())
+                        .or(opt.start_after.clone());
+                    break;
+                }
                 contents.push(file_status_to_list_object_content(&st, key));
             }
 
-            Ok(contents)
+            Ok((contents, next_token))
         }
     }
 }
@@ -135,13 +135,16 @@ pub struct ListObjectOption {
     pub start_after: Option<String>,
 }
 
+/// The objects of a page, and the continuation token of the next page if the listing was truncated.
+pub type ListObjectPage = (Vec<ListObjectContent>, Option<String>);
+
 /// OPTIMIZED: No async_trait - zero Box allocation
 pub trait ListObjectHandler: Send + Sync {
     fn handle(
         &self,
         opt: &ListObjectOption,
         bucket: &str,
-    ) -> impl std::future::Future<Output = Result<Vec<ListObjectContent>, String>> + Send;
+    ) -> impl std::future::Future<Output = Result<ListObjectPage, String>> + Send;
 }
 
 pub use crate::s3::dto::{DeleteMarker, ListObjectVersionsResult, ObjectVersion};
@@ -509,7 +512,7 @@ pub async fn handle_get_list_object<
 
     let ret = handler.handle(&opt, bucket.as_str()).await;
     match ret {
-        Ok(ans) => {
+        Ok((ans, next_token)) => {
             let (contents, common_prefixes) = if let Some(ref delim) = opt.delimiter {
                 let files = ans
                     .into_iter()
@@ -529,7 +532,10 @@ pub async fn handle_get_list_object<
                 key_count: Some(total_count as u32),
                 max_keys: Some(opt.max_keys.unwrap_or(1000) as u32),
                 delimiter: opt.delimiter,
-                is_truncated: false,
+                is_truncated: next_token.is_some(),
+                continuation_token: opt.continuation_token,
+                next_continuation_token: next_token,
+                start_after: opt.start_after,
                 contents,
                 common_prefixes,
             };
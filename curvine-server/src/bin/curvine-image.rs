@@ -0,0 +1,175 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap::{Parser, Subcommand};
+use curvine_common::version;
+use curvine_server::master::image::{ImageEntry, ImageReader, ImageReport};
+use orpc::common::{ByteUnit, FileUtils};
+use orpc::{err_box, try_err, CommonResult};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+// Offline viewer of the master metadata, it reads a checkpoint created by FsDir::create_checkpoint,
+// e.g. the checkpoint of a raft snapshot, and does not need a running cluster.
+fn main() -> CommonResult<()> {
+    let args = ImageArgs::parse();
+    if !FileUtils::exists(&args.checkpoint) {
+        return err_box!("Checkpoint {} does not exist", args.checkpoint);
+    }
+    let reader = ImageReader::open(&args.checkpoint)?;
+
+    match &args.command {
+        ImageCommand::Dump {
+            path,
+            format,
+            output,
+        } => {
+            let mut writer: Box<dyn Write> = match output {
+                Some(v) => Box::new(BufWriter::new(try_err!(File::create(v)))),
+                None => Box::new(BufWriter::new(std::io::stdout())),
+            };
+
+            let csv = match format.as_str() {
+                "json" => false,
+                "csv" => true,
+                v => return err_box!("Unsupported format {}, expected json or csv", v),
+            };
+            if csv {
+                try_err!(writeln!(writer, "{}", ImageEntry::CSV_HEADER));
+            }
+            // One entry per line, so that a large namespace is never held in memory.
+            reader.walk(path, |entry| {
+                let line = if csv { entry.to_csv() } else { entry.to_json() };
+                try_err!(writeln!(writer, "{}", line));
+                Ok(())
+            })?;
+            try_err!(writer.flush());
+        }
+
+        ImageCommand::Report {
+            path,
+            depth,
+            top,
+            json,
+        } => {
+            let mut report = ImageReport::new(path, *depth);
+            reader.walk(path, |entry| {
+                report.add(&entry);
+                Ok(())
+            })?;
+            let report = report.finish(*top);
+
+            if *json {
+                println!("{}", report.to_json());
+            } else {
+                print_report(&report);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_report(report: &ImageReport) {
+    let size = |len: i64| ByteUnit::byte_to_string(len as u64);
+
+    println!("Path:         {}", report.path);
+    println!("Directories:  {}", report.total_dirs);
+    println!("Files:        {}", report.total_files);
+    println!("Blocks:       {}", report.total_blocks);
+    println!("Size:         {}", size(report.total_len));
+
+    println!();
+    println!("Size by directory (depth {}):", report.depth);
+    println!(
+        "{:<50} {:>12} {:>10} {:>10}",
+        "Path", "Size", "Files", "Dirs"
+    );
+    for dir in &report.dirs {
+        println!(
+            "{:<50} {:>12} {:>10} {:>10}",
+            dir.path,
+            size(dir.len),
+            dir.files,
+            dir.dirs
+        );
+    }
+
+    println!();
+    println!("File size histogram:");
+    println!("{:<12} {:>10} {:>12}", "Size", "Files", "Total");
+    for bucket in &report.size_histogram {
+        let label = match bucket.max_len {
+            Some(0) => "0".to_string(),
+            Some(v) => format!("<= {}", size(v)),
+            None => "larger".to_string(),
+        };
+        println!(
+            "{:<12} {:>10} {:>12}",
+            label,
+            bucket.files,
+            size(bucket.len)
+        );
+    }
+}
+
+#[derive(Debug, Parser)]
+#[command(version = version::VERSION)]
+pub struct ImageArgs {
+    /// Checkpoint directory of the master metadata
+    #[arg(long)]
+    checkpoint: String,
+
+    #[command(subcommand)]
+    command: ImageCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ImageCommand {
+    /// Dump the entries under the path, with the size, replicas, storage policy, owner, mtime
+    /// and block ids of each
+    Dump {
+        #[arg(long, default_value = "/")]
+        path: String,
+
+        #[arg(long, default_value = "json", help = "Output format: json or csv")]
+        format: String,
+
+        #[arg(long, help = "Output file, the default is stdout")]
+        output: Option<String>,
+    },
+
+    /// Size by directory and the file size histogram of the path
+    Report {
+        #[arg(long, default_value = "/")]
+        path: String,
+
+        #[arg(
+            long,
+            default_value = "2",
+            help = "Depth of the directories below the path"
+        )]
+        depth: usize,
+
+        #[arg(
+            long,
+            default_value = "50",
+            help = "Number of the largest directories, 0 means all"
+        )]
+        top: usize,
+
+        #[arg(long, help = "Print the report as JSON")]
+        json: bool,
+    },
+}
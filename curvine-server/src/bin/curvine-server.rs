@@ -15,6 +15,7 @@
 use clap::Parser;
 use curvine_common::conf::ClusterConf;
 use curvine_common::version;
+use curvine_server::master::backup::MetaBackupManager;
 use curvine_server::master::Master;
 use curvine_server::worker::Worker;
 use orpc::common::{LocalTime, Utils};
@@ -37,6 +38,12 @@ fn main() -> CommonResult<()> {
     match service {
         ServiceType::Master => {
             conf.check_master_hostname()?;
+            if let Some(name) = &args.restore_from {
+                Master::init_logger(&conf);
+                MetaBackupManager::restore(&conf, name)?;
+                // The restored metadata must not be formatted.
+                conf.format_master = false;
+            }
             let master = Master::with_conf(conf)?;
             master.block_on_start();
         }
@@ -60,6 +67,11 @@ pub struct ServerArgs {
     // Configuration file path
     #[arg(long, default_value = "")]
     conf: String,
+
+    // Restore the master metadata from a backup in master.backup_dir before starting,
+    // the existing metadata and journal are deleted.
+    #[arg(long)]
+    restore_from: Option<String>,
 }
 
 impl ServerArgs {
@@ -0,0 +1,231 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_client::unified::UfsFileSystem;
+use curvine_common::conf::MasterConf;
+use curvine_common::fs::{FileSystem, Path, Reader, Writer};
+use curvine_common::state::MetaBackupInfo;
+use curvine_common::FsResult;
+use orpc::common::FileUtils;
+use orpc::runtime::{RpcRuntime, Runtime};
+use orpc::{err_box, try_err};
+use std::fs;
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+/// Where the metadata backups are saved, a local directory or a ufs path.
+/// Each backup is a directory named by the backup name, which contains the checkpoint files
+/// and an info file. The info file is written last, a backup without it is incomplete and ignored.
+pub enum BackupStore {
+    Local(String),
+
+    Ufs {
+        root: String,
+        ufs: UfsFileSystem,
+        rt: Arc<Runtime>,
+    },
+}
+
+impl BackupStore {
+    pub const INFO_FILE: &'static str = "backup_info.json";
+    const BUF_SIZE: usize = 4 * 1024 * 1024;
+
+    pub fn new(conf: &MasterConf, rt: Arc<Runtime>) -> FsResult<Self> {
+        let dir = conf.backup_dir.trim_end_matches('/');
+        if let Some(local) = dir.strip_prefix("file://") {
+            return Ok(Self::Local(local.to_string()));
+        }
+
+        let path = Path::from_str(dir)?;
+        if path.scheme().is_none() {
+            Ok(Self::Local(dir.to_string()))
+        } else {
+            let ufs = UfsFileSystem::new(&path, conf.backup_properties.clone(), None)?;
+            Ok(Self::Ufs {
+                root: dir.to_string(),
+                ufs,
+                rt,
+            })
+        }
+    }
+
+    fn join(root: &str, name: &str) -> String {
+        format!("{}/{}", root, name)
+    }
+
+    // Upload the files of local_dir as a backup, the info file is written after all the files.
+    pub fn upload(&self, local_dir: &str, info: &MetaBackupInfo) -> FsResult<()> {
+        let files = FileUtils::list_files(local_dir, false)?;
+        let info_str = try_err!(serde_json::to_string_pretty(info));
+
+        match self {
+            Self::Local(root) => {
+                let dir = Self::join(root, &info.name);
+                FileUtils::delete_path(&dir, true)?;
+                for file in &files {
+                    let dst = Self::join(&dir, file);
+                    FileUtils::create_parent_dir(&dst, true)?;
+                    fs::copy(Self::join(local_dir, file), dst)?;
+                }
+                fs::write(Self::join(&dir, Self::INFO_FILE), info_str)?;
+            }
+
+            Self::Ufs { root, ufs, rt } => rt.block_on(async {
+                let dir = Path::from_str(Self::join(root, &info.name))?;
+                if ufs.exists(&dir).await? {
+                    ufs.delete(&dir, true).await?;
+                }
+                ufs.mkdir(&dir, true).await?;
+
+                for file in &files {
+                    let dst = Path::from_str(Self::join(dir.full_path(), file))?;
+                    let mut writer = ufs.create(&dst, true).await?;
+                    let mut src = fs::File::open(Self::join(local_dir, file))?;
+                    let mut buf = vec![0u8; Self::BUF_SIZE];
+                    loop {
+                        let n = src.read(&mut buf)?;
+                        if n == 0 {
+                            break;
+                        }
+                        writer.write(&buf[..n]).await?;
+                    }
+                    writer.complete().await?;
+                }
+
+                let dst = Path::from_str(Self::join(dir.full_path(), Self::INFO_FILE))?;
+                let mut writer = ufs.create(&dst, true).await?;
+                writer.write(info_str.as_bytes()).await?;
+                writer.complete().await?;
+                FsResult::Ok(())
+            })?,
+        }
+
+        Ok(())
+    }
+
+    // List the complete backups, sorted from oldest to newest.
+    pub fn list(&self) -> FsResult<Vec<MetaBackupInfo>> {
+        let mut backups = vec![];
+        match self {
+            Self::Local(root) => {
+                if !FileUtils::exists(root) {
+                    return Ok(backups);
+                }
+                for entry in fs::read_dir(root)? {
+                    let entry = entry?;
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let info_path = Self::join(&Self::join(root, &name), Self::INFO_FILE);
+                    if !name.starts_with(MetaBackupInfo::NAME_PREFIX)
+                        || !FileUtils::exists(&info_path)
+                    {
+                        continue;
+                    }
+                    let info_str = fs::read_to_string(&info_path)?;
+                    backups.push(try_err!(serde_json::from_str(&info_str)));
+                }
+            }
+
+            Self::Ufs { root, ufs, rt } => rt.block_on(async {
+                let root = Path::from_str(root)?;
+                if !ufs.exists(&root).await? {
+                    return FsResult::Ok(());
+                }
+                for status in ufs.list_status(&root).await? {
+                    if !status.is_dir || !status.name.starts_with(MetaBackupInfo::NAME_PREFIX) {
+                        continue;
+                    }
+                    let info_path = Path::from_str(Self::join(
+                        status.path.trim_end_matches('/'),
+                        Self::INFO_FILE,
+                    ))?;
+                    if !ufs.exists(&info_path).await? {
+                        continue;
+                    }
+                    let mut reader = ufs.open(&info_path).await?;
+                    let info_str = reader.read_as_string().await?;
+                    reader.complete().await?;
+                    backups.push(try_err!(serde_json::from_str(&info_str)));
+                }
+                FsResult::Ok(())
+            })?,
+        }
+
+        backups.sort_by(|a: &MetaBackupInfo, b| a.name.cmp(&b.name));
+        Ok(backups)
+    }
+
+    pub fn get(&self, name: &str) -> FsResult<MetaBackupInfo> {
+        match self.list()?.into_iter().find(|x| x.name == name) {
+            Some(v) => Ok(v),
+            None => err_box!("Backup {} not found or incomplete", name),
+        }
+    }
+
+    // Download the checkpoint files of the backup to local_dir.
+    pub fn download(&self, name: &str, local_dir: &str) -> FsResult<MetaBackupInfo> {
+        let info = self.get(name)?;
+        FileUtils::delete_path(local_dir, true)?;
+        FileUtils::create_dir(local_dir, true)?;
+
+        match self {
+            Self::Local(root) => {
+                let dir = Self::join(root, name);
+                for file in FileUtils::list_files(&dir, false)? {
+                    if file == Self::INFO_FILE {
+                        continue;
+                    }
+                    let dst = Self::join(local_dir, &file);
+                    FileUtils::create_parent_dir(&dst, true)?;
+                    fs::copy(Self::join(&dir, &file), dst)?;
+                }
+            }
+
+            Self::Ufs { root, ufs, rt } => rt.block_on(async {
+                let dir = Path::from_str(Self::join(root, name))?;
+                for status in ufs.list_status(&dir).await? {
+                    if status.is_dir || status.name == Self::INFO_FILE {
+                        continue;
+                    }
+                    let mut reader = ufs.open(&Path::from_str(&status.path)?).await?;
+                    let mut dst = fs::File::create(Self::join(local_dir, &status.name))?;
+                    let mut buf = vec![0u8; Self::BUF_SIZE];
+                    loop {
+                        let n = reader.read(&mut buf).await?;
+                        if n == 0 {
+                            break;
+                        }
+                        dst.write_all(&buf[..n])?;
+                    }
+                    reader.complete().await?;
+                    dst.sync_all()?;
+                }
+                FsResult::Ok(())
+            })?,
+        }
+
+        Ok(info)
+    }
+
+    pub fn delete(&self, name: &str) -> FsResult<()> {
+        match self {
+            Self::Local(root) => FileUtils::delete_path(Self::join(root, name), true)?,
+
+            Self::Ufs { root, ufs, rt } => rt.block_on(async {
+                let dir = Path::from_str(Self::join(root, name))?;
+                ufs.delete(&dir, true).await
+            })?,
+        }
+        Ok(())
+    }
+}
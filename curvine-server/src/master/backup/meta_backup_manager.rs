@@ -0,0 +1,285 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::master::backup::BackupStore;
+use crate::master::fs::MasterFilesystem;
+use crate::master::journal::JournalSystem;
+use crate::master::{FollowerReader, MasterMonitor};
+use curvine_common::conf::ClusterConf;
+use curvine_common::error::FsError;
+use curvine_common::executor::ScheduledExecutor;
+use curvine_common::raft::storage::{JournalLogStorage, LogStorage};
+use curvine_common::raft::{RaftError, RaftUtils};
+use curvine_common::state::{MetaBackupInfo, MetaBackupStatus};
+use curvine_common::FsResult;
+use log::{error, info, warn};
+use orpc::common::{FileUtils, LocalTime, TimeSpent};
+use orpc::runtime::{LoopTask, RpcRuntime, Runtime};
+use orpc::CommonResult;
+use parking_lot::Mutex;
+use prost::Message;
+use raft::eraftpb::Snapshot;
+use raft::Storage;
+use std::sync::Arc;
+
+/// Takes the metadata backups and uploads them to master.backup_dir.
+/// A backup is a rocksdb checkpoint of the metadata together with the applied journal index and
+/// term at that moment, it is taken under the fs_dir write lock so no operation is half applied.
+#[derive(Clone)]
+pub struct MetaBackupManager {
+    fs: MasterFilesystem,
+    log_store: JournalLogStorage,
+    // Gets the index that covers the acknowledged writes, none if the journal is not replicated.
+    read_index: Option<Arc<FollowerReader>>,
+    rt: Arc<Runtime>,
+    status: Arc<Mutex<MetaBackupStatus>>,
+    store: Arc<BackupStore>,
+    // The local directory to create checkpoints in, separated from the raft snapshot checkpoints.
+    staging_dir: String,
+    retain_num: usize,
+    interval_ms: u64,
+    lock: Arc<Mutex<()>>,
+}
+
+impl MetaBackupManager {
+    pub const STAGING_DIR: &'static str = "backup";
+
+    pub fn new(
+        conf: &ClusterConf,
+        fs: MasterFilesystem,
+        js: &JournalSystem,
+        rt: Arc<Runtime>,
+    ) -> FsResult<Self> {
+        let read_index = if conf.testing {
+            None
+        } else {
+            Some(js.follower_reader())
+        };
+        let store = BackupStore::new(&conf.master, rt.clone())?;
+        Ok(Self {
+            fs,
+            log_store: js.log_store(),
+            read_index,
+            rt,
+            status: Arc::new(Mutex::new(MetaBackupStatus::default())),
+            store: Arc::new(store),
+            staging_dir: FileUtils::join_path(conf.master.meta_dir.as_str(), Self::STAGING_DIR),
+            retain_num: conf.master.backup_retain_num.max(1),
+            interval_ms: conf.master.backup_interval_ms(),
+            lock: Arc::new(Mutex::new(())),
+        })
+    }
+
+    pub fn start(&self) -> CommonResult<()> {
+        let scheduler = ScheduledExecutor::new("meta-backup", self.interval_ms);
+        scheduler.start(MetaBackupTask {
+            manager: self.clone(),
+            monitor: self.fs.master_monitor.clone(),
+        })?;
+        info!(
+            "Metadata backup started, interval {} ms, retain {}",
+            self.interval_ms, self.retain_num
+        );
+        Ok(())
+    }
+
+    // Start a backup in the background, the status shows when it is done.
+    pub fn start_backup(&self) -> MetaBackupStatus {
+        {
+            let mut status = self.status.lock();
+            if status.running {
+                return status.clone();
+            }
+            status.running = true;
+            status.start_time = LocalTime::mills() as i64;
+        }
+
+        let manager = self.clone();
+        self.rt.spawn_blocking(move || {
+            if let Err(e) = manager.backup() {
+                error!("metadata backup failed: {}", e);
+            }
+        });
+        self.status()
+    }
+
+    pub fn status(&self) -> MetaBackupStatus {
+        self.status.lock().clone()
+    }
+
+    // Take a backup, upload it and delete the expired backups.
+    pub fn backup(&self) -> FsResult<MetaBackupInfo> {
+        let _guard = self.lock.lock();
+        {
+            let mut status = self.status.lock();
+            status.running = true;
+            status.start_time = LocalTime::mills() as i64;
+        }
+
+        let res = self.backup0();
+        let mut status = self.status.lock();
+        status.running = false;
+        match &res {
+            Ok(info) => {
+                status.last_backup = Some(info.clone());
+                status.last_error = None;
+            }
+            Err(e) => status.last_error = Some(e.to_string()),
+        }
+        res
+    }
+
+    // The index of the entries applied to the checkpoint. The leader applies an operation
+    // before journaling it, so wait until the journal has caught up with the acknowledged writes.
+    fn applied_index(&self) -> FsResult<u64> {
+        match &self.read_index {
+            Some(reader) => self.rt.block_on(reader.leader_read_index()),
+            None => Ok(self.log_store.last_index().map_err(RaftError::from)?),
+        }
+    }
+
+    fn backup0(&self) -> FsResult<MetaBackupInfo> {
+        let spend = TimeSpent::new();
+        let create_time = LocalTime::mills() as i64;
+
+        let (journal_index, journal_term, dir) = {
+            // Hold the write lock, no path operation runs between the index and the checkpoint.
+            let fs_dir = self.fs.fs_dir.write();
+            let index = self.applied_index()?;
+            let term = self.log_store.term(index).map_err(RaftError::from)?;
+            let name = MetaBackupInfo::backup_name(create_time, index);
+            let dir = FileUtils::join_path(self.staging_dir.as_str(), name.as_str());
+
+            FileUtils::delete_path(&dir, true)?;
+            fs_dir.create_checkpoint_to(&dir)?;
+            (index, term, dir)
+        };
+
+        let res = self.upload(&dir, create_time, journal_index, journal_term);
+        if let Err(e) = FileUtils::delete_path(&dir, true) {
+            warn!("delete backup staging dir {}: {}", dir, e);
+        }
+        let info = res?;
+
+        info!(
+            "Metadata backup {} created, journal index {}, files {}, len {}, cost {} ms",
+            info.name,
+            info.journal_index,
+            info.files,
+            info.len,
+            spend.used_ms()
+        );
+
+        if let Err(e) = self.purge() {
+            warn!("purge metadata backups: {}", e);
+        }
+        Ok(info)
+    }
+
+    fn upload(
+        &self,
+        dir: &str,
+        create_time: i64,
+        journal_index: u64,
+        journal_term: u64,
+    ) -> FsResult<MetaBackupInfo> {
+        let files = FileUtils::list_files(dir, true)?;
+        let mut len = 0;
+        for file in &files {
+            len += FileUtils::metadata(file)?.len();
+        }
+
+        let info = MetaBackupInfo {
+            name: MetaBackupInfo::backup_name(create_time, journal_index),
+            journal_index,
+            journal_term,
+            create_time,
+            files: files.len() as u64,
+            len,
+        };
+        self.store.upload(dir, &info)?;
+        Ok(info)
+    }
+
+    // Keep the latest retain_num backups.
+    fn purge(&self) -> FsResult<()> {
+        let backups = self.store.list()?;
+        let del_num = backups.len().saturating_sub(self.retain_num);
+        for info in backups.iter().take(del_num) {
+            self.store.delete(&info.name)?;
+            info!("delete expired metadata backup {}", info.name);
+        }
+        Ok(())
+    }
+
+    pub fn list(&self) -> FsResult<Vec<MetaBackupInfo>> {
+        self.store.list()
+    }
+
+    // Restore the metadata of a master from a backup, it must be called before the master starts.
+    // The meta and journal directories are formatted, the backup is installed as the raft snapshot
+    // and loaded when the master starts. All masters of the cluster need to be restored from the same backup.
+    pub fn restore(conf: &ClusterConf, name: &str) -> FsResult<MetaBackupInfo> {
+        let rt = Arc::new(Runtime::new("meta-restore", 2, 2));
+        let store = BackupStore::new(&conf.master, rt)?;
+        let info = store.get(name)?;
+
+        // A raft snapshot index starts at 1.
+        let index = info.journal_index.max(1);
+        let term = info.journal_term.max(1);
+
+        FileUtils::delete_path(&conf.master.meta_dir, true)?;
+        let log_store = JournalLogStorage::from_conf(&conf.journal, true)?;
+
+        let db_conf = conf.meta_rocks_conf();
+        let dir = format!("{}/ck-{}", db_conf.checkpoint_dir, index);
+        store.download(name, &dir)?;
+
+        let data = RaftUtils::create_file_snapshot(&dir, 0, index)?;
+        let mut snapshot = Snapshot::default();
+        snapshot.set_data(data.encode_to_vec());
+        snapshot.mut_metadata().index = index;
+        snapshot.mut_metadata().term = term;
+        log_store.apply_snapshot(snapshot)?;
+
+        info!(
+            "Metadata restored from backup {}, journal index {}, term {}",
+            info.name, index, term
+        );
+        Ok(info)
+    }
+}
+
+struct MetaBackupTask {
+    manager: MetaBackupManager,
+    monitor: MasterMonitor,
+}
+
+impl LoopTask for MetaBackupTask {
+    type Error = FsError;
+
+    fn run(&self) -> FsResult<()> {
+        if !self.monitor.is_active() {
+            return Ok(());
+        }
+        if let Err(e) = self.manager.backup() {
+            error!("metadata backup failed: {}", e);
+        }
+        Ok(())
+    }
+
+    fn terminate(&self) -> bool {
+        self.monitor.is_stop()
+    }
+}
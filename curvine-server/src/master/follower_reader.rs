@@ -0,0 +1,124 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::master::journal::JournalProgress;
+use curvine_client::file::{FsClient, FsContext};
+use curvine_common::conf::ClusterConf;
+use curvine_common::error::FsError;
+use curvine_common::proto::FollowerReadProto;
+use curvine_common::raft::{LeaderContact, NodeId, RaftClient, RaftGroup};
+use curvine_common::state::ReadConsistency;
+use curvine_common::FsResult;
+use orpc::runtime::Runtime;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Decides whether a standby master can serve a read request.
+///
+/// A rejected read is answered with a not leader error, the client then sends it to the leader.
+/// For a linearizable read the standby gets a read index from the leader master and waits until
+/// its state machine has applied it. The leader only returns an index after the journal entries
+/// of the writes it has acknowledged are committed, so the read observes all of them.
+pub struct FollowerReader {
+    client: RaftClient,
+    local_id: Option<NodeId>,
+    leader_contact: LeaderContact,
+    leader_client: FsClient,
+    progress: Option<Arc<JournalProgress>>,
+    timeout: Duration,
+}
+
+impl FollowerReader {
+    pub fn new(
+        rt: Arc<Runtime>,
+        conf: &ClusterConf,
+        leader_contact: LeaderContact,
+        progress: Option<Arc<JournalProgress>>,
+    ) -> FsResult<Self> {
+        let group = RaftGroup::from_conf(&conf.journal);
+        let local_id = group.get_node_id(&conf.journal.local_addr()).ok();
+        let client = RaftClient::new(rt.clone(), &group, conf.journal.new_client_conf());
+        let leader_client = FsClient::new(Arc::new(FsContext::with_rt(conf.clone(), rt)?));
+
+        Ok(Self {
+            client,
+            local_id,
+            leader_contact,
+            leader_client,
+            progress,
+            timeout: Duration::from_millis(conf.journal.io_timeout_ms),
+        })
+    }
+
+    fn local_id(&self) -> FsResult<NodeId> {
+        match self.local_id {
+            Some(v) => Ok(v),
+            None => Err(FsError::not_leader("Not a journal node")),
+        }
+    }
+
+    // Called on the leader, waits until the journal entries sent so far are committed,
+    // then returns the committed index confirmed by a quorum.
+    pub async fn leader_read_index(&self) -> FsResult<u64> {
+        if let Some(progress) = &self.progress {
+            let sent = progress.sent();
+            if tokio::time::timeout(self.timeout, progress.wait_flushed(sent))
+                .await
+                .is_err()
+            {
+                return Err(FsError::common(format!(
+                    "Timed out waiting for the journal to flush {} entries, flushed {}",
+                    sent,
+                    progress.flushed()
+                )));
+            }
+        }
+
+        let index = self.client.read_index(self.local_id()?).await?;
+        Ok(index)
+    }
+
+    pub async fn check(&self, read: &FollowerReadProto) -> FsResult<()> {
+        match ReadConsistency::from(read.consistency) {
+            ReadConsistency::Linearizable => {
+                let local_id = self.local_id()?;
+                let index = match self.leader_client.leader_read_index().await {
+                    Ok(v) => v,
+                    Err(e) => return Err(FsError::not_leader(format!("Read index failed: {}", e))),
+                };
+
+                if let Err(e) = self.client.wait_applied(local_id, index).await {
+                    return Err(FsError::not_leader(format!(
+                        "Wait for read index {} failed: {}",
+                        index, e
+                    )));
+                }
+                Ok(())
+            }
+
+            ReadConsistency::BoundedStaleness => {
+                let staleness = self.leader_contact.elapsed_ms();
+                if staleness > read.max_staleness_ms {
+                    return Err(FsError::not_leader(format!(
+                        "Standby master is stale, last leader contact {} ms ago, max staleness {} ms",
+                        staleness, read.max_staleness_ms
+                    )));
+                }
+                Ok(())
+            }
+
+            ReadConsistency::Leader => Err(FsError::not_leader("Follower read is not allowed")),
+        }
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::master::fs::WorkerManager;
+use crate::master::meta::inode::InodeView::{Dir, File, FileEntry};
+use crate::master::meta::inode::{InodeFile, InodeView};
+use crate::master::meta::{BlockMeta, FsDir, InodeId, TreeVisitor};
+use crate::master::{SyncFsDir, SyncWorkerManager};
+use curvine_common::state::{
+    BlockLocation, FileType, FsckBlock, FsckBlockState, FsckFile, FsckOrphanBlock, FsckReport,
+    WorkerStatus,
+};
+use curvine_common::FsResult;
+
+pub const LOST_FOUND_DIR: &str = "/lost+found";
+
+/// Checks the files of a tree walk, the replicas of their blocks are compared with the live
+/// workers the same way as the replication reconciler counts them.
+/// At most limit files with problems are reported, the walk is then stopped and continues
+/// from next_start_after of the report. A limit of 0 means no limit.
+pub struct FsckChecker<'a> {
+    wm: &'a SyncWorkerManager,
+    limit: usize,
+    // The path of the last inode checked.
+    last: Option<String>,
+    report: FsckReport,
+}
+
+impl<'a> FsckChecker<'a> {
+    pub fn new(wm: &'a SyncWorkerManager, limit: usize) -> Self {
+        Self {
+            wm,
+            limit: if limit == 0 { usize::MAX } else { limit },
+            last: None,
+            report: FsckReport::default(),
+        }
+    }
+
+    // stopped is whether the checker stopped the walk, there are more files to check.
+    pub fn into_report(mut self, stopped: bool) -> FsckReport {
+        if stopped {
+            self.report.next_start_after = self.last;
+        }
+        self.report
+    }
+
+    fn check_file(
+        &self,
+        fs_dir: &FsDir,
+        path: &str,
+        file: &InodeFile,
+    ) -> FsResult<Option<FsckFile>> {
+        if file.is_writing() {
+            return Ok(Some(FsckFile {
+                path: path.to_string(),
+                len: file.len,
+                open_for_write: true,
+                blocks: vec![],
+            }));
+        }
+
+        let store = fs_dir.get_rocks_store();
+        let mut locs = vec![];
+        for meta in &file.blocks {
+            // The block is allocated but nothing has been written to it yet.
+            if meta.alloc_opts.is_none() {
+                locs.push((meta, store.get_locations(meta.id)?));
+            }
+        }
+
+        let wm = self.wm.read();
+        let blocks: Vec<FsckBlock> = locs
+            .iter()
+            .filter_map(|(meta, locs)| Self::check_block(&wm, file, meta, locs))
+            .collect();
+        drop(wm);
+
+        if blocks.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(FsckFile {
+                path: path.to_string(),
+                len: file.len,
+                open_for_write: false,
+                blocks,
+            }))
+        }
+    }
+
+    fn check_block(
+        wm: &WorkerManager,
+        file: &InodeFile,
+        meta: &BlockMeta,
+        locs: &[BlockLocation],
+    ) -> Option<FsckBlock> {
+        let live: Vec<u32> = locs
+            .iter()
+            .filter(|loc| !wm.is_removing(loc.worker_id, meta.id))
+            .filter(|loc| {
+                matches!(
+                    wm.get_worker(loc.worker_id),
+                    Some(worker) if worker.status == WorkerStatus::Live
+                )
+            })
+            .map(|loc| loc.worker_id)
+            .collect();
+
+        let (live, expected, state) = match &file.storage_policy.ec_policy {
+            Some(ec) if meta.is_striped() => {
+                let live = meta.units.iter().filter(|x| live.contains(x)).count();
+                let expected = ec.total_units() as usize;
+                let state = if live == 0 {
+                    Some(FsckBlockState::Missing)
+                } else if live < ec.data_units as usize {
+                    Some(FsckBlockState::Corrupt)
+                } else if live < expected {
+                    Some(FsckBlockState::UnderReplicated)
+                } else {
+                    None
+                };
+                (live, expected, state)
+            }
+
+            _ => {
+                // A corrupt replica is on a live worker but it can not be read.
+                let corrupt = live.iter().filter(|x| wm.is_corrupt(**x, meta.id)).count();
+                let live = live.len() - corrupt;
+                let expected = file.replicas as usize;
+                let state = if expected == 0 {
+                    None
+                } else if live == 0 && corrupt > 0 {
+                    Some(FsckBlockState::Corrupt)
+                } else if live == 0 {
+                    Some(FsckBlockState::Missing)
+                } else if live < expected {
+                    Some(FsckBlockState::UnderReplicated)
+                } else if live > expected {
+                    Some(FsckBlockState::OverReplicated)
+                } else {
+                    None
+                };
+                (live, expected, state)
+            }
+        };
+
+        state.map(|state| FsckBlock {
+            block_id: meta.id,
+            len: meta.len,
+            state,
+            live: live as i32,
+            expected: expected as i32,
+        })
+    }
+
+    // A block is an orphan if its file is gone or the file no longer has it.
+    // The blocks of one worker are checked under one hold of the locks, at most limit orphans
+    // are kept in the report.
+    pub fn check_orphans(
+        fs_dir: &SyncFsDir,
+        wm: &SyncWorkerManager,
+        limit: usize,
+        report: &mut FsckReport,
+    ) -> FsResult<()> {
+        let limit = if limit == 0 { usize::MAX } else { limit };
+        let mut worker_ids: Vec<u32> = wm.read().worker_map.workers.keys().copied().collect();
+        worker_ids.sort_unstable();
+
+        for worker_id in worker_ids {
+            let fs_dir = fs_dir.read();
+            let wm = wm.read();
+            let store = fs_dir.get_rocks_store();
+            for block_id in store.get_block_ids(worker_id)? {
+                if wm.is_removing(worker_id, block_id) {
+                    continue;
+                }
+
+                let referenced = match store.get_inode(InodeId::get_id(block_id))? {
+                    Some(File(_, file)) => file.blocks.iter().any(|x| x.id == block_id),
+                    _ => false,
+                };
+                if !referenced {
+                    report.orphan_count += 1;
+                    if report.orphan_blocks.len() < limit {
+                        report.orphan_blocks.push(FsckOrphanBlock {
+                            worker_id,
+                            block_id,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TreeVisitor for FsckChecker<'_> {
+    fn visit(&mut self, fs_dir: &FsDir, inode: &InodeView, path: &str) -> FsResult<bool> {
+        match inode {
+            File(_, file) if file.file_type == FileType::File => {
+                let checked = self.check_file(fs_dir, path, file)?;
+                if checked.is_some() && self.report.files.len() >= self.limit {
+                    return Ok(false);
+                }
+
+                self.report.total_files += 1;
+                self.report.total_size += file.len;
+                self.report.total_blocks += file.blocks.len() as i64;
+                if let Some(v) = checked {
+                    self.report.add_file(v);
+                }
+            }
+
+            Dir(..) => self.report.total_dirs += 1,
+
+            File(..) | FileEntry(..) => (),
+        }
+
+        self.last = Some(path.to_string());
+        Ok(true)
+    }
+}
@@ -93,7 +93,7 @@ impl LoopTask for HeartbeatChecker {
                         let spend = TimeSpent::new();
                         let block_ids = try_log!(fs.delete_locations(id), vec![]);
                         let block_num = block_ids.len();
-                        if let Err(e) = rm.report_under_replicated_blocks(id, block_ids) {
+                        if let Err(e) = rm.report_under_replicated_blocks(Some(id), block_ids) {
                             error!(
                                 "Errors on reporting under-replicated {} blocks. err: {:?}",
                                 block_num, e
@@ -15,6 +15,7 @@
 use crate::common::UfsFactory;
 use crate::master::fs::heartbeat_checker::HeartbeatChecker;
 use crate::master::fs::master_filesystem::MasterFilesystem;
+use crate::master::fs::StandbyWorkerSync;
 use crate::master::job::JobManager;
 use crate::master::meta::inode::ttl::ttl_manager::InodeTtlManager;
 use crate::master::meta::inode::ttl::ttl_scheduler::TtlHeartbeatChecker;
@@ -23,9 +24,11 @@ use crate::master::meta::inode::ttl_scheduler::TtlHeartbeatConfig;
 use crate::master::mount::MountManager;
 use crate::master::quota::QuotaManager;
 use crate::master::replication::master_replication_manager::MasterReplicationManager;
+use crate::master::replication::replication_reconciler::ReplicationReconciler;
 use crate::master::MasterMonitor;
+use curvine_client::file::FsClient;
 use curvine_common::executor::ScheduledExecutor;
-use log::info;
+use log::{info, warn};
 use orpc::runtime::GroupExecutor;
 use orpc::CommonResult;
 use std::sync::Arc;
@@ -36,6 +39,7 @@ pub struct MasterActor {
     pub executor: Arc<GroupExecutor>,
     pub replication_manager: Arc<MasterReplicationManager>,
     pub quota_manager: Arc<QuotaManager>,
+    pub leader_client: FsClient,
 }
 
 impl MasterActor {
@@ -45,6 +49,7 @@ impl MasterActor {
         executor: Arc<GroupExecutor>,
         replication_manager: &Arc<MasterReplicationManager>,
         quota_manager: Arc<QuotaManager>,
+        leader_client: FsClient,
     ) -> Self {
         Self {
             fs,
@@ -52,6 +57,7 @@ impl MasterActor {
             executor,
             replication_manager: replication_manager.clone(),
             quota_manager,
+            leader_client,
         }
     }
 
@@ -65,6 +71,22 @@ impl MasterActor {
             self.quota_manager.clone(),
         )
         .unwrap();
+
+        Self::start_standby_worker_sync(
+            self.fs.clone(),
+            self.master_monitor.clone(),
+            self.leader_client.clone(),
+        )
+        .unwrap();
+
+        if self.fs.conf.block_reconcile_enabled {
+            Self::start_replication_reconciler(
+                self.fs.clone(),
+                self.master_monitor.clone(),
+                self.replication_manager.clone(),
+            )
+            .unwrap();
+        }
     }
 
     pub fn start_ttl_scheduler(
@@ -142,4 +164,38 @@ impl MasterActor {
         scheduler.start(task)?;
         Ok(())
     }
+
+    fn start_replication_reconciler(
+        fs: MasterFilesystem,
+        master_monitor: MasterMonitor,
+        replication_manager: Arc<MasterReplicationManager>,
+    ) -> CommonResult<()> {
+        if !replication_manager.is_enabled() {
+            warn!("Block replication is disabled, the reconciler only removes excess replicas");
+        }
+
+        let interval_ms = fs.conf.block_reconcile_interval_ms();
+        let scheduler = ScheduledExecutor::new("replication-reconciler", interval_ms);
+
+        let task = ReplicationReconciler::new(fs, master_monitor, replication_manager);
+        scheduler.start(task)?;
+        info!(
+            "Replication reconciler started, interval {} ms",
+            interval_ms
+        );
+        Ok(())
+    }
+
+    fn start_standby_worker_sync(
+        fs: MasterFilesystem,
+        master_monitor: MasterMonitor,
+        leader_client: FsClient,
+    ) -> CommonResult<()> {
+        let check_ms = fs.conf.worker_check_interval_ms();
+        let scheduler = ScheduledExecutor::new("standby-worker-sync", check_ms);
+
+        let task = StandbyWorkerSync::new(fs, master_monitor, leader_client);
+        scheduler.start(task)?;
+        Ok(())
+    }
 }
@@ -14,15 +14,18 @@
 
 use crate::master::fs::context::ValidateAddBlock;
 use crate::master::fs::policy::ChooseContext;
+use crate::master::fs::{FsckChecker, LOST_FOUND_DIR};
 use crate::master::journal::JournalSystem;
 use crate::master::meta::inode::{InodeFile, InodePath, InodeView, PATH_SEPARATOR};
-use crate::master::meta::FsDir;
-
-use crate::master::meta::parse_glob_pattern;
+use crate::master::meta::{
+    FindVisitor, FsDir, LockPattern, SummaryVisitor, TreeVisitor, TreeWalker, WalkEnd,
+};
 use crate::master::{Master, MasterMonitor, SyncFsDir, SyncWorkerManager};
 use curvine_common::conf::{ClusterConf, MasterConf};
 use curvine_common::error::FsError;
+use curvine_common::fs::FindFilter;
 use curvine_common::state::*;
+use curvine_common::utils::parse_glob_pattern;
 use curvine_common::FsResult;
 use log::warn;
 use orpc::sync::ArcRwLock;
@@ -83,12 +86,13 @@ impl MasterFilesystem {
 
     pub fn print_tree(&self) {
         let fs_dir = self.fs_dir.read();
+        let _lock = fs_dir.lock_tree();
         fs_dir.print_tree();
     }
 
     pub fn mkdir_with_opts<T: AsRef<str>>(&self, path: T, opts: MkdirOpts) -> FsResult<FileStatus> {
-        let mut fs_dir = self.fs_dir.write();
-        let inp = Self::resolve_path(&fs_dir, path.as_ref())?;
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path.as_ref(), LockPattern::Create)?;
 
         // Creation of root directory is not allowed
         if inp.is_root() {
@@ -124,8 +128,8 @@ impl MasterFilesystem {
     }
 
     pub fn delete<T: AsRef<str>>(&self, path: T, recursive: bool) -> FsResult<bool> {
-        let mut fs_dir = self.fs_dir.write();
-        let inp = Self::resolve_path(&fs_dir, path.as_ref())?;
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path.as_ref(), LockPattern::Write)?;
 
         let delete_result = fs_dir.delete(&inp, recursive)?;
 
@@ -139,9 +143,12 @@ impl MasterFilesystem {
         let src = src.as_ref();
         let dst = dst.as_ref();
 
-        let mut fs_dir = self.fs_dir.write();
-        let src_inp = Self::resolve_path(&fs_dir, src)?;
-        let dst_inp = Self::resolve_path(&fs_dir, dst)?;
+        // Both parents are locked in path order, so two crossing renames cannot deadlock.
+        let fs_dir = self.fs_dir.read();
+        let (_lock, mut inps) =
+            fs_dir.lock_paths(&[(src, LockPattern::Write), (dst, LockPattern::Write)])?;
+        let dst_inp = inps.remove(1);
+        let src_inp = inps.remove(0);
 
         if src_inp.is_root() {
             return err_box!("Cannot rename root path");
@@ -175,7 +182,7 @@ impl MasterFilesystem {
         self.create_with_opts(path, ctx, OpenFlags::new_create().set_overwrite(true))
     }
 
-    fn truncate(&self, fs_dir: &mut FsDir, inp: &InodePath, opts: CreateFileOpts) -> FsResult<()> {
+    fn truncate(&self, fs_dir: &FsDir, inp: &InodePath, opts: CreateFileOpts) -> FsResult<()> {
         let clean_result = fs_dir.overwrite_file(inp, opts)?;
         if !clean_result.blocks.is_empty() {
             let mut worker_manager = self.worker_manager.write();
@@ -216,8 +223,12 @@ impl MasterFilesystem {
             );
         }
 
-        let mut fs_dir = self.fs_dir.write();
-        let inp = Self::resolve_path(&fs_dir, path)?;
+        if let Some(policy) = &opts.storage_policy.ec_policy {
+            policy.validate()?;
+        }
+
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path, LockPattern::Create)?;
 
         let last_inode = inp.get_last_inode();
         if let Some(inode) = &last_inode {
@@ -236,7 +247,7 @@ impl MasterFilesystem {
 
         let inp = if last_inode.is_some() {
             if flags.overwrite() {
-                self.truncate(&mut fs_dir, &inp, opts)?;
+                self.truncate(&fs_dir, &inp, opts)?;
             }
             inp
         } else {
@@ -271,8 +282,8 @@ impl MasterFilesystem {
             return Ok(FileBlocks::new(status, vec![]));
         }
 
-        let mut fs_dir = self.fs_dir.write();
-        let inp = Self::resolve_path(&fs_dir, path)?;
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path, LockPattern::Write)?;
 
         let inode = match inp.get_last_inode() {
             None => return err_ext!(FsError::file_not_found(inp.path())),
@@ -285,11 +296,18 @@ impl MasterFilesystem {
         };
 
         if flags.truncate() {
-            self.truncate(&mut fs_dir, &inp, opts)?;
+            self.truncate(&fs_dir, &inp, opts)?;
             let status = fs_dir.file_status(&inp)?;
             return Ok(FileBlocks::new(status, vec![]));
         }
 
+        if inode.as_file_ref()?.storage_policy.is_striped() {
+            return err_box!(
+                "Append is not supported for erasure-coded file {}",
+                inp.path()
+            );
+        }
+
         let status = fs_dir.reopen_file(&inp, opts.client_name)?;
         let file = inode.as_file_ref()?;
         let blocks = if !file.blocks.is_empty() {
@@ -302,14 +320,14 @@ impl MasterFilesystem {
 
     pub fn file_status<T: AsRef<str>>(&self, path: T) -> FsResult<FileStatus> {
         let fs_dir = self.fs_dir.read();
-        let inp = Self::resolve_path(&fs_dir, path.as_ref())?;
+        let (_lock, inp) = fs_dir.lock_path(path.as_ref(), LockPattern::Read)?;
         let status = fs_dir.file_status(&inp)?;
         Ok(status)
     }
 
     pub fn exists<T: AsRef<str>>(&self, path: T) -> FsResult<bool> {
         let fs_dir = self.fs_dir.read();
-        let inp = Self::resolve_path(&fs_dir, path.as_ref())?;
+        let (_lock, inp) = fs_dir.lock_path(path.as_ref(), LockPattern::Read)?;
         Ok(inp.get_last_inode().is_some())
     }
 
@@ -317,6 +335,7 @@ impl MasterFilesystem {
         let fs_dir = self.fs_dir.read();
         let (is_glob_pattern, _) = parse_glob_pattern(path.as_ref());
         if is_glob_pattern {
+            let _lock = fs_dir.lock_glob(path.as_ref())?;
             let paths = Self::resolve_path_by_glob_pattern(&fs_dir, path.as_ref())?;
             let mut all_statuses = Vec::new();
             for path in &paths {
@@ -325,13 +344,144 @@ impl MasterFilesystem {
             }
             Ok(all_statuses)
         } else {
-            let inp = Self::resolve_path(&fs_dir, path.as_ref())?;
+            let (_lock, inp) = fs_dir.lock_path(path.as_ref(), LockPattern::Read)?;
             fs_dir.list_status(&inp)
         }
     }
 
-    fn resolve_path(fs_dir: &FsDir, path: &str) -> CommonResult<InodePath> {
-        InodePath::resolve(fs_dir.root_ptr(), path, &fs_dir.store)
+    /// List a page of the children with names after start_after, a limit of 0 means no limit.
+    /// A glob pattern is always listed in full.
+    pub fn list_status_page<T: AsRef<str>>(
+        &self,
+        path: T,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> FsResult<(Vec<FileStatus>, bool)> {
+        let (is_glob_pattern, _) = parse_glob_pattern(path.as_ref());
+        if is_glob_pattern {
+            return Ok((self.list_status(path)?, false));
+        }
+
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path.as_ref(), LockPattern::Read)?;
+        fs_dir.list_status_page(&inp, start_after, limit)
+    }
+
+    /// Walk the tree under the path in batches of conf.tree_walk_batch inodes, each batch
+    /// holds the fs_dir read lock and the subtree lock, which are released between the batches.
+    /// Returns true if the visitor stopped the walk.
+    pub fn walk_tree<V: TreeVisitor>(
+        &self,
+        path: &str,
+        start_after: Option<&str>,
+        visitor: &mut V,
+    ) -> FsResult<bool> {
+        let mut after = start_after.map(String::from);
+        loop {
+            let end = {
+                let fs_dir = self.fs_dir.read();
+                let (_lock, inp) = fs_dir.lock_path(path, LockPattern::Subtree)?;
+                TreeWalker::new(&fs_dir, self.conf.tree_walk_batch).walk(
+                    &inp,
+                    after.as_deref(),
+                    visitor,
+                )?
+            };
+
+            match end {
+                WalkEnd::Done => return Ok(false),
+                WalkEnd::Stopped => return Ok(true),
+                WalkEnd::Paused(last) => after = Some(last),
+            }
+        }
+    }
+
+    /// The recursive summary of the path and the space quota of the cluster.
+    /// The tree is walked in batches, so the summary is not a snapshot of a busy tree.
+    pub fn content_summary<T: AsRef<str>>(&self, path: T) -> FsResult<ContentSummary> {
+        let mut visitor = SummaryVisitor::default();
+        self.walk_tree(path.as_ref(), None, &mut visitor)?;
+
+        let mut summary = visitor.summary;
+        let (mut capacity, mut available) = (0, 0);
+        let wm = self.worker_manager.read();
+        for (_, worker) in wm.worker_map.workers() {
+            capacity += worker.available + worker.fs_used;
+            available += worker.available;
+        }
+        summary.space_quota = capacity;
+        summary.space_quota_remaining = available;
+
+        Ok(summary)
+    }
+
+    /// Find a page of the entries under the path that match the opts.
+    pub fn find<T: AsRef<str>>(
+        &self,
+        path: T,
+        opts: FindOpts,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> FsResult<(Vec<FileStatus>, bool)> {
+        let filter = FindFilter::new(opts);
+        let mut visitor = FindVisitor::new(&filter, limit);
+        let has_more = self.walk_tree(path.as_ref(), start_after, &mut visitor)?;
+        Ok((visitor.res, has_more))
+    }
+
+    /// Check a page of the files under the path, the corrupted files of the page are then deleted
+    /// or moved to /lost+found if the opts ask for it.
+    /// The tree is walked in batches, the locks are not held for the whole check.
+    pub fn fsck<T: AsRef<str>>(&self, path: T, opts: FsckOpts) -> FsResult<FsckReport> {
+        let mut checker = FsckChecker::new(&self.worker_manager, opts.limit);
+        let stopped = self.walk_tree(path.as_ref(), opts.start_after.as_deref(), &mut checker)?;
+        let mut report = checker.into_report(stopped);
+
+        if opts.check_orphans && report.next_start_after.is_none() {
+            FsckChecker::check_orphans(
+                &self.fs_dir,
+                &self.worker_manager,
+                opts.limit,
+                &mut report,
+            )?;
+        }
+
+        if opts.action == FsckAction::None {
+            return Ok(report);
+        }
+
+        let corrupted: Vec<String> = report
+            .files
+            .iter()
+            .filter(|x| x.is_corrupted())
+            .map(|x| x.path.clone())
+            .collect();
+        for path in corrupted {
+            let res = match opts.action {
+                FsckAction::Delete => self.delete(&path, false),
+                _ => self.move_to_lost_found(&path),
+            };
+            match res {
+                Ok(true) => report.handled_files += 1,
+                Ok(false) => warn!("fsck {:?} {}: not handled", opts.action, path),
+                Err(e) => warn!("fsck {:?} {}: {}", opts.action, path, e),
+            }
+        }
+
+        Ok(report)
+    }
+
+    // Move the file to the same path under /lost+found, the parent directories are created.
+    fn move_to_lost_found(&self, path: &str) -> FsResult<bool> {
+        if path.starts_with(LOST_FOUND_DIR) {
+            return Ok(false);
+        }
+
+        let dst = format!("{}{}", LOST_FOUND_DIR, path);
+        if let Some((parent, _)) = dst.rsplit_once(PATH_SEPARATOR) {
+            self.mkdir(parent, true)?;
+        }
+        self.rename(path, &dst, RenameFlags::new(0))
     }
 
     fn resolve_path_by_glob_pattern(fs_dir: &FsDir, path: &str) -> CommonResult<Vec<InodePath>> {
@@ -372,9 +522,18 @@ impl MasterFilesystem {
             }
         }
 
+        // Each unit of an erasure-coded block must be placed on a distinct worker.
+        let (replicas, block_size) = match &file.storage_policy.ec_policy {
+            Some(policy) => (
+                policy.total_units() as u16,
+                policy.unit_capacity(file.block_size),
+            ),
+            None => (file.replicas, file.block_size),
+        };
+
         let res = ValidateAddBlock {
-            replicas: file.replicas,
-            block_size: file.block_size,
+            replicas,
+            block_size,
             storage_policy: file.storage_policy.clone(),
             client_host: client_addr.hostname.clone(),
         };
@@ -395,7 +554,20 @@ impl MasterFilesystem {
         let validate_block = Self::validate_add_block(file, &client_addr, None)?;
 
         let choose_ctx = ChooseContext::with_block(validate_block, exclude_workers);
-        Ok(wm.choose_worker(choose_ctx)?)
+        let workers = wm.choose_worker(choose_ctx)?;
+
+        if let Some(policy) = &file.storage_policy.ec_policy {
+            if workers.len() != policy.total_units() as usize {
+                return err_box!(
+                    "Erasure coding policy {} requires {} workers, only {} available",
+                    policy,
+                    policy.total_units(),
+                    workers.len()
+                );
+            }
+        }
+
+        Ok(workers)
     }
 
     pub fn create_locate_block(
@@ -420,8 +592,8 @@ impl MasterFilesystem {
         last_block: Option<ExtendedBlock>,
     ) -> FsResult<LocatedBlock> {
         let path = path.as_ref();
-        let mut fs_dir = self.fs_dir.write();
-        let inp = Self::resolve_path(&fs_dir, path)?;
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path, LockPattern::Write)?;
         let inode = match inp.get_last_inode() {
             Some(v) => v,
             None => return err_box!("File {} not exists", inp.path()),
@@ -441,14 +613,34 @@ impl MasterFilesystem {
                 alloc_opts: next.alloc_opts.clone(),
             };
 
-            return self.create_locate_block(path, extend_block, &locs);
+            return match file.storage_policy.ec_policy {
+                Some(policy) => {
+                    let locs = fs_dir.get_locations(next)?;
+                    self.worker_manager.read().create_striped_block(
+                        path,
+                        extend_block,
+                        policy,
+                        &next.units,
+                        &locs,
+                    )
+                }
+                None => self.create_locate_block(path, extend_block, &locs),
+            };
         }
 
+        let ec_policy = file.storage_policy.ec_policy;
         let choose_workers = self.choose_worker(&inp, client_addr, exclude_workers)?;
         let block = fs_dir.acquire_new_block(&inp, commit_blocks, &choose_workers, file_len)?;
-        let located = LocatedBlock {
-            block,
-            locs: choose_workers,
+        let located = match ec_policy {
+            Some(policy) => {
+                let indices = (0..choose_workers.len() as u32).collect();
+                LocatedBlock::with_striped(
+                    block,
+                    choose_workers,
+                    StripedBlock::new(policy, indices),
+                )
+            }
+            None => LocatedBlock::new(block, choose_workers),
         };
 
         Ok(located)
@@ -463,8 +655,8 @@ impl MasterFilesystem {
         only_flush: bool,
     ) -> FsResult<Option<FileBlocks>> {
         let path = path.as_ref();
-        let mut fs_dir = self.fs_dir.write();
-        let inp = Self::resolve_path(&fs_dir, path)?;
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path, LockPattern::Write)?;
 
         let inode = match inp.get_last_inode() {
             None => return err_box!("File does not exist: {}", inp.path()),
@@ -521,14 +713,27 @@ impl MasterFilesystem {
                 alloc_opts: meta.alloc_opts.clone(),
             };
 
-            let lc = try_option!(
-                file_locs.get(&meta.id),
-                "File {}, block {} Lost (no worker can read)",
-                path,
-                meta.id
-            );
-            let lb = wm.create_locate_block(path, extend_block, lc)?;
-            block_locs.push(lb);
+            let lb = if let Some(policy) = file.storage_policy.ec_policy {
+                let locs = match &meta.locs {
+                    Some(v) => v.clone(),
+                    None => file_locs.get(&meta.id).cloned().unwrap_or_default(),
+                };
+                wm.create_striped_block(path, extend_block, policy, &meta.units, &locs)
+            } else {
+                match file_locs.get(&meta.id) {
+                    Some(lc) => wm.create_locate_block(path, extend_block, lc),
+                    None => err_box!("File {}, block {} Lost (no worker can read)", path, meta.id),
+                }
+            };
+
+            // The workers known to a standby master may be out of date, let the leader answer.
+            match lb {
+                Ok(v) => block_locs.push(v),
+                Err(e) if !self.master_monitor.is_active() => {
+                    return Err(FsError::not_leader(e.to_string()))
+                }
+                Err(e) => return Err(e),
+            }
         }
 
         Ok(block_locs)
@@ -537,7 +742,7 @@ impl MasterFilesystem {
     pub fn get_block_locations<T: AsRef<str>>(&self, path: T) -> FsResult<FileBlocks> {
         let fs_dir = self.fs_dir.read();
         let path = path.as_ref();
-        let inp = Self::resolve_path(&fs_dir, path)?;
+        let (_lock, inp) = fs_dir.lock_path(path, LockPattern::Read)?;
 
         let inode = match inp.get_last_inode() {
             Some(v) => v,
@@ -604,6 +809,7 @@ impl MasterFilesystem {
 
     pub fn sum_hash(&self) -> u128 {
         let fs_dir = self.fs_dir.read();
+        let _lock = fs_dir.lock_tree();
         fs_dir.sum_hash()
     }
 
@@ -658,6 +864,12 @@ impl MasterFilesystem {
                     };
 
                     if exists {
+                        if item.status == BlockReportStatus::Finalized {
+                            let fs_dir = self.fs_dir.read();
+                            if let Ok(Some(len)) = fs_dir.committed_block_len(item.id) {
+                                wm.set_corrupt(list.worker_id, item.id, len != item.block_size);
+                            }
+                        }
                         batch.push((true, item.id, loc));
                     } else {
                         // The block does not exist, and the mark block needs to be deleted.
@@ -683,11 +895,117 @@ impl MasterFilesystem {
     }
 
     pub fn set_attr<T: AsRef<str>>(&self, path: T, opts: SetAttrOpts) -> FsResult<FileStatus> {
-        let mut fs_dir = self.fs_dir.write();
-        let inp = Self::resolve_path(&fs_dir, path.as_ref())?;
+        if let Some(replicas) = opts.replicas {
+            if replicas < self.conf.min_replication as i32
+                || replicas >= self.conf.max_replication as i32
+            {
+                return err_box!(
+                    "The replica number {} needs to be between {} and {}",
+                    replicas,
+                    self.conf.min_replication,
+                    self.conf.max_replication
+                );
+            }
+        }
+
+        if let Some(policy) = &opts.dir_policy {
+            self.check_dir_policy(policy)?;
+        }
+
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path.as_ref(), LockPattern::Write)?;
+        if opts.dir_policy.is_some() {
+            match inp.get_last_inode() {
+                Some(inode) if inode.is_dir() => (),
+                Some(_) => {
+                    return err_box!("Storage policy can only be set on a dir: {}", inp.path())
+                }
+                None => return err_ext!(FsError::file_not_found(inp.path())),
+            }
+        }
         fs_dir.set_attr(inp, opts)
     }
 
+    /// Change the storage type of a file, or a directory and optionally all files under it.
+    /// Returns the blocks of the changed files, (block id, len, replicas, striped), they need to
+    /// be moved to the new storage type.
+    pub fn set_storage_policy<T: AsRef<str>>(
+        &self,
+        path: T,
+        storage_type: StorageType,
+        recursive: bool,
+    ) -> FsResult<Vec<(i64, i64, u16, bool)>> {
+        if storage_type == StorageType::Ufs {
+            return err_box!("Storage type {:?} is not supported", storage_type);
+        }
+
+        let opts = SetAttrOptsBuilder::new()
+            .recursive(recursive)
+            .storage_type(storage_type)
+            .build();
+
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path.as_ref(), LockPattern::Write)?;
+        let inode = match inp.get_last_inode() {
+            Some(v) => v,
+            None => return err_ext!(FsError::file_not_found(inp.path())),
+        };
+
+        let blocks = fs_dir.file_blocks(inode, recursive)?;
+        fs_dir.set_attr(inp, opts)?;
+        Ok(blocks)
+    }
+
+    fn check_dir_policy(&self, policy: &DirPolicy) -> FsResult<()> {
+        if let Some(replicas) = policy.replicas {
+            if replicas < self.conf.min_replication || replicas >= self.conf.max_replication {
+                return err_box!(
+                    "The replica number {} needs to be between {} and {}",
+                    replicas,
+                    self.conf.min_replication,
+                    self.conf.max_replication
+                );
+            }
+        }
+
+        if let Some(block_size) = policy.block_size {
+            if block_size < self.conf.min_block_size || block_size >= self.conf.max_block_size {
+                return err_box!(
+                    "Block size needs to be between {} and {}",
+                    self.conf.min_block_size,
+                    self.conf.max_block_size
+                );
+            }
+        }
+
+        if let Some(ttl_ms) = policy.ttl_ms {
+            if ttl_ms < 0 {
+                return err_box!("Invalid ttl {}", ttl_ms);
+            }
+        }
+
+        if let Some(ec_policy) = &policy.ec_policy {
+            ec_policy.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the storage policy set on the directory and the policy that new children of the
+    /// directory get, which also includes the attributes inherited from its ancestors.
+    pub fn get_dir_policy<T: AsRef<str>>(&self, path: T) -> FsResult<(DirPolicy, DirPolicy)> {
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path.as_ref(), LockPattern::Read)?;
+        let inode = match inp.get_last_inode() {
+            Some(v) => v,
+            None => return err_ext!(FsError::file_not_found(inp.path())),
+        };
+        match inode.dir_policy() {
+            Some(policy) => Ok((policy.clone(), FsDir::inherit_policy(&inp))),
+            None => err_box!("{} is not a dir", inp.path()),
+        }
+    }
+
     pub fn symlink<T: AsRef<str>>(
         &self,
         target: T,
@@ -695,16 +1013,20 @@ impl MasterFilesystem {
         force: bool,
         mode: u32,
     ) -> FsResult<()> {
-        let mut fs_dir = self.fs_dir.write();
+        let fs_dir = self.fs_dir.read();
         let target = target.as_ref().to_string();
-        let link = Self::resolve_path(&fs_dir, link.as_ref())?;
+        let (_lock, link) = fs_dir.lock_path(link.as_ref(), LockPattern::Write)?;
         fs_dir.symlink(target, link, force, mode)
     }
 
     pub fn link<T: AsRef<str>>(&self, src_path: T, dst_path: T) -> FsResult<()> {
-        let mut fs_dir = self.fs_dir.write();
-        let src_path = Self::resolve_path(&fs_dir, src_path.as_ref())?;
-        let dst_path = Self::resolve_path(&fs_dir, dst_path.as_ref())?;
+        let fs_dir = self.fs_dir.read();
+        let (_lock, mut inps) = fs_dir.lock_paths(&[
+            (src_path.as_ref(), LockPattern::Write),
+            (dst_path.as_ref(), LockPattern::Create),
+        ])?;
+        let dst_path = inps.remove(1);
+        let src_path = inps.remove(0);
         fs_dir.link(src_path, dst_path)
     }
 
@@ -712,8 +1034,8 @@ impl MasterFilesystem {
         opts.validate()?;
 
         let path = path.as_ref();
-        let mut fs_dir = self.fs_dir.write();
-        let inp = Self::resolve_path(&fs_dir, path)?;
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path, LockPattern::Write)?;
 
         let del_res = fs_dir.resize(&inp, opts)?;
         self.worker_manager.write().remove_blocks(&del_res);
@@ -729,23 +1051,42 @@ impl MasterFilesystem {
         exclude_workers: Vec<u32>,
     ) -> FsResult<LocatedBlock> {
         let path = path.as_ref();
-        let mut fs_dir = self.fs_dir.write();
-        let inp = Self::resolve_path(&fs_dir, path)?;
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path, LockPattern::Write)?;
+
+        if let Some(inode) = inp.get_last_inode() {
+            if inode.as_file_ref()?.storage_policy.is_striped() {
+                return err_box!("Allocate is not supported for erasure-coded file {}", path);
+            }
+        }
 
         let choose_workers = self.choose_worker(&inp, client_addr, exclude_workers)?;
         let block = fs_dir.assign_worker(inp, block.id, &choose_workers)?;
 
-        Ok(LocatedBlock {
-            block,
-            locs: choose_workers,
-        })
+        Ok(LocatedBlock::new(block, choose_workers))
+    }
+
+    /// Choose a worker to replace a failed replica of a block that is being written.
+    pub fn request_replacement_worker(
+        &self,
+        block_id: i64,
+        exclude_workers: Vec<u32>,
+    ) -> FsResult<WorkerAddress> {
+        let wm = self.worker_manager.read();
+        let mut workers = wm.choose_workers(1, exclude_workers)?;
+        let worker = try_option!(
+            workers.pop(),
+            "No replacement worker for block {}",
+            block_id
+        );
+        Ok(worker)
     }
 
     pub fn get_lock<T: AsRef<str>>(&self, path: T, lock: FileLock) -> FsResult<Option<FileLock>> {
         let path = path.as_ref();
 
         let fs_dir = self.fs_dir.read();
-        let inp = Self::resolve_path(&fs_dir, path)?;
+        let (_lock, inp) = fs_dir.lock_path(path, LockPattern::Read)?;
         let expire_ms = self.conf.lock_expire_time_ms();
 
         fs_dir.get_lock(inp, &lock, expire_ms)
@@ -754,8 +1095,8 @@ impl MasterFilesystem {
     pub fn set_lock<T: AsRef<str>>(&self, path: T, lock: FileLock) -> FsResult<Option<FileLock>> {
         let path = path.as_ref();
 
-        let fs_dir = self.fs_dir.write();
-        let inp = Self::resolve_path(&fs_dir, path)?;
+        let fs_dir = self.fs_dir.read();
+        let (_lock, inp) = fs_dir.lock_path(path, LockPattern::Write)?;
 
         fs_dir.set_lock(inp, lock, self.conf.lock_expire_time_ms())
     }
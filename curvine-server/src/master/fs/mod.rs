@@ -18,6 +18,9 @@ pub use self::worker_manager::WorkerManager;
 mod heartbeat_checker;
 pub use self::heartbeat_checker::HeartbeatChecker;
 
+mod standby_worker_sync;
+pub use self::standby_worker_sync::StandbyWorkerSync;
+
 mod master_actor;
 pub use self::master_actor::MasterActor;
 
@@ -30,6 +33,9 @@ pub use self::master_filesystem::MasterFilesystem;
 mod delete_result;
 pub use self::delete_result::DeleteResult;
 
+mod fsck;
+pub use self::fsck::*;
+
 pub mod policy;
 
 pub mod context;
@@ -0,0 +1,60 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::master::fs::MasterFilesystem;
+use crate::master::{MasterMonitor, MasterState};
+use curvine_client::file::FsClient;
+use curvine_common::error::FsError;
+use curvine_common::FsResult;
+use orpc::runtime::{LoopTask, RpcRuntime};
+
+// Workers only send heartbeats to the active master. Standby masters copy the live
+// workers from the leader, so that they can resolve block locations for follower reads.
+pub struct StandbyWorkerSync {
+    fs: MasterFilesystem,
+    monitor: MasterMonitor,
+    client: FsClient,
+}
+
+impl StandbyWorkerSync {
+    pub fn new(fs: MasterFilesystem, monitor: MasterMonitor, client: FsClient) -> Self {
+        Self {
+            fs,
+            monitor,
+            client,
+        }
+    }
+}
+
+impl LoopTask for StandbyWorkerSync {
+    type Error = FsError;
+
+    fn run(&self) -> FsResult<()> {
+        if self.monitor.journal_state() != MasterState::Standby {
+            return Ok(());
+        }
+
+        let rt = self.client.context().rt();
+        let info = rt.block_on(self.client.get_master_info())?;
+        self.fs
+            .worker_manager
+            .write()
+            .sync_workers(info.live_workers);
+        Ok(())
+    }
+
+    fn terminate(&self) -> bool {
+        self.monitor.is_stop()
+    }
+}
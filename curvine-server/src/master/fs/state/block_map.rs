@@ -13,13 +13,20 @@
 // limitations under the License.
 
 use crate::master::fs::DeleteResult;
-use curvine_common::state::{DeleteBlockCmd, WorkerCommand};
+use curvine_common::state::{DeleteBlockCmd, MoveBlockCmd, StorageType, WorkerCommand};
 use std::collections::{HashMap, HashSet};
 
 // Manage all blocks in the cluster
 pub struct BlockMap {
     // Mark the block that needs to be deleted.
     remove_blocks: HashMap<u32, HashSet<i64>>,
+
+    // The blocks that need to be moved to another tier of the same worker.
+    // They are sent once on the next heartbeat, the storage migration job resends the ones not moved.
+    move_blocks: HashMap<u32, HashMap<i64, StorageType>>,
+
+    // The workers whose copy of the block was reported with a length other than the committed one.
+    corrupt_replicas: HashMap<i64, HashSet<u32>>,
 }
 
 impl Default for BlockMap {
@@ -32,6 +39,8 @@ impl BlockMap {
     pub fn new() -> Self {
         Self {
             remove_blocks: Default::default(),
+            move_blocks: Default::default(),
+            corrupt_replicas: Default::default(),
         }
     }
 
@@ -42,6 +51,21 @@ impl BlockMap {
             .insert(block_id);
     }
 
+    // Whether the block copy on the worker has been marked for deletion.
+    pub fn is_removing(&self, worker_id: u32, block_id: i64) -> bool {
+        self.remove_blocks
+            .get(&worker_id)
+            .map(|x| x.contains(&block_id))
+            .unwrap_or(false)
+    }
+
+    pub fn move_block(&mut self, worker_id: u32, block_id: i64, storage_type: StorageType) {
+        self.move_blocks
+            .entry(worker_id)
+            .or_default()
+            .insert(block_id, storage_type);
+    }
+
     // Indicates the block that needs to be deleted.
     pub fn remove_blocks(&mut self, del_res: &DeleteResult) {
         for (block_id, locs) in &del_res.blocks {
@@ -59,6 +83,22 @@ impl BlockMap {
     // alluxio solution: remove only after block report is deleted.This solution is adopted.
     pub fn handle_heartbeat(&mut self, worker_id: u32) -> Vec<WorkerCommand> {
         let mut cmds = vec![];
+        if let Some(moves) = self.move_blocks.remove(&worker_id) {
+            let mut by_type: HashMap<StorageType, Vec<i64>> = HashMap::new();
+            for (block_id, storage_type) in moves {
+                // A block being deleted is not moved.
+                if !self.is_removing(worker_id, block_id) {
+                    by_type.entry(storage_type).or_default().push(block_id);
+                }
+            }
+            for (storage_type, blocks) in by_type {
+                cmds.push(WorkerCommand::MoveBlock(MoveBlockCmd {
+                    storage_type,
+                    blocks,
+                }));
+            }
+        }
+
         let sets = match self.remove_blocks.get(&worker_id) {
             None => return cmds,
             Some(v) => v.iter().copied(),
@@ -75,5 +115,27 @@ impl BlockMap {
             .entry(worker_id)
             .or_default()
             .remove(&block_id);
+        self.set_corrupt(worker_id, block_id, false);
+    }
+
+    pub fn set_corrupt(&mut self, worker_id: u32, block_id: i64, corrupt: bool) {
+        if corrupt {
+            self.corrupt_replicas
+                .entry(block_id)
+                .or_default()
+                .insert(worker_id);
+        } else if let Some(workers) = self.corrupt_replicas.get_mut(&block_id) {
+            workers.remove(&worker_id);
+            if workers.is_empty() {
+                self.corrupt_replicas.remove(&block_id);
+            }
+        }
+    }
+
+    pub fn is_corrupt(&self, worker_id: u32, block_id: i64) -> bool {
+        self.corrupt_replicas
+            .get(&block_id)
+            .map(|x| x.contains(&worker_id))
+            .unwrap_or(false)
     }
 }
@@ -17,12 +17,12 @@ use crate::master::fs::state::{BlockMap, WorkerMap};
 use crate::master::fs::DeleteResult;
 use curvine_common::conf::ClusterConf;
 use curvine_common::state::{
-    BlockLocation, ExtendedBlock, HeartbeatStatus, LocatedBlock, StorageInfo, WorkerAddress,
-    WorkerCommand, WorkerInfo, WorkerStatus,
+    BlockLocation, EcPolicy, ExtendedBlock, HeartbeatStatus, LocatedBlock, StorageInfo,
+    StorageType, StripedBlock, WorkerAddress, WorkerCommand, WorkerInfo, WorkerStatus,
 };
 use curvine_common::FsResult;
 use log::{info, warn};
-use orpc::common::ByteUnit;
+use orpc::common::{ByteUnit, LocalTime};
 use orpc::{err_box, CommonResult};
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
@@ -142,6 +142,28 @@ impl WorkerManager {
         self.block_map.remove_block(worker_id, block_id)
     }
 
+    pub fn is_removing(&self, worker_id: u32, block_id: i64) -> bool {
+        self.block_map.is_removing(worker_id, block_id)
+    }
+
+    // Ask the worker to move the block to a dir with the storage type.
+    pub fn move_block(&mut self, worker_id: u32, block_id: i64, storage_type: StorageType) {
+        self.block_map.move_block(worker_id, block_id, storage_type)
+    }
+
+    // The available space of the dirs with the storage type on the worker,
+    // None if the worker has no such dir.
+    pub fn storage_available(&self, worker_id: u32, storage_type: StorageType) -> Option<i64> {
+        let worker = self.get_worker(worker_id)?;
+        let mut available = None;
+        for storage in worker.storage_map.values() {
+            if !storage.failed && storage.storage_type == storage_type {
+                available = Some(available.unwrap_or(0) + storage.available);
+            }
+        }
+        available
+    }
+
     // Indicates the block that needs to be deleted.
     pub fn remove_blocks(&mut self, del_res: &DeleteResult) {
         self.block_map.remove_blocks(del_res)
@@ -151,6 +173,15 @@ impl WorkerManager {
         self.block_map.deleted_block(worker_id, block_id)
     }
 
+    // Mark or clear the copy of the block on the worker as corrupt, by its reported length.
+    pub fn set_corrupt(&mut self, worker_id: u32, block_id: i64, corrupt: bool) {
+        self.block_map.set_corrupt(worker_id, block_id, corrupt)
+    }
+
+    pub fn is_corrupt(&self, worker_id: u32, block_id: i64) -> bool {
+        self.block_map.is_corrupt(worker_id, block_id)
+    }
+
     pub fn get_worker(&self, id: u32) -> Option<&WorkerInfo> {
         self.worker_map.workers.get(&id)
     }
@@ -183,11 +214,68 @@ impl WorkerManager {
             );
         }
 
-        let lb = LocatedBlock { block, locs: addrs };
+        let lb = LocatedBlock::new(block, addrs);
+
+        Ok(lb)
+    }
+
+    // Create the located block of an erasure-coded block, only the live units are returned.
+    pub fn create_striped_block(
+        &self,
+        path: impl AsRef<str>,
+        block: ExtendedBlock,
+        policy: EcPolicy,
+        units: &[u32],
+        locs: &[BlockLocation],
+    ) -> FsResult<LocatedBlock> {
+        let mut addrs = Vec::with_capacity(units.len());
+        let mut indices = Vec::with_capacity(units.len());
+        for (index, worker_id) in units.iter().enumerate() {
+            let live = locs.iter().any(|x| x.worker_id == *worker_id);
+            match self.get_worker(*worker_id) {
+                Some(info) if live => {
+                    addrs.push(info.address.clone());
+                    indices.push(index as u32);
+                }
+
+                _ => warn!(
+                    "File {} block {}, unit {} on worker {} has been lost",
+                    path.as_ref(),
+                    block.id,
+                    index,
+                    worker_id
+                ),
+            }
+        }
+
+        if addrs.len() < policy.data_units as usize {
+            return err_box!(
+                "File {} block {}, only {} units available, {} requires at least {}",
+                path.as_ref(),
+                block.id,
+                addrs.len(),
+                policy,
+                policy.data_units
+            );
+        }
 
+        let lb = LocatedBlock::with_striped(block, addrs, StripedBlock::new(policy, indices));
         Ok(lb)
     }
 
+    // Replace the workers with the live workers of the active master, only used by standby masters.
+    // The heartbeat time is reset, so that the workers are not expired at once after a failover.
+    pub fn sync_workers(&mut self, workers: Vec<WorkerInfo>) {
+        let now = LocalTime::mills();
+        self.worker_map.workers = workers
+            .into_iter()
+            .map(|mut x| {
+                x.last_update = now;
+                (x.worker_id(), x)
+            })
+            .collect();
+    }
+
     pub fn add_test_worker(&mut self, worker: WorkerInfo) {
         self.worker_map.workers.insert(worker.worker_id(), worker);
     }
@@ -0,0 +1,99 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::master::meta::inode::InodeView;
+use curvine_common::state::{FileType, StorageType};
+use serde::Serialize;
+
+/// An inode of the image with its full path.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImageEntry {
+    pub path: String,
+    pub file_type: FileType,
+    pub len: i64,
+    pub replicas: i32,
+    pub block_size: i64,
+    pub storage_type: StorageType,
+    // The erasure coding policy, e.g. RS-6-3-1024k.
+    pub ec_policy: Option<String>,
+    pub owner: String,
+    pub group: String,
+    pub mode: u32,
+    pub mtime: i64,
+    pub block_ids: Vec<i64>,
+}
+
+impl ImageEntry {
+    pub const CSV_HEADER: &'static str =
+        "path,type,size,replicas,block_size,storage_type,ec_policy,owner,group,mode,mtime,block_ids";
+
+    pub fn new(path: &str, inode: &InodeView) -> Self {
+        let status = inode.to_file_status(path);
+        let block_ids = match inode {
+            InodeView::File(_, file) => file.block_ids(),
+            _ => vec![],
+        };
+
+        Self {
+            path: status.path,
+            file_type: status.file_type,
+            len: status.len,
+            replicas: status.replicas,
+            block_size: status.block_size,
+            storage_type: status.storage_policy.storage_type,
+            ec_policy: status.storage_policy.ec_policy.map(|x| x.to_string()),
+            owner: status.owner,
+            group: status.group,
+            mode: status.mode,
+            mtime: status.mtime,
+            block_ids,
+        }
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.file_type == FileType::Dir
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    // The block ids are separated by spaces, the fields with a comma or quote are quoted.
+    pub fn to_csv(&self) -> String {
+        let block_ids: Vec<String> = self.block_ids.iter().map(|x| x.to_string()).collect();
+        let fields = [
+            Self::csv_field(&self.path),
+            format!("{:?}", self.file_type),
+            self.len.to_string(),
+            self.replicas.to_string(),
+            self.block_size.to_string(),
+            self.storage_type.as_str_name().to_string(),
+            self.ec_policy.clone().unwrap_or_default(),
+            Self::csv_field(&self.owner),
+            Self::csv_field(&self.group),
+            format!("{:o}", self.mode),
+            self.mtime.to_string(),
+            block_ids.join(" "),
+        ];
+        fields.join(",")
+    }
+
+    fn csv_field(s: &str) -> String {
+        if s.contains([',', '"', '\n']) {
+            format!("\"{}\"", s.replace('"', "\"\""))
+        } else {
+            s.to_string()
+        }
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::master::image::ImageEntry;
+use crate::master::meta::inode::{InodeView, PATH_SEPARATOR, ROOT_INODE_ID};
+use crate::master::meta::store::RocksInodeStore;
+use crate::master::meta::FsDir;
+use curvine_common::rocksdb::RocksUtils;
+use orpc::{err_box, try_err, CommonResult};
+
+/// Reads the namespace from a checkpoint of the inode store, see FsDir::create_checkpoint.
+/// The checkpoint is opened read-only, so it can be the checkpoint of a raft snapshot.
+pub struct ImageReader {
+    store: RocksInodeStore,
+}
+
+impl ImageReader {
+    pub fn open<T: AsRef<str>>(dir: T) -> CommonResult<Self> {
+        let store = RocksInodeStore::open_checkpoint(dir)?;
+        Ok(Self { store })
+    }
+
+    fn resolve(&self, path: &str) -> CommonResult<Option<i64>> {
+        let mut id = ROOT_INODE_ID;
+        for name in path.split(PATH_SEPARATOR).filter(|x| !x.is_empty()) {
+            match self.store.get_child_id(id, name)? {
+                Some(v) => id = v,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(id))
+    }
+
+    // The root is not always persisted, it has the default attributes then.
+    fn get_inode(&self, id: i64) -> CommonResult<Option<InodeView>> {
+        let inode = self.store.get_inode(id)?;
+        if inode.is_none() && id == ROOT_INODE_ID {
+            Ok(Some(FsDir::create_root()))
+        } else {
+            Ok(inode)
+        }
+    }
+
+    // The children of a directory in name order.
+    fn children(&self, id: i64) -> CommonResult<Vec<(String, i64)>> {
+        let mut vec = vec![];
+        for item in self.store.edges_iter(id)? {
+            let (key, value) = try_err!(item);
+            let (_, name) = RocksUtils::i64_str_from_bytes(&key)?;
+            vec.push((name, RocksUtils::i64_from_bytes(&value)?));
+        }
+        Ok(vec)
+    }
+
+    /// Visit the path and all the entries under it, in depth-first name order.
+    /// A hard linked file is visited once for each of its paths.
+    pub fn walk<F>(&self, path: &str, mut f: F) -> CommonResult<()>
+    where
+        F: FnMut(ImageEntry) -> CommonResult<()>,
+    {
+        let id = match self.resolve(path)? {
+            Some(v) => v,
+            None => return err_box!("Path {} does not exist in the image", path),
+        };
+
+        let path = match path.trim_end_matches(PATH_SEPARATOR) {
+            "" => PATH_SEPARATOR.to_string(),
+            v => v.to_string(),
+        };
+        let mut stack = vec![(path, id)];
+        while let Some((path, id)) = stack.pop() {
+            let inode = match self.get_inode(id)? {
+                Some(v) => v,
+                None => continue,
+            };
+            f(ImageEntry::new(&path, &inode))?;
+
+            if inode.is_dir() {
+                for (name, child_id) in self.children(id)?.into_iter().rev() {
+                    let child_path = if path == PATH_SEPARATOR {
+                        format!("{}{}", PATH_SEPARATOR, name)
+                    } else {
+                        format!("{}{}{}", path, PATH_SEPARATOR, name)
+                    };
+                    stack.push((child_path, child_id));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::master::image::ImageEntry;
+use crate::master::meta::inode::PATH_SEPARATOR;
+use orpc::common::ByteUnit;
+use serde::Serialize;
+use std::collections::HashMap;
+
+// The upper bounds of the buckets of the file size histogram, the last bucket has no bound.
+const SIZE_BUCKETS: [i64; 6] = [
+    0,
+    4 * ByteUnit::KB as i64,
+    64 * ByteUnit::KB as i64,
+    ByteUnit::MB as i64,
+    16 * ByteUnit::MB as i64,
+    128 * ByteUnit::MB as i64,
+];
+
+/// The files and bytes under a directory.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DirUsage {
+    pub path: String,
+    pub files: i64,
+    pub dirs: i64,
+    pub len: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SizeBucket {
+    // The files of at most max_len bytes, None is the last bucket.
+    pub max_len: Option<i64>,
+    pub files: i64,
+    pub len: i64,
+}
+
+/// The aggregate report of an image for capacity planning, the size of the directories up to
+/// a depth below the path, and the histogram of the file sizes.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageReport {
+    pub path: String,
+    pub depth: usize,
+    pub total_dirs: i64,
+    pub total_files: i64,
+    pub total_len: i64,
+    pub total_blocks: i64,
+    // Sorted by len, the largest first.
+    pub dirs: Vec<DirUsage>,
+    pub size_histogram: Vec<SizeBucket>,
+
+    #[serde(skip)]
+    dir_map: HashMap<String, DirUsage>,
+}
+
+impl ImageReport {
+    pub fn new(path: &str, depth: usize) -> Self {
+        let path = match path.trim_end_matches(PATH_SEPARATOR) {
+            "" => PATH_SEPARATOR.to_string(),
+            v => v.to_string(),
+        };
+
+        let mut size_histogram: Vec<SizeBucket> = SIZE_BUCKETS
+            .iter()
+            .map(|x| SizeBucket {
+                max_len: Some(*x),
+                files: 0,
+                len: 0,
+            })
+            .collect();
+        size_histogram.push(SizeBucket {
+            max_len: None,
+            files: 0,
+            len: 0,
+        });
+
+        Self {
+            path,
+            depth,
+            total_dirs: 0,
+            total_files: 0,
+            total_len: 0,
+            total_blocks: 0,
+            dirs: vec![],
+            size_histogram,
+            dir_map: HashMap::new(),
+        }
+    }
+
+    // The components of the path below the report path.
+    fn components<'a>(&self, path: &'a str) -> Vec<&'a str> {
+        let rest = if self.path == PATH_SEPARATOR {
+            Some(path)
+        } else {
+            path.strip_prefix(&self.path)
+        };
+        match rest {
+            Some(v) => v.split(PATH_SEPARATOR).filter(|x| !x.is_empty()).collect(),
+            None => vec![],
+        }
+    }
+
+    pub fn add(&mut self, entry: &ImageEntry) {
+        if entry.is_dir() {
+            self.total_dirs += 1;
+        } else {
+            self.total_files += 1;
+            self.total_len += entry.len;
+            self.total_blocks += entry.block_ids.len() as i64;
+
+            let index = SIZE_BUCKETS
+                .iter()
+                .position(|x| entry.len <= *x)
+                .unwrap_or(SIZE_BUCKETS.len());
+            let bucket = &mut self.size_histogram[index];
+            bucket.files += 1;
+            bucket.len += entry.len;
+        }
+
+        // Add the entry to the directories holding it, at most depth levels below the path.
+        let components = self.components(&entry.path);
+        let levels = components.len().min(self.depth);
+        let mut dir_path = self.path.clone();
+        for (i, name) in components.iter().take(levels).enumerate() {
+            if dir_path != PATH_SEPARATOR {
+                dir_path.push_str(PATH_SEPARATOR);
+            }
+            dir_path.push_str(name);
+
+            // The last component is the entry itself.
+            let is_self = i + 1 == components.len();
+            if is_self && !entry.is_dir() {
+                break;
+            }
+            let usage = self
+                .dir_map
+                .entry(dir_path.clone())
+                .or_insert_with(|| DirUsage {
+                    path: dir_path.clone(),
+                    ..Default::default()
+                });
+            if is_self {
+                break;
+            } else if entry.is_dir() {
+                usage.dirs += 1;
+            } else {
+                usage.files += 1;
+                usage.len += entry.len;
+            }
+        }
+    }
+
+    // Sort the directories, and keep the top n of them if n > 0.
+    pub fn finish(mut self, top: usize) -> Self {
+        let mut dirs: Vec<DirUsage> = self.dir_map.drain().map(|x| x.1).collect();
+        dirs.sort_by(|a, b| b.len.cmp(&a.len).then_with(|| a.path.cmp(&b.path)));
+        if top > 0 {
+            dirs.truncate(top);
+        }
+        self.dirs = dirs;
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
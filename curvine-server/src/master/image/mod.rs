@@ -0,0 +1,25 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Offline viewer of the master metadata, it reads a checkpoint of the inode store without
+//! a running cluster.
+
+mod image_entry;
+pub use self::image_entry::ImageEntry;
+
+mod image_reader;
+pub use self::image_reader::ImageReader;
+
+mod image_report;
+pub use self::image_report::*;
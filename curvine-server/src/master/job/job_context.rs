@@ -15,7 +15,7 @@
 use curvine_common::conf::ClientConf;
 use curvine_common::state::{
     JobTaskProgress, JobTaskState, LoadJobCommand, LoadJobInfo, LoadTaskInfo, MountInfo,
-    WorkerAddress,
+    StorageType, WorkerAddress,
 };
 use curvine_common::FsResult;
 use log::{info, warn};
@@ -95,6 +95,39 @@ impl JobContext {
         }
     }
 
+    // A storage migration job, the blocks are moved by the master, there is no worker task.
+    pub fn with_migrate(
+        job_id: String,
+        path: String,
+        storage_type: StorageType,
+        total_size: i64,
+    ) -> Self {
+        let job = LoadJobInfo {
+            job_id,
+            source_path: path.clone(),
+            target_path: path,
+            replicas: 0,
+            block_size: 0,
+            storage_type,
+            ttl_ms: 0,
+            ttl_action: Default::default(),
+            mount_info: Default::default(),
+            create_time: LocalTime::mills() as i64,
+            overwrite: None,
+        };
+
+        let mut ctx = JobContext {
+            info: job,
+            state: StateCtl::new(JobTaskState::Pending.into()),
+            progress: Default::default(),
+            assigned_workers: Default::default(),
+            tasks: Default::default(),
+        };
+        ctx.progress.total_size = total_size;
+        ctx.update_state(JobTaskState::Loading, "Moving blocks");
+        ctx
+    }
+
     pub fn add_task(&mut self, task: LoadTaskInfo) {
         self.update_state(
             JobTaskState::Loading,
@@ -29,3 +29,6 @@ pub use self::job_context::*;
 
 mod job_runner;
 pub use self::job_runner::LoadJobRunner;
+
+mod storage_migration;
+pub use self::storage_migration::{MigrateBlock, MigrateJob, StorageMigrationManager};
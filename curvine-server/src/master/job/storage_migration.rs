@@ -0,0 +1,402 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::master::fs::MasterFilesystem;
+use crate::master::replication::master_replication_manager::MasterReplicationManager;
+use crate::master::{JobContext, JobStore, MasterMonitor};
+use curvine_common::error::FsError;
+use curvine_common::executor::ScheduledExecutor;
+use curvine_common::state::{BlockLocation, JobTaskState, StorageType, WorkerStatus};
+use curvine_common::utils::CommonUtils;
+use curvine_common::FsResult;
+use log::{info, warn};
+use orpc::common::{ByteUnit, LocalTime};
+use orpc::runtime::LoopTask;
+use orpc::sync::FastDashMap;
+use orpc::CommonResult;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrateBlock {
+    pub id: i64,
+    pub len: i64,
+    pub replicas: usize,
+    pub striped: bool,
+    // The time of the last scheduled move, 0 if none.
+    #[serde(skip)]
+    pub scheduled_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrateJob {
+    pub job_id: String,
+    pub path: String,
+    pub storage_type: StorageType,
+    pub total_size: i64,
+    pub blocks: Vec<MigrateBlock>,
+    // The blocks that can not be moved, e.g. no worker has a dir of the storage type.
+    #[serde(skip)]
+    pub failed: usize,
+}
+
+/// Moves the blocks of the files whose storage type was changed.
+/// Blocks are moved between the dirs of a worker if it has a dir of the storage type, otherwise
+/// they are copied to another worker by the replication manager and the old copy is removed.
+/// The progress is kept in the job store, so it is queried and canceled like a load job.
+/// Jobs with blocks to move are journaled, a new leader reloads them and skips the blocks
+/// that are already on the storage type.
+#[derive(Clone)]
+pub struct StorageMigrationManager {
+    fs: MasterFilesystem,
+    jobs: JobStore,
+    replication_manager: Arc<MasterReplicationManager>,
+    pending: Arc<FastDashMap<String, MigrateJob>>,
+    // Whether the journaled jobs were reloaded since this master became active.
+    loaded: Arc<AtomicBool>,
+    max_moves: usize,
+    interval_ms: u64,
+}
+
+impl StorageMigrationManager {
+    pub fn new(
+        fs: MasterFilesystem,
+        jobs: JobStore,
+        replication_manager: Arc<MasterReplicationManager>,
+    ) -> Self {
+        let max_moves = fs.conf.storage_migration_max_moves.max(1);
+        let interval_ms = fs.conf.storage_migration_interval_ms();
+        Self {
+            fs,
+            jobs,
+            replication_manager,
+            pending: Arc::new(FastDashMap::default()),
+            loaded: Arc::new(AtomicBool::new(false)),
+            max_moves,
+            interval_ms,
+        }
+    }
+
+    pub fn start(&self) -> CommonResult<()> {
+        let scheduler = ScheduledExecutor::new("storage-migration", self.interval_ms);
+        scheduler.start(StorageMigrationTask {
+            manager: self.clone(),
+            monitor: self.fs.master_monitor.clone(),
+        })?;
+        info!(
+            "Storage migration started, interval {} ms",
+            self.interval_ms
+        );
+        Ok(())
+    }
+
+    // The job id is derived from the request id, so a retried request maps to the same job.
+    fn job_id(path: &str, storage_type: StorageType, req_id: i64) -> String {
+        CommonUtils::create_job_id(format!(
+            "{}:{}:{}",
+            path,
+            storage_type.as_str_name(),
+            req_id
+        ))
+    }
+
+    /// Returns the job submitted by a request that was already handled.
+    pub fn submitted(
+        &self,
+        path: impl AsRef<str>,
+        storage_type: StorageType,
+        req_id: i64,
+    ) -> (String, i64, i64) {
+        let job_id = Self::job_id(path.as_ref(), storage_type, req_id);
+        let total_size = self
+            .jobs
+            .get(&job_id)
+            .map(|x| x.progress.total_size)
+            .unwrap_or(0);
+        let total_blocks = self
+            .pending
+            .get(&job_id)
+            .map(|x| x.blocks.len() as i64)
+            .unwrap_or(0);
+        (job_id, total_blocks, total_size)
+    }
+
+    /// Change the storage type of the path and submit a job to move its blocks.
+    /// Returns the job id, the number of blocks and their total size.
+    pub fn submit(
+        &self,
+        path: impl AsRef<str>,
+        storage_type: StorageType,
+        recursive: bool,
+        req_id: i64,
+    ) -> FsResult<(String, i64, i64)> {
+        let path = path.as_ref();
+        let file_blocks = self.fs.set_storage_policy(path, storage_type, recursive)?;
+
+        let mut total_size = 0;
+        let blocks: Vec<MigrateBlock> = file_blocks
+            .into_iter()
+            .map(|(id, len, replicas, striped)| {
+                total_size += len;
+                MigrateBlock {
+                    id,
+                    len,
+                    replicas: replicas as usize,
+                    striped,
+                    scheduled_ms: 0,
+                }
+            })
+            .collect();
+        let total_blocks = blocks.len() as i64;
+
+        let job_id = Self::job_id(path, storage_type, req_id);
+        let job =
+            JobContext::with_migrate(job_id.clone(), path.to_string(), storage_type, total_size);
+        self.jobs.insert(job_id.clone(), job);
+
+        if blocks.is_empty() {
+            self.jobs
+                .update_state(&job_id, JobTaskState::Completed, "No blocks to move");
+        } else {
+            let job = MigrateJob {
+                job_id: job_id.clone(),
+                path: path.to_string(),
+                storage_type,
+                total_size,
+                blocks,
+                failed: 0,
+            };
+            self.fs.fs_dir.read().add_migration(job.clone())?;
+            self.pending.insert(job_id.clone(), job);
+        }
+
+        info!(
+            "Submit storage migration job {}, path {}, storage type {:?}, blocks {}, size {}",
+            job_id,
+            path,
+            storage_type,
+            total_blocks,
+            ByteUnit::byte_to_string(total_size as u64)
+        );
+        Ok((job_id, total_blocks, total_size))
+    }
+
+    // Reload the journaled jobs, e.g. the ones submitted to the previous leader.
+    fn load_jobs(&self) -> FsResult<()> {
+        let jobs = self.fs.fs_dir.read().get_migrations()?;
+        for job in jobs {
+            if self.pending.contains_key(&job.job_id) {
+                continue;
+            }
+
+            if self.jobs.get(&job.job_id).is_none() {
+                let ctx = JobContext::with_migrate(
+                    job.job_id.clone(),
+                    job.path.clone(),
+                    job.storage_type,
+                    job.total_size,
+                );
+                self.jobs.insert(job.job_id.clone(), ctx);
+            }
+
+            info!(
+                "Reload storage migration job {}, path {}, blocks {}",
+                job.job_id,
+                job.path,
+                job.blocks.len()
+            );
+            self.pending.insert(job.job_id.clone(), job);
+        }
+        Ok(())
+    }
+
+    // Remove the job from the pending jobs and the journal.
+    fn finish_job(&self, job_id: &str) -> FsResult<()> {
+        self.pending.remove(job_id);
+        self.fs.fs_dir.read().remove_migration(job_id)
+    }
+
+    fn check_jobs(&self) -> FsResult<()> {
+        if !self.loaded.load(Ordering::SeqCst) {
+            self.load_jobs()?;
+            self.loaded.store(true, Ordering::SeqCst);
+        }
+
+        let job_ids: Vec<String> = self.pending.iter().map(|x| x.key().clone()).collect();
+        let mut moves = 0;
+        for job_id in job_ids {
+            if moves >= self.max_moves {
+                break;
+            }
+
+            let state = self.jobs.get(&job_id).map(|x| x.state.state());
+            if state != Some(JobTaskState::Loading) {
+                // The job was canceled or expired.
+                self.finish_job(&job_id)?;
+                continue;
+            }
+
+            let mut job = match self.pending.get(&job_id) {
+                Some(v) => v.clone(),
+                None => continue,
+            };
+            moves += self.check_job(&job_id, &mut job, self.max_moves - moves)?;
+
+            if job.blocks.is_empty() {
+                self.finish_job(&job_id)?;
+                if job.failed == 0 {
+                    self.jobs
+                        .update_state(&job_id, JobTaskState::Completed, "All blocks moved");
+                } else {
+                    let msg = format!(
+                        "{} blocks can not be moved to {:?}",
+                        job.failed, job.storage_type
+                    );
+                    warn!("Storage migration job {} failed: {}", job_id, msg);
+                    self.jobs.update_state(&job_id, JobTaskState::Failed, msg);
+                }
+            } else {
+                self.pending.insert(job_id, job);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Schedule the moves of the blocks not on the storage type yet, returns the number of moves.
+    fn check_job(&self, job_id: &str, job: &mut MigrateJob, max_moves: usize) -> FsResult<usize> {
+        let storage_type = job.storage_type;
+        let locations: Vec<Vec<BlockLocation>> = {
+            let fs_dir = self.fs.fs_dir.read();
+            let mut res = Vec::with_capacity(job.blocks.len());
+            for block in &job.blocks {
+                res.push(fs_dir.get_block_locations(block.id)?);
+            }
+            res
+        };
+
+        // A block is not moved again until the previous move had the chance to finish.
+        let retry_ms = self.interval_ms * 3;
+        let now = LocalTime::mills();
+        let mut moves = 0;
+        let mut moved_size = 0;
+        let mut failed = 0;
+        let mut remaining = vec![];
+
+        let mut wm = self.fs.worker_manager.write();
+        for (mut block, locs) in job.blocks.drain(..).zip(locations) {
+            let live: Vec<&BlockLocation> = locs
+                .iter()
+                .filter(|loc| !wm.is_removing(loc.worker_id, block.id))
+                .filter(|loc| {
+                    wm.get_worker(loc.worker_id)
+                        .map(|w| w.status == WorkerStatus::Live)
+                        .unwrap_or(false)
+                })
+                .collect();
+
+            // The file was deleted, or all the copies are on the storage type.
+            if locs.is_empty()
+                || (!live.is_empty() && live.iter().all(|x| x.storage_type == storage_type))
+            {
+                moved_size += block.len;
+                continue;
+            }
+
+            if live.is_empty()
+                || moves >= max_moves
+                || now < block.scheduled_ms + retry_ms
+                || self.replication_manager.is_replicating(block.id)
+            {
+                remaining.push(block);
+                continue;
+            }
+
+            let matched = live
+                .iter()
+                .filter(|x| x.storage_type == storage_type)
+                .count();
+            let mut movable = true;
+            let mut migrating = false;
+            for loc in live.iter().filter(|x| x.storage_type != storage_type) {
+                let available = wm
+                    .storage_available(loc.worker_id, storage_type)
+                    .unwrap_or(0);
+                if available >= block.len {
+                    wm.move_block(loc.worker_id, block.id, storage_type);
+                } else if block.striped {
+                    // The units of a striped block can only be moved within a worker.
+                    movable = false;
+                } else if matched >= block.replicas {
+                    // The copy on the other worker is ready, remove the old one.
+                    wm.remove_block(loc.worker_id, block.id);
+                } else if !migrating {
+                    // Copy the block to a worker with the storage type, one copy at a time.
+                    migrating = true;
+                    if let Err(e) = self
+                        .replication_manager
+                        .migrate_block(block.id, storage_type)
+                    {
+                        warn!("Failed to migrate block {}: {}", block.id, e);
+                        movable = false;
+                    }
+                }
+                moves += 1;
+            }
+
+            if movable {
+                block.scheduled_ms = now;
+                remaining.push(block);
+            } else {
+                failed += 1;
+            }
+        }
+        drop(wm);
+
+        job.blocks = remaining;
+        job.failed += failed;
+        if moved_size > 0 {
+            if let Some(mut ctx) = self.jobs.get_mut(job_id) {
+                ctx.progress.loaded_size += moved_size;
+                ctx.progress.update_time = LocalTime::mills() as i64;
+            }
+        }
+
+        Ok(moves)
+    }
+}
+
+struct StorageMigrationTask {
+    manager: StorageMigrationManager,
+    monitor: MasterMonitor,
+}
+
+impl LoopTask for StorageMigrationTask {
+    type Error = FsError;
+
+    fn run(&self) -> FsResult<()> {
+        if !self.monitor.is_active() {
+            // Reload the jobs when this master becomes active again.
+            self.manager.loaded.store(false, Ordering::SeqCst);
+            self.manager.pending.clear();
+            return Ok(());
+        }
+        self.manager.check_jobs()
+    }
+
+    fn terminate(&self) -> bool {
+        self.monitor.is_stop()
+    }
+}
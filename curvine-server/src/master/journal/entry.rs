@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::master::job::MigrateJob;
 use crate::master::meta::inode::{InodeDir, InodeFile};
 use crate::master::meta::BlockMeta;
 use curvine_common::state::{CommitBlock, FileLock, MountInfo, SetAttrOpts};
@@ -125,6 +126,19 @@ pub struct SetLocksEntry {
     pub(crate) locks: Vec<FileLock>,
 }
 
+// A storage migration job that has blocks to move.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AddMigrationEntry {
+    pub(crate) op_ms: u64,
+    pub(crate) job: MigrateJob,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RemoveMigrationEntry {
+    pub(crate) op_ms: u64,
+    pub(crate) job_id: String,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub enum JournalEntry {
     Mkdir(MkdirEntry),
@@ -141,6 +155,8 @@ pub enum JournalEntry {
     Symlink(SymlinkEntry),
     Link(LinkEntry),
     SetLocks(SetLocksEntry),
+    AddMigration(AddMigrationEntry),
+    RemoveMigration(RemoveMigrationEntry),
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -83,11 +83,15 @@ impl JournalLoader {
             JournalEntry::Link(e) => self.link(e),
 
             JournalEntry::SetLocks(e) => self.set_locks(e),
+
+            JournalEntry::AddMigration(e) => self.add_migration(e),
+
+            JournalEntry::RemoveMigration(e) => self.remove_migration(e),
         }
     }
 
     fn mkdir(&self, entry: MkdirEntry) -> CommonResult<()> {
-        let mut fs_dir = self.fs_dir.write();
+        let fs_dir = self.fs_dir.write();
         fs_dir.update_last_inode_id(entry.dir.id)?;
         let inp = InodePath::resolve(fs_dir.root_ptr(), entry.path, &fs_dir.store)?;
         let name = inp.name().to_string();
@@ -96,7 +100,7 @@ impl JournalLoader {
     }
 
     fn create_file(&self, entry: CreateFileEntry) -> CommonResult<()> {
-        let mut fs_dir = self.fs_dir.write();
+        let fs_dir = self.fs_dir.write();
         fs_dir.update_last_inode_id(entry.file.id)?;
         let inp = InodePath::resolve(fs_dir.root_ptr(), entry.path, &fs_dir.store)?;
         let name = inp.name().to_string();
@@ -162,7 +166,7 @@ impl JournalLoader {
     }
 
     pub fn rename(&self, entry: RenameEntry) -> CommonResult<()> {
-        let mut fs_dir = self.fs_dir.write();
+        let fs_dir = self.fs_dir.write();
         let src_inp = InodePath::resolve(fs_dir.root_ptr(), entry.src, &fs_dir.store)?;
         let dst_inp = InodePath::resolve(fs_dir.root_ptr(), entry.dst, &fs_dir.store)?;
         fs_dir.unprotected_rename(
@@ -176,7 +180,7 @@ impl JournalLoader {
     }
 
     pub fn delete(&self, entry: DeleteEntry) -> CommonResult<()> {
-        let mut fs_dir = self.fs_dir.write();
+        let fs_dir = self.fs_dir.write();
         let inp = InodePath::resolve(fs_dir.root_ptr(), entry.path, &fs_dir.store)?;
         fs_dir.unprotected_delete(&inp, entry.mtime)?;
         Ok(())
@@ -198,7 +202,7 @@ impl JournalLoader {
     }
 
     pub fn set_attr(&self, entry: SetAttrEntry) -> CommonResult<()> {
-        let mut fs_dir = self.fs_dir.write();
+        let fs_dir = self.fs_dir.write();
         let inp = InodePath::resolve(fs_dir.root_ptr(), entry.path, &fs_dir.store)?;
         let last_inode = try_option!(inp.get_last_inode());
         fs_dir.unprotected_set_attr(last_inode, entry.opts)?;
@@ -206,14 +210,14 @@ impl JournalLoader {
     }
 
     pub fn symlink(&self, entry: SymlinkEntry) -> CommonResult<()> {
-        let mut fs_dir = self.fs_dir.write();
+        let fs_dir = self.fs_dir.write();
         let inp = InodePath::resolve(fs_dir.root_ptr(), entry.link, &fs_dir.store)?;
         fs_dir.unprotected_symlink(inp, entry.new_inode, entry.force)?;
         Ok(())
     }
 
     pub fn link(&self, entry: LinkEntry) -> CommonResult<()> {
-        let mut fs_dir = self.fs_dir.write();
+        let fs_dir = self.fs_dir.write();
         let old_path = InodePath::resolve(fs_dir.root_ptr(), entry.src_path, &fs_dir.store)?;
         let new_path = InodePath::resolve(fs_dir.root_ptr(), entry.dst_path, &fs_dir.store)?;
 
@@ -232,6 +236,16 @@ impl JournalLoader {
         fs_dir.store.apply_set_locks(entry.ino, &entry.locks)
     }
 
+    pub fn add_migration(&self, entry: AddMigrationEntry) -> CommonResult<()> {
+        let fs_dir = self.fs_dir.write();
+        fs_dir.store.apply_add_migration(&entry.job)
+    }
+
+    pub fn remove_migration(&self, entry: RemoveMigrationEntry) -> CommonResult<()> {
+        let fs_dir = self.fs_dir.write();
+        fs_dir.store.apply_remove_migration(&entry.job_id)
+    }
+
     // Clean up expired checkpoints.
     pub fn purge_checkpoint(&self, current_ck: impl AsRef<str>) -> CommonResult<()> {
         let ck_dir = match Path::new(current_ck.as_ref()).parent() {
@@ -309,7 +323,8 @@ impl AppStorage for JournalLoader {
 
     // Call rocksdb's API to create a snapshot.
     fn create_snapshot(&self, node_id: u64, last_applied: u64) -> RaftResult<SnapshotData> {
-        let fs_dir = self.fs_dir.read();
+        // The path operations only hold the read lock, the write lock keeps them out of the checkpoint.
+        let fs_dir = self.fs_dir.write();
         let dir = fs_dir.create_checkpoint(last_applied)?;
         let data = RaftUtils::create_file_snapshot(&dir, node_id, last_applied)?;
 
@@ -16,15 +16,18 @@ use crate::master::fs::{MasterFilesystem, WorkerManager};
 use crate::master::journal::{JournalLoader, JournalWriter};
 use crate::master::meta::inode::ttl::ttl_bucket::TtlBucketList;
 use crate::master::meta::FsDir;
-use crate::master::quota::eviction::evictor::{Evictor, LFUEvictor, LRUEvictor};
+use crate::master::quota::eviction::evictor::{
+    ARCEvictor, CostEvictor, Evictor, LFUEvictor, LRUEvictor,
+};
 use crate::master::quota::eviction::types::EvictionPolicy;
 use crate::master::quota::eviction::EvictionConf;
 use crate::master::{
-    MasterMonitor, MetaRaftJournal, MountManager, QuotaManager, SyncFsDir, SyncWorkerManager,
+    FollowerReader, MasterMembership, MasterMonitor, MetaRaftJournal, MountManager, QuotaManager,
+    SyncFsDir, SyncWorkerManager,
 };
 use curvine_common::conf::ClusterConf;
 use curvine_common::proto::raft::SnapshotData;
-use curvine_common::raft::storage::{AppStorage, LogStorage, RocksLogStorage};
+use curvine_common::raft::storage::{AppStorage, JournalLogStorage, LogStorage};
 use curvine_common::raft::{RaftClient, RaftResult, RoleMonitor, RoleStateListener};
 use curvine_common::FsResult;
 use log::info;
@@ -45,9 +48,12 @@ pub struct JournalSystem {
     master_monitor: MasterMonitor,
     mount_manager: Arc<MountManager>,
     quota_manager: Arc<QuotaManager>,
+    follower_reader: Arc<FollowerReader>,
+    membership: Arc<MasterMembership>,
 }
 
 impl JournalSystem {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         rt: Arc<Runtime>,
         fs: MasterFilesystem,
@@ -56,6 +62,8 @@ impl JournalSystem {
         master_monitor: MasterMonitor,
         mount_manager: Arc<MountManager>,
         quota_manager: Arc<QuotaManager>,
+        follower_reader: Arc<FollowerReader>,
+        membership: Arc<MasterMembership>,
     ) -> Self {
         Self {
             rt,
@@ -65,6 +73,8 @@ impl JournalSystem {
             master_monitor,
             mount_manager,
             quota_manager,
+            follower_reader,
+            membership,
         }
     }
 
@@ -72,7 +82,7 @@ impl JournalSystem {
         // When the journal system is used, please note that it is separate from the fs system.
         let rt = conf.journal.create_runtime();
 
-        let log_store = RocksLogStorage::from_conf(&conf.journal, conf.format_master);
+        let log_store = JournalLogStorage::from_conf(&conf.journal, conf.format_master)?;
         let worker_manager = SyncWorkerManager::new(WorkerManager::new(conf));
 
         let client = RaftClient::from_conf(rt.clone(), &conf.journal);
@@ -92,15 +102,23 @@ impl JournalSystem {
 
         let role_monitor = RoleMonitor::new();
         let master_monitor = MasterMonitor::new(role_monitor.read_ctl(), StateCtl::new(0));
+        let follower_reader = Arc::new(FollowerReader::new(
+            rt.clone(),
+            conf,
+            role_monitor.leader_contact(),
+            journal_writer.progress(),
+        )?);
+        let membership = Arc::new(MasterMembership::new(rt.clone(), conf));
 
         // Create TTL bucket list early with configuration
         let ttl_bucket_list = Arc::new(TtlBucketList::new(conf.master.ttl_bucket_interval_ms()));
 
-        let eviction_conf = EvictionConf::from_conf(conf);
+        let eviction_conf = EvictionConf::from_conf(conf)?;
         let evictor: Arc<dyn Evictor> = match eviction_conf.policy {
             EvictionPolicy::Lru => Arc::new(LRUEvictor::new(eviction_conf.clone())),
             EvictionPolicy::Lfu => Arc::new(LFUEvictor::new(eviction_conf.clone())),
-            //to-do: EvictionPolicy::Arc
+            EvictionPolicy::Arc => Arc::new(ARCEvictor::new(eviction_conf.clone())),
+            EvictionPolicy::Cost => Arc::new(CostEvictor::new(eviction_conf.clone())),
         };
 
         let fs_dir = SyncFsDir::new(FsDir::new(
@@ -138,6 +156,8 @@ impl JournalSystem {
             master_monitor,
             mount_manager,
             quota_manager,
+            follower_reader,
+            membership,
         );
 
         Ok(js)
@@ -178,6 +198,18 @@ impl JournalSystem {
         self.quota_manager.clone()
     }
 
+    pub fn follower_reader(&self) -> Arc<FollowerReader> {
+        self.follower_reader.clone()
+    }
+
+    pub fn membership(&self) -> Arc<MasterMembership> {
+        self.membership.clone()
+    }
+
+    pub fn log_store(&self) -> JournalLogStorage {
+        self.raft_journal.log_store().clone()
+    }
+
     // Create a snapshot manually, dedicated for testing.
     pub fn create_snapshot(&self) -> RaftResult<()> {
         let data = self.raft_journal.app_store().create_snapshot(1, 1)?;
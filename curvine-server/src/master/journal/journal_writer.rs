@@ -14,6 +14,7 @@
 
 #![allow(clippy::result_large_err)]
 
+use crate::master::job::MigrateJob;
 use crate::master::journal::*;
 use crate::master::meta::inode::{InodeDir, InodeFile, InodePath};
 use crate::master::{Master, MasterMetrics};
@@ -22,8 +23,10 @@ use curvine_common::raft::RaftClient;
 use curvine_common::state::{CommitBlock, FileLock, MountInfo, RenameFlags, SetAttrOpts};
 use curvine_common::FsResult;
 use log::info;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc::{Receiver, SendError, Sender, SyncSender};
-use std::sync::{mpsc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
+use tokio::sync::Notify;
 
 enum SenderAdapter {
     Bounded(SyncSender<JournalEntry>),
@@ -39,6 +42,41 @@ impl SenderAdapter {
     }
 }
 
+// The number of entries sent to the writer and flushed to raft. A write is acknowledged
+// after its entry is sent, so the entries sent before a read are committed once flushed.
+#[derive(Default)]
+pub struct JournalProgress {
+    sent: AtomicU64,
+    flushed: AtomicU64,
+    notify: Notify,
+}
+
+impl JournalProgress {
+    pub fn sent(&self) -> u64 {
+        self.sent.load(Ordering::SeqCst)
+    }
+
+    pub fn flushed(&self) -> u64 {
+        self.flushed.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn add_flushed(&self, len: u64) {
+        self.flushed.fetch_add(len, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    // Wait until the first `sent` entries have been flushed.
+    pub async fn wait_flushed(&self, sent: u64) {
+        loop {
+            let notified = self.notify.notified();
+            if self.flushed() >= sent {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
 // Write metadata operation logs.
 pub struct JournalWriter {
     enable: bool,
@@ -46,6 +84,7 @@ pub struct JournalWriter {
     sender: SenderAdapter,
     metrics: &'static MasterMetrics,
     receiver: Option<Mutex<Receiver<JournalEntry>>>,
+    progress: Option<Arc<JournalProgress>>,
 }
 
 impl JournalWriter {
@@ -58,13 +97,14 @@ impl JournalWriter {
             (SenderAdapter::Bounded(sender), receiver)
         };
 
-        let receiver = if !testing {
+        let (receiver, progress) = if !testing {
             // Start the send log thread.
-            let task = SenderTask::new(client, conf, 0);
+            let progress = Arc::new(JournalProgress::default());
+            let task = SenderTask::new(client, conf, 0, progress.clone());
             task.spawn(receiver).unwrap();
-            None
+            (None, Some(progress))
         } else {
-            Some(Mutex::new(receiver))
+            (Some(Mutex::new(receiver)), None)
         };
 
         Self {
@@ -73,6 +113,16 @@ impl JournalWriter {
             sender,
             metrics: Master::get_metrics(),
             receiver,
+            progress,
+        }
+    }
+
+    // None if the entries are not flushed to raft, e.g. the journal is disabled.
+    pub fn progress(&self) -> Option<Arc<JournalProgress>> {
+        if self.enable {
+            self.progress.clone()
+        } else {
+            None
         }
     }
 
@@ -84,6 +134,9 @@ impl JournalWriter {
         if self.enable {
             self.sender.send(entry)?;
             self.metrics.journal_queue_len.inc();
+            if let Some(progress) = &self.progress {
+                progress.sent.fetch_add(1, Ordering::SeqCst);
+            }
         }
         Ok(())
     }
@@ -244,6 +297,16 @@ impl JournalWriter {
         self.send(JournalEntry::SetLocks(entry))
     }
 
+    pub fn log_add_migration(&self, op_ms: u64, job: MigrateJob) -> FsResult<()> {
+        let entry = AddMigrationEntry { op_ms, job };
+        self.send(JournalEntry::AddMigration(entry))
+    }
+
+    pub fn log_remove_migration(&self, op_ms: u64, job_id: String) -> FsResult<()> {
+        let entry = RemoveMigrationEntry { op_ms, job_id };
+        self.send(JournalEntry::RemoveMigration(entry))
+    }
+
     // for testing
     pub fn take_entries(&self) -> Vec<JournalEntry> {
         let mut entries = vec![];
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::master::journal::{JournalBatch, JournalEntry};
+use crate::master::journal::{JournalBatch, JournalEntry, JournalProgress};
 use crate::master::{Master, MasterMetrics};
 use curvine_common::conf::JournalConf;
 use curvine_common::raft::RaftClient;
@@ -22,6 +22,7 @@ use orpc::common::{LocalTime, TimeSpent};
 use orpc::err_box;
 use std::sync::mpsc;
 use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
@@ -32,10 +33,16 @@ pub struct SenderTask {
     pub(crate) flush_batch_size: u64,
     pub(crate) last_flush_ms: u64,
     pub(crate) metrics: &'static MasterMetrics,
+    pub(crate) progress: Arc<JournalProgress>,
 }
 
 impl SenderTask {
-    pub fn new(client: RaftClient, conf: &JournalConf, batch_seq_id: u64) -> Self {
+    pub fn new(
+        client: RaftClient,
+        conf: &JournalConf,
+        batch_seq_id: u64,
+        progress: Arc<JournalProgress>,
+    ) -> Self {
         let sender = Self {
             client,
             batch: JournalBatch::new(batch_seq_id),
@@ -43,6 +50,7 @@ impl SenderTask {
             flush_batch_size: conf.writer_flush_batch_size,
             last_flush_ms: LocalTime::mills(),
             metrics: Master::get_metrics(),
+            progress,
         };
 
         sender
@@ -95,6 +103,7 @@ impl SenderTask {
 
             let bytes = SerdeUtils::serialize(&self.batch)?;
             self.client.block_on_send_propose(bytes)?;
+            self.progress.add_flushed(len);
 
             self.metrics.journal_flush_count.inc();
             self.metrics
@@ -12,27 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::master::backup::MetaBackupManager;
 use crate::master::fs::{FsRetryCache, MasterFilesystem, OperationStatus};
 use crate::master::job::JobHandler;
 use crate::master::replication::master_replication_handler::MasterReplicationHandler;
 use crate::master::replication::master_replication_manager::MasterReplicationManager;
-use crate::master::MountManager;
-use crate::master::{Master, MasterMetrics, RpcContext};
+use crate::master::{FollowerReader, Master, MasterMembership, MasterMetrics, RpcContext};
+use crate::master::{MasterRateLimiter, RateClass};
+use crate::master::{MountManager, StorageMigrationManager};
 use curvine_common::conf::ClusterConf;
 use curvine_common::error::FsError;
 use curvine_common::fs::Path;
 use curvine_common::fs::RpcCode;
 use curvine_common::proto::*;
 use curvine_common::state::{
-    CreateFileOpts, FileBlocks, FileStatus, HeartbeatStatus, OpenFlags, RenameFlags,
+    CreateFileOpts, FileBlocks, FileStatus, FsckAction, FsckOpts, HeartbeatStatus, OpenFlags,
+    RenameFlags, StorageType,
 };
 use curvine_common::utils::ProtoUtils;
 use curvine_common::FsResult;
+use orpc::common::Span;
 use orpc::err_box;
 use orpc::handler::MessageHandler;
-use orpc::io::net::ConnState;
+use orpc::io::net::{ConnState, InetAddr};
 use orpc::message::Message;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub struct MasterHandler {
     pub(crate) fs: MasterFilesystem,
@@ -43,6 +48,13 @@ pub struct MasterHandler {
     pub(crate) job_handler: JobHandler,
     pub(crate) mount_manager: Arc<MountManager>,
     pub(crate) replication_handler: Option<MasterReplicationHandler>,
+    pub(crate) follower_reader: Option<Arc<FollowerReader>>,
+    pub(crate) membership: Option<Arc<MasterMembership>>,
+    pub(crate) storage_migration: Option<StorageMigrationManager>,
+    pub(crate) backup_manager: Option<MetaBackupManager>,
+    pub(crate) rate_limiter: Option<Arc<MasterRateLimiter>>,
+    // Set while a follower read that has passed the consistency check is handled.
+    follower_read_checked: bool,
 }
 
 impl MasterHandler {
@@ -65,9 +77,145 @@ impl MasterHandler {
             mount_manager,
             job_handler,
             replication_handler: Some(MasterReplicationHandler::new(replication_manager)),
+            follower_reader: None,
+            membership: None,
+            storage_migration: None,
+            backup_manager: None,
+            rate_limiter: None,
+            follower_read_checked: false,
         }
     }
 
+    // Allow the standby master to serve follower reads.
+    pub fn with_follower_reader(mut self, reader: Arc<FollowerReader>) -> Self {
+        self.follower_reader = Some(reader);
+        self
+    }
+
+    pub fn with_membership(mut self, membership: Arc<MasterMembership>) -> Self {
+        self.membership = Some(membership);
+        self
+    }
+
+    pub fn with_storage_migration(mut self, storage_migration: StorageMigrationManager) -> Self {
+        self.storage_migration = Some(storage_migration);
+        self
+    }
+
+    pub fn with_backup_manager(mut self, backup_manager: MetaBackupManager) -> Self {
+        self.backup_manager = Some(backup_manager);
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, rate_limiter: Option<Arc<MasterRateLimiter>>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    // Queue the request if the client is over its rate, or reject it if the wait is too long,
+    // the client resends it after the backoff.
+    fn check_rate_limit(&self, code: RpcCode) -> FsResult<Option<Duration>> {
+        let (limiter, class) = match (&self.rate_limiter, RateClass::from_code(code)) {
+            (Some(limiter), Some(class)) => (limiter, class),
+            _ => return Ok(None),
+        };
+
+        let user = self.conn_state.as_ref().and_then(|x| x.user());
+        let label = [class.as_str()];
+        match limiter.acquire(self.client_ip(), user, class) {
+            Ok(wait) if wait.is_zero() => Ok(None),
+            Ok(wait) => {
+                self.metrics
+                    .rpc_queued_wait_ms
+                    .with_label_values(&label)
+                    .inc_by(wait.as_millis() as i64);
+                Ok(Some(wait))
+            }
+            Err(backoff_ms) => {
+                self.metrics
+                    .rpc_throttled_count
+                    .with_label_values(&label)
+                    .inc();
+                self.metrics
+                    .rpc_throttled_backoff_ms
+                    .with_label_values(&label)
+                    .inc_by(backoff_ms as i64);
+                Err(FsError::throttled(code, self.client_ip(), backoff_ms))
+            }
+        }
+    }
+
+    fn is_follower_read(&self, code: RpcCode) -> bool {
+        self.follower_reader.is_some()
+            && matches!(
+                code,
+                RpcCode::FileStatus
+                    | RpcCode::Exists
+                    | RpcCode::ListStatus
+                    | RpcCode::GetBlockLocations
+                    | RpcCode::GetContentSummary
+                    | RpcCode::Find
+            )
+    }
+
+    // A standby master serves a read only if the client allows it and the consistency is met,
+    // the consistency is checked in async_handle before the read is handled.
+    fn check_follower_read(&self, ctx: &RpcContext<'_>) -> FsResult<()> {
+        if self.fs.master_monitor.is_active() || self.follower_read_checked {
+            Ok(())
+        } else {
+            Err(FsError::not_leader_master(ctx.code, self.client_ip()))
+        }
+    }
+
+    fn parse_follower_read(ctx: &RpcContext<'_>) -> FsResult<Option<FollowerReadProto>> {
+        let read = match ctx.code {
+            RpcCode::FileStatus => ctx.parse_header::<GetFileStatusRequest>()?.follower_read,
+            RpcCode::Exists => ctx.parse_header::<ExistsRequest>()?.follower_read,
+            RpcCode::ListStatus => ctx.parse_header::<ListStatusRequest>()?.follower_read,
+            RpcCode::GetBlockLocations => {
+                ctx.parse_header::<GetBlockLocationsRequest>()?
+                    .follower_read
+            }
+            RpcCode::GetContentSummary => {
+                ctx.parse_header::<GetContentSummaryRequest>()?
+                    .follower_read
+            }
+            RpcCode::Find => ctx.parse_header::<FindRequest>()?.follower_read,
+            _ => None,
+        };
+        Ok(read)
+    }
+
+    // Waits on the leader for the read index of a standby master, then handles the read.
+    async fn follower_read(&mut self, msg: &Message) -> FsResult<Message> {
+        if !self.fs.master_monitor.is_active() {
+            let ctx = RpcContext::new(msg);
+            let read = Self::parse_follower_read(&ctx)?;
+            match (&self.follower_reader, read) {
+                (Some(reader), Some(read)) => reader.check(&read).await?,
+                _ => return Err(FsError::not_leader_master(ctx.code, self.client_ip())),
+            }
+        }
+
+        self.follower_read_checked = true;
+        let res = self.handle(msg);
+        self.follower_read_checked = false;
+        res
+    }
+
+    async fn leader_read_index(&self, msg: &Message) -> FsResult<Message> {
+        let ctx = RpcContext::new(msg);
+        let _: LeaderReadIndexRequest = ctx.parse_header()?;
+        let reader = match &self.follower_reader {
+            Some(v) if self.fs.master_monitor.is_active() => v,
+            _ => return Err(FsError::not_leader_master(ctx.code, self.client_ip())),
+        };
+
+        let index = reader.leader_read_index().await?;
+        ctx.response(LeaderReadIndexResponse { index })
+    }
+
     pub fn get_req_cache(&self, id: i64) -> Option<OperationStatus> {
         if let Some(ref c) = self.retry_cache {
             c.get(&id)
@@ -175,6 +323,7 @@ impl MasterHandler {
     pub fn file_status(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
         let header: GetFileStatusRequest = ctx.parse_header()?;
         ctx.set_audit(Some(header.path.to_string()), None);
+        self.check_follower_read(ctx)?;
 
         let status = self.fs.file_status(header.path.as_str())?;
         let rep_header = GetFileStatusResponse {
@@ -187,6 +336,7 @@ impl MasterHandler {
     pub fn exists(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
         let header: ExistsRequest = ctx.parse_header()?;
         ctx.set_audit(Some(header.path.to_string()), None);
+        self.check_follower_read(ctx)?;
 
         let exists = self.fs.exists(&header.path)?;
         let rep_header = ExistsResponse { exists };
@@ -232,17 +382,54 @@ impl MasterHandler {
     pub fn list_status(&mut self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
         let header: ListStatusRequest = ctx.parse_header()?;
         ctx.set_audit(Some(header.path.to_string()), None);
+        self.check_follower_read(ctx)?;
 
-        let list = self.fs.list_status(&header.path)?;
+        let limit = header.limit.unwrap_or(0).max(0) as usize;
+        let (list, has_more) =
+            self.fs
+                .list_status_page(&header.path, header.start_after.as_deref(), limit)?;
         let res = list
             .into_iter()
             .map(ProtoUtils::file_status_to_pb)
             .collect();
 
-        let rep_header = ListStatusResponse { statuses: res };
+        let rep_header = ListStatusResponse {
+            statuses: res,
+            has_more: Some(has_more),
+        };
+        ctx.response(rep_header)
+    }
+
+    pub fn content_summary(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let header: GetContentSummaryRequest = ctx.parse_header()?;
+        ctx.set_audit(Some(header.path.to_string()), None);
+        self.check_follower_read(ctx)?;
+
+        let summary = self.fs.content_summary(&header.path)?;
+        let rep_header = GetContentSummaryResponse {
+            summary: ProtoUtils::content_summary_to_pb(summary),
+        };
         ctx.response(rep_header)
     }
 
+    pub fn find(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let header: FindRequest = ctx.parse_header()?;
+        ctx.set_audit(Some(header.path.to_string()), None);
+        self.check_follower_read(ctx)?;
+
+        let opts = ProtoUtils::find_opts_from_pb(header.opts);
+        let limit = header.limit.max(0) as usize;
+        let (list, has_more) =
+            self.fs
+                .find(&header.path, opts, header.start_after.as_deref(), limit)?;
+        let statuses = list
+            .into_iter()
+            .map(ProtoUtils::file_status_to_pb)
+            .collect();
+
+        ctx.response(FindResponse { statuses, has_more })
+    }
+
     // The add block internally determines whether it is a retry request.
     pub fn add_block(&mut self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
         let req: AddBlockRequest = ctx.parse_header()?;
@@ -372,6 +559,7 @@ impl MasterHandler {
     pub fn get_block_locations(&mut self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
         let req: GetBlockLocationsRequest = ctx.parse_header()?;
         ctx.set_audit(Some(req.path.to_string()), None);
+        self.check_follower_read(ctx)?;
 
         let blocks = self.fs.get_block_locations(req.path)?;
         let rep_header = GetBlockLocationsResponse {
@@ -388,6 +576,91 @@ impl MasterHandler {
         ctx.response(rep_header)
     }
 
+    fn membership(&self) -> FsResult<&MasterMembership> {
+        match &self.membership {
+            Some(v) => Ok(v),
+            None => err_box!("Master membership is not supported"),
+        }
+    }
+
+    pub fn add_master(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let header: AddMasterRequest = ctx.parse_header()?;
+        let addr = InetAddr::new(header.hostname, header.port as u16);
+        let rpc_port = header.rpc_port.map(|x| x as u16);
+        let observer = header.observer.unwrap_or(false);
+        self.membership()?
+            .add_master(header.node_id, &addr, rpc_port, observer)?;
+        ctx.response(AddMasterResponse::default())
+    }
+
+    pub fn remove_master(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let header: RemoveMasterRequest = ctx.parse_header()?;
+        self.membership()?.remove_master(header.node_id)?;
+        ctx.response(RemoveMasterResponse::default())
+    }
+
+    pub fn transfer_master_leader(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let header: TransferMasterLeaderRequest = ctx.parse_header()?;
+        self.membership()?.transfer_leader(header.node_id)?;
+        ctx.response(TransferMasterLeaderResponse::default())
+    }
+
+    pub fn list_masters(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let _: ListMastersRequest = ctx.parse_header()?;
+        let group = self.membership()?.list_masters()?;
+        ctx.response(ProtoUtils::master_group_to_pb(group))
+    }
+
+    pub fn fsck(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let header: FsckRequest = ctx.parse_header()?;
+        ctx.set_audit(Some(header.path.to_string()), None);
+
+        let opts = FsckOpts {
+            action: FsckAction::from(header.action),
+            check_orphans: header.check_orphans,
+            start_after: header.start_after,
+            limit: header.limit.unwrap_or(0) as usize,
+        };
+        let report = self.fs.fsck(&header.path, opts)?;
+        ctx.response(ProtoUtils::fsck_report_to_pb(report))
+    }
+
+    fn backup_manager(&self) -> FsResult<&MetaBackupManager> {
+        match &self.backup_manager {
+            Some(v) => Ok(v),
+            None => err_box!("Metadata backup is not supported"),
+        }
+    }
+
+    pub fn backup_meta(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let _: BackupMetaRequest = ctx.parse_header()?;
+
+        let status = self.backup_manager()?.start_backup();
+        ctx.response(BackupMetaResponse {
+            status: ProtoUtils::meta_backup_status_to_pb(status),
+        })
+    }
+
+    pub fn meta_backup_status(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let _: GetMetaBackupStatusRequest = ctx.parse_header()?;
+
+        let status = self.backup_manager()?.status();
+        ctx.response(GetMetaBackupStatusResponse {
+            status: ProtoUtils::meta_backup_status_to_pb(status),
+        })
+    }
+
+    pub fn list_meta_backups(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let _: ListMetaBackupsRequest = ctx.parse_header()?;
+        let backups = self.backup_manager()?.list()?;
+        ctx.response(ListMetaBackupsResponse {
+            backups: backups
+                .into_iter()
+                .map(ProtoUtils::meta_backup_info_to_pb)
+                .collect(),
+        })
+    }
+
     pub fn worker_heartbeat(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
         let header: WorkerHeartbeatRequest = ctx.parse_header()?;
         let mut wm = self.fs.worker_manager.write();
@@ -488,6 +761,41 @@ impl MasterHandler {
         })
     }
 
+    fn get_dir_policy(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let header: GetDirPolicyRequest = ctx.parse_header()?;
+        ctx.set_audit(Some(header.path.to_string()), None);
+
+        let (policy, effective) = self.fs.get_dir_policy(&header.path)?;
+        ctx.response(GetDirPolicyResponse {
+            policy: ProtoUtils::dir_policy_to_pb(policy),
+            effective: ProtoUtils::dir_policy_to_pb(effective),
+        })
+    }
+
+    fn set_storage_policy(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let header: SetStoragePolicyRequest = ctx.parse_header()?;
+        ctx.set_audit(Some(header.path.to_string()), None);
+
+        let manager = match &self.storage_migration {
+            Some(v) => v,
+            None => return err_box!("Storage migration not initialized"),
+        };
+
+        let req_id = ctx.msg.req_id();
+        let storage_type = StorageType::from(header.storage_type);
+        let (job_id, total_blocks, total_size) = if self.check_is_retry(req_id)? {
+            manager.submitted(&header.path, storage_type, req_id)
+        } else {
+            let res = manager.submit(&header.path, storage_type, header.recursive, req_id);
+            self.set_req_cache(req_id, res)?
+        };
+        ctx.response(SetStoragePolicyResponse {
+            job_id,
+            total_blocks,
+            total_size,
+        })
+    }
+
     fn symlink_retry_check(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
         let header: SymlinkRequest = ctx.parse_header()?;
         ctx.set_audit(
@@ -560,6 +868,18 @@ impl MasterHandler {
         ctx.response(rep_header)
     }
 
+    pub fn request_replacement_worker(&mut self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let header: RequestReplacementWorkerRequest = ctx.parse_header()?;
+
+        let worker = self
+            .fs
+            .request_replacement_worker(header.block_id, header.exclude_workers)?;
+        let rep_header = RequestReplacementWorkerResponse {
+            worker: ProtoUtils::worker_address_to_pb(&worker),
+        };
+        ctx.response(rep_header)
+    }
+
     pub fn get_lock(&mut self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
         let header: GetLockRequest = ctx.parse_header()?;
         let lock = ProtoUtils::file_lock_from_pb(header.lock);
@@ -593,13 +913,32 @@ impl MasterHandler {
 impl MessageHandler for MasterHandler {
     type Error = FsError;
 
+    // Throttle the request before doing any work, so it is safe to resend.
+    // Requests a standby master rejects in handle are not counted.
+    fn admit(&mut self, msg: &Message) -> FsResult<Option<Duration>> {
+        let code = RpcCode::from(msg.code());
+        if !self.fs.master_monitor.is_active() && !self.is_follower_read(code) {
+            return Ok(None);
+        }
+        self.check_rate_limit(code)
+    }
+
+    // The leader read index and the follower reads of a standby master wait on raft.
+    fn is_sync(&self, msg: &Message) -> bool {
+        let code = RpcCode::from(msg.code());
+        let follower_read = self.is_follower_read(code) && !self.fs.master_monitor.is_active();
+        code != RpcCode::LeaderReadIndex && !follower_read
+    }
+
     fn handle(&mut self, msg: &Message) -> FsResult<Message> {
         let mut rpc_context = RpcContext::new(msg);
         let ctx = &mut rpc_context;
         let code = RpcCode::from(msg.code());
+        let mut span = Span::from_message(format!("master.{:?}", code), msg);
+        let _guard = span.enter();
 
-        // Check whether the master is active
-        if !self.fs.master_monitor.is_active() {
+        // Check whether the master is active, a standby master only serves follower reads.
+        if !self.fs.master_monitor.is_active() && !self.is_follower_read(code) {
             return Err(FsError::not_leader_master(ctx.code, self.client_ip()));
         }
 
@@ -619,12 +958,17 @@ impl MessageHandler for MasterHandler {
             RpcCode::Delete => self.retry_check_delete(ctx),
             RpcCode::Rename => self.retry_check_rename(ctx),
             RpcCode::ListStatus => self.list_status(ctx),
+            RpcCode::GetContentSummary => self.content_summary(ctx),
+            RpcCode::Find => self.find(ctx),
             RpcCode::GetBlockLocations => self.get_block_locations(ctx),
             RpcCode::SetAttr => self.set_attr_retry_check(ctx),
+            RpcCode::GetDirPolicy => self.get_dir_policy(ctx),
+            RpcCode::SetStoragePolicy => self.set_storage_policy(ctx),
             RpcCode::Symlink => self.symlink_retry_check(ctx),
             RpcCode::Link => self.link_retry_check(ctx),
             RpcCode::ResizeFile => self.resize_file(ctx),
             RpcCode::AssignWorker => self.assign_worker(ctx),
+            RpcCode::RequestReplacementWorker => self.request_replacement_worker(ctx),
             RpcCode::GetLock => self.get_lock(ctx),
             RpcCode::SetLock => self.set_lock(ctx),
 
@@ -640,13 +984,24 @@ impl MessageHandler for MasterHandler {
             RpcCode::WorkerBlockReport => self.block_report(ctx),
             RpcCode::GetMasterInfo => self.get_master_info(ctx),
 
+            // Master membership related requests
+            RpcCode::AddMaster => self.add_master(ctx),
+            RpcCode::RemoveMaster => self.remove_master(ctx),
+            RpcCode::TransferMasterLeader => self.transfer_master_leader(ctx),
+            RpcCode::ListMasters => self.list_masters(ctx),
+
+            RpcCode::Fsck => self.fsck(ctx),
+            RpcCode::BackupMeta => self.backup_meta(ctx),
+            RpcCode::ListMetaBackups => self.list_meta_backups(ctx),
+            RpcCode::GetMetaBackupStatus => self.meta_backup_status(ctx),
+
             // Load task related requests
             RpcCode::SubmitJob
             | RpcCode::GetJobStatus
             | RpcCode::CancelJob
             | RpcCode::ReportTask => self.job_handler.handle(ctx),
 
-            RpcCode::ReportBlockReplicationResult => {
+            RpcCode::ReportBlockReplicationResult | RpcCode::ReportUnderReplicatedBlocks => {
                 if let Some(ref mut replication_service) = self.replication_handler {
                     return replication_service.handle(msg);
                 } else {
@@ -675,6 +1030,23 @@ impl MessageHandler for MasterHandler {
                 .observe(used_us as f64);
         };
 
+        match response {
+            Ok(v) => Ok(v),
+            Err(e) => {
+                span.set_error(&e);
+                Ok(msg.error_ext(&e))
+            }
+        }
+    }
+
+    async fn async_handle(&mut self, msg: Message) -> FsResult<Message> {
+        let response = if RpcCode::from(msg.code()) == RpcCode::LeaderReadIndex {
+            self.leader_read_index(&msg).await
+        } else {
+            self.follower_read(&msg).await
+        };
+
+        // An error response keeps the connection open.
         match response {
             Ok(v) => Ok(v),
             Err(e) => Ok(msg.error_ext(&e)),
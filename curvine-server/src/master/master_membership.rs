@@ -0,0 +1,95 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_common::conf::ClusterConf;
+use curvine_common::raft::{NodeId, RaftClient, RaftPeer};
+use curvine_common::state::{MasterGroup, MasterNode};
+use curvine_common::FsResult;
+use orpc::io::net::InetAddr;
+use orpc::runtime::{RpcRuntime, Runtime};
+use std::sync::Arc;
+
+/// Changes the masters of the raft group through raft configuration changes.
+///
+/// A new master joins as a learner and becomes a voter once it has caught up with the leader,
+/// so adding a master never reduces the availability of the group.
+/// An observer stays a learner, it serves follower reads without being part of the quorum.
+/// The rpc port of every master is recorded in the group, so clients can discover new masters.
+pub struct MasterMembership {
+    rt: Arc<Runtime>,
+    client: RaftClient,
+    rpc_port: u16,
+}
+
+impl MasterMembership {
+    pub fn new(rt: Arc<Runtime>, conf: &ClusterConf) -> Self {
+        let client = RaftClient::from_conf(rt.clone(), &conf.journal);
+        Self {
+            rt,
+            client,
+            rpc_port: conf.master.rpc_port,
+        }
+    }
+
+    // addr is the journal address of the master, its rpc port defaults to the one of this master.
+    pub fn add_master(
+        &self,
+        id: NodeId,
+        addr: &InetAddr,
+        rpc_port: Option<u16>,
+        observer: bool,
+    ) -> FsResult<()> {
+        let peer = RaftPeer::new(id, &addr.hostname, addr.port)
+            .with_observer(observer)
+            .with_service_port(rpc_port.unwrap_or(self.rpc_port));
+        self.rt.block_on(self.client.add_learner(&peer))?;
+        Ok(())
+    }
+
+    pub fn remove_master(&self, id: NodeId) -> FsResult<()> {
+        self.rt.block_on(self.client.remove_node(id))?;
+        Ok(())
+    }
+
+    pub fn transfer_leader(&self, id: NodeId) -> FsResult<()> {
+        self.rt.block_on(self.client.transfer_leader(id))?;
+        Ok(())
+    }
+
+    pub fn list_masters(&self) -> FsResult<MasterGroup> {
+        let info = self.rt.block_on(self.client.group_info())?;
+
+        let mut masters: Vec<MasterNode> = info
+            .group
+            .into_iter()
+            .map(|x| MasterNode {
+                id: x.node_id,
+                hostname: x.hostname,
+                port: x.port as u16,
+                learner: info.learners.contains(&x.node_id),
+                observer: x.observer.unwrap_or(false),
+                rpc_port: match x.service_port.unwrap_or(0) {
+                    0 => self.rpc_port,
+                    port => port as u16,
+                },
+            })
+            .collect();
+        masters.sort_by_key(|x| x.id);
+
+        Ok(MasterGroup {
+            leader_id: info.leader_id,
+            masters,
+        })
+    }
+}
@@ -51,6 +51,13 @@ pub struct MasterMetrics {
     pub(crate) replication_inflight_number: Gauge,
     pub(crate) replication_failure_count: Counter,
 
+    // for the replication reconciler
+    pub(crate) reconcile_scanned_blocks: Counter,
+    pub(crate) reconcile_under_replicated: Counter,
+    pub(crate) reconcile_over_replicated: Counter,
+    pub(crate) reconcile_full_scans: Counter,
+    pub(crate) reconcile_cursor: Gauge,
+
     pub(crate) operation_duration: HistogramVec,
 
     // for quota eviction (LRU)
@@ -58,6 +65,11 @@ pub struct MasterMetrics {
     pub(crate) eviction_trigger_count: Counter,
     pub(crate) eviction_files_deleted: Counter,
     pub(crate) eviction_bytes_freed: Counter,
+
+    // for metadata rate limiting
+    pub(crate) rpc_throttled_count: CounterVec,
+    pub(crate) rpc_throttled_backoff_ms: CounterVec,
+    pub(crate) rpc_queued_wait_ms: CounterVec,
 }
 
 impl MasterMetrics {
@@ -113,6 +125,27 @@ impl MasterMetrics {
                 "Total failure count",
             )?,
 
+            reconcile_scanned_blocks: m::new_counter(
+                "reconcile_scanned_blocks",
+                "Number of blocks checked by the replication reconciler",
+            )?,
+            reconcile_under_replicated: m::new_counter(
+                "reconcile_under_replicated",
+                "Number of replications scheduled by the replication reconciler",
+            )?,
+            reconcile_over_replicated: m::new_counter(
+                "reconcile_over_replicated",
+                "Number of excess replicas deleted by the replication reconciler",
+            )?,
+            reconcile_full_scans: m::new_counter(
+                "reconcile_full_scans",
+                "Number of completed namespace scans of the replication reconciler",
+            )?,
+            reconcile_cursor: m::new_gauge(
+                "reconcile_cursor",
+                "The inode id where the next reconcile round starts",
+            )?,
+
             operation_duration: m::new_histogram_vec_with_buckets(
                 "operation_duration",
                 "Operation duration except WorkerHeartbeat",
@@ -137,6 +170,22 @@ impl MasterMetrics {
                 "eviction_bytes_freed",
                 "Total bytes freed by eviction",
             )?,
+
+            rpc_throttled_count: m::new_counter_vec(
+                "rpc_throttled_count",
+                "Number of requests rejected by the rate limiter",
+                &["class"],
+            )?,
+            rpc_throttled_backoff_ms: m::new_counter_vec(
+                "rpc_throttled_backoff_ms",
+                "Total backoff(ms) returned to the throttled clients",
+                &["class"],
+            )?,
+            rpc_queued_wait_ms: m::new_counter_vec(
+                "rpc_queued_wait_ms",
+                "Total time(ms) the requests over the rate waited for their turn",
+                &["class"],
+            )?,
         };
 
         Ok(wm)
@@ -16,23 +16,28 @@ use std::sync::Arc;
 
 use once_cell::sync::OnceCell;
 
+use curvine_client::file::{FsClient, FsContext};
 use curvine_common::conf::ClusterConf;
 use curvine_web::server::{WebHandlerService, WebServer};
 use log::error;
-use orpc::common::{LocalTime, Logger};
+use orpc::common::{LocalTime, Logger, Tracer};
 use orpc::handler::HandlerService;
 use orpc::io::net::ConnState;
 use orpc::runtime::{RpcRuntime, Runtime};
 use orpc::server::{RpcServer, ServerStateListener};
 use orpc::CommonResult;
 
+use crate::master::backup::MetaBackupManager;
 use crate::master::fs::{FsRetryCache, MasterActor, MasterFilesystem};
 use crate::master::journal::JournalSystem;
 use crate::master::replication::master_replication_manager::MasterReplicationManager;
 use crate::master::router_handler::MasterRouterHandler;
+use crate::master::{
+    FollowerReader, MasterMembership, MasterMetrics, MasterMonitor, MasterRateLimiter,
+    SyncWorkerManager,
+};
 use crate::master::{JobHandler, MountManager};
-use crate::master::{JobManager, MasterHandler};
-use crate::master::{MasterMetrics, MasterMonitor, SyncWorkerManager};
+use crate::master::{JobManager, MasterHandler, StorageMigrationManager};
 
 pub static MASTER_METRICS: OnceCell<MasterMetrics> = OnceCell::new();
 
@@ -45,9 +50,15 @@ pub struct MasterService {
     job_manager: Arc<JobManager>,
     rt: Arc<Runtime>,
     replication_manager: Arc<MasterReplicationManager>,
+    follower_reader: Arc<FollowerReader>,
+    membership: Arc<MasterMembership>,
+    storage_migration: StorageMigrationManager,
+    backup_manager: MetaBackupManager,
+    rate_limiter: Option<Arc<MasterRateLimiter>>,
 }
 
 impl MasterService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         conf: ClusterConf,
         fs: MasterFilesystem,
@@ -56,6 +67,10 @@ impl MasterService {
         job_manager: Arc<JobManager>,
         rt: Arc<Runtime>,
         replication_manager: Arc<MasterReplicationManager>,
+        follower_reader: Arc<FollowerReader>,
+        membership: Arc<MasterMembership>,
+        storage_migration: StorageMigrationManager,
+        backup_manager: MetaBackupManager,
     ) -> Self {
         Self {
             conf,
@@ -65,9 +80,19 @@ impl MasterService {
             job_manager,
             rt,
             replication_manager,
+            follower_reader,
+            membership,
+            storage_migration,
+            backup_manager,
+            rate_limiter: None,
         }
     }
 
+    pub fn with_rate_limiter(mut self, rate_limiter: Option<MasterRateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter.map(Arc::new);
+        self
+    }
+
     pub fn clone_worker_manager(&self) -> SyncWorkerManager {
         self.fs.worker_manager.clone()
     }
@@ -102,6 +127,11 @@ impl HandlerService for MasterService {
             JobHandler::new(self.job_manager.clone()),
             self.replication_manager.clone(),
         )
+        .with_follower_reader(self.follower_reader.clone())
+        .with_membership(self.membership.clone())
+        .with_storage_migration(self.storage_migration.clone())
+        .with_backup_manager(self.backup_manager.clone())
+        .with_rate_limiter(self.rate_limiter.clone())
     }
 }
 
@@ -122,16 +152,24 @@ pub struct Master {
     mount_manager: Arc<MountManager>,
     job_manager: Arc<JobManager>,
     replication_manager: Arc<MasterReplicationManager>,
+    storage_migration: StorageMigrationManager,
+    backup_manager: MetaBackupManager,
+    backup_enabled: bool,
 }
 
 impl Master {
-    fn new(conf: ClusterConf) -> CommonResult<Self> {
+    // The audit logs are written to a separate target.
+    pub fn init_logger(conf: &ClusterConf) {
         let mut log = conf.master.log.clone();
         if conf.master.audit_logging_enabled {
             log.targets = vec!["audit".to_string()]
         }
-
         Logger::init(log);
+    }
+
+    fn new(conf: ClusterConf) -> CommonResult<Self> {
+        Self::init_logger(&conf);
+        Tracer::init(&conf.trace, format!("{}-master", conf.cluster_id))?;
         MASTER_METRICS.get_or_init(|| MasterMetrics::new().unwrap());
         conf.print();
 
@@ -146,12 +184,16 @@ impl Master {
 
         let replication_manager = MasterReplicationManager::new(&fs, &conf, &rt, &worker_manager);
 
+        // Used by standby masters to request the leader.
+        let leader_client = FsClient::new(Arc::new(FsContext::with_rt(conf.clone(), rt.clone())?));
+
         let actor = MasterActor::new(
             fs.clone(),
             journal_system.master_monitor(),
             conf.master.new_executor(),
             &replication_manager,
             quota_manager,
+            leader_client,
         );
 
         let job_manager = Arc::new(JobManager::from_cluster_conf(
@@ -161,6 +203,15 @@ impl Master {
             &conf,
         ));
 
+        let storage_migration = StorageMigrationManager::new(
+            fs.clone(),
+            job_manager.jobs().clone(),
+            replication_manager.clone(),
+        );
+
+        let backup_manager =
+            MetaBackupManager::new(&conf, fs.clone(), &journal_system, rt.clone())?;
+
         // step3: Create rpc server.
         let retry_cache = FsRetryCache::with_conf(&conf.master);
         let service = MasterService::new(
@@ -171,7 +222,12 @@ impl Master {
             job_manager.clone(),
             rt.clone(),
             replication_manager.clone(),
-        );
+            journal_system.follower_reader(),
+            journal_system.membership(),
+            storage_migration.clone(),
+            backup_manager.clone(),
+        )
+        .with_rate_limiter(MasterRateLimiter::with_conf(&conf.master)?);
 
         let rpc_conf = conf.master_server_conf();
         let rpc_server = RpcServer::with_rt(rt.clone(), rpc_conf, service.clone());
@@ -189,6 +245,9 @@ impl Master {
             mount_manager,
             job_manager,
             replication_manager,
+            storage_migration,
+            backup_manager,
+            backup_enabled: conf.master.backup_enabled,
         })
     }
 
@@ -216,6 +275,9 @@ impl Master {
 
         // step5: Start job manager
         self.job_manager.start();
+        if let Err(e) = self.storage_migration.start() {
+            error!("Failed to start storage migration: {}", e);
+        }
 
         // step6: Start TTL scheduler (requires mount_manager and job_manager)
         if let Err(e) = self.actor.start_ttl_scheduler(
@@ -226,6 +288,13 @@ impl Master {
             error!("Failed to start inode ttl scheduler: {}", e);
         }
 
+        // step7: Start the scheduled metadata backup
+        if self.backup_enabled {
+            if let Err(e) = self.backup_manager.start() {
+                error!("Failed to start metadata backup: {}", e);
+            }
+        }
+
         rpc_status
     }
 
@@ -33,6 +33,8 @@ pub struct BlockMeta {
     // The pre-assigned worker id is required when deleting.
     pub(crate) locs: Option<Vec<BlockLocation>>,
     pub(crate) alloc_opts: Option<FileAllocOpts>,
+    // Worker id of each unit of an erasure-coded block, ordered by unit index.
+    pub(crate) units: Vec<u32>,
 }
 
 impl BlockMeta {
@@ -43,6 +45,7 @@ impl BlockMeta {
             replicas: 1,
             locs: None,
             alloc_opts: None,
+            units: vec![],
         }
     }
 
@@ -58,9 +61,18 @@ impl BlockMeta {
             replicas: 1,
             locs: Some(locs),
             alloc_opts: None,
+            units: vec![],
         }
     }
 
+    // Pre-allocated erasure-coded block, workers[i] stores unit i.
+    pub fn with_striped(id: i64, workers: &[WorkerAddress]) -> Self {
+        let mut meta = Self::with_pre(id, workers);
+        meta.replicas = workers.len() as u16;
+        meta.units = workers.iter().map(|x| x.worker_id).collect();
+        meta
+    }
+
     pub fn with_alloc(id: i64, alloc_opts: FileAllocOpts) -> Self {
         Self {
             id,
@@ -68,6 +80,7 @@ impl BlockMeta {
             replicas: 0,
             locs: None,
             alloc_opts: Some(alloc_opts),
+            units: vec![],
         }
     }
 
@@ -79,6 +92,10 @@ impl BlockMeta {
         self.len() == 0
     }
 
+    pub fn is_striped(&self) -> bool {
+        !self.units.is_empty()
+    }
+
     pub fn commit(&mut self, commit: &CommitBlock) {
         self.len = self.len.max(commit.block_len);
         let _ = self.locs.take();
@@ -1,4 +1,5 @@
 use crate::master::meta::feature::AclFeature;
+use curvine_common::state::DirPolicy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -6,6 +7,8 @@ use std::collections::HashMap;
 pub struct DirFeature {
     pub(crate) x_attr: HashMap<String, Vec<u8>>,
     pub(crate) acl: AclFeature,
+    // Default storage attributes inherited by new children.
+    pub(crate) policy: DirPolicy,
 }
 
 impl DirFeature {
@@ -13,6 +16,7 @@ impl DirFeature {
         Self {
             x_attr: HashMap::new(),
             acl: AclFeature::default(),
+            policy: DirPolicy::default(),
         }
     }
 }
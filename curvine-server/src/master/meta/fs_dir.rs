@@ -13,19 +13,23 @@
 // limitations under the License.
 
 use crate::master::fs::DeleteResult;
+use crate::master::job::MigrateJob;
 use crate::master::journal::{JournalEntry, JournalWriter};
 use crate::master::meta::inode::ttl::ttl_bucket::TtlBucketList;
 use crate::master::meta::inode::InodeView::{Dir, File, FileEntry};
 use crate::master::meta::inode::*;
 use crate::master::meta::store::{InodeStore, RocksInodeStore};
-use crate::master::meta::{BlockMeta, InodeId};
+use crate::master::meta::{BlockMeta, InodeId, LockMode, LockPattern, PathLockGuard, PathLocks};
 use crate::master::quota::eviction::evictor::Evictor;
+use crate::master::quota::eviction::FileAccess;
 use curvine_common::conf::ClusterConf;
 use curvine_common::error::FsError;
 use curvine_common::state::{
-    BlockLocation, CommitBlock, CreateFileOpts, ExtendedBlock, FileAllocOpts, FileLock, FileStatus,
-    MkdirOpts, MountInfo, RenameFlags, SetAttrOpts, WorkerAddress,
+    BlockLocation, CommitBlock, CreateFileOpts, DirPolicy, EcPolicy, ExtendedBlock, FileAllocOpts,
+    FileLock, FileStatus, MkdirOpts, MountInfo, RenameFlags, SetAttrOpts, StorageType,
+    WorkerAddress,
 };
+use curvine_common::utils::parse_glob_pattern;
 use curvine_common::FsResult;
 use log::{info, warn};
 use orpc::common::{LocalTime, TimeSpent};
@@ -34,13 +38,16 @@ use std::collections::{HashMap, LinkedList};
 use std::mem;
 use std::sync::Arc;
 
-/// Note: The modification operation uses &mut self, which is a necessary improvement. We use the unsafe API to perform modifications.
+/// Note: The path operations modify the tree through &self with the unsafe API, they must hold
+/// the path locks of the paths they change, see lock_paths. The operations that are not bound
+/// to a path, such as journal replay and restore, hold the global write lock instead.
 pub struct FsDir {
     pub(crate) root_dir: InodeView,
     pub(crate) inode_id: InodeId,
     pub(crate) store: InodeStore,
     pub(crate) journal_writer: JournalWriter,
     pub(crate) evictor: Arc<dyn Evictor>,
+    pub(crate) path_locks: PathLocks,
 }
 
 impl FsDir {
@@ -62,6 +69,7 @@ impl FsDir {
             store: state,
             journal_writer,
             evictor,
+            path_locks: PathLocks::new(),
         };
         fs_dir.update_last_inode_id(last_inode_id)?;
 
@@ -86,6 +94,108 @@ impl FsDir {
         &self.root_dir
     }
 
+    /// Lock the path for the pattern and resolve it.
+    pub fn lock_path(
+        &self,
+        path: &str,
+        pattern: LockPattern,
+    ) -> FsResult<(PathLockGuard<'_>, InodePath)> {
+        let (guard, mut paths) = self.lock_paths(&[(path, pattern)])?;
+        Ok((guard, paths.remove(0)))
+    }
+
+    /// Lock all the paths at once and resolve them, the paths are resolved under the locks.
+    /// A path locked for Create is locked again when its parent is missing, until the deepest
+    /// existing ancestor, under which the parents are created, is write locked.
+    pub fn lock_paths(
+        &self,
+        paths: &[(&str, LockPattern)],
+    ) -> FsResult<(PathLockGuard<'_>, Vec<InodePath>)> {
+        let mut components = Vec::with_capacity(paths.len());
+        let mut indexes = Vec::with_capacity(paths.len());
+        for (path, pattern) in paths {
+            let names = InodeView::path_components(path)?;
+            let index = match pattern {
+                LockPattern::Read => (names.len() - 1, LockMode::IntentRead),
+                LockPattern::Write | LockPattern::Create => {
+                    (names.len().saturating_sub(2), LockMode::Write)
+                }
+                LockPattern::Subtree => (names.len() - 1, LockMode::Read),
+            };
+            components.push(names);
+            indexes.push(index);
+        }
+
+        loop {
+            let locks: Vec<(&[String], usize, LockMode)> = components
+                .iter()
+                .zip(indexes.iter())
+                .map(|(x, i)| (x.as_slice(), i.0, i.1))
+                .collect();
+            let guard = self.path_locks.lock(&locks);
+
+            let mut inps = Vec::with_capacity(paths.len());
+            let mut relock = false;
+            for (i, (path, pattern)) in paths.iter().enumerate() {
+                let inp = InodePath::resolve(self.root_ptr(), path, &self.store)?;
+                if *pattern == LockPattern::Create {
+                    let deepest = inp.existing_len().saturating_sub(1);
+                    if indexes[i].0 > deepest {
+                        indexes[i].0 = deepest;
+                        relock = true;
+                    }
+                }
+                inps.push(inp);
+            }
+
+            if !relock {
+                let mut guard = guard;
+                guard.lock_inodes(&Self::file_locks(paths, &inps));
+                return Ok((guard, inps));
+            }
+        }
+    }
+
+    // A file may be hard linked, and its nlink can change under the lock of another path,
+    // so every file is locked by id as well.
+    fn file_locks(paths: &[(&str, LockPattern)], inps: &[InodePath]) -> Vec<(i64, LockMode)> {
+        let mut ids = vec![];
+        for ((_, pattern), inp) in paths.iter().zip(inps) {
+            let inode = match inp.get_last_inode() {
+                Some(v) => v,
+                None => continue,
+            };
+            if let File(_, file) = inode.as_ref() {
+                let mode = match pattern {
+                    LockPattern::Read | LockPattern::Subtree => LockMode::Read,
+                    LockPattern::Write | LockPattern::Create => LockMode::Write,
+                };
+                ids.push((file.id, mode));
+            }
+        }
+        ids
+    }
+
+    /// Lock the subtree that a glob pattern may match, the deepest dir without glob characters.
+    pub fn lock_glob(&self, path: &str) -> FsResult<PathLockGuard<'_>> {
+        let names = InodeView::path_components(path)?;
+        let index = names
+            .iter()
+            .position(|x| parse_glob_pattern(x).0)
+            .unwrap_or(names.len())
+            .saturating_sub(1);
+        Ok(self
+            .path_locks
+            .lock(&[(names.as_slice(), index, LockMode::Read)]))
+    }
+
+    /// Read lock the whole tree, for the walks that are not bound to a path.
+    pub fn lock_tree(&self) -> PathLockGuard<'_> {
+        let root = [PATH_SEPARATOR.to_string()];
+        self.path_locks
+            .lock(&[(root.as_slice(), 0, LockMode::Read)])
+    }
+
     fn next_inode_id(&self) -> FsResult<i64> {
         let id = self.inode_id.next()?;
         Ok(id)
@@ -95,7 +205,7 @@ impl FsDir {
         self.store.get_ttl_bucket_list()
     }
 
-    pub fn mkdir(&mut self, mut inp: InodePath, opts: MkdirOpts) -> FsResult<InodePath> {
+    pub fn mkdir(&self, mut inp: InodePath, opts: MkdirOpts) -> FsResult<InodePath> {
         // Create parent directory
         inp = self.create_parent_dir(inp, opts.parent_opts())?;
 
@@ -107,7 +217,7 @@ impl FsDir {
     // Create the first subdirectory that does not exist.
     // 1. If all directories on the path already exist, skip and return successful.
     // 2. If the parent directory does not exist, an error is returned.
-    fn create_single_dir(&mut self, mut inp: InodePath, opts: MkdirOpts) -> FsResult<InodePath> {
+    fn create_single_dir(&self, mut inp: InodePath, mut opts: MkdirOpts) -> FsResult<InodePath> {
         let op_ms = LocalTime::mills();
 
         if inp.is_full() || inp.is_root() {
@@ -117,6 +227,7 @@ impl FsDir {
         let pos = inp.existing_len() - 1;
         let name = inp.get_component(pos + 1)?.to_string();
 
+        Self::inherit_policy(&inp).apply_dir(&mut opts);
         let dir = InodeDir::with_opts(self.next_inode_id()?, LocalTime::mills() as i64, opts);
 
         inp = self.add_last_inode(inp, Dir(name.clone(), dir.clone()))?;
@@ -128,7 +239,7 @@ impl FsDir {
     }
 
     // Create all previous directories that may be missing on the path.
-    fn create_parent_dir(&mut self, mut inp: InodePath, opts: MkdirOpts) -> FsResult<InodePath> {
+    fn create_parent_dir(&self, mut inp: InodePath, opts: MkdirOpts) -> FsResult<InodePath> {
         let mut index = inp.existing_len();
 
         // The parent directory already exists and does not need to be created.
@@ -145,7 +256,7 @@ impl FsDir {
     }
 
     // Delete files or directories
-    pub fn delete(&mut self, inp: &InodePath, recursive: bool) -> FsResult<DeleteResult> {
+    pub fn delete(&self, inp: &InodePath, recursive: bool) -> FsResult<DeleteResult> {
         let op_ms = LocalTime::mills();
 
         if inp.is_root() {
@@ -167,11 +278,7 @@ impl FsDir {
         Ok(del_res)
     }
 
-    pub(crate) fn unprotected_delete(
-        &mut self,
-        inp: &InodePath,
-        mtime: i64,
-    ) -> FsResult<DeleteResult> {
+    pub(crate) fn unprotected_delete(&self, inp: &InodePath, mtime: i64) -> FsResult<DeleteResult> {
         let target = match inp.get_last_inode() {
             Some(v) => v,
             None => return err_box!("Path not exists: {}", inp.path()),
@@ -219,7 +326,7 @@ impl FsDir {
     }
 
     pub fn rename(
-        &mut self,
+        &self,
         src_inp: &InodePath,
         dst_inp: &InodePath,
         flags: RenameFlags,
@@ -237,7 +344,7 @@ impl FsDir {
     }
 
     pub(crate) fn unprotected_rename(
-        &mut self,
+        &self,
         src_inp: &InodePath,
         dst_inp: &InodePath,
         mtime: i64,
@@ -309,12 +416,14 @@ impl FsDir {
         Ok(del_res)
     }
 
-    pub fn create_file(&mut self, mut inp: InodePath, opts: CreateFileOpts) -> FsResult<InodePath> {
+    pub fn create_file(&self, mut inp: InodePath, mut opts: CreateFileOpts) -> FsResult<InodePath> {
         let op_ms = LocalTime::mills();
         if inp.get_last_inode().is_some() {
             return err_ext!(FsError::file_exists(inp.path()));
         }
 
+        Self::inherit_policy(&inp).apply_file(&mut opts);
+
         // Create a directory that does not exist.
         inp = self.create_parent_dir(inp, opts.dir_opts())?;
         let name = inp.name().to_string();
@@ -327,8 +436,20 @@ impl FsDir {
         Ok(inp)
     }
 
+    // The default storage attributes of the existing directories on the path, the nearest
+    // directory that sets an attribute wins.
+    pub fn inherit_policy(inp: &InodePath) -> DirPolicy {
+        let mut policy = DirPolicy::default();
+        for inode in inp.get_inodes().iter().rev() {
+            if let Some(parent) = inode.dir_policy() {
+                policy.inherit(parent);
+            }
+        }
+        policy
+    }
+
     pub(crate) fn add_last_inode(
-        &mut self,
+        &self,
         mut inp: InodePath,
         child: InodeView,
     ) -> FsResult<InodePath> {
@@ -382,17 +503,37 @@ impl FsDir {
     }
 
     pub fn list_status(&self, inp: &InodePath) -> FsResult<Vec<FileStatus>> {
+        let (res, _) = self.list_status_page(inp, None, 0)?;
+        Ok(res)
+    }
+
+    // List at most limit children with names after start_after, a limit of 0 means no limit.
+    // Returns the statuses and whether there are more children.
+    pub fn list_status_page(
+        &self,
+        inp: &InodePath,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> FsResult<(Vec<FileStatus>, bool)> {
         let inode = match inp.get_last_inode() {
             Some(v) => v,
             None => return err_box!("File {} not exists", inp.path()),
         };
 
-        let mut res = Vec::with_capacity(1.max(inode.child_len()));
+        let limit = if limit == 0 { usize::MAX } else { limit };
+        let mut res = Vec::with_capacity(1.max(inode.child_len().min(limit)));
         match inode.as_ref() {
+            // A file is listed as itself, it is on the first page only.
+            File(..) | FileEntry(..) if start_after.is_some() => (),
+
             File(_, _) => res.push(inode.to_file_status(inp.path())),
 
             Dir(_, d) => {
-                for item in d.children_iter() {
+                for item in d.children_after(start_after) {
+                    if res.len() >= limit {
+                        return Ok((res, true));
+                    }
+
                     let child_path = inp.child_path(item.name());
                     match item {
                         File(..) | Dir(..) => res.push(item.to_file_status(&child_path)),
@@ -415,11 +556,22 @@ impl FsDir {
             }
         }
 
-        Ok(res)
+        Ok((res, false))
+    }
+
+    // A FileEntry child only keeps the id, the inode is loaded from the store.
+    pub(crate) fn resolve_child(&self, child: &InodeView) -> FsResult<Option<InodePtr>> {
+        match child {
+            FileEntry(name, id) => match self.store.get_inode(*id, Some(name))? {
+                Some(full_inode) => Ok(Some(InodePtr::from_owned(full_inode))),
+                None => Ok(None),
+            },
+            _ => Ok(Some(InodePtr::from_ref(child))),
+        }
     }
 
     pub fn acquire_new_block(
-        &mut self,
+        &self,
         inp: &InodePath,
         commit_blocks: Vec<CommitBlock>,
         choose_workers: &[WorkerAddress],
@@ -435,7 +587,12 @@ impl FsDir {
         file.complete(file_len, &commit_blocks, "", true)?;
 
         // create block.
-        file.add_block(BlockMeta::with_pre(new_block_id, choose_workers));
+        let meta = if file.storage_policy.is_striped() {
+            BlockMeta::with_striped(new_block_id, choose_workers)
+        } else {
+            BlockMeta::with_pre(new_block_id, choose_workers)
+        };
+        file.add_block(meta);
 
         let block = ExtendedBlock {
             id: new_block_id,
@@ -457,7 +614,7 @@ impl FsDir {
     }
 
     pub fn complete_file(
-        &mut self,
+        &self,
         inp: &InodePath,
         len: i64,
         commit_block: Vec<CommitBlock>,
@@ -469,7 +626,7 @@ impl FsDir {
         let file = inode.as_file_mut()?;
         file.complete(len, &commit_block, client_name, only_flush)?;
 
-        self.evictor.on_access(file.id());
+        self.evictor.on_file_access(FileAccess::from_file(file));
 
         self.store
             .apply_complete_file(inode.as_ref(), &commit_block)?;
@@ -488,7 +645,7 @@ impl FsDir {
         file: &InodeFile,
     ) -> FsResult<HashMap<i64, Vec<BlockLocation>>> {
         let locs = self.store.get_file_locations(file)?;
-        self.evictor.on_access(file.id());
+        self.evictor.on_file_access(FileAccess::from_file(file));
         Ok(locs)
     }
 
@@ -502,7 +659,7 @@ impl FsDir {
     }
 
     pub fn reopen_file(
-        &mut self,
+        &self,
         inp: &InodePath,
         client_name: impl AsRef<str>,
     ) -> FsResult<FileStatus> {
@@ -556,13 +713,30 @@ impl FsDir {
         }
     }
 
+    // The length of a replicated block of a complete file, which all its replicas must have.
+    // None if the block is being written, is erasure-coded, or does not exist.
+    pub fn committed_block_len(&self, block_id: i64) -> FsResult<Option<i64>> {
+        let file_id = InodeId::get_id(block_id);
+        let file = match self.store.get_inode(file_id, None)? {
+            Some(File(_, file)) if !file.is_writing() => file,
+            _ => return Ok(None),
+        };
+
+        let len = file
+            .blocks
+            .iter()
+            .find(|x| x.id == block_id && x.alloc_opts.is_none() && !x.is_striped())
+            .map(|x| x.len);
+        Ok(len)
+    }
+
     /// Overwrite a file by cleaning all blocks and updating metadata.
     /// If file doesn't exist, create a new one.
     /// Returns DeleteResult containing blocks that need to be removed from workers.
     pub fn overwrite_file(
-        &mut self,
+        &self,
         inp: &InodePath,
-        opts: CreateFileOpts,
+        mut opts: CreateFileOpts,
     ) -> FsResult<DeleteResult> {
         let op_ms = LocalTime::mills();
         let mut delete_result = DeleteResult::new();
@@ -573,6 +747,7 @@ impl FsDir {
                     return err_box!("Path is not a file: {}", inp.path());
                 }
 
+                Self::inherit_policy(inp).apply_file(&mut opts);
                 let file = inode.as_mut().as_file_mut()?;
                 for block_meta in &file.blocks {
                     if let Ok(locations) = self.get_block_locations(block_meta.id) {
@@ -637,6 +812,10 @@ impl FsDir {
         self.store.create_checkpoint(id)
     }
 
+    pub fn create_checkpoint_to(&self, path: &str) -> CommonResult<()> {
+        self.store.create_checkpoint_to(path)
+    }
+
     pub fn restore<T: AsRef<str>>(&mut self, path: T) -> CommonResult<()> {
         let mut spend = TimeSpent::new();
         let path = path.as_ref();
@@ -738,7 +917,7 @@ impl FsDir {
         self.store.get_mount_point(id)
     }
 
-    pub fn set_attr(&mut self, inp: InodePath, opts: SetAttrOpts) -> FsResult<FileStatus> {
+    pub fn set_attr(&self, inp: InodePath, opts: SetAttrOpts) -> FsResult<FileStatus> {
         let op_ms = LocalTime::mills();
 
         let inode = match inp.get_last_inode() {
@@ -751,7 +930,7 @@ impl FsDir {
         Ok(inode.to_file_status(inp.path()))
     }
 
-    pub fn unprotected_set_attr(&mut self, inode: InodePtr, opts: SetAttrOpts) -> FsResult<()> {
+    pub fn unprotected_set_attr(&self, inode: InodePtr, opts: SetAttrOpts) -> FsResult<()> {
         let child_opts = opts.child_opts();
         let recursive = opts.recursive;
         let parent_inode_id = inode.id();
@@ -795,13 +974,47 @@ impl FsDir {
         Ok(())
     }
 
-    pub fn symlink(
-        &mut self,
-        target: String,
-        link: InodePath,
-        force: bool,
-        mode: u32,
-    ) -> FsResult<()> {
+    // The blocks of the complete files under the inode, (block id, len, replicas, striped).
+    pub fn file_blocks(
+        &self,
+        inode: InodePtr,
+        recursive: bool,
+    ) -> FsResult<Vec<(i64, i64, u16, bool)>> {
+        let mut blocks = vec![];
+        let mut stack = LinkedList::new();
+        stack.push_back(inode);
+
+        while let Some(cur_inode) = stack.pop_front() {
+            if let File(_, file) = cur_inode.as_ref() {
+                if file.is_complete() {
+                    let striped = file.storage_policy.is_striped();
+                    for meta in &file.blocks {
+                        blocks.push((meta.id, meta.len, file.replicas, striped));
+                    }
+                }
+                continue;
+            }
+
+            // Without recursive, only the dir itself is changed.
+            if !recursive {
+                continue;
+            }
+            for child in cur_inode.children() {
+                let resolved_child = match child {
+                    FileEntry(name, id) => match self.store.get_inode(*id, Some(name))? {
+                        Some(full_inode) => InodePtr::from_owned(full_inode),
+                        None => continue,
+                    },
+                    _ => InodePtr::from_ref(child),
+                };
+                stack.push_back(resolved_child);
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    pub fn symlink(&self, target: String, link: InodePath, force: bool, mode: u32) -> FsResult<()> {
         let op_ms = LocalTime::mills();
 
         let new_inode = InodeFile::with_link(self.inode_id.next()?, op_ms as i64, target, mode);
@@ -813,7 +1026,7 @@ impl FsDir {
     }
 
     pub fn unprotected_symlink(
-        &mut self,
+        &self,
         mut link: InodePath,
         new_inode: InodeFile,
         force: bool,
@@ -854,7 +1067,7 @@ impl FsDir {
     }
 
     // Create a link to an existing file
-    pub fn link(&mut self, src_path: InodePath, dst_path: InodePath) -> FsResult<()> {
+    pub fn link(&self, src_path: InodePath, dst_path: InodePath) -> FsResult<()> {
         let op_ms = LocalTime::mills();
 
         // Get the original inode ID and update nlink in memory if it's a direct File
@@ -892,7 +1105,7 @@ impl FsDir {
     }
 
     pub fn unprotected_link(
-        &mut self,
+        &self,
         mut new_path: InodePath,
         original_inode_id: i64,
         op_ms: u64,
@@ -945,7 +1158,7 @@ impl FsDir {
     /// 2. Complete the file operation to update metadata state
     /// 3. Collect locations of blocks to be deleted
     /// 4. Persist changes to store and write journal entry
-    pub fn resize(&mut self, inp: &InodePath, opts: FileAllocOpts) -> FsResult<DeleteResult> {
+    pub fn resize(&self, inp: &InodePath, opts: FileAllocOpts) -> FsResult<DeleteResult> {
         let op_ms = LocalTime::mills();
 
         let mut inode = match inp.get_last_inode() {
@@ -977,7 +1190,7 @@ impl FsDir {
     }
 
     pub fn assign_worker(
-        &mut self,
+        &self,
         inp: InodePath,
         block_id: i64,
         workers: &[WorkerAddress],
@@ -1006,6 +1219,78 @@ impl FsDir {
         Ok(block)
     }
 
+    // Build the full path of an inode by walking up its parents.
+    pub fn get_inode_path(&self, id: i64) -> FsResult<String> {
+        let mut names = vec![];
+        let mut cur = id;
+        while cur != ROOT_INODE_ID {
+            let (name, parent_id) = match self.store.get_inode(cur, None)? {
+                Some(File(name, file)) => (name, file.parent_id),
+                Some(Dir(name, dir)) => (name, dir.parent_id),
+                _ => return err_box!("Cannot resolve path for inode {}", id),
+            };
+            if parent_id == EMPTY_PARENT_ID {
+                return err_box!("Cannot resolve path for inode {}", id);
+            }
+            names.push(name);
+            cur = parent_id;
+        }
+
+        names.reverse();
+        Ok(format!("{}{}", PATH_SEPARATOR, names.join(PATH_SEPARATOR)))
+    }
+
+    // Get the ec policy, storage type and block meta of an erasure-coded block.
+    pub fn get_striped_block(
+        &self,
+        block_id: i64,
+    ) -> FsResult<Option<(EcPolicy, StorageType, i64, BlockMeta)>> {
+        let file = match self.store.get_inode(InodeId::get_id(block_id), None)? {
+            Some(File(_, file)) => file,
+            _ => return Ok(None),
+        };
+
+        let policy = match file.storage_policy.ec_policy {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        match file.blocks.iter().find(|x| x.id == block_id) {
+            Some(meta) if meta.is_striped() => Ok(Some((
+                policy,
+                file.storage_policy.storage_type,
+                file.block_size,
+                meta.clone(),
+            ))),
+            _ => Ok(None),
+        }
+    }
+
+    // An erasure-coded unit has been rebuilt on another worker, update the unit layout of the block.
+    pub fn update_block_unit(
+        &mut self,
+        block_id: i64,
+        index: usize,
+        worker_id: u32,
+    ) -> FsResult<()> {
+        let op_ms = LocalTime::mills();
+        let path = self.get_inode_path(InodeId::get_id(block_id))?;
+        let inp = InodePath::resolve(self.root_ptr(), &path, &self.store)?;
+
+        let mut inode = try_option!(inp.get_last_inode(), "File {} not exists", path);
+        let file = inode.as_file_mut()?;
+        let meta = file.search_block_mut_check(block_id)?;
+        match meta.units.get_mut(index) {
+            Some(v) => *v = worker_id,
+            None => return err_box!("Block {} has no unit {}", block_id, index),
+        }
+
+        self.store.apply_new_block(inode.as_ref(), &[])?;
+        self.journal_writer
+            .log_add_block(op_ms, inp.path(), inode.as_file_ref()?, vec![])?;
+        Ok(())
+    }
+
     pub fn get_locations(&self, meta: &BlockMeta) -> CommonResult<Vec<BlockLocation>> {
         if let Some(locs) = &meta.locs {
             Ok(locs.clone())
@@ -1052,4 +1337,23 @@ impl FsDir {
 
         Ok(conflict)
     }
+
+    pub fn add_migration(&self, job: MigrateJob) -> FsResult<()> {
+        let op_ms = LocalTime::mills();
+        self.store.apply_add_migration(&job)?;
+        self.journal_writer.log_add_migration(op_ms, job)?;
+        Ok(())
+    }
+
+    pub fn remove_migration(&self, job_id: &str) -> FsResult<()> {
+        let op_ms = LocalTime::mills();
+        self.store.apply_remove_migration(job_id)?;
+        self.journal_writer
+            .log_remove_migration(op_ms, job_id.to_string())?;
+        Ok(())
+    }
+
+    pub fn get_migrations(&self) -> FsResult<Vec<MigrateJob>> {
+        Ok(self.store.get_migrations()?)
+    }
 }
@@ -18,7 +18,7 @@ use crate::master::meta::inode::InodeView::{Dir, File};
 use crate::master::meta::inode::{
     ChildrenIter, Inode, InodeFile, InodePtr, InodeView, EMPTY_PARENT_ID,
 };
-use curvine_common::state::{MkdirOpts, StoragePolicy};
+use curvine_common::state::{DirPolicy, MkdirOpts, PolicyOverrides, StoragePolicy};
 use glob::Pattern;
 use orpc::CommonResult;
 use serde::{Deserialize, Serialize};
@@ -53,6 +53,12 @@ impl InodeDir {
     }
 
     pub fn with_opts(id: i64, time: i64, opts: MkdirOpts) -> Self {
+        // An ec policy set explicitly on mkdir is the default of the children of the directory.
+        let mut policy = DirPolicy::default();
+        if opts.overrides.contains(PolicyOverrides::EC_POLICY) {
+            policy.ec_policy = opts.storage_policy.ec_policy;
+        }
+
         Self {
             id,
             parent_id: EMPTY_PARENT_ID,
@@ -67,6 +73,7 @@ impl InodeDir {
                     group: opts.group,
                 },
                 x_attr: opts.x_attr,
+                policy,
             },
             children: InodeChildren::new_map(),
         }
@@ -116,6 +123,10 @@ impl InodeDir {
         self.children.iter()
     }
 
+    pub fn children_after(&self, start_after: Option<&str>) -> impl Iterator<Item = &InodeView> {
+        self.children.iter_after(start_after)
+    }
+
     pub fn children_vec(&self) -> Vec<InodeView> {
         self.children.iter().cloned().collect()
     }
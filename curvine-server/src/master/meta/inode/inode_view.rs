@@ -21,7 +21,7 @@ use crate::master::meta::inode::{
     Inode, InodeDir, InodeFile, InodePtr, PATH_SEPARATOR, ROOT_INODE_ID,
 };
 use core::panic;
-use curvine_common::state::{FileStatus, FileType, SetAttrOpts, StoragePolicy};
+use curvine_common::state::{DirPolicy, FileStatus, FileType, SetAttrOpts, StoragePolicy};
 use curvine_common::utils::SerdeUtils;
 use orpc::common::Utils;
 use orpc::{err_box, CommonResult};
@@ -299,6 +299,13 @@ impl InodeView {
         }
     }
 
+    pub fn dir_policy(&self) -> Option<&DirPolicy> {
+        match self {
+            Dir(_, d) => Some(&d.features.policy),
+            _ => None,
+        }
+    }
+
     pub fn x_attr(&self) -> &HashMap<String, Vec<u8>> {
         match self {
             File(_, f) => &f.features.x_attr,
@@ -363,6 +370,24 @@ impl InodeView {
             }
         }
 
+        // The replica number only applies to files, the blocks are reconciled by the master.
+        if let Some(replicas) = opts.replicas {
+            if let File(_, f) = self {
+                f.replicas = replicas as u16;
+            }
+        }
+
+        if let Some(policy) = opts.dir_policy {
+            if let Dir(_, d) = self {
+                d.features.policy = policy;
+            }
+        }
+
+        // The blocks of files are moved to the new storage type by a migration job.
+        if let Some(storage_type) = opts.storage_type {
+            self.storage_policy_mut().storage_type = storage_type;
+        }
+
         if let Some(ttl_ms) = opts.ttl_ms {
             self.storage_policy_mut().ttl_ms = ttl_ms;
         }
@@ -17,6 +17,7 @@ use glob::Pattern;
 use orpc::{err_box, CommonResult};
 use std::collections::btree_map::{Entry, Values};
 use std::collections::BTreeMap;
+use std::ops::Bound;
 use std::slice::Iter;
 use std::vec;
 
@@ -180,6 +181,40 @@ impl InodeChildren {
         }
     }
 
+    // Iterate the children in name order, starting after the given name.
+    pub fn iter_after<'a>(
+        &'a self,
+        start_after: Option<&str>,
+    ) -> impl Iterator<Item = &'a InodeView> + 'a {
+        let start = match start_after {
+            Some(name) => Bound::Excluded(name),
+            None => Bound::Unbounded,
+        };
+
+        let (list, map) = match self {
+            InodeChildren::List(list) => {
+                let index = match start {
+                    Bound::Excluded(name) => match Self::search_by_name(list, name) {
+                        Ok(v) => v + 1,
+                        Err(v) => v,
+                    },
+                    _ => 0,
+                };
+                (Some(list[index..].iter()), None)
+            }
+
+            InodeChildren::Map(map) => {
+                let range = map.range::<str, _>((start, Bound::Unbounded));
+                (None, Some(range.map(|x| x.1)))
+            }
+        };
+
+        list.into_iter()
+            .flatten()
+            .chain(map.into_iter().flatten())
+            .map(|x| x.as_ref())
+    }
+
     pub fn len(&self) -> usize {
         match self {
             InodeChildren::List(list) => list.len(),
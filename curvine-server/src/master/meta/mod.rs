@@ -29,8 +29,11 @@ pub mod store;
 mod block_meta;
 pub use block_meta::BlockMeta;
 
-mod glob_utils;
-pub use glob_utils::*;
+mod tree_walker;
+pub use self::tree_walker::*;
 
 mod lock_meta;
 pub use self::lock_meta::LockMeta;
+
+mod path_lock;
+pub use self::path_lock::*;
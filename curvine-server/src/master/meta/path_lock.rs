@@ -0,0 +1,263 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::master::meta::inode::PATH_SEPARATOR;
+use orpc::common::FastHashMap;
+use std::sync::{Arc, Condvar, Mutex};
+
+/// How an operation locks the components of a path.
+/// Every operation locks the ancestors of the component it locks with an intention mode, a
+/// write lock on a component therefore excludes all the operations on the subtree under it,
+/// and a read lock excludes the writes in the subtree but not the reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockPattern {
+    /// Intention read lock every component, the inode is only read.
+    Read,
+
+    /// Write lock the parent, the inode is changed or it is added to or removed from the parent.
+    /// The children of a dir are read together with the dir when it is listed, so a change of a
+    /// child is exclusive with the dir.
+    Write,
+
+    /// Like Write, but the missing parents are created as well, so the deepest existing
+    /// ancestor is write locked.
+    Create,
+
+    /// Read lock the last component, the whole subtree under the inode is walked without
+    /// being changed. Reads in the subtree still run.
+    Subtree,
+}
+
+/// The modes of a multiple granularity lock. An operation takes an intention mode on the
+/// ancestors of the component it reads or writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    IntentRead,
+    IntentWrite,
+    Read,
+    Write,
+}
+
+impl LockMode {
+    pub fn is_compatible(&self, other: LockMode) -> bool {
+        use LockMode::*;
+        matches!(
+            (self, other),
+            (IntentRead, IntentRead | IntentWrite | Read)
+                | (IntentWrite, IntentRead | IntentWrite)
+                | (Read, IntentRead | Read)
+        )
+    }
+
+    // The mode of the ancestors of a component locked with this mode.
+    fn intention(&self) -> LockMode {
+        match self {
+            LockMode::IntentRead | LockMode::Read => LockMode::IntentRead,
+            LockMode::IntentWrite | LockMode::Write => LockMode::IntentWrite,
+        }
+    }
+
+    // The weakest mode that covers both modes.
+    fn join(self, other: LockMode) -> LockMode {
+        use LockMode::*;
+        match (self, other) {
+            (a, b) if a == b => a,
+            (IntentRead, b) | (b, IntentRead) => b,
+            _ => Write,
+        }
+    }
+}
+
+#[derive(Default)]
+struct LockState {
+    held: Vec<LockMode>,
+    // (ticket, mode) of the waiters in arrival order.
+    waiting: Vec<(u64, LockMode)>,
+    next_ticket: u64,
+}
+
+// A lock of one path. A waiter is only passed by the later requests that are compatible
+// with it, so a stream of readers of a hot directory cannot starve a create in it, and a
+// long subtree walk does not hold up the reads queued behind a waiting write.
+#[derive(Default)]
+struct PathLock {
+    state: Mutex<LockState>,
+    cond: Condvar,
+}
+
+impl PathLock {
+    fn lock(&self, mode: LockMode) {
+        let mut state = self.state.lock().unwrap();
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        state.waiting.push((ticket, mode));
+
+        loop {
+            let blocked = state.held.iter().any(|x| !x.is_compatible(mode))
+                || state
+                    .waiting
+                    .iter()
+                    .take_while(|x| x.0 < ticket)
+                    .any(|x| !x.1.is_compatible(mode));
+            if !blocked {
+                break;
+            }
+            state = self.cond.wait(state).unwrap();
+        }
+
+        state.waiting.retain(|x| x.0 != ticket);
+        state.held.push(mode);
+    }
+
+    fn unlock(&self, mode: LockMode) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(pos) = state.held.iter().position(|x| *x == mode) {
+            state.held.swap_remove(pos);
+        }
+        self.cond.notify_all();
+    }
+}
+
+/// The namespace locks of the master, keyed by path.
+/// A lock only exists while it is held or waited on. The locks of an operation are acquired
+/// in the order of their paths, an ancestor sorts before its descendants, so operations that
+/// lock several paths, such as rename, cannot deadlock.
+#[derive(Default)]
+pub struct PathLocks {
+    locks: Mutex<FastHashMap<String, Arc<PathLock>>>,
+}
+
+impl PathLocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lock the prefixes of the paths, (components, index, mode). The component at the index
+    /// is locked with the mode and the components before it with the intention of the mode,
+    /// the components after it are covered by the lock.
+    pub fn lock(&self, paths: &[(&[String], usize, LockMode)]) -> PathLockGuard<'_> {
+        let mut keys: Vec<(String, LockMode)> = vec![];
+        for (components, lock_index, lock_mode) in paths {
+            let mut key = String::new();
+            for (index, name) in components.iter().enumerate().take(lock_index + 1) {
+                if index == 0 {
+                    key.push_str(PATH_SEPARATOR);
+                } else {
+                    if index > 1 {
+                        key.push_str(PATH_SEPARATOR);
+                    }
+                    key.push_str(name);
+                }
+
+                let mode = if index == *lock_index {
+                    *lock_mode
+                } else {
+                    lock_mode.intention()
+                };
+                keys.push((key.clone(), mode));
+            }
+        }
+
+        // Sort and merge the same paths into a mode that covers all of them.
+        keys.sort_by(|a, b| a.0.cmp(&b.0));
+        keys.dedup_by(|cur, prev| {
+            if cur.0 == prev.0 {
+                prev.1 = prev.1.join(cur.1);
+                true
+            } else {
+                false
+            }
+        });
+
+        let mut guard = PathLockGuard {
+            owner: self,
+            held: Vec::with_capacity(keys.len()),
+        };
+        guard.acquire(keys);
+        guard
+    }
+
+    /// The number of paths that are locked or waited on.
+    pub fn len(&self) -> usize {
+        self.locks.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn release(&self, key: &str, lock: Arc<PathLock>, mode: LockMode) {
+        lock.unlock(mode);
+
+        // The lock is only cloned under the map lock, the map and this guard hold the last references.
+        let mut locks = self.locks.lock().unwrap();
+        if Arc::strong_count(&lock) == 2 {
+            locks.remove(key);
+        }
+    }
+}
+
+/// The locks held by an operation, they are released in the reverse order when it is dropped.
+pub struct PathLockGuard<'a> {
+    owner: &'a PathLocks,
+    held: Vec<(String, Arc<PathLock>, LockMode)>,
+}
+
+impl PathLockGuard<'_> {
+    pub fn modes(&self) -> Vec<(&str, LockMode)> {
+        self.held.iter().map(|x| (x.0.as_str(), x.2)).collect()
+    }
+
+    /// Lock inodes by id after the paths, (inode id, mode). A hard linked file is reached
+    /// through several paths, whose locks do not exclude each other.
+    /// The ids of an operation must be locked at once, they are locked in id order after all
+    /// the paths, and a guard that holds an id lock never waits for another lock.
+    pub fn lock_inodes(&mut self, ids: &[(i64, LockMode)]) {
+        let mut ids = ids.to_vec();
+        ids.sort_by_key(|x| x.0);
+        ids.dedup_by(|cur, prev| {
+            if cur.0 == prev.0 {
+                prev.1 = prev.1.join(cur.1);
+                true
+            } else {
+                false
+            }
+        });
+
+        let keys = ids
+            .into_iter()
+            .map(|(id, mode)| (format!("#{}", id), mode))
+            .collect();
+        self.acquire(keys);
+    }
+
+    fn acquire(&mut self, keys: Vec<(String, LockMode)>) {
+        for (key, mode) in keys {
+            let lock = {
+                let mut locks = self.owner.locks.lock().unwrap();
+                locks.entry(key.clone()).or_default().clone()
+            };
+            lock.lock(mode);
+            self.held.push((key, lock, mode));
+        }
+    }
+}
+
+impl Drop for PathLockGuard<'_> {
+    fn drop(&mut self) {
+        while let Some((key, lock, mode)) = self.held.pop() {
+            self.owner.release(&key, lock, mode);
+        }
+    }
+}
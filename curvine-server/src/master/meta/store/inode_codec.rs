@@ -0,0 +1,281 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::master::meta::feature::{AclFeature, DirFeature, FileFeature};
+use crate::master::meta::inode::InodeView::{Dir, File, FileEntry};
+use crate::master::meta::inode::{InodeDir, InodeFile, InodeView};
+use crate::master::meta::BlockMeta;
+use curvine_common::state::{
+    BlockLocation, FileAllocOpts, FileType, StoragePolicy, StorageType, TtlAction,
+};
+use curvine_common::utils::SerdeUtils as Serde;
+use orpc::{err_box, CommonResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The encoding of the inodes stored in rocksdb.
+///
+/// An inode is stored as a 2 bytes header followed by the bincode of InodeView. The inodes
+/// written before the header was added have no header, bincode writes the variant index of
+/// InodeView first, so their first byte is never INODE_MAGIC. They are decoded with the
+/// legacy format below, which has no erasure coding and directory policy fields.
+pub struct InodeCodec;
+
+impl InodeCodec {
+    pub const INODE_MAGIC: u8 = 0xFF;
+    pub const INODE_VERSION: u8 = 1;
+
+    pub fn encode(inode: &InodeView) -> CommonResult<Vec<u8>> {
+        let mut bytes = vec![Self::INODE_MAGIC, Self::INODE_VERSION];
+        Serde::serialize_into(&mut bytes, inode)?;
+        Ok(bytes)
+    }
+
+    pub fn decode(bytes: &[u8]) -> CommonResult<InodeView> {
+        match bytes {
+            [Self::INODE_MAGIC, Self::INODE_VERSION, rest @ ..] => Serde::deserialize(rest),
+
+            [Self::INODE_MAGIC, version, ..] => {
+                err_box!("Unsupported inode format version {}", version)
+            }
+
+            _ => {
+                let inode: LegacyInodeView = Serde::deserialize(bytes)?;
+                Ok(inode.into())
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyStoragePolicy {
+    storage_type: StorageType,
+    ttl_ms: i64,
+    ttl_action: TtlAction,
+    ufs_mtime: i64,
+}
+
+impl From<LegacyStoragePolicy> for StoragePolicy {
+    fn from(v: LegacyStoragePolicy) -> Self {
+        Self {
+            storage_type: v.storage_type,
+            ttl_ms: v.ttl_ms,
+            ttl_action: v.ttl_action,
+            ufs_mtime: v.ufs_mtime,
+            ec_policy: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyBlockMeta {
+    id: i64,
+    len: i64,
+    replicas: u16,
+    locs: Option<Vec<BlockLocation>>,
+    alloc_opts: Option<FileAllocOpts>,
+}
+
+impl From<LegacyBlockMeta> for BlockMeta {
+    fn from(v: LegacyBlockMeta) -> Self {
+        Self {
+            id: v.id,
+            len: v.len,
+            replicas: v.replicas,
+            locs: v.locs,
+            alloc_opts: v.alloc_opts,
+            units: vec![],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyInodeFile {
+    id: i64,
+    parent_id: i64,
+    file_type: FileType,
+    mtime: i64,
+    atime: i64,
+    len: i64,
+    block_size: i64,
+    replicas: u16,
+    storage_policy: LegacyStoragePolicy,
+    features: FileFeature,
+    blocks: Vec<LegacyBlockMeta>,
+    nlink: u32,
+    next_seq: u32,
+    target: Option<String>,
+}
+
+impl From<LegacyInodeFile> for InodeFile {
+    fn from(v: LegacyInodeFile) -> Self {
+        Self {
+            id: v.id,
+            parent_id: v.parent_id,
+            file_type: v.file_type,
+            mtime: v.mtime,
+            atime: v.atime,
+            len: v.len,
+            block_size: v.block_size,
+            replicas: v.replicas,
+            storage_policy: v.storage_policy.into(),
+            features: v.features,
+            blocks: v.blocks.into_iter().map(BlockMeta::from).collect(),
+            nlink: v.nlink,
+            next_seq: v.next_seq,
+            target: v.target,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyDirFeature {
+    x_attr: HashMap<String, Vec<u8>>,
+    acl: AclFeature,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyInodeDir {
+    id: i64,
+    parent_id: i64,
+    mtime: i64,
+    atime: i64,
+    nlink: u32,
+    storage_policy: LegacyStoragePolicy,
+    features: LegacyDirFeature,
+}
+
+impl From<LegacyInodeDir> for InodeDir {
+    fn from(v: LegacyInodeDir) -> Self {
+        let mut dir = InodeDir::new(v.id, v.mtime);
+        dir.parent_id = v.parent_id;
+        dir.atime = v.atime;
+        dir.nlink = v.nlink;
+        dir.storage_policy = v.storage_policy.into();
+        dir.features = DirFeature {
+            x_attr: v.features.x_attr,
+            acl: v.features.acl,
+            policy: Default::default(),
+        };
+        dir
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum LegacyInodeView {
+    File(String, LegacyInodeFile),
+    Dir(String, LegacyInodeDir),
+    FileEntry(String, i64),
+}
+
+impl From<LegacyInodeView> for InodeView {
+    fn from(v: LegacyInodeView) -> Self {
+        match v {
+            LegacyInodeView::File(name, f) => File(name, f.into()),
+            LegacyInodeView::Dir(name, d) => Dir(name, d.into()),
+            LegacyInodeView::FileEntry(name, id) => FileEntry(name, id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curvine_common::state::EcPolicy;
+
+    #[test]
+    fn decode_legacy_inode() -> CommonResult<()> {
+        let legacy = LegacyInodeView::File(
+            "a".to_string(),
+            LegacyInodeFile {
+                id: 10,
+                parent_id: 1,
+                file_type: FileType::File,
+                mtime: 100,
+                atime: 100,
+                len: 4,
+                block_size: 1024,
+                replicas: 2,
+                storage_policy: LegacyStoragePolicy {
+                    storage_type: StorageType::Ssd,
+                    ttl_ms: 0,
+                    ttl_action: TtlAction::None,
+                    ufs_mtime: 0,
+                },
+                features: FileFeature::new(),
+                blocks: vec![LegacyBlockMeta {
+                    id: 11,
+                    len: 4,
+                    replicas: 2,
+                    locs: None,
+                    alloc_opts: None,
+                }],
+                nlink: 1,
+                next_seq: 1,
+                target: None,
+            },
+        );
+        let inode = InodeCodec::decode(&Serde::serialize(&legacy)?)?;
+        match &inode {
+            File(name, f) => {
+                assert_eq!(name, "a");
+                assert_eq!(f.storage_policy.storage_type, StorageType::Ssd);
+                assert_eq!(f.storage_policy.ec_policy, None);
+                assert_eq!(f.blocks[0].id, 11);
+                assert!(f.blocks[0].units.is_empty());
+            }
+            _ => panic!("not a file"),
+        }
+
+        let legacy = LegacyInodeView::Dir(
+            "b".to_string(),
+            LegacyInodeDir {
+                id: 12,
+                parent_id: 1,
+                mtime: 100,
+                atime: 100,
+                nlink: 2,
+                storage_policy: LegacyStoragePolicy {
+                    storage_type: StorageType::Disk,
+                    ttl_ms: 0,
+                    ttl_action: TtlAction::None,
+                    ufs_mtime: 0,
+                },
+                features: LegacyDirFeature {
+                    x_attr: HashMap::new(),
+                    acl: AclFeature::default(),
+                },
+            },
+        );
+        let inode = InodeCodec::decode(&Serde::serialize(&legacy)?)?;
+        assert_eq!(inode.id(), 12);
+        assert!(inode.dir_policy().unwrap().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn encode_inode() -> CommonResult<()> {
+        let policy = EcPolicy::rs(3, 2)?;
+        let mut dir = InodeDir::new(12, 100);
+        dir.features.policy.ec_policy = Some(policy);
+        let inode = Dir("b".to_string(), dir);
+
+        let bytes = InodeCodec::encode(&inode)?;
+        assert_eq!(bytes[0], InodeCodec::INODE_MAGIC);
+        let inode = InodeCodec::decode(&bytes)?;
+        assert_eq!(inode.dir_policy().unwrap().ec_policy, Some(policy));
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::master::fs::DeleteResult;
+use crate::master::job::MigrateJob;
 use crate::master::meta::inode::ttl::ttl_bucket::TtlBucketList;
 use crate::master::meta::inode::{InodeFile, InodeView, ROOT_INODE_ID};
 use crate::master::meta::store::{InodeWriteBatch, RocksInodeStore};
@@ -168,14 +169,37 @@ impl InodeStore {
 
         batch.write_inode(file)?;
         for commit in commit_blocks {
-            for item in &commit.locations {
-                batch.add_location(commit.block_id, item)?;
-            }
+            self.commit_locations(&mut batch, commit)?;
         }
 
         batch.commit()
     }
 
+    // The locations of a commit replace those of an earlier commit of the same block,
+    // e.g. a worker that was dropped from the write pipeline after a flush.
+    fn commit_locations(
+        &self,
+        batch: &mut InodeWriteBatch,
+        commit: &CommitBlock,
+    ) -> CommonResult<()> {
+        if !commit.locations.is_empty() {
+            for loc in self.store.get_locations(commit.block_id)? {
+                if !commit
+                    .locations
+                    .iter()
+                    .any(|x| x.worker_id == loc.worker_id)
+                {
+                    batch.delete_location(commit.block_id, loc.worker_id)?;
+                }
+            }
+        }
+
+        for item in &commit.locations {
+            batch.add_location(commit.block_id, item)?;
+        }
+        Ok(())
+    }
+
     pub fn apply_complete_file(
         &self,
         file: &InodeView,
@@ -185,9 +209,7 @@ impl InodeStore {
 
         batch.write_inode(file)?;
         for commit in commit_blocks {
-            for item in &commit.locations {
-                batch.add_location(commit.block_id, item)?;
-            }
+            self.commit_locations(&mut batch, commit)?;
         }
 
         batch.commit()
@@ -515,6 +537,10 @@ impl InodeStore {
         self.store.db.create_checkpoint(id)
     }
 
+    pub fn create_checkpoint_to(&self, path: &str) -> CommonResult<()> {
+        self.store.db.create_checkpoint_to(path)
+    }
+
     pub fn restore<T: AsRef<str>>(&mut self, path: T) -> CommonResult<()> {
         // Check if there are other references to the Arc, which would prevent the lock from being released
         let ref_count = Arc::strong_count(&self.store);
@@ -587,4 +613,16 @@ impl InodeStore {
     pub fn apply_set_locks(&self, id: i64, lock: &[FileLock]) -> CommonResult<()> {
         self.store.set_locks(id, lock)
     }
+
+    pub fn apply_add_migration(&self, job: &MigrateJob) -> CommonResult<()> {
+        self.store.add_migration(job)
+    }
+
+    pub fn apply_remove_migration(&self, job_id: &str) -> CommonResult<()> {
+        self.store.remove_migration(job_id)
+    }
+
+    pub fn get_migrations(&self) -> CommonResult<Vec<MigrateJob>> {
+        self.store.get_migrations()
+    }
 }
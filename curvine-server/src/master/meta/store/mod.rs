@@ -15,5 +15,8 @@
 mod inode_store;
 pub use self::inode_store::InodeStore;
 
+mod inode_codec;
+pub use self::inode_codec::InodeCodec;
+
 mod rocks_inode_store;
 pub use self::rocks_inode_store::*;
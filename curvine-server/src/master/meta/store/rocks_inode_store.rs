@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::master::job::MigrateJob;
 use crate::master::meta::inode::InodeView;
+use crate::master::meta::store::InodeCodec;
 use crate::master::meta::LockMeta;
 use curvine_common::rocksdb::{DBConf, DBEngine, RocksIterator, RocksUtils};
 use curvine_common::state::{BlockLocation, FileLock, MountInfo};
@@ -33,6 +35,7 @@ impl RocksInodeStore {
 
     pub const PREFIX_MOUNT: u8 = 0x01;
     pub const PREFIX_LOCK: u8 = 0x02;
+    pub const PREFIX_MIGRATION: u8 = 0x03;
 
     pub fn new(conf: DBConf, format: bool) -> CommonResult<Self> {
         let conf = conf
@@ -45,6 +48,28 @@ impl RocksInodeStore {
         Ok(Self { db })
     }
 
+    // Open a checkpoint created by create_checkpoint read-only.
+    pub fn open_checkpoint<T: AsRef<str>>(dir: T) -> CommonResult<Self> {
+        let mut conf = DBConf::new(dir.as_ref());
+        conf.data_dir = dir.as_ref().to_string();
+        let conf = conf
+            .add_cf(Self::CF_INODES)
+            .add_cf(Self::CF_EDGES)
+            .add_cf(Self::CF_BLOCK)
+            .add_cf(Self::CF_LOCATION)
+            .add_cf(Self::CF_COMMON);
+        let db = DBEngine::open_read_only(conf)?;
+        Ok(Self { db })
+    }
+
+    pub fn get_child_id(&self, id: i64, name: &str) -> CommonResult<Option<i64>> {
+        let key = RocksUtils::i64_str_to_bytes(id, name);
+        match self.db.get_cf(Self::CF_EDGES, key)? {
+            None => Ok(None),
+            Some(v) => Ok(Some(RocksUtils::i64_from_bytes(&v)?)),
+        }
+    }
+
     pub fn get_child_ids(
         &self,
         id: i64,
@@ -88,6 +113,21 @@ impl RocksInodeStore {
         self.db.scan(Self::CF_INODES)
     }
 
+    // Scan at most `limit` inodes in id order, starting from `start_id`.
+    pub fn scan_inodes(&self, start_id: i64, limit: usize) -> CommonResult<Vec<InodeView>> {
+        let start = RocksUtils::i64_to_bytes(start_id);
+        let end = RocksUtils::i64_to_bytes(i64::MAX);
+        let iter = self.db.range_scan(Self::CF_INODES, start, end)?;
+
+        let mut vec = Vec::with_capacity(limit);
+        for item in iter.take(limit) {
+            let bytes = item?;
+            vec.push(InodeCodec::decode(&bytes.1)?);
+        }
+
+        Ok(vec)
+    }
+
     pub fn edges_iter(&self, id: i64) -> CommonResult<RocksIterator<'_>> {
         self.db
             .prefix_scan(Self::CF_EDGES, RocksUtils::i64_to_bytes(id))
@@ -101,7 +141,7 @@ impl RocksInodeStore {
             None => Ok(None),
 
             Some(v) => {
-                let inode: InodeView = Serde::deserialize(&v)?;
+                let inode = InodeCodec::decode(&v)?;
                 Ok(Some(inode))
             }
         }
@@ -204,6 +244,36 @@ impl RocksInodeStore {
         }
     }
 
+    fn migration_key(job_id: &str) -> Vec<u8> {
+        let mut key = Vec::with_capacity(job_id.len() + 1);
+        key.push(Self::PREFIX_MIGRATION);
+        key.extend_from_slice(job_id.as_bytes());
+        key
+    }
+
+    pub fn add_migration(&self, job: &MigrateJob) -> CommonResult<()> {
+        let key = Self::migration_key(&job.job_id);
+        let value = Serde::serialize(job)?;
+        self.db.put_cf(Self::CF_COMMON, key, value)
+    }
+
+    pub fn remove_migration(&self, job_id: &str) -> CommonResult<()> {
+        let key = Self::migration_key(job_id);
+        self.db.delete_cf(Self::CF_COMMON, key)
+    }
+
+    pub fn get_migrations(&self) -> CommonResult<Vec<MigrateJob>> {
+        let iter = self
+            .db
+            .prefix_scan(Self::CF_COMMON, [Self::PREFIX_MIGRATION])?;
+        let mut vec = vec![];
+        for item in iter {
+            let bytes = item?;
+            vec.push(Serde::deserialize::<MigrateJob>(&bytes.1)?);
+        }
+        Ok(vec)
+    }
+
     pub fn get_rocksdb_memory(&self) -> CommonResult<Vec<(String, u64)>> {
         self.db.get_rocksdb_memory()
     }
@@ -279,7 +349,7 @@ impl<'a> InodeWriteBatch<'a> {
     // Add an inode.
     pub fn write_inode(&mut self, inode: &InodeView) -> CommonResult<()> {
         let key = RocksUtils::i64_to_bytes(inode.id());
-        let value = Serde::serialize(inode)?;
+        let value = InodeCodec::encode(inode)?;
         self.put_cf(RocksInodeStore::CF_INODES, key, value)
     }
 
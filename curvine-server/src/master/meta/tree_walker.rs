@@ -0,0 +1,252 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::master::meta::inode::InodeView::{Dir, File, FileEntry};
+use crate::master::meta::inode::{InodeFile, InodePath, InodeView, PATH_SEPARATOR};
+use crate::master::meta::FsDir;
+use curvine_common::error::FsError;
+use curvine_common::fs::FindFilter;
+use curvine_common::state::{ContentSummary, FileStatus, FileType};
+use curvine_common::FsResult;
+use orpc::{err_box, err_ext};
+
+/// Visits the inodes of a tree walk, a FileEntry is resolved before it is visited.
+pub trait TreeVisitor {
+    // The depth below the path to walk, the path itself is at depth 0.
+    fn max_depth(&self) -> usize {
+        usize::MAX
+    }
+
+    // Returns false to stop the walk, the inode counts as not visited.
+    fn visit(&mut self, fs_dir: &FsDir, inode: &InodeView, path: &str) -> FsResult<bool>;
+}
+
+/// How a walk ended.
+#[derive(Debug, PartialEq)]
+pub enum WalkEnd {
+    // Every inode under the path was visited.
+    Done,
+    // The visitor stopped the walk.
+    Stopped,
+    // The batch was used up, the walk continues after the path, the last inode visited.
+    Paused(String),
+}
+
+/// Walks the tree under a path in depth-first name order, visiting at most batch inodes.
+/// The caller holds the locks for one batch only and resumes a paused walk after the
+/// last inode visited, so the tree may change between the batches.
+pub struct TreeWalker<'a> {
+    fs_dir: &'a FsDir,
+    batch: usize,
+    visited: usize,
+    last: Option<String>,
+}
+
+impl<'a> TreeWalker<'a> {
+    pub fn new(fs_dir: &'a FsDir, batch: usize) -> Self {
+        Self {
+            fs_dir,
+            batch: batch.max(1),
+            visited: 0,
+            last: None,
+        }
+    }
+
+    // start_after is the path of an inode visited before, the inodes up to it are skipped.
+    pub fn walk<V: TreeVisitor>(
+        mut self,
+        inp: &InodePath,
+        start_after: Option<&str>,
+        visitor: &mut V,
+    ) -> FsResult<WalkEnd> {
+        let inode = match inp.get_last_inode() {
+            Some(v) => v,
+            None => return err_ext!(FsError::file_not_found(inp.path())),
+        };
+        let inode = match self.fs_dir.resolve_child(inode.as_ref())? {
+            Some(v) => v,
+            None => return err_ext!(FsError::file_not_found(inp.path())),
+        };
+
+        // The components of start_after below the path.
+        let after: Option<Vec<String>> = match start_after {
+            None => None,
+            Some(v) => {
+                let rest = if v == inp.path() {
+                    Some("")
+                } else if inp.is_root() {
+                    v.strip_prefix(PATH_SEPARATOR)
+                } else {
+                    v.strip_prefix(inp.path())
+                        .and_then(|x| x.strip_prefix(PATH_SEPARATOR))
+                };
+                match rest {
+                    Some(rest) => Some(
+                        rest.split(PATH_SEPARATOR)
+                            .filter(|x| !x.is_empty())
+                            .map(String::from)
+                            .collect(),
+                    ),
+                    None => return err_box!("{} is not under {}", v, inp.path()),
+                }
+            }
+        };
+
+        let end = self.walk_inode(inode.as_ref(), inp.path(), 0, after.as_deref(), visitor)?;
+        Ok(end.unwrap_or(WalkEnd::Done))
+    }
+
+    // Visit the inode and then its children, after is the rest of the components of the
+    // last inode visited, the inodes up to it are skipped.
+    // Returns None if the walk goes on.
+    fn walk_inode<V: TreeVisitor>(
+        &mut self,
+        inode: &InodeView,
+        path: &str,
+        depth: usize,
+        after: Option<&[String]>,
+        visitor: &mut V,
+    ) -> FsResult<Option<WalkEnd>> {
+        if after.is_none() {
+            if let Some(last) = self.last.take() {
+                return Ok(Some(WalkEnd::Paused(last)));
+            }
+            if !visitor.visit(self.fs_dir, inode, path)? {
+                return Ok(Some(WalkEnd::Stopped));
+            }
+            self.visited += 1;
+            if self.visited >= self.batch {
+                self.last = Some(path.to_string());
+            }
+        }
+
+        let dir = match inode {
+            Dir(_, d) if depth < visitor.max_depth() => d,
+            _ => return Ok(None),
+        };
+
+        let child_path = |name: &str| {
+            if path == PATH_SEPARATOR {
+                format!("{}{}", PATH_SEPARATOR, name)
+            } else {
+                format!("{}{}{}", path, PATH_SEPARATOR, name)
+            }
+        };
+
+        // Continue inside the child that holds the last inode visited.
+        let mut start = None;
+        if let Some([name, rest @ ..]) = after {
+            start = Some(name.as_str());
+            if let Some(child) = dir.get_child(name) {
+                if let Some(child) = self.fs_dir.resolve_child(child)? {
+                    let path = child_path(name);
+                    let end =
+                        self.walk_inode(child.as_ref(), &path, depth + 1, Some(rest), visitor)?;
+                    if end.is_some() {
+                        return Ok(end);
+                    }
+                }
+            }
+        }
+
+        for child in dir.children_after(start) {
+            if let Some(child) = self.fs_dir.resolve_child(child)? {
+                let path = child_path(child.name());
+                let end = self.walk_inode(child.as_ref(), &path, depth + 1, None, visitor)?;
+                if end.is_some() {
+                    return Ok(end);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Sums up the files and directories of a walk.
+#[derive(Default)]
+pub struct SummaryVisitor {
+    pub summary: ContentSummary,
+}
+
+impl SummaryVisitor {
+    // The bytes of all the replicas, or of all the units of an erasure-coded file.
+    fn space_consumed(file: &InodeFile) -> i64 {
+        match &file.storage_policy.ec_policy {
+            Some(ec) => file.len * ec.total_units() as i64 / ec.data_units as i64,
+            None => file.len * file.replicas as i64,
+        }
+    }
+}
+
+impl TreeVisitor for SummaryVisitor {
+    fn visit(&mut self, _: &FsDir, inode: &InodeView, _: &str) -> FsResult<bool> {
+        match inode {
+            File(_, file) => self.summary.add_file(
+                file.len,
+                Self::space_consumed(file),
+                file.storage_policy.storage_type,
+            ),
+            Dir(..) => self.summary.add_dir(),
+            FileEntry(..) => (),
+        }
+        Ok(true)
+    }
+}
+
+/// Collects the inodes of a walk that match the filter, a limit of 0 means no limit.
+/// The walk is stopped at the first match beyond the limit.
+pub struct FindVisitor<'a> {
+    filter: &'a FindFilter,
+    limit: usize,
+    pub res: Vec<FileStatus>,
+}
+
+impl<'a> FindVisitor<'a> {
+    pub fn new(filter: &'a FindFilter, limit: usize) -> Self {
+        Self {
+            filter,
+            limit: if limit == 0 { usize::MAX } else { limit },
+            res: vec![],
+        }
+    }
+
+    fn matches(&self, inode: &InodeView) -> bool {
+        match inode {
+            File(name, f) => self
+                .filter
+                .matches(name, f.file_type, Some(f.len), inode.mtime()),
+            Dir(name, _) => self
+                .filter
+                .matches(name, FileType::Dir, None, inode.mtime()),
+            FileEntry(..) => false,
+        }
+    }
+}
+
+impl TreeVisitor for FindVisitor<'_> {
+    fn max_depth(&self) -> usize {
+        self.filter.max_depth()
+    }
+
+    fn visit(&mut self, _: &FsDir, inode: &InodeView, path: &str) -> FsResult<bool> {
+        if self.matches(inode) {
+            if self.res.len() >= self.limit {
+                return Ok(false);
+            }
+            self.res.push(inode.to_file_status(path));
+        }
+        Ok(true)
+    }
+}
@@ -15,12 +15,14 @@
 use crate::master::fs::WorkerManager;
 use crate::master::journal::JournalLoader;
 use crate::master::meta::FsDir;
-use curvine_common::raft::storage::RocksLogStorage;
+use curvine_common::raft::storage::JournalLogStorage;
 use curvine_common::raft::RaftJournal;
 use orpc::sync::ArcRwLock;
 
 pub mod meta;
 
+pub mod backup;
+
 mod job;
 pub use self::job::*;
 
@@ -36,12 +38,23 @@ pub mod fs;
 
 pub mod journal;
 
+pub mod image;
+
 mod master_monitor;
 pub use self::master_monitor::*;
 
+mod follower_reader;
+pub use self::follower_reader::FollowerReader;
+
+mod master_membership;
+pub use self::master_membership::MasterMembership;
+
 mod master_metrics;
 pub use self::master_metrics::*;
 
+mod rate_limiter;
+pub use self::rate_limiter::*;
+
 mod router_handler;
 pub use self::router_handler::*;
 
@@ -53,7 +66,7 @@ pub mod mount;
 pub mod quota;
 pub use self::quota::*;
 
-pub type MetaRaftJournal = RaftJournal<RocksLogStorage, JournalLoader>;
+pub type MetaRaftJournal = RaftJournal<JournalLogStorage, JournalLoader>;
 pub type SyncFsDir = ArcRwLock<FsDir>;
 pub type SyncWorkerManager = ArcRwLock<WorkerManager>;
 pub use mount::MountManager;
@@ -0,0 +1,241 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use lru::LruCache;
+use std::hash::Hash;
+
+/// Adaptive replacement cache (Megiddo & Modha).
+///
+/// Keys seen once live in `t1`, keys seen at least twice live in `t2`.
+/// Evicted keys are remembered in the ghost lists `b1` and `b2`, a later hit in a ghost list
+/// moves the target size `p` of `t1` towards the list that would have kept the key.
+pub struct ArcCache<K>
+where
+    K: Eq + Hash + Clone,
+{
+    t1: LruCache<K, ()>,
+    t2: LruCache<K, ()>,
+    b1: LruCache<K, ()>,
+    b2: LruCache<K, ()>,
+    p: usize,
+    capacity: usize,
+}
+
+impl<K> ArcCache<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            t1: LruCache::unbounded(),
+            t2: LruCache::unbounded(),
+            b1: LruCache::unbounded(),
+            b2: LruCache::unbounded(),
+            p: 0,
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn put(&mut self, key: K) {
+        if self.t1.pop(&key).is_some() || self.t2.contains(&key) {
+            self.t2.put(key, ());
+            return;
+        }
+
+        // Ghost keys may come from evict(), so the cache is not necessarily full on a ghost hit.
+        if self.b1.contains(&key) {
+            let delta = (self.b2.len() / self.b1.len()).max(1);
+            self.p = (self.p + delta).min(self.capacity);
+            if self.len() >= self.capacity {
+                self.replace(false);
+            }
+            self.b1.pop(&key);
+            self.t2.put(key, ());
+            return;
+        }
+
+        if self.b2.contains(&key) {
+            let delta = (self.b1.len() / self.b2.len()).max(1);
+            self.p = self.p.saturating_sub(delta);
+            if self.len() >= self.capacity {
+                self.replace(true);
+            }
+            self.b2.pop(&key);
+            self.t2.put(key, ());
+            return;
+        }
+
+        let l1 = self.t1.len() + self.b1.len();
+        let total = l1 + self.t2.len() + self.b2.len();
+        if l1 >= self.capacity {
+            if self.t1.len() < self.capacity {
+                self.b1.pop_lru();
+                self.replace(false);
+            } else {
+                self.t1.pop_lru();
+            }
+        } else if total >= self.capacity {
+            if total >= 2 * self.capacity {
+                self.b2.pop_lru();
+            }
+            self.replace(false);
+        }
+        self.t1.put(key, ());
+    }
+
+    /// Removes a key evicted by the caller, the key is remembered in a ghost list.
+    pub fn evict(&mut self, key: &K) -> bool {
+        if self.t1.pop(key).is_some() {
+            self.b1.put(key.clone(), ());
+        } else if self.t2.pop(key).is_some() {
+            self.b2.put(key.clone(), ());
+        } else {
+            return false;
+        }
+
+        while self.b1.len() + self.b2.len() > self.capacity {
+            if self.b1.len() >= self.b2.len() {
+                self.b1.pop_lru();
+            } else {
+                self.b2.pop_lru();
+            }
+        }
+        true
+    }
+
+    /// Removes a key without remembering it, e.g. the file was deleted.
+    pub fn remove(&mut self, key: &K) -> bool {
+        let cached = self.t1.pop(key).is_some() || self.t2.pop(key).is_some();
+        let ghost = self.b1.pop(key).is_some() || self.b2.pop(key).is_some();
+        cached || ghost
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.t1.contains(key) || self.t2.contains(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.t1.len() + self.t2.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn target_recent(&self) -> usize {
+        self.p
+    }
+
+    /// Iterates the cached keys in the order ARC would replace them:
+    /// the list over its target size first, each list from the least recently used key.
+    pub fn iter(&self) -> impl Iterator<Item = &K> {
+        let t1 = self.t1.iter().rev().map(|(k, _)| k);
+        let t2 = self.t2.iter().rev().map(|(k, _)| k);
+        if !self.t1.is_empty() && self.t1.len() > self.p {
+            Box::new(t1.chain(t2)) as Box<dyn Iterator<Item = &K>>
+        } else {
+            Box::new(t2.chain(t1))
+        }
+    }
+
+    // Moves the least recently used key of t1 or t2 to its ghost list.
+    fn replace(&mut self, in_b2: bool) {
+        let t1_len = self.t1.len();
+        if t1_len > 0 && (t1_len > self.p || (in_b2 && t1_len == self.p)) {
+            if let Some((k, _)) = self.t1.pop_lru() {
+                self.b1.put(k, ());
+            }
+        } else if let Some((k, _)) = self.t2.pop_lru() {
+            self.b2.put(k, ());
+        } else if let Some((k, _)) = self.t1.pop_lru() {
+            self.b1.put(k, ());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_put_and_promote() {
+        let mut cache = ArcCache::new(4);
+        cache.put(1);
+        cache.put(2);
+        cache.put(1);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains_key(&1));
+        assert!(cache.contains_key(&2));
+
+        // 2 was seen once, it is replaced before 1.
+        let items: Vec<i32> = cache.iter().copied().collect();
+        assert_eq!(items, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut cache = ArcCache::new(3);
+        for i in 0..10 {
+            cache.put(i);
+        }
+        assert_eq!(cache.len(), 3);
+        assert!(cache.contains_key(&9));
+        assert!(!cache.contains_key(&0));
+    }
+
+    #[test]
+    fn test_scan_resistance() {
+        let mut cache = ArcCache::new(4);
+        cache.put(1);
+        cache.put(1);
+        cache.put(2);
+        cache.put(2);
+
+        // A scan of keys seen once does not flush the frequently used keys.
+        for i in 10..20 {
+            cache.put(i);
+        }
+        assert!(cache.contains_key(&1));
+        assert!(cache.contains_key(&2));
+    }
+
+    #[test]
+    fn test_ghost_hit_adapts() {
+        let mut cache = ArcCache::new(4);
+        cache.put(1);
+        cache.put(2);
+        assert!(cache.evict(&1));
+        assert!(!cache.contains_key(&1));
+        assert_eq!(cache.target_recent(), 0);
+
+        // A hit in b1 grows the target size of t1.
+        cache.put(1);
+        assert!(cache.contains_key(&1));
+        assert_eq!(cache.target_recent(), 1);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut cache = ArcCache::new(4);
+        cache.put(1);
+        cache.put(2);
+        cache.evict(&2);
+
+        assert!(cache.remove(&1));
+        assert!(cache.remove(&2));
+        assert!(!cache.remove(&3));
+        assert!(cache.is_empty());
+    }
+}
@@ -12,14 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
 use std::num::NonZeroUsize;
 use std::sync::Mutex;
 
-use crate::master::quota::eviction::lfu;
-use crate::master::quota::eviction::types::EvictionConf;
+use crate::master::quota::eviction::types::{EvictionConf, FileAccess};
+use crate::master::quota::eviction::{arc, lfu};
+use orpc::common::LocalTime;
 
 pub trait Evictor: Send + Sync {
     fn on_access(&self, inode_id: i64);
+
+    // Policies that weigh the file size or the ufs copy override this.
+    fn on_file_access(&self, access: FileAccess) {
+        self.on_access(access.inode_id)
+    }
+
     fn select_victims(&self, limit: usize) -> Vec<i64>;
     fn remove_victims(&self, inode_ids: &[i64]);
     fn cache_size(&self) -> usize;
@@ -151,3 +160,311 @@ impl Evictor for LFUEvictor {
         }
     }
 }
+
+pub struct ARCEvictor {
+    caches: Mutex<arc::ArcCache<i64>>,
+    conf: EvictionConf,
+}
+
+impl ARCEvictor {
+    pub fn new(conf: EvictionConf) -> Self {
+        let capacity = NonZeroUsize::new(conf.capacity)
+            .unwrap_or_else(|| NonZeroUsize::new(5_000_000).unwrap());
+
+        Self {
+            caches: Mutex::new(arc::ArcCache::new(capacity.get())),
+            conf,
+        }
+    }
+
+    fn peek_victims(&self, limit: usize) -> Vec<i64> {
+        if let Ok(caches) = self.caches.lock() {
+            caches.iter().take(limit).copied().collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn remove_victims(&self, inode_ids: &[i64]) {
+        if let Ok(mut caches) = self.caches.lock() {
+            for inode_id in inode_ids {
+                caches.evict(inode_id);
+            }
+        }
+    }
+}
+
+impl Evictor for ARCEvictor {
+    fn on_access(&self, inode_id: i64) {
+        if !self.conf.enable_quota_eviction {
+            return;
+        }
+
+        if let Ok(mut caches) = self.caches.lock() {
+            caches.put(inode_id);
+        }
+    }
+
+    fn select_victims(&self, limit: usize) -> Vec<i64> {
+        self.peek_victims(limit)
+    }
+
+    fn remove_victims(&self, inode_ids: &[i64]) {
+        self.remove_victims(inode_ids)
+    }
+
+    fn cache_size(&self) -> usize {
+        if let Ok(caches) = self.caches.lock() {
+            caches.len()
+        } else {
+            0
+        }
+    }
+}
+
+// Files with the same size class and ufs copy share a weight, so their scores
+// only differ by the idle time and each class can be kept in access order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct CostClass {
+    size_class: u32,
+    ufs_backed: bool,
+}
+
+impl CostClass {
+    fn of(access: &FileAccess) -> Self {
+        let size_mb = access.len.max(0) as f64 / (1024.0 * 1024.0);
+        Self {
+            size_class: (1.0 + size_mb).log2() as u32,
+            ufs_backed: access.ufs_backed,
+        }
+    }
+
+    fn weight(&self) -> f64 {
+        let weight = self.size_class as f64 + 1.0;
+        if self.ufs_backed {
+            weight
+        } else {
+            weight / CostEvictor::NO_UFS_PENALTY
+        }
+    }
+}
+
+struct CostCandidate {
+    score: f64,
+    inode_id: i64,
+    class: usize,
+}
+
+impl PartialEq for CostCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for CostCandidate {}
+
+impl PartialOrd for CostCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The heap top is the candidate most worth evicting.
+impl Ord for CostCandidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| other.inode_id.cmp(&self.inode_id))
+    }
+}
+
+#[derive(Default)]
+struct CostState {
+    latest_ms: i64,
+    files: HashMap<i64, CostClass>,
+    classes: BTreeMap<CostClass, lru::LruCache<i64, FileAccess>>,
+}
+
+impl CostState {
+    fn put(&mut self, access: FileAccess) {
+        self.latest_ms = self.latest_ms.max(access.access_ms);
+
+        let class = CostClass::of(&access);
+        if let Some(old) = self.files.insert(access.inode_id, class) {
+            if old != class {
+                self.pop_class(old, access.inode_id);
+            }
+        }
+        self.classes
+            .entry(class)
+            .or_insert_with(lru::LruCache::unbounded)
+            .put(access.inode_id, access);
+    }
+
+    fn pop(&mut self, inode_id: i64) {
+        if let Some(class) = self.files.remove(&inode_id) {
+            self.pop_class(class, inode_id);
+        }
+    }
+
+    fn pop_class(&mut self, class: CostClass, inode_id: i64) {
+        if let Some(files) = self.classes.get_mut(&class) {
+            files.pop(&inode_id);
+            if files.is_empty() {
+                self.classes.remove(&class);
+            }
+        }
+    }
+
+    // Merges the classes from their least recently accessed file, so only
+    // `limit` files and the head of each class are scored.
+    fn peek_victims(&self, limit: usize) -> Vec<i64> {
+        let mut iters: Vec<_> = self
+            .classes
+            .iter()
+            .map(|(class, files)| (class.weight(), files.iter().rev()))
+            .collect();
+
+        let mut heap = BinaryHeap::with_capacity(iters.len());
+        for (class, (weight, iter)) in iters.iter_mut().enumerate() {
+            if let Some(candidate) = self.next_candidate(class, *weight, iter) {
+                heap.push(candidate);
+            }
+        }
+
+        let mut victims = Vec::with_capacity(limit);
+        while victims.len() < limit {
+            let Some(top) = heap.pop() else {
+                break;
+            };
+            victims.push(top.inode_id);
+
+            let (weight, iter) = &mut iters[top.class];
+            if let Some(candidate) = self.next_candidate(top.class, *weight, iter) {
+                heap.push(candidate);
+            }
+        }
+        victims
+    }
+
+    fn next_candidate<'a>(
+        &self,
+        class: usize,
+        weight: f64,
+        iter: &mut impl Iterator<Item = (&'a i64, &'a FileAccess)>,
+    ) -> Option<CostCandidate> {
+        iter.next().map(|(&inode_id, access)| CostCandidate {
+            score: CostEvictor::idle_secs(access, self.latest_ms) * weight,
+            inode_id,
+            class,
+        })
+    }
+}
+
+/// Evicts the files that free the most space for the least expected re-fetch cost.
+///
+/// The score of a file grows with its idle time and size class, and is divided by
+/// `NO_UFS_PENALTY` when the file has no ufs copy, since freeing it loses the only copy.
+/// Idle time is measured against the latest access seen, which keeps replayed traces deterministic.
+pub struct CostEvictor {
+    state: Mutex<CostState>,
+    capacity: usize,
+    conf: EvictionConf,
+}
+
+impl CostEvictor {
+    pub const NO_UFS_PENALTY: f64 = 16.0;
+
+    pub fn new(conf: EvictionConf) -> Self {
+        let capacity = NonZeroUsize::new(conf.capacity)
+            .unwrap_or_else(|| NonZeroUsize::new(5_000_000).unwrap());
+
+        Self {
+            state: Mutex::new(CostState::default()),
+            capacity: capacity.get(),
+            conf,
+        }
+    }
+
+    fn idle_secs(access: &FileAccess, now_ms: i64) -> f64 {
+        (now_ms - access.access_ms).max(0) as f64 / 1000.0 + 1.0
+    }
+
+    pub fn score(access: &FileAccess, now_ms: i64) -> f64 {
+        Self::idle_secs(access, now_ms) * CostClass::of(access).weight()
+    }
+
+    fn record(&self, access: FileAccess) {
+        if let Ok(mut state) = self.state.lock() {
+            state.put(access);
+            // Like the lru cache, forget the first victim when the capacity is exceeded.
+            if state.files.len() > self.capacity {
+                if let Some(inode_id) = state.peek_victims(1).pop() {
+                    state.pop(inode_id);
+                }
+            }
+        }
+    }
+
+    fn peek_victims(&self, limit: usize) -> Vec<i64> {
+        if let Ok(state) = self.state.lock() {
+            state.peek_victims(limit)
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn remove_victims(&self, inode_ids: &[i64]) {
+        if let Ok(mut state) = self.state.lock() {
+            for &inode_id in inode_ids {
+                state.pop(inode_id);
+            }
+        }
+    }
+}
+
+impl Evictor for CostEvictor {
+    fn on_access(&self, inode_id: i64) {
+        if !self.conf.enable_quota_eviction {
+            return;
+        }
+
+        let now_ms = LocalTime::mills() as i64;
+        let access = match self.state.lock() {
+            Ok(state) => match state
+                .files
+                .get(&inode_id)
+                .and_then(|class| state.classes.get(class)?.peek(&inode_id))
+            {
+                Some(v) => FileAccess::new(inode_id, v.len, v.ufs_backed, now_ms),
+                None => FileAccess::new(inode_id, 0, false, now_ms),
+            },
+            Err(_) => return,
+        };
+        self.record(access);
+    }
+
+    fn on_file_access(&self, access: FileAccess) {
+        if !self.conf.enable_quota_eviction {
+            return;
+        }
+
+        self.record(access);
+    }
+
+    fn select_victims(&self, limit: usize) -> Vec<i64> {
+        self.peek_victims(limit)
+    }
+
+    fn remove_victims(&self, inode_ids: &[i64]) {
+        self.remove_victims(inode_ids)
+    }
+
+    fn cache_size(&self) -> usize {
+        if let Ok(state) = self.state.lock() {
+            state.files.len()
+        } else {
+            0
+        }
+    }
+}
@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod arc;
 pub mod evictor;
 pub mod lfu;
 pub mod types;
 
-pub use types::{EvictPlan, EvictionConf, EvictionMode, EvictionPolicy};
+pub use types::{EvictPlan, EvictionConf, EvictionMode, EvictionPolicy, FileAccess};
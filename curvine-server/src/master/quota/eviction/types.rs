@@ -12,14 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::master::meta::inode::InodeFile;
 use curvine_common::conf::ClusterConf;
+use orpc::common::LocalTime;
+use orpc::{err_box, CommonResult};
 use std::fmt;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum EvictionPolicy {
     Lru,
     Lfu,
-    //to-do: Arc,
+    Arc,
+    Cost,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -41,7 +45,7 @@ pub struct EvictionConf {
 }
 
 impl EvictionConf {
-    pub fn from_conf(conf: &ClusterConf) -> Self {
+    pub fn from_conf(conf: &ClusterConf) -> CommonResult<Self> {
         let master_conf = &conf.master;
 
         // Parse eviction mode from string
@@ -54,11 +58,17 @@ impl EvictionConf {
         let policy = match master_conf.quota_eviction_policy.to_lowercase().as_str() {
             "lru" => EvictionPolicy::Lru,
             "lfu" => EvictionPolicy::Lfu,
-            // "arc" => EvictionPolicy::Arc,
-            _ => EvictionPolicy::Lru,
+            "arc" => EvictionPolicy::Arc,
+            "cost" => EvictionPolicy::Cost,
+            _ => {
+                return err_box!(
+                    "Unsupported quota eviction policy {}",
+                    master_conf.quota_eviction_policy
+                )
+            }
         };
 
-        Self {
+        Ok(Self {
             enable_quota_eviction: master_conf.enable_quota_eviction,
             eviction_mode,
             policy,
@@ -67,8 +77,38 @@ impl EvictionConf {
             candidate_scan_page: master_conf.quota_eviction_scan_page as usize,
             dry_run: master_conf.quota_eviction_dry_run,
             capacity: master_conf.quota_eviction_capacity,
+        })
+    }
+}
+
+/// A file access observed by the evictor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FileAccess {
+    pub inode_id: i64,
+    pub len: i64,
+    // Whether the file has a copy in the ufs, which makes it cheap to evict.
+    pub ufs_backed: bool,
+    pub access_ms: i64,
+}
+
+impl FileAccess {
+    pub fn new(inode_id: i64, len: i64, ufs_backed: bool, access_ms: i64) -> Self {
+        Self {
+            inode_id,
+            len,
+            ufs_backed,
+            access_ms,
         }
     }
+
+    pub fn from_file(file: &InodeFile) -> Self {
+        Self::new(
+            file.id,
+            file.len,
+            file.storage_policy.ufs_mtime > 0,
+            LocalTime::mills() as i64,
+        )
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -0,0 +1,282 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_common::conf::MasterConf;
+use curvine_common::fs::RpcCode;
+use orpc::common::FastHashMap;
+use orpc::CommonResult;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Flows without requests for this long are no longer active, their share goes to the others.
+const FLOW_IDLE_SECS: f64 = 10.0;
+
+// The class of a metadata request, each class is limited separately.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RateClass {
+    Read = 0,
+    Write = 1,
+    List = 2,
+}
+
+impl RateClass {
+    // Requests of files being written (add block, complete file) are not limited, so that an open
+    // stream is never broken; neither are worker, report, membership and admin requests.
+    pub fn from_code(code: RpcCode) -> Option<Self> {
+        match code {
+            RpcCode::FileStatus
+            | RpcCode::Exists
+            | RpcCode::GetBlockLocations
+            | RpcCode::GetDirPolicy
+            | RpcCode::GetLock
+            | RpcCode::GetMountTable
+            | RpcCode::GetMountInfo
+            | RpcCode::GetJobStatus => Some(RateClass::Read),
+
+            RpcCode::ListStatus | RpcCode::GetContentSummary | RpcCode::Find => {
+                Some(RateClass::List)
+            }
+
+            RpcCode::Mkdir
+            | RpcCode::CreateFile
+            | RpcCode::OpenFile
+            | RpcCode::CreateFilesBatch
+            | RpcCode::Delete
+            | RpcCode::Rename
+            | RpcCode::SetAttr
+            | RpcCode::SetStoragePolicy
+            | RpcCode::Symlink
+            | RpcCode::Link
+            | RpcCode::ResizeFile
+            | RpcCode::AssignWorker
+            | RpcCode::SetLock
+            | RpcCode::Mount
+            | RpcCode::UnMount
+            | RpcCode::SubmitJob
+            | RpcCode::CancelJob => Some(RateClass::Write),
+
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateClass::Read => "read",
+            RateClass::Write => "write",
+            RateClass::List => "list",
+        }
+    }
+}
+
+// All times are seconds since the limiter was created.
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last: f64,
+}
+
+impl TokenBucket {
+    fn new(rate: u64, burst: f64) -> Self {
+        let rate = rate as f64;
+        let capacity = (rate * burst).max(1.0);
+        Self {
+            rate,
+            capacity,
+            tokens: capacity,
+            last: 0.0,
+        }
+    }
+
+    // Take a token and return how long to wait until it is available, at most max_wait;
+    // a request that would wait longer gets no token and the time to back off.
+    // The tokens of waiting requests are taken ahead, so the balance can be negative.
+    fn acquire(&mut self, now: f64, max_wait: f64) -> Result<f64, f64> {
+        if now > self.last {
+            self.tokens = (self.tokens + (now - self.last) * self.rate).min(self.capacity);
+            self.last = now;
+        }
+
+        let wait = ((1.0 - self.tokens) / self.rate).max(0.0);
+        if wait <= max_wait {
+            self.tokens -= 1.0;
+            Ok(wait)
+        } else {
+            Err(wait - max_wait)
+        }
+    }
+}
+
+struct Flow {
+    clock: f64,
+    last_seen: f64,
+}
+
+// Virtual clock fair queueing, the rate is divided equally among the active flows.
+// A request is stamped with the virtual clock of its flow, which advances 1/share per request
+// and never lags behind the real time, so an idle flow does not save credits and a busy flow
+// can not take the share of the others.
+// A request stamped within the burst window is served now, a later one waits for its stamp if
+// that is at most max_wait away, otherwise it is rejected and told when its turn comes instead
+// of holding the connection until then.
+struct FairQueue {
+    rate: f64,
+    burst: f64,
+    max_wait: f64,
+    flows: FastHashMap<String, Flow>,
+    last_clean: f64,
+}
+
+impl FairQueue {
+    fn new(rate: u64, burst: f64, max_wait: f64) -> Self {
+        Self {
+            rate: rate as f64,
+            burst,
+            max_wait,
+            flows: FastHashMap::new(),
+            last_clean: 0.0,
+        }
+    }
+
+    fn acquire(&mut self, client: &str, now: f64) -> Result<f64, f64> {
+        if now - self.last_clean >= FLOW_IDLE_SECS {
+            self.flows.retain(|_, f| now - f.last_seen < FLOW_IDLE_SECS);
+            self.last_clean = now;
+        }
+
+        if !self.flows.contains_key(client) {
+            let new_flow = Flow {
+                clock: now,
+                last_seen: now,
+            };
+            self.flows.insert(client.to_string(), new_flow);
+        }
+
+        let share = self.rate / self.flows.len() as f64;
+        let flow = self.flows.get_mut(client).unwrap();
+        flow.last_seen = now;
+
+        let start = flow.clock.max(now);
+        let wait = (start - now - self.burst).max(0.0);
+        if wait <= self.max_wait {
+            flow.clock = start + 1.0 / share;
+            Ok(wait)
+        } else {
+            Err(wait - self.max_wait)
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.flows.len()
+    }
+}
+
+// Metadata rate limiter of the master, keyed by the user of the client, or its host if the
+// client sends no user.
+// Clients configured in rate_limit_clients have their own token buckets per request class,
+// the requests of all other clients go through a fair queue per class, which shares the
+// class rate among them.
+// The limiter never blocks, it returns how long a request waits for its turn, or how long
+// the client should back off if the wait would exceed rate_limit_queue_wait.
+pub struct MasterRateLimiter {
+    start: Instant,
+    rates: [u64; 3],
+    max_wait: f64,
+    buckets: FastHashMap<String, [Option<Mutex<TokenBucket>>; 3]>,
+    queues: [Mutex<FairQueue>; 3],
+}
+
+impl MasterRateLimiter {
+    pub fn new(conf: &MasterConf) -> CommonResult<Self> {
+        let burst = conf.rate_limit_burst_ms() as f64 / 1000.0;
+        let max_wait = conf.rate_limit_queue_wait_ms() as f64 / 1000.0;
+        let rates = [
+            conf.rate_limit_read,
+            conf.rate_limit_write,
+            conf.rate_limit_list,
+        ];
+
+        let mut buckets = FastHashMap::new();
+        for (client, limits) in conf.rate_limit_client_limits()? {
+            let client_buckets =
+                limits.map(|rate| (rate > 0).then(|| Mutex::new(TokenBucket::new(rate, burst))));
+            buckets.insert(client, client_buckets);
+        }
+
+        Ok(Self {
+            start: Instant::now(),
+            rates,
+            max_wait,
+            buckets,
+            queues: rates.map(|rate| Mutex::new(FairQueue::new(rate, burst, max_wait))),
+        })
+    }
+
+    pub fn with_conf(conf: &MasterConf) -> CommonResult<Option<Self>> {
+        if conf.rate_limit_enabled {
+            Ok(Some(Self::new(conf)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Admit a request of the client, return how long it waits for its turn,
+    // or how long the client should back off in ms.
+    pub fn acquire(
+        &self,
+        host: &str,
+        user: Option<&str>,
+        class: RateClass,
+    ) -> Result<Duration, u64> {
+        self.acquire_at(host, user, class, self.start.elapsed())
+    }
+
+    // now is the time elapsed since the limiter was created.
+    pub fn acquire_at(
+        &self,
+        host: &str,
+        user: Option<&str>,
+        class: RateClass,
+        now: Duration,
+    ) -> Result<Duration, u64> {
+        let now = now.as_secs_f64();
+        let client_buckets = user
+            .and_then(|u| self.buckets.get(u))
+            .or_else(|| self.buckets.get(host));
+
+        let res = match client_buckets {
+            Some(buckets) => match &buckets[class as usize] {
+                Some(bucket) => bucket.lock().unwrap().acquire(now, self.max_wait),
+                None => Ok(0.0),
+            },
+
+            None if self.rates[class as usize] > 0 => self.queues[class as usize]
+                .lock()
+                .unwrap()
+                .acquire(user.unwrap_or(host), now),
+
+            None => Ok(0.0),
+        };
+
+        match res {
+            Ok(wait) => Ok(Duration::from_secs_f64(wait)),
+            Err(secs) => Err(((secs * 1000.0).ceil() as u64).max(1)),
+        }
+    }
+
+    // The number of clients sharing the rate of the class.
+    pub fn active_clients(&self, class: RateClass) -> usize {
+        self.queues[class as usize].lock().unwrap().len()
+    }
+}
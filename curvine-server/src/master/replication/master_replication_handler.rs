@@ -16,7 +16,10 @@ use crate::master::replication::master_replication_manager::MasterReplicationMan
 use crate::master::RpcContext;
 use curvine_common::error::FsError;
 use curvine_common::fs::RpcCode;
-use curvine_common::proto::{ReportBlockReplicationRequest, ReportBlockReplicationResponse};
+use curvine_common::proto::{
+    ReportBlockReplicationRequest, ReportBlockReplicationResponse,
+    ReportUnderReplicatedBlocksRequest, ReportUnderReplicatedBlocksResponse,
+};
 use curvine_common::FsResult;
 use log::warn;
 use orpc::error::ErrorImpl;
@@ -49,6 +52,25 @@ impl MasterReplicationHandler {
         };
         ctx.response(response)
     }
+
+    // A client committed blocks with fewer replicas than requested, e.g. after pipeline recovery.
+    pub fn report_under_replicated_blocks(&self, ctx: &mut RpcContext<'_>) -> FsResult<Message> {
+        let req: ReportUnderReplicatedBlocksRequest = ctx.parse_header()?;
+        let response = match self
+            .manager
+            .report_under_replicated_blocks(None, req.block_ids)
+        {
+            Ok(_) => ReportUnderReplicatedBlocksResponse {
+                success: true,
+                message: None,
+            },
+            Err(e) => ReportUnderReplicatedBlocksResponse {
+                success: false,
+                message: e.to_string().into(),
+            },
+        };
+        ctx.response(response)
+    }
 }
 
 impl MessageHandler for MasterReplicationHandler {
@@ -63,6 +85,7 @@ impl MessageHandler for MasterReplicationHandler {
 
         let response = match code {
             RpcCode::ReportBlockReplicationResult => self.report_replication_result(ctx),
+            RpcCode::ReportUnderReplicatedBlocks => self.report_under_replicated_blocks(ctx),
             _ => Err(FsError::Common(ErrorImpl::with_source(
                 format!("Unsupported operation: {:?}", code).into(),
             ))),
@@ -13,13 +13,15 @@
 // limitations under the License.
 
 use crate::master::fs::MasterFilesystem;
+use crate::master::meta::BlockMeta;
 use crate::master::{Master, MasterMetrics, SyncWorkerManager};
 use curvine_common::conf::ClusterConf;
 use curvine_common::fs::RpcCode;
 use curvine_common::proto::{
-    ReportBlockReplicationRequest, SubmitBlockReplicationRequest, SubmitBlockReplicationResponse,
+    EcReconstructProto, ReportBlockReplicationRequest, SubmitBlockReplicationRequest,
+    SubmitBlockReplicationResponse,
 };
-use curvine_common::state::{BlockLocation, WorkerAddress};
+use curvine_common::state::{BlockLocation, EcPolicy, StorageType, WorkerAddress};
 use curvine_common::utils::ProtoUtils;
 use log::{error, info, warn};
 use orpc::client::ClientFactory;
@@ -43,7 +45,11 @@ pub struct MasterReplicationManager {
     replication_semaphore: Arc<Semaphore>,
 
     staging_queue_sender: Arc<Sender<BlockId>>,
+    // The number of times each block is waiting in the staging queue.
+    staged_blocks: Arc<FastDashMap<BlockId, usize>>,
     inflight_blocks: Arc<FastDashMap<BlockId, InflightReplicationJob>>,
+    // Blocks copied to another tier by storage migration, with the target storage type.
+    target_types: Arc<FastDashMap<BlockId, StorageType>>,
 
     worker_client_factory: Arc<ClientFactory>,
 
@@ -56,6 +62,8 @@ struct InflightReplicationJob {
     _block_id: BlockId,
     permit: OwnedSemaphorePermit,
     target_worker: WorkerAddress,
+    // The unit index being rebuilt for an erasure-coded block.
+    unit_index: Option<u32>,
 }
 
 impl MasterReplicationManager {
@@ -74,7 +82,9 @@ impl MasterReplicationManager {
             worker_manager: worker_manager.clone(),
             replication_semaphore: Arc::new(semaphore),
             staging_queue_sender: Arc::new(send),
+            staged_blocks: Default::default(),
             inflight_blocks: Default::default(),
+            target_types: Default::default(),
             worker_client_factory: Arc::new(Default::default()),
             replication_enabled: conf.master.block_replication_enabled,
             metrics: Master::get_metrics(),
@@ -101,10 +111,27 @@ impl MasterReplicationManager {
                 if let Err(e) = manager.replicate_block(block_id, permit).await {
                     error!("Failed to replicate block: {}. err: {}", block_id, e);
                 }
+                manager.unstage(block_id);
             }
         });
     }
 
+    fn unstage(&self, block_id: BlockId) {
+        self.staged_blocks.remove_if_mut(&block_id, |_, count| {
+            *count -= 1;
+            *count == 0
+        });
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.replication_enabled
+    }
+
+    // Whether the block is waiting in the staging queue or being replicated.
+    pub fn is_replicating(&self, block_id: BlockId) -> bool {
+        self.staged_blocks.contains_key(&block_id) || self.inflight_blocks.contains_key(&block_id)
+    }
+
     fn get_next_worker(&self, worker_id: WorkerId) -> CommonResult<WorkerAddress> {
         let worker_manager = self.worker_manager.read();
         match worker_manager.get_worker(worker_id) {
@@ -125,6 +152,30 @@ impl MasterReplicationManager {
         Ok(worker_addr)
     }
 
+    // Choose a worker with a dir of the storage type, the one with the most space available wins.
+    fn assign_with_type(
+        &self,
+        exclusive_worker_ids: Vec<WorkerId>,
+        storage_type: StorageType,
+    ) -> CommonResult<WorkerAddress> {
+        let worker_manager = self.worker_manager.read();
+        let worker = worker_manager
+            .worker_map
+            .workers()
+            .values()
+            .filter(|x| x.is_live() && !exclusive_worker_ids.contains(&x.worker_id()))
+            .filter_map(|x| {
+                let available = worker_manager.storage_available(x.worker_id(), storage_type)?;
+                Some((available, x))
+            })
+            .max_by_key(|x| x.0);
+
+        match worker {
+            Some((_, worker)) => Ok(worker.address.clone()),
+            None => err_box!("No available worker with storage type {:?}", storage_type),
+        }
+    }
+
     async fn replicate_block(
         &self,
         block_id: BlockId,
@@ -132,11 +183,29 @@ impl MasterReplicationManager {
     ) -> CommonResult<()> {
         // todo: check whether the block_id replicas legal
 
-        let locations = {
+        let (locations, striped) = {
             let fs_dir = self.fs.fs_dir.read();
-            fs_dir.get_block_locations(block_id)?
+            (
+                fs_dir.get_block_locations(block_id)?,
+                fs_dir.get_striped_block(block_id)?,
+            )
         };
 
+        // Erasure-coded blocks can not be copied, the lost unit is decoded from the others.
+        if let Some((policy, storage_type, block_size, meta)) = striped {
+            return self
+                .reconstruct_block(
+                    block_id,
+                    permit,
+                    policy,
+                    storage_type,
+                    block_size,
+                    meta,
+                    locations,
+                )
+                .await;
+        }
+
         // step1: find out the available worker to replicate blocks
         // todo: use pluggable policy to find out the best worker to do replication
         let source_worker_id =
@@ -144,13 +213,134 @@ impl MasterReplicationManager {
         let source_worker_addr = self.get_next_worker(source_worker_id)?;
 
         // step2: choose the target worker
-        let target_worker_addr = self.assign(locations.iter().map(|x| x.worker_id).collect())?;
+        let exclusive = locations.iter().map(|x| x.worker_id).collect();
+        let target_type = self.target_types.remove(&block_id).map(|x| x.1);
+        let target_worker_addr = match target_type {
+            Some(storage_type) => self.assign_with_type(exclusive, storage_type)?,
+            None => self.assign(exclusive)?,
+        };
         info!(
             "block_id: {}. locations: {:?}, target: {}",
             block_id, &locations, &target_worker_addr
         );
 
         // step3: call the corresponding worker to do replication
+        let request = SubmitBlockReplicationRequest {
+            block_id,
+            target_worker_info: ProtoUtils::worker_address_to_pb(&target_worker_addr),
+            ec_reconstruct: None,
+            storage_type: target_type.map(|x| x.into()),
+        };
+        self.submit_job(&source_worker_addr, request).await?;
+
+        // step4: add into the replicating queue
+        self.inflight_blocks.insert(
+            block_id,
+            InflightReplicationJob {
+                _block_id: block_id,
+                permit,
+                target_worker: target_worker_addr,
+                unit_index: None,
+            },
+        );
+        self.metrics.replication_staging_number.dec();
+        self.metrics.replication_inflight_number.inc();
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn reconstruct_block(
+        &self,
+        block_id: BlockId,
+        permit: OwnedSemaphorePermit,
+        policy: EcPolicy,
+        storage_type: StorageType,
+        block_size: i64,
+        meta: BlockMeta,
+        locations: Vec<BlockLocation>,
+    ) -> CommonResult<()> {
+        let mut sources = vec![];
+        let mut source_indices = vec![];
+        let mut missing = vec![];
+        {
+            let worker_manager = self.worker_manager.read();
+            for (index, worker_id) in meta.units.iter().enumerate() {
+                let live = locations.iter().any(|x| x.worker_id == *worker_id);
+                match worker_manager.get_worker(*worker_id) {
+                    Some(worker) if live => {
+                        sources.push(worker.address.clone());
+                        source_indices.push(index as u32);
+                    }
+                    _ => missing.push(index as u32),
+                }
+            }
+        }
+
+        let target_index = match missing.first() {
+            Some(v) => *v,
+            None => {
+                self.metrics.replication_staging_number.dec();
+                return Ok(());
+            }
+        };
+        if sources.len() < policy.data_units as usize {
+            self.metrics.replication_staging_number.dec();
+            return err_box!(
+                "Block {} can not be reconstructed, only {} units available, {} requires at least {}",
+                block_id,
+                sources.len(),
+                policy,
+                policy.data_units
+            );
+        }
+
+        // The target must not hold any other unit of the block.
+        let target_worker_addr = self.assign(meta.units.clone())?;
+        info!(
+            "Reconstruct block_id: {}, unit {} of {}, sources: {:?}, target: {}",
+            block_id, target_index, policy, &source_indices, &target_worker_addr
+        );
+
+        let request = SubmitBlockReplicationRequest {
+            block_id,
+            target_worker_info: ProtoUtils::worker_address_to_pb(&target_worker_addr),
+            ec_reconstruct: Some(EcReconstructProto {
+                policy: ProtoUtils::ec_policy_to_pb(policy),
+                block_len: meta.len,
+                storage_type: storage_type.into(),
+                target_index,
+                sources: sources
+                    .iter()
+                    .map(ProtoUtils::worker_address_to_pb)
+                    .collect(),
+                source_indices,
+                block_size,
+            }),
+            storage_type: None,
+        };
+        self.submit_job(&sources[0], request).await?;
+
+        self.inflight_blocks.insert(
+            block_id,
+            InflightReplicationJob {
+                _block_id: block_id,
+                permit,
+                target_worker: target_worker_addr,
+                unit_index: Some(target_index),
+            },
+        );
+        self.metrics.replication_staging_number.dec();
+        self.metrics.replication_inflight_number.inc();
+
+        Ok(())
+    }
+
+    async fn submit_job(
+        &self,
+        source_worker_addr: &WorkerAddress,
+        request: SubmitBlockReplicationRequest,
+    ) -> CommonResult<()> {
         let source_worker_addr = InetAddr::new(
             &source_worker_addr.ip_addr,
             source_worker_addr.rpc_port as u16,
@@ -160,10 +350,6 @@ impl MasterReplicationManager {
             .create_raw(&source_worker_addr)
             .await?;
 
-        let request = SubmitBlockReplicationRequest {
-            block_id,
-            target_worker_info: ProtoUtils::worker_address_to_pb(&target_worker_addr),
-        };
         let msg = Builder::new_rpc(RpcCode::SubmitBlockReplicationJob)
             .request(RequestStatus::Rpc)
             .proto_header(request)
@@ -188,24 +374,14 @@ impl MasterReplicationManager {
             }
         }
 
-        // step4: add into the replicating queue
-        self.inflight_blocks.insert(
-            block_id,
-            InflightReplicationJob {
-                _block_id: block_id,
-                permit,
-                target_worker: target_worker_addr,
-            },
-        );
-        self.metrics.replication_staging_number.dec();
-        self.metrics.replication_inflight_number.inc();
-
         Ok(())
     }
 
+    // lost_worker is the worker whose copies are gone, None if the blocks were found by a
+    // client, a scan or a recheck.
     pub fn report_under_replicated_blocks(
         &self,
-        _worker_id: WorkerId,
+        lost_worker: Option<WorkerId>,
         block_ids: Vec<i64>,
     ) -> CommonResult<()> {
         if !self.replication_enabled {
@@ -216,13 +392,22 @@ impl MasterReplicationManager {
         let metrics = self.metrics;
 
         for block_id in &block_ids {
-            info!("Accepting block {} replication job", block_id);
+            match lost_worker {
+                Some(worker_id) => info!(
+                    "Accepting block {} replication job, lost on worker {}",
+                    block_id, worker_id
+                ),
+                None => info!("Accepting block {} replication job", block_id),
+            }
 
+            // Mark it before sending, the consumer may take it out immediately.
+            *self.staged_blocks.entry(*block_id).or_insert(0) += 1;
             match sender.try_send(*block_id) {
                 Ok(_) => {
                     metrics.replication_staging_number.inc();
                 }
                 Err(e) => {
+                    self.unstage(*block_id);
                     error!(
                         "Failed to queue replication job for block {}: {}. Queue may be full. Will retry on next heartbeat check.",
                         block_id, e
@@ -233,6 +418,17 @@ impl MasterReplicationManager {
         Ok(())
     }
 
+    // Copy the block to a worker with a dir of the storage type, used when the workers holding
+    // the block have no such dir.
+    pub fn migrate_block(&self, block_id: BlockId, storage_type: StorageType) -> CommonResult<()> {
+        if !self.replication_enabled {
+            return err_box!("Block replication is disabled");
+        }
+
+        self.target_types.insert(block_id, storage_type);
+        self.report_under_replicated_blocks(None, vec![block_id])
+    }
+
     pub fn finish_replicated_block(&self, req: ReportBlockReplicationRequest) -> CommonResult<()> {
         // todo: retry on failure of block replication
 
@@ -247,9 +443,12 @@ impl MasterReplicationManager {
             Some(entry) => {
                 if success {
                     info!("Successfully replicated {}", block_id);
-                    let dir = self.fs.fs_dir.write();
-                    let location =
-                        BlockLocation::new(entry.1.target_worker.worker_id, storage_type.into());
+                    let mut dir = self.fs.fs_dir.write();
+                    let worker_id = entry.1.target_worker.worker_id;
+                    if let Some(index) = entry.1.unit_index {
+                        dir.update_block_unit(block_id, index as usize, worker_id)?;
+                    }
+                    let location = BlockLocation::new(worker_id, storage_type.into());
                     dir.add_block_location(block_id, location)?;
                 } else {
                     error!(
@@ -260,6 +459,11 @@ impl MasterReplicationManager {
                 }
                 drop(entry.1.permit);
                 self.metrics.replication_inflight_number.dec();
+
+                // An erasure-coded block may have lost more than one unit, check it again.
+                if success && entry.1.unit_index.is_some() {
+                    self.report_under_replicated_blocks(None, vec![block_id])?;
+                }
             }
         }
         Ok(())
@@ -14,3 +14,4 @@
 
 pub mod master_replication_handler;
 pub mod master_replication_manager;
+pub mod replication_reconciler;
@@ -0,0 +1,354 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::master::fs::{MasterFilesystem, WorkerManager};
+use crate::master::meta::inode::InodeView::File;
+use crate::master::replication::master_replication_manager::{BlockId, MasterReplicationManager};
+use crate::master::{Master, MasterMetrics, MasterMonitor};
+use curvine_common::error::FsError;
+use curvine_common::state::{BlockLocation, StorageType, WorkerStatus};
+use curvine_common::FsResult;
+use log::{debug, info};
+use orpc::common::TimeSpent;
+use orpc::runtime::LoopTask;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+// The blocks of an inode, with the replica number, the storage type of the file and the
+// current locations.
+type InodeBlocks = (i64, Vec<(BlockId, usize, StorageType, Vec<BlockLocation>)>);
+
+/// A live copy of a block.
+#[derive(Debug, Clone)]
+pub struct Replica {
+    pub worker_id: u32,
+    pub hostname: String,
+    pub storage_type: StorageType,
+}
+
+impl Replica {
+    // The slower the storage, the higher the rank.
+    fn tier_rank(&self) -> u8 {
+        match self.storage_type {
+            StorageType::Mem => 0,
+            StorageType::Ssd => 1,
+            StorageType::Hdd | StorageType::Disk => 2,
+            StorageType::Ufs => 3,
+        }
+    }
+}
+
+/// Periodically scans the namespace and makes the live replicas of each block match the
+/// replica number of its file.
+/// Under-replicated blocks are sent to the replication manager, which adds one copy at a time,
+/// so a block missing several copies is repaired over several rounds.
+/// Excess copies are marked for deletion and removed by the worker on its next heartbeat.
+pub struct ReplicationReconciler {
+    fs: MasterFilesystem,
+    monitor: MasterMonitor,
+    replication_manager: Arc<MasterReplicationManager>,
+    scan_batch: usize,
+    max_actions: usize,
+    // The inode id where the next round starts.
+    cursor: AtomicI64,
+    metrics: &'static MasterMetrics,
+}
+
+impl ReplicationReconciler {
+    pub fn new(
+        fs: MasterFilesystem,
+        monitor: MasterMonitor,
+        replication_manager: Arc<MasterReplicationManager>,
+    ) -> Self {
+        let scan_batch = fs.conf.block_reconcile_scan_batch.max(1);
+        let max_actions = fs.conf.block_reconcile_max_actions.max(1);
+        Self {
+            fs,
+            monitor,
+            replication_manager,
+            scan_batch,
+            max_actions,
+            cursor: AtomicI64::new(0),
+            metrics: Master::get_metrics(),
+        }
+    }
+
+    /// Choose the copies to delete.
+    /// Copies are removed from the host holding the most copies first to keep the replicas
+    /// spread across hosts, then copies not on the storage type of the file, so a storage
+    /// migration is not undone, and then from the slowest tier.
+    pub fn choose_excess(
+        replicas: &[Replica],
+        excess: usize,
+        storage_type: StorageType,
+    ) -> Vec<u32> {
+        let mut remaining: Vec<&Replica> = replicas.iter().collect();
+        let mut victims = vec![];
+
+        for _ in 0..excess.min(replicas.len()) {
+            let mut hosts: HashMap<&str, usize> = HashMap::new();
+            for replica in &remaining {
+                *hosts.entry(replica.hostname.as_str()).or_default() += 1;
+            }
+
+            let index = (0..remaining.len()).max_by_key(|&i| {
+                let replica = remaining[i];
+                (
+                    hosts[replica.hostname.as_str()],
+                    replica.storage_type != storage_type,
+                    replica.tier_rank(),
+                    replica.worker_id,
+                )
+            });
+            match index {
+                Some(i) => victims.push(remaining.remove(i).worker_id),
+                None => break,
+            }
+        }
+
+        victims
+    }
+
+    // The copies of the block on live workers, copies being removed are not counted.
+    fn live_replicas(
+        wm: &WorkerManager,
+        block_id: BlockId,
+        locs: &[BlockLocation],
+    ) -> Vec<Replica> {
+        locs.iter()
+            .filter(|loc| !wm.is_removing(loc.worker_id, block_id))
+            .filter_map(|loc| {
+                let worker = wm.get_worker(loc.worker_id)?;
+                if worker.status != WorkerStatus::Live {
+                    return None;
+                }
+                Some(Replica {
+                    worker_id: loc.worker_id,
+                    hostname: worker.address.hostname.clone(),
+                    storage_type: loc.storage_type,
+                })
+            })
+            .collect()
+    }
+
+    fn load_blocks(&self, start: i64) -> FsResult<(usize, Vec<InodeBlocks>)> {
+        let fs_dir = self.fs.fs_dir.read();
+        let store = fs_dir.get_rocks_store();
+        let inodes = store.scan_inodes(start, self.scan_batch)?;
+
+        let mut files = Vec::with_capacity(inodes.len());
+        for inode in &inodes {
+            let mut blocks = vec![];
+            if let File(_, file) = inode {
+                // Blocks of a file being written are handled by the write pipeline, and
+                // erasure-coded blocks are rebuilt instead of copied.
+                if file.is_complete() && !file.storage_policy.is_striped() && file.replicas > 0 {
+                    for meta in &file.blocks {
+                        let locs = store.get_locations(meta.id)?;
+                        blocks.push((
+                            meta.id,
+                            file.replicas as usize,
+                            file.storage_policy.storage_type,
+                            locs,
+                        ));
+                    }
+                }
+            }
+            files.push((inode.id(), blocks));
+        }
+
+        Ok((inodes.len(), files))
+    }
+
+    fn reconcile(&self) -> FsResult<()> {
+        let spend = TimeSpent::new();
+        let start = self.cursor.load(Ordering::Relaxed);
+        let (scanned, files) = self.load_blocks(start)?;
+
+        let mut next_cursor = None;
+        let mut actions = 0;
+        let mut checked = 0;
+        let mut under_replicated = vec![];
+        let mut over_replicated = vec![];
+        {
+            let wm = self.fs.worker_manager.read();
+            'files: for (inode_id, blocks) in &files {
+                for (block_id, target, storage_type, locs) in blocks {
+                    // The next round starts again from this inode, the blocks already handled
+                    // are being replicated or have their excess copies marked and are skipped.
+                    if actions >= self.max_actions {
+                        next_cursor = Some(*inode_id);
+                        break 'files;
+                    }
+
+                    checked += 1;
+                    if self.replication_manager.is_replicating(*block_id) {
+                        continue;
+                    }
+
+                    let live = Self::live_replicas(&wm, *block_id, locs);
+                    if live.is_empty() {
+                        // There is no copy to replicate from.
+                        continue;
+                    } else if live.len() < *target {
+                        under_replicated.push(*block_id);
+                        actions += 1;
+                    } else if live.len() > *target {
+                        let excess = (live.len() - *target).min(self.max_actions - actions);
+                        for worker_id in Self::choose_excess(&live, excess, *storage_type) {
+                            over_replicated.push((worker_id, *block_id, *target));
+                            actions += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        let next_cursor = match next_cursor {
+            Some(v) => v,
+            None if scanned < self.scan_batch => {
+                self.metrics.reconcile_full_scans.inc();
+                0
+            }
+            None => files.last().map(|x| x.0 + 1).unwrap_or(0),
+        };
+        self.cursor.store(next_cursor, Ordering::Relaxed);
+        self.metrics.reconcile_cursor.set(next_cursor);
+        self.metrics.reconcile_scanned_blocks.inc_by(checked);
+
+        let under_num = under_replicated.len();
+        let mut over_num = 0;
+        if !under_replicated.is_empty() {
+            self.metrics
+                .reconcile_under_replicated
+                .inc_by(under_num as i64);
+            self.replication_manager
+                .report_under_replicated_blocks(None, under_replicated)?;
+        }
+        if !over_replicated.is_empty() {
+            // A copy may be lost since the scan, so the copies are counted again while the
+            // locations and the worker states can not change, in the lock order of delete.
+            let fs_dir = self.fs.fs_dir.read();
+            let mut wm = self.fs.worker_manager.write();
+            for (worker_id, block_id, target) in over_replicated {
+                let locs = fs_dir.get_block_locations(block_id)?;
+                let live = Self::live_replicas(&wm, block_id, &locs);
+                if live.len() > target && live.iter().any(|x| x.worker_id == worker_id) {
+                    wm.remove_block(worker_id, block_id);
+                    over_num += 1;
+                }
+            }
+            self.metrics
+                .reconcile_over_replicated
+                .inc_by(over_num as i64);
+        }
+
+        if under_num + over_num > 0 {
+            info!(
+                "Reconcile from inode {}: checked {} blocks, {} under-replicated, {} excess replicas, next {}, used {} ms",
+                start,
+                checked,
+                under_num,
+                over_num,
+                next_cursor,
+                spend.used_ms()
+            );
+        } else {
+            debug!(
+                "Reconcile from inode {}: checked {} blocks, next {}, used {} ms",
+                start,
+                checked,
+                next_cursor,
+                spend.used_ms()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl LoopTask for ReplicationReconciler {
+    type Error = FsError;
+
+    fn run(&self) -> FsResult<()> {
+        if !self.monitor.is_active() {
+            return Ok(());
+        }
+        self.reconcile()
+    }
+
+    fn terminate(&self) -> bool {
+        self.monitor.is_stop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replica(worker_id: u32, hostname: &str, storage_type: StorageType) -> Replica {
+        Replica {
+            worker_id,
+            hostname: hostname.to_string(),
+            storage_type,
+        }
+    }
+
+    #[test]
+    fn excess_keeps_host_spread() {
+        let replicas = vec![
+            replica(1, "host-a", StorageType::Mem),
+            replica(2, "host-a", StorageType::Mem),
+            replica(3, "host-b", StorageType::Hdd),
+        ];
+        assert_eq!(
+            ReplicationReconciler::choose_excess(&replicas, 1, StorageType::Mem),
+            vec![2]
+        );
+        assert_eq!(
+            ReplicationReconciler::choose_excess(&replicas, 2, StorageType::Mem),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn excess_keeps_faster_tier() {
+        let replicas = vec![
+            replica(1, "host-a", StorageType::Ssd),
+            replica(2, "host-b", StorageType::Mem),
+            replica(3, "host-c", StorageType::Disk),
+        ];
+        assert_eq!(
+            ReplicationReconciler::choose_excess(&replicas, 2, StorageType::Mem),
+            vec![3, 1]
+        );
+        assert_eq!(
+            ReplicationReconciler::choose_excess(&replicas, 5, StorageType::Mem).len(),
+            3
+        );
+    }
+
+    #[test]
+    fn excess_keeps_file_storage_type() {
+        let replicas = vec![
+            replica(1, "host-a", StorageType::Mem),
+            replica(2, "host-b", StorageType::Hdd),
+        ];
+        assert_eq!(
+            ReplicationReconciler::choose_excess(&replicas, 1, StorageType::Hdd),
+            vec![1]
+        );
+    }
+}
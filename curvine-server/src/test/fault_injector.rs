@@ -0,0 +1,57 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_common::FsResult;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use orpc::err_box;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum WorkerFault {
+    // Block writes, flushes and commits fail, as if the worker died in the middle of a write.
+    WriteBlock,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static FAULTS: Lazy<DashMap<(u32, WorkerFault), ()>> = Lazy::new(DashMap::new);
+
+/// Failures injected into the workers of a MiniCluster, keyed by worker id.
+/// All workers of a MiniCluster run in the same process, so a global registry is enough.
+pub struct FaultInjector;
+
+impl FaultInjector {
+    pub fn inject(worker_id: u32, fault: WorkerFault) {
+        FAULTS.insert((worker_id, fault), ());
+        ENABLED.store(true, Ordering::Release);
+    }
+
+    pub fn clear(worker_id: u32) {
+        FAULTS.retain(|k, _| k.0 != worker_id);
+        ENABLED.store(!FAULTS.is_empty(), Ordering::Release);
+    }
+
+    pub fn is_enabled() -> bool {
+        ENABLED.load(Ordering::Acquire)
+    }
+
+    pub fn check(worker_id: u32, fault: WorkerFault) -> FsResult<()> {
+        if FAULTS.contains_key(&(worker_id, fault)) {
+            err_box!("Injected fault {:?} on worker {}", fault, worker_id)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -15,10 +15,13 @@
 use crate::master::fs::MasterFilesystem;
 use crate::master::replication::master_replication_manager::MasterReplicationManager;
 use crate::master::Master;
+#[cfg(feature = "fault-injection")]
+use crate::test::{FaultInjector, WorkerFault};
 use crate::worker::Worker;
 use curvine_client::file::CurvineFileSystem;
 use curvine_common::conf::ClusterConf;
 use curvine_common::raft::{NodeId, RaftPeer};
+use curvine_common::state::WorkerAddress;
 use curvine_common::FsResult;
 use dashmap::DashMap;
 use log::info;
@@ -41,6 +44,7 @@ pub struct MiniCluster {
     pub worker_conf: Vec<ClusterConf>,
 
     pub master_entries: DashMap<usize, MasterEntry>,
+    pub worker_entries: DashMap<usize, WorkerAddress>,
     pub client_rt: Arc<Runtime>,
 }
 
@@ -52,6 +56,7 @@ impl MiniCluster {
             master_conf,
             worker_conf,
             master_entries: Default::default(),
+            worker_entries: Default::default(),
             client_rt,
         }
     }
@@ -73,13 +78,56 @@ impl MiniCluster {
         }
     }
 
+    // The configuration of a new master that joins the running cluster as the raft node id.
+    pub fn joining_master_conf(&self, id: NodeId) -> ClusterConf {
+        let mut conf = self.cluster_conf.clone();
+        conf.master.rpc_port = NetUtils::get_available_port();
+        conf.journal.rpc_port = NetUtils::get_available_port();
+        conf.master.web_port = NetUtils::get_available_port();
+        conf.master.meta_dir = format!("{}-{}", conf.master.meta_dir, id);
+        conf.journal.journal_dir = format!("{}-{}", conf.journal.journal_dir, id);
+        conf.journal.journal_addrs.push(
+            RaftPeer::new(id, &conf.journal.hostname, conf.journal.rpc_port)
+                .with_service_port(conf.master.rpc_port),
+        );
+        conf.journal.join_cluster = true;
+        conf
+    }
+
+    pub fn start_joining_master(&self, conf: ClusterConf) {
+        let master = Master::with_conf(conf).unwrap();
+        self.master_entries.insert(
+            self.master_entries.len(),
+            MasterEntry(master.get_fs(), master.get_replication_manager()),
+        );
+        thread::spawn(move || master.block_on_start());
+    }
+
     pub fn start_worker(&self) {
-        for conf in &self.worker_conf {
+        for (index, conf) in self.worker_conf.iter().enumerate() {
             let worker = Worker::with_conf(conf.clone()).unwrap();
+            self.worker_entries.insert(index, worker.addr.clone());
             thread::spawn(move || worker.block_on_start());
         }
     }
 
+    pub fn get_worker_address(&self, index: usize) -> WorkerAddress {
+        self.worker_entries.get(&index).unwrap().clone()
+    }
+
+    // Make the worker fail as if it crashed, the worker stays registered in the master.
+    #[cfg(feature = "fault-injection")]
+    pub fn inject_worker_fault(&self, index: usize, fault: WorkerFault) {
+        let addr = self.get_worker_address(index);
+        info!("Inject fault {:?} into worker {}", fault, addr);
+        FaultInjector::inject(addr.worker_id, fault);
+    }
+
+    #[cfg(feature = "fault-injection")]
+    pub fn clear_worker_faults(&self, index: usize) {
+        FaultInjector::clear(self.get_worker_address(index).worker_id);
+    }
+
     pub fn start_cluster(&self) {
         self.start_master();
 
@@ -217,6 +265,14 @@ impl MiniCluster {
             .unwrap()
     }
 
+    pub fn get_standby_master_fs(&self) -> Vec<MasterFilesystem> {
+        self.master_entries
+            .iter()
+            .filter(|x| !x.0.master_monitor.is_active())
+            .map(|x| x.0.clone())
+            .collect()
+    }
+
     pub fn get_active_master_replication_manager(&self) -> Arc<MasterReplicationManager> {
         self.master_entries
             .iter()
@@ -239,11 +295,14 @@ impl MiniCluster {
             master.master.meta_dir = format!("{}/{}", master.master.meta_dir, index);
             master.journal.journal_dir = format!("{}/{}", master.journal.journal_dir, index);
 
-            journal_addrs.push(RaftPeer::new(
-                (index + 1) as NodeId,
-                &master.journal.hostname,
-                master.journal.rpc_port,
-            ));
+            journal_addrs.push(
+                RaftPeer::new(
+                    (index + 1) as NodeId,
+                    &master.journal.hostname,
+                    master.journal.rpc_port,
+                )
+                .with_service_port(master.master.rpc_port),
+            );
 
             master_addrs.push(InetAddr::new(
                 &master.master.hostname,
@@ -264,6 +323,9 @@ impl MiniCluster {
             item.master
                 .init()
                 .expect("Failed to initialize master config");
+            item.journal
+                .init()
+                .expect("Failed to initialize journal config");
             item.fuse.init().expect("Failed to initialize fuse config");
             item.job.init().expect("Failed to initialize job config");
         }
@@ -295,6 +357,10 @@ impl MiniCluster {
                 .master
                 .init()
                 .expect("Failed to initialize master config");
+            worker
+                .worker
+                .init()
+                .expect("Failed to initialize worker config");
             worker
                 .fuse
                 .init()
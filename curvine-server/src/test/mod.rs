@@ -14,3 +14,8 @@
 
 mod mini_cluster;
 pub use self::mini_cluster::MiniCluster;
+
+#[cfg(feature = "fault-injection")]
+mod fault_injector;
+#[cfg(feature = "fault-injection")]
+pub use self::fault_injector::*;
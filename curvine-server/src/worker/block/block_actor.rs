@@ -12,9 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::worker::block::{BlockStore, HeartbeatTask, MasterClient};
+use crate::worker::block::{BlockStore, HeartbeatTask, MasterClient, TierTask};
 use curvine_client::file::FsContext;
-use curvine_common::conf::ClusterConf;
+use curvine_common::conf::{ClusterConf, WorkerConf};
 use curvine_common::executor::ScheduledExecutor;
 use curvine_common::state::{BlockReportInfo, HeartbeatStatus, WorkerAddress};
 use dashmap::DashMap;
@@ -36,6 +36,7 @@ pub struct BlockActor {
     heartbeat_interval_ms: u64,
     worker_ctl: StateCtl,
     block_report_limit: usize,
+    worker_conf: WorkerConf,
 
     // Block that needs to be reported when the heartbeats.
     // Includes the following situations:
@@ -74,6 +75,7 @@ impl BlockActor {
             heartbeat_interval_ms,
             worker_ctl,
             block_report_limit,
+            worker_conf: conf.worker.clone(),
             report_blocks: Arc::new(DashMap::new()),
         }
     }
@@ -101,6 +103,17 @@ impl BlockActor {
             self.heartbeat_interval_ms,
         )
         .unwrap();
+
+        if self.worker_conf.enable_tier_migration {
+            Self::start_tier_migration(
+                self.worker_ctl.clone(),
+                self.store.clone(),
+                self.report_blocks.clone(),
+                self.worker_conf.clone(),
+            )
+            .unwrap();
+            info!("worker tier migration started");
+        }
     }
 
     // Worker registration.
@@ -147,4 +160,23 @@ impl BlockActor {
         scheduler.start(task)?;
         Ok(())
     }
+
+    pub fn start_tier_migration(
+        worker_ctl: StateCtl,
+        store: BlockStore,
+        report_blocks: Arc<DashMap<i64, BlockReportInfo>>,
+        conf: WorkerConf,
+    ) -> CommonResult<()> {
+        let scheduler = ScheduledExecutor::new("worker-tier", conf.tier_check_interval_ms());
+
+        let task = TierTask {
+            worker_ctl,
+            store,
+            report_blocks,
+            conf,
+        };
+
+        scheduler.start(task)?;
+        Ok(())
+    }
 }
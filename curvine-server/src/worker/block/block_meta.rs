@@ -24,6 +24,8 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::{fmt, fs};
 
+pub const MOVING_SUFFIX: &str = ".moving";
+
 static FILE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^blk_(\w+)$").unwrap());
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -130,6 +132,17 @@ impl BlockMeta {
         Ok(meta)
     }
 
+    // The meta of a finalized block after it is moved to another dir.
+    pub fn with_dir(meta: &BlockMeta, dir: &VfsDir) -> Self {
+        Self {
+            id: meta.id,
+            len: meta.len,
+            state: BlockState::Finalized,
+            dir: dir.state.clone(),
+            actual_len: meta.actual_len,
+        }
+    }
+
     pub fn with_id(id: i64) -> Self {
         Self {
             id,
@@ -211,6 +224,15 @@ impl BlockMeta {
         Ok(path)
     }
 
+    // staging/blk_blockid.moving, the block is copied here before it is moved to another dir.
+    pub fn get_move_path(&self) -> CommonResult<PathBuf> {
+        let mut path = PathBuf::from(&self.dir.base_path);
+        path.push(STAGING_DIR);
+        try_err!(fs::create_dir_all(&path));
+        path.push(format!("{}{}", self.state.get_name(self.id), MOVING_SUFFIX));
+        Ok(path)
+    }
+
     pub fn get_block_file(&self) -> CommonResult<String> {
         let file = self.get_block_path()?.to_string_lossy().to_string();
         Ok(file)
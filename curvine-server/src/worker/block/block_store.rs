@@ -12,18 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::worker::block::BlockMeta;
+use crate::worker::block::{BlockAccess, BlockMeta};
 use crate::worker::storage::{BlockDataset, Dataset};
+use crate::worker::IoThrottle;
 use curvine_common::conf::ClusterConf;
-use curvine_common::state::{ExtendedBlock, StorageInfo};
+use curvine_common::state::{ExtendedBlock, StorageInfo, StorageType};
+use dashmap::DashMap;
 use log::error;
-use orpc::common::FileUtils;
-use orpc::{err_box, CommonResult};
+use orpc::common::{FileUtils, LocalTime};
+use orpc::{err_box, try_err, CommonResult};
+use std::fs;
 use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 #[derive(Clone)]
 pub struct BlockStore {
     state: Arc<RwLock<BlockDataset>>,
+
+    // Read stats of blocks, used by tier migration.
+    access: Arc<DashMap<i64, BlockAccess>>,
+
+    // Io bandwidth limits of the worker.
+    io_throttle: Arc<IoThrottle>,
 }
 
 impl BlockStore {
@@ -31,11 +40,17 @@ impl BlockStore {
         let dataset = BlockDataset::from_conf(cluster_id, conf)?;
         let block_store = BlockStore {
             state: Arc::new(RwLock::new(dataset)),
+            access: Arc::new(DashMap::new()),
+            io_throttle: Arc::new(IoThrottle::new(&conf.worker)?),
         };
 
         Ok(block_store)
     }
 
+    pub fn io_throttle(&self) -> &Arc<IoThrottle> {
+        &self.io_throttle
+    }
+
     pub(crate) fn write(&self) -> RwLockWriteGuard<'_, BlockDataset> {
         self.state.write().unwrap()
     }
@@ -77,7 +92,111 @@ impl BlockStore {
         state.all_blocks()
     }
 
+    pub fn record_read(&self, id: i64) {
+        let mut access = self.access.entry(id).or_default();
+        access.reads = access.reads.saturating_add(1);
+        access.last_ms = LocalTime::mills();
+    }
+
+    pub fn block_access(&self) -> &DashMap<i64, BlockAccess> {
+        &self.access
+    }
+
+    // Move a finalized block to another storage dir.
+    // The block file is copied without holding the lock; if the block is changed or removed meanwhile,
+    // the copy is discarded and None is returned.
+    pub fn move_block(&self, id: i64, dir_id: u32) -> CommonResult<Option<BlockMeta>> {
+        let (src, target) = {
+            let state = self.read();
+            let src = state.get_block_check(id)?.clone();
+            if !src.is_final() {
+                return err_box!("Block {} is not finalized", id);
+            }
+            if src.dir_id() == dir_id {
+                return err_box!("Block {} is already in dir {}", id, dir_id);
+            }
+
+            let dir = state.find_dir(dir_id)?;
+            if !dir.can_allocate(dir.storage_type(), src.len) {
+                return err_box!("Not enough space in dir {} to move block {}", dir_id, id);
+            }
+            dir.reserve_space(false, src.len);
+            let target = BlockMeta::with_dir(&src, dir);
+            (src, target)
+        };
+
+        let release_tmp = || -> CommonResult<()> {
+            self.read().find_dir(dir_id)?.release_space(false, src.len);
+            Ok(())
+        };
+
+        let tmp_path = target.get_move_path()?;
+        if let Err(e) = fs::copy(src.get_block_path()?, &tmp_path) {
+            let _ = fs::remove_file(&tmp_path);
+            release_tmp()?;
+            return err_box!("Copy block {} to dir {} failed: {}", id, dir_id, e);
+        }
+
+        let mut state = self.write();
+        let unchanged = match state.get_block(id) {
+            Some(cur) => cur.is_final() && cur.dir_id() == src.dir_id() && cur.len == src.len,
+            None => false,
+        };
+        let renamed = if unchanged {
+            fs::rename(&tmp_path, target.get_block_path()?)
+        } else {
+            Ok(())
+        };
+        if !unchanged || renamed.is_err() {
+            drop(state);
+            let _ = fs::remove_file(&tmp_path);
+            release_tmp()?;
+            try_err!(renamed);
+            return Ok(None);
+        }
+
+        let final_meta = BlockMeta::with_final(&target)?;
+        let dir = state.find_dir(dir_id)?;
+        dir.release_space(false, src.len);
+        dir.reserve_space(true, final_meta.actual_len);
+        state
+            .find_dir(src.dir_id())?
+            .release_space(true, src.actual_len);
+        state.block_map.insert(id, final_meta.clone());
+        drop(state);
+
+        FileUtils::delete_path(src.get_block_path()?, false)?;
+        Ok(Some(final_meta))
+    }
+
+    // Move a finalized block to the dir of the storage type with the most available space.
+    // Returns None if the block is already stored in such a dir, or it is changed during the move.
+    pub fn move_block_to(
+        &self,
+        id: i64,
+        storage_type: StorageType,
+    ) -> CommonResult<Option<BlockMeta>> {
+        let dir_id = {
+            let state = self.read();
+            if state.get_block_check(id)?.storage_type() == storage_type {
+                return Ok(None);
+            }
+
+            let dir = state
+                .dir_iter()
+                .filter(|x| x.storage_type() == storage_type && !x.is_failed())
+                .max_by_key(|x| x.available());
+            match dir {
+                Some(v) => v.id(),
+                None => return err_box!("No dir with storage type {:?}", storage_type),
+            }
+        };
+
+        self.move_block(id, dir_id)
+    }
+
     pub fn remove_block(&self, id: i64) -> CommonResult<()> {
+        self.access.remove(&id);
         let mut state = self.write();
         let block = ExtendedBlock::with_id(id);
         state.remove_block(&block)
@@ -86,6 +205,7 @@ impl BlockStore {
     // Asynchronously delete block.
     pub fn async_remove_block(&self, id: i64) -> CommonResult<BlockMeta> {
         // Delete the original data.
+        self.access.remove(&id);
         let mut state = self.write();
         let meta = state.block_map.remove(&id);
 
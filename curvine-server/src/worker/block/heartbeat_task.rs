@@ -15,9 +15,12 @@
 use crate::worker::block::{BlockStore, MasterClient};
 use crate::worker::storage::Dataset;
 use curvine_common::error::FsError;
-use curvine_common::state::{BlockReportInfo, HeartbeatStatus, WorkerCommand};
+use curvine_common::state::{
+    BlockReportInfo, BlockReportStatus, HeartbeatStatus, StorageType, WorkerCommand,
+};
 use curvine_common::utils::ProtoUtils;
 use curvine_common::FsResult;
+use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
 use log::{error, warn};
 use orpc::runtime::{GroupExecutor, LoopTask};
@@ -35,8 +38,8 @@ pub struct HeartbeatTask {
 }
 
 impl HeartbeatTask {
-    // Asynchronously delete the block file.
-    fn delete_block_task(
+    // Asynchronously delete or move the block files.
+    fn handle_cmds(
         executor: Arc<GroupExecutor>,
         store: BlockStore,
         cmds: Vec<WorkerCommand>,
@@ -46,7 +49,10 @@ impl HeartbeatTask {
             match cmd {
                 WorkerCommand::DeleteBlock(c) => {
                     for block in c.blocks {
-                        if report_blocks.contains_key(&block) {
+                        let deleted = report_blocks
+                            .get(&block)
+                            .map(|x| x.status == BlockReportStatus::Deleted);
+                        if deleted == Some(true) {
                             continue;
                         }
 
@@ -72,6 +78,61 @@ impl HeartbeatTask {
                         let _ = try_log!(res);
                     }
                 }
+
+                WorkerCommand::MoveBlock(c) => {
+                    let store1 = store.clone();
+                    let report_blocks1 = report_blocks.clone();
+                    let res = executor.spawn(move || {
+                        Self::move_blocks(&store1, c.storage_type, c.blocks, &report_blocks1)
+                    });
+
+                    let _ = try_log!(res);
+                }
+            }
+        }
+    }
+
+    // Move blocks to another tier, requested by a storage migration job on the master.
+    fn move_blocks(
+        store: &BlockStore,
+        storage_type: StorageType,
+        blocks: Vec<i64>,
+        report_blocks: &DashMap<i64, BlockReportInfo>,
+    ) {
+        for block in blocks {
+            let deleted = report_blocks
+                .get(&block)
+                .map(|x| x.status == BlockReportStatus::Deleted);
+            if deleted == Some(true) {
+                continue;
+            }
+
+            let meta = match store.move_block_to(block, storage_type) {
+                Ok(Some(v)) => v,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("Move block {} to {:?}: {}", block, storage_type, e);
+                    continue;
+                }
+            };
+
+            // Overwrite a pending report with the new storage type,
+            // but a pending deletion report must not be overwritten.
+            let info = BlockReportInfo::new(
+                meta.id,
+                BlockReportStatus::Finalized,
+                meta.storage_type(),
+                meta.len,
+            );
+            match report_blocks.entry(meta.id) {
+                Entry::Occupied(mut e) => {
+                    if e.get().status != BlockReportStatus::Deleted {
+                        e.insert(info);
+                    }
+                }
+                Entry::Vacant(e) => {
+                    e.insert(info);
+                }
             }
         }
     }
@@ -109,7 +170,7 @@ impl LoopTask for HeartbeatTask {
         match res {
             Ok(v) => {
                 let cmds = ProtoUtils::worker_cmd_from_pb(v.cmds);
-                Self::delete_block_task(
+                Self::handle_cmds(
                     self.executor.clone(),
                     self.store.clone(),
                     cmds,
@@ -26,3 +26,6 @@ pub use self::block_actor::BlockActor;
 
 mod heartbeat_task;
 pub use self::heartbeat_task::HeartbeatTask;
+
+mod tier_task;
+pub use self::tier_task::*;
@@ -0,0 +1,408 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::worker::block::BlockStore;
+use crate::worker::{TrafficClass, Worker};
+use curvine_common::conf::WorkerConf;
+use curvine_common::error::FsError;
+use curvine_common::state::{BlockReportInfo, BlockReportStatus, StorageType};
+use curvine_common::FsResult;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use log::{info, warn};
+use orpc::runtime::LoopTask;
+use orpc::server::ServerState;
+use orpc::sync::StateCtl;
+use orpc::CommonResult;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlockAccess {
+    // Number of reads, halved after every tier check.
+    pub reads: u32,
+    pub last_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TierMove {
+    pub block_id: i64,
+    pub len: i64,
+    pub dir_id: u32,
+    pub promote: bool,
+}
+
+#[derive(Debug, Default)]
+struct TierStat {
+    storage_type: StorageType,
+    capacity: i64,
+    used: i64,
+    // (dir_id, available)
+    dirs: Vec<(u32, i64)>,
+    // (block_id, len, reads, last_ms)
+    blocks: Vec<(i64, i64, u32, u64)>,
+}
+
+impl TierStat {
+    fn usage(&self, extra: i64) -> f64 {
+        if self.capacity <= 0 {
+            1.0
+        } else {
+            (self.used + extra) as f64 / self.capacity as f64
+        }
+    }
+
+    // Pick the dir with the most available space, and account for the block.
+    fn allocate(&mut self, len: i64) -> Option<u32> {
+        let (dir_id, available) = self.dirs.iter_mut().max_by_key(|x| x.1)?;
+        if *available <= len {
+            return None;
+        }
+        *available -= len;
+        self.used += len;
+        Some(*dir_id)
+    }
+}
+
+/// Moves blocks between the MEM, SSD and HDD dirs of a worker.
+///
+/// A tier over its high watermark demotes its coldest blocks to slower tiers until it is below
+/// the low watermark, and blocks read at least `tier_promote_reads` times are promoted to the
+/// fastest tier that stays below its high watermark.
+/// Every move is reported to the master on the next heartbeat, so the block location has the new storage type.
+pub struct TierTask {
+    pub(crate) worker_ctl: StateCtl,
+    pub(crate) store: BlockStore,
+    pub(crate) report_blocks: Arc<DashMap<i64, BlockReportInfo>>,
+    pub(crate) conf: WorkerConf,
+}
+
+impl TierTask {
+    // Smaller is faster, DISK dirs are treated as HDD.
+    fn tier_rank(storage_type: StorageType) -> Option<u8> {
+        match storage_type {
+            StorageType::Mem => Some(0),
+            StorageType::Ssd => Some(1),
+            StorageType::Hdd | StorageType::Disk => Some(2),
+            StorageType::Ufs => None,
+        }
+    }
+
+    fn collect_tiers(&self) -> BTreeMap<u8, TierStat> {
+        let mut tiers: BTreeMap<u8, TierStat> = BTreeMap::new();
+        let state = self.store.read();
+        let mut dir_rank = vec![];
+        for dir in state.dir_iter() {
+            let Some(rank) = Self::tier_rank(dir.storage_type()) else {
+                continue;
+            };
+            if dir.is_failed() {
+                continue;
+            }
+
+            let tier = tiers.entry(rank).or_insert_with(|| TierStat {
+                storage_type: dir.storage_type(),
+                ..Default::default()
+            });
+            tier.capacity += dir.capacity();
+            tier.used += dir.fs_used();
+            tier.dirs.push((dir.id(), dir.available()));
+            dir_rank.push((dir.id(), rank));
+        }
+
+        let access = self.store.block_access();
+        for meta in state.block_map.values() {
+            if !meta.is_final() {
+                continue;
+            }
+            let Some((_, rank)) = dir_rank.iter().find(|x| x.0 == meta.dir_id()) else {
+                continue;
+            };
+            let stat = access.get(&meta.id).map(|x| *x).unwrap_or_default();
+            if let Some(tier) = tiers.get_mut(rank) {
+                tier.blocks
+                    .push((meta.id, meta.len, stat.reads, stat.last_ms));
+            }
+        }
+
+        tiers
+    }
+
+    pub fn plan_moves(&self) -> CommonResult<Vec<TierMove>> {
+        let mut tiers = self.collect_tiers();
+        let ranks: Vec<u8> = tiers.keys().copied().collect();
+        let mut moves = vec![];
+        let mut moved = HashSet::new();
+
+        // Demote the coldest blocks of tiers over the high watermark.
+        for (i, rank) in ranks.iter().enumerate() {
+            let tier = &tiers[rank];
+            let mark = self.conf.tier_watermark(tier.storage_type);
+            if tier.usage(0) <= mark.high {
+                continue;
+            }
+
+            let mut need = tier.used - (mark.low * tier.capacity as f64) as i64;
+            let mut blocks = tier.blocks.clone();
+            blocks.sort_by_key(|x| (x.2, x.3));
+            for (id, len, _, _) in blocks {
+                if need <= 0 || moves.len() >= self.conf.tier_max_moves {
+                    break;
+                }
+
+                for slower in &ranks[i + 1..] {
+                    if let Some(dir_id) = tiers.get_mut(slower).and_then(|x| x.allocate(len)) {
+                        moves.push(TierMove {
+                            block_id: id,
+                            len,
+                            dir_id,
+                            promote: false,
+                        });
+                        moved.insert(id);
+                        need -= len;
+                        if let Some(x) = tiers.get_mut(rank) {
+                            x.used -= len
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Promote hot blocks to the fastest tier with room for them.
+        let mut hot = vec![];
+        for (i, rank) in ranks.iter().enumerate().skip(1) {
+            for block in &tiers[rank].blocks {
+                if block.2 >= self.conf.tier_promote_reads && !moved.contains(&block.0) {
+                    hot.push((i, *block));
+                }
+            }
+        }
+        hot.sort_by_key(|x| (std::cmp::Reverse(x.1 .2), std::cmp::Reverse(x.1 .3)));
+
+        for (i, (id, len, _, _)) in hot {
+            if moves.len() >= self.conf.tier_max_moves {
+                break;
+            }
+
+            for faster in &ranks[..i] {
+                let tier = &tiers[faster];
+                let mark = self.conf.tier_watermark(tier.storage_type);
+                if tier.usage(len) > mark.high {
+                    continue;
+                }
+                if let Some(dir_id) = tiers.get_mut(faster).and_then(|x| x.allocate(len)) {
+                    moves.push(TierMove {
+                        block_id: id,
+                        len,
+                        dir_id,
+                        promote: true,
+                    });
+                    break;
+                }
+            }
+        }
+
+        Ok(moves)
+    }
+
+    // Returns (promoted, demoted, moved bytes).
+    pub fn migrate(&self) -> CommonResult<(u64, u64, i64)> {
+        let moves = self.plan_moves()?;
+        let (mut promoted, mut demoted, mut bytes) = (0, 0, 0);
+
+        for m in moves {
+            let meta = match self.store.move_block(m.block_id, m.dir_id) {
+                Ok(Some(v)) => v,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("Move block {} to dir {}: {}", m.block_id, m.dir_id, e);
+                    continue;
+                }
+            };
+
+            // Only the blocks actually moved are charged, the wait paces the next move.
+            self.store
+                .io_throttle()
+                .acquire(TrafficClass::Eviction, meta.len as u64);
+
+            // Overwrite a pending report with the new storage type,
+            // but a pending deletion report must not be overwritten.
+            let info = BlockReportInfo::new(
+                meta.id,
+                BlockReportStatus::Finalized,
+                meta.storage_type(),
+                meta.len,
+            );
+            match self.report_blocks.entry(meta.id) {
+                Entry::Occupied(mut e) => {
+                    if e.get().status != BlockReportStatus::Deleted {
+                        e.insert(info);
+                    }
+                }
+                Entry::Vacant(e) => {
+                    e.insert(info);
+                }
+            }
+
+            if m.promote {
+                promoted += 1;
+            } else {
+                demoted += 1;
+            }
+            bytes += m.len;
+        }
+
+        // Decay the read stats, so only recently hot blocks are promoted.
+        self.store.block_access().retain(|_, v| {
+            v.reads /= 2;
+            v.reads > 0
+        });
+
+        if promoted + demoted > 0 {
+            info!(
+                "Tier migration promoted {} blocks, demoted {} blocks, moved {} bytes",
+                promoted, demoted, bytes
+            );
+        }
+        Ok((promoted, demoted, bytes))
+    }
+}
+
+impl LoopTask for TierTask {
+    type Error = FsError;
+
+    fn run(&self) -> FsResult<()> {
+        let (promoted, demoted, bytes) = self.migrate()?;
+
+        let metrics = Worker::get_metrics();
+        metrics.tier_promoted_blocks.inc_by(promoted as i64);
+        metrics.tier_demoted_blocks.inc_by(demoted as i64);
+        metrics.tier_moved_bytes.inc_by(bytes);
+        Ok(())
+    }
+
+    fn terminate(&self) -> bool {
+        let state: ServerState = self.worker_ctl.state();
+        state == ServerState::Stop
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::worker::block::{BlockStore, TierTask};
+    use crate::worker::storage::Dataset;
+    use curvine_common::conf::{ClusterConf, WorkerConf};
+    use curvine_common::state::{BlockReportInfo, BlockReportStatus, ExtendedBlock, StorageType};
+    use dashmap::DashMap;
+    use orpc::sync::StateCtl;
+    use orpc::CommonResult;
+    use std::sync::Arc;
+
+    fn create_task(name: &str) -> CommonResult<TierTask> {
+        let mut conf = ClusterConf {
+            format_worker: true,
+            worker: WorkerConf {
+                dir_reserved: "0".to_string(),
+                data_dir: vec![
+                    format!("[MEM:1000B]../testing/tier-{}/d1", name),
+                    format!("[SSD:1000B]../testing/tier-{}/d2", name),
+                    format!("[HDD:10000B]../testing/tier-{}/d3", name),
+                ],
+                tier_promote_reads: 2,
+                tier_watermarks: vec!["[MEM]0.8:0.5".to_string()],
+                ..WorkerConf::default()
+            },
+            ..Default::default()
+        };
+        conf.worker.init()?;
+
+        Ok(TierTask {
+            worker_ctl: StateCtl::new(0),
+            store: BlockStore::new("test", &conf)?,
+            report_blocks: Arc::new(DashMap::new()),
+            conf: conf.worker,
+        })
+    }
+
+    fn write_block(store: &BlockStore, id: i64, stg_type: StorageType) -> CommonResult<()> {
+        let mut block = ExtendedBlock::with_size_str(id, "100B", stg_type)?;
+        let meta = store.open_block(&block)?;
+        meta.write_test_data("100B")?;
+        block.len = 100;
+        store.finalize_block(&block)?;
+        Ok(())
+    }
+
+    #[test]
+    fn promote() -> CommonResult<()> {
+        let task = create_task("promote")?;
+        let store = task.store.clone();
+        write_block(&store, 1, StorageType::Hdd)?;
+        write_block(&store, 2, StorageType::Hdd)?;
+        // The reports queued by the writes are replaced with the new storage type.
+        for id in [1, 2] {
+            let info =
+                BlockReportInfo::new(id, BlockReportStatus::Finalized, StorageType::Hdd, 100);
+            task.report_blocks.insert(id, info);
+        }
+        for _ in 0..3 {
+            store.record_read(1);
+        }
+
+        task.migrate()?;
+        let meta = store.get_block(1)?;
+        assert_eq!(meta.storage_type(), StorageType::Mem);
+        assert!(meta.get_block_path()?.exists());
+        assert_eq!(store.get_block(2)?.storage_type(), StorageType::Hdd);
+
+        let report = task.report_blocks.get(&1).unwrap();
+        assert_eq!(report.status, BlockReportStatus::Finalized);
+        assert_eq!(report.storage_type, StorageType::Mem);
+
+        // The read stats decay, block 1 is not promoted again.
+        assert_eq!(store.block_access().get(&1).unwrap().reads, 1);
+        assert!(task.plan_moves()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn demote() -> CommonResult<()> {
+        let task = create_task("demote")?;
+        let store = task.store.clone();
+        for id in 1..=9 {
+            write_block(&store, id, StorageType::Mem)?;
+        }
+        // Block 9 is hot, it stays in MEM.
+        store.record_read(9);
+
+        // MEM is at 90% > 80%, blocks are demoted until it drops to 50%.
+        task.migrate()?;
+        let state = store.read();
+        let mem_dir = state
+            .dir_iter()
+            .find(|x| x.storage_type() == StorageType::Mem);
+        assert_eq!(mem_dir.unwrap().fs_used(), 500);
+        assert_eq!(state.num_blocks(), 9);
+        drop(state);
+
+        assert_eq!(store.get_block(9)?.storage_type(), StorageType::Mem);
+        let demoted = (1..=9)
+            .filter(|x| store.get_block(*x).unwrap().storage_type() == StorageType::Ssd)
+            .count();
+        assert_eq!(demoted, 4);
+        assert_eq!(task.report_blocks.len(), 4);
+        Ok(())
+    }
+}
@@ -15,6 +15,7 @@
 use crate::worker::block::BlockStore;
 use crate::worker::handler::WriteContext;
 use crate::worker::handler::WriteHandler;
+use crate::worker::TrafficClass;
 use curvine_common::error::FsError;
 use curvine_common::fs::RpcCode;
 use curvine_common::proto::{
@@ -182,6 +183,12 @@ impl BatchWriteHandler {
         let header: FilesBatchWriteRequest = msg.parse_header()?;
         let mut results = Vec::new();
 
+        // Charge the whole batch to the client write bandwidth once, before writing any file.
+        let batch_len: usize = header.files.iter().map(|x| x.content.len()).sum();
+        self.store
+            .io_throttle()
+            .acquire(TrafficClass::ClientWrite, batch_len as u64);
+
         // Use drain to extract elements while preserving the vector's allocated memory
         let files_vec = self.file.as_mut().unwrap();
         let contexts_vec = self.context.as_mut().unwrap();
@@ -214,7 +221,7 @@ impl BatchWriteHandler {
             self.write_handler.file = Some(file);
             self.write_handler.context = Some(context);
 
-            let response = self.write_handler.write(&single_msg);
+            let response = self.write_handler.write0(&single_msg, false);
 
             // Collect processed file and context back into the original vectors
             let file = self.write_handler.file.take().unwrap();
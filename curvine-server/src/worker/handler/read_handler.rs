@@ -14,12 +14,12 @@
 
 use crate::worker::block::BlockStore;
 use crate::worker::handler::ReadContext;
-use crate::worker::{Worker, WorkerMetrics};
+use crate::worker::{TrafficClass, Worker, WorkerMetrics};
 use curvine_common::error::FsError;
 use curvine_common::proto::{BlockReadResponse, DataHeaderProto};
 use curvine_common::FsResult;
 use log::{info, warn};
-use orpc::common::{ByteUnit, TimeSpent};
+use orpc::common::{ByteUnit, Span, TimeSpent};
 use orpc::handler::MessageHandler;
 use orpc::io::LocalFile;
 use orpc::message::{Builder, Message, RequestStatus};
@@ -59,6 +59,7 @@ impl ReadHandler {
     pub fn open(&mut self, msg: &Message) -> FsResult<Message> {
         let context = ReadContext::from_req(msg)?;
         let meta = self.store.get_block(context.block_id)?;
+        self.store.record_read(meta.id);
 
         if context.off > meta.len {
             return err_box!(
@@ -164,6 +165,11 @@ impl ReadHandler {
         self.metrics.read_time_us.inc_by(used as i64);
         self.metrics.read_count.inc();
 
+        // The chunk size is only known after reading, wait here before sending it.
+        self.store
+            .io_throttle()
+            .acquire(TrafficClass::ClientRead, region.len() as u64);
+
         Ok(msg.success_with_data(None, region))
     }
 
@@ -197,8 +203,10 @@ impl MessageHandler for ReadHandler {
 
     fn handle(&mut self, msg: &Message) -> FsResult<Message> {
         let request_status = msg.request_status();
+        let mut span = Span::from_message(format!("worker.read.{:?}", request_status), msg);
+        let _guard = span.enter();
 
-        match request_status {
+        let res = match request_status {
             RequestStatus::Open => self.open(msg),
 
             RequestStatus::Running => self.read(msg),
@@ -206,6 +214,12 @@ impl MessageHandler for ReadHandler {
             RequestStatus::Complete => self.complete(msg),
 
             _ => err_box!("Unsupported request type"),
+        };
+
+        match &res {
+            Ok(rep) => span.set_attr("bytes", rep.data_len()),
+            Err(e) => span.set_error(e),
         }
+        res
     }
 }
@@ -22,6 +22,8 @@ use curvine_common::proto::*;
 use curvine_common::state::LoadTaskInfo;
 use curvine_common::utils::SerdeUtils;
 use curvine_common::FsResult;
+use log::info;
+use orpc::common::ByteUnit;
 use orpc::err_box;
 use orpc::handler::MessageHandler;
 use orpc::message::{Builder, Message, RequestStatus};
@@ -48,6 +50,8 @@ impl MessageHandler for WorkerHandler {
 
             RpcCode::SubmitBlockReplicationJob => self.replication_handler.handle(msg),
 
+            RpcCode::WorkerThrottle => self.worker_throttle(msg),
+
             _ => {
                 let h = self.get_handler(msg)?;
                 let res = h.handle(msg);
@@ -113,4 +117,35 @@ impl WorkerHandler {
         self.task_manager.cancel_job(req.job_id)?;
         Ok(msg.success())
     }
+
+    // Change the io throttle limits, an empty request only queries the current limits.
+    pub fn worker_throttle(&self, msg: &Message) -> FsResult<Message> {
+        let req: WorkerThrottleRequest = msg.parse_header()?;
+        let io_throttle = self.store.io_throttle();
+
+        // Validate all classes first, so that a bad request changes nothing.
+        for item in &req.limits {
+            io_throttle.check_limit(&item.traffic_class)?;
+        }
+        for item in &req.limits {
+            io_throttle.set_limit(&item.traffic_class, item.rate)?;
+            info!(
+                "Set io throttle {} to {}/s",
+                item.traffic_class,
+                ByteUnit::byte_to_string(item.rate)
+            );
+        }
+
+        let limits = io_throttle
+            .limits()
+            .into_iter()
+            .map(|(traffic_class, rate)| IoThrottleProto {
+                traffic_class,
+                rate,
+            })
+            .collect();
+        let response = WorkerThrottleResponse { limits };
+
+        Ok(Builder::success(msg).proto_header(response).build())
+    }
 }
@@ -12,15 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "fault-injection")]
+use crate::test::{FaultInjector, WorkerFault};
 use crate::worker::block::BlockStore;
 use crate::worker::handler::WriteContext;
-use crate::worker::{Worker, WorkerMetrics};
+use crate::worker::{TrafficClass, Worker, WorkerMetrics};
 use curvine_common::error::FsError;
 use curvine_common::proto::{BlockWriteResponse, DataHeaderProto};
 use curvine_common::state::{ExtendedBlock, FileAllocMode};
 use curvine_common::FsResult;
 use log::{info, warn};
-use orpc::common::{ByteUnit, TimeSpent};
+use orpc::common::{ByteUnit, Span, TimeSpent};
 use orpc::handler::MessageHandler;
 use orpc::io::LocalFile;
 use orpc::message::{Builder, Message, RequestStatus};
@@ -150,6 +152,12 @@ impl WriteHandler {
     }
 
     pub fn write(&mut self, msg: &Message) -> FsResult<Message> {
+        self.write0(msg, true)
+    }
+
+    // Write the data of the message, throttle is false if the caller has already charged
+    // the data to the client write bandwidth.
+    pub(crate) fn write0(&mut self, msg: &Message, throttle: bool) -> FsResult<Message> {
         let file = try_option_mut!(self.file);
         let context = try_option_mut!(self.context);
         Self::check_context(context, msg)?;
@@ -182,6 +190,12 @@ impl WriteHandler {
                 );
             }
 
+            if throttle {
+                self.store
+                    .io_throttle()
+                    .acquire(TrafficClass::ClientWrite, data_len as u64);
+            }
+
             let spend = TimeSpent::new();
             file.write_region(&msg.data)?;
 
@@ -262,7 +276,16 @@ impl MessageHandler for WriteHandler {
     fn handle(&mut self, msg: &Message) -> FsResult<Message> {
         let request_status = msg.request_status();
 
-        match request_status {
+        #[cfg(feature = "fault-injection")]
+        if FaultInjector::is_enabled() && !matches!(request_status, RequestStatus::Cancel) {
+            FaultInjector::check(self.store.worker_id(), WorkerFault::WriteBlock)?;
+        }
+
+        let mut span = Span::from_message(format!("worker.write.{:?}", request_status), msg);
+        span.set_attr("bytes", msg.data_len());
+        let _guard = span.enter();
+
+        let res = match request_status {
             RequestStatus::Open => self.open(msg),
 
             RequestStatus::Running => self.write(msg),
@@ -272,6 +295,11 @@ impl MessageHandler for WriteHandler {
             RequestStatus::Cancel => self.complete(msg, false),
 
             _ => err_box!("Unsupported request type"),
+        };
+
+        if let Err(e) = &res {
+            span.set_error(e);
         }
+        res
     }
 }
@@ -0,0 +1,243 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_common::conf::WorkerConf;
+use orpc::common::ByteUnit;
+use orpc::{err_box, CommonResult};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// The traffic classes of the worker io, each class has its own bandwidth limit.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TrafficClass {
+    ClientRead = 0,
+    ClientWrite = 1,
+    Load = 2,
+    Replication = 3,
+    Eviction = 4,
+}
+
+impl TrafficClass {
+    pub const ALL: [TrafficClass; 5] = [
+        TrafficClass::ClientRead,
+        TrafficClass::ClientWrite,
+        TrafficClass::Load,
+        TrafficClass::Replication,
+        TrafficClass::Eviction,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrafficClass::ClientRead => "client_read",
+            TrafficClass::ClientWrite => "client_write",
+            TrafficClass::Load => "load",
+            TrafficClass::Replication => "replication",
+            TrafficClass::Eviction => "eviction",
+        }
+    }
+
+    pub fn from_str_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|x| x.as_str() == name)
+    }
+
+    // Foreground traffic is served first, it never waits for the worker total limit.
+    pub fn is_foreground(&self) -> bool {
+        matches!(self, TrafficClass::ClientRead | TrafficClass::ClientWrite)
+    }
+}
+
+// A token bucket of bytes, which holds at most 1 second of the rate.
+// Io is charged after it is reserved, the tokens may go negative for a large io,
+// and the caller waits until the debt is paid.
+struct RateBucket {
+    rate: u64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl RateBucket {
+    fn new(rate: u64) -> Self {
+        Self {
+            rate,
+            tokens: rate as f64,
+            last: Instant::now(),
+        }
+    }
+
+    // A bucket that was unlimited starts full.
+    fn set_rate(&mut self, rate: u64) {
+        self.refill(Instant::now());
+        self.tokens = if self.rate == 0 {
+            rate as f64
+        } else {
+            self.tokens.min(rate as f64)
+        };
+        self.rate = rate;
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let rate = self.rate as f64;
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(rate);
+        self.last = now;
+    }
+
+    // Take the bytes and return how long to wait before doing the io.
+    fn reserve(&mut self, bytes: u64, now: Instant) -> Duration {
+        if self.rate == 0 {
+            return Duration::ZERO;
+        }
+
+        self.refill(now);
+        self.tokens -= bytes as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate as f64)
+        }
+    }
+
+    // Take the bytes without waiting, the debt is capped so that the
+    // other traffic waits at most 1 second more after this traffic stops.
+    fn charge(&mut self, bytes: u64, now: Instant) {
+        if self.rate == 0 {
+            return;
+        }
+
+        self.refill(now);
+        self.tokens = (self.tokens - bytes as f64).max(-(self.rate as f64));
+    }
+}
+
+#[derive(Default)]
+struct ClassStat {
+    bytes: AtomicU64,
+    wait_us: AtomicU64,
+}
+
+// Io bandwidth throttling of a worker, shared by the block handlers, load tasks,
+// replication and tier migration.
+pub struct IoThrottle {
+    total: Mutex<RateBucket>,
+    classes: [Mutex<RateBucket>; 5],
+    stats: [ClassStat; 5],
+}
+
+impl IoThrottle {
+    pub const TOTAL: &'static str = "total";
+
+    pub fn new(conf: &WorkerConf) -> CommonResult<Self> {
+        let rate = |str: &str| -> CommonResult<Mutex<RateBucket>> {
+            let rate = ByteUnit::from_str(str)?.as_byte();
+            Ok(Mutex::new(RateBucket::new(rate)))
+        };
+
+        Ok(Self {
+            total: rate(&conf.io_throttle_total)?,
+            classes: [
+                rate(&conf.io_throttle_client_read)?,
+                rate(&conf.io_throttle_client_write)?,
+                rate(&conf.io_throttle_load)?,
+                rate(&conf.io_throttle_replication)?,
+                rate(&conf.io_throttle_eviction)?,
+            ],
+            stats: Default::default(),
+        })
+    }
+
+    // Reserve bandwidth for the io, returns how long the caller should wait before doing it.
+    pub fn reserve(&self, class: TrafficClass, bytes: u64) -> Duration {
+        let now = Instant::now();
+        let wait = self.classes[class as usize]
+            .lock()
+            .unwrap()
+            .reserve(bytes, now);
+
+        let mut total = self.total.lock().unwrap();
+        let wait = if class.is_foreground() {
+            total.charge(bytes, now);
+            wait
+        } else {
+            wait.max(total.reserve(bytes, now))
+        };
+        drop(total);
+
+        let stat = &self.stats[class as usize];
+        stat.bytes.fetch_add(bytes, Ordering::Relaxed);
+        stat.wait_us
+            .fetch_add(wait.as_micros() as u64, Ordering::Relaxed);
+        wait
+    }
+
+    // Wait for the bandwidth in a blocking thread.
+    pub fn acquire(&self, class: TrafficClass, bytes: u64) {
+        let wait = self.reserve(class, bytes);
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+    }
+
+    pub async fn async_acquire(&self, class: TrafficClass, bytes: u64) {
+        let wait = self.reserve(class, bytes);
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn bucket(&self, name: &str) -> CommonResult<&Mutex<RateBucket>> {
+        if name == Self::TOTAL {
+            return Ok(&self.total);
+        }
+        match TrafficClass::from_str_name(name) {
+            Some(class) => Ok(&self.classes[class as usize]),
+            None => err_box!("Unknown traffic class {}", name),
+        }
+    }
+
+    pub fn check_limit(&self, name: &str) -> CommonResult<()> {
+        self.bucket(name).map(|_| ())
+    }
+
+    // Change the limit of a traffic class or the worker total, 0 means unlimited.
+    pub fn set_limit(&self, name: &str, rate: u64) -> CommonResult<()> {
+        self.bucket(name)?.lock().unwrap().set_rate(rate);
+        Ok(())
+    }
+
+    pub fn limit(&self, name: &str) -> CommonResult<u64> {
+        Ok(self.bucket(name)?.lock().unwrap().rate)
+    }
+
+    // The limits of the worker total and all traffic classes.
+    pub fn limits(&self) -> Vec<(String, u64)> {
+        let mut res = vec![(Self::TOTAL.to_string(), self.total.lock().unwrap().rate)];
+        for class in TrafficClass::ALL {
+            let rate = self.classes[class as usize].lock().unwrap().rate;
+            res.push((class.as_str().to_string(), rate));
+        }
+        res
+    }
+
+    // Bytes charged to the class.
+    pub fn bytes(&self, class: TrafficClass) -> u64 {
+        self.stats[class as usize].bytes.load(Ordering::Relaxed)
+    }
+
+    // Time the io of the class waited for bandwidth.
+    pub fn wait_us(&self, class: TrafficClass) -> u64 {
+        self.stats[class as usize].wait_us.load(Ordering::Relaxed)
+    }
+}
@@ -22,6 +22,9 @@ pub mod block;
 mod worker_metrics;
 pub use self::worker_metrics::WorkerMetrics;
 
+mod io_throttle;
+pub use self::io_throttle::*;
+
 pub mod handler;
 mod replication;
 pub mod task;
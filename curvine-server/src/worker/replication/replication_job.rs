@@ -13,21 +13,54 @@
 // limitations under the License.
 
 use curvine_common::proto::SubmitBlockReplicationRequest;
-use curvine_common::state::{StorageType, WorkerAddress};
+use curvine_common::state::{
+    ExtendedBlock, FileType, LocatedBlock, StorageType, StripedBlock, WorkerAddress,
+};
 use curvine_common::utils::ProtoUtils;
 
+// Rebuild a lost unit of an erasure-coded block from the surviving units.
+pub struct ReconstructTask {
+    pub source: LocatedBlock,
+    pub target_index: u32,
+    // The block size of the file, the rebuilt unit is sized by the unit capacity of it.
+    pub block_size: i64,
+}
+
 pub struct ReplicationJob {
     pub block_id: i64,
     pub target_worker_addr: WorkerAddress,
     pub storage_type: Option<StorageType>,
+    pub reconstruct: Option<ReconstructTask>,
 }
 
 impl From<SubmitBlockReplicationRequest> for ReplicationJob {
     fn from(val: SubmitBlockReplicationRequest) -> Self {
+        let reconstruct = val.ec_reconstruct.map(|ec| {
+            let storage_type = StorageType::from(ec.storage_type);
+            let block =
+                ExtendedBlock::new(val.block_id, ec.block_len, storage_type, FileType::File);
+            let locs = ec
+                .sources
+                .iter()
+                .map(ProtoUtils::worker_address_from_pb)
+                .collect();
+            let striped =
+                StripedBlock::new(ProtoUtils::ec_policy_from_pb(ec.policy), ec.source_indices);
+            ReconstructTask {
+                source: LocatedBlock::with_striped(block, locs, striped),
+                target_index: ec.target_index,
+                block_size: ec.block_size,
+            }
+        });
+
         ReplicationJob {
             block_id: val.block_id,
             target_worker_addr: ProtoUtils::worker_address_from_pb(&val.target_worker_info),
-            storage_type: None,
+            storage_type: reconstruct
+                .as_ref()
+                .map(|x| x.source.block.storage_type)
+                .or(val.storage_type.map(StorageType::from)),
+            reconstruct,
         }
     }
 }
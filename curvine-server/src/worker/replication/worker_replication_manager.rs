@@ -13,16 +13,18 @@
 // limitations under the License.
 
 use crate::worker::block::{BlockState, BlockStore, MasterClient};
-use crate::worker::replication::replication_job::ReplicationJob;
-use curvine_client::block::BlockWriterRemote;
+use crate::worker::replication::replication_job::{ReconstructTask, ReplicationJob};
+use crate::worker::TrafficClass;
+use curvine_client::block::{BlockWriterRemote, StripedBlockReader};
 use curvine_client::file::FsContext;
 use curvine_common::conf::ClusterConf;
 use curvine_common::fs::RpcCode;
 use curvine_common::proto::{ReportBlockReplicationRequest, ReportBlockReplicationResponse};
-use curvine_common::state::{ExtendedBlock, FileType};
+use curvine_common::state::{ExtendedBlock, FileType, WorkerAddress};
 use log::{error, info};
 use once_cell::sync::OnceCell;
 use orpc::runtime::{AsyncRuntime, RpcRuntime};
+use orpc::sys::DataSlice;
 use orpc::{err_box, try_option, CommonResult};
 use std::sync::Arc;
 use tokio::sync::mpsc::{Receiver, Sender};
@@ -115,14 +117,18 @@ impl WorkerReplicationManager {
             .acquire_owned()
             .await
             .unwrap();
+        if let Some(task) = &job.reconstruct {
+            return self.reconstruct_block(task, &job.target_worker_addr).await;
+        }
+
         let block_meta = self.block_store.get_block(job.block_id)?;
         if block_meta.state != BlockState::Finalized {
             return err_box!("Block: {} is not finalized", job.block_id);
         }
-        // update the storage type for the replication job.
-        job.with_storage_type(block_meta.storage_type());
-        let extend_block =
-            ExtendedBlock::new(block_meta.id, 0, block_meta.storage_type(), FileType::File);
+        // The copy keeps the storage type of the source block, unless the master asks for another one.
+        let storage_type = job.storage_type.unwrap_or(block_meta.storage_type());
+        job.with_storage_type(storage_type);
+        let extend_block = ExtendedBlock::new(block_meta.id, 0, storage_type, FileType::File);
         info!(
             "Replicating block_id: {} from {} to {}",
             job.block_id,
@@ -137,9 +143,13 @@ impl WorkerReplicationManager {
         )
         .await?;
         let mut reader = block_meta.create_reader(0)?;
+        let io_throttle = self.block_store.io_throttle();
         let mut remaining = block_meta.len;
         while remaining > 0 {
             let size = remaining.min(self.replicate_chunk_size as i64);
+            io_throttle
+                .async_acquire(TrafficClass::Replication, size as u64)
+                .await;
             let slice = reader.read_region(true, size as i32)?;
             writer.write(slice).await?;
             remaining -= size;
@@ -149,6 +159,53 @@ impl WorkerReplicationManager {
         Ok(())
     }
 
+    // Decode the lost unit stripe by stripe and write it to the target worker.
+    async fn reconstruct_block(
+        &self,
+        task: &ReconstructTask,
+        target: &WorkerAddress,
+    ) -> CommonResult<()> {
+        let striped = try_option!(task.source.striped.as_ref());
+        let policy = striped.policy;
+        let block = &task.source.block;
+        let target_index = task.target_index as usize;
+        info!(
+            "Reconstructing unit {} of block_id: {} with {} to {}",
+            target_index, block.id, policy, target.worker_id
+        );
+
+        let unit_block = ExtendedBlock::new(block.id, 0, block.storage_type, FileType::File);
+        let mut writer = BlockWriterRemote::with_block_size(
+            &self.fs_client_context,
+            unit_block,
+            target.clone(),
+            0,
+            policy.unit_capacity(task.block_size),
+        )
+        .await?;
+        let mut reader =
+            StripedBlockReader::new(self.fs_client_context.clone(), task.source.clone(), 0)?;
+
+        let io_throttle = self.block_store.io_throttle();
+        let stripes = (block.len + policy.stripe_size() - 1) / policy.stripe_size();
+        for stripe in 0..stripes {
+            let cell = reader.read_unit_cell(stripe, target_index).await?;
+            if !cell.is_empty() {
+                // Decoding a cell reads one cell from each data unit.
+                let bytes = cell.len() as u64 * policy.data_units as u64;
+                io_throttle
+                    .async_acquire(TrafficClass::Replication, bytes)
+                    .await;
+                writer.write(DataSlice::buffer(cell)).await?;
+            }
+        }
+        reader.complete().await?;
+
+        writer.flush().await?;
+        writer.complete().await?;
+        Ok(())
+    }
+
     pub fn accept_job(&self, job: ReplicationJob) -> CommonResult<()> {
         if let Err(e) = self.jobs_queue_sender.try_send(job) {
             return err_box!("Failed to queue replication job: {}", e);
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::worker::block::{BlockMeta, BlockState};
+use crate::worker::block::{BlockMeta, BlockState, MOVING_SUFFIX};
 use crate::worker::storage::{DirState, StorageVersion, ACTIVE_DIR, STAGING_DIR};
 use curvine_common::conf::WorkerDataDir;
 use curvine_common::state::{ExtendedBlock, StorageType};
@@ -245,6 +245,11 @@ impl VfsDir {
         }
 
         for file in staging_dir {
+            // A block copy left by an interrupted tier migration.
+            if file.ends_with(MOVING_SUFFIX) {
+                FileUtils::delete_path(&file, false)?;
+                continue;
+            }
             if let Ok(v) = BlockMeta::from_file(&file, BlockState::Recovering, self) {
                 vec.push(v);
             }
@@ -14,6 +14,7 @@
 
 use crate::common::UfsFactory;
 use crate::worker::task::TaskContext;
+use crate::worker::{IoThrottle, TrafficClass};
 use curvine_client::file::CurvineFileSystem;
 use curvine_client::rpc::JobMasterClient;
 use curvine_client::unified::{CacheSyncReader, UfsFileSystem, UnifiedReader, UnifiedWriter};
@@ -29,6 +30,7 @@ pub struct LoadTaskRunner {
     task: Arc<TaskContext>,
     fs: CurvineFileSystem,
     factory: Arc<UfsFactory>,
+    io_throttle: Arc<IoThrottle>,
     master_client: JobMasterClient,
     progress_interval_ms: u64,
     task_timeout_ms: u64,
@@ -39,6 +41,7 @@ impl LoadTaskRunner {
         task: Arc<TaskContext>,
         fs: CurvineFileSystem,
         factory: Arc<UfsFactory>,
+        io_throttle: Arc<IoThrottle>,
         progress_interval_ms: u64,
         task_timeout_ms: u64,
     ) -> Self {
@@ -47,6 +50,7 @@ impl LoadTaskRunner {
             task,
             fs,
             factory,
+            io_throttle,
             master_client,
             progress_interval_ms,
             task_timeout_ms,
@@ -100,6 +104,9 @@ impl LoadTaskRunner {
                 break;
             }
 
+            self.io_throttle
+                .async_acquire(TrafficClass::Load, chunk.len() as u64)
+                .await;
             writer.async_write(chunk).await?;
             total_cost_ms += spend.used_ms();
 
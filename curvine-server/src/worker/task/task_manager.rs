@@ -15,6 +15,7 @@
 use crate::common::UfsFactory;
 use crate::worker::task::load_task_runner::LoadTaskRunner;
 use crate::worker::task::TaskStore;
+use crate::worker::IoThrottle;
 use curvine_client::file::{CurvineFileSystem, FsContext};
 use curvine_common::conf::ClusterConf;
 use curvine_common::state::LoadTaskInfo;
@@ -29,6 +30,7 @@ pub struct TaskManager {
     fs: CurvineFileSystem,
     tasks: TaskStore,
     factory: Arc<UfsFactory>,
+    io_throttle: Arc<IoThrottle>,
     progress_interval_ms: u64,
     task_timeout_ms: u64,
     worker_task_semaphore: Arc<Semaphore>,
@@ -45,6 +47,7 @@ impl TaskManager {
     ///
     /// * `rt` - An existing Arc-wrapped Runtime for async task execution
     /// * `conf` - The cluster configuration containing job and client settings
+    /// * `io_throttle` - The worker io throttle, load tasks are charged to the `load` class
     ///
     /// # Returns
     ///
@@ -57,6 +60,8 @@ impl TaskManager {
     /// - This ensures data distribution across all workers instead of local bias
     /// - Initializes filesystem client with the modified configuration
     /// - Sets up task store and timing configurations from job settings
+    /// - **Bandwidth Control**: Every chunk copied by a load task waits for the
+    ///   `load` traffic class and the worker total limit of `io_throttle`.
     /// - **Concurrency Control**: Uses a Semaphore to limit concurrent load tasks
     ///   based on `conf.job.load_task_concurrency_limit` to prevent excessive
     ///   bandwidth and resource consumption during data copy operations.
@@ -68,7 +73,11 @@ impl TaskManager {
     /// # Limit concurrent load tasks to prevent resource exhaustion
     /// worker_max_concurrent_tasks = 10
     /// ```
-    pub fn with_rt(rt: Arc<Runtime>, conf: &ClusterConf) -> FsResult<Self> {
+    pub fn with_rt(
+        rt: Arc<Runtime>,
+        conf: &ClusterConf,
+        io_throttle: Arc<IoThrottle>,
+    ) -> FsResult<Self> {
         let mut new_conf = conf.clone();
         new_conf.client.hostname = "localhost".to_string();
 
@@ -80,6 +89,7 @@ impl TaskManager {
             fs,
             tasks: TaskStore::new(),
             factory,
+            io_throttle,
             progress_interval_ms: conf.job.task_report_interval.as_millis() as u64,
             task_timeout_ms: conf.job.task_timeout.as_millis() as u64,
             worker_task_semaphore,
@@ -130,6 +140,7 @@ impl TaskManager {
             context.clone(),
             self.fs.clone(),
             self.factory.clone(),
+            self.io_throttle.clone(),
             self.progress_interval_ms,
             self.task_timeout_ms,
         );
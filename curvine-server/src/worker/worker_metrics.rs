@@ -14,7 +14,8 @@
 
 use crate::worker::block::BlockStore;
 use crate::worker::storage::Dataset;
-use orpc::common::{Counter, CounterVec, Gauge, Metrics as m, Metrics};
+use crate::worker::TrafficClass;
+use orpc::common::{Counter, CounterVec, Gauge, GaugeVec, Metrics as m, Metrics};
 use orpc::sys::SysUtils;
 use orpc::CommonResult;
 use std::fmt::{Debug, Formatter};
@@ -41,6 +42,14 @@ pub struct WorkerMetrics {
     pub(crate) num_blocks_to_delete: Gauge,
 
     pub(crate) used_memory_bytes: Gauge,
+
+    pub(crate) tier_promoted_blocks: Counter,
+    pub(crate) tier_demoted_blocks: Counter,
+    pub(crate) tier_moved_bytes: Counter,
+
+    pub(crate) io_throttle_rate: GaugeVec,
+    pub(crate) io_throttle_bytes: CounterVec,
+    pub(crate) io_throttle_wait_ms: CounterVec,
 }
 
 impl WorkerMetrics {
@@ -70,6 +79,32 @@ impl WorkerMetrics {
             )?,
 
             used_memory_bytes: m::new_gauge("used_memory_bytes", "Total memory used")?,
+
+            tier_promoted_blocks: m::new_counter(
+                "tier_promoted_blocks",
+                "Number of blocks promoted to a faster tier",
+            )?,
+            tier_demoted_blocks: m::new_counter(
+                "tier_demoted_blocks",
+                "Number of blocks demoted to a slower tier",
+            )?,
+            tier_moved_bytes: m::new_counter("tier_moved_bytes", "Bytes moved between tiers")?,
+
+            io_throttle_rate: m::new_gauge_vec(
+                "io_throttle_rate",
+                "Io bandwidth limit in bytes per second, 0 is unlimited",
+                &["class"],
+            )?,
+            io_throttle_bytes: m::new_counter_vec(
+                "io_throttle_bytes",
+                "Bytes charged to the io throttle",
+                &["class"],
+            )?,
+            io_throttle_wait_ms: m::new_counter_vec(
+                "io_throttle_wait_ms",
+                "Milliseconds spent waiting for io bandwidth",
+                &["class"],
+            )?,
         };
 
         Ok(wm)
@@ -96,6 +131,22 @@ impl WorkerMetrics {
 
         let total_disks = state.dir_iter().count();
         self.store_total_disks.set(total_disks as i64);
+        drop(state);
+
+        let io_throttle = self.store.io_throttle();
+        for (name, rate) in io_throttle.limits() {
+            self.io_throttle_rate
+                .with_label_values(&[name.as_str()])
+                .set(rate as i64);
+        }
+        // The throttle keeps its own totals, the counters catch up with them.
+        for class in TrafficClass::ALL {
+            let label = [class.as_str()];
+            let bytes = self.io_throttle_bytes.with_label_values(&label);
+            bytes.inc_by(io_throttle.bytes(class) as i64 - bytes.get());
+            let wait_ms = self.io_throttle_wait_ms.with_label_values(&label);
+            wait_ms.inc_by((io_throttle.wait_us(class) / 1000) as i64 - wait_ms.get());
+        }
 
         Metrics::text_output()
     }
@@ -23,7 +23,7 @@ use curvine_common::state::{HeartbeatStatus, WorkerAddress};
 use curvine_web::server::{WebHandlerService, WebServer};
 use log::info;
 use once_cell::sync::OnceCell;
-use orpc::common::{LocalTime, Logger};
+use orpc::common::{LocalTime, Logger, Tracer};
 use orpc::handler::HandlerService;
 use orpc::io::net::ConnState;
 use orpc::runtime::{RpcRuntime, Runtime};
@@ -49,7 +49,7 @@ impl WorkerService {
     pub fn with_conf(conf: &ClusterConf, rt: Arc<Runtime>) -> CommonResult<Self> {
         let store: BlockStore = BlockStore::new(&conf.cluster_id, conf)?;
 
-        let task_manager = TaskManager::with_rt(rt.clone(), conf)?;
+        let task_manager = TaskManager::with_rt(rt.clone(), conf, store.io_throttle().clone())?;
 
         let replication_manager =
             WorkerReplicationManager::new(&store, &rt, conf, &task_manager.get_fs_context());
@@ -108,6 +108,7 @@ pub struct Worker {
 impl Worker {
     pub fn with_conf(conf: ClusterConf) -> CommonResult<Self> {
         Logger::init(conf.worker.log.clone());
+        Tracer::init(&conf.trace, format!("{}-worker", conf.cluster_id))?;
 
         let rt = Arc::new(conf.worker_server_conf().create_runtime());
         let service: WorkerService = WorkerService::with_conf(&conf, rt.clone())?;
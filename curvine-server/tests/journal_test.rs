@@ -19,12 +19,14 @@ use curvine_common::state::{
     BlockLocation, ClientAddress, CommitBlock, CreateFileOpts, MountOptions, OpenFlags,
     RenameFlags, WorkerInfo,
 };
+use curvine_server::master::backup::MetaBackupManager;
 use curvine_server::master::fs::MasterFilesystem;
 use curvine_server::master::journal::{JournalLoader, JournalSystem};
 use curvine_server::master::{Master, MountManager};
 use log::info;
-use orpc::common::{Logger, TimeSpent};
+use orpc::common::{Logger, TimeSpent, Utils};
 use orpc::io::net::NetUtils;
+use orpc::runtime::Runtime;
 use orpc::{err_box, CommonResult};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -300,3 +302,64 @@ fn test_master_restart_with_snapshot_recovery() -> CommonResult<()> {
 
     Ok(())
 }
+
+// Take backups, check the retention and restore a master from an older backup.
+#[test]
+fn test_meta_backup_and_restore() -> CommonResult<()> {
+    Logger::default();
+    Master::init_test_metrics();
+    let mut conf = ClusterConf {
+        testing: true,
+        ..Default::default()
+    };
+    conf.change_test_meta_dir("meta-backup");
+    conf.master.backup_dir = Utils::cur_dir_sub("../testing/meta-backup/store");
+    conf.master.backup_retain_num = 2;
+    let _ = std::fs::remove_dir_all(&conf.master.backup_dir);
+
+    let js = JournalSystem::from_conf(&conf)?;
+    let fs = MasterFilesystem::with_js(&conf, &js);
+    let rt = Arc::new(Runtime::new("backup-test", 1, 1));
+    let manager = MetaBackupManager::new(&conf, fs.clone(), &js, rt)?;
+
+    let mut backups = vec![];
+    for dir in ["/a", "/b"] {
+        fs.mkdir(dir, false)?;
+        backups.push(manager.backup()?);
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(manager.status().last_backup.as_ref(), backups.last());
+
+    // A backup started in the background is shown by the status.
+    fs.mkdir("/c", false)?;
+    assert!(manager.start_backup().running);
+    while manager.status().running {
+        thread::sleep(Duration::from_millis(10));
+    }
+    let status = manager.status();
+    assert_eq!(status.last_error, None);
+    backups.push(status.last_backup.unwrap());
+
+    // Only the latest 2 backups are kept.
+    let list = manager.list()?;
+    assert_eq!(list, backups[1..].to_vec());
+    assert!(list.iter().all(|x| x.files > 0 && x.len > 0));
+
+    drop(manager);
+    drop(fs);
+    drop(js);
+
+    let info = MetaBackupManager::restore(&conf, &backups[1].name)?;
+    assert_eq!(info, backups[1]);
+    assert!(MetaBackupManager::restore(&conf, &backups[0].name).is_err());
+
+    conf.format_master = false;
+    let js = JournalSystem::from_conf(&conf)?;
+    js.apply_snapshot()?;
+    let fs = MasterFilesystem::with_js(&conf, &js);
+    assert!(fs.exists("/a")?);
+    assert!(fs.exists("/b")?);
+    assert!(!fs.exists("/c")?);
+
+    Ok(())
+}
@@ -18,19 +18,27 @@ use curvine_common::proto::{
     CreateFileRequest, DeleteRequest, MkdirOptsProto, MkdirRequest, RenameRequest,
 };
 use curvine_common::state::{
-    BlockLocation, ClientAddress, CommitBlock, CreateFileOpts, WorkerInfo,
+    BlockLocation, BlockReportInfo, BlockReportList, BlockReportStatus, ClientAddress, CommitBlock,
+    CreateFileOpts, CreateFileOptsBuilder, DirPolicy, FileType, FindOpts, FsckAction,
+    FsckBlockState, FsckOpts, FsckOrphanBlock, FsckReport, MkdirOptsBuilder, StorageType,
+    WorkerInfo,
 };
 use curvine_common::state::{OpenFlags, RenameFlags, SetAttrOptsBuilder};
 use curvine_server::master::fs::{FsRetryCache, MasterFilesystem, OperationStatus};
+use curvine_server::master::image::{ImageReader, ImageReport};
 use curvine_server::master::journal::JournalSystem;
+use curvine_server::master::meta::{LockMode, PathLocks};
 use curvine_server::master::replication::master_replication_manager::MasterReplicationManager;
 use curvine_server::master::{JobHandler, JobManager, Master, MasterHandler, RpcContext};
+use curvine_server::master::{MasterRateLimiter, RateClass};
 use orpc::common::LocalTime;
 use orpc::common::Utils;
 use orpc::message::Builder;
 use orpc::runtime::AsyncRuntime;
-use orpc::CommonResult;
-use std::sync::Arc;
+use orpc::{CommonError, CommonResult};
+use std::collections::HashMap;
+use std::sync::{Arc, Barrier};
+use std::time::Duration;
 // Test the master filesystem function separately.
 // This test does not require a cluster startup.
 // Returns (MasterFilesystem, JournalSystem) to ensure proper resource cleanup.
@@ -412,6 +420,514 @@ fn list_status(fs: &MasterFilesystem) -> CommonResult<()> {
 
     let _ = list_status_with_glob(fs);
     let _ = list_status_without_glob(fs);
+    list_status_page(fs)?;
+    Ok(())
+}
+
+fn list_status_page(fs: &MasterFilesystem) -> CommonResult<()> {
+    for name in ["f3", "f1", "f5", "f2", "f4"] {
+        fs.create(format!("/page/{}", name), true)?;
+    }
+
+    let (list, has_more) = fs.list_status_page("/page", None, 2)?;
+    let names: Vec<&str> = list.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(names, vec!["f1", "f2"]);
+    assert!(has_more);
+
+    let (list, has_more) = fs.list_status_page("/page", Some("f2"), 2)?;
+    let names: Vec<&str> = list.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(names, vec!["f3", "f4"]);
+    assert!(has_more);
+
+    let (list, has_more) = fs.list_status_page("/page", Some("f4"), 2)?;
+    assert_eq!(list.len(), 1);
+    assert_eq!(list[0].name, "f5");
+    assert!(!has_more);
+
+    // A start name that is not a child continues from the next name.
+    let (list, has_more) = fs.list_status_page("/page", Some("f2a"), 0)?;
+    assert_eq!(list.len(), 3);
+    assert_eq!(list[0].name, "f3");
+    assert!(!has_more);
+
+    Ok(())
+}
+
+#[test]
+fn test_dir_policy_inheritance() -> CommonResult<()> {
+    let (fs, _js) = new_fs(true, "dir_policy");
+    fs.mkdir("/policy/a/b", true)?;
+
+    let set_policy = |path: &str, policy: DirPolicy| {
+        let opts = SetAttrOptsBuilder::new().dir_policy(policy).build();
+        fs.set_attr(path, opts)
+    };
+    set_policy(
+        "/policy",
+        DirPolicy {
+            storage_type: Some(StorageType::Ssd),
+            replicas: Some(3),
+            ..Default::default()
+        },
+    )?;
+    set_policy(
+        "/policy/a",
+        DirPolicy {
+            replicas: Some(2),
+            block_size: Some(8 * 1024 * 1024),
+            ..Default::default()
+        },
+    )?;
+
+    // The nearest directory wins for each attribute.
+    let (own, effective) = fs.get_dir_policy("/policy/a/b")?;
+    assert!(own.is_empty());
+    assert_eq!(effective.storage_type, Some(StorageType::Ssd));
+    assert_eq!(effective.replicas, Some(2));
+    assert_eq!(effective.block_size, Some(8 * 1024 * 1024));
+
+    let flags = OpenFlags::new_create();
+    let opts = CreateFileOptsBuilder::new().create_parent(true).build();
+    let status = fs.create_with_opts("/policy/a/b/c/inherit.log", opts, flags)?;
+    assert_eq!(status.replicas, 2);
+    assert_eq!(status.block_size, 8 * 1024 * 1024);
+    assert_eq!(status.storage_policy.storage_type, StorageType::Ssd);
+    let status = fs.file_status("/policy/a/b/c")?;
+    assert_eq!(status.storage_policy.storage_type, StorageType::Ssd);
+
+    // Attributes set by the client are kept.
+    let opts = CreateFileOptsBuilder::new()
+        .replicas(1)
+        .storage_type(StorageType::Mem)
+        .build();
+    let status = fs.create_with_opts("/policy/a/explicit.log", opts, flags)?;
+    assert_eq!(status.replicas, 1);
+    assert_eq!(status.block_size, 8 * 1024 * 1024);
+    assert_eq!(status.storage_policy.storage_type, StorageType::Mem);
+
+    let opts = MkdirOptsBuilder::new()
+        .storage_type(StorageType::Hdd)
+        .build();
+    fs.mkdir_with_opts("/policy/a/hdd", opts)?;
+    let status = fs.file_status("/policy/a/hdd")?;
+    assert_eq!(status.storage_policy.storage_type, StorageType::Hdd);
+
+    // An empty policy clears the policy of the directory.
+    set_policy("/policy/a", DirPolicy::default())?;
+    let (_, effective) = fs.get_dir_policy("/policy/a")?;
+    assert_eq!(effective.replicas, Some(3));
+    assert_eq!(effective.block_size, None);
+
+    let invalid = DirPolicy {
+        replicas: Some(0),
+        ..Default::default()
+    };
+    assert!(set_policy("/policy", invalid).is_err());
+    assert!(set_policy("/policy/a/explicit.log", DirPolicy::default()).is_err());
+    assert!(fs.get_dir_policy("/policy/a/explicit.log").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_set_storage_policy() -> CommonResult<()> {
+    let (fs, _js) = new_fs(true, "storage_policy");
+    fs.mkdir("/storage/a", true)?;
+
+    let path = "/storage/a/1.log";
+    let addr = ClientAddress::default();
+    fs.create(path, true)?;
+    let b1 = fs.add_block(path, addr.clone(), vec![], vec![], 0, None)?;
+    let commit = CommitBlock {
+        block_id: b1.block.id,
+        block_len: 1024,
+        locations: vec![BlockLocation {
+            worker_id: b1.locs[0].worker_id,
+            storage_type: Default::default(),
+        }],
+    };
+    fs.complete_file(path, 1024, vec![commit], &addr.client_name, false)?;
+    // Blocks of a file being written are not moved.
+    fs.create("/storage/2.log", true)?;
+
+    // Without recursive, only the directory is changed.
+    let blocks = fs.set_storage_policy("/storage", StorageType::Hdd, false)?;
+    assert!(blocks.is_empty());
+    let status = fs.file_status("/storage")?;
+    assert_eq!(status.storage_policy.storage_type, StorageType::Hdd);
+    let status = fs.file_status(path)?;
+    assert_ne!(status.storage_policy.storage_type, StorageType::Hdd);
+
+    let blocks = fs.set_storage_policy("/storage", StorageType::Ssd, true)?;
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].0, b1.block.id);
+    assert_eq!(blocks[0].1, 1024);
+    for path in ["/storage/a", "/storage/a/1.log", "/storage/2.log"] {
+        let status = fs.file_status(path)?;
+        assert_eq!(status.storage_policy.storage_type, StorageType::Ssd);
+    }
+
+    assert!(fs
+        .set_storage_policy("/storage", StorageType::Ufs, true)
+        .is_err());
+    assert!(fs
+        .set_storage_policy("/storage/none", StorageType::Mem, true)
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_content_summary_and_find() -> CommonResult<()> {
+    let (mut fs, _js) = new_fs(true, "summary_test");
+    fs.mkdir("/sum/a/b", true)?;
+    fs.create("/sum/a/2.log", true)?;
+    fs.create("/sum/a/b/3.txt", true)?;
+
+    let path = "/sum/1.log";
+    let addr = ClientAddress::default();
+    fs.create(path, true)?;
+    let b1 = fs.add_block(path, addr.clone(), vec![], vec![], 0, None)?;
+    let commit = CommitBlock {
+        block_id: b1.block.id,
+        block_len: 1024,
+        locations: vec![BlockLocation {
+            worker_id: b1.locs[0].worker_id,
+            storage_type: Default::default(),
+        }],
+    };
+    fs.complete_file(path, 1024, vec![commit], &addr.client_name, false)?;
+    let status = fs.file_status(path)?;
+
+    let summary = fs.content_summary("/sum")?;
+    assert_eq!(summary.directory_count, 3);
+    assert_eq!(summary.file_count, 3);
+    assert_eq!(summary.length, 1024);
+    assert_eq!(summary.space_consumed, 1024 * status.replicas as i64);
+    let storage_type = status.storage_policy.storage_type;
+    assert_eq!(summary.type_lengths.get(&storage_type), Some(&1024));
+    let info = fs.master_info()?;
+    assert_eq!(summary.space_quota, info.available + info.fs_used);
+    assert_eq!(summary.space_quota_remaining, info.available);
+
+    let summary = fs.content_summary(path)?;
+    assert_eq!(summary.directory_count, 0);
+    assert_eq!(summary.file_count, 1);
+
+    let find = |opts: FindOpts, start_after: Option<&str>, limit: usize| {
+        let (list, has_more) = fs.find("/sum", opts, start_after, limit)?;
+        let paths: Vec<String> = list.into_iter().map(|x| x.path).collect();
+        Ok::<_, CommonError>((paths, has_more))
+    };
+
+    let by_name = FindOpts {
+        name: Some("*.log".to_string()),
+        ..Default::default()
+    };
+    let (paths, has_more) = find(by_name.clone(), None, 0)?;
+    assert_eq!(paths, vec!["/sum/1.log", "/sum/a/2.log"]);
+    assert!(!has_more);
+
+    // Paging continues after the last returned path.
+    let (paths, has_more) = find(by_name.clone(), None, 1)?;
+    assert_eq!(paths, vec!["/sum/1.log"]);
+    assert!(has_more);
+    let (paths, has_more) = find(by_name, Some("/sum/1.log"), 1)?;
+    assert_eq!(paths, vec!["/sum/a/2.log"]);
+    assert!(!has_more);
+
+    let dirs = FindOpts {
+        file_type: Some(FileType::Dir),
+        ..Default::default()
+    };
+    let (paths, _) = find(dirs.clone(), None, 0)?;
+    assert_eq!(paths, vec!["/sum", "/sum/a", "/sum/a/b"]);
+    let (paths, _) = find(dirs, Some("/sum/a/2.log"), 0)?;
+    assert_eq!(paths, vec!["/sum/a/b"]);
+
+    let by_size = FindOpts {
+        min_size: Some(1),
+        ..Default::default()
+    };
+    let (paths, _) = find(by_size, None, 0)?;
+    assert_eq!(paths, vec!["/sum/1.log"]);
+
+    let by_depth = FindOpts {
+        file_type: Some(FileType::File),
+        max_depth: Some(1),
+        ..Default::default()
+    };
+    let (paths, _) = find(by_depth, None, 0)?;
+    assert_eq!(paths, vec!["/sum/1.log"]);
+
+    assert!(find(FindOpts::default(), Some("/other/1.log"), 0).is_err());
+
+    // A walk over several batches, the locks are released between them, gives the same result.
+    let all = find(FindOpts::default(), None, 0)?;
+    fs.conf = Arc::new(MasterConf {
+        tree_walk_batch: 2,
+        ..(*fs.conf).clone()
+    });
+    let find = |start_after: Option<&str>, limit: usize| {
+        let (list, has_more) = fs.find("/sum", FindOpts::default(), start_after, limit)?;
+        let paths: Vec<String> = list.into_iter().map(|x| x.path).collect();
+        Ok::<_, CommonError>((paths, has_more))
+    };
+    assert_eq!(find(None, 0)?, all);
+    let (paths, has_more) = find(Some("/sum/a"), 2)?;
+    assert_eq!(paths, vec!["/sum/a/2.log", "/sum/a/b"]);
+    assert!(has_more);
+
+    let summary = fs.content_summary("/sum")?;
+    assert_eq!(summary.directory_count, 3);
+    assert_eq!(summary.file_count, 3);
+    assert_eq!(summary.length, 1024);
+
+    Ok(())
+}
+
+#[test]
+fn test_fsck() -> CommonResult<()> {
+    let (fs, _js) = new_fs(true, "fsck_test");
+    let mut worker = WorkerInfo::default();
+    worker.address.worker_id = 101;
+    fs.add_test_worker(worker);
+
+    let addr = ClientAddress::default();
+    let write = |path: &str, workers: &[u32]| {
+        fs.create(path, true)?;
+        let b1 = fs.add_block(path, addr.clone(), vec![], vec![], 0, None)?;
+        let locations = workers
+            .iter()
+            .map(|x| BlockLocation {
+                worker_id: *x,
+                storage_type: Default::default(),
+            })
+            .collect();
+        let commit = CommitBlock {
+            block_id: b1.block.id,
+            block_len: 1024,
+            locations,
+        };
+        fs.complete_file(path, 1024, vec![commit], &addr.client_name, false)?;
+        Ok::<_, CommonError>(b1.block.id)
+    };
+
+    let ok_block = write("/fsck/ok.log", &[100])?;
+    write("/fsck/over.log", &[100, 101])?;
+    // Worker 999 is not registered, the only replica is lost.
+    write("/fsck/lost.log", &[999])?;
+    write("/fsck/a/under.log", &[100])?;
+    fs.set_attr(
+        "/fsck/a/under.log",
+        SetAttrOptsBuilder::new().replicas(2).build(),
+    )?;
+    fs.create("/fsck/open.log", true)?;
+
+    // A block reported by the worker that the file does not have.
+    fs.block_report(BlockReportList {
+        cluster_id: "".to_string(),
+        worker_id: 100,
+        full_report: false,
+        total_len: 1024,
+        blocks: vec![BlockReportInfo::new(
+            ok_block + 1,
+            BlockReportStatus::Finalized,
+            StorageType::Disk,
+            1024,
+        )],
+    })?;
+
+    let opts = FsckOpts {
+        check_orphans: true,
+        ..Default::default()
+    };
+    let report = fs.fsck("/fsck", opts)?;
+    assert_eq!(report.total_dirs, 2);
+    assert_eq!(report.total_files, 5);
+    assert_eq!(report.total_blocks, 4);
+    assert_eq!(report.missing_blocks, 1);
+    assert_eq!(report.under_replicated_blocks, 1);
+    assert_eq!(report.over_replicated_blocks, 1);
+    assert_eq!(report.open_files, 1);
+    assert_eq!(report.corrupt_files, 1);
+    assert!(!report.is_healthy());
+
+    let paths: Vec<&str> = report.files.iter().map(|x| x.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/fsck/a/under.log",
+            "/fsck/lost.log",
+            "/fsck/open.log",
+            "/fsck/over.log"
+        ]
+    );
+    let lost = &report.files[1].blocks[0];
+    assert_eq!(lost.state, FsckBlockState::Missing);
+    assert_eq!((lost.live, lost.expected), (0, 1));
+    assert_eq!(
+        report.orphan_blocks,
+        vec![FsckOrphanBlock {
+            worker_id: 100,
+            block_id: ok_block + 1
+        }]
+    );
+    assert_eq!(report.orphan_count, 1);
+    assert_eq!(report.next_start_after, None);
+
+    // Pages of one file with problems add up to the same report.
+    let mut paged = FsckReport::default();
+    let mut paths = vec![];
+    let mut page_opts = FsckOpts {
+        limit: 1,
+        ..Default::default()
+    };
+    loop {
+        let page = fs.fsck("/fsck", page_opts.clone())?;
+        assert!(page.files.len() <= 1);
+        paths.extend(page.files.iter().map(|x| x.path.clone()));
+        paged.merge(&page);
+        match page.next_start_after {
+            Some(v) => page_opts.start_after = Some(v),
+            None => break,
+        }
+    }
+    assert_eq!(
+        paths,
+        report
+            .files
+            .iter()
+            .map(|x| x.path.clone())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(paged.total_dirs, report.total_dirs);
+    assert_eq!(paged.total_files, report.total_files);
+    assert_eq!(paged.total_blocks, report.total_blocks);
+    assert_eq!(paged.corrupt_files, report.corrupt_files);
+
+    let move_opts = FsckOpts {
+        action: FsckAction::Move,
+        ..Default::default()
+    };
+    let report = fs.fsck("/fsck", move_opts.clone())?;
+    assert_eq!(report.handled_files, 1);
+    assert!(!fs.exists("/fsck/lost.log")?);
+    assert!(fs.exists("/lost+found/fsck/lost.log")?);
+    assert!(fs.fsck("/fsck", FsckOpts::default())?.is_healthy());
+
+    // A file already under /lost+found is not moved.
+    let report = fs.fsck("/lost+found", move_opts)?;
+    assert_eq!(report.corrupt_files, 1);
+    assert_eq!(report.handled_files, 0);
+
+    let delete_opts = FsckOpts {
+        action: FsckAction::Delete,
+        ..Default::default()
+    };
+    let report = fs.fsck("/lost+found", delete_opts)?;
+    assert_eq!(report.handled_files, 1);
+    assert!(!fs.exists("/lost+found/fsck/lost.log")?);
+
+    assert!(fs.fsck("/none", FsckOpts::default()).is_err());
+
+    // The only replica is reported with another length than the committed one.
+    let report_len = |len: i64| {
+        fs.block_report(BlockReportList {
+            cluster_id: "".to_string(),
+            worker_id: 100,
+            full_report: false,
+            total_len: 1,
+            blocks: vec![BlockReportInfo::new(
+                ok_block,
+                BlockReportStatus::Finalized,
+                StorageType::Disk,
+                len,
+            )],
+        })
+    };
+    report_len(512)?;
+    let report = fs.fsck("/fsck/ok.log", FsckOpts::default())?;
+    assert_eq!(report.corrupt_blocks, 1);
+    let corrupt = &report.files[0].blocks[0];
+    assert_eq!(corrupt.state, FsckBlockState::Corrupt);
+    assert_eq!((corrupt.live, corrupt.expected), (0, 1));
+    assert!(!report.is_healthy());
+
+    report_len(1024)?;
+    assert!(fs.fsck("/fsck/ok.log", FsckOpts::default())?.is_healthy());
+
+    Ok(())
+}
+
+#[test]
+fn test_image_reader_and_report() -> CommonResult<()> {
+    let (fs, _js) = new_fs(true, "image_test");
+    fs.mkdir("/image/a/b", true)?;
+    fs.create("/image/a/empty.log", true)?;
+
+    let path = "/image/a/b/1,log";
+    let addr = ClientAddress::default();
+    fs.create(path, true)?;
+    let b1 = fs.add_block(path, addr.clone(), vec![], vec![], 0, None)?;
+    let commit = CommitBlock {
+        block_id: b1.block.id,
+        block_len: 1024 * 1024,
+        locations: vec![BlockLocation {
+            worker_id: b1.locs[0].worker_id,
+            storage_type: Default::default(),
+        }],
+    };
+    fs.complete_file(path, 1024 * 1024, vec![commit], &addr.client_name, false)?;
+
+    let checkpoint = fs.fs_dir().read().create_checkpoint(1)?;
+    // Changes after the checkpoint are not in the image.
+    fs.create("/image/2.log", true)?;
+
+    let reader = ImageReader::open(&checkpoint)?;
+    let mut entries = vec![];
+    reader.walk("/image/", |entry| {
+        entries.push(entry);
+        Ok(())
+    })?;
+    let paths: Vec<&str> = entries.iter().map(|x| x.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/image",
+            "/image/a",
+            "/image/a/b",
+            "/image/a/b/1,log",
+            "/image/a/empty.log"
+        ]
+    );
+    let file = &entries[3];
+    assert_eq!(file.len, 1024 * 1024);
+    assert_eq!(file.block_ids, vec![b1.block.id]);
+    assert!(file
+        .to_csv()
+        .starts_with("\"/image/a/b/1,log\",File,1048576,"));
+    assert!(file.to_json().contains("\"block_ids\":["));
+
+    let mut report = ImageReport::new("/image", 1);
+    reader.walk("/image", |entry| {
+        report.add(&entry);
+        Ok(())
+    })?;
+    let report = report.finish(0);
+    assert_eq!(report.total_dirs, 3);
+    assert_eq!(report.total_files, 2);
+    assert_eq!(report.total_len, 1024 * 1024);
+    assert_eq!(report.dirs.len(), 1);
+    assert_eq!(report.dirs[0].path, "/image/a");
+    assert_eq!(report.dirs[0].files, 2);
+    assert_eq!(report.dirs[0].dirs, 1);
+    assert_eq!(report.dirs[0].len, 1024 * 1024);
+    let files: Vec<i64> = report.size_histogram.iter().map(|x| x.files).collect();
+    assert_eq!(files, vec![1, 0, 0, 1, 0, 0, 0]);
+
+    assert!(reader.walk("/none", |_| Ok(())).is_err());
+
     Ok(())
 }
 
@@ -690,3 +1206,195 @@ fn rename_retry(handler: &mut MasterHandler) -> CommonResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_path_locks() -> CommonResult<()> {
+    // The prefixes are locked in path order, the ancestors with the intention modes.
+    let locks = Arc::new(PathLocks::new());
+    let src = vec!["".to_string(), "b".to_string(), "1.log".to_string()];
+    let dst = vec!["".to_string(), "a".to_string(), "2.log".to_string()];
+    let guard = locks.lock(&[
+        (src.as_slice(), 1, LockMode::Write),
+        (dst.as_slice(), 2, LockMode::IntentRead),
+    ]);
+    assert_eq!(
+        guard.modes(),
+        vec![
+            ("/", LockMode::IntentWrite),
+            ("/a", LockMode::IntentRead),
+            ("/a/2.log", LockMode::IntentRead),
+            ("/b", LockMode::Write),
+        ]
+    );
+    drop(guard);
+    assert!(locks.is_empty());
+
+    // A subtree walk excludes the writes under it, but not the reads.
+    assert!(LockMode::Read.is_compatible(LockMode::IntentRead));
+    assert!(!LockMode::Read.is_compatible(LockMode::IntentWrite));
+    assert!(LockMode::IntentRead.is_compatible(LockMode::IntentWrite));
+    assert!(!LockMode::IntentRead.is_compatible(LockMode::Write));
+
+    let root = vec!["/".to_string()];
+    let walk = locks.lock(&[(root.as_slice(), 0, LockMode::Read)]);
+    let writer = {
+        let locks = locks.clone();
+        let src = src.clone();
+        std::thread::spawn(move || {
+            let _guard = locks.lock(&[(src.as_slice(), 1, LockMode::Write)]);
+        })
+    };
+    std::thread::sleep(Duration::from_millis(100));
+    // The writer waits for the walk, the reads queued behind it are not held up.
+    assert!(!writer.is_finished());
+    drop(locks.lock(&[(dst.as_slice(), 2, LockMode::IntentRead)]));
+    drop(walk);
+    writer.join().unwrap();
+
+    // The paths of a hard linked file do not exclude each other, its inode lock does.
+    let mut guard = locks.lock(&[(src.as_slice(), 1, LockMode::Write)]);
+    guard.lock_inodes(&[(1000, LockMode::Write)]);
+    let linked = {
+        let locks = locks.clone();
+        std::thread::spawn(move || {
+            let mut guard = locks.lock(&[(dst.as_slice(), 1, LockMode::Write)]);
+            guard.lock_inodes(&[(1000, LockMode::Write)]);
+        })
+    };
+    std::thread::sleep(Duration::from_millis(100));
+    assert!(!linked.is_finished());
+    drop(guard);
+    linked.join().unwrap();
+    assert!(locks.is_empty());
+
+    // Creates in distinct dirs run in parallel, crossing renames must not deadlock.
+    let (fs, _js) = new_fs(true, "path_locks");
+    let barrier = Arc::new(Barrier::new(4));
+    let mut handles = vec![];
+    for i in 0..4 {
+        let fs = fs.clone();
+        let barrier = barrier.clone();
+        handles.push(std::thread::spawn(move || {
+            for j in 0..50 {
+                fs.create(format!("/d{}/f{}", i, j), true).unwrap();
+                fs.create(format!("/shared/d{}-f{}", i, j), true).unwrap();
+            }
+            barrier.wait();
+            let (src, dst) = if i % 2 == 0 {
+                ("/d0/f0", "/d1/r0")
+            } else {
+                ("/d1/f1", "/d0/r1")
+            };
+            let _ = fs.rename(src, dst, RenameFlags::empty());
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(fs.list_status("/shared")?.len(), 200);
+    let files: usize = (0..4)
+        .map(|i| fs.list_status(format!("/d{}", i)).unwrap().len())
+        .sum();
+    assert_eq!(files, 200);
+    assert!(fs.exists("/d1/r0")? && fs.exists("/d0/r1")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_rate_limiter() -> CommonResult<()> {
+    let from_code = RateClass::from_code;
+    assert_eq!(from_code(RpcCode::FileStatus), Some(RateClass::Read));
+    assert_eq!(from_code(RpcCode::ListStatus), Some(RateClass::List));
+    assert_eq!(from_code(RpcCode::CreateFile), Some(RateClass::Write));
+    assert_eq!(from_code(RpcCode::AddBlock), None);
+    assert_eq!(from_code(RpcCode::WorkerHeartbeat), None);
+
+    let mut conf = MasterConf {
+        rate_limit_enabled: true,
+        rate_limit_read: 100,
+        rate_limit_write: 0,
+        rate_limit_list: 10,
+        rate_limit_burst: "100ms".to_string(),
+        rate_limit_queue_wait: "10ms".to_string(),
+        rate_limit_clients: HashMap::from([
+            ("10.0.0.1".to_string(), "10,0,1".to_string()),
+            ("alice".to_string(), "100,0,0".to_string()),
+        ]),
+        ..Default::default()
+    };
+    conf.init()?;
+    let limiter = MasterRateLimiter::new(&conf)?;
+    let acquire =
+        |host, user, class, ms| limiter.acquire_at(host, user, class, Duration::from_millis(ms));
+    let served = |res: Result<Duration, u64>| res == Ok(Duration::ZERO);
+    let queued = |res: Result<Duration, u64>| {
+        let wait = res.unwrap();
+        !wait.is_zero() && wait <= Duration::from_millis(10)
+    };
+
+    // A configured client has its own token buckets, the burst is at least one request.
+    assert!(served(acquire("10.0.0.1", None, RateClass::Read, 0)));
+    assert!(matches!(
+        acquire("10.0.0.1", None, RateClass::Read, 0),
+        Err(90..=91)
+    ));
+    assert!(served(acquire("10.0.0.1", None, RateClass::Read, 100)));
+    assert!(served(acquire("10.0.0.1", None, RateClass::List, 100)));
+    assert!(matches!(
+        acquire("10.0.0.1", None, RateClass::List, 100),
+        Err(990..=991)
+    ));
+    for _ in 0..100 {
+        assert!(served(acquire("10.0.0.1", None, RateClass::Write, 100)));
+    }
+
+    // A client is looked up by its user first, a request over the rate waits for the next token
+    // if it comes within the queue wait.
+    let alice = Some("alice");
+    for _ in 0..10 {
+        assert!(served(acquire("10.0.0.1", alice, RateClass::Read, 0)));
+    }
+    assert!(queued(acquire("10.0.0.1", alice, RateClass::Read, 0)));
+    assert!(acquire("10.0.0.1", alice, RateClass::Read, 0).is_err());
+
+    // The other clients share the class rate fairly, whatever rate they send at.
+    let mut admitted = [0; 2];
+    for ms in 0..1000 {
+        for (i, client) in ["10.0.0.2", "10.0.0.3"].into_iter().enumerate() {
+            let sends = if i == 0 { 10 } else { 1 };
+            for _ in 0..sends {
+                if acquire(client, None, RateClass::Read, ms).is_ok() {
+                    admitted[i] += 1;
+                }
+            }
+        }
+    }
+    assert_eq!(limiter.active_clients(RateClass::Read), 2);
+    assert!(admitted.iter().all(|x| (50..=65).contains(x)));
+
+    // A throttled client is told when its turn comes.
+    let backoff = (0..10)
+        .find_map(|_| acquire("10.0.0.2", None, RateClass::Read, 1000).err())
+        .unwrap();
+    assert!(backoff > 0 && backoff <= 20);
+
+    // The requests of a user from several hosts are one flow.
+    assert!(acquire("10.0.0.4", Some("bob"), RateClass::Read, 1000).is_ok());
+    assert!(acquire("10.0.0.5", Some("bob"), RateClass::Read, 1000).is_ok());
+    assert_eq!(limiter.active_clients(RateClass::Read), 3);
+
+    // A request stamped after the burst window waits for its turn instead of being rejected.
+    assert!(served(acquire("10.0.0.2", None, RateClass::List, 0)));
+    assert!(served(acquire("10.0.0.2", None, RateClass::List, 0)));
+    assert!(acquire("10.0.0.2", None, RateClass::List, 0).is_err());
+    assert!(queued(acquire("10.0.0.2", None, RateClass::List, 95)));
+
+    // A rate of 0 is unlimited.
+    for _ in 0..1000 {
+        assert!(served(acquire("10.0.0.2", None, RateClass::Write, 0)));
+    }
+
+    Ok(())
+}
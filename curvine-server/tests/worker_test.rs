@@ -19,7 +19,7 @@ use curvine_common::proto::{
 };
 use curvine_common::state::{ExtendedBlock, FileType, StorageType};
 use curvine_common::utils::ProtoUtils;
-use curvine_server::worker::Worker;
+use curvine_server::worker::{IoThrottle, TrafficClass, Worker};
 use orpc::common::Utils;
 use orpc::io::net::NetUtils;
 use orpc::message::{Builder, Message, RequestStatus};
@@ -27,6 +27,7 @@ use orpc::sys::DataSlice::Buffer;
 use orpc::CommonResult;
 use prost::bytes::BytesMut;
 use std::thread;
+use std::time::Duration;
 
 const CHUNK_SIZE: i32 = 1024;
 const LOOP_NUM: i32 = 100;
@@ -56,6 +57,33 @@ fn test_worker_block_write_and_read_with_checksum_validation() -> CommonResult<(
     Ok(())
 }
 
+#[test]
+fn test_io_throttle() -> CommonResult<()> {
+    let mut conf = ClusterConf::default();
+    conf.worker.io_throttle_load = "1KB".to_string();
+    let throttle = IoThrottle::new(&conf.worker)?;
+    let wait_ms = |class, bytes| throttle.reserve(class, bytes).as_millis() as u64;
+    let close = |ms: u64, expect: u64| ms.abs_diff(expect) <= 50;
+
+    // Background classes wait for their own limit, after a burst of 1 second.
+    assert!(close(wait_ms(TrafficClass::Load, 1024), 0));
+    assert!(close(wait_ms(TrafficClass::Load, 512), 500));
+    assert!(close(wait_ms(TrafficClass::Replication, 1 << 20), 0));
+
+    // Client reads use the total but never wait for it, load waits for both limits.
+    throttle.set_limit(IoThrottle::TOTAL, 1024)?;
+    throttle.set_limit("load", 0)?;
+    assert!(close(wait_ms(TrafficClass::ClientRead, 1 << 20), 0));
+    assert!(close(wait_ms(TrafficClass::Load, 512), 1500));
+
+    assert!(throttle.set_limit("unknown", 100).is_err());
+    assert_eq!(throttle.limit("load")?, 0);
+    assert_eq!(throttle.limits().len(), 6);
+    assert_eq!(throttle.bytes(TrafficClass::Load), 1024 + 512 + 512);
+    assert_eq!(throttle.bytes(TrafficClass::ClientRead), 1 << 20);
+    Ok(())
+}
+
 fn block_write(id: i64, conf: &ClusterConf) -> CommonResult<u64> {
     let block_size = (CHUNK_SIZE * LOOP_NUM) as i64;
     let block = ExtendedBlock::new(id, block_size, StorageType::Disk, FileType::File);
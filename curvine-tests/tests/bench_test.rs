@@ -14,7 +14,16 @@
 
 #![allow(unused)]
 
+use curvine_common::conf::{ClusterConf, JournalConf, MasterConf};
+use curvine_common::state::WorkerInfo;
+use curvine_server::master::fs::MasterFilesystem;
+use curvine_server::master::journal::JournalSystem;
+use curvine_server::master::Master;
 use curvine_tests::{BenchArgs, CurvineBench};
+use log::info;
+use orpc::common::{TimeSpent, Utils};
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 fn run_bench(action: &str, dir: &str) {
     let args = BenchArgs {
@@ -44,3 +53,95 @@ fn fs() {
     run_bench("fs.write", "/bench");
     run_bench("fs.read", "/bench");
 }
+
+// Parallel creates on the master filesystem, without rpc and journal, and the tree must
+// be complete and match the store afterwards.
+// Creates in distinct dirs lock only their own parent and must be clearly faster than the
+// same creates serialized by one global lock, which is what the namespace lock did.
+#[test]
+fn parallel_create() {
+    Master::init_test_metrics();
+    let conf = ClusterConf {
+        format_master: true,
+        testing: true,
+        master: MasterConf {
+            meta_dir: Utils::test_sub_dir("bench-test/meta"),
+            ..Default::default()
+        },
+        journal: JournalConf {
+            enable: false,
+            journal_dir: Utils::test_sub_dir("bench-test/journal"),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let js = JournalSystem::from_conf(&conf).unwrap();
+    let fs = MasterFilesystem::with_js(&conf, &js);
+    fs.add_test_worker(WorkerInfo::default());
+
+    let threads = thread::available_parallelism()
+        .map_or(1, |x| x.get())
+        .min(8);
+    let files = 500;
+    let global = Arc::new(Mutex::new(()));
+    let one_dir = run_create(&fs, "/one", threads, files, false, None);
+    let serialized = run_create(&fs, "/serialized", threads, files, true, Some(global));
+    let distinct_dirs = run_create(&fs, "/distinct", threads, files, true, None);
+
+    // No create is lost, and the tree in memory is the tree persisted in the store.
+    assert_eq!(fs.list_status("/one").unwrap().len(), threads * files);
+    for dir in ["/serialized", "/distinct"] {
+        let summary = fs.content_summary(dir).unwrap();
+        assert_eq!(summary.file_count, (threads * files) as i64);
+        assert_eq!(summary.directory_count, threads as i64 + 1);
+    }
+    let hash = fs.sum_hash();
+    fs.restore_from_rocksdb().unwrap();
+    assert_eq!(hash, fs.sum_hash());
+
+    info!(
+        "parallel create, threads {}, one dir {:.0} ops/s, serialized {:.0} ops/s, distinct dirs {:.0} ops/s",
+        threads, one_dir, serialized, distinct_dirs
+    );
+    if threads >= 4 {
+        assert!(
+            distinct_dirs > serialized * 1.5,
+            "distinct dirs {:.0} ops/s, serialized {:.0} ops/s",
+            distinct_dirs,
+            serialized
+        );
+    }
+}
+
+fn run_create(
+    fs: &MasterFilesystem,
+    dir: &str,
+    threads: usize,
+    files: usize,
+    distinct: bool,
+    global: Option<Arc<Mutex<()>>>,
+) -> f64 {
+    let spend = TimeSpent::new();
+    let mut handles = vec![];
+    for i in 0..threads {
+        let fs = fs.clone();
+        let global = global.clone();
+        let parent = if distinct {
+            format!("{}/{}", dir, i)
+        } else {
+            dir.to_string()
+        };
+        handles.push(thread::spawn(move || {
+            for j in 0..files {
+                let _guard = global.as_ref().map(|x| x.lock().unwrap());
+                fs.create(format!("{}/{}-{}", parent, i, j), true).unwrap();
+            }
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let ops = (threads * files) as f64;
+    ops * 1000.0 / spend.used_ms().max(1) as f64
+}
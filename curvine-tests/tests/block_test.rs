@@ -217,6 +217,7 @@ fn run(testing: Testing, mut conf: ClusterConf, path: Path) -> CommonResult<()>
         assert_eq!(write_ck, read_ck);
 
         seek(&fs, &path).await?;
+        pread(&fs, &path).await?;
 
         Ok::<(), FsError>(())
     })
@@ -301,6 +302,48 @@ async fn seek(fs: &CurvineFileSystem, path: &Path) -> CommonResult<()> {
     Ok(())
 }
 
+async fn pread(fs: &CurvineFileSystem, path: &Path) -> CommonResult<()> {
+    let mut reader = fs.open(path).await?;
+    let mut content = vec![0; reader.len() as usize];
+    reader.read_full(&mut content).await?;
+
+    // Positional reads across blocks, the stream position is not changed.
+    let mut buf = vec![0; 1024];
+    for pos in [0, 1024 * 1024 - 1, 1024 * 1024 + 100, content.len() - 10] {
+        let size = reader.pread(pos as i64, &mut buf).await?;
+        assert_eq!(size, buf.len().min(content.len() - pos));
+        assert_eq!(&buf[..size], &content[pos..pos + size]);
+    }
+    assert_eq!(reader.pos(), content.len() as i64);
+
+    let positional = reader.positional();
+    let mut bufs = vec![vec![0; 4096], vec![0; 2 * 1024 * 1024], vec![0; 100]];
+    let offsets = [512 * 1024, 1024 * 1024 - 2048, content.len() - 50];
+    let ranges = offsets
+        .iter()
+        .zip(bufs.iter_mut())
+        .map(|(pos, buf)| (*pos as i64, buf.as_mut_slice()))
+        .collect();
+    let read_len = positional.read_ranges(ranges).await?;
+    for ((pos, buf), size) in offsets.iter().zip(bufs.iter()).zip(read_len) {
+        assert_eq!(size, buf.len().min(content.len() - pos));
+        assert_eq!(&buf[..size], &content[*pos..pos + size]);
+    }
+
+    // The block readers of the reads above are kept and reused by the reads of the same blocks.
+    let cached = positional.cached_readers();
+    assert!(cached > 0);
+    for pos in [0, 4096, 512 * 1024] {
+        assert_eq!(reader.pread(pos, &mut buf).await?, buf.len());
+        assert_eq!(&buf[..], &content[pos as usize..pos as usize + buf.len()]);
+    }
+    assert_eq!(positional.cached_readers(), cached);
+
+    reader.complete().await?;
+    assert_eq!(positional.cached_readers(), 0);
+    Ok(())
+}
+
 #[test]
 fn test_local_random_position_file_write() -> CommonResult<()> {
     let testing = Testing::default();
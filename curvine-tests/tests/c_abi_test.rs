@@ -0,0 +1,62 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_tests::Testing;
+use orpc::CommonResult;
+use std::path::PathBuf;
+use std::process::Command;
+
+// Compile curvine-libsdk/tests/c/curvine_test.c against libcurvine_libsdk and run it on a cluster.
+// The library is built by `cargo build -p curvine-libsdk` or a workspace build, the test fails without it.
+#[test]
+fn c_abi() -> CommonResult<()> {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sdk_dir = manifest_dir.join("../curvine-libsdk");
+    let target_dir = std::env::var("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| manifest_dir.join("../target"));
+    let lib_dir = target_dir.join(if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    });
+    let lib = lib_dir.join("libcurvine_libsdk.so");
+    assert!(
+        lib.exists(),
+        "{:?} not found, build it with `cargo build -p curvine-libsdk`",
+        lib
+    );
+
+    let bin = lib_dir.join("curvine_c_test");
+    let status = Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(sdk_dir.join("tests/c/curvine_test.c"))
+        .arg("-I")
+        .arg(sdk_dir.join("include"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .args(["-lcurvine_libsdk", "-lpthread", "-o"])
+        .arg(&bin)
+        .status()?;
+    assert!(status.success());
+
+    let testing = Testing::builder().default().build()?;
+    let _cluster = testing.start_cluster()?;
+    let status = Command::new(&bin)
+        .arg(testing.active_conf_path())
+        .status()?;
+    assert!(status.success());
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_common::conf::ClusterConf;
+use curvine_common::fs::Path;
+use curvine_tests::Testing;
+use orpc::common::Metrics;
+use orpc::runtime::RpcRuntime;
+use orpc::CommonResult;
+use std::sync::Arc;
+
+// Bytes passed to the codec for the op, 0 if nothing was compressed yet.
+fn compress_bytes(codec: &str, op: &str) -> CommonResult<i64> {
+    let value = match Metrics::get("rpc_compress_input_bytes") {
+        Some(v) => v
+            .try_into_counter_vec()?
+            .with_label_values(&[codec, op])
+            .get(),
+        None => 0,
+    };
+    Ok(value)
+}
+
+// The client proposes lz4, which the workers accept and the master does not, and the masters
+// send the journal with zstd. Data and metadata must survive the round trip either way.
+#[test]
+fn compressed_roundtrip() -> CommonResult<()> {
+    let testing = Testing::builder()
+        .default()
+        .masters(3)
+        .workers(1)
+        .mutate_conf(|conf| {
+            conf.master.rpc_compress_codecs = "zstd".to_string();
+            conf.worker.rpc_compress_codecs = "lz4".to_string();
+            // Data sent with sendfile or read short-circuit is never compressed.
+            conf.worker.enable_send_file = false;
+            conf.client.short_circuit = false;
+            conf.client.rpc_compress_type_str = "lz4".to_string();
+            conf.client.rpc_compress_min_size_str = "1KB".to_string();
+            conf.journal.rpc_compress_type = "zstd".to_string();
+            conf.journal.rpc_compress_min_size = 0;
+        })
+        .build()?;
+    let _cluster = testing.start_cluster()?;
+    let conf = testing.get_active_cluster_conf()?;
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+
+    rt.block_on(async move {
+        let data = "compress ".repeat(128 * 1024);
+        for i in 0..4 {
+            let path = Path::from_str(format!("/compression/{}.log", i))?;
+            fs.write_string(&path, &data).await?;
+            assert_eq!(fs.read_string(&path).await?, data);
+        }
+
+        let list = fs.list_status(&Path::from_str("/compression")?).await?;
+        assert_eq!(list.len(), 4);
+        assert!(list.iter().all(|x| x.len == data.len() as i64));
+
+        Ok::<(), orpc::CommonError>(())
+    })?;
+
+    assert!(compress_bytes("lz4", "compress")? > 0);
+    assert!(compress_bytes("lz4", "decompress")? > 0);
+    assert!(compress_bytes("zstd", "compress")? > 0);
+    assert!(compress_bytes("zstd", "decompress")? > 0);
+    Ok(())
+}
+
+#[test]
+fn invalid_codecs() -> CommonResult<()> {
+    let mut conf = ClusterConf::default();
+    conf.master.rpc_compress_codecs = "lz4,gzip".to_string();
+    assert!(conf.master.init().is_err());
+
+    conf.worker.rpc_compress_codecs = "snappy".to_string();
+    assert!(conf.worker.init().is_err());
+
+    conf.journal.rpc_compress_type = "lz4,zstd".to_string();
+    assert!(conf.journal.init().is_err());
+
+    conf.worker.rpc_compress_codecs = " zstd, lz4 ".to_string();
+    conf.worker.init()?;
+    assert_eq!(conf.worker.rpc_compress_codecs().len(), 2);
+    Ok(())
+}
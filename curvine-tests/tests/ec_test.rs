@@ -0,0 +1,225 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::BytesMut;
+use curvine_client::file::CurvineFileSystem;
+use curvine_common::fs::{Path, Reader, Writer};
+use curvine_common::state::{
+    BlockLocation, CreateFileOptsBuilder, EcPolicy, FileBlocks, MkdirOptsBuilder,
+};
+use curvine_tests::Testing;
+use orpc::common::Utils;
+use orpc::runtime::RpcRuntime;
+use orpc::CommonResult;
+use std::sync::Arc;
+
+// Write and read erasure-coded files of different lengths, including partial stripes.
+#[test]
+fn ec_write_read() -> CommonResult<()> {
+    let testing = Testing::builder()
+        .default()
+        .workers(5)
+        .mutate_conf(|conf| {
+            conf.client.block_size_str = "64KB".to_string();
+            conf.master.min_block_size = 4 * 1024;
+        })
+        .build()?;
+    let _cluster = testing.start_cluster()?;
+    let conf = testing.get_active_cluster_conf()?;
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+    let policy = EcPolicy::new(3, 2, 4096)?;
+
+    rt.block_on(async move {
+        for (i, len) in [10, 4096 * 3, 100_000, 64 * 1024 * 2 + 12345]
+            .iter()
+            .enumerate()
+        {
+            let path = Path::from_str(format!("/ec/write_read_{}", i))?;
+            let data = test_data(*len);
+
+            let blocks = write_file(&fs, &path, &data, Some(policy)).await?;
+            for lb in &blocks.block_locs {
+                let striped = lb.striped.as_ref().unwrap();
+                assert_eq!(striped.policy, policy);
+                assert_eq!(lb.locs.len(), 5);
+            }
+
+            assert_eq!(read_file(&fs, &path).await?, data);
+        }
+
+        // The blocks are split by the block size of the file, not the one of the client.
+        let path = Path::from_str("/ec/block_size")?;
+        let data = test_data(100_000);
+        let opts = CreateFileOptsBuilder::with_conf(&fs.fs_context().cluster_conf().client)
+            .client_name(fs.fs_context().clone_client_name())
+            .block_size(32 * 1024)
+            .ec_policy(Some(policy))
+            .build();
+        let mut writer = fs.create_with_opts(&path, opts, true).await?;
+        writer.write(&data).await?;
+        writer.complete().await?;
+        let blocks = fs.get_block_locations(&path).await?;
+        assert_eq!(blocks.block_locs.len(), 4);
+        assert!(blocks.block_locs.iter().all(|x| x.block.len <= 32 * 1024));
+        assert_eq!(read_file(&fs, &path).await?, data);
+
+        // Files inherit the policy of the parent directory.
+        let dir = Path::from_str("/ec/dir")?;
+        let opts = MkdirOptsBuilder::with_conf(&fs.fs_context().cluster_conf().client)
+            .create_parent(true)
+            .ec_policy(Some(policy))
+            .build();
+        fs.mkdir_with_opts(&dir, opts).await?;
+
+        let path = Path::from_str("/ec/dir/a/inherit")?;
+        let data = test_data(50_000);
+        let blocks = write_file(&fs, &path, &data, None).await?;
+        let striped = blocks.block_locs[0].striped.as_ref().unwrap();
+        assert_eq!(striped.policy, policy);
+        assert_eq!(read_file(&fs, &path).await?, data);
+
+        // An explicit empty policy keeps the file replicated.
+        let path = Path::from_str("/ec/dir/a/replicated")?;
+        let opts = CreateFileOptsBuilder::with_conf(&fs.fs_context().cluster_conf().client)
+            .client_name(fs.fs_context().clone_client_name())
+            .ec_policy(None)
+            .build();
+        let mut writer = fs.create_with_opts(&path, opts, true).await?;
+        writer.write(&data).await?;
+        writer.complete().await?;
+        let blocks = fs.get_block_locations(&path).await?;
+        assert!(blocks.block_locs[0].striped.is_none());
+        assert_eq!(read_file(&fs, &path).await?, data);
+
+        Ok(())
+    })
+}
+
+// Lose units of a block, read it in degraded mode and let the master rebuild the lost unit.
+#[test]
+fn ec_reconstruct() -> CommonResult<()> {
+    reconstruct("/ec/reconstruct", None, 100_000)
+}
+
+// The rebuilt unit is sized by the block size of the file, here a unit is larger than
+// the default block size of the workers.
+#[test]
+fn ec_reconstruct_block_size() -> CommonResult<()> {
+    reconstruct("/ec/reconstruct_block_size", Some(256 * 1024), 200_000)
+}
+
+fn reconstruct(path: &str, block_size: Option<i64>, len: usize) -> CommonResult<()> {
+    let testing = Testing::builder()
+        .default()
+        .workers(4)
+        .mutate_conf(|conf| {
+            conf.client.block_size_str = "64KB".to_string();
+            conf.master.min_block_size = 4 * 1024;
+            conf.master.block_replication_enabled = true;
+        })
+        .build()?;
+    let cluster = testing.start_cluster()?;
+    let conf = testing.get_active_cluster_conf()?;
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+    let policy = EcPolicy::new(2, 1, 4096)?;
+
+    let path = Path::from_str(path)?;
+    let data = test_data(len);
+    let blocks = rt.block_on(async {
+        let mut builder = CreateFileOptsBuilder::with_conf(&fs.fs_context().cluster_conf().client)
+            .client_name(fs.fs_context().clone_client_name())
+            .create_parent(true)
+            .ec_policy(Some(policy));
+        if let Some(block_size) = block_size {
+            builder = builder.block_size(block_size);
+        }
+        let mut writer = fs.create_with_opts(&path, builder.build(), true).await?;
+        writer.write(&data).await?;
+        writer.complete().await?;
+        fs.get_block_locations(&path).await
+    })?;
+    let lb = blocks.block_locs.first().unwrap();
+    let block_id = lb.block.id;
+
+    // Drop the location of the first data unit.
+    let lost = lb.locs[lb
+        .striped
+        .as_ref()
+        .unwrap()
+        .indices
+        .iter()
+        .position(|x| *x == 0)
+        .unwrap()]
+    .clone();
+    let fs_dir = cluster.get_active_master_fs().fs_dir();
+    fs_dir.write().block_report(vec![(
+        false,
+        block_id,
+        BlockLocation::new(lost.worker_id, Default::default()),
+    )])?;
+
+    let blocks = rt.block_on(fs.get_block_locations(&path))?;
+    assert_eq!(blocks.block_locs[0].locs.len(), 2);
+    assert_eq!(rt.block_on(read_file(&fs, &path))?, data);
+
+    // The lost unit is rebuilt on the only worker that holds no unit of the block.
+    cluster
+        .get_active_master_replication_manager()
+        .report_under_replicated_blocks(Some(lost.worker_id), vec![block_id])?;
+    Utils::sleep(5000);
+
+    let blocks = rt.block_on(fs.get_block_locations(&path))?;
+    let lb = &blocks.block_locs[0];
+    assert_eq!(lb.locs.len(), 3);
+    assert!(lb.locs.iter().all(|x| x.worker_id != lost.worker_id));
+    assert_eq!(rt.block_on(read_file(&fs, &path))?, data);
+
+    Ok(())
+}
+
+async fn write_file(
+    fs: &CurvineFileSystem,
+    path: &Path,
+    data: &[u8],
+    policy: Option<EcPolicy>,
+) -> CommonResult<FileBlocks> {
+    let mut builder = CreateFileOptsBuilder::with_conf(&fs.fs_context().cluster_conf().client)
+        .client_name(fs.fs_context().clone_client_name())
+        .create_parent(true);
+    if let Some(policy) = policy {
+        builder = builder.ec_policy(Some(policy));
+    }
+    let mut writer = fs.create_with_opts(path, builder.build(), true).await?;
+    writer.write(data).await?;
+    writer.complete().await?;
+
+    let blocks = fs.get_block_locations(path).await?;
+    Ok(blocks)
+}
+
+async fn read_file(fs: &CurvineFileSystem, path: &Path) -> CommonResult<Vec<u8>> {
+    let status = fs.get_status(path).await?;
+    let mut reader = fs.open(path).await?;
+    let mut buf = BytesMut::zeroed(status.len as usize);
+    let n = reader.read_full(&mut buf).await?;
+    reader.complete().await?;
+    buf.truncate(n);
+    Ok(buf.to_vec())
+}
+
+fn test_data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
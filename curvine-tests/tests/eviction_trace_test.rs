@@ -0,0 +1,299 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_common::conf::ClusterConf;
+use curvine_common::fs::Path;
+use curvine_server::master::quota::eviction::evictor::{
+    ARCEvictor, CostEvictor, Evictor, LFUEvictor, LRUEvictor,
+};
+use curvine_server::master::quota::eviction::types::{EvictionConf, EvictionMode, EvictionPolicy};
+use curvine_server::master::quota::eviction::FileAccess;
+use curvine_tests::Testing;
+use orpc::common::LocalTime;
+use orpc::runtime::RpcRuntime;
+use orpc::{err_box, CommonResult};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+const MB: i64 = 1024 * 1024;
+
+fn create_conf(policy: EvictionPolicy) -> EvictionConf {
+    EvictionConf {
+        enable_quota_eviction: true,
+        eviction_mode: EvictionMode::FreeFile,
+        policy,
+        high_watermark: 0.8,
+        low_watermark: 0.6,
+        candidate_scan_page: 4,
+        dry_run: false,
+        capacity: 1_000_000,
+    }
+}
+
+fn create_evictor(policy: EvictionPolicy) -> Box<dyn Evictor> {
+    let conf = create_conf(policy);
+    match policy {
+        EvictionPolicy::Lru => Box::new(LRUEvictor::new(conf)),
+        EvictionPolicy::Lfu => Box::new(LFUEvictor::new(conf)),
+        EvictionPolicy::Arc => Box::new(ARCEvictor::new(conf)),
+        EvictionPolicy::Cost => Box::new(CostEvictor::new(conf)),
+    }
+}
+
+// Trace line format: time_ms,inode_id,len,ufs_backed
+fn load_trace(name: &str) -> CommonResult<Vec<FileAccess>> {
+    let path = format!("{}/tests/traces/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let mut trace = vec![];
+    for line in fs::read_to_string(path)?.lines() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let cols: Vec<i64> = line.split(',').map(|x| x.trim().parse().unwrap()).collect();
+        trace.push(FileAccess::new(cols[1], cols[2], cols[3] != 0, cols[0]));
+    }
+    Ok(trace)
+}
+
+#[derive(Debug, Default)]
+struct SimResult {
+    hits: usize,
+    misses: usize,
+    // Bytes read again from the ufs after being evicted.
+    refetch_bytes: i64,
+    // Evicted files without a ufs copy.
+    lost_files: usize,
+}
+
+impl SimResult {
+    fn hit_ratio(&self) -> f64 {
+        self.hits as f64 / (self.hits + self.misses) as f64
+    }
+}
+
+// Replays a trace against a cache of `capacity` bytes, evicting whenever the cache is full.
+fn simulate(policy: EvictionPolicy, trace: &[FileAccess], capacity: i64) -> SimResult {
+    let evictor = create_evictor(policy);
+    let page = create_conf(policy).candidate_scan_page;
+    let mut resident: HashMap<i64, FileAccess> = HashMap::new();
+    let mut evicted: HashMap<i64, FileAccess> = HashMap::new();
+    let mut used = 0;
+    let mut res = SimResult::default();
+
+    for access in trace {
+        if let Entry::Vacant(e) = resident.entry(access.inode_id) {
+            res.misses += 1;
+            if evicted.remove(&access.inode_id).is_some() {
+                res.refetch_bytes += access.len;
+            }
+            e.insert(*access);
+            used += access.len;
+        } else {
+            res.hits += 1;
+        }
+        evictor.on_file_access(*access);
+
+        while used > capacity {
+            let victims = evictor.select_victims(page);
+            if victims.is_empty() {
+                break;
+            }
+            for id in &victims {
+                if let Some(file) = resident.remove(id) {
+                    used -= file.len;
+                    if !file.ufs_backed {
+                        res.lost_files += 1;
+                    }
+                    evicted.insert(file.inode_id, file);
+                }
+            }
+            evictor.remove_victims(&victims);
+        }
+    }
+
+    res
+}
+
+#[test]
+fn test_arc_scan_trace() -> CommonResult<()> {
+    let trace = load_trace("scan.csv")?;
+    let lru = simulate(EvictionPolicy::Lru, &trace, 16 * 4 * MB);
+    let arc = simulate(EvictionPolicy::Arc, &trace, 16 * 4 * MB);
+
+    // One-off scans flush the hot files out of LRU, ARC keeps them in t2.
+    assert!(lru.hit_ratio() < 0.5);
+    assert!(arc.hit_ratio() > 0.6);
+    assert!(arc.hit_ratio() > lru.hit_ratio() + 0.2);
+
+    // Only the first loads of the hot files are fetched again under ARC.
+    assert!(arc.refetch_bytes <= 4 * 4 * MB);
+    assert!(arc.refetch_bytes * 10 < lru.refetch_bytes);
+    assert_eq!(lru.lost_files + arc.lost_files, 0);
+    Ok(())
+}
+
+#[test]
+fn test_cost_mixed_trace() -> CommonResult<()> {
+    let trace = load_trace("mixed.csv")?;
+    let capacity = 1024 * MB;
+    let lru = simulate(EvictionPolicy::Lru, &trace, capacity);
+    let lfu = simulate(EvictionPolicy::Lfu, &trace, capacity);
+    let arc = simulate(EvictionPolicy::Arc, &trace, capacity);
+    let cost = simulate(EvictionPolicy::Cost, &trace, capacity);
+
+    // Files without a ufs copy are only evicted after being idle for a long time.
+    for other in [&lru, &lfu, &arc] {
+        assert!(other.lost_files > 100);
+        assert!(cost.lost_files * 10 < other.lost_files);
+    }
+
+    // Evicting the large idle files first keeps the small hot ones cached,
+    // at the price of re-fetching the large files more often.
+    assert!(cost.hit_ratio() > lru.hit_ratio() + 0.1);
+    assert!(cost.hit_ratio() > arc.hit_ratio() + 0.1);
+    assert!(cost.refetch_bytes > lru.refetch_bytes);
+    Ok(())
+}
+
+#[test]
+fn test_cost_score() {
+    let now = 100_000;
+    let small = FileAccess::new(1, MB, true, now - 10_000);
+    let large = FileAccess::new(2, 1024 * MB, true, now - 10_000);
+    let local = FileAccess::new(3, 1024 * MB, false, now - 10_000);
+    let recent = FileAccess::new(4, MB, true, now);
+
+    assert!(CostEvictor::score(&large, now) > CostEvictor::score(&small, now));
+    assert!(CostEvictor::score(&large, now) > CostEvictor::score(&local, now));
+    assert!(CostEvictor::score(&small, now) > CostEvictor::score(&recent, now));
+
+    let evictor = CostEvictor::new(create_conf(EvictionPolicy::Cost));
+    for access in [small, large, local, recent] {
+        evictor.on_file_access(access);
+    }
+    assert_eq!(evictor.select_victims(2), vec![2, 1]);
+
+    evictor.remove_victims(&[2]);
+    assert_eq!(evictor.cache_size(), 3);
+    assert_eq!(evictor.select_victims(1), vec![1]);
+}
+
+#[test]
+fn test_arc_ghost_adapts() {
+    let evictor = ARCEvictor::new(create_conf(EvictionPolicy::Arc));
+    evictor.on_access(1);
+    evictor.on_access(2);
+    evictor.on_access(2);
+
+    // 1 was seen once, it is the first victim.
+    assert_eq!(evictor.select_victims(2), vec![1, 2]);
+    evictor.remove_victims(&[1]);
+    assert_eq!(evictor.cache_size(), 1);
+
+    // 1 is read again after eviction and comes back as a frequent file.
+    evictor.on_access(1);
+    evictor.on_access(3);
+    assert_eq!(evictor.select_victims(3), vec![2, 1, 3]);
+}
+
+#[test]
+fn test_policy_conf() -> CommonResult<()> {
+    let mut conf = ClusterConf::default();
+    for (name, policy) in [
+        ("lru", EvictionPolicy::Lru),
+        ("LFU", EvictionPolicy::Lfu),
+        ("arc", EvictionPolicy::Arc),
+        ("Cost", EvictionPolicy::Cost),
+    ] {
+        conf.master.quota_eviction_policy = name.to_string();
+        assert_eq!(EvictionConf::from_conf(&conf)?.policy, policy);
+    }
+
+    conf.master.quota_eviction_policy = "fifo".to_string();
+    assert!(EvictionConf::from_conf(&conf).is_err());
+    Ok(())
+}
+
+// The cost policy runs in the master: the large idle file is evicted before the small hot ones.
+#[test]
+fn test_cost_eviction_cluster() -> CommonResult<()> {
+    let testing = Testing::builder()
+        .default()
+        .masters(1)
+        .workers(1)
+        .mutate_conf(|conf| {
+            let base = conf.worker.data_dir[0].clone();
+            conf.worker.data_dir = vec![format!("[MEM:64MB]{}/mem", base)];
+            conf.client.block_size_str = "4MB".to_string();
+            conf.client.storage_type_str = "MEM".to_string();
+            conf.master.worker_check_interval = "1s".to_string();
+            conf.master.enable_quota_eviction = true;
+            conf.master.quota_eviction_mode = "delete".to_string();
+            conf.master.quota_eviction_policy = "cost".to_string();
+            conf.master.quota_eviction_high_rate = 0.5;
+            conf.master.quota_eviction_low_rate = 0.3;
+            conf.master.quota_eviction_scan_page = 1;
+        })
+        .build()?;
+    testing.start_cluster()?;
+    let conf = testing.get_active_cluster_conf()?;
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+
+    rt.block_on(async move {
+        let large = Path::from_str("/evict/large")?;
+        fs.write_string(&large, "l".repeat(24 * MB as usize))
+            .await?;
+
+        let mut small = vec![];
+        for i in 0..4 {
+            let path = Path::from_str(format!("/evict/small-{}", i))?;
+            fs.write_string(&path, "s".repeat(MB as usize)).await?;
+            small.push(path);
+        }
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        for path in &small {
+            fs.read_string(path).await?;
+        }
+
+        // 36MB of 64MB is above the high watermark.
+        let trigger = Path::from_str("/evict/trigger")?;
+        fs.write_string(&trigger, "t".repeat(8 * MB as usize))
+            .await?;
+
+        let deadline = LocalTime::mills() + 60 * 1000;
+        loop {
+            let mut small_exists = true;
+            for path in &small {
+                small_exists &= fs.exists(path).await?;
+            }
+            if !fs.exists(&large).await? {
+                break;
+            }
+            assert!(
+                small_exists,
+                "A small hot file is evicted before the large one"
+            );
+            if LocalTime::mills() > deadline {
+                return err_box!("The large file is not evicted");
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        Ok::<(), orpc::CommonError>(())
+    })?;
+
+    Ok(())
+}
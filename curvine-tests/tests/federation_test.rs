@@ -0,0 +1,105 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_client::rpc::JobMasterClient;
+use curvine_common::conf::NamespaceConf;
+use curvine_common::error::FsError;
+use curvine_common::fs::Path;
+use curvine_common::state::StorageType;
+use curvine_tests::Testing;
+use orpc::runtime::RpcRuntime;
+use orpc::CommonResult;
+use std::sync::Arc;
+
+// Two master groups, /fed is owned by ns1 and the rest of the namespace by the default group.
+#[test]
+fn federation() -> CommonResult<()> {
+    let default_testing = Testing::builder().default().workers(1).build()?;
+    let _default_cluster = default_testing.start_cluster()?;
+    let ns1_testing = Testing::builder().default().workers(1).build()?;
+    let _ns1_cluster = ns1_testing.start_cluster()?;
+
+    let mut conf = default_testing.get_active_cluster_conf()?;
+    let ns1_conf = ns1_testing.get_active_cluster_conf()?;
+    conf.federation.namespaces = vec![NamespaceConf {
+        name: "ns1".to_string(),
+        master_addrs: ns1_conf.client.master_addrs.clone(),
+        mount_points: vec!["/fed".to_string()],
+    }];
+    conf.federation.init()?;
+
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = default_testing.get_fs(Some(rt.clone()), Some(conf))?;
+    let default_fs = default_testing.get_fs(Some(rt.clone()), None)?;
+    let ns1_fs = ns1_testing.get_fs(Some(rt.clone()), None)?;
+
+    rt.block_on(async move {
+        let fed_file = Path::from_str("/fed/a/1.log")?;
+        let root_file = Path::from_str("/federation/1.log")?;
+        fs.write_string(&fed_file, "ns1").await?;
+        fs.write_string(&root_file, "default").await?;
+
+        // Each file only exists in the master group that owns it.
+        assert!(ns1_fs.exists(&fed_file).await?);
+        assert!(!default_fs.exists(&fed_file).await?);
+        assert!(default_fs.exists(&root_file).await?);
+        assert!(!ns1_fs.exists(&root_file).await?);
+        assert_eq!(fs.read_string(&fed_file).await?, "ns1");
+        assert_eq!(fs.read_string(&root_file).await?, "default");
+
+        // Rename within a namespace works, across namespaces it is rejected.
+        let fed_dst = Path::from_str("/fed/a/2.log")?;
+        assert!(fs.rename(&fed_file, &fed_dst).await?);
+        assert!(fs.rename(&fed_dst, &root_file).await.is_err());
+        assert!(fs
+            .rename(&root_file, &Path::from_str("/fed/3.log")?)
+            .await
+            .is_err());
+        assert!(ns1_fs.exists(&fed_dst).await?);
+
+        // The report aggregates all namespaces.
+        let infos = fs.fs_client().get_namespaces_info().await?;
+        assert_eq!(infos.len(), 2);
+        assert_eq!(infos[0].0, "default");
+        assert_eq!(infos[1].0, "ns1");
+        assert!(infos.iter().all(|x| x.1.capacity > 0));
+        assert!(infos[1].1.inode_file_num >= 1);
+        let total = fs.get_master_info().await?;
+        assert_eq!(total.capacity, infos[0].1.capacity + infos[1].1.capacity);
+        assert_eq!(total.live_workers.len(), 2);
+
+        // Listing the root shows the mount points of the other namespaces.
+        let names: Vec<String> = fs
+            .list_status(&Path::from_str("/")?)
+            .await?
+            .into_iter()
+            .map(|x| x.name)
+            .collect();
+        assert!(names.contains(&"fed".to_string()));
+        assert!(names.contains(&"federation".to_string()));
+
+        // Jobs are found on the namespace that runs them.
+        let (job_id, _, _) = fs
+            .set_storage_policy(&fed_dst, StorageType::Mem, false)
+            .await?;
+        let client = JobMasterClient::new(fs.fs_client());
+        assert_eq!(client.get_job_status(&job_id).await?.job_id, job_id);
+        assert!(fs.fs_client().namespace("ns2").is_err());
+
+        Ok::<(), FsError>(())
+    })
+    .unwrap();
+
+    Ok(())
+}
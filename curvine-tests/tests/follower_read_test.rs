@@ -0,0 +1,86 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_common::fs::{Path, Writer};
+use curvine_common::state::ReadConsistency;
+use curvine_tests::Testing;
+use orpc::common::{DurationUnit, Utils};
+use orpc::runtime::RpcRuntime;
+use orpc::CommonResult;
+use std::sync::Arc;
+use std::time::Duration;
+
+// Metadata reads are spread across the standby masters.
+#[test]
+fn follower_read() -> CommonResult<()> {
+    let testing = Testing::builder()
+        .default()
+        .masters(3)
+        .workers(2)
+        .mutate_conf(|conf| {
+            conf.master.worker_check_interval = "1s".to_string();
+            conf.master.worker_check_interval_unit = DurationUnit::from_str("1s").unwrap();
+        })
+        .build()?;
+    let cluster = testing.start_cluster()?;
+
+    // Standby masters learn the workers from the leader.
+    Utils::sleep(3000);
+    for fs in cluster.get_standby_master_fs() {
+        assert_eq!(fs.worker_manager.read().worker_list().len(), 2);
+    }
+
+    for consistency in [
+        ReadConsistency::Linearizable,
+        ReadConsistency::BoundedStaleness,
+    ] {
+        let mut conf = testing.get_active_cluster_conf()?;
+        conf.client.read_consistency = consistency;
+        conf.client.max_read_staleness = Duration::from_secs(5);
+        let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+        let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+
+        rt.block_on(async move {
+            let dir = Path::from_str(format!("/follower_read/{:?}", consistency))?;
+            let path = Path::from_str(format!("{}/a.log", dir))?;
+            let mut writer = fs.create(&path, true).await?;
+            writer.write(b"follower read").await?;
+            writer.complete().await?;
+
+            // Linearizable reads see the writes right away,
+            // bounded staleness reads see the writes older than the staleness bound.
+            if consistency == ReadConsistency::BoundedStaleness {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+
+            // Every master serves at least one of the reads.
+            for _ in 0..6 {
+                assert!(fs.exists(&path).await?);
+                assert_eq!(fs.get_status(&path).await?.len, 13);
+                assert_eq!(fs.list_status(&dir).await?.len(), 1);
+                let blocks = fs.get_block_locations(&path).await?;
+                assert_eq!(blocks.block_locs.len(), 1);
+                assert!(!blocks.block_locs[0].locs.is_empty());
+            }
+
+            let missing = Path::from_str(format!("{}/missing.log", dir))?;
+            assert!(!fs.exists(&missing).await?);
+            assert!(fs.get_status(&missing).await.is_err());
+
+            Ok::<(), orpc::CommonError>(())
+        })?;
+    }
+
+    Ok(())
+}
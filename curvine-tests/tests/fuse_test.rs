@@ -53,11 +53,23 @@ fn persist_restore() {
             FileStatus::with_name(11, "file2".to_string(), false),
         ];
         let dir_handle1 = state1
-            .new_dir_handle(a.ino, dir_status_list.clone())
+            .new_dir_handle(
+                a.ino,
+                &Path::from_str("/a").unwrap(),
+                dir_status_list.clone(),
+                Some("file2".to_string()),
+                true,
+            )
             .await
             .unwrap();
         let dir_handle2 = state1
-            .new_dir_handle(b.ino, dir_status_list.clone())
+            .new_dir_handle(
+                b.ino,
+                &Path::from_str("/b").unwrap(),
+                dir_status_list.clone(),
+                Some("file2".to_string()),
+                true,
+            )
             .await
             .unwrap();
 
@@ -97,7 +109,7 @@ fn persist_restore() {
         let original_id_creator = state1.node_read().current_id();
         let original_fh_creator = state1.current_fh();
         let original_handle1_status = handle1.status().clone();
-        let _original_dir_handle1_list = dir_handle1.get_all().to_vec();
+        let _original_dir_handle1_list = dir_handle1.get_list(0);
 
         // Persist state
         let mut writer = StateWriter::new(&test_path).unwrap();
@@ -147,6 +159,7 @@ fn persist_restore() {
         assert_eq!(restored_dir_handle1.ino, dir_handle1.ino);
         assert_eq!(restored_dir_handle1.fh, dir_handle1.fh);
         assert_eq!(restored_dir_handle1.len(), dir_handle1.len());
+        assert_eq!(restored_dir_handle1.path, dir_handle1.path);
 
         let restored_dir_handle2 = state2
             .find_dir_handle(dir_handle2.ino, dir_handle2.fh)
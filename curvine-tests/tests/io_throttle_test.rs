@@ -0,0 +1,84 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_common::fs::Path;
+use curvine_tests::Testing;
+use orpc::runtime::RpcRuntime;
+use orpc::CommonResult;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// Client reads and batch writes wait for the worker bandwidth limits,
+// and `cv admin throttle` changes the limits of a running worker.
+#[test]
+fn io_throttle() -> CommonResult<()> {
+    let testing = Testing::builder()
+        .default()
+        .masters(1)
+        .workers(1)
+        .mutate_conf(|conf| {
+            conf.worker.io_throttle_client_read = "1MB".to_string();
+            // Short-circuit io bypasses the worker.
+            conf.client.short_circuit = false;
+        })
+        .build()?;
+    let cluster = testing.start_cluster()?;
+    let conf = testing.get_active_cluster_conf()?;
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+    let worker = cluster.get_worker_address(0);
+
+    rt.block_on(async move {
+        // 3MB at 1MB/s after a burst of 1 second.
+        let path = Path::from_str("/io_throttle/read.log")?;
+        let data = "x".repeat(3 * 1024 * 1024);
+        fs.write_string(&path, &data).await?;
+
+        let start = Instant::now();
+        assert_eq!(fs.read_string(&path).await?.len(), data.len());
+        assert!(start.elapsed() >= Duration::from_millis(1500));
+
+        let block_client = fs.fs_context().block_client(&worker).await?;
+        let limits = vec![
+            ("client_read".to_string(), 0),
+            ("client_write".to_string(), 128 * 1024),
+        ];
+        let res = block_client.worker_throttle(limits).await?;
+        assert!(res.contains(&("client_read".to_string(), 0)));
+        assert!(res.contains(&("client_write".to_string(), 128 * 1024)));
+        assert!(block_client
+            .worker_throttle(vec![("unknown".to_string(), 1)])
+            .await
+            .is_err());
+
+        let start = Instant::now();
+        assert_eq!(fs.read_string(&path).await?.len(), data.len());
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        // 384KB of small files written in batches at 128KB/s after a burst of 1 second.
+        let content = "x".repeat(16 * 1024);
+        let paths = (0..24)
+            .map(|i| Path::from_str(format!("/io_throttle/batch/{}.log", i)))
+            .collect::<CommonResult<Vec<_>>>()?;
+        let files: Vec<_> = paths.into_iter().map(|x| (x, content.as_str())).collect();
+        let start = Instant::now();
+        fs.write_batch_string(&files).await?;
+        assert!(start.elapsed() >= Duration::from_millis(1500));
+        assert_eq!(fs.read_string(&files[23].0).await?, content);
+
+        Ok::<(), orpc::CommonError>(())
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,202 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_client::file::FsClient;
+use curvine_common::fs::{Path, Writer};
+use curvine_common::state::MasterGroup;
+use curvine_tests::Testing;
+use orpc::common::LocalTime;
+use orpc::io::net::InetAddr;
+use orpc::runtime::RpcRuntime;
+use orpc::{err_box, CommonResult};
+use std::sync::Arc;
+use std::time::Duration;
+
+// Wait until the master group satisfies the condition.
+async fn wait_masters<F>(client: &FsClient, f: F) -> CommonResult<MasterGroup>
+where
+    F: Fn(&MasterGroup) -> bool,
+{
+    let deadline = LocalTime::mills() + 60 * 1000;
+    while LocalTime::mills() < deadline {
+        if let Ok(group) = client.list_masters().await {
+            if f(&group) {
+                return Ok(group);
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    err_box!("Wait for the master group timeout")
+}
+
+// Add, promote, transfer the leadership to and remove a master in a running cluster.
+#[test]
+fn master_membership() -> CommonResult<()> {
+    let testing = Testing::builder().default().masters(3).workers(1).build()?;
+    let cluster = testing.start_cluster()?;
+    let conf = testing.get_active_cluster_conf()?;
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+    let client = fs.fs_client();
+
+    rt.block_on(async move {
+        let group = wait_masters(&client, |x| x.leader_id != 0).await?;
+        assert_eq!(group.masters.len(), 3);
+        assert!(group.masters.iter().all(|x| !x.learner));
+
+        // The leader can not be removed, and a non-member can not be the leader.
+        assert!(client.remove_master(group.leader_id).await.is_err());
+        assert!(client.transfer_master_leader(100).await.is_err());
+
+        // The new master is added as a learner, then started and promoted after catching up.
+        let new_id = 4;
+        let new_conf = cluster.joining_master_conf(new_id);
+        let addr = InetAddr::new(&new_conf.journal.hostname, new_conf.journal.rpc_port);
+        client
+            .add_master(new_id, &addr, Some(new_conf.master.rpc_port), false)
+            .await?;
+        let group = wait_masters(&client, |x| x.masters.len() == 4).await?;
+        assert!(group.masters.iter().any(|x| x.id == new_id && x.learner));
+        cluster.start_joining_master(new_conf.clone());
+        let group = wait_masters(&client, |x| {
+            x.masters.len() == 4 && x.masters.iter().all(|x| !x.learner)
+        })
+        .await?;
+        let new_master = group.masters.iter().find(|x| x.id == new_id).unwrap();
+        assert_eq!(new_master.port, new_conf.journal.rpc_port);
+        assert_eq!(new_master.rpc_port, new_conf.master.rpc_port);
+
+        // The client only knows the initial masters, it discovers the new one from the group.
+        assert_eq!(client.refresh_masters().await?, 1);
+        assert_eq!(client.refresh_masters().await?, 0);
+
+        // Writes still succeed after the leadership moves to another master.
+        let old_leader = group.leader_id;
+        let target = group
+            .masters
+            .iter()
+            .find(|x| x.id != old_leader && x.id != new_id)
+            .unwrap()
+            .id;
+        client.transfer_master_leader(target).await?;
+        wait_masters(&client, |x| x.leader_id == target).await?;
+        while client.get_master_info().await?.live_workers.is_empty() {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+
+        let path = Path::from_str("/master_membership/a.log")?;
+        let mut writer = fs.create(&path, true).await?;
+        writer.write(b"membership").await?;
+        writer.complete().await?;
+        assert_eq!(fs.get_status(&path).await?.len, 10);
+
+        // The client reaches the new master once it becomes the leader.
+        client.transfer_master_leader(new_id).await?;
+        wait_masters(&client, |x| x.leader_id == new_id).await?;
+        assert_eq!(fs.get_status(&path).await?.len, 10);
+
+        // Remove the new master.
+        client.transfer_master_leader(target).await?;
+        wait_masters(&client, |x| x.leader_id == target).await?;
+        client.remove_master(new_id).await?;
+        let group = wait_masters(&client, |x| x.masters.len() == 3).await?;
+        assert!(group.masters.iter().all(|x| x.id != new_id));
+
+        Ok::<(), orpc::CommonError>(())
+    })?;
+
+    Ok(())
+}
+
+// An observer replicates the journal but never votes, is never promoted and can not be the leader.
+#[test]
+fn master_observer() -> CommonResult<()> {
+    let testing = Testing::builder().default().masters(3).workers(1).build()?;
+    let cluster = testing.start_cluster()?;
+    let conf = testing.get_active_cluster_conf()?;
+    assert!(conf.journal.raft_pre_vote);
+    assert!(conf.journal.raft_check_quorum);
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+    let client = fs.fs_client();
+
+    rt.block_on(async move {
+        wait_masters(&client, |x| x.leader_id != 0).await?;
+
+        let observer_id = 5;
+        let mut observer_conf = cluster.joining_master_conf(observer_id);
+        observer_conf
+            .journal
+            .journal_addrs
+            .last_mut()
+            .unwrap()
+            .observer = true;
+        let addr = InetAddr::new(
+            &observer_conf.journal.hostname,
+            observer_conf.journal.rpc_port,
+        );
+        client
+            .add_master(
+                observer_id,
+                &addr,
+                Some(observer_conf.master.rpc_port),
+                true,
+            )
+            .await?;
+        cluster.start_joining_master(observer_conf);
+        let group = wait_masters(&client, |x| x.masters.len() == 4).await?;
+        let observer = group.masters.iter().find(|x| x.id == observer_id).unwrap();
+        assert!(observer.learner && observer.observer);
+
+        // The journal is replicated to the observer.
+        while client.get_master_info().await?.live_workers.is_empty() {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        let path = Path::from_str("/master_observer/a.log")?;
+        let mut writer = fs.create(&path, true).await?;
+        writer.write(b"observer").await?;
+        writer.complete().await?;
+        for _ in 0..60 {
+            let standby = cluster.get_standby_master_fs();
+            if standby
+                .iter()
+                .all(|x| x.exists(path.path()).unwrap_or(false))
+            {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        for master_fs in cluster.get_standby_master_fs() {
+            assert!(master_fs.exists(path.path())?);
+        }
+
+        // The observer is still a learner after catching up.
+        let group = client.list_masters().await?;
+        let observer = group.masters.iter().find(|x| x.id == observer_id).unwrap();
+        assert!(observer.learner && observer.observer);
+        assert!(group
+            .masters
+            .iter()
+            .filter(|x| x.id != observer_id)
+            .all(|x| !x.learner && !x.observer));
+        assert!(client.transfer_master_leader(observer_id).await.is_err());
+
+        client.remove_master(observer_id).await?;
+        wait_masters(&client, |x| x.masters.len() == 3).await?;
+
+        Ok::<(), orpc::CommonError>(())
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::BytesMut;
+use curvine_client::file::CurvineFileSystem;
+use curvine_common::fs::{Path, Reader, Writer};
+use curvine_server::test::{MiniCluster, WorkerFault};
+use curvine_tests::Testing;
+use orpc::common::LocalTime;
+use orpc::runtime::RpcRuntime;
+use orpc::{err_box, CommonResult};
+use std::sync::Arc;
+use std::time::Duration;
+
+const KB: usize = 1024;
+
+fn create_testing(workers: u16) -> CommonResult<Testing> {
+    Testing::builder()
+        .default()
+        .masters(1)
+        .workers(workers)
+        .mutate_conf(|conf| {
+            conf.client.block_size_str = "1MB".to_string();
+            conf.master.min_block_size = 1024 * 1024;
+            conf.client.replicas = 2;
+            // Remote writes, so that a worker failure surfaces on the next chunk.
+            conf.client.short_circuit = false;
+            conf.master.block_replication_enabled = true;
+        })
+        .build()
+}
+
+fn test_data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+fn worker_index(cluster: &MiniCluster, worker_id: u32) -> usize {
+    cluster
+        .worker_entries
+        .iter()
+        .find(|x| x.worker_id == worker_id)
+        .map(|x| *x.key())
+        .unwrap()
+}
+
+async fn read_file(fs: &CurvineFileSystem, path: &Path) -> CommonResult<Vec<u8>> {
+    let status = fs.get_status(path).await?;
+    let mut reader = fs.open(path).await?;
+    let mut buf = BytesMut::zeroed(status.len as usize);
+    let len = reader.read_full(&mut buf).await?;
+    reader.complete().await?;
+    buf.truncate(len);
+    Ok(buf.to_vec())
+}
+
+// A replica worker fails in the middle of a block, it is replaced by the third worker.
+#[test]
+fn pipeline_replace_failed_replica() -> CommonResult<()> {
+    let testing = create_testing(3)?;
+    let cluster = testing.start_cluster()?;
+    let conf = testing.get_active_cluster_conf()?;
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+
+    rt.block_on(async move {
+        let path = Path::from_str("/pipeline/replace.dat")?;
+        let data = test_data(2 * 1024 * KB);
+
+        let mut writer = fs.create(&path, true).await?;
+        writer.write(&data[..512 * KB]).await?;
+        writer.flush().await?;
+
+        let first = fs.get_block_locations(&path).await?.block_locs[0].clone();
+        assert_eq!(first.locs.len(), 2);
+        let failed = first.locs[0].worker_id;
+        cluster.inject_worker_fault(worker_index(&cluster, failed), WorkerFault::WriteBlock);
+
+        writer.write(&data[512 * KB..]).await?;
+        writer.complete().await?;
+
+        assert_eq!(read_file(&fs, &path).await?, data);
+        for lb in fs.get_block_locations(&path).await?.block_locs {
+            assert_eq!(lb.locs.len(), 2, "block {}", lb.block.id);
+            assert!(lb.locs.iter().all(|x| x.worker_id != failed));
+        }
+
+        cluster.clear_worker_faults(worker_index(&cluster, failed));
+        Ok::<(), orpc::CommonError>(())
+    })?;
+
+    Ok(())
+}
+
+// There is no worker to replace the failed one, the block is committed with one replica
+// and repaired by the master once the worker is back.
+#[test]
+fn pipeline_degraded_then_repaired() -> CommonResult<()> {
+    let testing = create_testing(2)?;
+    let cluster = testing.start_cluster()?;
+    let conf = testing.get_active_cluster_conf()?;
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+    let master_fs = cluster.get_active_master_fs();
+
+    rt.block_on(async move {
+        let path = Path::from_str("/pipeline/degraded.dat")?;
+        let data = test_data(768 * KB);
+
+        let mut writer = fs.create(&path, true).await?;
+        writer.write(&data[..256 * KB]).await?;
+        writer.flush().await?;
+
+        let block_id = fs.get_block_locations(&path).await?.block_locs[0].block.id;
+        let failed = fs.get_block_locations(&path).await?.block_locs[0].locs[0].worker_id;
+        let index = worker_index(&cluster, failed);
+        cluster.inject_worker_fault(index, WorkerFault::WriteBlock);
+
+        writer.write(&data[256 * KB..]).await?;
+        // The worker comes back before the block is committed and reported.
+        cluster.clear_worker_faults(index);
+        writer.complete().await?;
+
+        assert_eq!(read_file(&fs, &path).await?, data);
+
+        let deadline = LocalTime::mills() + 30 * 1000;
+        loop {
+            let locs = master_fs.fs_dir.read().get_block_locations(block_id)?;
+            if locs.len() == 2 {
+                break;
+            }
+            if LocalTime::mills() > deadline {
+                return err_box!("Block {} is not repaired, locations {:?}", block_id, locs);
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+
+        Ok::<(), orpc::CommonError>(())
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_common::fs::Path;
+use curvine_server::master::Master;
+use curvine_server::test::MiniCluster;
+use curvine_tests::Testing;
+use orpc::runtime::RpcRuntime;
+use orpc::CommonResult;
+use std::sync::Arc;
+
+// The value of a metric with the read class label, 0 if it is not reported yet.
+fn read_class_metric(cluster: &MiniCluster, name: &str) -> CommonResult<f64> {
+    let output = Master::get_metrics().text_output(cluster.get_active_master_fs())?;
+    let prefix = format!("{}{{class=\"read\"}} ", name);
+    let value = output
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .map(|v| v.trim().parse::<f64>().unwrap())
+        .unwrap_or(0.0);
+    Ok(value)
+}
+
+// Clients over the rate are queued or throttled, and resend the throttled requests after the
+// backoff the master returns, so every request succeeds.
+#[test]
+fn throttled_retry() -> CommonResult<()> {
+    let testing = Testing::builder()
+        .default()
+        .workers(1)
+        .mutate_conf(|conf| {
+            conf.master.rate_limit_enabled = true;
+            conf.master.rate_limit_read = 20;
+            conf.master.rate_limit_write = 0;
+            conf.master.rate_limit_burst = "100ms".to_string();
+            conf.master.rate_limit_queue_wait = "10ms".to_string();
+        })
+        .build()?;
+    let cluster = testing.start_cluster()?;
+
+    let dir = Path::from_str("/rate_limit")?;
+    let mut handles = vec![];
+    for user in ["alice", "bob"] {
+        let mut conf = testing.get_active_cluster_conf()?;
+        conf.client.user = user.to_string();
+        let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+        let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+
+        let dir = dir.clone();
+        handles.push(std::thread::spawn(move || {
+            rt.block_on(async move {
+                fs.mkdir(&dir, true).await?;
+                for _ in 0..30 {
+                    assert!(fs.exists(&dir).await?);
+                }
+                Ok::<(), orpc::CommonError>(())
+            })
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap()?;
+    }
+
+    assert!(read_class_metric(&cluster, "rpc_throttled_count")? > 0.0);
+    assert!(read_class_metric(&cluster, "rpc_throttled_backoff_ms")? > 0.0);
+    Ok(())
+}
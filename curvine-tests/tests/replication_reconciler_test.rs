@@ -0,0 +1,147 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::BytesMut;
+use curvine_client::file::CurvineFileSystem;
+use curvine_common::fs::{Path, Reader, Writer};
+use curvine_common::state::SetAttrOptsBuilder;
+use curvine_tests::Testing;
+use orpc::common::LocalTime;
+use orpc::runtime::RpcRuntime;
+use orpc::{err_box, CommonResult};
+use std::sync::Arc;
+use std::time::Duration;
+
+const KB: usize = 1024;
+
+async fn wait_replicas(fs: &CurvineFileSystem, path: &Path, replicas: usize) -> CommonResult<()> {
+    let deadline = LocalTime::mills() + 60 * 1000;
+    loop {
+        let blocks = fs.get_block_locations(path).await?;
+        if blocks.block_locs.iter().all(|x| x.locs.len() == replicas) {
+            return Ok(());
+        }
+        if LocalTime::mills() > deadline {
+            return err_box!("Expected {} replicas, blocks {:?}", replicas, blocks);
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+async fn read_file(fs: &CurvineFileSystem, path: &Path) -> CommonResult<Vec<u8>> {
+    let status = fs.get_status(path).await?;
+    let mut reader = fs.open(path).await?;
+    let mut buf = BytesMut::zeroed(status.len as usize);
+    let len = reader.read_full(&mut buf).await?;
+    reader.complete().await?;
+    buf.truncate(len);
+    Ok(buf.to_vec())
+}
+
+// Raising the replica number adds copies, lowering it removes the excess copies.
+#[test]
+fn reconcile_setrep_up_and_down() -> CommonResult<()> {
+    let testing = Testing::builder()
+        .default()
+        .masters(1)
+        .workers(3)
+        .mutate_conf(|conf| {
+            conf.client.block_size_str = "1MB".to_string();
+            conf.master.min_block_size = 1024 * 1024;
+            conf.client.replicas = 1;
+            conf.master.block_replication_enabled = true;
+            conf.master.block_reconcile_enabled = true;
+            conf.master.block_reconcile_interval = "1s".to_string();
+        })
+        .build()?;
+    let _cluster = testing.start_cluster()?;
+    let conf = testing.get_active_cluster_conf()?;
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+
+    rt.block_on(async move {
+        let path = Path::from_str("/reconcile/setrep.dat")?;
+        let data: Vec<u8> = (0..1536 * KB).map(|i| (i % 251) as u8).collect();
+
+        let mut writer = fs.create(&path, true).await?;
+        writer.write(&data).await?;
+        writer.complete().await?;
+        wait_replicas(&fs, &path, 1).await?;
+
+        let opts = SetAttrOptsBuilder::new().replicas(3).build();
+        let status = fs.set_attr(&path, opts).await?;
+        assert_eq!(status.replicas, 3);
+        wait_replicas(&fs, &path, 3).await?;
+        assert_eq!(read_file(&fs, &path).await?, data);
+
+        let opts = SetAttrOptsBuilder::new().replicas(1).build();
+        fs.set_attr(&path, opts).await?;
+        wait_replicas(&fs, &path, 1).await?;
+        assert_eq!(read_file(&fs, &path).await?, data);
+
+        let opts = SetAttrOptsBuilder::new().replicas(0).build();
+        assert!(fs.set_attr(&path, opts).await.is_err());
+
+        Ok::<(), orpc::CommonError>(())
+    })?;
+
+    Ok(())
+}
+
+// With one action per round, the blocks of one file are repaired over several rounds,
+// each round resuming from the same file.
+#[test]
+fn reconcile_max_actions() -> CommonResult<()> {
+    let testing = Testing::builder()
+        .default()
+        .masters(1)
+        .workers(3)
+        .mutate_conf(|conf| {
+            conf.client.block_size_str = "1MB".to_string();
+            conf.master.min_block_size = 1024 * 1024;
+            conf.client.replicas = 1;
+            conf.master.block_replication_enabled = true;
+            conf.master.block_reconcile_enabled = true;
+            conf.master.block_reconcile_interval = "1s".to_string();
+            conf.master.block_reconcile_max_actions = 1;
+        })
+        .build()?;
+    let _cluster = testing.start_cluster()?;
+    let conf = testing.get_active_cluster_conf()?;
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+
+    rt.block_on(async move {
+        let path = Path::from_str("/reconcile/max_actions.dat")?;
+        let data: Vec<u8> = (0..4096 * KB).map(|i| (i % 251) as u8).collect();
+
+        let mut writer = fs.create(&path, true).await?;
+        writer.write(&data).await?;
+        writer.complete().await?;
+        assert_eq!(fs.get_block_locations(&path).await?.block_locs.len(), 4);
+
+        let opts = SetAttrOptsBuilder::new().replicas(2).build();
+        fs.set_attr(&path, opts).await?;
+        wait_replicas(&fs, &path, 2).await?;
+
+        let opts = SetAttrOptsBuilder::new().replicas(1).build();
+        fs.set_attr(&path, opts).await?;
+        wait_replicas(&fs, &path, 1).await?;
+        assert_eq!(read_file(&fs, &path).await?, data);
+
+        Ok::<(), orpc::CommonError>(())
+    })?;
+
+    Ok(())
+}
@@ -112,7 +112,7 @@ fn test_block_replication_e2e() -> CommonResult<()> {
 
     // Report that this block is under-replicated
     // We'll use worker_id 1 (assuming it exists)
-    master_replication_manager.report_under_replicated_blocks(1, vec![block_id])?;
+    master_replication_manager.report_under_replicated_blocks(Some(1), vec![block_id])?;
 
     // Step 4: Wait for replication to complete
     info!("Waiting for replication to complete...");
@@ -247,7 +247,7 @@ fn test_replication_with_simulated_worker_failure() -> CommonResult<()> {
         "Simulating under-replication for {} blocks",
         blocks_to_replicate.len()
     );
-    replication_manager.report_under_replicated_blocks(1, blocks_to_replicate)?;
+    replication_manager.report_under_replicated_blocks(Some(1), blocks_to_replicate)?;
 
     // Wait for replication
     Utils::sleep(8000);
@@ -0,0 +1,169 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_common::conf::ClusterConf;
+use curvine_common::fs::Path;
+use curvine_common::state::StorageType;
+use curvine_tests::Testing;
+use orpc::common::LocalTime;
+use orpc::runtime::RpcRuntime;
+use orpc::{err_box, CommonResult};
+use std::sync::Arc;
+use std::time::Duration;
+
+// A frequently read block on SSD is promoted to MEM, and the master sees the new storage type.
+#[test]
+fn tier_promote() -> CommonResult<()> {
+    let testing = Testing::builder()
+        .default()
+        .masters(1)
+        .workers(1)
+        .mutate_conf(|conf| {
+            let base = conf.worker.data_dir[0].clone();
+            conf.worker.data_dir = vec![
+                format!("[MEM:64MB]{}/mem", base),
+                format!("[SSD:1GB]{}/ssd", base),
+            ];
+            conf.worker.enable_tier_migration = true;
+            conf.worker.tier_check_interval = "1s".to_string();
+            conf.worker.tier_promote_reads = 2;
+            conf.client.storage_type_str = "SSD".to_string();
+        })
+        .build()?;
+    let cluster = testing.start_cluster()?;
+    let master_fs = cluster.get_active_master_fs();
+    let storage_type = move |block_id: i64| -> CommonResult<StorageType> {
+        let locs = master_fs.fs_dir.read().get_block_locations(block_id)?;
+        Ok(locs[0].storage_type)
+    };
+    let conf = testing.get_active_cluster_conf()?;
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+
+    rt.block_on(async move {
+        let path = Path::from_str("/tier/hot.log")?;
+        fs.write_string(&path, "tier migration").await?;
+
+        let block_id = fs.get_block_locations(&path).await?.block_locs[0].block.id;
+        assert_eq!(storage_type(block_id)?, StorageType::Ssd);
+
+        for _ in 0..4 {
+            assert_eq!(fs.read_string(&path).await?, "tier migration");
+        }
+
+        let deadline = LocalTime::mills() + 30 * 1000;
+        loop {
+            if storage_type(block_id)? == StorageType::Mem {
+                break;
+            }
+            if LocalTime::mills() > deadline {
+                return err_box!("The block is not promoted to MEM");
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+
+        // The moved block is still readable.
+        assert_eq!(fs.read_string(&path).await?, "tier migration");
+        Ok::<(), orpc::CommonError>(())
+    })?;
+
+    Ok(())
+}
+
+// Changing the storage policy moves the blocks of the file, and the journaled job is removed.
+#[test]
+fn tier_move_by_storage_policy() -> CommonResult<()> {
+    let testing = Testing::builder()
+        .default()
+        .masters(1)
+        .workers(1)
+        .mutate_conf(|conf| {
+            let base = conf.worker.data_dir[0].clone();
+            conf.worker.data_dir = vec![
+                format!("[MEM:64MB]{}/mem", base),
+                format!("[SSD:1GB]{}/ssd", base),
+            ];
+            conf.client.storage_type_str = "SSD".to_string();
+        })
+        .build()?;
+    let cluster = testing.start_cluster()?;
+    let master_fs = cluster.get_active_master_fs();
+    let conf = testing.get_active_cluster_conf()?;
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = testing.get_fs(Some(rt.clone()), Some(conf))?;
+
+    rt.block_on(async move {
+        let path = Path::from_str("/tier/move.log")?;
+        fs.write_string(&path, "tier move").await?;
+        let block_id = fs.get_block_locations(&path).await?.block_locs[0].block.id;
+
+        let (_, total_blocks, _) = fs
+            .set_storage_policy(&path, StorageType::Mem, false)
+            .await?;
+        assert_eq!(total_blocks, 1);
+
+        let deadline = LocalTime::mills() + 30 * 1000;
+        loop {
+            let (locs, jobs) = {
+                let fs_dir = master_fs.fs_dir.read();
+                (
+                    fs_dir.get_block_locations(block_id)?,
+                    fs_dir.get_migrations()?,
+                )
+            };
+            if locs[0].storage_type == StorageType::Mem && jobs.is_empty() {
+                break;
+            }
+            if LocalTime::mills() > deadline {
+                return err_box!("The block is not moved to MEM");
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+
+        assert_eq!(fs.read_string(&path).await?, "tier move");
+        Ok::<(), orpc::CommonError>(())
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn invalid_watermarks() -> CommonResult<()> {
+    let mut conf = ClusterConf::default();
+    conf.worker.tier_watermarks = vec!["[MEM]0.5:0.8".to_string()];
+    assert!(conf.worker.init().is_err());
+
+    conf.worker.tier_watermarks = vec!["MEM:0.9".to_string()];
+    assert!(conf.worker.init().is_err());
+
+    // A misspelled tier is not applied to the disk tier.
+    conf.worker.tier_watermarks = vec!["[SSDD]0.9:0.7".to_string()];
+    assert!(conf.worker.init().is_err());
+
+    conf.worker.tier_watermarks = vec![];
+    conf.worker.tier_high_watermark = 1.5;
+    assert!(conf.worker.init().is_err());
+
+    conf.worker.tier_high_watermark = 0.9;
+    conf.worker.tier_check_interval = "fast".to_string();
+    assert!(conf.worker.init().is_err());
+
+    conf.worker.tier_check_interval = "5s".to_string();
+    conf.worker.tier_watermarks = vec!["[SSD]0.8:0.6".to_string()];
+    conf.worker.init()?;
+    assert_eq!(conf.worker.tier_check_interval_ms(), 5000);
+    assert_eq!(conf.worker.tier_watermark(StorageType::Ssd).low, 0.6);
+    assert_eq!(conf.worker.tier_watermark(StorageType::Mem).high, 0.9);
+    Ok(())
+}
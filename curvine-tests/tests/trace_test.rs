@@ -0,0 +1,134 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use curvine_client::file::CurvineFileSystem;
+use curvine_common::conf::ClusterConf;
+use curvine_common::fs::Path;
+use curvine_tests::Testing;
+use orpc::common::{Span, Tracer};
+use orpc::runtime::RpcRuntime;
+use orpc::CommonResult;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::process::Command;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+// Set by cross_process to run trace_client as the client process.
+const CLIENT_CONF_ENV: &str = "CURVINE_TRACE_CLIENT_CONF";
+const CLIENT_TRACE_ENV: &str = "CURVINE_TRACE_CLIENT_FILE";
+
+fn read_spans(path: &str) -> CommonResult<Vec<Value>> {
+    let content = fs::read_to_string(path)?;
+    let mut spans = vec![];
+    for line in content.lines() {
+        spans.push(serde_json::from_str::<Value>(line)?);
+    }
+    Ok(spans)
+}
+
+// The client process of cross_process, it does nothing when run by the test harness.
+#[test]
+fn trace_client() -> CommonResult<()> {
+    let conf_path = match env::var(CLIENT_CONF_ENV) {
+        Ok(v) => v,
+        Err(_) => return Ok(()),
+    };
+    let mut conf = ClusterConf::from(conf_path)?;
+    conf.trace.file_path = env::var(CLIENT_TRACE_ENV)?;
+    Tracer::init(&conf.trace, "trace-client")?;
+
+    let rt = Arc::new(conf.client_rpc_conf().create_runtime());
+    let fs = CurvineFileSystem::with_rt(conf, rt.clone())?;
+    let span = Span::root("test.client");
+    rt.block_on(span.instrument(async move {
+        let path = Path::from_str("/trace/a.log")?;
+        let data = "trace".repeat(64 * 1024);
+        fs.write_string(&path, &data).await?;
+        assert_eq!(fs.read_string(&path).await?, data);
+        Ok::<(), orpc::CommonError>(())
+    }))?;
+
+    drop(span);
+    Tracer::flush();
+    Ok(())
+}
+
+// A client in another process starts a trace, the master and worker spans of its requests
+// join the trace as children of the client spans.
+#[test]
+fn cross_process() -> CommonResult<()> {
+    let dir = TempDir::new()?;
+    let server_file = dir.path().join("server.json").to_string_lossy().to_string();
+    let client_file = dir.path().join("client.json").to_string_lossy().to_string();
+
+    let trace_file = server_file.clone();
+    let testing = Testing::builder()
+        .default()
+        .masters(1)
+        .workers(1)
+        .mutate_conf(move |conf| {
+            conf.trace.enabled = true;
+            conf.trace.exporter = "file".to_string();
+            conf.trace.file_path = trace_file.clone();
+            conf.trace.sample_ratio = 1.0;
+            // Worker reads of short-circuit clients are not traced.
+            conf.client.short_circuit = false;
+        })
+        .build()?;
+    let _cluster = testing.start_cluster()?;
+
+    let status = Command::new(env::current_exe()?)
+        .args(["trace_client", "--exact", "--nocapture"])
+        .env(CLIENT_CONF_ENV, testing.active_conf_path())
+        .env(CLIENT_TRACE_ENV, &client_file)
+        .status()?;
+    assert!(status.success());
+    Tracer::flush();
+
+    let client_spans = read_spans(&client_file)?;
+    let root = client_spans
+        .iter()
+        .find(|x| x["name"] == "test.client")
+        .unwrap();
+    let trace_id = root["trace_id"].clone();
+    let client_ids: HashSet<_> = client_spans
+        .iter()
+        .filter(|x| x["trace_id"] == trace_id)
+        .map(|x| x["span_id"].clone())
+        .collect();
+
+    let server_spans: Vec<_> = read_spans(&server_file)?
+        .into_iter()
+        .filter(|x| x["trace_id"] == trace_id)
+        .collect();
+    for prefix in ["master.", "worker.write.", "worker.read."] {
+        let span = server_spans
+            .iter()
+            .find(|x| x["name"].as_str().unwrap().starts_with(prefix));
+        assert!(span.is_some(), "no {} span", prefix);
+    }
+    // The servers only continue traces, every span has its parent in the client or the servers.
+    let server_ids: HashSet<_> = server_spans.iter().map(|x| x["span_id"].clone()).collect();
+    for span in &server_spans {
+        let parent = &span["parent_span_id"];
+        assert!(client_ids.contains(parent) || server_ids.contains(parent));
+    }
+    assert!(server_spans
+        .iter()
+        .any(|x| client_ids.contains(&x["parent_span_id"])));
+    Ok(())
+}
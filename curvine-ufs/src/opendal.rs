@@ -27,6 +27,7 @@ use opendal::{
     layers::{LoggingLayer, RetryLayer, TimeoutLayer},
     Metadata, Operator,
 };
+use orpc::common::Span;
 use orpc::sys::DataSlice;
 use orpc::{err_box, err_ext, try_option_mut};
 use std::collections::HashMap;
@@ -80,6 +81,7 @@ impl Reader for OpendalReader {
         if !self.has_remaining() {
             return Ok(DataSlice::Empty);
         }
+        let mut span = Span::current_child("ufs.opendal.read");
 
         // Initialize stream if needed
         if self.byte_stream.is_none() {
@@ -98,7 +100,7 @@ impl Reader for OpendalReader {
             );
         }
 
-        if let Some(stream) = &mut self.byte_stream {
+        let res = if let Some(stream) = &mut self.byte_stream {
             if let Some(chunk_result) = stream.next().await {
                 match chunk_result {
                     Ok(chunk) => Ok(DataSlice::Bytes(chunk)),
@@ -109,7 +111,12 @@ impl Reader for OpendalReader {
             }
         } else {
             Ok(DataSlice::Empty)
+        };
+        match &res {
+            Ok(chunk) => span.set_attr("bytes", chunk.len()),
+            Err(e) => span.set_error(e),
         }
+        res
     }
 
     async fn seek(&mut self, pos: i64) -> FsResult<()> {
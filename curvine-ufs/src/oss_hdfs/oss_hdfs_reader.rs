@@ -17,6 +17,7 @@ use curvine_common::error::FsError;
 use curvine_common::fs::{Path, Reader};
 use curvine_common::state::FileStatus;
 use curvine_common::FsResult;
+use orpc::common::Span;
 use orpc::sys::DataSlice;
 use std::os::raw::c_void;
 
@@ -239,6 +240,7 @@ impl Reader for OssHdfsReader {
         if self.length == 0 || self.pos >= self.length {
             return Ok(DataSlice::empty());
         }
+        let mut span = Span::current_child("ufs.oss_hdfs.read");
 
         // Only read up to remaining bytes.
         let remaining = (self.length - self.pos).max(0) as usize;
@@ -295,6 +297,7 @@ impl Reader for OssHdfsReader {
                     return Ok(DataSlice::Buffer(buffer));
                 }
                 let err_msg = err.unwrap_or_else(jindo_last_error);
+                span.set_error(&err_msg);
                 return Err(FsError::common(format!("Failed to read: {}", err_msg)));
             }
 
@@ -318,6 +321,7 @@ impl Reader for OssHdfsReader {
         // once by the caller), causing `pos` to advance too far. This leads to incorrect read
         // positions and breaks `seek()` behavior.
 
+        span.set_attr("bytes", buffer.len());
         Ok(DataSlice::Buffer(buffer))
     }
 
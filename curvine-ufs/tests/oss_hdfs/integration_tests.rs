@@ -708,6 +708,8 @@ mod tests {
         // Set permission
         let opts = SetAttrOpts {
             recursive: false,
+            dir_policy: None,
+            storage_type: None,
             replicas: None,
             owner: None,
             group: None,
@@ -751,6 +753,8 @@ mod tests {
         // Set owner
         let opts = SetAttrOpts {
             recursive: false,
+            dir_policy: None,
+            storage_type: None,
             replicas: None,
             owner: Some("testuser".to_string()),
             group: Some("testgroup".to_string()),
@@ -790,6 +794,8 @@ mod tests {
         // Set both permission and owner
         let opts = SetAttrOpts {
             recursive: false,
+            dir_policy: None,
+            storage_type: None,
             replicas: None,
             owner: Some("testuser".to_string()),
             group: Some("testgroup".to_string()),
@@ -1207,6 +1213,8 @@ mod tests {
         // 6. Set attributes
         let opts = SetAttrOpts {
             recursive: false,
+            dir_policy: None,
+            storage_type: None,
             replicas: None,
             owner: Some("testuser".to_string()),
             group: None,
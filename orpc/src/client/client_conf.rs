@@ -14,6 +14,7 @@
 
 use crate::common::Utils;
 use crate::io::retry::{TimeBondedRetry, TimeBondedRetryBuilder};
+use crate::message::CompressionType;
 use crate::runtime::Runtime;
 use std::time::Duration;
 
@@ -46,6 +47,15 @@ pub struct ClientConf {
     pub conn_size: usize,
 
     pub use_libc: bool,
+
+    // Payload compression proposed to the server when connecting, none disables it.
+    // Messages smaller than compress_min_size are not compressed.
+    pub compress_type: CompressionType,
+    pub compress_min_size: usize,
+
+    // The user of the client sent to the server when connecting, servers use it to tell clients apart,
+    // e.g. to limit the requests of each user. None sends no user.
+    pub user: Option<String>,
 }
 
 impl ClientConf {
@@ -104,6 +114,11 @@ impl Default for ClientConf {
             conn_size: 1,
 
             use_libc: false,
+
+            compress_type: CompressionType::None,
+            compress_min_size: 4 * 1024,
+
+            user: None,
         }
     }
 }
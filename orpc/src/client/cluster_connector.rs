@@ -17,7 +17,7 @@ use crate::error::ErrorExt;
 use crate::io::net::{InetAddr, NodeAddr};
 use crate::io::retry::{TimeBondedRetry, TimeBondedRetryBuilder};
 use crate::io::{IOError, IOResult};
-use crate::message::{Message, MessageBuilder, RefMessage};
+use crate::message::{BoxMessage, Message, MessageBuilder, RefMessage};
 use crate::runtime::Runtime;
 use crate::sync::FastDashMap;
 use crate::{err_box, err_msg, CommonError};
@@ -26,14 +26,16 @@ use log::{debug, warn};
 use prost::Message as PMessage;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 // Cluster connection manager
 // 1. Request the master, automatically obtain the active master node, handle retries and master switching
 // 2. Create a connection with the worker node
+// 3. Spread read requests that followers can serve across all nodes
 pub struct ClusterConnector {
     factory: ClientFactory,
     leader_id: AtomicU64,
+    read_seq: AtomicU64,
     group: FastDashMap<u64, Arc<NodeAddr>>,
     rpc_timeout: Duration,
     data_timeout: Duration,
@@ -50,6 +52,7 @@ impl ClusterConnector {
         Self {
             factory,
             leader_id: AtomicU64::new(Self::DEFAULT_LEADER_ID),
+            read_seq: AtomicU64::new(0),
             group: FastDashMap::default(),
             rpc_timeout,
             data_timeout,
@@ -70,6 +73,23 @@ impl ClusterConnector {
         }
     }
 
+    // Add a node discovered at runtime unless a node has the address, returns whether it was added.
+    pub fn add_addr(&self, addr: InetAddr) -> IOResult<bool> {
+        if self.group.iter().any(|x| x.addr == addr) {
+            return Ok(false);
+        }
+
+        let id = self
+            .group
+            .iter()
+            .map(|x| x.id)
+            .max()
+            .unwrap_or(Self::DEFAULT_LEADER_ID)
+            + 1;
+        self.add_node(NodeAddr::from_addr(id, addr))?;
+        Ok(true)
+    }
+
     pub fn factory(&self) -> &ClientFactory {
         &self.factory
     }
@@ -184,16 +204,51 @@ impl ClusterConnector {
         }
     }
 
+    // Send a request to the node, if the node throttles it, wait for the backoff
+    // the node suggests and send it again, as long as the wait ends before the deadline.
+    // All the attempts of a request share the deadline, so the backoffs of a request add up
+    // to rpc_timeout at most.
+    async fn backoff_rpc<E>(
+        &self,
+        id: u64,
+        msg: BoxMessage,
+        deadline: Instant,
+    ) -> Result<Message, (bool, E)>
+    where
+        E: ErrorExt + From<IOError> + From<CommonError>,
+    {
+        loop {
+            match self.timeout_rpc::<E>(id, msg.clone()).await {
+                Err((retry, e)) => match e.backoff() {
+                    Some(backoff) if Instant::now() + backoff <= deadline => {
+                        debug!(
+                            "rpc({}) throttled by node {}, retry after {:?}",
+                            msg.req_id(),
+                            self.get_addr_string(id),
+                            backoff
+                        );
+                        tokio::time::sleep(backoff).await;
+                    }
+
+                    _ => return Err((retry, e)),
+                },
+
+                res => return res,
+            }
+        }
+    }
+
     // Send a retry request to the specified node.
     pub async fn retry_rpc<E>(&self, id: u64, msg: Message) -> Result<Message, E>
     where
         E: ErrorExt + From<IOError> + From<CommonError>,
     {
         let msg = msg.into_arc();
+        let deadline = Instant::now() + self.rpc_timeout;
         let mut last_error: Option<E> = None;
         let mut policy = self.retry_builder.build();
         while policy.attempt().await {
-            match self.timeout_rpc::<E>(id, msg.clone()).await {
+            match self.backoff_rpc::<E>(id, msg.clone(), deadline).await {
                 Ok(v) => return Ok(v),
 
                 Err((retry, e)) => {
@@ -225,12 +280,51 @@ impl ClusterConnector {
     where
         E: ErrorExt + From<IOError> + From<CommonError>,
     {
-        let mut last_error: Option<E> = None;
+        let deadline = Instant::now() + self.rpc_timeout;
+        self.rpc0(msg.into_arc(), deadline).await
+    }
+
+    // Send a read request to the nodes in turn, any node may serve it.
+    // If the selected node can not serve it, e.g. it lags too far behind the leader,
+    // the request is sent to the leader.
+    pub async fn read_rpc<E>(&self, msg: Message) -> Result<Message, E>
+    where
+        E: ErrorExt + From<IOError> + From<CommonError>,
+    {
         let msg = msg.into_arc();
+        let deadline = Instant::now() + self.rpc_timeout;
+        let node_list = self.node_list(true);
+        if !node_list.is_empty() {
+            let seq = self.read_seq.fetch_add(1, Ordering::Relaxed);
+            let id = node_list[(seq % node_list.len() as u64) as usize];
+            match self.backoff_rpc::<E>(id, msg.clone(), deadline).await {
+                Ok(v) => return Ok(v),
+
+                Err((false, e)) => return Err(e),
+
+                Err((true, e)) => {
+                    debug!(
+                        "read rpc({}) can not be served by node {}: {}",
+                        msg.req_id(),
+                        self.get_addr_string(id),
+                        e
+                    );
+                }
+            }
+        }
+
+        self.rpc0(msg, deadline).await
+    }
+
+    async fn rpc0<E>(&self, msg: BoxMessage, deadline: Instant) -> Result<Message, E>
+    where
+        E: ErrorExt + From<IOError> + From<CommonError>,
+    {
+        let mut last_error: Option<E> = None;
 
         // Send a request to the current leader node.
         if let Some(id) = self.leader_id() {
-            match self.timeout_rpc(id, msg.clone()).await {
+            match self.backoff_rpc(id, msg.clone(), deadline).await {
                 Ok(v) => return Ok(v),
 
                 Err((false, e)) => {
@@ -266,7 +360,7 @@ impl ClusterConnector {
                 .map(|&id| {
                     let msg = msg.clone();
                     Box::pin(async move {
-                        let res = self.timeout_rpc::<E>(id, msg).await;
+                        let res = self.backoff_rpc::<E>(id, msg, deadline).await;
                         (id, res)
                     })
                 })
@@ -340,6 +434,24 @@ impl ClusterConnector {
         }
     }
 
+    pub async fn proto_read_rpc<T, R, E>(&self, code: impl Into<i8>, header: T) -> Result<R, E>
+    where
+        T: PMessage + Default,
+        R: PMessage + Default,
+        E: ErrorExt + From<IOError> + From<CommonError>,
+    {
+        let msg = MessageBuilder::new_rpc(code.into())
+            .proto_header(header)
+            .build();
+
+        let rep = self.read_rpc::<E>(msg).await?;
+
+        match rep.parse_header() {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     pub fn rpc_timeout(&self) -> Duration {
         self.rpc_timeout
     }
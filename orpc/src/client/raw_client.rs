@@ -17,7 +17,7 @@ use crate::err_box;
 use crate::handler::RpcFrame;
 use crate::io::net::InetAddr;
 use crate::io::{IOError, IOResult};
-use crate::message::Message;
+use crate::message::{CompressionType, Message};
 use log::warn;
 use socket2::SockRef;
 use std::time::Duration;
@@ -39,7 +39,20 @@ impl RawClient {
         sock_ref.set_keepalive(true)?;
 
         let local_addr = stream.local_addr()?.into();
-        let frame = RpcFrame::with_client(stream, conf.buffer_size);
+        let mut frame = RpcFrame::with_client(stream, conf.buffer_size);
+        if conf.compress_type != CompressionType::None || conf.user.is_some() {
+            let timeout = Duration::from_millis(conf.rpc_timeout_ms);
+            time::timeout(
+                timeout,
+                frame.handshake(
+                    conf.compress_type,
+                    conf.compress_min_size,
+                    conf.user.as_deref(),
+                ),
+            )
+            .await??;
+        }
+
         let client = Self {
             frame,
             state: ClientState::new(addr.clone(), local_addr),
@@ -39,6 +39,15 @@ pub use self::byte_unit::ByteUnit;
 mod metrics;
 pub use self::metrics::*;
 
+mod trace_context;
+pub use self::trace_context::{ContextGuard, TraceContext};
+
+mod tracer;
+pub use self::tracer::*;
+
+mod trace_exporter;
+pub use self::trace_exporter::SpanExporter;
+
 mod logger_format;
 pub use self::logger_format::LogFormatter;
 
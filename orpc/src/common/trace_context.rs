@@ -0,0 +1,199 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::err_box;
+use crate::io::IOResult;
+use bytes::{Buf, BufMut};
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+
+tokio::task_local! {
+    static TASK_CONTEXT: Option<TraceContext>;
+}
+
+thread_local! {
+    static THREAD_CONTEXT: Cell<Option<TraceContext>> = const { Cell::new(None) };
+}
+
+/// W3C trace context (traceparent), https://www.w3.org/TR/trace-context/
+/// It is carried in the rpc message when the request is sent inside a sampled span.
+/// Binary format: trace_id (16 bytes) + span_id (8 bytes) + flags (1 byte).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: u128,
+    pub span_id: u64,
+    pub flags: u8,
+}
+
+impl TraceContext {
+    pub const ENCODE_SIZE: usize = 25;
+
+    pub const VERSION: u8 = 0;
+
+    pub const FLAG_SAMPLED: u8 = 0x01;
+
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: Self::random_id(),
+            span_id: Self::random_id() as u64,
+            flags: Self::FLAG_SAMPLED,
+        }
+    }
+
+    // A new span in the same trace.
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id,
+            span_id: Self::random_id() as u64,
+            flags: self.flags,
+        }
+    }
+
+    fn random_id() -> u128 {
+        // All zero ids are invalid.
+        rand::random::<u128>().max(1)
+    }
+
+    pub fn is_sampled(&self) -> bool {
+        self.flags & Self::FLAG_SAMPLED != 0
+    }
+
+    pub fn trace_id_hex(&self) -> String {
+        format!("{:032x}", self.trace_id)
+    }
+
+    pub fn span_id_hex(&self) -> String {
+        format!("{:016x}", self.span_id)
+    }
+
+    pub fn traceparent(&self) -> String {
+        format!(
+            "{:02x}-{:032x}-{:016x}-{:02x}",
+            Self::VERSION,
+            self.trace_id,
+            self.span_id,
+            self.flags
+        )
+    }
+
+    pub fn from_traceparent(value: &str) -> IOResult<Self> {
+        let parts: Vec<&str> = value.trim().split('-').collect();
+        if parts.len() != 4 || parts[1].len() != 32 || parts[2].len() != 16 {
+            return err_box!("invalid traceparent: {}", value);
+        }
+
+        let ctx = Self {
+            trace_id: u128::from_str_radix(parts[1], 16)?,
+            span_id: u64::from_str_radix(parts[2], 16)?,
+            flags: u8::from_str_radix(parts[3], 16)?,
+        };
+        if ctx.trace_id == 0 || ctx.span_id == 0 {
+            return err_box!("invalid traceparent: {}", value);
+        }
+        Ok(ctx)
+    }
+
+    pub fn encode(&self, buf: &mut impl BufMut) {
+        buf.put_u128(self.trace_id);
+        buf.put_u64(self.span_id);
+        buf.put_u8(self.flags);
+    }
+
+    pub fn decode(buf: &mut impl Buf) -> IOResult<Self> {
+        if buf.remaining() < Self::ENCODE_SIZE {
+            return err_box!("trace context is too short: {}", buf.remaining());
+        }
+
+        Ok(Self {
+            trace_id: buf.get_u128(),
+            span_id: buf.get_u64(),
+            flags: buf.get_u8(),
+        })
+    }
+
+    // The context of the current task, or of the current thread for synchronous code.
+    pub fn current() -> Option<Self> {
+        TASK_CONTEXT
+            .try_with(|x| *x)
+            .ok()
+            .flatten()
+            .or_else(|| THREAD_CONTEXT.with(|x| x.get()))
+    }
+
+    // Run a future with this context as the current context.
+    pub async fn scope<F: Future>(self, f: F) -> F::Output {
+        TASK_CONTEXT.scope(Some(self), f).await
+    }
+
+    // Set the context of the current thread, the previous one is restored when the guard is dropped.
+    pub fn enter(self) -> ContextGuard {
+        let prev = THREAD_CONTEXT.with(|x| x.replace(Some(self)));
+        ContextGuard { prev }
+    }
+}
+
+impl Display for TraceContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.traceparent())
+    }
+}
+
+pub struct ContextGuard {
+    prev: Option<TraceContext>,
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        THREAD_CONTEXT.with(|x| x.set(self.prev));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common::TraceContext;
+    use bytes::BytesMut;
+
+    #[test]
+    fn traceparent() {
+        let ctx = TraceContext::new_root();
+        let parsed = TraceContext::from_traceparent(&ctx.traceparent()).unwrap();
+        assert_eq!(ctx, parsed);
+
+        let w3c = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let ctx = TraceContext::from_traceparent(w3c).unwrap();
+        assert_eq!(ctx.trace_id_hex(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(ctx.span_id_hex(), "00f067aa0ba902b7");
+        assert!(ctx.is_sampled());
+        assert_eq!(ctx.to_string(), w3c);
+
+        assert!(TraceContext::from_traceparent("00-0-0-01").is_err());
+
+        let mut buf = BytesMut::new();
+        ctx.encode(&mut buf);
+        assert_eq!(buf.len(), TraceContext::ENCODE_SIZE);
+        assert_eq!(TraceContext::decode(&mut buf).unwrap(), ctx);
+    }
+
+    #[test]
+    fn current() {
+        assert!(TraceContext::current().is_none());
+        let ctx = TraceContext::new_root();
+        {
+            let _guard = ctx.enter();
+            assert_eq!(TraceContext::current(), Some(ctx));
+        }
+        assert!(TraceContext::current().is_none());
+    }
+}
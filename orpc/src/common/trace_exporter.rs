@@ -0,0 +1,175 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::{SpanData, TraceConf};
+use crate::{err_box, CommonResult};
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::header::{CONTENT_TYPE, HOST};
+use hyper::{Method, Request, Uri};
+use hyper_util::rt::TokioIo;
+use serde_json::{json, Value};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::runtime::{Builder, Runtime};
+use tokio::time;
+
+const OTLP_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub enum SpanExporter {
+    File(BufWriter<File>),
+
+    // OTLP/HTTP with json encoding, plain http only.
+    // The exporter thread posts the spans with its own single threaded runtime.
+    Otlp { uri: Uri, rt: Runtime },
+}
+
+impl SpanExporter {
+    pub fn from_conf(conf: &TraceConf) -> CommonResult<Self> {
+        match conf.exporter.to_lowercase().as_str() {
+            "file" => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&conf.file_path)?;
+                Ok(SpanExporter::File(BufWriter::new(file)))
+            }
+
+            "otlp" => {
+                let endpoint = conf.otlp_endpoint.trim_end_matches('/');
+                if !endpoint.starts_with("http://") {
+                    return err_box!("Unsupported otlp endpoint: {}", conf.otlp_endpoint);
+                }
+                let uri: Uri = format!("{}/v1/traces", endpoint).parse()?;
+                if uri.host().is_none() {
+                    return err_box!("Unsupported otlp endpoint: {}", conf.otlp_endpoint);
+                }
+
+                let rt = Builder::new_current_thread().enable_all().build()?;
+                Ok(SpanExporter::Otlp { uri, rt })
+            }
+
+            _ => err_box!("Unsupported trace exporter: {}", conf.exporter),
+        }
+    }
+
+    pub fn export(&mut self, service: &str, spans: &[SpanData]) -> CommonResult<()> {
+        match self {
+            SpanExporter::File(writer) => {
+                for span in spans {
+                    let line = Self::span_to_json(service, span);
+                    writeln!(writer, "{}", line)?;
+                }
+                writer.flush()?;
+                Ok(())
+            }
+
+            SpanExporter::Otlp { uri, rt } => {
+                let body = Self::otlp_request(service, spans).to_string();
+                rt.block_on(Self::post(uri, body))
+            }
+        }
+    }
+
+    fn span_to_json(service: &str, span: &SpanData) -> Value {
+        let attributes: serde_json::Map<String, Value> = span
+            .attributes
+            .iter()
+            .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+            .collect();
+
+        json!({
+            "service": service,
+            "name": span.name,
+            "trace_id": span.context.trace_id_hex(),
+            "span_id": span.context.span_id_hex(),
+            "parent_span_id": span.parent_span_id.map(|x| format!("{:016x}", x)),
+            "start_unix_nano": span.start_ns as u64,
+            "end_unix_nano": span.end_ns as u64,
+            "duration_us": ((span.end_ns.saturating_sub(span.start_ns)) / 1000) as u64,
+            "attributes": attributes,
+            "error": span.error,
+        })
+    }
+
+    // See opentelemetry-proto, ExportTraceServiceRequest json encoding.
+    fn otlp_request(service: &str, spans: &[SpanData]) -> Value {
+        let spans: Vec<Value> = spans
+            .iter()
+            .map(|span| {
+                let attributes: Vec<Value> = span
+                    .attributes
+                    .iter()
+                    .map(|(k, v)| json!({"key": k, "value": {"stringValue": v}}))
+                    .collect();
+                let parent_span_id = span
+                    .parent_span_id
+                    .map(|x| format!("{:016x}", x))
+                    .unwrap_or_default();
+                let status = match &span.error {
+                    Some(msg) => json!({"code": 2, "message": msg}),
+                    None => json!({"code": 1}),
+                };
+
+                json!({
+                    "traceId": span.context.trace_id_hex(),
+                    "spanId": span.context.span_id_hex(),
+                    "parentSpanId": parent_span_id,
+                    "name": span.name,
+                    "kind": span.kind as i32,
+                    "startTimeUnixNano": span.start_ns.to_string(),
+                    "endTimeUnixNano": span.end_ns.to_string(),
+                    "attributes": attributes,
+                    "status": status,
+                })
+            })
+            .collect();
+
+        json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [{"key": "service.name", "value": {"stringValue": service}}]
+                },
+                "scopeSpans": [{
+                    "scope": {"name": "curvine"},
+                    "spans": spans,
+                }]
+            }]
+        })
+    }
+
+    async fn post(uri: &Uri, body: String) -> CommonResult<()> {
+        let host = uri.host().unwrap_or_default();
+        let port = uri.port_u16().unwrap_or(80);
+        let stream = time::timeout(OTLP_TIMEOUT, TcpStream::connect((host, port))).await??;
+        let (mut sender, conn) =
+            hyper::client::conn::http1::handshake(TokioIo::new(stream)).await?;
+        tokio::spawn(conn);
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(uri.path())
+            .header(HOST, format!("{}:{}", host, port))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from(body)))?;
+        let response = time::timeout(OTLP_TIMEOUT, sender.send_request(request)).await??;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            err_box!("Otlp collector returned: {}", response.status())
+        }
+    }
+}
@@ -0,0 +1,355 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::{ContextGuard, LocalTime, SpanExporter, TraceContext};
+use crate::message::Message;
+use crate::CommonResult;
+use log::{info, warn};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender};
+use std::thread;
+use std::time::Duration;
+
+static TRACER: OnceCell<Tracer> = OnceCell::new();
+
+// Distributed tracing configuration.
+// exporter = "otlp": spans are posted as OTLP/HTTP json to {otlp_endpoint}/v1/traces.
+// exporter = "file": spans are appended to file_path, one json object per line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TraceConf {
+    pub enabled: bool,
+    pub exporter: String,
+    pub otlp_endpoint: String,
+    pub file_path: String,
+
+    // Fraction of root spans (FUSE operations) that start a trace.
+    pub sample_ratio: f64,
+
+    // Spans are exported in batches by a background thread, spans are dropped if the queue is full.
+    pub queue_size: usize,
+    pub batch_size: usize,
+    pub flush_interval_ms: u64,
+}
+
+impl Default for TraceConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            exporter: "otlp".to_string(),
+            otlp_endpoint: "http://localhost:4318".to_string(),
+            file_path: "trace.json".to_string(),
+            sample_ratio: 0.01,
+            queue_size: 4096,
+            batch_size: 512,
+            flush_interval_ms: 5000,
+        }
+    }
+}
+
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SpanKind {
+    Internal = 1,
+    Server = 2,
+    Client = 3,
+}
+
+// A finished span.
+#[derive(Debug, Clone)]
+pub struct SpanData {
+    pub name: String,
+    pub kind: SpanKind,
+    pub context: TraceContext,
+    pub parent_span_id: Option<u64>,
+    pub start_ns: u128,
+    pub end_ns: u128,
+    pub attributes: Vec<(String, String)>,
+    pub error: Option<String>,
+}
+
+enum ExportTask {
+    Span(SpanData),
+    Flush(SyncSender<()>),
+}
+
+pub struct Tracer {
+    service: String,
+    sample_ratio: f64,
+    sender: SyncSender<ExportTask>,
+}
+
+impl Tracer {
+    // Initialize the process wide tracer, it does nothing if tracing is disabled.
+    pub fn init(conf: &TraceConf, service: impl Into<String>) -> CommonResult<()> {
+        if !conf.enabled || TRACER.get().is_some() {
+            return Ok(());
+        }
+
+        let service = service.into();
+        let exporter = SpanExporter::from_conf(conf)?;
+        let (sender, receiver) = mpsc::sync_channel(conf.queue_size.max(1));
+
+        let batch_size = conf.batch_size.max(1);
+        let interval = Duration::from_millis(conf.flush_interval_ms.max(1));
+        let exporter_service = service.clone();
+        thread::Builder::new()
+            .name("trace-exporter".to_string())
+            .spawn(move || {
+                Self::export_loop(exporter, exporter_service, receiver, batch_size, interval)
+            })?;
+
+        let tracer = Tracer {
+            service,
+            sample_ratio: conf.sample_ratio,
+            sender,
+        };
+        if TRACER.set(tracer).is_ok() {
+            info!("Tracing enabled, exporter: {}", conf.exporter);
+        }
+        Ok(())
+    }
+
+    pub fn get() -> Option<&'static Tracer> {
+        TRACER.get()
+    }
+
+    pub fn is_enabled() -> bool {
+        TRACER.get().is_some()
+    }
+
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    fn sample(&self) -> bool {
+        self.sample_ratio >= 1.0 || rand::random::<f64>() < self.sample_ratio
+    }
+
+    fn export(span: SpanData) {
+        if let Some(tracer) = TRACER.get() {
+            // The span is dropped if the queue is full, tracing never blocks requests.
+            let _ = tracer.sender.try_send(ExportTask::Span(span));
+        }
+    }
+
+    // Wait until all queued spans are exported.
+    pub fn flush() {
+        if let Some(tracer) = TRACER.get() {
+            let (tx, rx) = mpsc::sync_channel(1);
+            if tracer.sender.send(ExportTask::Flush(tx)).is_ok() {
+                let _ = rx.recv_timeout(Duration::from_secs(30));
+            }
+        }
+    }
+
+    fn export_loop(
+        mut exporter: SpanExporter,
+        service: String,
+        receiver: Receiver<ExportTask>,
+        batch_size: usize,
+        interval: Duration,
+    ) {
+        let mut batch = Vec::with_capacity(batch_size);
+        loop {
+            let flush = match receiver.recv_timeout(interval) {
+                Ok(ExportTask::Span(span)) => {
+                    batch.push(span);
+                    if batch.len() < batch_size {
+                        continue;
+                    }
+                    None
+                }
+                Ok(ExportTask::Flush(tx)) => Some(tx),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+
+            if !batch.is_empty() {
+                if let Err(e) = exporter.export(&service, &batch) {
+                    warn!("Failed to export {} spans: {}", batch.len(), e);
+                }
+                batch.clear();
+            }
+            if let Some(tx) = flush {
+                let _ = tx.send(());
+            }
+        }
+    }
+}
+
+struct SpanInner {
+    data: SpanData,
+}
+
+/// A timed operation of a trace, exported when dropped.
+/// Spans are only recorded if the tracer is enabled and the trace is sampled, otherwise all
+/// operations are no-ops.
+pub struct Span {
+    inner: Option<SpanInner>,
+}
+
+impl Span {
+    pub fn none() -> Self {
+        Self { inner: None }
+    }
+
+    fn start(
+        name: impl Into<String>,
+        kind: SpanKind,
+        context: TraceContext,
+        parent: Option<u64>,
+    ) -> Self {
+        let data = SpanData {
+            name: name.into(),
+            kind,
+            context,
+            parent_span_id: parent,
+            start_ns: LocalTime::nanos(),
+            end_ns: 0,
+            attributes: vec![],
+            error: None,
+        };
+        Self {
+            inner: Some(SpanInner { data }),
+        }
+    }
+
+    // Start a new trace, subject to sampling.
+    pub fn root(name: impl Into<String>) -> Self {
+        match TRACER.get() {
+            Some(tracer) if tracer.sample() => {
+                Self::start(name, SpanKind::Internal, TraceContext::new_root(), None)
+            }
+            _ => Self::none(),
+        }
+    }
+
+    // A child of the current task or thread context, no-op if there is none.
+    pub fn current_child(name: impl Into<String>) -> Self {
+        Self::child_of(name, SpanKind::Internal, TraceContext::current())
+    }
+
+    pub fn child_of(name: impl Into<String>, kind: SpanKind, parent: Option<TraceContext>) -> Self {
+        match parent {
+            Some(parent) if parent.is_sampled() && Tracer::is_enabled() => {
+                Self::start(name, kind, parent.child(), Some(parent.span_id))
+            }
+            _ => Self::none(),
+        }
+    }
+
+    // Server side span of a request, the parent is the trace context carried in the message.
+    pub fn from_message(name: impl Into<String>, msg: &Message) -> Self {
+        Self::child_of(name, SpanKind::Server, msg.protocol.trace)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    pub fn context(&self) -> Option<TraceContext> {
+        self.inner.as_ref().map(|x| x.data.context)
+    }
+
+    pub fn set_attr(&mut self, key: impl Into<String>, value: impl ToString) {
+        if let Some(inner) = &mut self.inner {
+            inner.data.attributes.push((key.into(), value.to_string()));
+        }
+    }
+
+    pub fn set_error(&mut self, err: impl ToString) {
+        if let Some(inner) = &mut self.inner {
+            inner.data.error = Some(err.to_string());
+        }
+    }
+
+    // Make this span the current context of the thread, for synchronous code.
+    pub fn enter(&self) -> Option<ContextGuard> {
+        self.context().map(|x| x.enter())
+    }
+
+    // Run a future with this span as the current context, rpc requests sent by the future
+    // carry the context.
+    pub async fn instrument<F: Future>(&self, f: F) -> F::Output {
+        match self.context() {
+            Some(ctx) => ctx.scope(f).await,
+            None => f.await,
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(mut inner) = self.inner.take() {
+            inner.data.end_ns = LocalTime::nanos();
+            Tracer::export(inner.data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common::{Span, TraceConf, TraceContext, Tracer};
+    use crate::message::Builder;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn file_exporter() {
+        let path = env::temp_dir().join(format!("orpc-trace-{}.json", std::process::id()));
+        let conf = TraceConf {
+            enabled: true,
+            exporter: "file".to_string(),
+            file_path: path.to_string_lossy().to_string(),
+            sample_ratio: 1.0,
+            ..Default::default()
+        };
+        Tracer::init(&conf, "test").unwrap();
+
+        let (trace_id, root_id) = {
+            let mut root = Span::root("fuse.read");
+            root.set_attr("path", "/a");
+            let ctx = root.context().unwrap();
+
+            // Requests built in the span carry its context.
+            let _guard = root.enter();
+            let msg = Builder::new_rpc(1).build();
+            assert_eq!(msg.protocol.trace, Some(ctx));
+
+            let mut server = Span::from_message("master.open", &msg);
+            server.set_error("not found");
+            (ctx.trace_id, ctx.span_id)
+        };
+        assert!(TraceContext::current().is_none());
+        Tracer::flush();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        let spans: Vec<serde_json::Value> = content
+            .lines()
+            .map(|x| serde_json::from_str(x).unwrap())
+            .filter(|x: &serde_json::Value| x["trace_id"] == format!("{:032x}", trace_id))
+            .collect();
+        assert_eq!(spans.len(), 2);
+
+        let server = spans.iter().find(|x| x["name"] == "master.open").unwrap();
+        assert_eq!(server["parent_span_id"], format!("{:016x}", root_id));
+        assert_eq!(server["error"], "not found");
+        let root = spans.iter().find(|x| x["name"] == "fuse.read").unwrap();
+        assert_eq!(root["attributes"]["path"], "/a");
+    }
+}
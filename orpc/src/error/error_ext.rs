@@ -14,6 +14,7 @@
 
 use bytes::BytesMut;
 use std::error::Error;
+use std::time::Duration;
 
 // Wrong extension interface
 // Supports setting context, supporting serialization and deserialization.
@@ -37,4 +38,10 @@ pub trait ErrorExt: Error {
     fn should_continue(&self) -> bool {
         false
     }
+
+    // How long to wait before re-submitting the request to the same node.
+    // For example, the server throttles the client and tells it when to come back.
+    fn backoff(&self) -> Option<Duration> {
+        None
+    }
 }
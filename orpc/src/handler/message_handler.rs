@@ -18,6 +18,7 @@ use crate::sys::DataSlice;
 use bytes::BytesMut;
 use log::info;
 use std::future::Future;
+use std::time::Duration;
 
 /// Message Processors.
 pub trait MessageHandler: Send + Sync + 'static {
@@ -29,6 +30,14 @@ pub trait MessageHandler: Send + Sync + 'static {
         true
     }
 
+    // Admission of a request before it is processed, e.g. to rate limit the clients.
+    // Ok(Some(wait)) processes it after the wait, which is done on the io thread without holding
+    // a handler thread; an error rejects it and is sent back as the response.
+    #[allow(unused)]
+    fn admit(&mut self, msg: &Message) -> Result<Option<Duration>, Self::Error> {
+        Ok(None)
+    }
+
     // Process messages in synchronization, and call rt.spawn_blocking in the io thread to process.
     // There is currently no good way to unify asynchronous and synchronous code.
     fn handle(&mut self, msg: &Message) -> Result<Message, Self::Error>;
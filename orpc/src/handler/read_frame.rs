@@ -15,7 +15,7 @@
 use crate::handler::rpc_frame::FrameSate;
 use crate::io::IOResult;
 use crate::message;
-use crate::message::Message;
+use crate::message::{Message, RpcCompressor, COMPRESS_FLAG};
 use crate::sys::DataSlice;
 use bytes::BytesMut;
 use std::mem;
@@ -25,11 +25,16 @@ use tokio::net::TcpStream;
 pub struct ReadFrame {
     io: ReadHalf<TcpStream>,
     buf: BytesMut,
+    compressor: RpcCompressor,
 }
 
 impl ReadFrame {
-    pub(crate) fn new(io: ReadHalf<TcpStream>, buf: BytesMut) -> Self {
-        Self { io, buf }
+    pub(crate) fn new(io: ReadHalf<TcpStream>, buf: BytesMut, compressor: RpcCompressor) -> Self {
+        Self {
+            io,
+            buf,
+            compressor,
+        }
     }
 
     // Read data of the specified length.
@@ -51,26 +56,35 @@ impl ReadFrame {
                 FrameSate::Head => {
                     let mut buf = self.read_full(message::PROTOCOL_SIZE).await?;
 
-                    let (protocol, header_size, data_size) = Message::decode_protocol(&mut buf)?;
+                    let (protocol, header_size, data_size, flags) =
+                        Message::decode_protocol(&mut buf)?;
                     let _ = mem::replace(
                         &mut state,
-                        FrameSate::Data(protocol, header_size, data_size),
+                        FrameSate::Data(protocol, header_size, data_size, flags),
                     );
                 }
 
-                FrameSate::Data(protocol, header_size, data_size) => {
-                    let header = if header_size > 0 {
+                FrameSate::Data(mut protocol, header_size, data_size, flags) => {
+                    let mut header = if header_size > 0 {
                         let buf = self.read_full(header_size).await?;
                         Some(buf)
                     } else {
                         None
                     };
+                    Message::split_trace(&mut protocol, flags, &mut header)?;
 
-                    let data = if data_size <= 0 {
-                        DataSlice::Empty
+                    let (header, data) = if flags & COMPRESS_FLAG != 0 {
+                        let data = self.read_full(data_size).await?;
+                        self.compressor
+                            .decompress_msg(header.unwrap_or_default(), data)?
                     } else {
-                        let bytes = self.read_full(data_size).await?;
-                        DataSlice::Buffer(bytes)
+                        let data = if data_size <= 0 {
+                            DataSlice::Empty
+                        } else {
+                            let bytes = self.read_full(data_size).await?;
+                            DataSlice::Buffer(bytes)
+                        };
+                        (header, data)
                     };
                     let msg = Message {
                         protocol,
@@ -15,13 +15,16 @@
 use crate::handler::{Frame, ReadFrame, RpcCodec, WriteFrame};
 use crate::io::net::ConnState;
 use crate::io::IOResult;
-use crate::message::{Message, Protocol, RefMessage};
+use crate::message::{
+    CompressionType, Message, Protocol, RefMessage, ResponseStatus, RpcCompressor, COMPRESS_FLAG,
+};
 use crate::server::ServerConf;
 use crate::sys::{DataSlice, RawIOSlice};
-use crate::{message, sys};
+use crate::{err_box, message, sys};
 use bytes::BytesMut;
 use std::mem;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::{Arc, OnceLock};
 use tokio::io::{AsyncReadExt, AsyncWriteExt, Interest};
 use tokio::net::TcpStream;
 use tokio_util::codec::Framed;
@@ -31,7 +34,7 @@ use std::os::unix::io::{AsRawFd, RawFd};
 
 pub enum FrameSate {
     Head,
-    Data(Protocol, i32, i32),
+    Data(Protocol, i32, i32, i32),
 }
 
 /// Custom data frame resolution
@@ -39,10 +42,20 @@ pub struct RpcFrame {
     io: TcpStream,
     buf: BytesMut,
     enable_splice: bool,
+    compressor: RpcCompressor,
+    // Codecs the server accepts when a client proposes compression.
+    accept_codecs: Vec<CompressionType>,
+    // The user the client sent in the handshake, shared with the conn state of the handler.
+    client_user: Arc<OnceLock<String>>,
 }
 
 impl RpcFrame {
-    fn new(io: TcpStream, buffer_size: usize, enable_splice: bool) -> Self {
+    fn new(
+        io: TcpStream,
+        buffer_size: usize,
+        enable_splice: bool,
+        accept_codecs: Vec<CompressionType>,
+    ) -> Self {
         let enable_splice = if cfg!(target_os = "linux") {
             enable_splice
         } else {
@@ -53,15 +66,47 @@ impl RpcFrame {
             io,
             buf: BytesMut::with_capacity(buffer_size),
             enable_splice,
+            compressor: RpcCompressor::none(),
+            accept_codecs,
+            client_user: Arc::new(OnceLock::new()),
         }
     }
 
     pub fn with_client(io: TcpStream, buffer_size: usize) -> Self {
-        Self::new(io, buffer_size, false)
+        Self::new(io, buffer_size, false, vec![])
     }
 
     pub fn with_server(io: TcpStream, conf: &ServerConf) -> Self {
-        Self::new(io, conf.buffer_size, conf.enable_splice)
+        Self::new(
+            io,
+            conf.buffer_size,
+            conf.enable_splice,
+            conf.compress_codecs.clone(),
+        )
+    }
+
+    pub fn compressor(&self) -> RpcCompressor {
+        self.compressor
+    }
+
+    // Client side, propose a compression codec to the server and tell it the user of the client.
+    // The server answers with the codec it accepts, or none; servers that do not support
+    // the handshake must be upgraded before clients enable compression or set a user.
+    pub async fn handshake(
+        &mut self,
+        codec: CompressionType,
+        min_size: usize,
+        user: Option<&str>,
+    ) -> IOResult<()> {
+        let req = RpcCompressor::handshake_request(codec, min_size, user);
+        self.send(req).await?;
+
+        let rep = self.receive().await?;
+        if rep.is_empty() {
+            return err_box!("Connection closed during handshake");
+        }
+        self.compressor = RpcCompressor::from_response(&rep, min_size)?;
+        Ok(())
     }
 
     // Read data of the specified length.
@@ -186,8 +231,12 @@ impl RpcFrame {
 
     pub fn split(self) -> (ReadFrame, WriteFrame) {
         let (read, write) = tokio::io::split(self.io);
-        let read_frame = ReadFrame::new(read, BytesMut::with_capacity(self.buf.capacity()));
-        let write_frame = WriteFrame::new(write, self.buf);
+        let read_frame = ReadFrame::new(
+            read,
+            BytesMut::with_capacity(self.buf.capacity()),
+            self.compressor,
+        );
+        let write_frame = WriteFrame::new(write, self.buf, self.compressor);
         (read_frame, write_frame)
     }
 }
@@ -202,6 +251,15 @@ impl AsRawFd for RpcFrame {
 impl Frame for RpcFrame {
     async fn send(&mut self, msg: impl RefMessage) -> IOResult<()> {
         let msg = msg.as_ref();
+        if let Some((header, data)) = self.compressor.compress_msg(msg)? {
+            msg.encode_protocol_with(&mut self.buf, header.len(), data.len(), true);
+            self.io.write_all(&self.buf.split()).await?;
+            self.io.write_all(&header).await?;
+            self.io.write_all(&data).await?;
+            self.io.flush().await?;
+            return Ok(());
+        }
+
         msg.encode_protocol(&mut self.buf);
         self.io.write_all(&self.buf.split()).await?;
 
@@ -227,21 +285,32 @@ impl Frame for RpcFrame {
                         Err(_) => return Ok(Message::empty()),
                     };
 
-                    let (protocol, header_size, data_size) = Message::decode_protocol(&mut buf)?;
+                    let (protocol, header_size, data_size, flags) =
+                        Message::decode_protocol(&mut buf)?;
                     let _ = mem::replace(
                         &mut state,
-                        FrameSate::Data(protocol, header_size, data_size),
+                        FrameSate::Data(protocol, header_size, data_size, flags),
                     );
                 }
 
-                FrameSate::Data(protocol, header_size, data_size) => {
-                    let header = if header_size > 0 {
+                FrameSate::Data(mut protocol, header_size, data_size, flags) => {
+                    let mut header = if header_size > 0 {
                         let buf = self.read_full(header_size).await?;
                         Some(buf)
                     } else {
                         None
                     };
-                    let data = self.read_data(data_size).await?;
+                    Message::split_trace(&mut protocol, flags, &mut header)?;
+
+                    let (header, data) = if flags & COMPRESS_FLAG != 0 {
+                        // Compressed data is always read into the buffer, bypassing splice.
+                        let data = self.read_full(data_size).await?;
+                        self.compressor
+                            .decompress_msg(header.unwrap_or_default(), data)?
+                    } else {
+                        let data = self.read_data(data_size).await?;
+                        (header, data)
+                    };
                     let msg = Message {
                         protocol,
                         header,
@@ -253,6 +322,17 @@ impl Frame for RpcFrame {
                     // Heartbeat message.
                     if msg.is_heartbeat() {
                         continue;
+                    } else if msg.is_handshake()
+                        && msg.response_status() == ResponseStatus::Undefined
+                    {
+                        // Reply uncompressed, then switch to the negotiated codec.
+                        let (compressor, rep) = RpcCompressor::accept(&msg, &self.accept_codecs)?;
+                        if let Some(user) = RpcCompressor::handshake_user(&msg) {
+                            let _ = self.client_user.set(user);
+                        }
+                        self.send(rep).await?;
+                        self.compressor = compressor;
+                        continue;
                     } else {
                         return Ok(msg);
                     }
@@ -265,6 +345,6 @@ impl Frame for RpcFrame {
         let ip = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(0, 0, 0, 0), 0));
         let client_addr = self.io.peer_addr().unwrap_or(ip);
         let local_addr = self.io.local_addr().unwrap_or(ip);
-        ConnState::new(client_addr.into(), local_addr.into())
+        ConnState::new(client_addr.into(), local_addr.into()).with_user(self.client_user.clone())
     }
 }
@@ -73,6 +73,15 @@ impl<F: Frame, M: MessageHandler> StreamHandler<F, M> {
     }
 
     pub async fn call(&mut self, request: Message) -> IOResult<()> {
+        match self.handler.as_mut().admit(&request) {
+            Ok(None) => (),
+            Ok(Some(wait)) => tokio::time::sleep(wait).await,
+            Err(e) => {
+                debug!("handler request {} rejected: {}", request.req_id(), e);
+                return self.frame.send(request.error_ext(&e)).await;
+            }
+        }
+
         let response = if self.handler.is_sync(&request) {
             let handler = self.handler.clone();
             self.rt
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::io::IOResult;
-use crate::message::Message;
+use crate::message::{Message, RpcCompressor};
 use bytes::BytesMut;
 use tokio::io::{AsyncWriteExt, WriteHalf};
 use tokio::net::TcpStream;
@@ -21,14 +21,28 @@ use tokio::net::TcpStream;
 pub struct WriteFrame {
     io: WriteHalf<TcpStream>,
     buf: BytesMut,
+    compressor: RpcCompressor,
 }
 
 impl WriteFrame {
-    pub(crate) fn new(io: WriteHalf<TcpStream>, buf: BytesMut) -> Self {
-        Self { io, buf }
+    pub(crate) fn new(io: WriteHalf<TcpStream>, buf: BytesMut, compressor: RpcCompressor) -> Self {
+        Self {
+            io,
+            buf,
+            compressor,
+        }
     }
 
     pub async fn send(&mut self, msg: &Message) -> IOResult<()> {
+        if let Some((header, data)) = self.compressor.compress_msg(msg)? {
+            msg.encode_protocol_with(&mut self.buf, header.len(), data.len(), true);
+            self.io.write_all(&self.buf.split()).await?;
+            self.io.write_all(&header).await?;
+            self.io.write_all(&data).await?;
+            self.io.flush().await?;
+            return Ok(());
+        }
+
         msg.encode_protocol(&mut self.buf);
         self.io.write_all(&self.buf.split()).await?;
 
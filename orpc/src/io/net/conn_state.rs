@@ -13,11 +13,14 @@
 // limitations under the License.
 
 use crate::io::net::InetAddr;
+use std::sync::{Arc, OnceLock};
 
 #[derive(Debug, Clone, Default)]
 pub struct ConnState {
     pub remote_addr: InetAddr,
     pub local_addr: InetAddr,
+    // Set once the client sends its user in the handshake.
+    user: Arc<OnceLock<String>>,
 }
 
 impl ConnState {
@@ -25,6 +28,16 @@ impl ConnState {
         Self {
             remote_addr,
             local_addr,
+            user: Default::default(),
         }
     }
+
+    pub fn with_user(mut self, user: Arc<OnceLock<String>>) -> Self {
+        self.user = user;
+        self
+    }
+
+    pub fn user(&self) -> Option<&str> {
+        self.user.get().map(|x| x.as_str())
+    }
 }
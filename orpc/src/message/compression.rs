@@ -0,0 +1,416 @@
+// Copyright 2025 OPPO.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::{CounterVec, HistogramVec, Metrics, TimeSpent};
+use crate::io::IOResult;
+use crate::message::{Builder, Message, RequestStatus, MAX_DATE_SIZE};
+use crate::sys::DataSlice;
+use crate::{err_box, CommonError};
+use bytes::{Buf, BufMut, BytesMut};
+use num_enum::{FromPrimitive, IntoPrimitive};
+use once_cell::sync::Lazy;
+
+// Set in the header length field when the header and data sections of a message are compressed.
+// The lengths on the wire are the compressed lengths.
+pub const COMPRESS_FLAG: i32 = 1 << 30;
+
+const ZSTD_LEVEL: i32 = 1;
+
+static METRICS: Lazy<CompressMetrics> = Lazy::new(|| CompressMetrics::new().unwrap());
+
+#[repr(i8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, IntoPrimitive, FromPrimitive)]
+pub enum CompressionType {
+    #[default]
+    None = 0,
+    Lz4 = 1,
+    Zstd = 2,
+}
+
+impl CompressionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompressionType::None => "none",
+            CompressionType::Lz4 => "lz4",
+            CompressionType::Zstd => "zstd",
+        }
+    }
+
+    // Parse a comma separated codec list, e.g. "lz4,zstd".
+    pub fn parse_list(value: &str) -> Result<Vec<Self>, CommonError> {
+        let mut res = vec![];
+        for item in value.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+            let codec = Self::try_from(item)?;
+            if codec != CompressionType::None && !res.contains(&codec) {
+                res.push(codec);
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl TryFrom<&str> for CompressionType {
+    type Error = CommonError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let codec = match value.to_uppercase().as_str() {
+            "NONE" | "" => CompressionType::None,
+            "LZ4" => CompressionType::Lz4,
+            "ZSTD" => CompressionType::Zstd,
+            _ => return err_box!("invalid compression type: {}", value),
+        };
+
+        Ok(codec)
+    }
+}
+
+struct CompressMetrics {
+    input_bytes: CounterVec,
+    output_bytes: CounterVec,
+    ratio: HistogramVec,
+    used_us: HistogramVec,
+}
+
+impl CompressMetrics {
+    fn new() -> IOResult<Self> {
+        let metrics = Self {
+            input_bytes: Metrics::new_counter_vec(
+                "rpc_compress_input_bytes",
+                "Bytes passed to rpc compression and decompression",
+                &["codec", "op"],
+            )?,
+            output_bytes: Metrics::new_counter_vec(
+                "rpc_compress_output_bytes",
+                "Bytes produced by rpc compression and decompression",
+                &["codec", "op"],
+            )?,
+            ratio: Metrics::new_histogram_vec_with_buckets(
+                "rpc_compress_ratio",
+                "Compressed size divided by the original size of rpc payloads",
+                &["codec"],
+                &[0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0, 1.2],
+            )?,
+            used_us: Metrics::new_histogram_vec(
+                "rpc_compress_us",
+                "Rpc compression and decompression latency in microseconds",
+                &["codec", "op"],
+            )?,
+        };
+        Ok(metrics)
+    }
+
+    fn observe(&self, codec: CompressionType, op: &str, input: usize, output: usize, us: u64) {
+        let labels = [codec.as_str(), op];
+        self.input_bytes
+            .with_label_values(&labels)
+            .inc_by(input as i64);
+        self.output_bytes
+            .with_label_values(&labels)
+            .inc_by(output as i64);
+        self.used_us.with_label_values(&labels).observe(us as f64);
+        if op == "compress" && input > 0 {
+            self.ratio
+                .with_label_values(&[codec.as_str()])
+                .observe(output as f64 / input as f64);
+        }
+    }
+}
+
+/// Per connection payload compression, negotiated by a handshake message after connecting.
+/// The client proposes a codec and the minimum message size worth compressing, the server
+/// answers with the same codec if it accepts it, or `none` otherwise.
+/// Messages whose data is an io slice are never compressed, they are sent with sendfile.
+#[derive(Debug, Copy, Clone)]
+pub struct RpcCompressor {
+    codec: CompressionType,
+    min_size: usize,
+}
+
+impl RpcCompressor {
+    pub fn new(codec: CompressionType, min_size: usize) -> Self {
+        Self { codec, min_size }
+    }
+
+    pub fn none() -> Self {
+        Self::new(CompressionType::None, 0)
+    }
+
+    pub fn codec(&self) -> CompressionType {
+        self.codec
+    }
+
+    pub fn min_size(&self) -> usize {
+        self.min_size
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.codec != CompressionType::None
+    }
+
+    // Compress the header and data of a message.
+    // Returns None if the message should be sent as is.
+    pub fn compress_msg(&self, msg: &Message) -> IOResult<Option<(BytesMut, BytesMut)>> {
+        if !self.is_enabled() || matches!(msg.data, DataSlice::IOSlice(_)) {
+            return Ok(None);
+        }
+
+        let raw_len = msg.header_len() + msg.data_len();
+        if raw_len < self.min_size {
+            return Ok(None);
+        }
+
+        let header = match msg.header_bytes() {
+            Some(v) => self.compress(v)?,
+            None => BytesMut::new(),
+        };
+        let data = self.compress(msg.data.as_slice())?;
+
+        if header.len() + data.len() >= raw_len {
+            Ok(None)
+        } else {
+            Ok(Some((header, data)))
+        }
+    }
+
+    // Restore the header and data sections of a compressed message.
+    pub fn decompress_msg(
+        &self,
+        header: BytesMut,
+        data: BytesMut,
+    ) -> IOResult<(Option<BytesMut>, DataSlice)> {
+        if !self.is_enabled() {
+            return err_box!("Received a compressed message, but compression is not negotiated");
+        }
+
+        let header = if header.is_empty() {
+            None
+        } else {
+            Some(self.decompress(&header)?)
+        };
+
+        let data = if data.is_empty() {
+            DataSlice::Empty
+        } else {
+            DataSlice::Buffer(self.decompress(&data)?)
+        };
+
+        Ok((header, data))
+    }
+
+    // The compressed block is prefixed with the original length.
+    pub fn compress(&self, src: &[u8]) -> IOResult<BytesMut> {
+        if src.is_empty() {
+            return Ok(BytesMut::new());
+        }
+
+        let spent = TimeSpent::new();
+        let block = match self.codec {
+            CompressionType::Lz4 => lz4_flex::block::compress(src),
+            CompressionType::Zstd => zstd::bulk::compress(src, ZSTD_LEVEL)?,
+            CompressionType::None => return err_box!("Compression is not enabled"),
+        };
+
+        let mut buf = BytesMut::with_capacity(4 + block.len());
+        buf.put_u32(src.len() as u32);
+        buf.put_slice(&block);
+
+        METRICS.observe(
+            self.codec,
+            "compress",
+            src.len(),
+            buf.len(),
+            spent.used_us(),
+        );
+        Ok(buf)
+    }
+
+    pub fn decompress(&self, src: &[u8]) -> IOResult<BytesMut> {
+        if src.len() < 4 {
+            return err_box!("Compressed block is too short: {}", src.len());
+        }
+
+        let spent = TimeSpent::new();
+        let mut len_buf = &src[..4];
+        let raw_len = len_buf.get_u32() as usize;
+        if raw_len > MAX_DATE_SIZE as usize {
+            return err_box!("Data exceeds maximum size: {}", MAX_DATE_SIZE);
+        }
+
+        let block = match self.codec {
+            CompressionType::Lz4 => match lz4_flex::block::decompress(&src[4..], raw_len) {
+                Ok(v) => v,
+                Err(e) => return err_box!("lz4 decompress failed: {}", e),
+            },
+            CompressionType::Zstd => zstd::bulk::decompress(&src[4..], raw_len)?,
+            CompressionType::None => return err_box!("Compression is not enabled"),
+        };
+
+        if block.len() != raw_len {
+            return err_box!(
+                "Decompressed length mismatch, expected {}, actual {}",
+                raw_len,
+                block.len()
+            );
+        }
+
+        METRICS.observe(
+            self.codec,
+            "decompress",
+            src.len(),
+            raw_len,
+            spent.used_us(),
+        );
+        Ok(BytesMut::from(&block[..]))
+    }
+
+    // Handshake request sent by the client: codec (1 byte) + min size (4 bytes),
+    // then the user of the client if it has one: length (2 bytes) + utf8 bytes.
+    pub fn handshake_request(
+        codec: CompressionType,
+        min_size: usize,
+        user: Option<&str>,
+    ) -> Message {
+        let mut header = BytesMut::with_capacity(5);
+        header.put_i8(codec.into());
+        header.put_u32(min_size as u32);
+        if let Some(user) = user {
+            header.put_u16(user.len() as u16);
+            header.put_slice(user.as_bytes());
+        }
+
+        Builder::new()
+            .request(RequestStatus::Handshake)
+            .new_req_id()
+            .header(header)
+            .build()
+    }
+
+    // The server accepts the proposed codec if it is in the accepted list.
+    // Returns the compressor of the connection and the response to the client.
+    pub fn accept(req: &Message, accepted: &[CompressionType]) -> IOResult<(Self, Message)> {
+        let mut header = match req.header_bytes() {
+            Some(v) if v.len() >= 5 => v,
+            _ => return err_box!("Invalid handshake request"),
+        };
+        let codec = CompressionType::from(header.get_i8());
+        let min_size = header.get_u32() as usize;
+
+        let codec = if accepted.contains(&codec) {
+            codec
+        } else {
+            CompressionType::None
+        };
+
+        let mut rep_header = BytesMut::with_capacity(1);
+        rep_header.put_i8(codec.into());
+        let rep = Builder::success(req).header(rep_header).build();
+
+        Ok((Self::new(codec, min_size), rep))
+    }
+
+    // The user the client sent in the handshake request, if any.
+    pub fn handshake_user(req: &Message) -> Option<String> {
+        let mut header = req.header_bytes()?;
+        if header.len() < 7 {
+            return None;
+        }
+        header.advance(5);
+        let len = header.get_u16() as usize;
+        if header.len() < len {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&header[..len]).to_string())
+    }
+
+    // The client reads the codec chosen by the server.
+    pub fn from_response(rep: &Message, min_size: usize) -> IOResult<Self> {
+        if !rep.is_success() {
+            return err_box!("Handshake failed: {}", rep.to_error_msg());
+        }
+
+        let codec = match rep.header_bytes() {
+            Some(v) if !v.is_empty() => CompressionType::from(v[0] as i8),
+            _ => return err_box!("Invalid handshake response"),
+        };
+        Ok(Self::new(codec, min_size))
+    }
+}
+
+impl Default for RpcCompressor {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::message::{Builder, CompressionType, RpcCompressor};
+    use crate::sys::DataSlice;
+    use bytes::BytesMut;
+
+    #[test]
+    fn roundtrip() {
+        let header = BytesMut::from("header ".repeat(100).as_str());
+        let data = BytesMut::from("data ".repeat(1000).as_str());
+
+        for codec in [CompressionType::Lz4, CompressionType::Zstd] {
+            let compressor = RpcCompressor::new(codec, 1024);
+            let msg = Builder::new_rpc(1)
+                .header(header.clone())
+                .data(DataSlice::Buffer(data.clone()))
+                .build();
+
+            let (h, d) = compressor.compress_msg(&msg).unwrap().unwrap();
+            assert!(h.len() + d.len() < header.len() + data.len());
+
+            let (h, d) = compressor.decompress_msg(h, d).unwrap();
+            assert_eq!(h.unwrap(), header);
+            assert_eq!(d.as_slice(), &data[..]);
+
+            // Messages smaller than min_size are sent as is.
+            let small = Builder::new_rpc(1).header(BytesMut::from("abc")).build();
+            assert!(compressor.compress_msg(&small).unwrap().is_none());
+        }
+
+        let codecs = CompressionType::parse_list("zstd, lz4,none").unwrap();
+        assert_eq!(codecs, vec![CompressionType::Zstd, CompressionType::Lz4]);
+        assert!(CompressionType::try_from("gzip").is_err());
+    }
+
+    #[test]
+    fn handshake() {
+        let req = RpcCompressor::handshake_request(CompressionType::Zstd, 4096, None);
+        assert_eq!(RpcCompressor::handshake_user(&req), None);
+
+        let (server, rep) = RpcCompressor::accept(&req, &[CompressionType::Lz4]).unwrap();
+        assert!(!server.is_enabled());
+        let client = RpcCompressor::from_response(&rep, 4096).unwrap();
+        assert_eq!(client.codec(), CompressionType::None);
+
+        let (server, rep) = RpcCompressor::accept(&req, &[CompressionType::Zstd]).unwrap();
+        assert_eq!(server.codec(), CompressionType::Zstd);
+        assert_eq!(server.min_size(), 4096);
+        let client = RpcCompressor::from_response(&rep, 4096).unwrap();
+        assert_eq!(client.codec(), CompressionType::Zstd);
+
+        // The user follows the codec, a server reading only the codec ignores it.
+        let req = RpcCompressor::handshake_request(CompressionType::None, 4096, Some("alice"));
+        assert_eq!(
+            RpcCompressor::handshake_user(&req).as_deref(),
+            Some("alice")
+        );
+        let (server, _) = RpcCompressor::accept(&req, &[CompressionType::Zstd]).unwrap();
+        assert!(!server.is_enabled());
+    }
+}
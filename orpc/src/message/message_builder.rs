@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::common::Utils;
+use crate::common::{TraceContext, Utils};
 use crate::message::{
     Builder, Message, Protocol, RequestStatus, ResponseStatus, Status, EMPTY_REQ_ID, INIT_SEQ_ID,
 };
@@ -29,6 +29,7 @@ pub struct MessageBuilder {
     seq_id: i32,
     header: Option<BytesMut>,
     data: DataSlice,
+    trace: Option<TraceContext>,
 }
 
 impl MessageBuilder {
@@ -40,6 +41,7 @@ impl MessageBuilder {
             seq_id: INIT_SEQ_ID,
             header: None,
             data: DataSlice::Empty,
+            trace: None,
         }
     }
 
@@ -52,6 +54,7 @@ impl MessageBuilder {
             seq_id: INIT_SEQ_ID,
             header: None,
             data: DataSlice::Empty,
+            trace: None,
         }
     }
 
@@ -63,6 +66,7 @@ impl MessageBuilder {
             seq_id: req.seq_id(),
             header: None,
             data: DataSlice::Empty,
+            trace: None,
         }
     }
 
@@ -125,6 +129,11 @@ impl MessageBuilder {
         self
     }
 
+    pub fn trace(mut self, trace: Option<TraceContext>) -> Self {
+        self.trace = trace;
+        self
+    }
+
     pub fn data(mut self, data: DataSlice) -> Self {
         if !data.is_empty() {
             self.data = data;
@@ -133,11 +142,25 @@ impl MessageBuilder {
     }
 
     pub fn build(self) -> Message {
+        // Requests sent inside a span carry its context.
+        let trace = match self.status {
+            Status(
+                RequestStatus::Rpc
+                | RequestStatus::Open
+                | RequestStatus::Running
+                | RequestStatus::Cancel
+                | RequestStatus::Complete,
+                ResponseStatus::Undefined,
+            ) => self.trace.or_else(TraceContext::current),
+            _ => None,
+        };
+
         let protocol = Protocol {
             code: self.code,
             status: self.status,
             req_id: self.req_id,
             seq_id: self.seq_id,
+            trace,
         };
 
         Message {
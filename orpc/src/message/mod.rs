@@ -24,6 +24,9 @@ pub use self::message_builder::MessageBuilder;
 mod box_message;
 pub use self::box_message::BoxMessage;
 
+mod compression;
+pub use self::compression::*;
+
 pub type Message = RpcMessage;
 
 pub type Builder = MessageBuilder;
@@ -12,10 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::common::Utils;
+use crate::common::{TraceContext, Utils};
 use crate::error::ErrorExt;
 use crate::io::IOResult;
-use crate::message::{BoxMessage, Builder, RefMessage};
+use crate::message::{BoxMessage, Builder, RefMessage, COMPRESS_FLAG};
 use crate::sys::DataSlice;
 use crate::{err_box, CommonError, CommonResult};
 use bytes::{Buf, BufMut, BytesMut};
@@ -40,6 +40,10 @@ pub const EMPTY_REQ_ID: i64 = -1;
 
 pub const MAX_DATE_SIZE: i32 = 16 * 1024 * 1024;
 
+// Set in the header length field when a trace context is carried,
+// it is placed in front of the header section and counted in the header length.
+pub const TRACE_FLAG: i32 = 1 << 29;
+
 #[repr(i8)]
 #[derive(Debug, Copy, Clone, PartialEq, IntoPrimitive, FromPrimitive)]
 pub enum RequestStatus {
@@ -54,6 +58,8 @@ pub enum RequestStatus {
     Running = 3,  //Streaming request data delivery
     Cancel = 4,   //Cancel request
     Complete = 5, //Request complete
+
+    Handshake = 6, //Connection setup, negotiates payload compression
 }
 
 #[repr(i8)]
@@ -97,12 +103,14 @@ impl Default for Status {
 /// status: request or response status
 /// req_id: the unique id requested
 /// seq_id: the requested sequence number.
+/// trace: the trace context of the caller, only set on requests.
 #[derive(Debug, Copy, Clone)]
 pub struct Protocol {
     pub code: i8,
     pub status: Status,
     pub req_id: i64,
     pub seq_id: i32,
+    pub trace: Option<TraceContext>,
 }
 
 impl Protocol {
@@ -112,6 +120,7 @@ impl Protocol {
             status,
             req_id: Utils::req_id(),
             seq_id,
+            trace: TraceContext::current(),
         }
     }
 
@@ -121,6 +130,7 @@ impl Protocol {
             status: Status::from(buf.get_i8()),
             req_id: buf.get_i64(),
             seq_id: buf.get_i32(),
+            trace: None,
         }
     }
 }
@@ -214,6 +224,7 @@ impl RpcMessage {
             status,
             req_id: self.protocol.req_id,
             seq_id: self.protocol.seq_id,
+            trace: None,
         };
 
         Self {
@@ -280,6 +291,10 @@ impl RpcMessage {
         self.request_status() == RequestStatus::Heartbeat
     }
 
+    pub fn is_handshake(&self) -> bool {
+        self.request_status() == RequestStatus::Handshake
+    }
+
     pub fn to_error_msg(&self) -> String {
         self.data.to_error_msg()
     }
@@ -299,15 +314,41 @@ impl RpcMessage {
     }
 
     pub fn encode_protocol(&self, buf: &mut BytesMut) {
-        let header_len = self.header_len();
-        let data_len = self.data_len();
+        self.encode_protocol_with(buf, self.header_len(), self.data_len(), false)
+    }
+
+    // Encode the protocol with the lengths of the header and data actually written,
+    // which differ from the message when the payload is compressed.
+    pub fn encode_protocol_with(
+        &self,
+        buf: &mut BytesMut,
+        header_len: usize,
+        data_len: usize,
+        compressed: bool,
+    ) {
+        let trace_len = match self.protocol.trace {
+            Some(_) => TraceContext::ENCODE_SIZE,
+            None => 0,
+        };
 
-        buf.put_i32((header_len + data_len) as i32 + HEAD_SIZE);
-        buf.put_i32(header_len as i32);
+        let mut header_field = (trace_len + header_len) as i32;
+        if compressed {
+            header_field |= COMPRESS_FLAG;
+        }
+        if trace_len > 0 {
+            header_field |= TRACE_FLAG;
+        }
+
+        buf.put_i32((trace_len + header_len + data_len) as i32 + HEAD_SIZE);
+        buf.put_i32(header_field);
         buf.put_i8(self.protocol.code);
         buf.put_i8(self.protocol.status.encode());
         buf.put_i64(self.protocol.req_id);
         buf.put_i32(self.protocol.seq_id);
+
+        if let Some(trace) = &self.protocol.trace {
+            trace.encode(buf);
+        }
     }
 
     /// Decode protocol data
@@ -323,9 +364,12 @@ impl RpcMessage {
     /// * Protocol - The protocol object created by parsing
     /// * i32 - Header size
     /// * i32 - Data size
-    pub fn decode_protocol(buf: &mut BytesMut) -> IOResult<(Protocol, i32, i32)> {
+    /// * i32 - Flags of the message, COMPRESS_FLAG and TRACE_FLAG
+    pub fn decode_protocol(buf: &mut BytesMut) -> IOResult<(Protocol, i32, i32, i32)> {
         let total_size = buf.get_i32();
-        let header_size = buf.get_i32();
+        let header_field = buf.get_i32();
+        let flags = header_field & (COMPRESS_FLAG | TRACE_FLAG);
+        let header_size = header_field & !flags;
         let data_size = total_size - header_size - HEAD_SIZE;
         if data_size < 0 {
             return err_box!("data length is negative");
@@ -334,7 +378,30 @@ impl RpcMessage {
         }
 
         let protocol = Protocol::create(buf);
-        Ok((protocol, header_size, data_size))
+        Ok((protocol, header_size, data_size, flags))
+    }
+
+    // Split the trace context off the front of the header section.
+    pub fn split_trace(
+        protocol: &mut Protocol,
+        flags: i32,
+        header: &mut Option<BytesMut>,
+    ) -> IOResult<()> {
+        if flags & TRACE_FLAG == 0 {
+            return Ok(());
+        }
+
+        let buf = match header {
+            Some(v) if v.len() >= TraceContext::ENCODE_SIZE => v,
+            _ => return err_box!("trace context is missing"),
+        };
+        let mut trace_buf = buf.split_to(TraceContext::ENCODE_SIZE);
+        protocol.trace = Some(TraceContext::decode(&mut trace_buf)?);
+
+        if buf.is_empty() {
+            let _ = header.take();
+        }
+        Ok(())
     }
 
     pub fn encode(&self, buf: &mut BytesMut) -> IOResult<()> {
@@ -354,15 +421,21 @@ impl RpcMessage {
 
     pub fn decode(buf: &mut BytesMut) -> IOResult<Self> {
         // The BytesMut returned by tokio does not contain the first length byte.
-        let header_size = buf.get_u32() as usize;
+        let header_field = buf.get_i32();
+        let flags = header_field & (COMPRESS_FLAG | TRACE_FLAG);
+        let header_size = (header_field & !flags) as usize;
+        if flags & COMPRESS_FLAG != 0 {
+            return err_box!("Compressed messages are not supported by the codec");
+        }
 
-        let protocol = Protocol::create(buf);
+        let mut protocol = Protocol::create(buf);
 
-        let header = if header_size > 0 {
+        let mut header = if header_size > 0 {
             Some(buf.split_to(header_size))
         } else {
             None
         };
+        Self::split_trace(&mut protocol, flags, &mut header)?;
 
         let data = if buf.has_remaining() {
             DataSlice::Buffer(buf.split())
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::io::net::{InetAddr, NetUtils};
+use crate::message::CompressionType;
 use crate::runtime::Runtime;
 use crate::sys::pipe::PipePool;
 use std::sync::Arc;
@@ -48,6 +49,9 @@ pub struct ServerConf {
     pub pipe_pool_idle_time: usize,
 
     pub enable_send_file: bool,
+
+    // Payload compression codecs accepted when a client proposes one during the handshake.
+    pub compress_codecs: Vec<CompressionType>,
 }
 
 impl ServerConf {
@@ -74,6 +78,8 @@ impl ServerConf {
             pipe_pool_idle_time: 0,
 
             enable_send_file: true,
+
+            compress_codecs: vec![CompressionType::Lz4, CompressionType::Zstd],
         }
     }
 